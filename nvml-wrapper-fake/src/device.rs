@@ -0,0 +1,674 @@
+//! Per-device queries and the handful of setters tests tend to need.
+
+use crate::ffi::bindings::*;
+use crate::fixture::{Pci, ProcessKind};
+use crate::state::{call, enable_state, with_gpu, write, write_str};
+use std::os::raw::{c_char, c_int, c_uint, c_ulonglong};
+
+/// Exports getters that write a single value computed from the GPU.
+macro_rules! getters {
+    ($($name:ident($out:ident: $ty:ty) => |$gpu:ident| $value:expr;)*) => {
+        $(
+            #[no_mangle]
+            pub unsafe extern "C" fn $name(device: nvmlDevice_t, $out: *mut $ty) -> nvmlReturn_t {
+                with_gpu(stringify!($name), device, |$gpu| write($out, $value))
+            }
+        )*
+    };
+}
+
+/// Exports getters that copy a string from the GPU into a caller buffer.
+macro_rules! string_getters {
+    ($($name:ident => |$gpu:ident| $value:expr;)*) => {
+        $(
+            #[no_mangle]
+            pub unsafe extern "C" fn $name(
+                device: nvmlDevice_t,
+                buf: *mut c_char,
+                length: c_uint,
+            ) -> nvmlReturn_t {
+                with_gpu(stringify!($name), device, |$gpu| write_str(&$value, buf, length))
+            }
+        )*
+    };
+}
+
+string_getters! {
+    nvmlDeviceGetName => |gpu| gpu.name;
+    nvmlDeviceGetUUID => |gpu| gpu.uuid;
+    nvmlDeviceGetSerial => |gpu| gpu.serial;
+    nvmlDeviceGetBoardPartNumber => |gpu| gpu.board_part_number;
+    nvmlDeviceGetVbiosVersion => |gpu| gpu.vbios_version;
+    nvmlDeviceGetInforomImageVersion => |gpu| gpu.inforom_image_version;
+}
+
+getters! {
+    nvmlDeviceGetMinorNumber(minor: c_uint) => |gpu| gpu.minor_number.unwrap_or_default();
+    nvmlDeviceGetBoardId(id: c_uint) => |gpu| gpu.board_id;
+    nvmlDeviceGetBrand(brand: nvmlBrandType_t) => |gpu| gpu.brand;
+    nvmlDeviceGetArchitecture(arch: nvmlDeviceArchitecture_t) => |gpu| gpu.architecture;
+    nvmlDeviceGetBAR1MemoryInfo(bar1: nvmlBAR1Memory_t) => |gpu| nvmlBAR1Memory_t {
+        bar1Total: gpu.bar1_memory.total,
+        bar1Free: gpu.bar1_memory.total.saturating_sub(gpu.bar1_memory.used),
+        bar1Used: gpu.bar1_memory.used,
+    };
+    nvmlDeviceGetMemoryInfo(memory: nvmlMemory_t) => |gpu| nvmlMemory_t {
+        total: gpu.memory.total,
+        free: gpu.memory.free(),
+        used: gpu.memory.reserved + gpu.memory.used,
+    };
+    nvmlDeviceGetPowerUsage(power: c_uint) => |gpu| gpu.power.usage;
+    nvmlDeviceGetPowerManagementLimit(limit: c_uint) => |gpu| gpu.power.limit;
+    nvmlDeviceGetPowerManagementDefaultLimit(limit: c_uint) => |gpu| gpu.power.default_limit;
+    nvmlDeviceGetEnforcedPowerLimit(limit: c_uint) => |gpu| gpu.power.enforced_limit;
+    nvmlDeviceGetTotalEnergyConsumption(energy: c_ulonglong) => |gpu| {
+        gpu.power.total_energy_consumption
+    };
+    nvmlDeviceGetPerformanceState(state: nvmlPstates_t) => |gpu| gpu.performance_state;
+    nvmlDeviceGetPowerState(state: nvmlPstates_t) => |gpu| gpu.performance_state;
+    nvmlDeviceGetNumFans(count: c_uint) => |gpu| gpu.fans.len() as c_uint;
+    nvmlDeviceGetUtilizationRates(utilization: nvmlUtilization_t) => |gpu| nvmlUtilization_t {
+        gpu: gpu.utilization.gpu,
+        memory: gpu.utilization.memory,
+    };
+    nvmlDeviceGetComputeMode(mode: nvmlComputeMode_t) => |gpu| gpu.compute_mode;
+    nvmlDeviceGetPersistenceMode(mode: nvmlEnableState_t) => |gpu| {
+        enable_state(gpu.persistence_mode)
+    };
+    nvmlDeviceGetDisplayMode(mode: nvmlEnableState_t) => |gpu| enable_state(gpu.display_mode);
+    nvmlDeviceGetDisplayActive(active: nvmlEnableState_t) => |gpu| {
+        enable_state(gpu.display_active)
+    };
+    nvmlDeviceGetCurrPcieLinkGeneration(gen: c_uint) => |gpu| gpu.pcie.current_link_gen;
+    nvmlDeviceGetCurrPcieLinkWidth(width: c_uint) => |gpu| gpu.pcie.current_link_width;
+    nvmlDeviceGetMaxPcieLinkGeneration(gen: c_uint) => |gpu| gpu.pcie.max_link_gen;
+    nvmlDeviceGetMaxPcieLinkWidth(width: c_uint) => |gpu| gpu.pcie.max_link_width;
+    nvmlDeviceGetNumGpuCores(cores: c_uint) => |gpu| gpu.num_cores;
+    nvmlDeviceGetMemoryBusWidth(width: c_uint) => |gpu| gpu.memory_bus_width;
+    nvmlDeviceGetIrqNum(irq: c_uint) => |gpu| gpu.irq_num;
+    nvmlDeviceGetNumaNodeId(node: c_uint) => |gpu| gpu.numa_node_id;
+    nvmlDeviceGetMultiGpuBoard(multi_gpu: c_uint) => |gpu| gpu.multi_gpu_board as c_uint;
+    nvmlDeviceGetCurrentClocksThrottleReasons(reasons: c_ulonglong) => |gpu| {
+        gpu.throttle_reasons
+    };
+    nvmlDeviceGetSupportedClocksThrottleReasons(reasons: c_ulonglong) => |gpu| {
+        gpu.supported_throttle_reasons
+    };
+    nvmlDeviceGetCurrentClocksEventReasons(reasons: c_ulonglong) => |gpu| {
+        gpu.throttle_reasons
+    };
+    nvmlDeviceGetSupportedClocksEventReasons(reasons: c_ulonglong) => |gpu| {
+        gpu.supported_throttle_reasons
+    };
+    nvmlDeviceGetPowerSource(source: nvmlPowerSource_t) => |gpu| gpu.power_source;
+    nvmlDeviceGetBusType(bus_type: nvmlBusType_t) => |gpu| gpu.bus_type;
+    nvmlDeviceGetSupportedEventTypes(types: c_ulonglong) => |gpu| gpu.supported_event_types;
+    nvmlDeviceGetMaxMigDeviceCount(count: c_uint) => |gpu| gpu.max_mig_device_count;
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetIndex(
+    device: nvmlDevice_t,
+    index: *mut c_uint,
+) -> nvmlReturn_t {
+    call(|state| {
+        let i = state.index_of(device)?;
+        state.fixture_for("nvmlDeviceGetIndex", Some(i))?;
+
+        write(index, i)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetCudaComputeCapability(
+    device: nvmlDevice_t,
+    major: *mut c_int,
+    minor: *mut c_int,
+) -> nvmlReturn_t {
+    with_gpu("nvmlDeviceGetCudaComputeCapability", device, |gpu| {
+        write(major, gpu.cuda_compute_capability.0)?;
+        write(minor, gpu.cuda_compute_capability.1)
+    })
+}
+
+/// Copy `s` into a fixed-size C string field, truncating if needed.
+fn fill_c_str<const N: usize>(dst: &mut [c_char; N], s: &str) {
+    let len = s.len().min(N - 1);
+    for (d, b) in dst.iter_mut().zip(s.bytes().take(len)) {
+        *d = b as c_char;
+    }
+    dst[len] = 0;
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetPciInfo_v3(
+    device: nvmlDevice_t,
+    pci: *mut nvmlPciInfo_t,
+) -> nvmlReturn_t {
+    with_gpu("nvmlDeviceGetPciInfo_v3", device, |gpu| {
+        write(pci, pci_info(&gpu.pci))
+    })
+}
+
+/// The `nvmlPciInfo_t` for the device at `pci`.
+pub(crate) fn pci_info(pci: &Pci) -> nvmlPciInfo_t {
+    let bus = pci.bus.unwrap_or_default();
+    let mut info = nvmlPciInfo_t {
+        busIdLegacy: [0; 16],
+        domain: pci.domain,
+        bus,
+        device: pci.device,
+        pciDeviceId: pci.pci_device_id,
+        pciSubSystemId: pci.pci_sub_system_id,
+        busId: [0; 32],
+    };
+    fill_c_str(&mut info.busId, &pci.bus_id());
+    fill_c_str(
+        &mut info.busIdLegacy,
+        &format!("{:04X}:{:02X}:{:02X}.0", pci.domain, bus, pci.device),
+    );
+
+    info
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetMemoryInfo_v2(
+    device: nvmlDevice_t,
+    memory: *mut nvmlMemory_v2_t,
+) -> nvmlReturn_t {
+    with_gpu("nvmlDeviceGetMemoryInfo_v2", device, |gpu| {
+        if memory.is_null() {
+            return Err(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT);
+        }
+
+        let version = (*memory).version;
+        write(
+            memory,
+            nvmlMemory_v2_t {
+                version,
+                total: gpu.memory.total,
+                reserved: gpu.memory.reserved,
+                free: gpu.memory.free(),
+                used: gpu.memory.used,
+            },
+        )
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetTemperature(
+    device: nvmlDevice_t,
+    sensor: nvmlTemperatureSensors_t,
+    temp: *mut c_uint,
+) -> nvmlReturn_t {
+    with_gpu("nvmlDeviceGetTemperature", device, |gpu| match sensor {
+        nvmlTemperatureSensors_enum_NVML_TEMPERATURE_GPU => write(temp, gpu.temperature),
+        _ => Err(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT),
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetTemperatureThreshold(
+    device: nvmlDevice_t,
+    threshold: nvmlTemperatureThresholds_t,
+    temp: *mut c_uint,
+) -> nvmlReturn_t {
+    with_gpu("nvmlDeviceGetTemperatureThreshold", device, |gpu| {
+        let t = &gpu.temperature_thresholds;
+        let value = match threshold {
+            nvmlTemperatureThresholds_enum_NVML_TEMPERATURE_THRESHOLD_SHUTDOWN => t.shutdown,
+            nvmlTemperatureThresholds_enum_NVML_TEMPERATURE_THRESHOLD_SLOWDOWN => t.slowdown,
+            nvmlTemperatureThresholds_enum_NVML_TEMPERATURE_THRESHOLD_MEM_MAX => t.memory_max,
+            nvmlTemperatureThresholds_enum_NVML_TEMPERATURE_THRESHOLD_GPU_MAX => t.gpu_max,
+            _ => return Err(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED),
+        };
+
+        write(temp, value)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetPowerManagementLimitConstraints(
+    device: nvmlDevice_t,
+    min_limit: *mut c_uint,
+    max_limit: *mut c_uint,
+) -> nvmlReturn_t {
+    with_gpu(
+        "nvmlDeviceGetPowerManagementLimitConstraints",
+        device,
+        |gpu| {
+            write(min_limit, gpu.power.min_limit)?;
+            write(max_limit, gpu.power.max_limit)
+        },
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceSetPowerManagementLimit(
+    device: nvmlDevice_t,
+    limit: c_uint,
+) -> nvmlReturn_t {
+    with_gpu("nvmlDeviceSetPowerManagementLimit", device, |gpu| {
+        if limit < gpu.power.min_limit || limit > gpu.power.max_limit {
+            return Err(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT);
+        }
+
+        gpu.power.limit = limit;
+        gpu.power.enforced_limit = limit;
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceSetPersistenceMode(
+    device: nvmlDevice_t,
+    mode: nvmlEnableState_t,
+) -> nvmlReturn_t {
+    with_gpu("nvmlDeviceSetPersistenceMode", device, |gpu| {
+        gpu.persistence_mode = mode == nvmlEnableState_enum_NVML_FEATURE_ENABLED;
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceSetComputeMode(
+    device: nvmlDevice_t,
+    mode: nvmlComputeMode_t,
+) -> nvmlReturn_t {
+    with_gpu("nvmlDeviceSetComputeMode", device, |gpu| {
+        if mode >= nvmlComputeMode_enum_NVML_COMPUTEMODE_COUNT {
+            return Err(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT);
+        }
+
+        gpu.compute_mode = mode;
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetFanSpeed(
+    device: nvmlDevice_t,
+    speed: *mut c_uint,
+) -> nvmlReturn_t {
+    with_gpu("nvmlDeviceGetFanSpeed", device, |gpu| {
+        match gpu.fans.first() {
+            Some(&fan) => write(speed, fan),
+            None => Err(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED),
+        }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetFanSpeed_v2(
+    device: nvmlDevice_t,
+    fan: c_uint,
+    speed: *mut c_uint,
+) -> nvmlReturn_t {
+    with_gpu("nvmlDeviceGetFanSpeed_v2", device, |gpu| {
+        match gpu.fans.get(fan as usize) {
+            Some(&fan) => write(speed, fan),
+            None => Err(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT),
+        }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetClockInfo(
+    device: nvmlDevice_t,
+    clock_type: nvmlClockType_t,
+    clock: *mut c_uint,
+) -> nvmlReturn_t {
+    with_gpu("nvmlDeviceGetClockInfo", device, |gpu| {
+        let value = gpu
+            .clocks
+            .get(clock_type)
+            .ok_or(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT)?;
+
+        write(clock, value)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetMaxClockInfo(
+    device: nvmlDevice_t,
+    clock_type: nvmlClockType_t,
+    clock: *mut c_uint,
+) -> nvmlReturn_t {
+    with_gpu("nvmlDeviceGetMaxClockInfo", device, |gpu| {
+        let value = gpu
+            .max_clocks
+            .get(clock_type)
+            .ok_or(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT)?;
+
+        write(clock, value)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetEncoderUtilization(
+    device: nvmlDevice_t,
+    utilization: *mut c_uint,
+    sampling_period_us: *mut c_uint,
+) -> nvmlReturn_t {
+    with_gpu("nvmlDeviceGetEncoderUtilization", device, |gpu| {
+        write(utilization, gpu.utilization.encoder)?;
+        write(sampling_period_us, gpu.utilization.sampling_period)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetDecoderUtilization(
+    device: nvmlDevice_t,
+    utilization: *mut c_uint,
+    sampling_period_us: *mut c_uint,
+) -> nvmlReturn_t {
+    with_gpu("nvmlDeviceGetDecoderUtilization", device, |gpu| {
+        write(utilization, gpu.utilization.decoder)?;
+        write(sampling_period_us, gpu.utilization.sampling_period)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetJpgUtilization(
+    device: nvmlDevice_t,
+    utilization: *mut c_uint,
+    sampling_period_us: *mut c_uint,
+) -> nvmlReturn_t {
+    with_gpu("nvmlDeviceGetJpgUtilization", device, |gpu| {
+        write(utilization, gpu.utilization.jpeg)?;
+        write(sampling_period_us, gpu.utilization.sampling_period)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetOfaUtilization(
+    device: nvmlDevice_t,
+    utilization: *mut c_uint,
+    sampling_period_us: *mut c_uint,
+) -> nvmlReturn_t {
+    with_gpu("nvmlDeviceGetOfaUtilization", device, |gpu| {
+        write(utilization, gpu.utilization.ofa)?;
+        write(sampling_period_us, gpu.utilization.sampling_period)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetPcieThroughput(
+    device: nvmlDevice_t,
+    counter: nvmlPcieUtilCounter_t,
+    value: *mut c_uint,
+) -> nvmlReturn_t {
+    with_gpu("nvmlDeviceGetPcieThroughput", device, |gpu| match counter {
+        nvmlPcieUtilCounter_enum_NVML_PCIE_UTIL_TX_BYTES => write(value, gpu.pcie.tx_throughput),
+        nvmlPcieUtilCounter_enum_NVML_PCIE_UTIL_RX_BYTES => write(value, gpu.pcie.rx_throughput),
+        _ => Err(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT),
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetEccMode(
+    device: nvmlDevice_t,
+    current: *mut nvmlEnableState_t,
+    pending: *mut nvmlEnableState_t,
+) -> nvmlReturn_t {
+    with_gpu("nvmlDeviceGetEccMode", device, |gpu| {
+        write(current, enable_state(gpu.ecc.current))?;
+        write(pending, enable_state(gpu.ecc.pending))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetMigMode(
+    device: nvmlDevice_t,
+    current: *mut c_uint,
+    pending: *mut c_uint,
+) -> nvmlReturn_t {
+    with_gpu("nvmlDeviceGetMigMode", device, |gpu| {
+        write(current, gpu.mig_mode as c_uint)?;
+        write(pending, gpu.mig_mode as c_uint)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetTotalEccErrors(
+    device: nvmlDevice_t,
+    error_type: nvmlMemoryErrorType_t,
+    _counter_type: nvmlEccCounterType_t,
+    count: *mut c_ulonglong,
+) -> nvmlReturn_t {
+    with_gpu("nvmlDeviceGetTotalEccErrors", device, |gpu| {
+        if !gpu.ecc.current {
+            return Err(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED);
+        }
+
+        match error_type {
+            nvmlMemoryErrorType_enum_NVML_MEMORY_ERROR_TYPE_CORRECTED => {
+                write(count, gpu.ecc.corrected_errors)
+            }
+            nvmlMemoryErrorType_enum_NVML_MEMORY_ERROR_TYPE_UNCORRECTED => {
+                write(count, gpu.ecc.uncorrected_errors)
+            }
+            _ => Err(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT),
+        }
+    })
+}
+
+/// Count-then-fill listing of the GPU's processes of the given kind.
+///
+/// Like NVML, an empty list is reported as success even without a buffer,
+/// and a buffer that's too small fails with `InsufficientSize` after
+/// writing the required count.
+unsafe fn running_processes(
+    function: &str,
+    device: nvmlDevice_t,
+    count: *mut c_uint,
+    infos: *mut nvmlProcessInfo_t,
+    kind: ProcessKind,
+) -> nvmlReturn_t {
+    with_gpu(function, device, |gpu| {
+        if count.is_null() {
+            return Err(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT);
+        }
+
+        let processes: Vec<_> = gpu.processes.iter().filter(|p| p.kind == kind).collect();
+        let capacity = *count as usize;
+        *count = processes.len() as c_uint;
+
+        if processes.is_empty() {
+            return Ok(());
+        }
+        if infos.is_null() || capacity < processes.len() {
+            return Err(nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE);
+        }
+
+        for (i, p) in processes.iter().enumerate() {
+            infos.add(i).write(nvmlProcessInfo_t {
+                pid: p.pid,
+                usedGpuMemory: p.used_memory,
+                gpuInstanceId: c_uint::MAX,
+                computeInstanceId: c_uint::MAX,
+            });
+        }
+
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetComputeRunningProcesses_v3(
+    device: nvmlDevice_t,
+    count: *mut c_uint,
+    infos: *mut nvmlProcessInfo_t,
+) -> nvmlReturn_t {
+    running_processes(
+        "nvmlDeviceGetComputeRunningProcesses_v3",
+        device,
+        count,
+        infos,
+        ProcessKind::Compute,
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetGraphicsRunningProcesses_v3(
+    device: nvmlDevice_t,
+    count: *mut c_uint,
+    infos: *mut nvmlProcessInfo_t,
+) -> nvmlReturn_t {
+    running_processes(
+        "nvmlDeviceGetGraphicsRunningProcesses_v3",
+        device,
+        count,
+        infos,
+        ProcessKind::Graphics,
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetMPSComputeRunningProcesses_v3(
+    device: nvmlDevice_t,
+    count: *mut c_uint,
+    infos: *mut nvmlProcessInfo_t,
+) -> nvmlReturn_t {
+    running_processes(
+        "nvmlDeviceGetMPSComputeRunningProcesses_v3",
+        device,
+        count,
+        infos,
+        ProcessKind::Mps,
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetRunningProcessDetailList(
+    device: nvmlDevice_t,
+    list: *mut nvmlProcessDetailList_t,
+) -> nvmlReturn_t {
+    with_gpu("nvmlDeviceGetRunningProcessDetailList", device, |gpu| {
+        if list.is_null() {
+            return Err(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT);
+        }
+        let list = &mut *list;
+
+        // `NVML_PROCESS_MODE_*`
+        let kind = match list.mode {
+            0 => ProcessKind::Compute,
+            1 => ProcessKind::Graphics,
+            2 => ProcessKind::Mps,
+            _ => return Err(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT),
+        };
+        let processes: Vec<_> = gpu.processes.iter().filter(|p| p.kind == kind).collect();
+        let capacity = list.numProcArrayEntries as usize;
+        list.numProcArrayEntries = processes.len() as c_uint;
+
+        if processes.is_empty() {
+            return Ok(());
+        }
+        if list.procArray.is_null() || capacity < processes.len() {
+            return Err(nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE);
+        }
+
+        for (i, p) in processes.iter().enumerate() {
+            list.procArray.add(i).write(nvmlProcessDetail_v1_t {
+                pid: p.pid,
+                usedGpuMemory: p.used_memory,
+                gpuInstanceId: c_uint::MAX,
+                computeInstanceId: c_uint::MAX,
+                usedGpuCcProtectedMemory: 0,
+            });
+        }
+
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetProcessesUtilizationInfo(
+    device: nvmlDevice_t,
+    info: *mut nvmlProcessesUtilizationInfo_t,
+) -> nvmlReturn_t {
+    with_gpu("nvmlDeviceGetProcessesUtilizationInfo", device, |gpu| {
+        if info.is_null() {
+            return Err(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT);
+        }
+        let info = &mut *info;
+
+        let samples: Vec<_> = gpu
+            .processes
+            .iter()
+            .filter_map(|p| p.utilization.as_ref().map(|u| (p.pid, u)))
+            .filter(|(_, u)| u.timestamp > info.lastSeenTimeStamp)
+            .collect();
+
+        if samples.is_empty() {
+            return Err(nvmlReturn_enum_NVML_ERROR_NOT_FOUND);
+        }
+
+        let capacity = info.processSamplesCount as usize;
+        info.processSamplesCount = samples.len() as c_uint;
+        if info.procUtilArray.is_null() || capacity < samples.len() {
+            return Err(nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE);
+        }
+
+        for (i, (pid, u)) in samples.into_iter().enumerate() {
+            info.procUtilArray
+                .add(i)
+                .write(nvmlProcessUtilizationInfo_v1_t {
+                    timeStamp: u.timestamp,
+                    pid,
+                    smUtil: u.sm,
+                    memUtil: u.memory,
+                    encUtil: u.encoder,
+                    decUtil: u.decoder,
+                    jpgUtil: u.jpeg,
+                    ofaUtil: u.ofa,
+                });
+        }
+
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetProcessUtilization(
+    device: nvmlDevice_t,
+    utilization: *mut nvmlProcessUtilizationSample_t,
+    count: *mut c_uint,
+    last_seen_timestamp: c_ulonglong,
+) -> nvmlReturn_t {
+    with_gpu("nvmlDeviceGetProcessUtilization", device, |gpu| {
+        if count.is_null() {
+            return Err(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT);
+        }
+
+        let samples: Vec<_> = gpu
+            .processes
+            .iter()
+            .filter_map(|p| p.utilization.as_ref().map(|u| (p.pid, u)))
+            .filter(|(_, u)| u.timestamp > last_seen_timestamp)
+            .collect();
+
+        if samples.is_empty() {
+            return Err(nvmlReturn_enum_NVML_ERROR_NOT_FOUND);
+        }
+
+        let capacity = *count as usize;
+        *count = samples.len() as c_uint;
+        if utilization.is_null() || capacity < samples.len() {
+            return Err(nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE);
+        }
+
+        for (i, (pid, u)) in samples.into_iter().enumerate() {
+            utilization.add(i).write(nvmlProcessUtilizationSample_t {
+                pid,
+                timeStamp: u.timestamp,
+                smUtil: u.sm,
+                memUtil: u.memory,
+                encUtil: u.encoder,
+                decUtil: u.decoder,
+            });
+        }
+
+        Ok(())
+    })
+}
@@ -0,0 +1,80 @@
+//! Event sets, fed from each GPU's `events` list in the fixture.
+
+use crate::ffi::bindings::*;
+use crate::state::{call, handle, write};
+use std::os::raw::{c_uint, c_ulonglong};
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlEventSetCreate(set: *mut nvmlEventSet_t) -> nvmlReturn_t {
+    call(|state| {
+        state.fixture_for("nvmlEventSetCreate", None)?;
+        write(set, state.create_event_set())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlEventSetFree(set: nvmlEventSet_t) -> nvmlReturn_t {
+    call(|state| {
+        state.fixture_for("nvmlEventSetFree", None)?;
+        state.free_event_set(set)
+    })
+}
+
+/// Queue every event in the GPU's fixture whose type is in `event_types`.
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceRegisterEvents(
+    device: nvmlDevice_t,
+    event_types: c_ulonglong,
+    set: nvmlEventSet_t,
+) -> nvmlReturn_t {
+    call(|state| {
+        let index = state.index_of(device)?;
+        let gpu = &state
+            .fixture_for("nvmlDeviceRegisterEvents", Some(index))?
+            .gpus[index as usize];
+
+        if event_types & !gpu.supported_event_types != 0 {
+            return Err(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED);
+        }
+
+        let events: Vec<_> = gpu
+            .events
+            .iter()
+            .filter(|e| e.event_type & event_types != 0)
+            .cloned()
+            .collect();
+
+        let set = state.event_set(set)?;
+        set.pending.extend(events.into_iter().map(|e| (index, e)));
+        Ok(())
+    })
+}
+
+/// Deliver the next queued event, or time out immediately if there is none.
+#[no_mangle]
+pub unsafe extern "C" fn nvmlEventSetWait_v2(
+    set: nvmlEventSet_t,
+    data: *mut nvmlEventData_t,
+    _timeout_ms: c_uint,
+) -> nvmlReturn_t {
+    call(|state| {
+        state.fixture_for("nvmlEventSetWait_v2", None)?;
+
+        let (index, event) = state
+            .event_set(set)?
+            .pending
+            .pop_front()
+            .ok_or(nvmlReturn_enum_NVML_ERROR_TIMEOUT)?;
+
+        write(
+            data,
+            nvmlEventData_t {
+                device: handle(index as usize),
+                eventType: event.event_type,
+                eventData: event.data,
+                gpuInstanceId: c_uint::MAX,
+                computeInstanceId: c_uint::MAX,
+            },
+        )
+    })
+}
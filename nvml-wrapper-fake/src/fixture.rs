@@ -0,0 +1,751 @@
+/*!
+The description of the fake system, as loaded from a JSON or TOML fixture.
+
+Every field has a default, so a fixture only needs to spell out what a test
+cares about:
+
+```json
+{
+    "driver_version": "550.54.15",
+    "gpus": [
+        { "name": "NVIDIA A100-SXM4-80GB", "memory": { "total": 85899345920 } },
+        { "name": "NVIDIA A100-SXM4-80GB", "temperature": 71 }
+    ],
+    "errors": [
+        { "function": "nvmlDeviceGetPowerUsage", "device": 1, "code": "NVML_ERROR_GPU_IS_LOST" }
+    ]
+}
+```
+
+Enum-like values (brand, architecture, compute mode, ...) use the raw values
+from `nvml.h`.
+*/
+
+use crate::ffi::bindings::*;
+use crate::topology::Topology;
+use serde::de::{self, Deserializer};
+use serde_derive::{Deserialize, Serialize};
+use std::fmt;
+
+/// The whole fake system.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Fixture {
+    pub driver_version: String,
+    pub nvml_version: String,
+    pub cuda_driver_version: i32,
+    pub gpus: Vec<Gpu>,
+    /// Errors to return instead of the modelled result.
+    pub errors: Vec<ErrorRule>,
+    /// How the GPUs are connected. Topology queries aren't supported without
+    /// one.
+    pub topology: Option<Topology>,
+}
+
+impl Default for Fixture {
+    fn default() -> Self {
+        Self {
+            driver_version: "550.54.15".into(),
+            nvml_version: "12.550.54.15".into(),
+            cuda_driver_version: 12040,
+            gpus: vec![Gpu::default()],
+            errors: vec![],
+            topology: None,
+        }
+    }
+}
+
+impl Fixture {
+    /// Parse a fixture from its JSON representation.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let mut fixture: Self = serde_json::from_str(json)?;
+        fixture.fill_in_identities();
+
+        Ok(fixture)
+    }
+
+    /// Parse a fixture from its TOML representation.
+    pub fn from_toml(toml: &str) -> Result<Self, toml::de::Error> {
+        let mut fixture: Self = toml::from_str(toml)?;
+        fixture.fill_in_identities();
+
+        Ok(fixture)
+    }
+
+    /// Give GPUs that don't specify a UUID, serial or PCI location a unique one
+    /// derived from their index, and take their NUMA node from the topology if
+    /// there is one.
+    pub fn fill_in_identities(&mut self) {
+        for (i, gpu) in self.gpus.iter_mut().enumerate() {
+            if gpu.uuid.is_empty() {
+                gpu.uuid = format!("GPU-00000000-0000-0000-0000-{:012x}", i);
+            }
+            if gpu.serial.is_empty() {
+                gpu.serial = format!("{:013}", i);
+            }
+            if gpu.minor_number.is_none() {
+                gpu.minor_number = Some(i as u32);
+            }
+            if gpu.pci.bus.is_none() {
+                gpu.pci.bus = Some(i as u32 + 1);
+            }
+        }
+
+        if let Some(topology) = &self.topology {
+            for (i, gpu) in self.gpus.iter_mut().enumerate() {
+                if let Some(node) = topology.numa_node_of(i) {
+                    gpu.numa_node_id = node as u32;
+                }
+            }
+        }
+    }
+
+    /// Returns the error configured for the given function and device, if any.
+    pub fn error_for(&self, function: &str, device: Option<u32>) -> Option<nvmlReturn_t> {
+        self.errors
+            .iter()
+            .find(|rule| {
+                rule.function == function && (rule.device.is_none() || rule.device == device)
+            })
+            .map(|rule| rule.code.0)
+    }
+}
+
+/// Makes `function` return `code`, optionally only for the device at index
+/// `device`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ErrorRule {
+    pub function: String,
+    #[serde(default)]
+    pub device: Option<u32>,
+    pub code: ReturnCode,
+}
+
+/// An `nvmlReturn_t`, written either as its number or as its name in
+/// `nvml.h` (e.g. `"NVML_ERROR_NOT_SUPPORTED"`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ReturnCode(pub nvmlReturn_t);
+
+const RETURN_CODES: &[(&str, nvmlReturn_t)] = &[
+    ("NVML_SUCCESS", nvmlReturn_enum_NVML_SUCCESS),
+    (
+        "NVML_ERROR_UNINITIALIZED",
+        nvmlReturn_enum_NVML_ERROR_UNINITIALIZED,
+    ),
+    (
+        "NVML_ERROR_INVALID_ARGUMENT",
+        nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT,
+    ),
+    (
+        "NVML_ERROR_NOT_SUPPORTED",
+        nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED,
+    ),
+    (
+        "NVML_ERROR_NO_PERMISSION",
+        nvmlReturn_enum_NVML_ERROR_NO_PERMISSION,
+    ),
+    (
+        "NVML_ERROR_ALREADY_INITIALIZED",
+        nvmlReturn_enum_NVML_ERROR_ALREADY_INITIALIZED,
+    ),
+    ("NVML_ERROR_NOT_FOUND", nvmlReturn_enum_NVML_ERROR_NOT_FOUND),
+    (
+        "NVML_ERROR_INSUFFICIENT_SIZE",
+        nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE,
+    ),
+    (
+        "NVML_ERROR_INSUFFICIENT_POWER",
+        nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_POWER,
+    ),
+    (
+        "NVML_ERROR_DRIVER_NOT_LOADED",
+        nvmlReturn_enum_NVML_ERROR_DRIVER_NOT_LOADED,
+    ),
+    ("NVML_ERROR_TIMEOUT", nvmlReturn_enum_NVML_ERROR_TIMEOUT),
+    ("NVML_ERROR_IRQ_ISSUE", nvmlReturn_enum_NVML_ERROR_IRQ_ISSUE),
+    (
+        "NVML_ERROR_LIBRARY_NOT_FOUND",
+        nvmlReturn_enum_NVML_ERROR_LIBRARY_NOT_FOUND,
+    ),
+    (
+        "NVML_ERROR_FUNCTION_NOT_FOUND",
+        nvmlReturn_enum_NVML_ERROR_FUNCTION_NOT_FOUND,
+    ),
+    (
+        "NVML_ERROR_CORRUPTED_INFOROM",
+        nvmlReturn_enum_NVML_ERROR_CORRUPTED_INFOROM,
+    ),
+    (
+        "NVML_ERROR_GPU_IS_LOST",
+        nvmlReturn_enum_NVML_ERROR_GPU_IS_LOST,
+    ),
+    (
+        "NVML_ERROR_RESET_REQUIRED",
+        nvmlReturn_enum_NVML_ERROR_RESET_REQUIRED,
+    ),
+    (
+        "NVML_ERROR_OPERATING_SYSTEM",
+        nvmlReturn_enum_NVML_ERROR_OPERATING_SYSTEM,
+    ),
+    (
+        "NVML_ERROR_LIB_RM_VERSION_MISMATCH",
+        nvmlReturn_enum_NVML_ERROR_LIB_RM_VERSION_MISMATCH,
+    ),
+    ("NVML_ERROR_IN_USE", nvmlReturn_enum_NVML_ERROR_IN_USE),
+    ("NVML_ERROR_MEMORY", nvmlReturn_enum_NVML_ERROR_MEMORY),
+    ("NVML_ERROR_NO_DATA", nvmlReturn_enum_NVML_ERROR_NO_DATA),
+    (
+        "NVML_ERROR_VGPU_ECC_NOT_SUPPORTED",
+        nvmlReturn_enum_NVML_ERROR_VGPU_ECC_NOT_SUPPORTED,
+    ),
+    (
+        "NVML_ERROR_INSUFFICIENT_RESOURCES",
+        nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_RESOURCES,
+    ),
+    (
+        "NVML_ERROR_FREQ_NOT_SUPPORTED",
+        nvmlReturn_enum_NVML_ERROR_FREQ_NOT_SUPPORTED,
+    ),
+    (
+        "NVML_ERROR_ARGUMENT_VERSION_MISMATCH",
+        nvmlReturn_enum_NVML_ERROR_ARGUMENT_VERSION_MISMATCH,
+    ),
+    (
+        "NVML_ERROR_DEPRECATED",
+        nvmlReturn_enum_NVML_ERROR_DEPRECATED,
+    ),
+    ("NVML_ERROR_NOT_READY", nvmlReturn_enum_NVML_ERROR_NOT_READY),
+    (
+        "NVML_ERROR_GPU_NOT_FOUND",
+        nvmlReturn_enum_NVML_ERROR_GPU_NOT_FOUND,
+    ),
+    (
+        "NVML_ERROR_INVALID_STATE",
+        nvmlReturn_enum_NVML_ERROR_INVALID_STATE,
+    ),
+    (
+        "NVML_ERROR_RESET_TYPE_NOT_SUPPORTED",
+        nvmlReturn_enum_NVML_ERROR_RESET_TYPE_NOT_SUPPORTED,
+    ),
+    ("NVML_ERROR_UNKNOWN", nvmlReturn_enum_NVML_ERROR_UNKNOWN),
+];
+
+impl ReturnCode {
+    /// Look up a return code by its name in `nvml.h`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        RETURN_CODES
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, code)| Self(*code))
+    }
+
+    /// The name of this return code in `nvml.h`, if it's one we know about.
+    pub fn name(&self) -> Option<&'static str> {
+        RETURN_CODES
+            .iter()
+            .find(|(_, code)| *code == self.0)
+            .map(|(n, _)| *n)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ReturnCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = ReturnCode;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an nvmlReturn_t value or name")
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<ReturnCode, E> {
+                Ok(ReturnCode(v as nvmlReturn_t))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<ReturnCode, E> {
+                ReturnCode::from_name(v)
+                    .ok_or_else(|| E::custom(format!("unknown return code `{}`", v)))
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+/// A single GPU.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Gpu {
+    pub name: String,
+    /// Derived from the GPU's index if left empty.
+    pub uuid: String,
+    /// Derived from the GPU's index if left empty.
+    pub serial: String,
+    pub board_part_number: String,
+    pub vbios_version: String,
+    pub inforom_image_version: String,
+    /// Derived from the GPU's index if left unset.
+    pub minor_number: Option<u32>,
+    pub board_id: u32,
+    /// An `nvmlBrandType_t`.
+    pub brand: u32,
+    /// An `nvmlDeviceArchitecture_t`.
+    pub architecture: u32,
+    pub cuda_compute_capability: (i32, i32),
+    pub pci: Pci,
+    pub memory: Memory,
+    pub bar1_memory: Bar1Memory,
+    /// Current GPU core temperature, in °C.
+    pub temperature: u32,
+    pub temperature_thresholds: TemperatureThresholds,
+    pub power: Power,
+    /// An `nvmlPstates_t`.
+    pub performance_state: u32,
+    /// Speed of each fan, in percent.
+    pub fans: Vec<u32>,
+    pub clocks: Clocks,
+    pub max_clocks: Clocks,
+    pub utilization: Utilization,
+    pub processes: Vec<Process>,
+    /// An `nvmlComputeMode_t`.
+    pub compute_mode: u32,
+    pub persistence_mode: bool,
+    pub display_mode: bool,
+    pub display_active: bool,
+    pub pcie: Pcie,
+    pub num_cores: u32,
+    pub memory_bus_width: u32,
+    pub irq_num: u32,
+    /// Taken from the topology if there is one.
+    pub numa_node_id: u32,
+    pub multi_gpu_board: bool,
+    /// `nvmlClocksEventReason*` bits, also reported as throttle reasons.
+    pub throttle_reasons: u64,
+    /// `nvmlClocksEventReason*` bits, also reported as throttle reasons.
+    pub supported_throttle_reasons: u64,
+    pub ecc: Ecc,
+    pub mig_mode: bool,
+    pub max_mig_device_count: u32,
+    /// An `nvmlPowerSource_t`.
+    pub power_source: u32,
+    /// An `nvmlBusType_t`.
+    pub bus_type: u32,
+    /// `nvmlEventType*` bits.
+    pub supported_event_types: u64,
+    /// Events delivered, in order, to event sets this GPU is registered with.
+    pub events: Vec<Event>,
+}
+
+impl Default for Gpu {
+    fn default() -> Self {
+        Self {
+            name: "NVIDIA Fake GPU".into(),
+            uuid: String::new(),
+            serial: String::new(),
+            board_part_number: "900-00000-0000-000".into(),
+            vbios_version: "96.00.00.00.01".into(),
+            inforom_image_version: "G000.0000.00.01".into(),
+            minor_number: None,
+            board_id: 0x100,
+            brand: nvmlBrandType_enum_NVML_BRAND_NVIDIA,
+            architecture: NVML_DEVICE_ARCH_AMPERE,
+            cuda_compute_capability: (8, 0),
+            pci: Pci::default(),
+            memory: Memory::default(),
+            bar1_memory: Bar1Memory::default(),
+            temperature: 45,
+            temperature_thresholds: TemperatureThresholds::default(),
+            power: Power::default(),
+            performance_state: nvmlPStates_enum_NVML_PSTATE_0,
+            fans: vec![30],
+            clocks: Clocks {
+                graphics: 1410,
+                sm: 1410,
+                memory: 1215,
+                video: 1275,
+            },
+            max_clocks: Clocks {
+                graphics: 1410,
+                sm: 1410,
+                memory: 1215,
+                video: 1290,
+            },
+            utilization: Utilization::default(),
+            processes: vec![],
+            compute_mode: nvmlComputeMode_enum_NVML_COMPUTEMODE_DEFAULT,
+            persistence_mode: true,
+            display_mode: false,
+            display_active: false,
+            pcie: Pcie::default(),
+            num_cores: 6912,
+            memory_bus_width: 5120,
+            irq_num: 0,
+            numa_node_id: 0,
+            multi_gpu_board: false,
+            throttle_reasons: 0,
+            supported_throttle_reasons: 0x1ff,
+            ecc: Ecc::default(),
+            mig_mode: false,
+            max_mig_device_count: 0,
+            power_source: 0,
+            bus_type: 2,
+            supported_event_types: 0x1f,
+            events: vec![],
+        }
+    }
+}
+
+impl Gpu {
+    /// The PCI bus ID in the format NVML uses (`domain:bus:device.function`).
+    pub fn bus_id(&self) -> String {
+        self.pci.bus_id()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Pci {
+    pub domain: u32,
+    /// Derived from the GPU's index if left unset.
+    pub bus: Option<u32>,
+    pub device: u32,
+    pub pci_device_id: u32,
+    pub pci_sub_system_id: u32,
+}
+
+impl Pci {
+    /// The PCI bus ID in the format NVML uses (`domain:bus:device.function`).
+    pub fn bus_id(&self) -> String {
+        format!(
+            "{:08X}:{:02X}:{:02X}.0",
+            self.domain,
+            self.bus.unwrap_or(0),
+            self.device
+        )
+    }
+}
+
+impl Default for Pci {
+    fn default() -> Self {
+        Self {
+            domain: 0,
+            bus: None,
+            device: 0,
+            pci_device_id: 0x20b010de,
+            pci_sub_system_id: 0x134f10de,
+        }
+    }
+}
+
+/// Framebuffer memory, in bytes. Free memory is whatever is left over.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Memory {
+    pub total: u64,
+    pub reserved: u64,
+    pub used: u64,
+}
+
+impl Default for Memory {
+    fn default() -> Self {
+        Self {
+            total: 40 << 30,
+            reserved: 512 << 20,
+            used: 0,
+        }
+    }
+}
+
+impl Memory {
+    pub fn free(&self) -> u64 {
+        self.total.saturating_sub(self.reserved + self.used)
+    }
+}
+
+/// BAR1 memory, in bytes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Bar1Memory {
+    pub total: u64,
+    pub used: u64,
+}
+
+impl Default for Bar1Memory {
+    fn default() -> Self {
+        Self {
+            total: 64 << 30,
+            used: 4 << 20,
+        }
+    }
+}
+
+/// Temperature thresholds, in °C.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TemperatureThresholds {
+    pub shutdown: u32,
+    pub slowdown: u32,
+    pub memory_max: u32,
+    pub gpu_max: u32,
+}
+
+impl Default for TemperatureThresholds {
+    fn default() -> Self {
+        Self {
+            shutdown: 92,
+            slowdown: 89,
+            memory_max: 95,
+            gpu_max: 87,
+        }
+    }
+}
+
+/// Power readings and limits, in milliwatts (energy in millijoules).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Power {
+    pub usage: u32,
+    pub limit: u32,
+    pub default_limit: u32,
+    pub min_limit: u32,
+    pub max_limit: u32,
+    pub enforced_limit: u32,
+    pub total_energy_consumption: u64,
+}
+
+impl Default for Power {
+    fn default() -> Self {
+        Self {
+            usage: 60_000,
+            limit: 400_000,
+            default_limit: 400_000,
+            min_limit: 100_000,
+            max_limit: 400_000,
+            enforced_limit: 400_000,
+            total_energy_consumption: 0,
+        }
+    }
+}
+
+/// Clock speeds, in MHz.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Clocks {
+    pub graphics: u32,
+    pub sm: u32,
+    pub memory: u32,
+    pub video: u32,
+}
+
+impl Clocks {
+    /// Look up a clock by its `nvmlClockType_t`.
+    pub fn get(&self, clock_type: nvmlClockType_t) -> Option<u32> {
+        match clock_type {
+            nvmlClockType_enum_NVML_CLOCK_GRAPHICS => Some(self.graphics),
+            nvmlClockType_enum_NVML_CLOCK_SM => Some(self.sm),
+            nvmlClockType_enum_NVML_CLOCK_MEM => Some(self.memory),
+            nvmlClockType_enum_NVML_CLOCK_VIDEO => Some(self.video),
+            _ => None,
+        }
+    }
+}
+
+/// Utilization, in percent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Utilization {
+    pub gpu: u32,
+    pub memory: u32,
+    pub encoder: u32,
+    pub decoder: u32,
+    pub jpeg: u32,
+    pub ofa: u32,
+    /// Sampling period for the media engine readings, in μs.
+    pub sampling_period: u32,
+}
+
+impl Default for Utilization {
+    fn default() -> Self {
+        Self {
+            gpu: 0,
+            memory: 0,
+            encoder: 0,
+            decoder: 0,
+            jpeg: 0,
+            ofa: 0,
+            sampling_period: 167_000,
+        }
+    }
+}
+
+/// What kind of context a process has on a GPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessKind {
+    Compute,
+    Graphics,
+    Mps,
+}
+
+/// A process running on a GPU.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Process {
+    pub pid: u32,
+    #[serde(default)]
+    pub name: String,
+    pub kind: ProcessKind,
+    /// In bytes.
+    #[serde(default)]
+    pub used_memory: u64,
+    /// Per-process utilization sample, if this process should show up in
+    /// `nvmlDeviceGetProcessUtilization` and
+    /// `nvmlDeviceGetProcessesUtilizationInfo`.
+    #[serde(default)]
+    pub utilization: Option<ProcessUtilization>,
+}
+
+/// Per-process utilization, in percent.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProcessUtilization {
+    /// CPU timestamp of the sample, in μs.
+    pub timestamp: u64,
+    pub sm: u32,
+    pub memory: u32,
+    pub encoder: u32,
+    pub decoder: u32,
+    pub jpeg: u32,
+    pub ofa: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Pcie {
+    pub current_link_gen: u32,
+    pub current_link_width: u32,
+    pub max_link_gen: u32,
+    pub max_link_width: u32,
+    /// In KB/s.
+    pub tx_throughput: u32,
+    /// In KB/s.
+    pub rx_throughput: u32,
+}
+
+impl Default for Pcie {
+    fn default() -> Self {
+        Self {
+            current_link_gen: 4,
+            current_link_width: 16,
+            max_link_gen: 4,
+            max_link_width: 16,
+            tx_throughput: 0,
+            rx_throughput: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Ecc {
+    pub current: bool,
+    pub pending: bool,
+    pub corrected_errors: u64,
+    pub uncorrected_errors: u64,
+}
+
+/// An event delivered through `nvmlEventSetWait`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Event {
+    /// A single `nvmlEventType*` bit.
+    pub event_type: u64,
+    #[serde(default)]
+    pub data: u64,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn minimal_fixture() {
+        let fixture = Fixture::from_json(r#"{ "gpus": [{}, { "name": "Second" }] }"#).unwrap();
+
+        assert_eq!(fixture.gpus.len(), 2);
+        assert_eq!(fixture.gpus[1].name, "Second");
+        assert_ne!(fixture.gpus[0].uuid, fixture.gpus[1].uuid);
+        assert_ne!(fixture.gpus[0].bus_id(), fixture.gpus[1].bus_id());
+    }
+
+    #[test]
+    fn error_rules() {
+        let fixture = Fixture::from_json(
+            r#"{
+                "errors": [
+                    { "function": "nvmlDeviceGetPowerUsage", "device": 1, "code": "NVML_ERROR_GPU_IS_LOST" },
+                    { "function": "nvmlDeviceGetFanSpeed_v2", "code": 3 }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(fixture.error_for("nvmlDeviceGetPowerUsage", Some(0)), None);
+        assert_eq!(
+            fixture.error_for("nvmlDeviceGetPowerUsage", Some(1)),
+            Some(nvmlReturn_enum_NVML_ERROR_GPU_IS_LOST)
+        );
+        assert_eq!(
+            fixture.error_for("nvmlDeviceGetFanSpeed_v2", Some(0)),
+            Some(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED)
+        );
+    }
+
+    #[test]
+    fn toml_fixture() {
+        let fixture = Fixture::from_toml(
+            r#"
+            driver_version = "535.0"
+
+            [[gpus]]
+            name = "From TOML"
+            fans = [10, 20]
+
+            [[errors]]
+            function = "nvmlDeviceGetName"
+            code = "NVML_ERROR_UNKNOWN"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(fixture.driver_version, "535.0");
+        assert_eq!(fixture.gpus[0].fans, vec![10, 20]);
+        assert_eq!(
+            fixture.error_for("nvmlDeviceGetName", Some(0)),
+            Some(nvmlReturn_enum_NVML_ERROR_UNKNOWN)
+        );
+    }
+
+    #[test]
+    fn example_fixtures_parse() {
+        let a100 = Fixture::from_json(include_str!("../fixtures/dual-a100.json")).unwrap();
+        assert_eq!(a100.gpus.len(), 2);
+
+        let geforce = Fixture::from_toml(include_str!("../fixtures/geforce.toml")).unwrap();
+        assert_eq!(geforce.gpus[0].brand, nvmlBrandType_enum_NVML_BRAND_GEFORCE);
+
+        let wrapper_tests =
+            Fixture::from_json(include_str!("../fixtures/wrapper-tests.json")).unwrap();
+        assert_eq!(wrapper_tests.gpus.len(), 2);
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected() {
+        assert!(Fixture::from_json(r#"{ "gpus": [{ "nmae": "typo" }] }"#).is_err());
+        assert!(
+            Fixture::from_json(r#"{ "errors": [{ "function": "x", "code": "NOPE" }] }"#).is_err()
+        );
+    }
+}
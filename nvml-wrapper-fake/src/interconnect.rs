@@ -0,0 +1,313 @@
+//! Topology, P2P, affinity and NvLink queries, answered from the fixture's
+//! [`Topology`](crate::topology::Topology).
+
+use crate::device::pci_info;
+use crate::ffi::bindings::*;
+use crate::fixture::Pci;
+use crate::state::{handle, with_topology, write};
+use crate::topology::{Remote, Topology};
+use std::os::raw::{c_uint, c_ulong};
+
+/// Write the handles of `gpus` to the `count`-long `array`, or just their
+/// number if `array` is null.
+unsafe fn write_handles(
+    gpus: &[usize],
+    count: *mut c_uint,
+    array: *mut nvmlDevice_t,
+) -> Result<(), nvmlReturn_t> {
+    if count.is_null() {
+        return Err(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT);
+    }
+
+    let capacity = *count as usize;
+    *count = gpus.len() as c_uint;
+
+    if array.is_null() {
+        return Ok(());
+    }
+    if capacity < gpus.len() {
+        return Err(nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE);
+    }
+
+    for (i, &gpu) in gpus.iter().enumerate() {
+        array.add(i).write(handle(gpu));
+    }
+
+    Ok(())
+}
+
+/// Set the bits for `items` in the `size`-long bitmask at `set`.
+unsafe fn write_bitmask<I>(items: I, size: c_uint, set: *mut c_ulong) -> Result<(), nvmlReturn_t>
+where
+    I: IntoIterator<Item = u32>,
+{
+    if set.is_null() || size == 0 {
+        return Err(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT);
+    }
+
+    let bits = c_ulong::BITS;
+    for i in 0..size as usize {
+        set.add(i).write(0);
+    }
+    for item in items {
+        let word = (item / bits) as usize;
+        if word < size as usize {
+            *set.add(word) |= 1 << (item % bits);
+        }
+    }
+
+    Ok(())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetTopologyCommonAncestor(
+    device1: nvmlDevice_t,
+    device2: nvmlDevice_t,
+    path_info: *mut nvmlGpuTopologyLevel_t,
+) -> nvmlReturn_t {
+    with_topology(
+        "nvmlDeviceGetTopologyCommonAncestor",
+        &[device1, device2],
+        |_, topology, gpus| match topology.common_ancestor(gpus[0], gpus[1]) {
+            Some(level) => write(path_info, level),
+            None => Err(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED),
+        },
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetTopologyNearestGpus(
+    device: nvmlDevice_t,
+    level: nvmlGpuTopologyLevel_t,
+    count: *mut c_uint,
+    array: *mut nvmlDevice_t,
+) -> nvmlReturn_t {
+    with_topology(
+        "nvmlDeviceGetTopologyNearestGpus",
+        &[device],
+        |_, topology, gpus| match topology.nearest_gpus(gpus[0], level) {
+            Some(nearest) => write_handles(&nearest, count, array),
+            None => Err(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED),
+        },
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlSystemGetTopologyGpuSet(
+    cpu_number: c_uint,
+    count: *mut c_uint,
+    array: *mut nvmlDevice_t,
+) -> nvmlReturn_t {
+    with_topology(
+        "nvmlSystemGetTopologyGpuSet",
+        &[],
+        |_, topology, _| match topology.gpus_near_cpu(cpu_number) {
+            Some(gpus) => write_handles(&gpus, count, array),
+            None => Err(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT),
+        },
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetP2PStatus(
+    device1: nvmlDevice_t,
+    device2: nvmlDevice_t,
+    caps: nvmlGpuP2PCapsIndex_t,
+    status: *mut nvmlGpuP2PStatus_t,
+) -> nvmlReturn_t {
+    with_topology(
+        "nvmlDeviceGetP2PStatus",
+        &[device1, device2],
+        |_, topology, gpus| match topology.p2p_status(gpus[0], gpus[1], caps) {
+            Some(s) => write(status, s),
+            None => Err(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED),
+        },
+    )
+}
+
+/// The CPUs local to `gpu`.
+fn local_cpus(topology: &Topology, gpu: usize) -> Result<Vec<u32>, nvmlReturn_t> {
+    topology
+        .numa_node_of(gpu)
+        .and_then(|node| topology.numa_nodes.get(node))
+        .map(|node| node.cpus.clone().collect())
+        .ok_or(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetCpuAffinity(
+    device: nvmlDevice_t,
+    size: c_uint,
+    cpu_set: *mut c_ulong,
+) -> nvmlReturn_t {
+    with_topology(
+        "nvmlDeviceGetCpuAffinity",
+        &[device],
+        |_, topology, gpus| write_bitmask(local_cpus(topology, gpus[0])?, size, cpu_set),
+    )
+}
+
+/// Every NUMA node is modelled as its own socket, so both scopes give the same
+/// answer.
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetCpuAffinityWithinScope(
+    device: nvmlDevice_t,
+    size: c_uint,
+    cpu_set: *mut c_ulong,
+    _scope: nvmlAffinityScope_t,
+) -> nvmlReturn_t {
+    with_topology(
+        "nvmlDeviceGetCpuAffinityWithinScope",
+        &[device],
+        |_, topology, gpus| write_bitmask(local_cpus(topology, gpus[0])?, size, cpu_set),
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetMemoryAffinity(
+    device: nvmlDevice_t,
+    size: c_uint,
+    node_set: *mut c_ulong,
+    _scope: nvmlAffinityScope_t,
+) -> nvmlReturn_t {
+    with_topology(
+        "nvmlDeviceGetMemoryAffinity",
+        &[device],
+        |_, topology, gpus| match topology.numa_node_of(gpus[0]) {
+            Some(node) => write_bitmask([node as u32], size, node_set),
+            None => Err(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED),
+        },
+    )
+}
+
+/// What's at the other end of `link`, failing like NVML for invalid link
+/// numbers and with `NotSupported` for inactive links.
+fn remote(topology: &Topology, gpu: usize, link: c_uint) -> Result<Remote, nvmlReturn_t> {
+    if link >= NVML_NVLINK_MAX_LINKS {
+        return Err(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT);
+    }
+
+    topology
+        .links(gpu)
+        .get(link as usize)
+        .copied()
+        .ok_or(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetNvLinkState(
+    device: nvmlDevice_t,
+    link: c_uint,
+    is_active: *mut nvmlEnableState_t,
+) -> nvmlReturn_t {
+    with_topology(
+        "nvmlDeviceGetNvLinkState",
+        &[device],
+        |_, topology, gpus| {
+            let state = match remote(topology, gpus[0], link) {
+                Ok(_) => nvmlEnableState_enum_NVML_FEATURE_ENABLED,
+                Err(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED) => {
+                    nvmlEnableState_enum_NVML_FEATURE_DISABLED
+                }
+                Err(code) => return Err(code),
+            };
+
+            write(is_active, state)
+        },
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetNvLinkVersion(
+    device: nvmlDevice_t,
+    link: c_uint,
+    version: *mut c_uint,
+) -> nvmlReturn_t {
+    with_topology(
+        "nvmlDeviceGetNvLinkVersion",
+        &[device],
+        |_, topology, gpus| {
+            remote(topology, gpus[0], link)?;
+            write(version, topology.nvlink_version)
+        },
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetNvLinkCapability(
+    device: nvmlDevice_t,
+    link: c_uint,
+    capability: nvmlNvLinkCapability_t,
+    result: *mut c_uint,
+) -> nvmlReturn_t {
+    with_topology(
+        "nvmlDeviceGetNvLinkCapability",
+        &[device],
+        |_, topology, gpus| {
+            remote(topology, gpus[0], link)?;
+
+            let supported = match capability {
+                nvmlNvLinkCapability_enum_NVML_NVLINK_CAP_P2P_SUPPORTED
+                | nvmlNvLinkCapability_enum_NVML_NVLINK_CAP_P2P_ATOMICS
+                | nvmlNvLinkCapability_enum_NVML_NVLINK_CAP_VALID => true,
+                nvmlNvLinkCapability_enum_NVML_NVLINK_CAP_SYSMEM_ACCESS
+                | nvmlNvLinkCapability_enum_NVML_NVLINK_CAP_SYSMEM_ATOMICS
+                | nvmlNvLinkCapability_enum_NVML_NVLINK_CAP_SLI_BRIDGE => false,
+                _ => return Err(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT),
+            };
+
+            write(result, supported as c_uint)
+        },
+    )
+}
+
+/// Where NVSwitch `index` sits on the PCI bus.
+fn nvswitch_pci(index: usize) -> Pci {
+    Pci {
+        domain: 0,
+        bus: Some(0xc0 + index as u32),
+        device: 0,
+        pci_device_id: 0x1af110de,
+        pci_sub_system_id: 0,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetNvLinkRemotePciInfo_v2(
+    device: nvmlDevice_t,
+    link: c_uint,
+    pci: *mut nvmlPciInfo_t,
+) -> nvmlReturn_t {
+    with_topology(
+        "nvmlDeviceGetNvLinkRemotePciInfo_v2",
+        &[device],
+        |fixture, topology, gpus| {
+            let info = match remote(topology, gpus[0], link)? {
+                Remote::Gpu(gpu) => pci_info(&fixture.gpus[gpu].pci),
+                Remote::NvSwitch(switch) => pci_info(&nvswitch_pci(switch)),
+            };
+
+            write(pci, info)
+        },
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetNvLinkRemoteDeviceType(
+    device: nvmlDevice_t,
+    link: c_uint,
+    device_type: *mut nvmlIntNvLinkDeviceType_t,
+) -> nvmlReturn_t {
+    with_topology(
+        "nvmlDeviceGetNvLinkRemoteDeviceType",
+        &[device],
+        |_, topology, gpus| {
+            let remote_type = match remote(topology, gpus[0], link)? {
+                Remote::Gpu(_) => nvmlIntNvLinkDeviceType_enum_NVML_NVLINK_DEVICE_TYPE_GPU,
+                Remote::NvSwitch(_) => nvmlIntNvLinkDeviceType_enum_NVML_NVLINK_DEVICE_TYPE_SWITCH,
+            };
+
+            write(device_type, remote_type)
+        },
+    )
+}
@@ -0,0 +1,58 @@
+/*!
+A fake NVML shared library, backed by an in-memory model of a GPU system.
+
+Building this crate produces `libnvidia_ml_fake.so` (`nvidia_ml_fake.dll` on
+Windows) next to your other build artifacts. It exports the subset of the NVML
+C API that covers system and device queries, process listing and events, so
+code written against `nvml-wrapper` can be exercised end to end without an
+NVIDIA GPU or driver:
+
+```no_run
+use nvml_wrapper::Nvml;
+
+let nvml = Nvml::builder()
+    .lib_path("target/debug/libnvidia_ml_fake.so".as_ref())
+    .init()?;
+
+assert_eq!(nvml.device_count()?, 1);
+# Ok::<(), nvml_wrapper::error::NvmlError>(())
+```
+
+Anything not exported fails to load, exactly like a symbol missing from an
+older driver would.
+
+## Fixtures
+
+The system being modelled is described by a JSON or TOML
+[`Fixture`](fixture::Fixture): the GPUs and their properties, and errors that
+specific functions should return (optionally for one device only). It's read
+from the path in the `NVML_FAKE_FIXTURE` environment variable the first time
+NVML is initialized; without it, a single default GPU is modelled. See
+`fixtures/` for examples.
+
+A fixture can also describe how the GPUs are connected (NUMA nodes, PCIe
+switches, NvLinks and NVSwitches) with a [`Topology`](topology::Topology),
+which every topology, affinity, P2P and NvLink query is answered from.
+Presets model common server shapes, such as an 8-GPU NVSwitch system.
+
+Tests can also swap the fixture at any point through the extra
+`nvmlFakeLoadFixture(const char *json)` export. The model is process-wide
+state, so tests that need different fixtures concurrently should each load
+their own copy of the library file.
+*/
+
+// The exports mirror NVML's C names and take raw pointers from C callers;
+// their contract is NVML's, not something to restate per function.
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(clippy::missing_safety_doc)]
+
+pub use nvml_wrapper_sys as ffi;
+
+pub mod device;
+pub mod event;
+pub mod fixture;
+pub mod interconnect;
+pub mod state;
+pub mod system;
+pub mod topology;
@@ -0,0 +1,279 @@
+/*!
+Process-wide state shared by every exported function.
+
+Device handles are the GPU's index plus one, cast to a pointer, and event set
+handles work the same way with the event set's slot. Neither is ever
+dereferenced.
+*/
+
+use crate::ffi::bindings::*;
+use crate::fixture::{Event, Fixture, Gpu};
+use crate::topology::Topology;
+use once_cell::sync::Lazy;
+use std::collections::VecDeque;
+use std::env;
+use std::ffi::CStr;
+use std::fs;
+use std::os::raw::{c_char, c_uint};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+
+/// Environment variable pointing at the fixture to load on init. Files ending
+/// in `.toml` are parsed as TOML, anything else as JSON.
+pub const FIXTURE_ENV_VAR: &str = "NVML_FAKE_FIXTURE";
+
+static STATE: Lazy<Mutex<State>> = Lazy::new(Default::default);
+
+/// The outcome of an exported function, `Err` holding the code to return.
+pub type FakeResult = Result<(), nvmlReturn_t>;
+
+#[derive(Debug, Default)]
+pub struct State {
+    init_count: u32,
+    fixture: Option<Fixture>,
+    event_sets: Vec<Option<EventSet>>,
+}
+
+/// Events waiting to be delivered to a caller of `nvmlEventSetWait`.
+#[derive(Debug, Default)]
+pub struct EventSet {
+    pub pending: VecDeque<(c_uint, Event)>,
+}
+
+/// Lock the global state, ignoring poisoning (a panicking test shouldn't
+/// take every other test down with it).
+pub fn lock() -> MutexGuard<'static, State> {
+    STATE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Run `f` against the global state and turn its outcome into a return code.
+pub fn call<F>(f: F) -> nvmlReturn_t
+where
+    F: FnOnce(&mut State) -> FakeResult,
+{
+    match f(&mut lock()) {
+        Ok(()) => nvmlReturn_enum_NVML_SUCCESS,
+        Err(code) => code,
+    }
+}
+
+/// Run `f` against the loaded fixture, after checking that the library is
+/// initialized and that no error is configured for `function`.
+pub fn with_fixture<F>(function: &str, f: F) -> nvmlReturn_t
+where
+    F: FnOnce(&mut Fixture) -> FakeResult,
+{
+    call(|state| f(state.fixture_for(function, None)?))
+}
+
+/// Run `f` against the GPU `device` refers to, after checking that the
+/// library is initialized, that the handle is valid and that no error is
+/// configured for `function` on that GPU.
+pub fn with_gpu<F>(function: &str, device: nvmlDevice_t, f: F) -> nvmlReturn_t
+where
+    F: FnOnce(&mut Gpu) -> FakeResult,
+{
+    call(|state| {
+        let index = state.index_of(device)?;
+        let fixture = state.fixture_for(function, Some(index))?;
+
+        f(&mut fixture.gpus[index as usize])
+    })
+}
+
+/// Run `f` against the loaded fixture, its topology and the indices of the
+/// GPUs in `devices`, after the same checks as `with_gpu()` (against the first
+/// GPU, if any). Fails with `NotSupported` if there's no topology or it doesn't
+/// cover one of the GPUs.
+pub fn with_topology<F>(function: &str, devices: &[nvmlDevice_t], f: F) -> nvmlReturn_t
+where
+    F: FnOnce(&Fixture, &Topology, &[usize]) -> FakeResult,
+{
+    call(|state| {
+        let indices = devices
+            .iter()
+            .map(|&device| state.index_of(device))
+            .collect::<Result<Vec<_>, _>>()?;
+        let fixture = state.fixture_for(function, indices.first().copied())?;
+
+        let topology = fixture
+            .topology
+            .as_ref()
+            .ok_or(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED)?;
+        let indices: Vec<_> = indices.into_iter().map(|i| i as usize).collect();
+        if indices.iter().any(|&i| i >= topology.gpus.len()) {
+            return Err(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED);
+        }
+
+        f(fixture, topology, &indices)
+    })
+}
+
+impl State {
+    /// Replace the fixture. Takes effect immediately, even if initialized.
+    pub fn load(&mut self, fixture: Fixture) {
+        self.fixture = Some(fixture);
+    }
+
+    /// Load the fixture from `NVML_FAKE_FIXTURE` (or the default one) if one
+    /// hasn't been loaded explicitly, and bump the init count.
+    pub fn init(&mut self, function: &str) -> FakeResult {
+        if self.fixture.is_none() {
+            self.fixture = Some(fixture_from_env()?);
+        }
+
+        if let Some(code) = self.fixture().error_for(function, None) {
+            return Err(code);
+        }
+
+        self.init_count += 1;
+        Ok(())
+    }
+
+    pub fn shutdown(&mut self) -> FakeResult {
+        if self.init_count == 0 {
+            return Err(nvmlReturn_enum_NVML_ERROR_UNINITIALIZED);
+        }
+
+        self.init_count -= 1;
+        if self.init_count == 0 {
+            self.event_sets.clear();
+        }
+
+        Ok(())
+    }
+
+    fn fixture(&mut self) -> &mut Fixture {
+        self.fixture.get_or_insert_with(Default::default)
+    }
+
+    /// The fixture, provided the library is initialized and no error is
+    /// configured for `function` on `device`.
+    pub fn fixture_for(
+        &mut self,
+        function: &str,
+        device: Option<c_uint>,
+    ) -> Result<&mut Fixture, nvmlReturn_t> {
+        if self.init_count == 0 {
+            return Err(nvmlReturn_enum_NVML_ERROR_UNINITIALIZED);
+        }
+
+        let fixture = self.fixture();
+        match fixture.error_for(function, device) {
+            Some(code) => Err(code),
+            None => Ok(fixture),
+        }
+    }
+
+    /// The index of the GPU `device` refers to.
+    pub fn index_of(&mut self, device: nvmlDevice_t) -> Result<c_uint, nvmlReturn_t> {
+        let count = self.fixture().gpus.len();
+
+        match (device as usize).checked_sub(1) {
+            Some(index) if index < count => Ok(index as c_uint),
+            _ => Err(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT),
+        }
+    }
+
+    pub fn create_event_set(&mut self) -> nvmlEventSet_t {
+        let slot = match self.event_sets.iter().position(Option::is_none) {
+            Some(slot) => slot,
+            None => {
+                self.event_sets.push(None);
+                self.event_sets.len() - 1
+            }
+        };
+
+        self.event_sets[slot] = Some(EventSet::default());
+        (slot + 1) as nvmlEventSet_t
+    }
+
+    pub fn event_set(&mut self, set: nvmlEventSet_t) -> Result<&mut EventSet, nvmlReturn_t> {
+        (set as usize)
+            .checked_sub(1)
+            .and_then(|slot| self.event_sets.get_mut(slot))
+            .and_then(Option::as_mut)
+            .ok_or(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT)
+    }
+
+    pub fn free_event_set(&mut self, set: nvmlEventSet_t) -> FakeResult {
+        self.event_set(set)?;
+        self.event_sets[set as usize - 1] = None;
+
+        Ok(())
+    }
+}
+
+fn fixture_from_env() -> Result<Fixture, nvmlReturn_t> {
+    let path = match env::var_os(FIXTURE_ENV_VAR) {
+        Some(path) => path,
+        None => return Ok(Fixture::default()),
+    };
+
+    let contents = fs::read_to_string(&path).map_err(|e| {
+        eprintln!("nvml-wrapper-fake: failed to read {:?}: {}", path, e);
+        nvmlReturn_enum_NVML_ERROR_LIBRARY_NOT_FOUND
+    })?;
+
+    let parsed = if Path::new(&path).extension() == Some("toml".as_ref()) {
+        Fixture::from_toml(&contents).map_err(|e| e.to_string())
+    } else {
+        Fixture::from_json(&contents).map_err(|e| e.to_string())
+    };
+
+    parsed.map_err(|e| {
+        eprintln!("nvml-wrapper-fake: invalid fixture {:?}: {}", path, e);
+        nvmlReturn_enum_NVML_ERROR_UNKNOWN
+    })
+}
+
+/// The handle for the GPU at `index`.
+pub fn handle(index: usize) -> nvmlDevice_t {
+    (index + 1) as nvmlDevice_t
+}
+
+/// Write `value` through `ptr`, failing with `InvalidArg` if it's null.
+pub unsafe fn write<T>(ptr: *mut T, value: T) -> FakeResult {
+    if ptr.is_null() {
+        return Err(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT);
+    }
+
+    ptr.write(value);
+    Ok(())
+}
+
+/// Copy `s` into the `length`-byte buffer at `buf` as a nul-terminated
+/// string, failing with `InsufficientSize` if it doesn't fit.
+pub unsafe fn write_str(s: &str, buf: *mut c_char, length: c_uint) -> FakeResult {
+    if buf.is_null() {
+        return Err(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT);
+    }
+    if s.len() >= length as usize {
+        return Err(nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE);
+    }
+
+    buf.copy_from_nonoverlapping(s.as_ptr() as *const c_char, s.len());
+    buf.add(s.len()).write(0);
+    Ok(())
+}
+
+/// Read the nul-terminated string at `ptr`, failing with `InvalidArg` if it's
+/// null or not UTF-8.
+pub unsafe fn read_str<'a>(ptr: *const c_char) -> Result<&'a str, nvmlReturn_t> {
+    if ptr.is_null() {
+        return Err(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT);
+    }
+
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|_| nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT)
+}
+
+/// Convert a bool to an `nvmlEnableState_t`.
+pub fn enable_state(enabled: bool) -> nvmlEnableState_t {
+    if enabled {
+        nvmlEnableState_enum_NVML_FEATURE_ENABLED
+    } else {
+        nvmlEnableState_enum_NVML_FEATURE_DISABLED
+    }
+}
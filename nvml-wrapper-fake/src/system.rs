@@ -0,0 +1,266 @@
+//! Library lifecycle, system queries and device enumeration.
+
+use crate::ffi::bindings::*;
+use crate::fixture::Fixture;
+use crate::state::{call, handle, read_str, with_fixture, write, write_str};
+use std::os::raw::{c_char, c_int, c_uint};
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlInit_v2() -> nvmlReturn_t {
+    call(|state| state.init("nvmlInit_v2"))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlInitWithFlags(_flags: c_uint) -> nvmlReturn_t {
+    call(|state| state.init("nvmlInitWithFlags"))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlShutdown() -> nvmlReturn_t {
+    call(|state| state.shutdown())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlErrorString(result: nvmlReturn_t) -> *const c_char {
+    let s: &'static [u8] = match result {
+        nvmlReturn_enum_NVML_SUCCESS => b"Success\0",
+        nvmlReturn_enum_NVML_ERROR_UNINITIALIZED => b"Uninitialized\0",
+        nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT => b"Invalid Argument\0",
+        nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED => b"Not Supported\0",
+        nvmlReturn_enum_NVML_ERROR_NO_PERMISSION => b"Insufficient Permissions\0",
+        nvmlReturn_enum_NVML_ERROR_NOT_FOUND => b"Not Found\0",
+        nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE => b"Insufficient Size\0",
+        nvmlReturn_enum_NVML_ERROR_TIMEOUT => b"Timeout\0",
+        nvmlReturn_enum_NVML_ERROR_GPU_IS_LOST => b"GPU is lost\0",
+        nvmlReturn_enum_NVML_ERROR_NO_DATA => b"No data\0",
+        _ => b"Unknown Error\0",
+    };
+
+    s.as_ptr() as *const c_char
+}
+
+/// Replace the fixture with the one described by the JSON string `json`.
+///
+/// Not part of NVML; this is how tests script the fake. Returns
+/// `NVML_ERROR_INVALID_ARGUMENT` if `json` isn't a valid fixture.
+#[no_mangle]
+pub unsafe extern "C" fn nvmlFakeLoadFixture(json: *const c_char) -> nvmlReturn_t {
+    call(|state| {
+        let fixture = Fixture::from_json(read_str(json)?).map_err(|e| {
+            eprintln!("nvml-wrapper-fake: invalid fixture: {}", e);
+            nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT
+        })?;
+
+        state.load(fixture);
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlSystemGetDriverVersion(
+    version: *mut c_char,
+    length: c_uint,
+) -> nvmlReturn_t {
+    with_fixture("nvmlSystemGetDriverVersion", |f| {
+        write_str(&f.driver_version, version, length)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlSystemGetNVMLVersion(
+    version: *mut c_char,
+    length: c_uint,
+) -> nvmlReturn_t {
+    with_fixture("nvmlSystemGetNVMLVersion", |f| {
+        write_str(&f.nvml_version, version, length)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlSystemGetCudaDriverVersion(version: *mut c_int) -> nvmlReturn_t {
+    with_fixture("nvmlSystemGetCudaDriverVersion", |f| {
+        write(version, f.cuda_driver_version)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlSystemGetCudaDriverVersion_v2(version: *mut c_int) -> nvmlReturn_t {
+    with_fixture("nvmlSystemGetCudaDriverVersion_v2", |f| {
+        write(version, f.cuda_driver_version)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlSystemGetProcessName(
+    pid: c_uint,
+    name: *mut c_char,
+    length: c_uint,
+) -> nvmlReturn_t {
+    with_fixture("nvmlSystemGetProcessName", |f| {
+        let process = f
+            .gpus
+            .iter()
+            .flat_map(|gpu| &gpu.processes)
+            .find(|p| p.pid == pid)
+            .ok_or(nvmlReturn_enum_NVML_ERROR_NOT_FOUND)?;
+
+        write_str(&process.name, name, length)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlSystemGetHicVersion(
+    count: *mut c_uint,
+    _entries: *mut nvmlHwbcEntry_t,
+) -> nvmlReturn_t {
+    with_fixture("nvmlSystemGetHicVersion", |_| write(count, 0))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlUnitGetCount(count: *mut c_uint) -> nvmlReturn_t {
+    with_fixture("nvmlUnitGetCount", |_| write(count, 0))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlGetExcludedDeviceCount(count: *mut c_uint) -> nvmlReturn_t {
+    with_fixture("nvmlGetExcludedDeviceCount", |_| write(count, 0))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetCount_v2(count: *mut c_uint) -> nvmlReturn_t {
+    with_fixture("nvmlDeviceGetCount_v2", |f| {
+        write(count, f.gpus.len() as c_uint)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetHandleByIndex_v2(
+    index: c_uint,
+    device: *mut nvmlDevice_t,
+) -> nvmlReturn_t {
+    call(|state| {
+        let fixture = state.fixture_for("nvmlDeviceGetHandleByIndex_v2", Some(index))?;
+        if index as usize >= fixture.gpus.len() {
+            return Err(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT);
+        }
+
+        write(device, handle(index as usize))
+    })
+}
+
+/// Find the first GPU matching `pred` and write its handle to `device`.
+unsafe fn handle_by<P>(function: &str, device: *mut nvmlDevice_t, pred: P) -> nvmlReturn_t
+where
+    P: Fn(&crate::fixture::Gpu) -> bool,
+{
+    with_fixture(function, |f| {
+        let index = f
+            .gpus
+            .iter()
+            .position(pred)
+            .ok_or(nvmlReturn_enum_NVML_ERROR_NOT_FOUND)?;
+
+        write(device, handle(index))
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetHandleByUUID(
+    uuid: *const c_char,
+    device: *mut nvmlDevice_t,
+) -> nvmlReturn_t {
+    let uuid = match read_str(uuid) {
+        Ok(uuid) => uuid,
+        Err(code) => return code,
+    };
+
+    handle_by("nvmlDeviceGetHandleByUUID", device, |gpu| gpu.uuid == uuid)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetHandleBySerial(
+    serial: *const c_char,
+    device: *mut nvmlDevice_t,
+) -> nvmlReturn_t {
+    let serial = match read_str(serial) {
+        Ok(serial) => serial,
+        Err(code) => return code,
+    };
+
+    handle_by("nvmlDeviceGetHandleBySerial", device, |gpu| {
+        gpu.serial == serial
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceGetHandleByPciBusId_v2(
+    bus_id: *const c_char,
+    device: *mut nvmlDevice_t,
+) -> nvmlReturn_t {
+    let bus_id = match read_str(bus_id) {
+        Ok(bus_id) => bus_id,
+        Err(code) => return code,
+    };
+
+    handle_by("nvmlDeviceGetHandleByPciBusId_v2", device, |gpu| {
+        gpu.bus_id().eq_ignore_ascii_case(bus_id)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn nvmlDeviceOnSameBoard(
+    device1: nvmlDevice_t,
+    device2: nvmlDevice_t,
+    on_same_board: *mut c_int,
+) -> nvmlReturn_t {
+    call(|state| {
+        let a = state.index_of(device1)?;
+        let b = state.index_of(device2)?;
+        let fixture = state.fixture_for("nvmlDeviceOnSameBoard", Some(a))?;
+        let same = fixture.gpus[a as usize].board_id == fixture.gpus[b as usize].board_id;
+
+        write(on_same_board, same as c_int)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::ffi::CString;
+    use std::ptr;
+
+    // The state is global, so everything touching it lives in one test.
+    #[test]
+    fn lifecycle() {
+        unsafe {
+            let json = CString::new(r#"{ "gpus": [{}, {}] }"#).unwrap();
+            assert_eq!(nvmlFakeLoadFixture(json.as_ptr()), 0);
+
+            let mut count = 0;
+            assert_eq!(
+                nvmlDeviceGetCount_v2(&mut count),
+                nvmlReturn_enum_NVML_ERROR_UNINITIALIZED
+            );
+
+            assert_eq!(nvmlInit_v2(), 0);
+            assert_eq!(nvmlDeviceGetCount_v2(&mut count), 0);
+            assert_eq!(count, 2);
+
+            let mut device = ptr::null_mut();
+            assert_eq!(nvmlDeviceGetHandleByIndex_v2(1, &mut device), 0);
+            assert_eq!(
+                nvmlDeviceGetHandleByIndex_v2(2, &mut device),
+                nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT
+            );
+
+            let mut buf = [0; 4];
+            assert_eq!(
+                nvmlSystemGetDriverVersion(buf.as_mut_ptr(), buf.len() as c_uint),
+                nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE
+            );
+
+            assert_eq!(nvmlShutdown(), 0);
+            assert_eq!(nvmlShutdown(), nvmlReturn_enum_NVML_ERROR_UNINITIALIZED);
+        }
+    }
+}
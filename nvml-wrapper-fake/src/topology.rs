@@ -0,0 +1,491 @@
+/*!
+How the GPUs are wired to each other and to the CPUs: NUMA nodes, PCIe host
+bridges and switches, and NvLinks (direct or through NVSwitches).
+
+Every topology query (common ancestor, nearest GPUs, the GPU set of a CPU, P2P
+status, CPU and memory affinity, NUMA node and NvLink state / remote end) is
+answered from this one model, so the answers agree with each other.
+
+A fixture describes it under `topology`, referring to other elements by their
+index in the corresponding list and to GPUs by their index in `gpus`:
+
+```json
+{
+    "gpus": [{}, {}, {}],
+    "topology": {
+        "numa_nodes": [{ "cpus": { "start": 0, "end": 16 } }],
+        "host_bridges": [{ "numa_node": 0 }],
+        "pcie_switches": [{ "upstream": { "host_bridge": 0 } }],
+        "gpus": [
+            { "upstream": { "switch": 0 } },
+            { "upstream": { "switch": 0 } },
+            { "upstream": { "host_bridge": 0 } }
+        ],
+        "nvlinks": [{ "gpus": [0, 1], "links": 4 }]
+    }
+}
+```
+
+The same can be built in Rust, and [`Topology::dgx_8x_nvswitch()`] and
+[`Topology::dual_socket_4x_pcie()`] model common server shapes:
+
+```
+use nvidia_ml_fake::topology::Topology;
+
+let mut topology = Topology::new();
+let node = topology.numa_node(0..16);
+let bridge = topology.host_bridge(node);
+let switch = topology.pcie_switch(bridge);
+let a = topology.gpu(switch);
+let b = topology.gpu(switch);
+topology.nvlink(a, b, 4);
+
+let fixture = topology.into_fixture();
+assert_eq!(fixture.gpus.len(), 2);
+```
+
+GPUs the model doesn't cover (and every GPU, if a fixture has no topology)
+get `NVML_ERROR_NOT_SUPPORTED` from topology queries.
+*/
+
+use crate::ffi::bindings::*;
+use crate::fixture::{Fixture, Gpu};
+use serde_derive::{Deserialize, Serialize};
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Topology {
+    pub numa_nodes: Vec<NumaNode>,
+    pub host_bridges: Vec<HostBridge>,
+    pub pcie_switches: Vec<PcieSwitch>,
+    /// Where each GPU is attached, by GPU index.
+    pub gpus: Vec<GpuSlot>,
+    /// Direct GPU to GPU NvLinks.
+    pub nvlinks: Vec<NvLinkBundle>,
+    pub nvswitches: Vec<NvSwitch>,
+    /// The NvLink version every link reports.
+    pub nvlink_version: u32,
+}
+
+impl Default for Topology {
+    fn default() -> Self {
+        Self {
+            numa_nodes: vec![],
+            host_bridges: vec![],
+            pcie_switches: vec![],
+            gpus: vec![],
+            nvlinks: vec![],
+            nvswitches: vec![],
+            nvlink_version: 3,
+        }
+    }
+}
+
+/// A NUMA node, and the CPUs local to it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NumaNode {
+    pub cpus: Range<u32>,
+}
+
+/// A PCIe host bridge (root complex).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HostBridge {
+    pub numa_node: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PcieSwitch {
+    pub upstream: Upstream,
+}
+
+/// What a PCIe switch or GPU is plugged into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Upstream {
+    HostBridge(usize),
+    Switch(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GpuSlot {
+    pub upstream: Upstream,
+    /// GPUs with the same board share it internally (multi-GPU boards).
+    #[serde(default)]
+    pub board: Option<u32>,
+}
+
+/// `links` NvLinks between two GPUs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NvLinkBundle {
+    pub gpus: (usize, usize),
+    pub links: u32,
+}
+
+/// An NVSwitch, with `links_per_gpu` NvLinks to each of `gpus`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NvSwitch {
+    pub gpus: Vec<usize>,
+    pub links_per_gpu: u32,
+}
+
+/// What's at the other end of an NvLink.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Remote {
+    Gpu(usize),
+    NvSwitch(usize),
+}
+
+impl Topology {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+    An NVIDIA DGX A100-style system.
+
+    Two NUMA nodes of 64 CPUs, each with two host bridges. Each host bridge
+    has a PCIe switch with two GPUs behind it. Six NVSwitches connect all
+    eight GPUs to each other, with two NvLinks from every GPU to every switch.
+    */
+    pub fn dgx_8x_nvswitch() -> Self {
+        let mut topology = Self::new();
+        let mut gpus = vec![];
+
+        for cpus in [0..64, 64..128] {
+            let node = topology.numa_node(cpus);
+
+            for _ in 0..2 {
+                let bridge = topology.host_bridge(node);
+                let switch = topology.pcie_switch(bridge);
+
+                gpus.push(topology.gpu(switch));
+                gpus.push(topology.gpu(switch));
+            }
+        }
+
+        for _ in 0..6 {
+            topology.nvswitch(&gpus, 2);
+        }
+
+        topology
+    }
+
+    /**
+    A dual-socket server with four PCIe GPUs and no NvLink.
+
+    Two NUMA nodes of 32 CPUs, each with a host bridge and a PCIe switch with
+    two GPUs behind it.
+    */
+    pub fn dual_socket_4x_pcie() -> Self {
+        let mut topology = Self::new();
+
+        for cpus in [0..32, 32..64] {
+            let node = topology.numa_node(cpus);
+            let bridge = topology.host_bridge(node);
+            let switch = topology.pcie_switch(bridge);
+
+            topology.gpu(switch);
+            topology.gpu(switch);
+        }
+
+        topology
+    }
+
+    /// Add a NUMA node with the given CPUs, returning its index.
+    pub fn numa_node(&mut self, cpus: Range<u32>) -> usize {
+        self.numa_nodes.push(NumaNode { cpus });
+        self.numa_nodes.len() - 1
+    }
+
+    /// Add a host bridge on the given NUMA node.
+    pub fn host_bridge(&mut self, numa_node: usize) -> Upstream {
+        self.host_bridges.push(HostBridge { numa_node });
+        Upstream::HostBridge(self.host_bridges.len() - 1)
+    }
+
+    /// Add a PCIe switch plugged into `upstream`.
+    pub fn pcie_switch(&mut self, upstream: Upstream) -> Upstream {
+        self.pcie_switches.push(PcieSwitch { upstream });
+        Upstream::Switch(self.pcie_switches.len() - 1)
+    }
+
+    /// Add a GPU plugged into `upstream`, returning its index.
+    pub fn gpu(&mut self, upstream: Upstream) -> usize {
+        self.gpus.push(GpuSlot {
+            upstream,
+            board: None,
+        });
+        self.gpus.len() - 1
+    }
+
+    /// Connect two GPUs with `links` NvLinks.
+    pub fn nvlink(&mut self, a: usize, b: usize, links: u32) -> &mut Self {
+        self.nvlinks.push(NvLinkBundle {
+            gpus: (a, b),
+            links,
+        });
+        self
+    }
+
+    /// Add an NVSwitch with `links_per_gpu` NvLinks to each of `gpus`,
+    /// returning its index.
+    pub fn nvswitch(&mut self, gpus: &[usize], links_per_gpu: u32) -> usize {
+        self.nvswitches.push(NvSwitch {
+            gpus: gpus.to_vec(),
+            links_per_gpu,
+        });
+        self.nvswitches.len() - 1
+    }
+
+    /// A fixture with a default GPU for each GPU in this topology.
+    pub fn into_fixture(self) -> Fixture {
+        let mut fixture = Fixture {
+            gpus: vec![Gpu::default(); self.gpus.len()],
+            topology: Some(self),
+            ..Fixture::default()
+        };
+        fixture.fill_in_identities();
+
+        fixture
+    }
+
+    /// The switches above `gpu`, nearest first, and its host bridge.
+    fn path(&self, gpu: usize) -> Option<(Vec<usize>, usize)> {
+        let mut switches = vec![];
+        let mut upstream = self.gpus.get(gpu)?.upstream;
+
+        loop {
+            match upstream {
+                Upstream::HostBridge(bridge) => return Some((switches, bridge)),
+                Upstream::Switch(switch) => {
+                    // A cycle would loop forever otherwise
+                    if switches.contains(&switch) {
+                        return None;
+                    }
+
+                    switches.push(switch);
+                    upstream = self.pcie_switches.get(switch)?.upstream;
+                }
+            }
+        }
+    }
+
+    /// The NUMA node `gpu` is attached to.
+    pub fn numa_node_of(&self, gpu: usize) -> Option<usize> {
+        let (_, bridge) = self.path(gpu)?;
+
+        self.host_bridges.get(bridge).map(|b| b.numa_node)
+    }
+
+    /// The closest point two GPUs share, as an `nvmlGpuTopologyLevel_t`.
+    pub fn common_ancestor(&self, a: usize, b: usize) -> Option<nvmlGpuTopologyLevel_t> {
+        let (a_switches, a_bridge) = self.path(a)?;
+        let (b_switches, b_bridge) = self.path(b)?;
+
+        let level = if a == b
+            || (self.gpus[a].board.is_some() && self.gpus[a].board == self.gpus[b].board)
+        {
+            nvmlGpuLevel_enum_NVML_TOPOLOGY_INTERNAL
+        } else if !a_switches.is_empty() && a_switches.first() == b_switches.first() {
+            nvmlGpuLevel_enum_NVML_TOPOLOGY_SINGLE
+        } else if a_switches.iter().any(|s| b_switches.contains(s)) {
+            nvmlGpuLevel_enum_NVML_TOPOLOGY_MULTIPLE
+        } else if a_bridge == b_bridge {
+            nvmlGpuLevel_enum_NVML_TOPOLOGY_HOSTBRIDGE
+        } else if self.numa_node_of(a) == self.numa_node_of(b) {
+            nvmlGpuLevel_enum_NVML_TOPOLOGY_NODE
+        } else {
+            nvmlGpuLevel_enum_NVML_TOPOLOGY_SYSTEM
+        };
+
+        Some(level)
+    }
+
+    /// The other GPUs whose common ancestor with `gpu` is at `level` or closer.
+    pub fn nearest_gpus(&self, gpu: usize, level: nvmlGpuTopologyLevel_t) -> Option<Vec<usize>> {
+        self.path(gpu)?;
+
+        Some(
+            (0..self.gpus.len())
+                .filter(|&other| other != gpu)
+                .filter(|&other| matches!(self.common_ancestor(gpu, other), Some(l) if l <= level))
+                .collect(),
+        )
+    }
+
+    /// The GPUs attached to the NUMA node `cpu` belongs to.
+    pub fn gpus_near_cpu(&self, cpu: u32) -> Option<Vec<usize>> {
+        let node = self.numa_nodes.iter().position(|n| n.cpus.contains(&cpu))?;
+
+        Some(
+            (0..self.gpus.len())
+                .filter(|&gpu| self.numa_node_of(gpu) == Some(node))
+                .collect(),
+        )
+    }
+
+    /// What each of `gpu`'s NvLinks is connected to, by link number: direct
+    /// links first, in the order they were added, then NVSwitch links.
+    pub fn links(&self, gpu: usize) -> Vec<Remote> {
+        let mut links = vec![];
+
+        for bundle in &self.nvlinks {
+            let remote = match bundle.gpus {
+                (a, b) if a == gpu => b,
+                (a, b) if b == gpu => a,
+                _ => continue,
+            };
+
+            links.extend((0..bundle.links).map(|_| Remote::Gpu(remote)));
+        }
+
+        for (i, switch) in self.nvswitches.iter().enumerate() {
+            if switch.gpus.contains(&gpu) {
+                links.extend((0..switch.links_per_gpu).map(|_| Remote::NvSwitch(i)));
+            }
+        }
+
+        links
+    }
+
+    /// Whether two GPUs can reach each other over NvLink.
+    pub fn nvlinked(&self, a: usize, b: usize) -> bool {
+        self.nvlinks
+            .iter()
+            .any(|l| l.gpus == (a, b) || l.gpus == (b, a))
+            || self
+                .nvswitches
+                .iter()
+                .any(|s| s.gpus.contains(&a) && s.gpus.contains(&b))
+    }
+
+    /// The `nvmlGpuP2PStatus_t` for the `nvmlGpuP2PCapsIndex_t` `caps` between
+    /// two GPUs.
+    ///
+    /// NvLinked GPUs can do everything; otherwise, PCIe P2P works below a
+    /// common host bridge but not across host bridges.
+    pub fn p2p_status(
+        &self,
+        a: usize,
+        b: usize,
+        caps: nvmlGpuP2PCapsIndex_t,
+    ) -> Option<nvmlGpuP2PStatus_t> {
+        let level = self.common_ancestor(a, b)?;
+        let nvlinked = self.nvlinked(a, b);
+
+        let status = match caps {
+            nvmlGpuP2PCapsIndex_enum_NVML_P2P_CAPS_INDEX_NVLINK if nvlinked => {
+                nvmlGpuP2PStatus_enum_NVML_P2P_STATUS_OK
+            }
+            nvmlGpuP2PCapsIndex_enum_NVML_P2P_CAPS_INDEX_NVLINK => {
+                nvmlGpuP2PStatus_enum_NVML_P2P_STATUS_NOT_SUPPORTED
+            }
+            nvmlGpuP2PCapsIndex_enum_NVML_P2P_CAPS_INDEX_READ
+            | nvmlGpuP2PCapsIndex_enum_NVML_P2P_CAPS_INDEX_WRITE
+            | nvmlGpuP2PCapsIndex_enum_NVML_P2P_CAPS_INDEX_ATOMICS
+            | nvmlGpuP2PCapsIndex_enum_NVML_P2P_CAPS_INDEX_PROP => {
+                if nvlinked || level <= nvmlGpuLevel_enum_NVML_TOPOLOGY_HOSTBRIDGE {
+                    nvmlGpuP2PStatus_enum_NVML_P2P_STATUS_OK
+                } else {
+                    nvmlGpuP2PStatus_enum_NVML_P2P_STATUS_IOH_TOPOLOGY_NOT_SUPPORTED
+                }
+            }
+            _ => nvmlGpuP2PStatus_enum_NVML_P2P_STATUS_UNKNOWN,
+        };
+
+        Some(status)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn dgx() {
+        let t = Topology::dgx_8x_nvswitch();
+
+        assert_eq!(t.gpus.len(), 8);
+        assert_eq!(
+            t.common_ancestor(0, 1),
+            Some(nvmlGpuLevel_enum_NVML_TOPOLOGY_SINGLE)
+        );
+        assert_eq!(
+            t.common_ancestor(0, 2),
+            Some(nvmlGpuLevel_enum_NVML_TOPOLOGY_NODE)
+        );
+        assert_eq!(
+            t.common_ancestor(0, 7),
+            Some(nvmlGpuLevel_enum_NVML_TOPOLOGY_SYSTEM)
+        );
+        assert_eq!(t.links(3).len(), 12);
+        assert_eq!(t.gpus_near_cpu(70), Some(vec![4, 5, 6, 7]));
+        assert_eq!(
+            t.p2p_status(0, 7, nvmlGpuP2PCapsIndex_enum_NVML_P2P_CAPS_INDEX_NVLINK),
+            Some(nvmlGpuP2PStatus_enum_NVML_P2P_STATUS_OK)
+        );
+    }
+
+    #[test]
+    fn dual_socket() {
+        let t = Topology::dual_socket_4x_pcie();
+
+        assert_eq!(
+            t.nearest_gpus(0, nvmlGpuLevel_enum_NVML_TOPOLOGY_NODE),
+            Some(vec![1])
+        );
+        assert_eq!(
+            t.nearest_gpus(0, nvmlGpuLevel_enum_NVML_TOPOLOGY_SYSTEM),
+            Some(vec![1, 2, 3])
+        );
+        assert!(t.links(0).is_empty());
+        assert_eq!(
+            t.p2p_status(0, 1, nvmlGpuP2PCapsIndex_enum_NVML_P2P_CAPS_INDEX_READ),
+            Some(nvmlGpuP2PStatus_enum_NVML_P2P_STATUS_OK)
+        );
+        assert_eq!(
+            t.p2p_status(0, 2, nvmlGpuP2PCapsIndex_enum_NVML_P2P_CAPS_INDEX_READ),
+            Some(nvmlGpuP2PStatus_enum_NVML_P2P_STATUS_IOH_TOPOLOGY_NOT_SUPPORTED)
+        );
+    }
+
+    #[test]
+    fn nested_switches_and_boards() {
+        let mut t = Topology::new();
+        let node = t.numa_node(0..8);
+        let bridge = t.host_bridge(node);
+        let root = t.pcie_switch(bridge);
+        let left = t.pcie_switch(root);
+        let right = t.pcie_switch(root);
+        let a = t.gpu(left);
+        let b = t.gpu(left);
+        let c = t.gpu(right);
+        t.gpus[a].board = Some(0);
+        t.gpus[b].board = Some(0);
+
+        assert_eq!(
+            t.common_ancestor(a, b),
+            Some(nvmlGpuLevel_enum_NVML_TOPOLOGY_INTERNAL)
+        );
+        assert_eq!(
+            t.common_ancestor(a, c),
+            Some(nvmlGpuLevel_enum_NVML_TOPOLOGY_MULTIPLE)
+        );
+        assert_eq!(t.common_ancestor(a, 3), None);
+    }
+
+    #[test]
+    fn fixture_round_trip() {
+        let fixture = Topology::dgx_8x_nvswitch().into_fixture();
+        let json = serde_json::to_string(&fixture).unwrap();
+
+        assert_eq!(Fixture::from_json(&json).unwrap(), fixture);
+    }
+}
@@ -0,0 +1,487 @@
+//! Drives the built fake library through `nvml-wrapper`, the way a real
+//! consumer would load it.
+
+use nvidia_ml_fake::topology::Topology;
+use nvml_wrapper::bitmasks::device::{ClocksEventReasons, ProcessTypes, ThrottleReasons};
+use nvml_wrapper::bitmasks::event::EventTypes;
+use nvml_wrapper::conformance::{Conformance, Outcome, Target};
+use nvml_wrapper::enum_wrappers::device::{
+    Clock, P2pCapabilitiesIndex, P2pStatus, TemperatureSensor, TopologyLevel,
+};
+use nvml_wrapper::enums::device::UsedGpuMemory;
+use nvml_wrapper::enums::event::XidError;
+use nvml_wrapper::error::NvmlError;
+use nvml_wrapper::fault::{Fault, Faults};
+use nvml_wrapper::structs::device::UtilizationInfo;
+use nvml_wrapper::{Device, Nvml};
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+use std::ffi::CString;
+use std::fs;
+use std::os::raw::c_char;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A private copy of the fake library with its own fixture loaded.
+///
+/// The model is per loaded library, so each test copies the file to get an
+/// instance no other test can touch.
+struct Fake {
+    path: PathBuf,
+    _lib: libloading::Library,
+}
+
+impl Fake {
+    fn new(fixture: &str) -> Self {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let name = format!("{}nvidia_ml_fake{}", DLL_PREFIX, DLL_SUFFIX);
+        let built = std::env::current_exe()
+            .unwrap()
+            .parent()
+            .and_then(|deps| deps.parent())
+            .unwrap()
+            .join(&name);
+
+        let path = std::env::temp_dir().join(format!(
+            "{}-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::SeqCst),
+            name
+        ));
+        fs::copy(&built, &path).expect("the fake library should be built");
+
+        unsafe {
+            let lib = libloading::Library::new(&path).unwrap();
+            let load: libloading::Symbol<unsafe extern "C" fn(*const c_char) -> u32> =
+                lib.get(b"nvmlFakeLoadFixture").unwrap();
+            let json = CString::new(fixture).unwrap();
+            assert_eq!(load(json.as_ptr()), 0, "invalid fixture");
+
+            Self { path, _lib: lib }
+        }
+    }
+
+    fn init(&self) -> Result<Nvml, NvmlError> {
+        Nvml::builder().lib_path(self.path.as_os_str()).init()
+    }
+}
+
+impl Drop for Fake {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[test]
+fn default_fixture() -> Result<(), NvmlError> {
+    let fake = Fake::new("{}");
+    let nvml = fake.init()?;
+
+    assert_eq!(nvml.device_count()?, 1);
+    assert_eq!(nvml.sys_driver_version()?, "550.54.15");
+
+    let device = nvml.device_by_index(0)?;
+    assert_eq!(device.name()?, "NVIDIA Fake GPU");
+    assert_eq!(device.temperature(TemperatureSensor::Gpu)?, 45);
+    assert_eq!(device.clock_info(Clock::SM)?, 1410);
+    assert!(device.running_compute_processes()?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn gpus_and_properties() -> Result<(), NvmlError> {
+    let fake = Fake::new(
+        r#"{
+            "gpus": [
+                { "name": "Alpha", "memory": { "total": 1000, "reserved": 100, "used": 300 } },
+                {
+                    "name": "Beta",
+                    "fans": [40, 60],
+                    "processes": [
+                        { "pid": 42, "name": "trainer", "kind": "compute", "used_memory": 2048 },
+                        { "pid": 43, "kind": "graphics" }
+                    ]
+                }
+            ]
+        }"#,
+    );
+    let nvml = fake.init()?;
+
+    assert_eq!(nvml.device_count()?, 2);
+
+    let alpha = nvml.device_by_index(0)?;
+    let memory = alpha.memory_info()?;
+    assert_eq!(memory.total, 1000);
+    assert_eq!(memory.free, 600);
+
+    let beta = nvml.device_by_uuid(nvml.device_by_index(1)?.uuid()?)?;
+    assert_eq!(beta.name()?, "Beta");
+    assert_eq!(beta.index()?, 1);
+    assert_eq!(beta.fan_speed(1)?, 60);
+    assert_eq!(
+        nvml.device_by_pci_bus_id(beta.pci_info()?.bus_id)?.name()?,
+        "Beta"
+    );
+
+    let processes = beta.running_compute_processes()?;
+    assert_eq!(processes.len(), 1);
+    assert_eq!(processes[0].pid, 42);
+    assert_eq!(processes[0].used_gpu_memory, UsedGpuMemory::Used(2048));
+    assert_eq!(processes[0].gpu_instance_id, None);
+    assert_eq!(nvml.sys_process_name(42, 64)?, "trainer");
+
+    Ok(())
+}
+
+#[test]
+fn gpu_processes_are_merged_per_pid() -> Result<(), NvmlError> {
+    let fake = Fake::new(
+        r#"{
+            "gpus": [{
+                "processes": [
+                    {
+                        "pid": 42, "kind": "compute", "used_memory": 2048,
+                        "utilization": { "timestamp": 100, "sm": 80, "jpeg": 5 }
+                    },
+                    { "pid": 42, "kind": "graphics", "used_memory": 2048 },
+                    { "pid": 43, "kind": "mps", "used_memory": 1024 }
+                ]
+            }]
+        }"#,
+    );
+    let nvml = fake.init()?;
+    let device = nvml.device_by_index(0)?;
+
+    let processes = device.gpu_processes()?;
+    assert_eq!(processes.len(), 2);
+
+    assert_eq!(processes[0].pid, 42);
+    assert_eq!(
+        processes[0].types,
+        ProcessTypes::COMPUTE | ProcessTypes::GRAPHICS
+    );
+    assert_eq!(processes[0].used_gpu_memory, UsedGpuMemory::Used(2048));
+    assert_eq!(processes[0].gpu_instance_id, None);
+    let utilization = processes[0].utilization.as_ref().unwrap();
+    assert_eq!((utilization.sm_util, utilization.jpg_util), (80, 5));
+
+    assert_eq!(processes[1].pid, 43);
+    assert_eq!(processes[1].types, ProcessTypes::MPS);
+    assert_eq!(processes[1].utilization, None);
+
+    Ok(())
+}
+
+#[test]
+fn engine_utilization_skips_missing_engines() -> Result<(), NvmlError> {
+    let fake = Fake::new(
+        r#"{
+            "gpus": [{
+                "utilization": {
+                    "gpu": 60, "memory": 30, "encoder": 10, "decoder": 20, "jpeg": 40,
+                    "sampling_period": 100000
+                }
+            }],
+            "errors": [
+                { "function": "nvmlDeviceGetOfaUtilization", "code": "NVML_ERROR_NOT_SUPPORTED" }
+            ]
+        }"#,
+    );
+    let nvml = fake.init()?;
+    let device = nvml.device_by_index(0)?;
+
+    let engines = device.engine_utilization()?;
+    assert_eq!((engines.gpu, engines.memory), (60, 30));
+    assert_eq!(
+        engines.jpeg,
+        Some(UtilizationInfo {
+            utilization: 40,
+            sampling_period: 100_000,
+        })
+    );
+    assert_eq!(engines.encoder.map(|e| e.utilization), Some(10));
+    assert_eq!(engines.decoder.map(|d| d.utilization), Some(20));
+    assert_eq!(engines.ofa, None);
+
+    Ok(())
+}
+
+#[test]
+fn injected_errors() -> Result<(), NvmlError> {
+    let fake = Fake::new(
+        r#"{
+            "gpus": [{}, {}],
+            "errors": [
+                { "function": "nvmlDeviceGetPowerUsage", "device": 1, "code": "NVML_ERROR_GPU_IS_LOST" },
+                { "function": "nvmlDeviceGetBrand", "code": "NVML_ERROR_NOT_SUPPORTED" }
+            ]
+        }"#,
+    );
+    let nvml = fake.init()?;
+    let first = nvml.device_by_index(0)?;
+    let second = nvml.device_by_index(1)?;
+
+    assert!(first.power_usage().is_ok());
+    assert!(matches!(second.power_usage(), Err(NvmlError::GpuLost)));
+    assert!(matches!(first.brand(), Err(NvmlError::NotSupported)));
+
+    Ok(())
+}
+
+#[test]
+fn unexported_functions_fail_to_load() -> Result<(), NvmlError> {
+    let fake = Fake::new("{}");
+    let nvml = fake.init()?;
+
+    assert!(matches!(
+        nvml.device_by_index(0)?.fan_speed_rpm(0),
+        Err(NvmlError::FailedToLoadSymbol(_))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn clocks_event_reasons_fall_back_to_throttle_reasons() -> Result<(), NvmlError> {
+    let fake = Fake::new(r#"{ "gpus": [{ "throttle_reasons": 48 }] }"#);
+    let expected = ClocksEventReasons::SYNC_BOOST | ClocksEventReasons::SW_THERMAL_SLOWDOWN;
+
+    let nvml = fake.init()?;
+    let device = nvml.device_by_index(0)?;
+    assert_eq!(device.current_clocks_event_reasons()?, expected);
+    assert_eq!(
+        device.supported_clocks_event_reasons_strict()?,
+        ClocksEventReasons::all()
+    );
+
+    // An older driver, without the clocks event reasons functions
+    let faults = Faults::new()
+        .with(Fault::error(
+            "nvmlDeviceGetCurrentClocksEventReasons",
+            NvmlError::FailedToLoadSymbol(String::new()),
+        ))
+        .with(Fault::error(
+            "nvmlDeviceGetSupportedClocksEventReasons",
+            NvmlError::FailedToLoadSymbol(String::new()),
+        ));
+    let nvml = Nvml::builder()
+        .lib_path(fake.path.as_os_str())
+        .faults(faults)
+        .init()?;
+    let device = nvml.device_by_index(0)?;
+    assert_eq!(device.current_clocks_event_reasons()?, expected);
+    assert_eq!(
+        ThrottleReasons::from(device.supported_clocks_event_reasons()?),
+        ThrottleReasons::all()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn events() -> Result<(), NvmlError> {
+    let fake = Fake::new(
+        r#"{
+            "gpus": [{
+                "events": [
+                    { "event_type": 8, "data": 79 },
+                    { "event_type": 1 }
+                ]
+            }]
+        }"#,
+    );
+    let nvml = fake.init()?;
+    let device = nvml.device_by_index(0)?;
+
+    let set = device
+        .register_events(EventTypes::CRITICAL_XID_ERROR, nvml.create_event_set()?)
+        .map_err(|e| e.error)?;
+
+    let event = set.wait(0)?;
+    assert_eq!(event.event_type, EventTypes::CRITICAL_XID_ERROR);
+    assert_eq!(event.event_data, Some(XidError::Value(79)));
+    assert!(matches!(set.wait(0), Err(NvmlError::Timeout)));
+
+    Ok(())
+}
+
+#[test]
+fn init_count_matches_nvml() -> Result<(), NvmlError> {
+    let fake = Fake::new("{}");
+
+    let first = fake.init()?;
+    let second = fake.init()?;
+    first.shutdown()?;
+
+    // Still initialized through `second`
+    assert_eq!(second.device_count()?, 1);
+
+    Ok(())
+}
+
+#[test]
+fn record_and_replay() -> Result<(), NvmlError> {
+    let fake = Fake::new(
+        r#"{
+            "gpus": [{
+                "name": "Recorded",
+                "processes": [{ "pid": 7, "kind": "compute", "used_memory": 512 }]
+            }]
+        }"#,
+    );
+    let recording = fake.path.with_extension("jsonl");
+
+    let snapshot = |nvml: &Nvml| -> Result<_, NvmlError> {
+        let device = nvml.device_by_index(0)?;
+        let processes = device.running_compute_processes()?;
+
+        Ok((
+            nvml.sys_driver_version()?,
+            device.name()?,
+            device.memory_info()?.total,
+            processes.iter().map(|p| p.pid).collect::<Vec<_>>(),
+            device.fan_speed_rpm(0).is_err(),
+        ))
+    };
+
+    let nvml = Nvml::builder()
+        .lib_path(fake.path.as_os_str())
+        .record_to(&recording)
+        .init()?;
+    let recorded = snapshot(&nvml)?;
+    nvml.shutdown()?;
+    drop(fake);
+
+    // The fake library is gone; everything has to come from the recording
+    let nvml = Nvml::builder().replay_from(&recording).init()?;
+    let replayed = snapshot(&nvml)?;
+    nvml.shutdown()?;
+    let _ = fs::remove_file(&recording);
+
+    assert_eq!(replayed, recorded);
+    assert_eq!(recorded.1, "Recorded");
+    assert_eq!(recorded.3, vec![7]);
+
+    Ok(())
+}
+
+#[test]
+fn conformance() -> Result<(), NvmlError> {
+    let fake = Fake::new(
+        r#"{
+            "gpus": [
+                { "fans": [40, 60] },
+                {}
+            ],
+            "errors": [
+                { "function": "nvmlDeviceGetBrand", "code": "NVML_ERROR_NOT_SUPPORTED" },
+                { "function": "nvmlDeviceGetPowerUsage", "device": 1, "code": "NVML_ERROR_GPU_IS_LOST" }
+            ]
+        }"#,
+    );
+    let nvml = fake.init()?;
+    let report = Conformance::new(&nvml).reps(2).run();
+
+    assert_eq!(report.driver_version.as_deref(), Some("550.54.15"));
+    assert_eq!(report.devices.len(), 2);
+    assert_eq!(report.devices[0].fans, Some(2));
+
+    let outcome = |target, method| &report.check(target, method).unwrap().outcome;
+    assert_eq!(
+        outcome(Target::Device(0), "temperature(Gpu)"),
+        &Outcome::Supported
+    );
+    assert_eq!(outcome(Target::Device(1), "brand"), &Outcome::NotSupported);
+    assert_eq!(
+        outcome(Target::Device(0), "fan_speed_rpm(1)"),
+        &Outcome::Unavailable
+    );
+
+    // The only failure is the one the fixture asks for
+    let failures: Vec<_> = report.failures().collect();
+    assert_eq!(failures.len(), 1, "{:#?}", failures);
+    assert_eq!(failures[0].target, Target::Device(1));
+    assert_eq!(failures[0].method, "power_usage");
+
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn topology() -> Result<(), NvmlError> {
+    let fixture = Topology::dual_socket_4x_pcie().into_fixture();
+    let fake = Fake::new(&serde_json::to_string(&fixture).unwrap());
+    let nvml = fake.init()?;
+
+    let gpus = (0..4)
+        .map(|i| nvml.device_by_index(i))
+        .collect::<Result<Vec<_>, _>>()?;
+    let indices = |devices: Vec<Device>| -> Result<Vec<u32>, NvmlError> {
+        devices.iter().map(Device::index).collect()
+    };
+
+    assert_eq!(
+        gpus[0].topology_common_ancestor(nvml.device_by_index(1)?)?,
+        TopologyLevel::Single
+    );
+    assert_eq!(
+        gpus[0].topology_common_ancestor(nvml.device_by_index(2)?)?,
+        TopologyLevel::System
+    );
+    assert_eq!(
+        indices(gpus[1].topology_nearest_gpus(TopologyLevel::Node)?)?,
+        [0]
+    );
+    assert_eq!(indices(nvml.topology_gpu_set(40)?)?, [2, 3]);
+    assert_eq!(gpus[2].numa_node_id()?, 1);
+    assert_eq!(gpus[2].cpu_affinity(1)?, [0xffff_ffff_0000_0000]);
+
+    assert_eq!(
+        gpus[0].p2p_status(&gpus[1], P2pCapabilitiesIndex::Read)?,
+        P2pStatus::Ok
+    );
+    assert_eq!(
+        gpus[0].p2p_status(&gpus[3], P2pCapabilitiesIndex::Read)?,
+        P2pStatus::IohTopologyNotSupported
+    );
+    assert_eq!(
+        gpus[0].p2p_status(&gpus[1], P2pCapabilitiesIndex::NvLink)?,
+        P2pStatus::NotSupported
+    );
+    assert!(!gpus[0].link_wrapper_for(0).is_active()?);
+
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn nvswitch_topology() -> Result<(), NvmlError> {
+    let fixture = Topology::dgx_8x_nvswitch().into_fixture();
+    let fake = Fake::new(&serde_json::to_string(&fixture).unwrap());
+    let nvml = fake.init()?;
+
+    let first = nvml.device_by_index(0)?;
+    let last = nvml.device_by_index(7)?;
+
+    assert_eq!(
+        first.topology_common_ancestor(nvml.device_by_index(2)?)?,
+        TopologyLevel::Node
+    );
+    assert_eq!(first.topology_nearest_gpus(TopologyLevel::System)?.len(), 7);
+    assert_eq!(
+        first.p2p_status(&last, P2pCapabilitiesIndex::NvLink)?,
+        P2pStatus::Ok
+    );
+
+    let link = first.link_wrapper_for(11);
+    assert!(link.is_active()?);
+    assert_eq!(link.remote_pci_info()?.pci_device_id, 0x1af110de);
+    assert!(!first.link_wrapper_for(12).is_active()?);
+    assert!(matches!(
+        first.link_wrapper_for(12).remote_pci_info(),
+        Err(NvmlError::NotSupported)
+    ));
+
+    Ok(())
+}
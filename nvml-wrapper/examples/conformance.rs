@@ -0,0 +1,36 @@
+//! Runs the conformance checks against the system NVML (or the library at the
+//! path given as the first argument) and writes the report to stdout as JSON.
+//!
+//! Exits with a non-zero status if any check failed.
+
+use nvml_wrapper::conformance::Conformance;
+use nvml_wrapper::Nvml;
+use std::env;
+use std::io;
+use std::process;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let lib_path = env::args_os().nth(1);
+    let mut builder = Nvml::builder();
+
+    if let Some(path) = &lib_path {
+        builder.lib_path(path);
+    }
+
+    let nvml = builder.init()?;
+    let report = Conformance::new(&nvml).reps(3).run();
+
+    report.write_json(io::stdout().lock())?;
+
+    let summary = report.summary();
+    eprintln!("{:?}", summary);
+    for check in report.failures() {
+        eprintln!("{}", check);
+    }
+
+    if summary.failed > 0 {
+        process::exit(1);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,169 @@
+/*!
+Classifying the raw arguments of NVML functions, for backends that wrap another
+backend and need to look at the arguments passing through.
+*/
+
+use std::any::TypeId;
+use std::mem;
+use std::os::raw;
+
+/// Output arrays, as `(function, array argument, length argument, how the
+/// length is passed)`.
+#[rustfmt::skip]
+pub(crate) const ARRAYS: &[(&str, &str, &str, LenKind)] = {
+    use LenKind::*;
+
+    &[
+        ("nvmlSystemGetHicVersion", "hwbcEntries", "hwbcCount", Ptr),
+        ("nvmlSystemGetTopologyGpuSet", "deviceArray", "count", Ptr),
+        ("nvmlUnitGetDevices", "devices", "deviceCount", Ptr),
+        ("nvmlDeviceGetMemoryAffinity", "nodeSet", "nodeSetSize", Value),
+        ("nvmlDeviceGetCpuAffinityWithinScope", "cpuSet", "cpuSetSize", Value),
+        ("nvmlDeviceGetCpuAffinity", "cpuSet", "cpuSetSize", Value),
+        ("nvmlDeviceGetTopologyNearestGpus", "deviceArray", "count", Ptr),
+        ("nvmlDeviceGetSupportedMemoryClocks", "clocksMHz", "count", Ptr),
+        ("nvmlDeviceGetSupportedGraphicsClocks", "clocksMHz", "count", Ptr),
+        ("nvmlDeviceGetSupportedPerformanceStates", "pstates", "size", Value),
+        ("nvmlDeviceGetEncoderSessions", "sessionInfos", "sessionCount", Ptr),
+        ("nvmlDeviceGetFBCSessions", "sessionInfo", "sessionCount", Ptr),
+        ("nvmlDeviceGetComputeRunningProcesses_v3", "infos", "infoCount", Ptr),
+        ("nvmlDeviceGetGraphicsRunningProcesses_v3", "infos", "infoCount", Ptr),
+        ("nvmlDeviceGetMPSComputeRunningProcesses_v3", "infos", "infoCount", Ptr),
+        ("nvmlDeviceGetComputeRunningProcesses", "infos", "infoCount", Ptr),
+        ("nvmlDeviceGetGraphicsRunningProcesses", "infos", "infoCount", Ptr),
+        ("nvmlDeviceGetMPSComputeRunningProcesses", "infos", "infoCount", Ptr),
+        ("nvmlDeviceGetComputeRunningProcesses_v2", "infos", "infoCount", Ptr),
+        ("nvmlDeviceGetGraphicsRunningProcesses_v2", "infos", "infoCount", Ptr),
+        ("nvmlDeviceGetMPSComputeRunningProcesses_v2", "infos", "infoCount", Ptr),
+        ("nvmlDeviceGetSamples", "samples", "sampleCount", Ptr),
+        ("nvmlDeviceGetAccountingPids", "pids", "count", Ptr),
+        ("nvmlDeviceGetRetiredPages", "addresses", "pageCount", Ptr),
+        ("nvmlDeviceGetRetiredPages_v2", "addresses", "pageCount", Ptr),
+        ("nvmlDeviceGetRetiredPages_v2", "timestamps", "pageCount", Ptr),
+        ("nvmlDeviceGetProcessUtilization", "utilization", "processSamplesCount", Ptr),
+        ("nvmlDeviceGetFieldValues", "values", "valuesCount", Value),
+        ("nvmlDeviceClearFieldValues", "values", "valuesCount", Value),
+        ("nvmlDeviceGetSupportedVgpus", "vgpuTypeIds", "vgpuCount", Ptr),
+        ("nvmlDeviceGetCreatableVgpus", "vgpuTypeIds", "vgpuCount", Ptr),
+        ("nvmlDeviceGetActiveVgpus", "vgpuInstances", "vgpuCount", Ptr),
+        ("nvmlVgpuInstanceGetEncoderSessions", "sessionInfo", "sessionCount", Ptr),
+        ("nvmlVgpuInstanceGetFBCSessions", "sessionInfo", "sessionCount", Ptr),
+        ("nvmlVgpuInstanceGetMetadata", "vgpuMetadata", "bufferSize", BytesPtr),
+        ("nvmlDeviceGetVgpuMetadata", "pgpuMetadata", "bufferSize", BytesPtr),
+        ("nvmlDeviceGetVgpuUtilization", "utilizationSamples", "vgpuInstanceSamplesCount", Ptr),
+        ("nvmlDeviceGetVgpuProcessUtilization", "utilizationSamples", "vgpuProcessSamplesCount", Ptr),
+        ("nvmlVgpuInstanceGetAccountingPids", "pids", "count", Ptr),
+        ("nvmlDeviceGetGpuInstancePossiblePlacements", "placements", "count", Ptr),
+        ("nvmlDeviceGetGpuInstancePossiblePlacements_v2", "placements", "count", Ptr),
+        ("nvmlDeviceGetGpuInstances", "gpuInstances", "count", Ptr),
+        ("nvmlGpuInstanceGetComputeInstancePossiblePlacements", "placements", "count", Ptr),
+        ("nvmlGpuInstanceGetComputeInstances", "computeInstances", "count", Ptr),
+    ]
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LenKind {
+    /// The number of elements, passed by value.
+    Value,
+    /// The number of elements, passed by pointer and updated by the call.
+    Ptr,
+    /// The size in bytes, passed by pointer and updated by the call.
+    BytesPtr,
+}
+
+/// An argument, classified by its type.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Kind {
+    Int(i64),
+    Uint(u64),
+    Handle(Pointer),
+    Const(Pointer),
+    Mut(Pointer),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Pointer {
+    pub addr: *mut u8,
+    /// The size of the pointee.
+    pub size: usize,
+    /// The type of the pointee.
+    pub ty: TypeId,
+}
+
+impl Pointer {
+    fn new<T: 'static>(ptr: *mut T) -> Self {
+        Self {
+            addr: ptr as *mut u8,
+            size: mem::size_of::<T>(),
+            ty: TypeId::of::<T>(),
+        }
+    }
+
+    pub fn is<T: 'static>(&self) -> bool {
+        self.ty == TypeId::of::<T>()
+    }
+}
+
+/// An argument to an NVML function.
+pub(crate) trait RawArg {
+    fn kind(&self) -> Kind;
+}
+
+macro_rules! int_args {
+    ($($ty:ty => $kind:ident,)*) => {
+        $(
+            impl RawArg for $ty {
+                fn kind(&self) -> Kind {
+                    Kind::$kind((*self).into())
+                }
+            }
+        )*
+    };
+}
+
+int_args! {
+    raw::c_uint => Uint,
+    raw::c_ulonglong => Uint,
+    raw::c_int => Int,
+}
+
+impl<T: 'static> RawArg for *mut T {
+    fn kind(&self) -> Kind {
+        // Handles are pointers to opaque (zero-sized) structs
+        let p = Pointer::new(*self);
+
+        if p.size == 0 {
+            Kind::Handle(p)
+        } else {
+            Kind::Mut(p)
+        }
+    }
+}
+
+impl<T: 'static> RawArg for *const T {
+    fn kind(&self) -> Kind {
+        Kind::Const(Pointer::new(*self as *mut T))
+    }
+}
+
+impl Kind {
+    /// The integer this argument holds or points to, for array lengths and
+    /// buffer sizes.
+    pub unsafe fn count(&self) -> Option<usize> {
+        match *self {
+            Kind::Uint(value) => usize::try_from(value).ok(),
+            Kind::Int(value) => usize::try_from(value).ok(),
+            Kind::Mut(p) if !p.addr.is_null() && p.is::<raw::c_uint>() => {
+                Some(*(p.addr as *const raw::c_uint) as usize)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn pointer(&self) -> Option<Pointer> {
+        match *self {
+            Kind::Const(p) | Kind::Mut(p) if !p.addr.is_null() => Some(p),
+            _ => None,
+        }
+    }
+}
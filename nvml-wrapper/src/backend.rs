@@ -0,0 +1,1605 @@
+/*!
+The function table that `Nvml` calls into.
+
+Every method on `Nvml` and the handle types obtained from it (`Device`, `Unit`,
+`NvLink`, `EventSet`, `GpmSample`, ...) ends up calling one of the methods on the
+[`NvmlBackend`] trait. By default that backend is the dynamically loaded
+[`NvmlLib`] from `nvml-wrapper-sys`, but any other implementation can be handed
+to [`NvmlBuilder::init_with_backend`](crate::NvmlBuilder::init_with_backend).
+This makes it possible to run code built on top of this wrapper against an
+in-process mock on machines that don't have an NVIDIA GPU.
+
+Each trait method mirrors the NVML C function of the same name: it takes the
+same (raw) arguments and returns the raw `nvmlReturn_t` the function produced.
+The outer `Result` is used to signal that the backend is unable to provide the
+function at all; the wrapper surfaces this as `NvmlError::FailedToLoadSymbol`,
+exactly like it does for symbols missing from an older NVML library.
+
+Every method has a default implementation that does just that, so a backend
+only needs to implement the functions it cares about.
+
+```
+use nvml_wrapper::backend::NvmlBackend;
+use nvml_wrapper::error::NvmlError;
+use nvml_wrapper::Nvml;
+use nvml_wrapper_sys::bindings::*;
+use std::os::raw::c_uint;
+
+struct TwoGpus;
+
+impl NvmlBackend for TwoGpus {
+    unsafe fn nvmlInit_v2(&self) -> Result<nvmlReturn_t, NvmlError> {
+        Ok(nvmlReturn_enum_NVML_SUCCESS)
+    }
+
+    unsafe fn nvmlShutdown(&self) -> Result<nvmlReturn_t, NvmlError> {
+        Ok(nvmlReturn_enum_NVML_SUCCESS)
+    }
+
+    unsafe fn nvmlDeviceGetCount_v2(&self, count: *mut c_uint) -> Result<nvmlReturn_t, NvmlError> {
+        *count = 2;
+        Ok(nvmlReturn_enum_NVML_SUCCESS)
+    }
+}
+
+# fn main() -> Result<(), NvmlError> {
+let nvml = Nvml::builder().init_with_backend(TwoGpus)?;
+assert_eq!(nvml.device_count()?, 2);
+
+// Functions the backend doesn't provide fail the same way missing symbols do
+assert!(matches!(
+    nvml.sys_driver_version(),
+    Err(NvmlError::FailedToLoadSymbol(_))
+));
+# Ok(())
+# }
+```
+
+# Safety
+
+The trait methods are `unsafe` for the same reason the C functions are: the
+pointer arguments are dereferenced without any checks. Implementations may
+assume the wrapper only ever passes pointers that are valid for the reads and
+writes the corresponding NVML function would perform.
+
+Handles (`nvmlDevice_t`, `nvmlUnit_t`, `nvmlEventSet_t`, ...) are opaque to the
+wrapper. A backend can use any non-null pointer-sized value it likes for them,
+as long as it recognizes the values it handed out when they are passed back in.
+*/
+
+#![allow(non_snake_case)]
+// The safety contract is shared by every method and is documented above
+#![allow(clippy::missing_safety_doc)]
+
+use crate::error::{nvml_sym, NvmlError};
+use crate::ffi::bindings::*;
+use std::os::raw;
+
+/// The error returned by default for functions a backend doesn't provide.
+pub(crate) fn not_provided(name: &str) -> NvmlError {
+    NvmlError::FailedToLoadSymbol(format!("{} is not provided by this backend", name))
+}
+
+macro_rules! nvml_backend {
+    ($(
+        $(#[$attr:meta])*
+        fn $name:ident($($arg:ident: $ty:ty),* $(,)?);
+    )*) => {
+        /**
+        A provider of the NVML C API.
+
+        See the [module-level documentation](self) for details.
+        */
+        pub trait NvmlBackend: Send + Sync {
+            /// Get the underlying `NvmlLib` if this backend is (or wraps) the
+            /// dynamically loaded NVML library.
+            fn lib(&self) -> Option<&NvmlLib> {
+                None
+            }
+
+            /**
+            Release any resources held by this backend.
+
+            Called by `Nvml::shutdown()` after `nvmlShutdown` succeeded. Backends that
+            are dropped without being closed should release their resources in their
+            `Drop` implementation instead.
+            */
+            fn close(self: Box<Self>) -> Result<(), NvmlError> {
+                Ok(())
+            }
+
+            #[doc = "Calls `nvmlErrorString`."]
+            unsafe fn nvmlErrorString(
+                &self,
+                result: nvmlReturn_t,
+            ) -> Result<*const raw::c_char, NvmlError> {
+                let _ = result;
+                Err(not_provided("nvmlErrorString"))
+            }
+
+            $(
+                $(#[$attr])*
+                #[doc = concat!("Calls `", stringify!($name), "`.")]
+                unsafe fn $name(&self, $($arg: $ty),*) -> Result<nvmlReturn_t, NvmlError> {
+                    $(let _ = $arg;)*
+                    Err(not_provided(stringify!($name)))
+                }
+            )*
+        }
+
+        impl NvmlBackend for NvmlLib {
+            fn lib(&self) -> Option<&NvmlLib> {
+                Some(self)
+            }
+
+            fn close(self: Box<Self>) -> Result<(), NvmlError> {
+                Ok(self.__library.close()?)
+            }
+
+            unsafe fn nvmlErrorString(
+                &self,
+                result: nvmlReturn_t,
+            ) -> Result<*const raw::c_char, NvmlError> {
+                let sym = nvml_sym(self.nvmlErrorString.as_ref())?;
+
+                Ok(sym(result))
+            }
+
+            $(
+                $(#[$attr])*
+                unsafe fn $name(&self, $($arg: $ty),*) -> Result<nvmlReturn_t, NvmlError> {
+                    let sym = nvml_sym(self.$name.as_ref())?;
+
+                    Ok(sym($($arg),*))
+                }
+            )*
+        }
+    };
+}
+
+// Invokes `$callback!` with the signature of every NVML function. The list
+// mirrors the fields of `NvmlLib`; keep it in sync when the bindings are
+// regenerated.
+macro_rules! nvml_functions {
+    ($callback:ident) => {
+        $callback! {
+            fn nvmlInit_v2();
+            fn nvmlInitWithFlags(flags: raw::c_uint);
+            fn nvmlShutdown();
+            fn nvmlSystemGetDriverVersion(version: *mut raw::c_char, length: raw::c_uint);
+            fn nvmlSystemGetNVMLVersion(version: *mut raw::c_char, length: raw::c_uint);
+            fn nvmlSystemGetCudaDriverVersion(cudaDriverVersion: *mut raw::c_int);
+            fn nvmlSystemGetCudaDriverVersion_v2(cudaDriverVersion: *mut raw::c_int);
+            fn nvmlSystemGetProcessName(pid: raw::c_uint, name: *mut raw::c_char, length: raw::c_uint);
+            fn nvmlSystemGetHicVersion(hwbcCount: *mut raw::c_uint, hwbcEntries: *mut nvmlHwbcEntry_t);
+            fn nvmlSystemGetTopologyGpuSet(
+                cpuNumber: raw::c_uint,
+                count: *mut raw::c_uint,
+                deviceArray: *mut nvmlDevice_t,
+            );
+            fn nvmlSystemGetDriverBranch(
+                branchInfo: *mut nvmlSystemDriverBranchInfo_t,
+                length: raw::c_uint,
+            );
+            fn nvmlUnitGetCount(unitCount: *mut raw::c_uint);
+            fn nvmlUnitGetHandleByIndex(index: raw::c_uint, unit: *mut nvmlUnit_t);
+            fn nvmlUnitGetUnitInfo(unit: nvmlUnit_t, info: *mut nvmlUnitInfo_t);
+            fn nvmlUnitGetLedState(unit: nvmlUnit_t, state: *mut nvmlLedState_t);
+            fn nvmlUnitGetPsuInfo(unit: nvmlUnit_t, psu: *mut nvmlPSUInfo_t);
+            fn nvmlUnitGetTemperature(unit: nvmlUnit_t, type_: raw::c_uint, temp: *mut raw::c_uint);
+            fn nvmlUnitGetFanSpeedInfo(unit: nvmlUnit_t, fanSpeeds: *mut nvmlUnitFanSpeeds_t);
+            fn nvmlUnitGetDevices(
+                unit: nvmlUnit_t,
+                deviceCount: *mut raw::c_uint,
+                devices: *mut nvmlDevice_t,
+            );
+            fn nvmlDeviceGetCount_v2(deviceCount: *mut raw::c_uint);
+            fn nvmlDeviceGetAttributes_v2(device: nvmlDevice_t, attributes: *mut nvmlDeviceAttributes_t);
+            fn nvmlDeviceGetHandleByIndex_v2(index: raw::c_uint, device: *mut nvmlDevice_t);
+            fn nvmlDeviceGetHandleBySerial(serial: *const raw::c_char, device: *mut nvmlDevice_t);
+            fn nvmlDeviceGetHandleByUUID(uuid: *const raw::c_char, device: *mut nvmlDevice_t);
+            fn nvmlDeviceGetHandleByUUIDV(uuid: *const nvmlUUID_t, device: *mut nvmlDevice_t);
+            fn nvmlDeviceGetHandleByPciBusId_v2(pciBusId: *const raw::c_char, device: *mut nvmlDevice_t);
+            fn nvmlDeviceGetName(device: nvmlDevice_t, name: *mut raw::c_char, length: raw::c_uint);
+            fn nvmlDeviceGetBrand(device: nvmlDevice_t, type_: *mut nvmlBrandType_t);
+            fn nvmlDeviceGetIndex(device: nvmlDevice_t, index: *mut raw::c_uint);
+            fn nvmlDeviceGetSerial(device: nvmlDevice_t, serial: *mut raw::c_char, length: raw::c_uint);
+            fn nvmlDeviceGetModuleId(device: nvmlDevice_t, moduleId: *mut raw::c_uint);
+            fn nvmlDeviceGetC2cModeInfoV(device: nvmlDevice_t, c2cModeInfo: *mut nvmlC2cModeInfo_v1_t);
+            fn nvmlDeviceGetMemoryAffinity(
+                device: nvmlDevice_t,
+                nodeSetSize: raw::c_uint,
+                nodeSet: *mut raw::c_ulong,
+                scope: nvmlAffinityScope_t,
+            );
+            fn nvmlDeviceGetCpuAffinityWithinScope(
+                device: nvmlDevice_t,
+                cpuSetSize: raw::c_uint,
+                cpuSet: *mut raw::c_ulong,
+                scope: nvmlAffinityScope_t,
+            );
+            fn nvmlDeviceGetCpuAffinity(
+                device: nvmlDevice_t,
+                cpuSetSize: raw::c_uint,
+                cpuSet: *mut raw::c_ulong,
+            );
+            fn nvmlDeviceSetCpuAffinity(device: nvmlDevice_t);
+            fn nvmlDeviceClearCpuAffinity(device: nvmlDevice_t);
+            fn nvmlDeviceGetNumaNodeId(device: nvmlDevice_t, node: *mut raw::c_uint);
+            fn nvmlDeviceGetAddressingMode(device: nvmlDevice_t, mode: *mut nvmlDeviceAddressingMode_t);
+            fn nvmlDeviceGetRepairStatus(device: nvmlDevice_t, repairStatus: *mut nvmlRepairStatus_t);
+            fn nvmlDeviceGetTopologyCommonAncestor(
+                device1: nvmlDevice_t,
+                device2: nvmlDevice_t,
+                pathInfo: *mut nvmlGpuTopologyLevel_t,
+            );
+            fn nvmlDeviceGetTopologyNearestGpus(
+                device: nvmlDevice_t,
+                level: nvmlGpuTopologyLevel_t,
+                count: *mut raw::c_uint,
+                deviceArray: *mut nvmlDevice_t,
+            );
+            fn nvmlDeviceGetP2PStatus(
+                device1: nvmlDevice_t,
+                device2: nvmlDevice_t,
+                p2pIndex: nvmlGpuP2PCapsIndex_t,
+                p2pStatus: *mut nvmlGpuP2PStatus_t,
+            );
+            fn nvmlDeviceGetUUID(device: nvmlDevice_t, uuid: *mut raw::c_char, length: raw::c_uint);
+            fn nvmlDeviceGetMinorNumber(device: nvmlDevice_t, minorNumber: *mut raw::c_uint);
+            fn nvmlDeviceGetBoardPartNumber(
+                device: nvmlDevice_t,
+                partNumber: *mut raw::c_char,
+                length: raw::c_uint,
+            );
+            fn nvmlDeviceGetInforomVersion(
+                device: nvmlDevice_t,
+                object: nvmlInforomObject_t,
+                version: *mut raw::c_char,
+                length: raw::c_uint,
+            );
+            fn nvmlDeviceGetInforomImageVersion(
+                device: nvmlDevice_t,
+                version: *mut raw::c_char,
+                length: raw::c_uint,
+            );
+            fn nvmlDeviceGetInforomConfigurationChecksum(device: nvmlDevice_t, checksum: *mut raw::c_uint);
+            fn nvmlDeviceValidateInforom(device: nvmlDevice_t);
+            fn nvmlDeviceGetLastBBXFlushTime(
+                device: nvmlDevice_t,
+                timestamp: *mut raw::c_ulonglong,
+                durationUs: *mut raw::c_ulong,
+            );
+            fn nvmlDeviceGetDisplayMode(device: nvmlDevice_t, display: *mut nvmlEnableState_t);
+            fn nvmlDeviceGetDisplayActive(device: nvmlDevice_t, isActive: *mut nvmlEnableState_t);
+            fn nvmlDeviceGetPersistenceMode(device: nvmlDevice_t, mode: *mut nvmlEnableState_t);
+            fn nvmlDeviceGetPciInfoExt(device: nvmlDevice_t, pci: *mut nvmlPciInfoExt_t);
+            fn nvmlDeviceGetPciInfo_v3(device: nvmlDevice_t, pci: *mut nvmlPciInfo_t);
+            fn nvmlDeviceGetMaxPcieLinkGeneration(device: nvmlDevice_t, maxLinkGen: *mut raw::c_uint);
+            fn nvmlDeviceGetGpuMaxPcieLinkGeneration(
+                device: nvmlDevice_t,
+                maxLinkGenDevice: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetMaxPcieLinkWidth(device: nvmlDevice_t, maxLinkWidth: *mut raw::c_uint);
+            fn nvmlDeviceGetCurrPcieLinkGeneration(device: nvmlDevice_t, currLinkGen: *mut raw::c_uint);
+            fn nvmlDeviceGetCurrPcieLinkWidth(device: nvmlDevice_t, currLinkWidth: *mut raw::c_uint);
+            fn nvmlDeviceGetPcieThroughput(
+                device: nvmlDevice_t,
+                counter: nvmlPcieUtilCounter_t,
+                value: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetPcieReplayCounter(device: nvmlDevice_t, value: *mut raw::c_uint);
+            fn nvmlDeviceGetClockInfo(
+                device: nvmlDevice_t,
+                type_: nvmlClockType_t,
+                clock: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetMaxClockInfo(
+                device: nvmlDevice_t,
+                type_: nvmlClockType_t,
+                clock: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetGpcClkVfOffset(device: nvmlDevice_t, offset: *mut raw::c_int);
+            fn nvmlDeviceGetApplicationsClock(
+                device: nvmlDevice_t,
+                clockType: nvmlClockType_t,
+                clockMHz: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetDefaultApplicationsClock(
+                device: nvmlDevice_t,
+                clockType: nvmlClockType_t,
+                clockMHz: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetClock(
+                device: nvmlDevice_t,
+                clockType: nvmlClockType_t,
+                clockId: nvmlClockId_t,
+                clockMHz: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetMaxCustomerBoostClock(
+                device: nvmlDevice_t,
+                clockType: nvmlClockType_t,
+                clockMHz: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetSupportedMemoryClocks(
+                device: nvmlDevice_t,
+                count: *mut raw::c_uint,
+                clocksMHz: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetSupportedGraphicsClocks(
+                device: nvmlDevice_t,
+                memoryClockMHz: raw::c_uint,
+                count: *mut raw::c_uint,
+                clocksMHz: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetAutoBoostedClocksEnabled(
+                device: nvmlDevice_t,
+                isEnabled: *mut nvmlEnableState_t,
+                defaultIsEnabled: *mut nvmlEnableState_t,
+            );
+            fn nvmlDeviceGetFanSpeed(device: nvmlDevice_t, speed: *mut raw::c_uint);
+            fn nvmlDeviceGetFanSpeed_v2(device: nvmlDevice_t, fan: raw::c_uint, speed: *mut raw::c_uint);
+            fn nvmlDeviceGetFanSpeedRPM(device: nvmlDevice_t, fanSpeed: *mut nvmlFanSpeedInfo_t);
+            fn nvmlDeviceGetTargetFanSpeed(
+                device: nvmlDevice_t,
+                fan: raw::c_uint,
+                targetSpeed: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetMinMaxFanSpeed(
+                device: nvmlDevice_t,
+                minSpeed: *mut raw::c_uint,
+                maxSpeed: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetFanControlPolicy_v2(
+                device: nvmlDevice_t,
+                fan: raw::c_uint,
+                policy: *mut nvmlFanControlPolicy_t,
+            );
+            fn nvmlDeviceGetNumFans(device: nvmlDevice_t, numFans: *mut raw::c_uint);
+            fn nvmlDeviceGetTemperature(
+                device: nvmlDevice_t,
+                sensorType: nvmlTemperatureSensors_t,
+                temp: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetCoolerInfo(device: nvmlDevice_t, coolerInfo: *mut nvmlCoolerInfo_t);
+            fn nvmlDeviceGetTemperatureV(device: nvmlDevice_t, temperature: *mut nvmlTemperature_t);
+            fn nvmlDeviceGetTemperatureThreshold(
+                device: nvmlDevice_t,
+                thresholdType: nvmlTemperatureThresholds_t,
+                temp: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetMarginTemperature(
+                device: nvmlDevice_t,
+                marginTempInfo: *mut nvmlMarginTemperature_t,
+            );
+            fn nvmlDeviceGetThermalSettings(
+                device: nvmlDevice_t,
+                sensorIndex: raw::c_uint,
+                pThermalSettings: *mut nvmlGpuThermalSettings_t,
+            );
+            fn nvmlDeviceGetPerformanceState(device: nvmlDevice_t, pState: *mut nvmlPstates_t);
+            fn nvmlDeviceGetCurrentClocksEventReasons(
+                device: nvmlDevice_t,
+                clocksEventReasons: *mut raw::c_ulonglong,
+            );
+            fn nvmlDeviceGetCurrentClocksThrottleReasons(
+                device: nvmlDevice_t,
+                clocksThrottleReasons: *mut raw::c_ulonglong,
+            );
+            fn nvmlDeviceGetSupportedClocksEventReasons(
+                device: nvmlDevice_t,
+                supportedClocksEventReasons: *mut raw::c_ulonglong,
+            );
+            fn nvmlDeviceGetSupportedClocksThrottleReasons(
+                device: nvmlDevice_t,
+                supportedClocksThrottleReasons: *mut raw::c_ulonglong,
+            );
+            fn nvmlDeviceGetPowerState(device: nvmlDevice_t, pState: *mut nvmlPstates_t);
+            fn nvmlDeviceGetDynamicPstatesInfo(
+                device: nvmlDevice_t,
+                pDynamicPstatesInfo: *mut nvmlGpuDynamicPstatesInfo_t,
+            );
+            fn nvmlDeviceGetMemClkVfOffset(device: nvmlDevice_t, offset: *mut raw::c_int);
+            fn nvmlDeviceGetMinMaxClockOfPState(
+                device: nvmlDevice_t,
+                type_: nvmlClockType_t,
+                pstate: nvmlPstates_t,
+                minClockMHz: *mut raw::c_uint,
+                maxClockMHz: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetSupportedPerformanceStates(
+                device: nvmlDevice_t,
+                pstates: *mut nvmlPstates_t,
+                size: raw::c_uint,
+            );
+            fn nvmlDeviceGetGpcClkMinMaxVfOffset(
+                device: nvmlDevice_t,
+                minOffset: *mut raw::c_int,
+                maxOffset: *mut raw::c_int,
+            );
+            fn nvmlDeviceGetMemClkMinMaxVfOffset(
+                device: nvmlDevice_t,
+                minOffset: *mut raw::c_int,
+                maxOffset: *mut raw::c_int,
+            );
+            fn nvmlDeviceGetClockOffsets(device: nvmlDevice_t, info: *mut nvmlClockOffset_t);
+            fn nvmlDeviceSetClockOffsets(device: nvmlDevice_t, info: *mut nvmlClockOffset_t);
+            fn nvmlDeviceGetPerformanceModes(device: nvmlDevice_t, perfModes: *mut nvmlDevicePerfModes_t);
+            fn nvmlDeviceGetCurrentClockFreqs(
+                device: nvmlDevice_t,
+                currentClockFreqs: *mut nvmlDeviceCurrentClockFreqs_t,
+            );
+            fn nvmlDeviceGetPowerManagementMode(device: nvmlDevice_t, mode: *mut nvmlEnableState_t);
+            fn nvmlDeviceGetPowerManagementLimit(device: nvmlDevice_t, limit: *mut raw::c_uint);
+            fn nvmlDeviceGetPowerManagementLimitConstraints(
+                device: nvmlDevice_t,
+                minLimit: *mut raw::c_uint,
+                maxLimit: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetPowerManagementDefaultLimit(
+                device: nvmlDevice_t,
+                defaultLimit: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetPowerUsage(device: nvmlDevice_t, power: *mut raw::c_uint);
+            fn nvmlDeviceGetPowerMizerMode_v1(
+                device: nvmlDevice_t,
+                powerMizerMode: *mut nvmlDevicePowerMizerModes_v1_t,
+            );
+            fn nvmlDeviceSetPowerMizerMode_v1(
+                device: nvmlDevice_t,
+                powerMizerMode: *mut nvmlDevicePowerMizerModes_v1_t,
+            );
+            fn nvmlDeviceGetTotalEnergyConsumption(device: nvmlDevice_t, energy: *mut raw::c_ulonglong);
+            fn nvmlDeviceGetEnforcedPowerLimit(device: nvmlDevice_t, limit: *mut raw::c_uint);
+            fn nvmlDeviceGetGpuOperationMode(
+                device: nvmlDevice_t,
+                current: *mut nvmlGpuOperationMode_t,
+                pending: *mut nvmlGpuOperationMode_t,
+            );
+            fn nvmlDeviceGetMemoryInfo(device: nvmlDevice_t, memory: *mut nvmlMemory_t);
+            fn nvmlDeviceGetMemoryInfo_v2(device: nvmlDevice_t, memory: *mut nvmlMemory_v2_t);
+            fn nvmlDeviceGetComputeMode(device: nvmlDevice_t, mode: *mut nvmlComputeMode_t);
+            fn nvmlDeviceGetCudaComputeCapability(
+                device: nvmlDevice_t,
+                major: *mut raw::c_int,
+                minor: *mut raw::c_int,
+            );
+            fn nvmlDeviceGetDramEncryptionMode(
+                device: nvmlDevice_t,
+                current: *mut nvmlDramEncryptionInfo_t,
+                pending: *mut nvmlDramEncryptionInfo_t,
+            );
+            fn nvmlDeviceSetDramEncryptionMode(
+                device: nvmlDevice_t,
+                dramEncryption: *const nvmlDramEncryptionInfo_t,
+            );
+            fn nvmlDeviceGetEccMode(
+                device: nvmlDevice_t,
+                current: *mut nvmlEnableState_t,
+                pending: *mut nvmlEnableState_t,
+            );
+            fn nvmlDeviceGetDefaultEccMode(device: nvmlDevice_t, defaultMode: *mut nvmlEnableState_t);
+            fn nvmlDeviceGetBoardId(device: nvmlDevice_t, boardId: *mut raw::c_uint);
+            fn nvmlDeviceGetMultiGpuBoard(device: nvmlDevice_t, multiGpuBool: *mut raw::c_uint);
+            fn nvmlDeviceGetTotalEccErrors(
+                device: nvmlDevice_t,
+                errorType: nvmlMemoryErrorType_t,
+                counterType: nvmlEccCounterType_t,
+                eccCounts: *mut raw::c_ulonglong,
+            );
+            fn nvmlDeviceGetDetailedEccErrors(
+                device: nvmlDevice_t,
+                errorType: nvmlMemoryErrorType_t,
+                counterType: nvmlEccCounterType_t,
+                eccCounts: *mut nvmlEccErrorCounts_t,
+            );
+            fn nvmlDeviceGetMemoryErrorCounter(
+                device: nvmlDevice_t,
+                errorType: nvmlMemoryErrorType_t,
+                counterType: nvmlEccCounterType_t,
+                locationType: nvmlMemoryLocation_t,
+                count: *mut raw::c_ulonglong,
+            );
+            fn nvmlDeviceGetUtilizationRates(device: nvmlDevice_t, utilization: *mut nvmlUtilization_t);
+            fn nvmlDeviceGetEncoderUtilization(
+                device: nvmlDevice_t,
+                utilization: *mut raw::c_uint,
+                samplingPeriodUs: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetEncoderCapacity(
+                device: nvmlDevice_t,
+                encoderQueryType: nvmlEncoderType_t,
+                encoderCapacity: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetEncoderStats(
+                device: nvmlDevice_t,
+                sessionCount: *mut raw::c_uint,
+                averageFps: *mut raw::c_uint,
+                averageLatency: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetEncoderSessions(
+                device: nvmlDevice_t,
+                sessionCount: *mut raw::c_uint,
+                sessionInfos: *mut nvmlEncoderSessionInfo_t,
+            );
+            fn nvmlDeviceGetDecoderUtilization(
+                device: nvmlDevice_t,
+                utilization: *mut raw::c_uint,
+                samplingPeriodUs: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetJpgUtilization(
+                device: nvmlDevice_t,
+                utilization: *mut raw::c_uint,
+                samplingPeriodUs: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetOfaUtilization(
+                device: nvmlDevice_t,
+                utilization: *mut raw::c_uint,
+                samplingPeriodUs: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetFBCStats(device: nvmlDevice_t, fbcStats: *mut nvmlFBCStats_t);
+            fn nvmlDeviceGetFBCSessions(
+                device: nvmlDevice_t,
+                sessionCount: *mut raw::c_uint,
+                sessionInfo: *mut nvmlFBCSessionInfo_t,
+            );
+            fn nvmlDeviceGetDriverModel_v2(
+                device: nvmlDevice_t,
+                current: *mut nvmlDriverModel_t,
+                pending: *mut nvmlDriverModel_t,
+            );
+            fn nvmlDeviceGetVbiosVersion(
+                device: nvmlDevice_t,
+                version: *mut raw::c_char,
+                length: raw::c_uint,
+            );
+            fn nvmlDeviceGetBridgeChipInfo(
+                device: nvmlDevice_t,
+                bridgeHierarchy: *mut nvmlBridgeChipHierarchy_t,
+            );
+            fn nvmlDeviceGetComputeRunningProcesses_v3(
+                device: nvmlDevice_t,
+                infoCount: *mut raw::c_uint,
+                infos: *mut nvmlProcessInfo_t,
+            );
+            fn nvmlDeviceGetGraphicsRunningProcesses_v3(
+                device: nvmlDevice_t,
+                infoCount: *mut raw::c_uint,
+                infos: *mut nvmlProcessInfo_t,
+            );
+            fn nvmlDeviceGetMPSComputeRunningProcesses_v3(
+                device: nvmlDevice_t,
+                infoCount: *mut raw::c_uint,
+                infos: *mut nvmlProcessInfo_t,
+            );
+            fn nvmlDeviceGetRunningProcessDetailList(
+                device: nvmlDevice_t,
+                plist: *mut nvmlProcessDetailList_t,
+            );
+            fn nvmlDeviceOnSameBoard(
+                device1: nvmlDevice_t,
+                device2: nvmlDevice_t,
+                onSameBoard: *mut raw::c_int,
+            );
+            fn nvmlDeviceGetAPIRestriction(
+                device: nvmlDevice_t,
+                apiType: nvmlRestrictedAPI_t,
+                isRestricted: *mut nvmlEnableState_t,
+            );
+            fn nvmlDeviceGetSamples(
+                device: nvmlDevice_t,
+                type_: nvmlSamplingType_t,
+                lastSeenTimeStamp: raw::c_ulonglong,
+                sampleValType: *mut nvmlValueType_t,
+                sampleCount: *mut raw::c_uint,
+                samples: *mut nvmlSample_t,
+            );
+            fn nvmlDeviceGetBAR1MemoryInfo(device: nvmlDevice_t, bar1Memory: *mut nvmlBAR1Memory_t);
+            fn nvmlDeviceGetViolationStatus(
+                device: nvmlDevice_t,
+                perfPolicyType: nvmlPerfPolicyType_t,
+                violTime: *mut nvmlViolationTime_t,
+            );
+            fn nvmlDeviceGetIrqNum(device: nvmlDevice_t, irqNum: *mut raw::c_uint);
+            fn nvmlDeviceGetNumGpuCores(device: nvmlDevice_t, numCores: *mut raw::c_uint);
+            fn nvmlDeviceGetPowerSource(device: nvmlDevice_t, powerSource: *mut nvmlPowerSource_t);
+            fn nvmlDeviceGetMemoryBusWidth(device: nvmlDevice_t, busWidth: *mut raw::c_uint);
+            fn nvmlDeviceGetPcieLinkMaxSpeed(device: nvmlDevice_t, maxSpeed: *mut raw::c_uint);
+            fn nvmlDeviceGetPcieSpeed(device: nvmlDevice_t, pcieSpeed: *mut raw::c_uint);
+            fn nvmlDeviceGetAdaptiveClockInfoStatus(
+                device: nvmlDevice_t,
+                adaptiveClockStatus: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetBusType(device: nvmlDevice_t, type_: *mut nvmlBusType_t);
+            fn nvmlDeviceGetGpuFabricInfo(device: nvmlDevice_t, gpuFabricInfo: *mut nvmlGpuFabricInfo_t);
+            fn nvmlDeviceGetGpuFabricInfoV(device: nvmlDevice_t, gpuFabricInfo: *mut nvmlGpuFabricInfoV_t);
+            fn nvmlSystemGetConfComputeCapabilities(capabilities: *mut nvmlConfComputeSystemCaps_t);
+            fn nvmlSystemGetConfComputeState(state: *mut nvmlConfComputeSystemState_t);
+            fn nvmlDeviceGetConfComputeMemSizeInfo(
+                device: nvmlDevice_t,
+                memInfo: *mut nvmlConfComputeMemSizeInfo_t,
+            );
+            fn nvmlSystemGetConfComputeGpusReadyState(isAcceptingWork: *mut raw::c_uint);
+            fn nvmlDeviceGetConfComputeProtectedMemoryUsage(
+                device: nvmlDevice_t,
+                memory: *mut nvmlMemory_t,
+            );
+            fn nvmlDeviceGetConfComputeGpuCertificate(
+                device: nvmlDevice_t,
+                gpuCert: *mut nvmlConfComputeGpuCertificate_t,
+            );
+            fn nvmlDeviceGetConfComputeGpuAttestationReport(
+                device: nvmlDevice_t,
+                gpuAtstReport: *mut nvmlConfComputeGpuAttestationReport_t,
+            );
+            fn nvmlSystemGetConfComputeKeyRotationThresholdInfo(
+                pKeyRotationThrInfo: *mut nvmlConfComputeGetKeyRotationThresholdInfo_t,
+            );
+            fn nvmlDeviceSetConfComputeUnprotectedMemSize(device: nvmlDevice_t, sizeKiB: raw::c_ulonglong);
+            fn nvmlSystemSetConfComputeGpusReadyState(isAcceptingWork: raw::c_uint);
+            fn nvmlSystemSetConfComputeKeyRotationThresholdInfo(
+                pKeyRotationThrInfo: *mut nvmlConfComputeSetKeyRotationThresholdInfo_t,
+            );
+            fn nvmlSystemGetConfComputeSettings(settings: *mut nvmlSystemConfComputeSettings_t);
+            fn nvmlDeviceGetGspFirmwareVersion(device: nvmlDevice_t, version: *mut raw::c_char);
+            fn nvmlDeviceGetGspFirmwareMode(
+                device: nvmlDevice_t,
+                isEnabled: *mut raw::c_uint,
+                defaultMode: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetSramEccErrorStatus(device: nvmlDevice_t, status: *mut nvmlEccSramErrorStatus_t);
+            fn nvmlDeviceSetPowerManagementLimit_v2(
+                device: nvmlDevice_t,
+                powerValue: *mut nvmlPowerValue_v2_t,
+            );
+            fn nvmlDeviceGetAccountingMode(device: nvmlDevice_t, mode: *mut nvmlEnableState_t);
+            fn nvmlDeviceGetAccountingStats(
+                device: nvmlDevice_t,
+                pid: raw::c_uint,
+                stats: *mut nvmlAccountingStats_t,
+            );
+            fn nvmlDeviceGetAccountingPids(
+                device: nvmlDevice_t,
+                count: *mut raw::c_uint,
+                pids: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetAccountingBufferSize(device: nvmlDevice_t, bufferSize: *mut raw::c_uint);
+            fn nvmlDeviceGetRetiredPages(
+                device: nvmlDevice_t,
+                cause: nvmlPageRetirementCause_t,
+                pageCount: *mut raw::c_uint,
+                addresses: *mut raw::c_ulonglong,
+            );
+            fn nvmlDeviceGetRetiredPages_v2(
+                device: nvmlDevice_t,
+                cause: nvmlPageRetirementCause_t,
+                pageCount: *mut raw::c_uint,
+                addresses: *mut raw::c_ulonglong,
+                timestamps: *mut raw::c_ulonglong,
+            );
+            fn nvmlDeviceGetRetiredPagesPendingStatus(
+                device: nvmlDevice_t,
+                isPending: *mut nvmlEnableState_t,
+            );
+            fn nvmlDeviceGetRemappedRows(
+                device: nvmlDevice_t,
+                corrRows: *mut raw::c_uint,
+                uncRows: *mut raw::c_uint,
+                isPending: *mut raw::c_uint,
+                failureOccurred: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetRowRemapperHistogram(
+                device: nvmlDevice_t,
+                values: *mut nvmlRowRemapperHistogramValues_t,
+            );
+            fn nvmlDeviceGetArchitecture(device: nvmlDevice_t, arch: *mut nvmlDeviceArchitecture_t);
+            fn nvmlDeviceGetClkMonStatus(device: nvmlDevice_t, status: *mut nvmlClkMonStatus_t);
+            fn nvmlDeviceGetProcessUtilization(
+                device: nvmlDevice_t,
+                utilization: *mut nvmlProcessUtilizationSample_t,
+                processSamplesCount: *mut raw::c_uint,
+                lastSeenTimeStamp: raw::c_ulonglong,
+            );
+            fn nvmlDeviceGetProcessesUtilizationInfo(
+                device: nvmlDevice_t,
+                procesesUtilInfo: *mut nvmlProcessesUtilizationInfo_t,
+            );
+            fn nvmlDeviceGetPlatformInfo(device: nvmlDevice_t, platformInfo: *mut nvmlPlatformInfo_t);
+            fn nvmlDeviceGetPdi(device: nvmlDevice_t, pdi: *mut nvmlPdi_t);
+            fn nvmlUnitSetLedState(unit: nvmlUnit_t, color: nvmlLedColor_t);
+            fn nvmlDeviceSetPersistenceMode(device: nvmlDevice_t, mode: nvmlEnableState_t);
+            fn nvmlDeviceSetComputeMode(device: nvmlDevice_t, mode: nvmlComputeMode_t);
+            fn nvmlDeviceSetEccMode(device: nvmlDevice_t, ecc: nvmlEnableState_t);
+            fn nvmlDeviceClearEccErrorCounts(device: nvmlDevice_t, counterType: nvmlEccCounterType_t);
+            fn nvmlDeviceSetDriverModel(
+                device: nvmlDevice_t,
+                driverModel: nvmlDriverModel_t,
+                flags: raw::c_uint,
+            );
+            fn nvmlDeviceSetGpuLockedClocks(
+                device: nvmlDevice_t,
+                minGpuClockMHz: raw::c_uint,
+                maxGpuClockMHz: raw::c_uint,
+            );
+            fn nvmlDeviceResetGpuLockedClocks(device: nvmlDevice_t);
+            fn nvmlDeviceSetMemoryLockedClocks(
+                device: nvmlDevice_t,
+                minMemClockMHz: raw::c_uint,
+                maxMemClockMHz: raw::c_uint,
+            );
+            fn nvmlDeviceResetMemoryLockedClocks(device: nvmlDevice_t);
+            fn nvmlDeviceSetApplicationsClocks(
+                device: nvmlDevice_t,
+                memClockMHz: raw::c_uint,
+                graphicsClockMHz: raw::c_uint,
+            );
+            fn nvmlDeviceResetApplicationsClocks(device: nvmlDevice_t);
+            fn nvmlDeviceSetAutoBoostedClocksEnabled(device: nvmlDevice_t, enabled: nvmlEnableState_t);
+            fn nvmlDeviceSetDefaultAutoBoostedClocksEnabled(
+                device: nvmlDevice_t,
+                enabled: nvmlEnableState_t,
+                flags: raw::c_uint,
+            );
+            fn nvmlDeviceSetDefaultFanSpeed_v2(device: nvmlDevice_t, fan: raw::c_uint);
+            fn nvmlDeviceSetFanControlPolicy(
+                device: nvmlDevice_t,
+                fan: raw::c_uint,
+                policy: nvmlFanControlPolicy_t,
+            );
+            fn nvmlDeviceSetTemperatureThreshold(
+                device: nvmlDevice_t,
+                thresholdType: nvmlTemperatureThresholds_t,
+                temp: *mut raw::c_int,
+            );
+            fn nvmlDeviceSetPowerManagementLimit(device: nvmlDevice_t, limit: raw::c_uint);
+            fn nvmlDeviceSetGpuOperationMode(device: nvmlDevice_t, mode: nvmlGpuOperationMode_t);
+            fn nvmlDeviceSetAPIRestriction(
+                device: nvmlDevice_t,
+                apiType: nvmlRestrictedAPI_t,
+                isRestricted: nvmlEnableState_t,
+            );
+            fn nvmlDeviceSetFanSpeed_v2(device: nvmlDevice_t, fan: raw::c_uint, speed: raw::c_uint);
+            fn nvmlDeviceSetGpcClkVfOffset(device: nvmlDevice_t, offset: raw::c_int);
+            fn nvmlDeviceSetMemClkVfOffset(device: nvmlDevice_t, offset: raw::c_int);
+            fn nvmlDeviceSetAccountingMode(device: nvmlDevice_t, mode: nvmlEnableState_t);
+            fn nvmlDeviceClearAccountingPids(device: nvmlDevice_t);
+            fn nvmlDeviceGetNvLinkState(
+                device: nvmlDevice_t,
+                link: raw::c_uint,
+                isActive: *mut nvmlEnableState_t,
+            );
+            fn nvmlDeviceGetNvLinkVersion(
+                device: nvmlDevice_t,
+                link: raw::c_uint,
+                version: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetNvLinkCapability(
+                device: nvmlDevice_t,
+                link: raw::c_uint,
+                capability: nvmlNvLinkCapability_t,
+                capResult: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetNvLinkRemotePciInfo_v2(
+                device: nvmlDevice_t,
+                link: raw::c_uint,
+                pci: *mut nvmlPciInfo_t,
+            );
+            fn nvmlDeviceGetNvLinkErrorCounter(
+                device: nvmlDevice_t,
+                link: raw::c_uint,
+                counter: nvmlNvLinkErrorCounter_t,
+                counterValue: *mut raw::c_ulonglong,
+            );
+            fn nvmlDeviceResetNvLinkErrorCounters(device: nvmlDevice_t, link: raw::c_uint);
+            fn nvmlDeviceSetNvLinkUtilizationControl(
+                device: nvmlDevice_t,
+                link: raw::c_uint,
+                counter: raw::c_uint,
+                control: *mut nvmlNvLinkUtilizationControl_t,
+                reset: raw::c_uint,
+            );
+            fn nvmlDeviceGetNvLinkUtilizationControl(
+                device: nvmlDevice_t,
+                link: raw::c_uint,
+                counter: raw::c_uint,
+                control: *mut nvmlNvLinkUtilizationControl_t,
+            );
+            fn nvmlDeviceGetNvLinkUtilizationCounter(
+                device: nvmlDevice_t,
+                link: raw::c_uint,
+                counter: raw::c_uint,
+                rxcounter: *mut raw::c_ulonglong,
+                txcounter: *mut raw::c_ulonglong,
+            );
+            fn nvmlDeviceFreezeNvLinkUtilizationCounter(
+                device: nvmlDevice_t,
+                link: raw::c_uint,
+                counter: raw::c_uint,
+                freeze: nvmlEnableState_t,
+            );
+            fn nvmlDeviceResetNvLinkUtilizationCounter(
+                device: nvmlDevice_t,
+                link: raw::c_uint,
+                counter: raw::c_uint,
+            );
+            fn nvmlDeviceGetNvLinkRemoteDeviceType(
+                device: nvmlDevice_t,
+                link: raw::c_uint,
+                pNvLinkDeviceType: *mut nvmlIntNvLinkDeviceType_t,
+            );
+            fn nvmlDeviceSetNvLinkDeviceLowPowerThreshold(
+                device: nvmlDevice_t,
+                info: *mut nvmlNvLinkPowerThres_t,
+            );
+            fn nvmlSystemSetNvlinkBwMode(nvlinkBwMode: raw::c_uint);
+            fn nvmlSystemGetNvlinkBwMode(nvlinkBwMode: *mut raw::c_uint);
+            fn nvmlDeviceGetNvlinkSupportedBwModes(
+                device: nvmlDevice_t,
+                supportedBwMode: *mut nvmlNvlinkSupportedBwModes_t,
+            );
+            fn nvmlDeviceGetNvlinkBwMode(device: nvmlDevice_t, getBwMode: *mut nvmlNvlinkGetBwMode_t);
+            fn nvmlDeviceSetNvlinkBwMode(device: nvmlDevice_t, setBwMode: *mut nvmlNvlinkSetBwMode_t);
+            fn nvmlDeviceGetNvLinkInfo(device: nvmlDevice_t, info: *mut nvmlNvLinkInfo_t);
+            fn nvmlEventSetCreate(set: *mut nvmlEventSet_t);
+            fn nvmlDeviceRegisterEvents(
+                device: nvmlDevice_t,
+                eventTypes: raw::c_ulonglong,
+                set: nvmlEventSet_t,
+            );
+            fn nvmlDeviceGetSupportedEventTypes(device: nvmlDevice_t, eventTypes: *mut raw::c_ulonglong);
+            fn nvmlEventSetWait_v2(set: nvmlEventSet_t, data: *mut nvmlEventData_t, timeoutms: raw::c_uint);
+            fn nvmlEventSetFree(set: nvmlEventSet_t);
+            fn nvmlSystemEventSetCreate(request: *mut nvmlSystemEventSetCreateRequest_t);
+            fn nvmlSystemEventSetFree(request: *mut nvmlSystemEventSetFreeRequest_t);
+            fn nvmlSystemRegisterEvents(request: *mut nvmlSystemRegisterEventRequest_t);
+            fn nvmlSystemEventSetWait(request: *mut nvmlSystemEventSetWaitRequest_t);
+            fn nvmlDeviceModifyDrainState(pciInfo: *mut nvmlPciInfo_t, newState: nvmlEnableState_t);
+            fn nvmlDeviceQueryDrainState(pciInfo: *mut nvmlPciInfo_t, currentState: *mut nvmlEnableState_t);
+            fn nvmlDeviceRemoveGpu_v2(
+                pciInfo: *mut nvmlPciInfo_t,
+                gpuState: nvmlDetachGpuState_t,
+                linkState: nvmlPcieLinkState_t,
+            );
+            fn nvmlDeviceDiscoverGpus(pciInfo: *mut nvmlPciInfo_t);
+            fn nvmlDeviceGetFieldValues(
+                device: nvmlDevice_t,
+                valuesCount: raw::c_int,
+                values: *mut nvmlFieldValue_t,
+            );
+            fn nvmlDeviceClearFieldValues(
+                device: nvmlDevice_t,
+                valuesCount: raw::c_int,
+                values: *mut nvmlFieldValue_t,
+            );
+            fn nvmlDeviceGetVirtualizationMode(
+                device: nvmlDevice_t,
+                pVirtualMode: *mut nvmlGpuVirtualizationMode_t,
+            );
+            fn nvmlDeviceGetHostVgpuMode(device: nvmlDevice_t, pHostVgpuMode: *mut nvmlHostVgpuMode_t);
+            fn nvmlDeviceSetVirtualizationMode(
+                device: nvmlDevice_t,
+                virtualMode: nvmlGpuVirtualizationMode_t,
+            );
+            fn nvmlDeviceGetVgpuHeterogeneousMode(
+                device: nvmlDevice_t,
+                pHeterogeneousMode: *mut nvmlVgpuHeterogeneousMode_t,
+            );
+            fn nvmlDeviceSetVgpuHeterogeneousMode(
+                device: nvmlDevice_t,
+                pHeterogeneousMode: *const nvmlVgpuHeterogeneousMode_t,
+            );
+            fn nvmlVgpuInstanceGetPlacementId(
+                vgpuInstance: nvmlVgpuInstance_t,
+                pPlacement: *mut nvmlVgpuPlacementId_t,
+            );
+            fn nvmlDeviceGetVgpuTypeSupportedPlacements(
+                device: nvmlDevice_t,
+                vgpuTypeId: nvmlVgpuTypeId_t,
+                pPlacementList: *mut nvmlVgpuPlacementList_t,
+            );
+            fn nvmlDeviceGetVgpuTypeCreatablePlacements(
+                device: nvmlDevice_t,
+                vgpuTypeId: nvmlVgpuTypeId_t,
+                pPlacementList: *mut nvmlVgpuPlacementList_t,
+            );
+            fn nvmlVgpuTypeGetGspHeapSize(vgpuTypeId: nvmlVgpuTypeId_t, gspHeapSize: *mut raw::c_ulonglong);
+            fn nvmlVgpuTypeGetFbReservation(
+                vgpuTypeId: nvmlVgpuTypeId_t,
+                fbReservation: *mut raw::c_ulonglong,
+            );
+            fn nvmlVgpuInstanceGetRuntimeStateSize(
+                vgpuInstance: nvmlVgpuInstance_t,
+                pState: *mut nvmlVgpuRuntimeState_t,
+            );
+            fn nvmlDeviceSetVgpuCapabilities(
+                device: nvmlDevice_t,
+                capability: nvmlDeviceVgpuCapability_t,
+                state: nvmlEnableState_t,
+            );
+            fn nvmlDeviceGetGridLicensableFeatures_v4(
+                device: nvmlDevice_t,
+                pGridLicensableFeatures: *mut nvmlGridLicensableFeatures_t,
+            );
+            fn nvmlGetVgpuDriverCapabilities(
+                capability: nvmlVgpuDriverCapability_t,
+                capResult: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetVgpuCapabilities(
+                device: nvmlDevice_t,
+                capability: nvmlDeviceVgpuCapability_t,
+                capResult: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetSupportedVgpus(
+                device: nvmlDevice_t,
+                vgpuCount: *mut raw::c_uint,
+                vgpuTypeIds: *mut nvmlVgpuTypeId_t,
+            );
+            fn nvmlDeviceGetCreatableVgpus(
+                device: nvmlDevice_t,
+                vgpuCount: *mut raw::c_uint,
+                vgpuTypeIds: *mut nvmlVgpuTypeId_t,
+            );
+            fn nvmlVgpuTypeGetClass(
+                vgpuTypeId: nvmlVgpuTypeId_t,
+                vgpuTypeClass: *mut raw::c_char,
+                size: *mut raw::c_uint,
+            );
+            fn nvmlVgpuTypeGetName(
+                vgpuTypeId: nvmlVgpuTypeId_t,
+                vgpuTypeName: *mut raw::c_char,
+                size: *mut raw::c_uint,
+            );
+            fn nvmlVgpuTypeGetGpuInstanceProfileId(
+                vgpuTypeId: nvmlVgpuTypeId_t,
+                gpuInstanceProfileId: *mut raw::c_uint,
+            );
+            fn nvmlVgpuTypeGetDeviceID(
+                vgpuTypeId: nvmlVgpuTypeId_t,
+                deviceID: *mut raw::c_ulonglong,
+                subsystemID: *mut raw::c_ulonglong,
+            );
+            fn nvmlVgpuTypeGetFramebufferSize(vgpuTypeId: nvmlVgpuTypeId_t, fbSize: *mut raw::c_ulonglong);
+            fn nvmlVgpuTypeGetNumDisplayHeads(
+                vgpuTypeId: nvmlVgpuTypeId_t,
+                numDisplayHeads: *mut raw::c_uint,
+            );
+            fn nvmlVgpuTypeGetResolution(
+                vgpuTypeId: nvmlVgpuTypeId_t,
+                displayIndex: raw::c_uint,
+                xdim: *mut raw::c_uint,
+                ydim: *mut raw::c_uint,
+            );
+            fn nvmlVgpuTypeGetLicense(
+                vgpuTypeId: nvmlVgpuTypeId_t,
+                vgpuTypeLicenseString: *mut raw::c_char,
+                size: raw::c_uint,
+            );
+            fn nvmlVgpuTypeGetFrameRateLimit(
+                vgpuTypeId: nvmlVgpuTypeId_t,
+                frameRateLimit: *mut raw::c_uint,
+            );
+            fn nvmlVgpuTypeGetMaxInstances(
+                device: nvmlDevice_t,
+                vgpuTypeId: nvmlVgpuTypeId_t,
+                vgpuInstanceCount: *mut raw::c_uint,
+            );
+            fn nvmlVgpuTypeGetMaxInstancesPerVm(
+                vgpuTypeId: nvmlVgpuTypeId_t,
+                vgpuInstanceCountPerVm: *mut raw::c_uint,
+            );
+            fn nvmlVgpuTypeGetBAR1Info(vgpuTypeId: nvmlVgpuTypeId_t, bar1Info: *mut nvmlVgpuTypeBar1Info_t);
+            fn nvmlDeviceGetActiveVgpus(
+                device: nvmlDevice_t,
+                vgpuCount: *mut raw::c_uint,
+                vgpuInstances: *mut nvmlVgpuInstance_t,
+            );
+            fn nvmlVgpuInstanceGetVmID(
+                vgpuInstance: nvmlVgpuInstance_t,
+                vmId: *mut raw::c_char,
+                size: raw::c_uint,
+                vmIdType: *mut nvmlVgpuVmIdType_t,
+            );
+            fn nvmlVgpuInstanceGetUUID(
+                vgpuInstance: nvmlVgpuInstance_t,
+                uuid: *mut raw::c_char,
+                size: raw::c_uint,
+            );
+            fn nvmlVgpuInstanceGetVmDriverVersion(
+                vgpuInstance: nvmlVgpuInstance_t,
+                version: *mut raw::c_char,
+                length: raw::c_uint,
+            );
+            fn nvmlVgpuInstanceGetFbUsage(vgpuInstance: nvmlVgpuInstance_t, fbUsage: *mut raw::c_ulonglong);
+            fn nvmlVgpuInstanceGetLicenseStatus(
+                vgpuInstance: nvmlVgpuInstance_t,
+                licensed: *mut raw::c_uint,
+            );
+            fn nvmlVgpuInstanceGetType(vgpuInstance: nvmlVgpuInstance_t, vgpuTypeId: *mut nvmlVgpuTypeId_t);
+            fn nvmlVgpuInstanceGetFrameRateLimit(
+                vgpuInstance: nvmlVgpuInstance_t,
+                frameRateLimit: *mut raw::c_uint,
+            );
+            fn nvmlVgpuInstanceGetEccMode(
+                vgpuInstance: nvmlVgpuInstance_t,
+                eccMode: *mut nvmlEnableState_t,
+            );
+            fn nvmlVgpuInstanceGetEncoderCapacity(
+                vgpuInstance: nvmlVgpuInstance_t,
+                encoderCapacity: *mut raw::c_uint,
+            );
+            fn nvmlVgpuInstanceSetEncoderCapacity(
+                vgpuInstance: nvmlVgpuInstance_t,
+                encoderCapacity: raw::c_uint,
+            );
+            fn nvmlVgpuInstanceGetEncoderStats(
+                vgpuInstance: nvmlVgpuInstance_t,
+                sessionCount: *mut raw::c_uint,
+                averageFps: *mut raw::c_uint,
+                averageLatency: *mut raw::c_uint,
+            );
+            fn nvmlVgpuInstanceGetEncoderSessions(
+                vgpuInstance: nvmlVgpuInstance_t,
+                sessionCount: *mut raw::c_uint,
+                sessionInfo: *mut nvmlEncoderSessionInfo_t,
+            );
+            fn nvmlVgpuInstanceGetFBCStats(vgpuInstance: nvmlVgpuInstance_t, fbcStats: *mut nvmlFBCStats_t);
+            fn nvmlVgpuInstanceGetFBCSessions(
+                vgpuInstance: nvmlVgpuInstance_t,
+                sessionCount: *mut raw::c_uint,
+                sessionInfo: *mut nvmlFBCSessionInfo_t,
+            );
+            fn nvmlVgpuInstanceGetGpuInstanceId(
+                vgpuInstance: nvmlVgpuInstance_t,
+                gpuInstanceId: *mut raw::c_uint,
+            );
+            fn nvmlVgpuInstanceGetGpuPciId(
+                vgpuInstance: nvmlVgpuInstance_t,
+                vgpuPciId: *mut raw::c_char,
+                length: *mut raw::c_uint,
+            );
+            fn nvmlVgpuTypeGetCapabilities(
+                vgpuTypeId: nvmlVgpuTypeId_t,
+                capability: nvmlVgpuCapability_t,
+                capResult: *mut raw::c_uint,
+            );
+            fn nvmlVgpuInstanceGetMdevUUID(
+                vgpuInstance: nvmlVgpuInstance_t,
+                mdevUuid: *mut raw::c_char,
+                size: raw::c_uint,
+            );
+            fn nvmlGpuInstanceGetCreatableVgpus(
+                gpuInstance: nvmlGpuInstance_t,
+                pVgpus: *mut nvmlVgpuTypeIdInfo_t,
+            );
+            fn nvmlVgpuTypeGetMaxInstancesPerGpuInstance(pMaxInstance: *mut nvmlVgpuTypeMaxInstance_t);
+            fn nvmlGpuInstanceGetActiveVgpus(
+                gpuInstance: nvmlGpuInstance_t,
+                pVgpuInstanceInfo: *mut nvmlActiveVgpuInstanceInfo_t,
+            );
+            fn nvmlGpuInstanceSetVgpuSchedulerState(
+                gpuInstance: nvmlGpuInstance_t,
+                pScheduler: *mut nvmlVgpuSchedulerState_t,
+            );
+            fn nvmlGpuInstanceGetVgpuSchedulerState(
+                gpuInstance: nvmlGpuInstance_t,
+                pSchedulerStateInfo: *mut nvmlVgpuSchedulerStateInfo_t,
+            );
+            fn nvmlGpuInstanceGetVgpuSchedulerLog(
+                gpuInstance: nvmlGpuInstance_t,
+                pSchedulerLogInfo: *mut nvmlVgpuSchedulerLogInfo_t,
+            );
+            fn nvmlGpuInstanceGetVgpuTypeCreatablePlacements(
+                gpuInstance: nvmlGpuInstance_t,
+                pCreatablePlacementInfo: *mut nvmlVgpuCreatablePlacementInfo_t,
+            );
+            fn nvmlGpuInstanceGetVgpuHeterogeneousMode(
+                gpuInstance: nvmlGpuInstance_t,
+                pHeterogeneousMode: *mut nvmlVgpuHeterogeneousMode_t,
+            );
+            fn nvmlGpuInstanceSetVgpuHeterogeneousMode(
+                gpuInstance: nvmlGpuInstance_t,
+                pHeterogeneousMode: *const nvmlVgpuHeterogeneousMode_t,
+            );
+            fn nvmlVgpuInstanceGetMetadata(
+                vgpuInstance: nvmlVgpuInstance_t,
+                vgpuMetadata: *mut nvmlVgpuMetadata_t,
+                bufferSize: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetVgpuMetadata(
+                device: nvmlDevice_t,
+                pgpuMetadata: *mut nvmlVgpuPgpuMetadata_t,
+                bufferSize: *mut raw::c_uint,
+            );
+            fn nvmlGetVgpuCompatibility(
+                vgpuMetadata: *mut nvmlVgpuMetadata_t,
+                pgpuMetadata: *mut nvmlVgpuPgpuMetadata_t,
+                compatibilityInfo: *mut nvmlVgpuPgpuCompatibility_t,
+            );
+            fn nvmlDeviceGetPgpuMetadataString(
+                device: nvmlDevice_t,
+                pgpuMetadata: *mut raw::c_char,
+                bufferSize: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetVgpuSchedulerLog(
+                device: nvmlDevice_t,
+                pSchedulerLog: *mut nvmlVgpuSchedulerLog_t,
+            );
+            fn nvmlDeviceGetVgpuSchedulerState(
+                device: nvmlDevice_t,
+                pSchedulerState: *mut nvmlVgpuSchedulerGetState_t,
+            );
+            fn nvmlDeviceGetVgpuSchedulerCapabilities(
+                device: nvmlDevice_t,
+                pCapabilities: *mut nvmlVgpuSchedulerCapabilities_t,
+            );
+            fn nvmlDeviceSetVgpuSchedulerState(
+                device: nvmlDevice_t,
+                pSchedulerState: *mut nvmlVgpuSchedulerSetState_t,
+            );
+            fn nvmlGetVgpuVersion(supported: *mut nvmlVgpuVersion_t, current: *mut nvmlVgpuVersion_t);
+            fn nvmlSetVgpuVersion(vgpuVersion: *mut nvmlVgpuVersion_t);
+            fn nvmlDeviceGetVgpuUtilization(
+                device: nvmlDevice_t,
+                lastSeenTimeStamp: raw::c_ulonglong,
+                sampleValType: *mut nvmlValueType_t,
+                vgpuInstanceSamplesCount: *mut raw::c_uint,
+                utilizationSamples: *mut nvmlVgpuInstanceUtilizationSample_t,
+            );
+            fn nvmlDeviceGetVgpuInstancesUtilizationInfo(
+                device: nvmlDevice_t,
+                vgpuUtilInfo: *mut nvmlVgpuInstancesUtilizationInfo_t,
+            );
+            fn nvmlDeviceGetVgpuProcessUtilization(
+                device: nvmlDevice_t,
+                lastSeenTimeStamp: raw::c_ulonglong,
+                vgpuProcessSamplesCount: *mut raw::c_uint,
+                utilizationSamples: *mut nvmlVgpuProcessUtilizationSample_t,
+            );
+            fn nvmlDeviceGetVgpuProcessesUtilizationInfo(
+                device: nvmlDevice_t,
+                vgpuProcUtilInfo: *mut nvmlVgpuProcessesUtilizationInfo_t,
+            );
+            fn nvmlVgpuInstanceGetAccountingMode(
+                vgpuInstance: nvmlVgpuInstance_t,
+                mode: *mut nvmlEnableState_t,
+            );
+            fn nvmlVgpuInstanceGetAccountingPids(
+                vgpuInstance: nvmlVgpuInstance_t,
+                count: *mut raw::c_uint,
+                pids: *mut raw::c_uint,
+            );
+            fn nvmlVgpuInstanceGetAccountingStats(
+                vgpuInstance: nvmlVgpuInstance_t,
+                pid: raw::c_uint,
+                stats: *mut nvmlAccountingStats_t,
+            );
+            fn nvmlVgpuInstanceClearAccountingPids(vgpuInstance: nvmlVgpuInstance_t);
+            fn nvmlVgpuInstanceGetLicenseInfo_v2(
+                vgpuInstance: nvmlVgpuInstance_t,
+                licenseInfo: *mut nvmlVgpuLicenseInfo_t,
+            );
+            fn nvmlGetExcludedDeviceCount(deviceCount: *mut raw::c_uint);
+            fn nvmlGetExcludedDeviceInfoByIndex(index: raw::c_uint, info: *mut nvmlExcludedDeviceInfo_t);
+            fn nvmlDeviceReadWritePRM_v1(device: nvmlDevice_t, buffer: *mut nvmlPRMTLV_v1_t);
+            fn nvmlDeviceSetMigMode(
+                device: nvmlDevice_t,
+                mode: raw::c_uint,
+                activationStatus: *mut nvmlReturn_t,
+            );
+            fn nvmlDeviceGetMigMode(
+                device: nvmlDevice_t,
+                currentMode: *mut raw::c_uint,
+                pendingMode: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetGpuInstanceProfileInfo(
+                device: nvmlDevice_t,
+                profile: raw::c_uint,
+                info: *mut nvmlGpuInstanceProfileInfo_t,
+            );
+            fn nvmlDeviceGetGpuInstanceProfileInfoV(
+                device: nvmlDevice_t,
+                profile: raw::c_uint,
+                info: *mut nvmlGpuInstanceProfileInfo_v2_t,
+            );
+            fn nvmlDeviceGetGpuInstanceProfileInfoByIdV(
+                device: nvmlDevice_t,
+                profileId: raw::c_uint,
+                info: *mut nvmlGpuInstanceProfileInfo_v2_t,
+            );
+            fn nvmlDeviceGetGpuInstancePossiblePlacements_v2(
+                device: nvmlDevice_t,
+                profileId: raw::c_uint,
+                placements: *mut nvmlGpuInstancePlacement_t,
+                count: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetGpuInstanceRemainingCapacity(
+                device: nvmlDevice_t,
+                profileId: raw::c_uint,
+                count: *mut raw::c_uint,
+            );
+            fn nvmlDeviceCreateGpuInstance(
+                device: nvmlDevice_t,
+                profileId: raw::c_uint,
+                gpuInstance: *mut nvmlGpuInstance_t,
+            );
+            fn nvmlDeviceCreateGpuInstanceWithPlacement(
+                device: nvmlDevice_t,
+                profileId: raw::c_uint,
+                placement: *const nvmlGpuInstancePlacement_t,
+                gpuInstance: *mut nvmlGpuInstance_t,
+            );
+            fn nvmlGpuInstanceDestroy(gpuInstance: nvmlGpuInstance_t);
+            fn nvmlDeviceGetGpuInstances(
+                device: nvmlDevice_t,
+                profileId: raw::c_uint,
+                gpuInstances: *mut nvmlGpuInstance_t,
+                count: *mut raw::c_uint,
+            );
+            fn nvmlDeviceGetGpuInstanceById(
+                device: nvmlDevice_t,
+                id: raw::c_uint,
+                gpuInstance: *mut nvmlGpuInstance_t,
+            );
+            fn nvmlGpuInstanceGetInfo(gpuInstance: nvmlGpuInstance_t, info: *mut nvmlGpuInstanceInfo_t);
+            fn nvmlGpuInstanceGetComputeInstanceProfileInfo(
+                gpuInstance: nvmlGpuInstance_t,
+                profile: raw::c_uint,
+                engProfile: raw::c_uint,
+                info: *mut nvmlComputeInstanceProfileInfo_t,
+            );
+            fn nvmlGpuInstanceGetComputeInstanceProfileInfoV(
+                gpuInstance: nvmlGpuInstance_t,
+                profile: raw::c_uint,
+                engProfile: raw::c_uint,
+                info: *mut nvmlComputeInstanceProfileInfo_v2_t,
+            );
+            fn nvmlGpuInstanceGetComputeInstanceRemainingCapacity(
+                gpuInstance: nvmlGpuInstance_t,
+                profileId: raw::c_uint,
+                count: *mut raw::c_uint,
+            );
+            fn nvmlGpuInstanceGetComputeInstancePossiblePlacements(
+                gpuInstance: nvmlGpuInstance_t,
+                profileId: raw::c_uint,
+                placements: *mut nvmlComputeInstancePlacement_t,
+                count: *mut raw::c_uint,
+            );
+            fn nvmlGpuInstanceCreateComputeInstance(
+                gpuInstance: nvmlGpuInstance_t,
+                profileId: raw::c_uint,
+                computeInstance: *mut nvmlComputeInstance_t,
+            );
+            fn nvmlGpuInstanceCreateComputeInstanceWithPlacement(
+                gpuInstance: nvmlGpuInstance_t,
+                profileId: raw::c_uint,
+                placement: *const nvmlComputeInstancePlacement_t,
+                computeInstance: *mut nvmlComputeInstance_t,
+            );
+            fn nvmlComputeInstanceDestroy(computeInstance: nvmlComputeInstance_t);
+            fn nvmlGpuInstanceGetComputeInstances(
+                gpuInstance: nvmlGpuInstance_t,
+                profileId: raw::c_uint,
+                computeInstances: *mut nvmlComputeInstance_t,
+                count: *mut raw::c_uint,
+            );
+            fn nvmlGpuInstanceGetComputeInstanceById(
+                gpuInstance: nvmlGpuInstance_t,
+                id: raw::c_uint,
+                computeInstance: *mut nvmlComputeInstance_t,
+            );
+            fn nvmlComputeInstanceGetInfo_v2(
+                computeInstance: nvmlComputeInstance_t,
+                info: *mut nvmlComputeInstanceInfo_t,
+            );
+            fn nvmlDeviceIsMigDeviceHandle(device: nvmlDevice_t, isMigDevice: *mut raw::c_uint);
+            fn nvmlDeviceGetGpuInstanceId(device: nvmlDevice_t, id: *mut raw::c_uint);
+            fn nvmlDeviceGetComputeInstanceId(device: nvmlDevice_t, id: *mut raw::c_uint);
+            fn nvmlDeviceGetMaxMigDeviceCount(device: nvmlDevice_t, count: *mut raw::c_uint);
+            fn nvmlDeviceGetMigDeviceHandleByIndex(
+                device: nvmlDevice_t,
+                index: raw::c_uint,
+                migDevice: *mut nvmlDevice_t,
+            );
+            fn nvmlDeviceGetDeviceHandleFromMigDeviceHandle(
+                migDevice: nvmlDevice_t,
+                device: *mut nvmlDevice_t,
+            );
+            fn nvmlGpmMetricsGet(metricsGet: *mut nvmlGpmMetricsGet_t);
+            fn nvmlGpmSampleFree(gpmSample: nvmlGpmSample_t);
+            fn nvmlGpmSampleAlloc(gpmSample: *mut nvmlGpmSample_t);
+            fn nvmlGpmSampleGet(device: nvmlDevice_t, gpmSample: nvmlGpmSample_t);
+            fn nvmlGpmMigSampleGet(
+                device: nvmlDevice_t,
+                gpuInstanceId: raw::c_uint,
+                gpmSample: nvmlGpmSample_t,
+            );
+            fn nvmlGpmQueryDeviceSupport(device: nvmlDevice_t, gpmSupport: *mut nvmlGpmSupport_t);
+            fn nvmlGpmQueryIfStreamingEnabled(device: nvmlDevice_t, state: *mut raw::c_uint);
+            fn nvmlGpmSetStreamingEnabled(device: nvmlDevice_t, state: raw::c_uint);
+            fn nvmlDeviceGetCapabilities(device: nvmlDevice_t, caps: *mut nvmlDeviceCapabilities_t);
+            fn nvmlDeviceWorkloadPowerProfileGetProfilesInfo(
+                device: nvmlDevice_t,
+                profilesInfo: *mut nvmlWorkloadPowerProfileProfilesInfo_t,
+            );
+            fn nvmlDeviceWorkloadPowerProfileGetCurrentProfiles(
+                device: nvmlDevice_t,
+                currentProfiles: *mut nvmlWorkloadPowerProfileCurrentProfiles_t,
+            );
+            fn nvmlDeviceWorkloadPowerProfileSetRequestedProfiles(
+                device: nvmlDevice_t,
+                requestedProfiles: *mut nvmlWorkloadPowerProfileRequestedProfiles_t,
+            );
+            fn nvmlDeviceWorkloadPowerProfileClearRequestedProfiles(
+                device: nvmlDevice_t,
+                requestedProfiles: *mut nvmlWorkloadPowerProfileRequestedProfiles_t,
+            );
+            fn nvmlDevicePowerSmoothingActivatePresetProfile(
+                device: nvmlDevice_t,
+                profile: *mut nvmlPowerSmoothingProfile_t,
+            );
+            fn nvmlDevicePowerSmoothingUpdatePresetProfileParam(
+                device: nvmlDevice_t,
+                profile: *mut nvmlPowerSmoothingProfile_t,
+            );
+            fn nvmlDevicePowerSmoothingSetState(
+                device: nvmlDevice_t,
+                state: *mut nvmlPowerSmoothingState_t,
+            );
+            fn nvmlDeviceGetSramUniqueUncorrectedEccErrorCounts(
+                device: nvmlDevice_t,
+                errorCounts: *mut nvmlEccSramUniqueUncorrectedErrorCounts_t,
+            );
+            #[cfg(feature = "legacy-functions")]
+            fn nvmlInit();
+            #[cfg(feature = "legacy-functions")]
+            fn nvmlDeviceGetCount(deviceCount: *mut raw::c_uint);
+            #[cfg(feature = "legacy-functions")]
+            fn nvmlDeviceGetHandleByIndex(index: raw::c_uint, device: *mut nvmlDevice_t);
+            #[cfg(feature = "legacy-functions")]
+            fn nvmlDeviceGetHandleByPciBusId(pciBusId: *const raw::c_char, device: *mut nvmlDevice_t);
+            #[cfg(feature = "legacy-functions")]
+            fn nvmlDeviceGetPciInfo(device: nvmlDevice_t, pci: *mut nvmlPciInfo_t);
+            #[cfg(feature = "legacy-functions")]
+            fn nvmlDeviceGetPciInfo_v2(device: nvmlDevice_t, pci: *mut nvmlPciInfo_t);
+            #[cfg(feature = "legacy-functions")]
+            fn nvmlDeviceGetNvLinkRemotePciInfo(
+                device: nvmlDevice_t,
+                link: raw::c_uint,
+                pci: *mut nvmlPciInfo_t,
+            );
+            #[cfg(feature = "legacy-functions")]
+            fn nvmlDeviceGetGridLicensableFeatures(
+                device: nvmlDevice_t,
+                pGridLicensableFeatures: *mut nvmlGridLicensableFeatures_t,
+            );
+            #[cfg(feature = "legacy-functions")]
+            fn nvmlDeviceGetGridLicensableFeatures_v2(
+                device: nvmlDevice_t,
+                pGridLicensableFeatures: *mut nvmlGridLicensableFeatures_t,
+            );
+            #[cfg(feature = "legacy-functions")]
+            fn nvmlDeviceGetGridLicensableFeatures_v3(
+                device: nvmlDevice_t,
+                pGridLicensableFeatures: *mut nvmlGridLicensableFeatures_t,
+            );
+            #[cfg(feature = "legacy-functions")]
+            fn nvmlDeviceRemoveGpu(pciInfo: *mut nvmlPciInfo_t);
+            #[cfg(feature = "legacy-functions")]
+            fn nvmlEventSetWait(set: nvmlEventSet_t, data: *mut nvmlEventData_t, timeoutms: raw::c_uint);
+            #[cfg(feature = "legacy-functions")]
+            fn nvmlDeviceGetAttributes(device: nvmlDevice_t, attributes: *mut nvmlDeviceAttributes_t);
+            #[cfg(feature = "legacy-functions")]
+            fn nvmlComputeInstanceGetInfo(
+                computeInstance: nvmlComputeInstance_t,
+                info: *mut nvmlComputeInstanceInfo_t,
+            );
+            #[cfg(feature = "legacy-functions")]
+            fn nvmlDeviceGetComputeRunningProcesses(
+                device: nvmlDevice_t,
+                infoCount: *mut raw::c_uint,
+                infos: *mut nvmlProcessInfo_v1_t,
+            );
+            #[cfg(feature = "legacy-functions")]
+            fn nvmlDeviceGetComputeRunningProcesses_v2(
+                device: nvmlDevice_t,
+                infoCount: *mut raw::c_uint,
+                infos: *mut nvmlProcessInfo_v2_t,
+            );
+            #[cfg(feature = "legacy-functions")]
+            fn nvmlDeviceGetGraphicsRunningProcesses(
+                device: nvmlDevice_t,
+                infoCount: *mut raw::c_uint,
+                infos: *mut nvmlProcessInfo_v1_t,
+            );
+            #[cfg(feature = "legacy-functions")]
+            fn nvmlDeviceGetGraphicsRunningProcesses_v2(
+                device: nvmlDevice_t,
+                infoCount: *mut raw::c_uint,
+                infos: *mut nvmlProcessInfo_v2_t,
+            );
+            #[cfg(feature = "legacy-functions")]
+            fn nvmlDeviceGetMPSComputeRunningProcesses(
+                device: nvmlDevice_t,
+                infoCount: *mut raw::c_uint,
+                infos: *mut nvmlProcessInfo_v1_t,
+            );
+            #[cfg(feature = "legacy-functions")]
+            fn nvmlDeviceGetMPSComputeRunningProcesses_v2(
+                device: nvmlDevice_t,
+                infoCount: *mut raw::c_uint,
+                infos: *mut nvmlProcessInfo_v2_t,
+            );
+            #[cfg(feature = "legacy-functions")]
+            fn nvmlDeviceGetGpuInstancePossiblePlacements(
+                device: nvmlDevice_t,
+                profileId: raw::c_uint,
+                placements: *mut nvmlGpuInstancePlacement_t,
+                count: *mut raw::c_uint,
+            );
+            #[cfg(feature = "legacy-functions")]
+            fn nvmlVgpuInstanceGetLicenseInfo(
+                vgpuInstance: nvmlVgpuInstance_t,
+                licenseInfo: *mut nvmlVgpuLicenseInfo_t,
+            );
+            fn nvmlDeviceGetDriverModel(
+                device: nvmlDevice_t,
+                current: *mut nvmlDriverModel_t,
+                pending: *mut nvmlDriverModel_t,
+            );
+        }
+    };
+}
+
+pub(crate) use nvml_functions;
+
+nvml_functions!(nvml_backend);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bitmasks::InitFlags;
+    use crate::Nvml;
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    #[derive(Default)]
+    struct Calls {
+        init_flags: AtomicU32,
+        shutdown: AtomicBool,
+        closed: AtomicBool,
+    }
+
+    /// A single GPU with 8 GiB of memory, 1 GiB of which is in use.
+    struct Mock(Arc<Calls>);
+
+    impl NvmlBackend for Mock {
+        fn close(self: Box<Self>) -> Result<(), NvmlError> {
+            self.0.closed.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+
+        unsafe fn nvmlInit_v2(&self) -> Result<nvmlReturn_t, NvmlError> {
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlInitWithFlags(&self, flags: raw::c_uint) -> Result<nvmlReturn_t, NvmlError> {
+            self.0.init_flags.store(flags, Ordering::SeqCst);
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlShutdown(&self) -> Result<nvmlReturn_t, NvmlError> {
+            self.0.shutdown.store(true, Ordering::SeqCst);
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetCount_v2(
+            &self,
+            count: *mut raw::c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *count = 1;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetHandleByIndex_v2(
+            &self,
+            index: raw::c_uint,
+            device: *mut nvmlDevice_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            if index != 0 {
+                return Ok(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT);
+            }
+
+            *device = 1 as nvmlDevice_t;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetMemoryInfo_v2(
+            &self,
+            device: nvmlDevice_t,
+            memory: *mut nvmlMemory_v2_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            assert_eq!(device, 1 as nvmlDevice_t);
+
+            (*memory).total = 8 << 30;
+            (*memory).used = 1 << 30;
+            (*memory).free = 7 << 30;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+    }
+
+    fn mock() -> (Nvml, Arc<Calls>) {
+        let calls = Arc::new(Calls::default());
+        let nvml = Nvml::builder()
+            .init_with_backend(Mock(calls.clone()))
+            .expect("initialized mock");
+
+        (nvml, calls)
+    }
+
+    #[test]
+    fn device_methods_call_into_backend() {
+        let (nvml, _) = mock();
+
+        assert_eq!(nvml.device_count().unwrap(), 1);
+
+        let memory = nvml.device_by_index(0).unwrap().memory_info().unwrap();
+        assert_eq!(memory.total, 8 << 30);
+        assert_eq!(memory.used, 1 << 30);
+
+        assert!(matches!(
+            nvml.device_by_index(1),
+            Err(NvmlError::InvalidArg)
+        ));
+    }
+
+    #[test]
+    fn missing_functions_fail_to_load() {
+        let (nvml, _) = mock();
+
+        match nvml.sys_driver_version() {
+            Err(NvmlError::FailedToLoadSymbol(msg)) => {
+                assert!(msg.contains("nvmlSystemGetDriverVersion"))
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn init_flags_are_forwarded() {
+        let calls = Arc::new(Calls::default());
+        Nvml::builder()
+            .flags(InitFlags::NO_GPUS)
+            .init_with_backend(Mock(calls.clone()))
+            .unwrap();
+
+        assert_eq!(
+            calls.init_flags.load(Ordering::SeqCst),
+            InitFlags::NO_GPUS.bits()
+        );
+    }
+
+    #[test]
+    fn shutdown_closes_backend() {
+        let (nvml, calls) = mock();
+        nvml.shutdown().unwrap();
+
+        assert!(calls.shutdown.load(Ordering::SeqCst));
+        assert!(calls.closed.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn drop_shuts_down() {
+        let (nvml, calls) = mock();
+        drop(nvml);
+
+        assert!(calls.shutdown.load(Ordering::SeqCst));
+        assert!(!calls.closed.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    #[should_panic(expected = "not backed by the NVML library")]
+    fn lib_panics_for_other_backends() {
+        let (nvml, _) = mock();
+        nvml.lib();
+    }
+}
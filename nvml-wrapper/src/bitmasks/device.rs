@@ -1,5 +1,6 @@
 #![allow(deprecated)]
 
+use crate::enums::device::ProcessMode;
 use crate::ffi::bindings::*;
 use bitflags::bitflags;
 #[cfg(feature = "serde")]
@@ -7,6 +8,8 @@ use serde_derive::{Deserialize, Serialize};
 
 bitflags! {
     /// Flags used to specify why a GPU is throttling.
+    ///
+    /// NVIDIA has deprecated these in favour of `ClocksEventReasons`.
     // Checked against local
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -73,6 +76,110 @@ bitflags! {
     }
 }
 
+bitflags! {
+    /**
+    Flags used to specify why a GPU's clocks are being held below their
+    maximum.
+
+    This replaces `ThrottleReasons`, which NVIDIA has deprecated. The bindings
+    bundled with this wrapper don't define any newer clocks event reasons yet,
+    so for now the flags are identical to `ThrottleReasons`, and the two
+    convert into each other with `From`.
+    */
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+    pub struct ClocksEventReasons: u64 {
+        /// Nothing is running on the GPU.
+        const GPU_IDLE                    = nvmlClocksEventReasonGpuIdle as u64;
+        /// GPU clocks are limited by the current applications clocks setting.
+        const APPLICATIONS_CLOCKS_SETTING = nvmlClocksEventReasonApplicationsClocksSetting as u64;
+        /// Software power scaling algorithm is reducing clocks.
+        const SW_POWER_CAP                = nvmlClocksEventReasonSwPowerCap as u64;
+        /**
+        Hardware slowdown (reducing the core clocks by a factor of 2 or more)
+        is engaged.
+
+        This is an indicator of:
+
+        * Temperature being too high
+        * External Power Brake Asseration being triggered (e.g. by the system power supply)
+        * Power draw being too high and Fast Trigger protection reducing the clocks
+        */
+        const HW_SLOWDOWN                 = nvmlClocksThrottleReasonHwSlowdown as u64;
+        /**
+        This GPU is being held at lower clocks by another GPU in its sync boost
+        group.
+
+        All GPUs in a sync boost group will boost to the minimum possible clocks
+        across the entire group. Look at the clocks event reasons for other GPUs
+        in the system to find out why this GPU is being held at lower clocks.
+        */
+        const SYNC_BOOST                  = nvmlClocksEventReasonSyncBoost as u64;
+        /**
+        Software thermal slowdown.
+
+        This is an indicator of one or more of the following:
+
+        * The current GPU temperature is above the max GPU operating temperature
+        * The current memory temperature is above the max memory operating temperature
+        */
+        const SW_THERMAL_SLOWDOWN         = nvmlClocksEventReasonSwThermalSlowdown as u64;
+        /**
+        Hardware thermal slowdown is engaged, reducing core clocks by 2x or more.
+
+        This indicates that the temperature of the GPU is too high.
+        */
+        const HW_THERMAL_SLOWDOWN         = nvmlClocksThrottleReasonHwThermalSlowdown as u64;
+        /**
+        Hardware power brake slowdown is engaged, reducing core clocks by 2x or more.
+
+        This indicates that an external power brake assertion is being triggered,
+        such as by the system power supply.
+        */
+        const HW_POWER_BRAKE_SLOWDOWN     = nvmlClocksThrottleReasonHwPowerBrakeSlowdown as u64;
+        /// GPU clocks are limited by the current setting of display clocks.
+        const DISPLAY_CLOCK_SETTING       = nvmlClocksEventReasonDisplayClockSetting as u64;
+        /// Clocks are as high as possible.
+        const NONE                        = nvmlClocksEventReasonNone as u64;
+    }
+}
+
+impl From<ThrottleReasons> for ClocksEventReasons {
+    fn from(reasons: ThrottleReasons) -> Self {
+        Self::from_bits_retain(reasons.bits())
+    }
+}
+
+impl From<ClocksEventReasons> for ThrottleReasons {
+    fn from(reasons: ClocksEventReasons) -> Self {
+        Self::from_bits_retain(reasons.bits())
+    }
+}
+
+bitflags! {
+    /// The kinds of context a process has on a GPU, in `GpuProcess`.
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+    pub struct ProcessTypes: u32 {
+        /// A compute context (such as a CUDA application).
+        const COMPUTE  = 1 << 0;
+        /// A graphics context (OpenGL, DirectX).
+        const GRAPHICS = 1 << 1;
+        /// A compute context as an MPS client.
+        const MPS      = 1 << 2;
+    }
+}
+
+impl From<ProcessMode> for ProcessTypes {
+    fn from(mode: ProcessMode) -> Self {
+        match mode {
+            ProcessMode::Compute => Self::COMPUTE,
+            ProcessMode::Graphics => Self::GRAPHICS,
+            ProcessMode::Mps => Self::MPS,
+        }
+    }
+}
+
 bitflags! {
     /// Flags specifying the PowerMizer modes supported by a GPU.
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -104,3 +211,49 @@ bitflags! {
         const CAPTURE_WITH_WAIT_TIMEOUT   = NVML_NVFBC_SESSION_FLAG_CAPTURE_WITH_WAIT_TIMEOUT;
     }
 }
+
+bitflags! {
+    /// Virtualization capabilities reported in vGPU and physical GPU metadata.
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+    pub struct VgpuVirtualizationCaps: u32 {
+        /// The vGPU (or physical GPU) supports migration.
+        const MIGRATION = NVML_VGPU_VIRTUALIZATION_CAP_MIGRATION_YES;
+    }
+}
+
+bitflags! {
+    /// The kinds of VM migration a vGPU is compatible with on a physical GPU.
+    ///
+    /// Empty if the vGPU can't be moved to the physical GPU at all.
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+    pub struct VgpuVmCompatibility: u32 {
+        /// The VM can be moved after being shut down.
+        const COLD      = nvmlVgpuVmCompatibility_enum_NVML_VGPU_VM_COMPATIBILITY_COLD;
+        /// The VM can be moved after being hibernated.
+        const HIBERNATE = nvmlVgpuVmCompatibility_enum_NVML_VGPU_VM_COMPATIBILITY_HIBERNATE;
+        /// The VM can be moved while asleep.
+        const SLEEP     = nvmlVgpuVmCompatibility_enum_NVML_VGPU_VM_COMPATIBILITY_SLEEP;
+        /// The VM can be moved while running.
+        const LIVE      = nvmlVgpuVmCompatibility_enum_NVML_VGPU_VM_COMPATIBILITY_LIVE;
+    }
+}
+
+bitflags! {
+    /// What limits the compatibility of a vGPU with a physical GPU.
+    ///
+    /// Empty if nothing does.
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+    pub struct VgpuCompatibilityLimit: u32 {
+        /// The host driver of the physical GPU.
+        const HOST_DRIVER  = nvmlVgpuPgpuCompatibilityLimitCode_enum_NVML_VGPU_COMPATIBILITY_LIMIT_HOST_DRIVER;
+        /// The guest driver in the vGPU's VM.
+        const GUEST_DRIVER = nvmlVgpuPgpuCompatibilityLimitCode_enum_NVML_VGPU_COMPATIBILITY_LIMIT_GUEST_DRIVER;
+        /// The physical GPU itself.
+        const GPU          = nvmlVgpuPgpuCompatibilityLimitCode_enum_NVML_VGPU_COMPATIBILITY_LIMIT_GPU;
+        /// Something else.
+        const OTHER        = nvmlVgpuPgpuCompatibilityLimitCode_enum_NVML_VGPU_COMPATIBILITY_LIMIT_OTHER;
+    }
+}
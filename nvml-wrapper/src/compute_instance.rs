@@ -0,0 +1,476 @@
+use crate::error::{nvml_try, NvmlError};
+use crate::ffi::bindings::*;
+use crate::struct_wrappers::device::ComputeInstanceInfo;
+use crate::{Device, GpuInstance, Nvml};
+
+use std::mem;
+
+use static_assertions::assert_impl_all;
+
+/**
+A MIG compute instance: a partition of a `GpuInstance`'s multiprocessors.
+
+Obtain one by creating it with `GpuInstance.create_compute_instance()` (or
+`create_compute_instance_with_placement()`), or look up existing ones with
+`GpuInstance.compute_instances()` and `GpuInstance.compute_instance_by_id()`.
+
+Like GPU instances, compute instances are part of the device's configuration:
+dropping a `ComputeInstance` leaves the instance in place until
+[`destroy()`](Self::destroy) is called.
+
+CUDA applications and most `Device` methods see a compute instance as a MIG
+device; [`mig_device()`](Self::mig_device) gets that `Device`.
+
+Lifetimes are used to enforce that each `ComputeInstance` instance cannot be
+used after the `Nvml` instance it was obtained from is dropped.
+*/
+#[derive(Debug)]
+pub struct ComputeInstance<'nvml> {
+    instance: nvmlComputeInstance_t,
+    gpu_instance: nvmlGpuInstance_t,
+    device: nvmlDevice_t,
+    nvml: &'nvml Nvml,
+}
+
+unsafe impl Send for ComputeInstance<'_> {}
+unsafe impl Sync for ComputeInstance<'_> {}
+
+assert_impl_all!(ComputeInstance: Send, Sync);
+
+impl<'nvml> ComputeInstance<'nvml> {
+    /**
+    Create a new `ComputeInstance` wrapper.
+
+    You will most likely never need to call this; see the methods available to
+    you on `GpuInstance` to get one.
+
+    # Safety
+
+    It is your responsibility to ensure that the given `nvmlComputeInstance_t`
+    pointer is valid and belongs to `gpu_instance`.
+    */
+    pub unsafe fn new(instance: nvmlComputeInstance_t, gpu_instance: &GpuInstance<'nvml>) -> Self {
+        Self {
+            instance,
+            gpu_instance: gpu_instance.handle(),
+            device: gpu_instance.device().handle(),
+            nvml: gpu_instance.nvml(),
+        }
+    }
+
+    /// Access the `Nvml` reference this struct wraps
+    pub fn nvml(&self) -> &'nvml Nvml {
+        self.nvml
+    }
+
+    /// The `GpuInstance` this compute instance is a partition of.
+    pub fn gpu_instance(&self) -> GpuInstance<'nvml> {
+        unsafe { GpuInstance::new(self.gpu_instance, &self.device()) }
+    }
+
+    /// The (physical) `Device` this compute instance's GPU instance is a
+    /// partition of.
+    pub fn device(&self) -> Device<'nvml> {
+        unsafe { Device::new(self.device, self.nvml) }
+    }
+
+    /// Get the raw compute instance handle contained in this struct
+    ///
+    /// Sometimes necessary for C interop.
+    ///
+    /// # Safety
+    ///
+    /// This is unsafe to prevent it from being used without care.
+    pub unsafe fn handle(&self) -> nvmlComputeInstance_t {
+        self.instance
+    }
+
+    /**
+    Gets the ID, profile and placement of this compute instance.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `ComputeInstance` is invalid
+    * `NotSupported`, if the device doesn't support MIG
+    * `NoPermission`, if the user doesn't have permission to perform this
+      operation
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlComputeInstanceGetInfo_v2")]
+    pub fn info(&self) -> Result<ComputeInstanceInfo, NvmlError> {
+        unsafe {
+            let mut info: nvmlComputeInstanceInfo_t = mem::zeroed();
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlComputeInstanceGetInfo_v2(self.instance, &mut info)?,
+            )?;
+
+            Ok(info.into())
+        }
+    }
+
+    /**
+    Gets the MIG device that corresponds to this compute instance, as returned
+    by `Device.mig_device_by_index()` on the parent device.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `ComputeInstance` is invalid
+    * `NotSupported`, if the device doesn't support MIG
+    * `NotFound`, if no MIG device matches this compute instance (e.g. because
+      it was destroyed)
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    pub fn mig_device(&self) -> Result<Device<'nvml>, NvmlError> {
+        let gpu_instance_id = self.gpu_instance().info()?.id;
+        let id = self.info()?.id;
+        let parent = self.device();
+
+        for index in 0..parent.mig_device_count()? {
+            let mig_device = match parent.mig_device_by_index(index) {
+                Ok(mig_device) => mig_device,
+                // Indices without a MIG device behind them
+                Err(NvmlError::NotFound) | Err(NvmlError::InvalidArg) => continue,
+                Err(e) => return Err(e),
+            };
+
+            if mig_device.mig_gpu_instance_id()? == gpu_instance_id
+                && mig_device.mig_compute_instance_id()? == id
+            {
+                return Ok(mig_device);
+            }
+        }
+
+        Err(NvmlError::NotFound)
+    }
+
+    /**
+    Destroys this compute instance.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `ComputeInstance` is invalid
+    * `NotSupported`, if the device doesn't support MIG
+    * `NoPermission`, if the user doesn't have permission to perform this
+      operation
+    * `InUse`, if processes are running on the compute instance
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlComputeInstanceDestroy")]
+    pub fn destroy(self) -> Result<(), NvmlError> {
+        unsafe {
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlComputeInstanceDestroy(self.instance)?,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::backend::NvmlBackend;
+    use crate::enums::device::{ComputeInstanceEngineProfile, ComputeInstanceProfile};
+    use crate::error::NvmlError;
+    use crate::ffi::bindings::*;
+    use crate::struct_wrappers::device::{ComputeInstanceInfo, ComputeInstancePlacement};
+    use crate::test_utils::{mock_backend, mock_device};
+    use crate::Nvml;
+    use std::os::raw::c_uint;
+    use std::sync::Mutex;
+
+    /// One GPU with a single GPU instance (ID 1) of 4 slices, in which compute
+    /// instances of one profile (ID 0) of 1 slice can be created. Each existing
+    /// compute instance is a MIG device, at the index it was created at.
+    #[derive(Default)]
+    struct Mock {
+        /// The placements of the existing instances, by instance ID - 1.
+        instances: Mutex<Vec<Option<c_uint>>>,
+    }
+
+    const PROFILE: c_uint = 0;
+    const DEVICE: nvmlDevice_t = mock_device(0);
+    const GPU_INSTANCE: nvmlGpuInstance_t = 1 as nvmlGpuInstance_t;
+    /// MIG device handles are this plus the compute instance ID.
+    const MIG_DEVICE: usize = 100;
+
+    /// The index into `Mock::instances` of the instance with the given handle,
+    /// which start at 1.
+    fn instance_index(instance: nvmlComputeInstance_t) -> Option<usize> {
+        (instance as usize).checked_sub(1)
+    }
+
+    impl NvmlBackend for Mock {
+        mock_backend!();
+
+        unsafe fn nvmlDeviceGetGpuInstanceById(
+            &self,
+            _device: nvmlDevice_t,
+            id: c_uint,
+            instance: *mut nvmlGpuInstance_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            if id != 1 {
+                return Ok(nvmlReturn_enum_NVML_ERROR_NOT_FOUND);
+            }
+
+            *instance = GPU_INSTANCE;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlGpuInstanceGetInfo(
+            &self,
+            _instance: nvmlGpuInstance_t,
+            info: *mut nvmlGpuInstanceInfo_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *info = nvmlGpuInstanceInfo_t {
+                device: DEVICE,
+                id: 1,
+                profileId: 9,
+                placement: nvmlGpuInstancePlacement_t { start: 0, size: 4 },
+            };
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlGpuInstanceGetComputeInstanceProfileInfoV(
+            &self,
+            _instance: nvmlGpuInstance_t,
+            profile: c_uint,
+            engine_profile: c_uint,
+            info: *mut nvmlComputeInstanceProfileInfo_v2_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            if profile != NVML_COMPUTE_INSTANCE_PROFILE_1_SLICE
+                || engine_profile != NVML_COMPUTE_INSTANCE_ENGINE_PROFILE_SHARED
+            {
+                return Ok(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED);
+            }
+
+            let name = b"1c.4g.20gb\0";
+            (*info).id = PROFILE;
+            (*info).sliceCount = 1;
+            (*info).instanceCount = 4;
+            for (i, &c) in name.iter().enumerate() {
+                (*info).name[i] = c as _;
+            }
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlGpuInstanceGetComputeInstancePossiblePlacements(
+            &self,
+            _instance: nvmlGpuInstance_t,
+            _profile_id: c_uint,
+            placements: *mut nvmlComputeInstancePlacement_t,
+            count: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            if !placements.is_null() {
+                for start in 0..4 {
+                    *placements.add(start as usize) =
+                        nvmlComputeInstancePlacement_t { start, size: 1 };
+                }
+            }
+            *count = 4;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlGpuInstanceCreateComputeInstanceWithPlacement(
+            &self,
+            _instance: nvmlGpuInstance_t,
+            _profile_id: c_uint,
+            placement: *const nvmlComputeInstancePlacement_t,
+            instance: *mut nvmlComputeInstance_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let mut instances = self.instances.lock().unwrap();
+            let start = (*placement).start;
+            if start >= 4 || instances.contains(&Some(start)) {
+                return Ok(nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_RESOURCES);
+            }
+
+            instances.push(Some(start));
+            *instance = instances.len() as nvmlComputeInstance_t;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlComputeInstanceDestroy(
+            &self,
+            instance: nvmlComputeInstance_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let mut instances = self.instances.lock().unwrap();
+            match instance_index(instance).and_then(|i| instances.get_mut(i)) {
+                Some(i @ Some(_)) => {
+                    *i = None;
+                    Ok(nvmlReturn_enum_NVML_SUCCESS)
+                }
+                _ => Ok(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT),
+            }
+        }
+
+        unsafe fn nvmlGpuInstanceGetComputeInstances(
+            &self,
+            _instance: nvmlGpuInstance_t,
+            _profile_id: c_uint,
+            instances: *mut nvmlComputeInstance_t,
+            count: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let existing = self.instances.lock().unwrap();
+            let mut n = 0;
+            for (i, _) in existing.iter().enumerate().filter(|(_, s)| s.is_some()) {
+                *instances.add(n) = (i + 1) as nvmlComputeInstance_t;
+                n += 1;
+            }
+            *count = n as c_uint;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlComputeInstanceGetInfo_v2(
+            &self,
+            instance: nvmlComputeInstance_t,
+            info: *mut nvmlComputeInstanceInfo_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let instances = self.instances.lock().unwrap();
+            let start = match instance_index(instance).and_then(|i| instances.get(i)) {
+                Some(Some(start)) => *start,
+                _ => return Ok(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT),
+            };
+
+            *info = nvmlComputeInstanceInfo_t {
+                device: DEVICE,
+                gpuInstance: GPU_INSTANCE,
+                id: instance as c_uint,
+                profileId: PROFILE,
+                placement: nvmlComputeInstancePlacement_t { start, size: 1 },
+            };
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetMaxMigDeviceCount(
+            &self,
+            _device: nvmlDevice_t,
+            count: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *count = 4;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetMigDeviceHandleByIndex(
+            &self,
+            _device: nvmlDevice_t,
+            index: c_uint,
+            mig_device: *mut nvmlDevice_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            match self.instances.lock().unwrap().get(index as usize) {
+                Some(Some(_)) => {
+                    *mig_device = (MIG_DEVICE + index as usize + 1) as nvmlDevice_t;
+                    Ok(nvmlReturn_enum_NVML_SUCCESS)
+                }
+                _ => Ok(nvmlReturn_enum_NVML_ERROR_NOT_FOUND),
+            }
+        }
+
+        unsafe fn nvmlDeviceGetGpuInstanceId(
+            &self,
+            _device: nvmlDevice_t,
+            id: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *id = 1;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetComputeInstanceId(
+            &self,
+            device: nvmlDevice_t,
+            id: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            match (device as usize).checked_sub(MIG_DEVICE) {
+                Some(compute_instance) => {
+                    *id = compute_instance as c_uint;
+                    Ok(nvmlReturn_enum_NVML_SUCCESS)
+                }
+                None => Ok(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED),
+            }
+        }
+    }
+
+    fn nvml() -> Nvml {
+        Nvml::builder()
+            .init_with_backend(Mock::default())
+            .expect("initialized mock")
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn create_resolve_and_destroy() {
+        let nvml = nvml();
+        let device = nvml.device_by_index(0).unwrap();
+        let gpu_instance = device.gpu_instance_by_id(1).unwrap();
+
+        let profile = gpu_instance
+            .compute_instance_profile_info(
+                ComputeInstanceProfile::OneSlice,
+                ComputeInstanceEngineProfile::Shared,
+            )
+            .unwrap();
+        assert_eq!(profile.name, "1c.4g.20gb");
+
+        let placements = gpu_instance
+            .compute_instance_possible_placements(profile.id)
+            .unwrap();
+        assert_eq!(placements.len(), 4);
+
+        let first = gpu_instance
+            .create_compute_instance_with_placement(profile.id, &placements[2])
+            .unwrap();
+        let second = gpu_instance
+            .create_compute_instance_with_placement(profile.id, &placements[0])
+            .unwrap();
+        assert!(matches!(
+            gpu_instance.create_compute_instance_with_placement(profile.id, &placements[2]),
+            Err(NvmlError::InsufficientResources)
+        ));
+
+        assert_eq!(
+            second.info().unwrap(),
+            ComputeInstanceInfo {
+                id: 2,
+                profile_id: PROFILE,
+                placement: ComputeInstancePlacement { start: 0, size: 1 },
+            }
+        );
+        assert_eq!(second.gpu_instance().info().unwrap().id, 1);
+
+        let mig_device = second.mig_device().unwrap();
+        assert_eq!(mig_device.mig_compute_instance_id().unwrap(), 2);
+        assert_eq!(unsafe { mig_device.handle() }, unsafe {
+            device.mig_device_by_index(1).unwrap().handle()
+        });
+
+        first.destroy().unwrap();
+        let remaining = gpu_instance.compute_instances(profile.id).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].info().unwrap().id, 2);
+        // Its MIG device is skipped over
+        assert_eq!(
+            remaining[0]
+                .mig_device()
+                .unwrap()
+                .mig_compute_instance_id()
+                .unwrap(),
+            2
+        );
+    }
+}
@@ -0,0 +1,1359 @@
+/*!
+A conformance harness that calls every read-only wrapper method against the
+NVML an `Nvml` instance talks to, and reports how each call went.
+
+Run it on each new driver / GPU combination, and against mock backends such as
+`nvml-wrapper-fake`, to spot regressions in the wrapper. A method that is
+`Supported` on one driver and `Failed` on the next for the same GPU is worth a
+look.
+
+```no_run
+use nvml_wrapper::conformance::Conformance;
+use nvml_wrapper::Nvml;
+
+# fn main() -> Result<(), Box<dyn std::error::Error>> {
+let nvml = Nvml::init()?;
+let report = Conformance::new(&nvml).reps(3).run();
+
+report.write_json(std::io::stdout())?;
+for check in report.failures() {
+    eprintln!("{}", check);
+}
+# Ok(())
+# }
+```
+
+# Probing
+
+Before calling anything else, the harness probes each device for what it has
+and what mode it's in: its fans, its active NvLinks, and whether MIG, vGPU host
+mode, GPM and accounting are enabled. Per-fan and per-link methods are then
+called for every fan and active link, and methods that can't apply (e.g. vGPU
+queries on a GPU that isn't a vGPU host) are reported as `Skipped` instead of
+being called.
+
+Methods that change state (`set_*()`, `clear_*()`, `reset_*()` and the like)
+are never called.
+
+# Classification
+
+Each call is classified as:
+
+* `Supported` if it succeeded, or failed with `NvmlError::NotFound` or
+  `NvmlError::NoData` (e.g. no utilization samples being available yet)
+* `NotSupported` or `NoPermission` if it failed with `NvmlError::NotSupported`
+  or `NvmlError::NoPermission`
+* `Unavailable` if the function is missing from the NVML library (or backend)
+* `Failed` for any other error, a panic, or if repeated calls (see
+  [`Conformance::reps()`]) were classified differently
+
+The `Failed` ones are what to look at; the rest describe the hardware and
+driver.
+
+# Report format
+
+[`Report::write_json()`] writes a single JSON object:
+
+```text
+{
+  "format": "nvml-wrapper-conformance", "version": 1,
+  "nvml_version": "12.535.54.03", "driver_version": "535.54.03", "cuda_driver_version": 12020,
+  "devices": [{"index": 0, "name": "...", "architecture": "Ampere", "fans": 1,
+               "active_links": [], "mig": false, "vgpu_host": false, "gpm": false,
+               "accounting": false}],
+  "checks": [
+    {"target": "system", "method": "device_count", "outcome": "supported"},
+    {"target": "device", "device": 0, "method": "temperature(Gpu)", "outcome": "supported"},
+    {"target": "link", "device": 0, "link": 2, "method": "version", "outcome": "not_supported"},
+    {"target": "unit", "unit": 0, "method": "psu_info", "outcome": "failed", "detail": "..."}
+  ]
+}
+```
+
+Values that couldn't be probed are `null`. `detail` is present for `skipped`
+(why) and `failed` (the error) outcomes. With the `serde` feature enabled,
+[`Report`] can also be (de)serialized directly.
+*/
+
+use crate::device::Device;
+use crate::enum_wrappers::device::*;
+use crate::enum_wrappers::nv_link::{Capability, ErrorCounter};
+use crate::enums::device::ProcessMode;
+use crate::enums::nv_link::Counter;
+use crate::enums::unit::TemperatureReading;
+use crate::error::NvmlError;
+#[cfg(target_os = "linux")]
+use crate::ffi::bindings::NVML_GPU_INSTANCE_PROFILE_COUNT;
+use crate::ffi::bindings::NVML_NVLINK_MAX_LINKS;
+use crate::nv_link::NvLink;
+use crate::unit::Unit;
+use crate::Nvml;
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+use std::any::Any;
+use std::fmt::{self, Display, Write as _};
+use std::io::{self, Write};
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+
+/// The value of the `format` field in JSON reports.
+pub const FORMAT: &str = "nvml-wrapper-conformance";
+/// The version of the JSON report format written by this version of the crate.
+pub const VERSION: u32 = 1;
+
+/// How a call went.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Outcome {
+    Supported,
+    NotSupported,
+    NoPermission,
+    /// The function is missing from the NVML library.
+    Unavailable,
+    /// The method wasn't called; the field says why.
+    Skipped(String),
+    /// The field describes what went wrong.
+    Failed(String),
+}
+
+impl Outcome {
+    /// Classify the result of a call.
+    pub fn of<T>(result: &Result<T, NvmlError>) -> Self {
+        match result {
+            // There being nothing to return is a successful answer
+            Ok(_) | Err(NvmlError::NotFound) | Err(NvmlError::NoData) => Outcome::Supported,
+            Err(NvmlError::NotSupported) => Outcome::NotSupported,
+            Err(NvmlError::NoPermission) => Outcome::NoPermission,
+            Err(NvmlError::FailedToLoadSymbol(_)) => Outcome::Unavailable,
+            Err(e) => Outcome::Failed(e.to_string()),
+        }
+    }
+
+    /// The name this outcome goes by in JSON reports, e.g. `not_supported`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Outcome::Supported => "supported",
+            Outcome::NotSupported => "not_supported",
+            Outcome::NoPermission => "no_permission",
+            Outcome::Unavailable => "unavailable",
+            Outcome::Skipped(_) => "skipped",
+            Outcome::Failed(_) => "failed",
+        }
+    }
+
+    /// Why the method was skipped or what went wrong, if applicable.
+    pub fn detail(&self) -> Option<&str> {
+        match self {
+            Outcome::Skipped(detail) | Outcome::Failed(detail) => Some(detail),
+            _ => None,
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Outcome::Failed(_))
+    }
+}
+
+/// What a method was called on.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Target {
+    /// `Nvml` itself.
+    System,
+    /// The device at the given index.
+    Device(u32),
+    /// The given NvLink of the device at the given index.
+    Link { device: u32, link: u32 },
+    /// The unit at the given index.
+    Unit(u32),
+}
+
+impl Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Target::System => write!(f, "system"),
+            Target::Device(index) => write!(f, "device {}", index),
+            Target::Link { device, link } => write!(f, "device {} link {}", device, link),
+            Target::Unit(index) => write!(f, "unit {}", index),
+        }
+    }
+}
+
+/// A single method call and how it went.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Check {
+    pub target: Target,
+    /// The method's name, followed by its arguments in parentheses if it takes
+    /// any (e.g. `clock(Graphics, Current)`).
+    pub method: String,
+    pub outcome: Outcome,
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}: {}",
+            self.target,
+            self.method,
+            self.outcome.name()
+        )?;
+
+        match self.outcome.detail() {
+            Some(detail) => write!(f, " ({})", detail),
+            None => Ok(()),
+        }
+    }
+}
+
+/// What was found out about a device before running checks against it.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Capabilities {
+    pub index: u32,
+    pub name: Option<String>,
+    pub architecture: Option<String>,
+    /// The number of fans, if known. Per-fan methods are skipped if not.
+    pub fans: Option<u32>,
+    pub active_links: Vec<u32>,
+    /// Whether MIG mode is currently enabled.
+    pub mig: bool,
+    /// Whether the device is in vGPU host mode.
+    pub vgpu_host: bool,
+    /// Whether GPM metrics are supported.
+    pub gpm: bool,
+    /// Whether accounting is enabled.
+    pub accounting: bool,
+}
+
+impl Capabilities {
+    fn probe(index: u32, device: &Device) -> Self {
+        let active_links = (0..NVML_NVLINK_MAX_LINKS)
+            .filter(|&link| device.link_wrapper_for(link).is_active().unwrap_or(false))
+            .collect();
+
+        Self {
+            index,
+            name: device.name().ok(),
+            architecture: device.architecture().ok().map(|a| a.to_string()),
+            fans: device.num_fans().ok(),
+            active_links,
+            mig: device.mig_mode().map_or(false, |m| m.current != 0),
+            vgpu_host: matches!(
+                device.virtualization_mode(),
+                Ok(GpuVirtualizationMode::HostVgpu)
+            ),
+            gpm: device.gpm_support().unwrap_or(false),
+            accounting: device.is_accounting_enabled().unwrap_or(false),
+        }
+    }
+}
+
+/// The outcome of a conformance run.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Report {
+    pub nvml_version: Option<String>,
+    pub driver_version: Option<String>,
+    pub cuda_driver_version: Option<i32>,
+    /// What was probed, for each device that could be obtained.
+    pub devices: Vec<Capabilities>,
+    pub checks: Vec<Check>,
+}
+
+/// How many checks had each outcome.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct Summary {
+    pub supported: usize,
+    pub not_supported: usize,
+    pub no_permission: usize,
+    pub unavailable: usize,
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+impl Report {
+    /// The checks that failed.
+    pub fn failures(&self) -> impl Iterator<Item = &Check> {
+        self.checks.iter().filter(|c| c.outcome.is_failure())
+    }
+
+    /// The check for `method` on `target`, if it was run.
+    pub fn check(&self, target: Target, method: &str) -> Option<&Check> {
+        self.checks
+            .iter()
+            .find(|c| c.target == target && c.method == method)
+    }
+
+    pub fn summary(&self) -> Summary {
+        let mut summary = Summary::default();
+
+        for check in &self.checks {
+            *match check.outcome {
+                Outcome::Supported => &mut summary.supported,
+                Outcome::NotSupported => &mut summary.not_supported,
+                Outcome::NoPermission => &mut summary.no_permission,
+                Outcome::Unavailable => &mut summary.unavailable,
+                Outcome::Skipped(_) => &mut summary.skipped,
+                Outcome::Failed(_) => &mut summary.failed,
+            } += 1;
+        }
+
+        summary
+    }
+
+    /**
+    Write this report as JSON.
+
+    See the [module-level documentation](self) for the format.
+    */
+    pub fn write_json<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(self.to_json().as_bytes())
+    }
+
+    /// This report as JSON, as written by `write_json()`.
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+
+        // Writing to a `String` can't fail
+        let _ = write!(
+            json,
+            "{{\"format\":{},\"version\":{},\"nvml_version\":{},\"driver_version\":{},\
+            \"cuda_driver_version\":{},\"devices\":[",
+            string(FORMAT),
+            VERSION,
+            opt(self.nvml_version.as_deref().map(string)),
+            opt(self.driver_version.as_deref().map(string)),
+            opt(self.cuda_driver_version),
+        );
+
+        for (i, caps) in self.devices.iter().enumerate() {
+            let links: Vec<_> = caps.active_links.iter().map(u32::to_string).collect();
+
+            let _ = write!(
+                json,
+                "{}\n{{\"index\":{},\"name\":{},\"architecture\":{},\"fans\":{},\
+                \"active_links\":[{}],\"mig\":{},\"vgpu_host\":{},\"gpm\":{},\"accounting\":{}}}",
+                if i == 0 { "" } else { "," },
+                caps.index,
+                opt(caps.name.as_deref().map(string)),
+                opt(caps.architecture.as_deref().map(string)),
+                opt(caps.fans),
+                links.join(","),
+                caps.mig,
+                caps.vgpu_host,
+                caps.gpm,
+                caps.accounting,
+            );
+        }
+
+        json.push_str("],\"checks\":[");
+
+        for (i, check) in self.checks.iter().enumerate() {
+            let target = match check.target {
+                Target::System => "\"target\":\"system\"".to_string(),
+                Target::Device(index) => format!("\"target\":\"device\",\"device\":{}", index),
+                Target::Link { device, link } => format!(
+                    "\"target\":\"link\",\"device\":{},\"link\":{}",
+                    device, link
+                ),
+                Target::Unit(index) => format!("\"target\":\"unit\",\"unit\":{}", index),
+            };
+
+            let _ = write!(
+                json,
+                "{}\n{{{},\"method\":{},\"outcome\":{}",
+                if i == 0 { "" } else { "," },
+                target,
+                string(&check.method),
+                string(check.outcome.name()),
+            );
+            if let Some(detail) = check.outcome.detail() {
+                let _ = write!(json, ",\"detail\":{}", string(detail));
+            }
+            json.push('}');
+        }
+
+        json.push_str("]}\n");
+        json
+    }
+}
+
+/// `s` as a JSON string literal.
+fn string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+
+    out
+}
+
+/// `value` as JSON, or `null`.
+fn opt<T: Display>(value: Option<T>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => "null".into(),
+    }
+}
+
+/**
+Runs the conformance checks.
+
+See the [module-level documentation](self) for details.
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct Conformance<'nvml> {
+    nvml: &'nvml Nvml,
+    reps: usize,
+}
+
+impl<'nvml> Conformance<'nvml> {
+    pub fn new(nvml: &'nvml Nvml) -> Self {
+        Self { nvml, reps: 1 }
+    }
+
+    /**
+    Call each method `reps` times (1 by default).
+
+    A method is reported as `Failed` if its calls aren't all classified the
+    same way, e.g. if it succeeds once and then returns `NotSupported`.
+    */
+    pub fn reps(mut self, reps: usize) -> Self {
+        self.reps = reps.max(1);
+        self
+    }
+
+    /// Probe every device and run every check against it.
+    pub fn run(&self) -> Report {
+        let nvml = self.nvml;
+        let mut run = Run {
+            reps: self.reps,
+            checks: Vec::new(),
+        };
+
+        run.system(nvml);
+
+        let mut devices = Vec::new();
+        for index in 0..nvml.device_count().unwrap_or(0) {
+            let target = Target::Device(index);
+
+            let device = match nvml.device_by_index(index) {
+                Ok(device) => device,
+                Err(e) => {
+                    run.push(target, "device_by_index".into(), Outcome::of::<()>(&Err(e)));
+                    continue;
+                }
+            };
+
+            let caps = Capabilities::probe(index, &device);
+            run.device(target, &device, &caps);
+
+            for &link in &caps.active_links {
+                run.link(
+                    Target::Link {
+                        device: index,
+                        link,
+                    },
+                    &device.link_wrapper_for(link),
+                );
+            }
+
+            devices.push(caps);
+        }
+
+        for index in 0..nvml.unit_count().unwrap_or(0) {
+            let target = Target::Unit(index);
+
+            match nvml.unit_by_index(index) {
+                Ok(unit) => run.unit(target, &unit),
+                Err(e) => run.push(target, "unit_by_index".into(), Outcome::of::<()>(&Err(e))),
+            }
+        }
+
+        Report {
+            nvml_version: nvml.sys_nvml_version().ok(),
+            driver_version: nvml.sys_driver_version().ok(),
+            cuda_driver_version: nvml.sys_cuda_driver_version().ok(),
+            devices,
+            checks: run.checks,
+        }
+    }
+}
+
+/// Call each listed no-argument method on `$on`.
+macro_rules! checks {
+    ($run:ident, $target:expr, $on:expr; $($method:ident),* $(,)?) => {
+        $(
+            $run.check($target, stringify!($method), || $on.$method());
+        )*
+    };
+}
+
+struct Run {
+    reps: usize,
+    checks: Vec<Check>,
+}
+
+impl Run {
+    fn push(&mut self, target: Target, method: String, outcome: Outcome) {
+        self.checks.push(Check {
+            target,
+            method,
+            outcome,
+        });
+    }
+
+    fn check<M, T, F>(&mut self, target: Target, method: M, f: F)
+    where
+        M: Into<String>,
+        F: Fn() -> Result<T, NvmlError>,
+    {
+        let first = call(&f);
+        let mut outcome = first.clone();
+
+        for i in 1..self.reps {
+            let next = call(&f);
+
+            if mem::discriminant(&next) != mem::discriminant(&first) {
+                outcome = Outcome::Failed(format!(
+                    "call #{} was {} after {}",
+                    i + 1,
+                    next.name(),
+                    first.name()
+                ));
+                break;
+            }
+        }
+
+        self.push(target, method.into(), outcome);
+    }
+
+    fn skip<M: Into<String>>(&mut self, target: Target, method: M, why: &str) {
+        self.push(target, method.into(), Outcome::Skipped(why.into()));
+    }
+
+    fn system(&mut self, nvml: &Nvml) {
+        let target = Target::System;
+
+        checks!(self, target, nvml;
+            device_count,
+            sys_driver_version,
+            sys_nvml_version,
+            sys_cuda_driver_version,
+            hic_versions,
+            hic_count,
+            unit_count,
+            excluded_device_count,
+            mig_devices,
+            vgpu_version,
+        );
+
+        self.check(target, "sys_process_name(<own pid>, 64)", || {
+            nvml.sys_process_name(process::id(), 64)
+        });
+
+        for index in 0..nvml.excluded_device_count().unwrap_or(0) {
+            self.check(target, format!("excluded_device_info({})", index), || {
+                nvml.excluded_device_info(index)
+            });
+        }
+
+        #[cfg(target_os = "linux")]
+        self.check(target, "topology_gpu_set(0)", || nvml.topology_gpu_set(0));
+    }
+
+    // Deprecated methods are still part of the wrapper's API
+    #[allow(deprecated)]
+    fn device(&mut self, target: Target, device: &Device, caps: &Capabilities) {
+        checks!(self, target, device;
+            auto_boosted_clocks_enabled,
+            bar1_memory_info,
+            board_id,
+            numa_node_id,
+            brand,
+            bridge_chip_info,
+            compute_mode,
+            cuda_compute_capability,
+            running_compute_processes,
+            running_compute_processes_count,
+            mps_running_compute_processes,
+            check_confidential_compute_status,
+            get_confidential_compute_state,
+            is_cc_enabled,
+            is_multi_gpu_protected_pcie_enabled,
+            is_cc_dev_mode_enabled,
+            current_pcie_link_gen,
+            current_pcie_link_width,
+            decoder_utilization,
+            jpeg_utilization,
+            ofa_utilization,
+            fbc_stats,
+            fbc_sessions_info,
+            fbc_session_count,
+            attributes,
+            is_display_active,
+            is_display_connected,
+            is_ecc_enabled,
+            encoder_utilization,
+            encoder_stats,
+            encoder_sessions,
+            enforced_power_limit,
+            gpc_clock_vf_offset,
+            mem_clock_vf_offset,
+            min_max_fan_speed,
+            num_fans,
+            gpu_operation_mode,
+            running_graphics_processes,
+            running_graphics_processes_count,
+            index,
+            config_checksum,
+            info_rom_image_version,
+            max_pcie_link_gen,
+            max_pcie_link_width,
+            memory_info,
+            is_multi_gpu_board,
+            mig_mode,
+            mig_is_mig_device_handle,
+            name,
+            pci_info,
+            pcie_replay_counter,
+            performance_state,
+            power_management_limit_default,
+            power_management_limit,
+            power_management_limit_constraints,
+            power_mizer_mode,
+            is_power_management_algo_active,
+            power_state,
+            power_usage,
+            total_energy_consumption,
+            are_pages_pending_retired,
+            remapped_rows,
+            row_remapper_histogram,
+            memory_health,
+            serial,
+            board_part_number,
+            current_clocks_event_reasons,
+            current_clocks_event_reasons_strict,
+            supported_clocks_event_reasons,
+            supported_clocks_event_reasons_strict,
+            current_throttle_reasons,
+            current_throttle_reasons_strict,
+            supported_throttle_reasons,
+            supported_throttle_reasons_strict,
+            supported_memory_clocks,
+            uuid,
+            utilization_rates,
+            engine_utilization,
+            vbios_version,
+            irq_num,
+            num_cores,
+            power_source,
+            memory_bus_width,
+            max_pcie_link_speed,
+            pcie_link_speed,
+            bus_type,
+            architecture,
+            validate_info_rom,
+            accounting_buffer_size,
+            is_accounting_enabled,
+            supported_performance_states,
+            performance_modes,
+            virtualization_mode,
+            gsp_firmware_mode,
+            gsp_firmware_version,
+            gpm_support,
+            gpm_streaming_enabled,
+        );
+
+        #[cfg(target_os = "linux")]
+        checks!(self, target, device;
+            minor_number,
+            is_in_persistent_mode,
+            supported_event_types,
+            supported_event_types_strict,
+        );
+
+        #[cfg(target_os = "windows")]
+        checks!(self, target, device; driver_model);
+
+        #[cfg(feature = "legacy-functions")]
+        checks!(self, target, device;
+            running_compute_processes_v2,
+            running_compute_processes_count_v2,
+            running_graphics_processes_v2,
+            running_graphics_processes_count_v2,
+        );
+
+        self.device_with_args(target, device);
+
+        let fans = caps.fans.unwrap_or(0);
+        if fans == 0 {
+            self.skip(target, "fan_speed", "no fans");
+        }
+        for fan in 0..fans {
+            self.check(target, format!("fan_speed({})", fan), || {
+                device.fan_speed(fan)
+            });
+            self.check(target, format!("fan_speed_rpm({})", fan), || {
+                device.fan_speed_rpm(fan)
+            });
+            self.check(target, format!("fan_control_policy({})", fan), || {
+                device.fan_control_policy(fan)
+            });
+        }
+
+        if caps.mig {
+            self.check(target, "mig_device_count", || device.mig_device_count());
+
+            #[cfg(target_os = "linux")]
+            for profile in 0..NVML_GPU_INSTANCE_PROFILE_COUNT {
+                self.check(
+                    target,
+                    format!("gpu_instance_profile_info({})", profile),
+                    || device.gpu_instance_profile_info(profile),
+                );
+
+                // The other queries take the ID of a profile the device has
+                if let Ok(info) = device.gpu_instance_profile_info(profile) {
+                    self.check(target, format!("gpu_instances({})", info.id), || {
+                        device.gpu_instances(info.id)
+                    });
+                    self.check(
+                        target,
+                        format!("gpu_instance_remaining_capacity({})", info.id),
+                        || device.gpu_instance_remaining_capacity(info.id),
+                    );
+                }
+            }
+        } else {
+            self.skip(target, "mig_device_count", "MIG mode is disabled");
+            self.skip(target, "gpu_instance_*", "MIG mode is disabled");
+        }
+
+        if caps.accounting {
+            self.check(target, "accounting_pids", || device.accounting_pids());
+        } else {
+            self.skip(target, "accounting_pids", "accounting is disabled");
+        }
+
+        if caps.gpm {
+            self.check(target, "gpm_sample", || device.gpm_sample());
+        } else {
+            self.skip(target, "gpm_sample", "GPM is not supported");
+        }
+
+        if caps.vgpu_host {
+            checks!(self, target, device;
+                active_vgpus,
+                vgpu_supported_types,
+                vgpu_creatable_types,
+                vgpu_scheduler_capabilities,
+                vgpu_scheduler_log,
+                vgpu_scheduler_state,
+                vgpu_host_mode,
+                is_vgpu_heterogeneous_mode_enabled,
+                vgpu_metadata,
+                pgpu_metadata_string,
+            );
+
+            self.check(target, "vgpu_utilization_stats(None)", || {
+                device.vgpu_utilization_stats(None)
+            });
+            self.check(target, "vgpu_process_utilization_stats(None)", || {
+                device.vgpu_process_utilization_stats(None)
+            });
+            self.check(target, "vgpu_instances_utilization_info(None)", || {
+                device.vgpu_instances_utilization_info(None)
+            });
+            self.check(target, "vgpu_processes_utilization_info(None)", || {
+                device.vgpu_processes_utilization_info(None)
+            });
+
+            for vgpu_type in device.vgpu_supported_types().unwrap_or_default() {
+                self.check(
+                    target,
+                    format!("vgpu_type_info({})", vgpu_type.id()),
+                    || vgpu_type.info(),
+                );
+            }
+
+            // The instance methods are checked on whichever vGPU is running
+            if let Some(instance) = device.vgpu_instances().ok().and_then(|mut v| v.pop()) {
+                checks!(self, target, instance;
+                    uuid,
+                    vm_id,
+                    vm_driver_version,
+                    mdev_uuid,
+                    gpu_pci_id,
+                    fb_usage,
+                    frame_rate_limit,
+                    is_ecc_enabled,
+                    gpu_instance_id,
+                    placement_id,
+                    license_info,
+                    metadata,
+                    runtime_state_size,
+                    encoder_capacity,
+                    encoder_stats,
+                    encoder_sessions,
+                    fbc_stats,
+                    fbc_sessions,
+                    is_accounting_enabled,
+                    accounting_pids,
+                );
+            } else {
+                self.skip(target, "vgpu_instance_*", "no running vGPU instances");
+            }
+
+            for cap in [
+                VgpuCapability::FractionalMultiVgpu,
+                VgpuCapability::HeterogeneousTimesliceProfiles,
+                VgpuCapability::HeterogeneousTimesliceSizes,
+                VgpuCapability::ReadDeviceBufferBw,
+                VgpuCapability::WriteDeviceBufferBw,
+            ] {
+                self.check(target, format!("vgpu_capabilities({:?})", cap), || {
+                    device.vgpu_capabilities(cap)
+                });
+            }
+        } else {
+            self.skip(target, "vgpu_*", "not a vGPU host");
+        }
+    }
+
+    fn device_with_args(&mut self, target: Target, device: &Device) {
+        let clocks = [Clock::Graphics, Clock::SM, Clock::Memory, Clock::Video];
+
+        for clock in clocks {
+            self.check(target, format!("applications_clock({:?})", clock), || {
+                device.applications_clock(clock)
+            });
+            self.check(
+                target,
+                format!("default_applications_clock({:?})", clock),
+                || device.default_applications_clock(clock),
+            );
+            self.check(target, format!("clock_info({:?})", clock), || {
+                device.clock_info(clock)
+            });
+            self.check(target, format!("max_clock_info({:?})", clock), || {
+                device.max_clock_info(clock)
+            });
+            self.check(
+                target,
+                format!("max_customer_boost_clock({:?})", clock),
+                || device.max_customer_boost_clock(clock),
+            );
+
+            for id in [
+                ClockId::Current,
+                ClockId::TargetAppClock,
+                ClockId::DefaultAppClock,
+                ClockId::CustomerMaxBoost,
+            ] {
+                self.check(target, format!("clock({:?}, {:?})", clock, id), || {
+                    device.clock(clock, id)
+                });
+            }
+        }
+
+        for api in [Api::ApplicationClocks, Api::AutoBoostedClocks] {
+            self.check(target, format!("is_api_restricted({:?})", api), || {
+                device.is_api_restricted(api)
+            });
+        }
+
+        for for_type in [EncoderType::H264, EncoderType::HEVC] {
+            self.check(target, format!("encoder_capacity({:?})", for_type), || {
+                device.encoder_capacity(for_type)
+            });
+        }
+
+        for object in [InfoRom::OEM, InfoRom::ECC, InfoRom::Power] {
+            self.check(target, format!("info_rom_version({:?})", object), || {
+                device.info_rom_version(object)
+            });
+        }
+
+        for counter in [PcieUtilCounter::Send, PcieUtilCounter::Receive] {
+            self.check(target, format!("pcie_throughput({:?})", counter), || {
+                device.pcie_throughput(counter)
+            });
+        }
+
+        for cause in [
+            RetirementCause::MultipleSingleBitEccErrors,
+            RetirementCause::DoubleBitEccError,
+        ] {
+            self.check(target, format!("retired_pages({:?})", cause), || {
+                device.retired_pages(cause)
+            });
+        }
+
+        self.check(target, "temperature(Gpu)", || {
+            device.temperature(TemperatureSensor::Gpu)
+        });
+        self.check(target, "temperature_v(Gpu)", || {
+            device.temperature_v(TemperatureSensor::Gpu)
+        });
+        self.check(target, "margin_temperature()", || {
+            device.margin_temperature()
+        });
+        self.check(target, "thermal_settings()", || device.thermal_settings());
+
+        for threshold in [
+            TemperatureThreshold::Shutdown,
+            TemperatureThreshold::Slowdown,
+            TemperatureThreshold::MemoryMax,
+            TemperatureThreshold::GpuMax,
+            TemperatureThreshold::AcousticMin,
+            TemperatureThreshold::AcousticCurr,
+            TemperatureThreshold::AcousticMax,
+            TemperatureThreshold::GpsCurr,
+        ] {
+            self.check(
+                target,
+                format!("temperature_threshold({:?})", threshold),
+                || device.temperature_threshold(threshold),
+            );
+        }
+
+        for error_type in [MemoryError::Corrected, MemoryError::Uncorrected] {
+            for counter in [EccCounter::Volatile, EccCounter::Aggregate] {
+                self.check(
+                    target,
+                    format!("total_ecc_errors({:?}, {:?})", error_type, counter),
+                    || device.total_ecc_errors(error_type, counter),
+                );
+
+                for location in [
+                    MemoryLocation::L1Cache,
+                    MemoryLocation::L2Cache,
+                    MemoryLocation::Device,
+                    MemoryLocation::RegisterFile,
+                    MemoryLocation::Texture,
+                    MemoryLocation::Shared,
+                    MemoryLocation::Cbu,
+                    MemoryLocation::SRAM,
+                ] {
+                    self.check(
+                        target,
+                        format!(
+                            "memory_error_counter({:?}, {:?}, {:?})",
+                            error_type, counter, location
+                        ),
+                        || device.memory_error_counter(error_type, counter, location),
+                    );
+                }
+            }
+        }
+
+        for policy in [
+            PerformancePolicy::Power,
+            PerformancePolicy::Thermal,
+            PerformancePolicy::SyncBoost,
+            PerformancePolicy::BoardLimit,
+            PerformancePolicy::LowUtilization,
+            PerformancePolicy::Reliability,
+            PerformancePolicy::TotalAppClocks,
+            PerformancePolicy::TotalBaseClocks,
+        ] {
+            self.check(target, format!("violation_status({:?})", policy), || {
+                device.violation_status(policy)
+            });
+        }
+
+        for sampling in [
+            Sampling::Power,
+            Sampling::GpuUtilization,
+            Sampling::MemoryUtilization,
+            Sampling::EncoderUtilization,
+            Sampling::DecoderUtilization,
+            Sampling::ProcessorClock,
+            Sampling::MemoryClock,
+        ] {
+            self.check(target, format!("samples({:?}, None)", sampling), || {
+                device.samples(sampling, None)
+            });
+        }
+
+        self.check(target, "process_utilization_stats(None)", || {
+            device.process_utilization_stats(None)
+        });
+        self.check(target, "processes_utilization_info(None)", || {
+            device.processes_utilization_info(None)
+        });
+
+        for mode in [
+            ProcessMode::Compute,
+            ProcessMode::Graphics,
+            ProcessMode::Mps,
+        ] {
+            self.check(
+                target,
+                format!("running_process_detail_list({:?})", mode),
+                || device.running_process_detail_list(mode),
+            );
+        }
+        self.check(target, "gpu_processes()", || device.gpu_processes());
+
+        self.check(target, "is_on_same_board_as(<itself>)", || {
+            device.is_on_same_board_as(device)
+        });
+
+        match device
+            .supported_memory_clocks()
+            .ok()
+            .and_then(|c| c.first().copied())
+        {
+            Some(mem_clock) => self.check(
+                target,
+                format!("supported_graphics_clocks({})", mem_clock),
+                || device.supported_graphics_clocks(mem_clock),
+            ),
+            None => self.skip(
+                target,
+                "supported_graphics_clocks",
+                "no supported memory clocks",
+            ),
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            self.check(target, "cpu_affinity(64)", || device.cpu_affinity(64));
+            self.check(target, "cpu_affinity_within_scope(64, 0)", || {
+                device.cpu_affinity_within_scope(64, 0)
+            });
+            self.check(target, "memory_affinity(64, 0)", || {
+                device.memory_affinity(64, 0)
+            });
+
+            for level in [
+                TopologyLevel::Internal,
+                TopologyLevel::Single,
+                TopologyLevel::Multiple,
+                TopologyLevel::HostBridge,
+                TopologyLevel::Node,
+                TopologyLevel::System,
+            ] {
+                self.check(
+                    target,
+                    format!("topology_nearest_gpus({:?})", level),
+                    || device.topology_nearest_gpus(level),
+                );
+            }
+        }
+    }
+
+    fn link(&mut self, target: Target, link: &NvLink) {
+        checks!(self, target, link;
+            is_active,
+            version,
+            remote_pci_info,
+            bw_mode,
+        );
+
+        self.check(
+            target,
+            format!("remote_device_type({})", link.link()),
+            || link.remote_device_type(link.link()),
+        );
+
+        for cap in [
+            Capability::P2p,
+            Capability::SysMemAccess,
+            Capability::P2pAtomics,
+            Capability::SysMemAtomics,
+            Capability::SliBridge,
+            Capability::ValidLink,
+        ] {
+            self.check(target, format!("has_capability({:?})", cap), || {
+                link.has_capability(cap)
+            });
+        }
+
+        for counter in [
+            ErrorCounter::DlReplay,
+            ErrorCounter::DlRecovery,
+            ErrorCounter::DlCrcFlit,
+            ErrorCounter::DlCrcData,
+        ] {
+            self.check(target, format!("error_counter({:?})", counter), || {
+                link.error_counter(counter)
+            });
+        }
+
+        for counter in [Counter::Zero, Counter::One] {
+            self.check(
+                target,
+                format!("utilization_control({:?})", counter),
+                || link.utilization_control(counter.clone()),
+            );
+            self.check(
+                target,
+                format!("utilization_counter({:?})", counter),
+                || link.utilization_counter(counter.clone()),
+            );
+        }
+    }
+
+    fn unit(&mut self, target: Target, unit: &Unit) {
+        checks!(self, target, unit;
+            devices,
+            device_count,
+            fan_info,
+            led_state,
+            psu_info,
+            info,
+        );
+
+        for reading in [
+            TemperatureReading::Intake,
+            TemperatureReading::Exhaust,
+            TemperatureReading::Board,
+        ] {
+            self.check(target, format!("temperature({:?})", reading), || {
+                unit.temperature(reading.clone())
+            });
+        }
+    }
+}
+
+/// Call `f` once and classify the result, catching panics.
+fn call<T, F>(f: &F) -> Outcome
+where
+    F: Fn() -> Result<T, NvmlError>,
+{
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => Outcome::of(&result),
+        Err(payload) => Outcome::Failed(format!("panicked: {}", panic_message(&*payload))),
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "<non-string payload>"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::NvmlBackend;
+    use crate::ffi::bindings::*;
+    use crate::test_utils::mock_backend;
+    use std::os::raw::c_uint;
+
+    /// One device with two fans, the second of which is broken.
+    struct Mock;
+
+    impl NvmlBackend for Mock {
+        mock_backend!();
+
+        unsafe fn nvmlDeviceGetTemperature(
+            &self,
+            _device: nvmlDevice_t,
+            _sensor: nvmlTemperatureSensors_t,
+            temp: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *temp = 40;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetPowerUsage(
+            &self,
+            _device: nvmlDevice_t,
+            _power: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            Ok(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED)
+        }
+
+        unsafe fn nvmlDeviceGetSerial(
+            &self,
+            _device: nvmlDevice_t,
+            _serial: *mut std::os::raw::c_char,
+            _length: c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            Ok(nvmlReturn_enum_NVML_ERROR_NO_PERMISSION)
+        }
+
+        unsafe fn nvmlDeviceGetNumFans(
+            &self,
+            _device: nvmlDevice_t,
+            fans: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *fans = 2;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetFanSpeed_v2(
+            &self,
+            _device: nvmlDevice_t,
+            fan: c_uint,
+            speed: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            if fan == 1 {
+                return Ok(nvmlReturn_enum_NVML_ERROR_GPU_IS_LOST);
+            }
+
+            *speed = 30;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+    }
+
+    /// One device in MIG mode with a single GPU instance profile (ID 19),
+    /// with no GPU instances yet.
+    #[cfg(target_os = "linux")]
+    struct MigMock;
+
+    #[cfg(target_os = "linux")]
+    impl NvmlBackend for MigMock {
+        mock_backend!();
+
+        unsafe fn nvmlDeviceGetMigMode(
+            &self,
+            _device: nvmlDevice_t,
+            current: *mut c_uint,
+            pending: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *current = NVML_DEVICE_MIG_ENABLE;
+            *pending = NVML_DEVICE_MIG_ENABLE;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetGpuInstanceProfileInfoV(
+            &self,
+            _device: nvmlDevice_t,
+            profile: c_uint,
+            info: *mut nvmlGpuInstanceProfileInfo_v2_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            if profile != 0 {
+                return Ok(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED);
+            }
+
+            (*info).id = 19;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetGpuInstancePossiblePlacements_v2(
+            &self,
+            _device: nvmlDevice_t,
+            _profile_id: c_uint,
+            _placements: *mut nvmlGpuInstancePlacement_t,
+            count: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *count = 0;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetGpuInstances(
+            &self,
+            _device: nvmlDevice_t,
+            _profile_id: c_uint,
+            _instances: *mut nvmlGpuInstance_t,
+            count: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *count = 0;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetGpuInstanceRemainingCapacity(
+            &self,
+            _device: nvmlDevice_t,
+            _profile_id: c_uint,
+            count: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *count = 7;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+    }
+
+    fn report() -> Report {
+        let nvml = Nvml::builder().init_with_backend(Mock).unwrap();
+
+        Conformance::new(&nvml).reps(2).run()
+    }
+
+    #[test]
+    fn classification() {
+        let report = report();
+        let outcome = |method| &report.check(Target::Device(0), method).unwrap().outcome;
+
+        assert_eq!(outcome("temperature(Gpu)"), &Outcome::Supported);
+        assert_eq!(outcome("power_usage"), &Outcome::NotSupported);
+        assert_eq!(outcome("serial"), &Outcome::NoPermission);
+        assert_eq!(outcome("uuid"), &Outcome::Unavailable);
+        assert_eq!(outcome("fan_speed(0)"), &Outcome::Supported);
+        assert!(outcome("fan_speed(1)").is_failure());
+        assert!(matches!(outcome("gpm_sample"), Outcome::Skipped(_)));
+        assert!(matches!(outcome("gpu_instance_*"), Outcome::Skipped(_)));
+
+        assert_eq!(report.failures().count(), 1);
+        assert_eq!(report.summary().failed, 1);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn mig_queries() {
+        let nvml = Nvml::builder().init_with_backend(MigMock).unwrap();
+        let report = Conformance::new(&nvml).run();
+        let outcome = |method| &report.check(Target::Device(0), method).unwrap().outcome;
+
+        assert_eq!(outcome("gpu_instance_profile_info(0)"), &Outcome::Supported);
+        assert_eq!(
+            outcome("gpu_instance_profile_info(1)"),
+            &Outcome::NotSupported
+        );
+        assert_eq!(outcome("gpu_instances(19)"), &Outcome::Supported);
+        assert_eq!(
+            outcome("gpu_instance_remaining_capacity(19)"),
+            &Outcome::Supported
+        );
+        assert!(report
+            .check(Target::Device(0), "gpu_instances(0)")
+            .is_none());
+    }
+
+    #[test]
+    fn probing() {
+        let report = report();
+
+        assert_eq!(report.devices.len(), 1);
+        assert_eq!(report.devices[0].fans, Some(2));
+        assert_eq!(report.devices[0].name, None);
+        assert!(report.devices[0].active_links.is_empty());
+        assert!(report.check(Target::Device(0), "fan_speed(2)").is_none());
+    }
+
+    #[test]
+    fn json() {
+        let json = report().to_json();
+
+        assert!(json.starts_with(r#"{"format":"nvml-wrapper-conformance","version":1,"#));
+        assert!(json.contains(r#""fans":2,"#));
+        assert!(json.contains(
+            r#"{"target":"device","device":0,"method":"fan_speed(1)","outcome":"failed","detail":"#
+        ));
+        assert_eq!(string("a\"b\\\n\u{1}"), r#""a\"b\\\n\u0001""#);
+    }
+}
@@ -0,0 +1,336 @@
+/*!
+The backend used inside [`Nvml::with_error_context()`](crate::Nvml::with_error_context),
+which records the context of failing calls for
+[`NvmlErrorWithContext`](crate::error::NvmlErrorWithContext).
+*/
+
+#![allow(non_snake_case)]
+// The safety contract is the one documented on `NvmlBackend`
+#![allow(clippy::missing_safety_doc)]
+
+use crate::args::{Kind, RawArg};
+use crate::backend::{nvml_functions, NvmlBackend};
+use crate::error::{DeviceIdentity, ErrorContext, NvmlError};
+use crate::ffi::bindings::*;
+use std::ffi::CStr;
+use std::mem;
+use std::os::raw;
+use std::sync::{Arc, Mutex};
+
+/// The context of the most recent failing call made through a `ContextRecorder`.
+pub(crate) type LastContext = Arc<Mutex<Option<ErrorContext>>>;
+
+/**
+Records the function, return code and device handle of every call to `inner`
+that returns an error code, and clears the record after a successful one.
+
+Nothing else is queried, so a failing call costs no extra NVML calls.
+*/
+pub(crate) struct ContextRecorder {
+    inner: &'static dyn NvmlBackend,
+    last: LastContext,
+}
+
+impl ContextRecorder {
+    /**
+    # Safety
+
+    `inner` must outlive the returned recorder.
+    */
+    pub(crate) unsafe fn new(inner: &dyn NvmlBackend, last: LastContext) -> Self {
+        Self {
+            // SAFETY: upheld by the caller
+            inner: mem::transmute::<&dyn NvmlBackend, &'static dyn NvmlBackend>(inner),
+            last,
+        }
+    }
+
+    /// Record the context of a call to `function` with `args` that returned
+    /// `result`, or clear it if the call succeeded.
+    fn record(
+        &self,
+        function: &'static str,
+        args: &[&dyn RawArg],
+        result: &Result<nvmlReturn_t, NvmlError>,
+    ) {
+        let context = match *result {
+            Ok(code) if code != nvmlReturn_enum_NVML_SUCCESS => Some(ErrorContext {
+                function,
+                code,
+                description: None,
+                device: None,
+                handle: args.iter().find_map(|arg| match arg.kind() {
+                    Kind::Handle(p) if p.is::<nvmlDevice_st>() => Some(p.addr as usize),
+                    _ => None,
+                }),
+                backend: backend_id(self.inner),
+            }),
+            _ => None,
+        };
+
+        *self.last.lock().unwrap_or_else(|e| e.into_inner()) = context;
+    }
+}
+
+/// Identifies `backend`, to tell which instance an `ErrorContext` came from.
+pub(crate) fn backend_id(backend: &dyn NvmlBackend) -> usize {
+    backend as *const dyn NvmlBackend as *const () as usize
+}
+
+/// The driver's description of `code`.
+pub(crate) unsafe fn description(backend: &dyn NvmlBackend, code: nvmlReturn_t) -> Option<String> {
+    match backend.nvmlErrorString(code) {
+        Ok(s) if !s.is_null() => Some(CStr::from_ptr(s).to_string_lossy().into_owned()),
+        _ => None,
+    }
+}
+
+/// Query what identifies `device`.
+pub(crate) unsafe fn identify(backend: &dyn NvmlBackend, device: nvmlDevice_t) -> DeviceIdentity {
+    let succeeded = |result| matches!(result, Ok(nvmlReturn_enum_NVML_SUCCESS));
+
+    let mut index = 0;
+    let index = if succeeded(backend.nvmlDeviceGetIndex(device, &mut index)) {
+        Some(index)
+    } else {
+        None
+    };
+
+    let mut uuid = [0; NVML_DEVICE_UUID_V2_BUFFER_SIZE as usize];
+    let uuid = if succeeded(backend.nvmlDeviceGetUUID(
+        device,
+        uuid.as_mut_ptr(),
+        NVML_DEVICE_UUID_V2_BUFFER_SIZE,
+    )) {
+        Some(c_str(&uuid))
+    } else {
+        None
+    };
+
+    let mut pci: nvmlPciInfo_t = mem::zeroed();
+    let pci_bus_id = if succeeded(backend.nvmlDeviceGetPciInfo_v3(device, &mut pci)) {
+        Some(c_str(&pci.busId))
+    } else {
+        None
+    };
+
+    DeviceIdentity {
+        index,
+        uuid,
+        pci_bus_id,
+    }
+}
+
+/// The (possibly unterminated) C string in `buffer`.
+fn c_str(buffer: &[raw::c_char]) -> String {
+    let bytes: Vec<u8> = buffer
+        .iter()
+        .take_while(|&&c| c != 0)
+        .map(|&c| c as u8)
+        .collect();
+
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+macro_rules! context_recorder {
+    ($(
+        $(#[$attr:meta])*
+        fn $name:ident($($arg:ident: $ty:ty),* $(,)?);
+    )*) => {
+        impl NvmlBackend for ContextRecorder {
+            fn lib(&self) -> Option<&NvmlLib> {
+                self.inner.lib()
+            }
+
+            unsafe fn nvmlErrorString(
+                &self,
+                result: nvmlReturn_t,
+            ) -> Result<*const raw::c_char, NvmlError> {
+                self.inner.nvmlErrorString(result)
+            }
+
+            $(
+                $(#[$attr])*
+                unsafe fn $name(&self, $($arg: $ty),*) -> Result<nvmlReturn_t, NvmlError> {
+                    let result = self.inner.$name($($arg),*);
+                    self.record(stringify!($name), &[$(&$arg),*], &result);
+
+                    result
+                }
+            )*
+        }
+    };
+}
+
+nvml_functions!(context_recorder);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::enum_wrappers::device::TemperatureSensor;
+    use crate::test_utils::mock_backend;
+    use crate::Nvml;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// One GPU whose temperature can't be read, counting the calls made to
+    /// identify it.
+    #[derive(Default)]
+    struct Mock {
+        identity_calls: Arc<AtomicUsize>,
+    }
+
+    impl NvmlBackend for Mock {
+        mock_backend!();
+
+        unsafe fn nvmlErrorString(
+            &self,
+            result: nvmlReturn_t,
+        ) -> Result<*const raw::c_char, NvmlError> {
+            let s: &[u8] = match result {
+                nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED => b"Not Supported\0",
+                _ => b"Unknown Error\0",
+            };
+
+            Ok(s.as_ptr() as *const raw::c_char)
+        }
+
+        unsafe fn nvmlDeviceGetIndex(
+            &self,
+            _device: nvmlDevice_t,
+            index: *mut raw::c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            self.identity_calls.fetch_add(1, Ordering::SeqCst);
+
+            *index = 0;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetUUID(
+            &self,
+            _device: nvmlDevice_t,
+            uuid: *mut raw::c_char,
+            length: raw::c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            self.identity_calls.fetch_add(1, Ordering::SeqCst);
+
+            let s = b"GPU-0\0";
+            if (length as usize) < s.len() {
+                return Ok(nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE);
+            }
+
+            uuid.copy_from(s.as_ptr() as *const raw::c_char, s.len());
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetTemperature(
+            &self,
+            _device: nvmlDevice_t,
+            _sensor: nvmlTemperatureSensors_t,
+            _temp: *mut raw::c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            Ok(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED)
+        }
+    }
+
+    fn nvml(mock: Mock) -> Nvml {
+        Nvml::builder()
+            .init_with_backend(mock)
+            .expect("initialized mock")
+    }
+
+    #[test]
+    fn failing_calls_carry_context() {
+        let mock = Mock::default();
+        let identity_calls = mock.identity_calls.clone();
+        let nvml = nvml(mock);
+
+        let e = nvml
+            .with_error_context(|nvml| nvml.device_by_index(0)?.temperature(TemperatureSensor::Gpu))
+            .unwrap_err();
+
+        assert!(matches!(e.error, NvmlError::NotSupported));
+        let context = e.context.as_ref().unwrap();
+        assert_eq!(context.function, "nvmlDeviceGetTemperature");
+        assert_eq!(context.code, nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED);
+        assert!(context.has_device());
+        assert_eq!((&context.description, &context.device), (&None, &None));
+        assert_eq!(identity_calls.load(Ordering::SeqCst), 0);
+
+        let e = e.resolve(&nvml);
+        let context = e.context.as_ref().unwrap();
+        assert_eq!(context.description.as_deref(), Some("Not Supported"));
+        assert_eq!(
+            context.device,
+            Some(DeviceIdentity {
+                index: Some(0),
+                uuid: Some("GPU-0".into()),
+                pci_bus_id: None,
+            })
+        );
+        assert_eq!(
+            e.to_string(),
+            "the requested operation is not available on the target device: \
+             `nvmlDeviceGetTemperature` returned 3 (\"Not Supported\") for device 0 (GPU-0)"
+        );
+    }
+
+    #[test]
+    fn calls_without_a_device() {
+        let nvml = nvml(Mock::default());
+
+        let e = nvml
+            .with_error_context(|nvml| nvml.device_by_index(1).map(|_| ()))
+            .unwrap_err()
+            .resolve(&nvml);
+        let context = e.context.unwrap();
+        assert_eq!(context.function, "nvmlDeviceGetHandleByIndex_v2");
+        assert!(!context.has_device());
+        assert_eq!(context.device, None);
+    }
+
+    #[test]
+    fn context_is_only_attached_to_its_error() {
+        let nvml = nvml(Mock::default());
+        let device = nvml.device_by_index(0).unwrap();
+
+        // Calls made outside the scope record nothing
+        let _ = device.temperature(TemperatureSensor::Gpu);
+        let e = nvml
+            .with_error_context(|_| Err::<(), _>(NvmlError::NotSupported))
+            .unwrap_err();
+        assert_eq!(e.context, None);
+
+        // Cleared by a later successful call
+        let e = nvml
+            .with_error_context(|nvml| {
+                let device = nvml.device_by_index(0)?;
+                let _ = device.temperature(TemperatureSensor::Gpu);
+                device.index()?;
+                Err::<(), _>(NvmlError::NotSupported)
+            })
+            .unwrap_err();
+        assert_eq!(e.context, None);
+
+        // Not attached to an unrelated error
+        let e = nvml
+            .with_error_context(|nvml| {
+                let _ = nvml.device_by_index(0)?.temperature(TemperatureSensor::Gpu);
+                Err::<(), _>(NvmlError::GpuLost)
+            })
+            .unwrap_err();
+        assert_eq!(e.context, None);
+        assert_eq!(e.to_string(), NvmlError::GpuLost.to_string());
+    }
+
+    #[test]
+    fn resolving_needs_the_recording_instance() {
+        let nvml = nvml(Mock::default());
+        let other = self::nvml(Mock::default());
+
+        let e = nvml
+            .with_error_context(|nvml| nvml.device_by_index(0)?.temperature(TemperatureSensor::Gpu))
+            .unwrap_err()
+            .resolve(&other);
+        assert_eq!(e.context.unwrap().device, None);
+    }
+}
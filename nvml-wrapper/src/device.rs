@@ -1,10 +1,12 @@
 #[cfg(target_os = "linux")]
 use crate::EventSet;
 use crate::GpmSample;
+#[cfg(target_os = "linux")]
+use crate::GpuInstance;
 use crate::NvLink;
 use crate::Nvml;
 
-use crate::bitmasks::device::{PowerMizerModes, ThrottleReasons};
+use crate::bitmasks::device::{ClocksEventReasons, PowerMizerModes, ThrottleReasons};
 #[cfg(target_os = "linux")]
 use crate::bitmasks::event::EventTypes;
 #[cfg(target_os = "windows")]
@@ -14,19 +16,20 @@ use crate::enum_wrappers::{bool_from_state, device::*, state_from_bool};
 
 use crate::enums::device::{
     BusType, DeviceArchitecture, FanControlPolicy, GpuLockedClocksSetting, PcieLinkMaxSpeed,
-    PowerMizerMode, PowerSource,
+    PowerMizerMode, PowerSource, ProcessMode, ThermalTarget,
 };
 use crate::error::nvml_try_count;
 #[cfg(target_os = "linux")]
 use crate::error::NvmlErrorWithSource;
-use crate::error::{nvml_sym, nvml_try, Bits, NvmlError};
+use crate::error::{nvml_try, optional, Bits, NvmlError};
 
 use crate::ffi::bindings::*;
 
 use crate::struct_wrappers::device::*;
 use crate::structs::device::*;
 
-use crate::vgpu::VgpuType;
+use crate::high_level::memory_health::{self, MemoryHealth};
+use crate::vgpu::{VgpuInstance, VgpuType};
 
 #[cfg(target_os = "linux")]
 use std::convert::TryInto;
@@ -140,9 +143,7 @@ impl<'nvml> Device<'nvml> {
     #[cfg(target_os = "linux")]
     #[doc(alias = "nvmlDeviceClearCpuAffinity")]
     pub fn clear_cpu_affinity(&mut self) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceClearCpuAffinity.as_ref())?;
-
-        unsafe { nvml_try(sym(self.device)) }
+        unsafe { nvml_try(self.nvml.backend.nvmlDeviceClearCpuAffinity(self.device)?) }
     }
 
     /**
@@ -174,12 +175,14 @@ impl<'nvml> Device<'nvml> {
     // Tested (except for AutoBoostedClocks)
     #[doc(alias = "nvmlDeviceGetAPIRestriction")]
     pub fn is_api_restricted(&self, api: Api) -> Result<bool, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetAPIRestriction.as_ref())?;
-
         unsafe {
             let mut restricted_state: nvmlEnableState_t = mem::zeroed();
 
-            nvml_try(sym(self.device, api.as_c(), &mut restricted_state))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetAPIRestriction(
+                self.device,
+                api.as_c(),
+                &mut restricted_state,
+            )?)?;
 
             bool_from_state(restricted_state)
         }
@@ -208,12 +211,14 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetApplicationsClock")]
     pub fn applications_clock(&self, clock_type: Clock) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetApplicationsClock.as_ref())?;
-
         unsafe {
             let mut clock: c_uint = mem::zeroed();
 
-            nvml_try(sym(self.device, clock_type.as_c(), &mut clock))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetApplicationsClock(
+                self.device,
+                clock_type.as_c(),
+                &mut clock,
+            )?)?;
 
             Ok(clock)
         }
@@ -246,13 +251,15 @@ impl<'nvml> Device<'nvml> {
     // Tested on machines other than my own
     #[doc(alias = "nvmlDeviceGetAutoBoostedClocksEnabled")]
     pub fn auto_boosted_clocks_enabled(&self) -> Result<AutoBoostClocksEnabledInfo, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetAutoBoostedClocksEnabled.as_ref())?;
-
         unsafe {
             let mut is_enabled: nvmlEnableState_t = mem::zeroed();
             let mut is_enabled_default: nvmlEnableState_t = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut is_enabled, &mut is_enabled_default))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetAutoBoostedClocksEnabled(
+                self.device,
+                &mut is_enabled,
+                &mut is_enabled_default,
+            )?)?;
 
             Ok(AutoBoostClocksEnabledInfo {
                 is_enabled: bool_from_state(is_enabled)?,
@@ -283,11 +290,13 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetBAR1MemoryInfo")]
     pub fn bar1_memory_info(&self) -> Result<BAR1MemoryInfo, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetBAR1MemoryInfo.as_ref())?;
-
         unsafe {
             let mut mem_info: nvmlBAR1Memory_t = mem::zeroed();
-            nvml_try(sym(self.device, &mut mem_info))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetBAR1MemoryInfo(self.device, &mut mem_info)?,
+            )?;
 
             Ok(mem_info.into())
         }
@@ -316,8 +325,6 @@ impl<'nvml> Device<'nvml> {
         size: usize,
         scope: nvmlAffinityScope_t,
     ) -> Result<Vec<c_ulong>, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetMemoryAffinity.as_ref())?;
-
         unsafe {
             if size == 0 {
                 return Err(NvmlError::InsufficientSize(Some(1)));
@@ -325,12 +332,12 @@ impl<'nvml> Device<'nvml> {
 
             let mut affinities: Vec<c_ulong> = vec![0; size];
 
-            nvml_try(sym(
+            nvml_try(self.nvml.backend.nvmlDeviceGetMemoryAffinity(
                 self.device,
                 size as c_uint,
                 affinities.as_mut_ptr(),
                 scope,
-            ))?;
+            )?)?;
 
             Ok(affinities)
         }
@@ -365,11 +372,13 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetBoardId")]
     pub fn board_id(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetBoardId.as_ref())?;
-
         unsafe {
             let mut id: c_uint = mem::zeroed();
-            nvml_try(sym(self.device, &mut id))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetBoardId(self.device, &mut id)?,
+            )?;
 
             Ok(id)
         }
@@ -392,11 +401,13 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceGetNumaNodeId")]
     pub fn numa_node_id(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetNumaNodeId.as_ref())?;
-
         unsafe {
             let mut id: c_uint = mem::zeroed();
-            nvml_try(sym(self.device, &mut id))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetNumaNodeId(self.device, &mut id)?,
+            )?;
 
             Ok(id)
         }
@@ -419,11 +430,13 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetBrand")]
     pub fn brand(&self) -> Result<Brand, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetBrand.as_ref())?;
-
         unsafe {
             let mut brand: nvmlBrandType_t = mem::zeroed();
-            nvml_try(sym(self.device, &mut brand))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetBrand(self.device, &mut brand)?,
+            )?;
 
             Brand::try_from(brand)
         }
@@ -451,11 +464,13 @@ impl<'nvml> Device<'nvml> {
     // Tested on machines other than my own
     #[doc(alias = "nvmlDeviceGetBridgeChipInfo")]
     pub fn bridge_chip_info(&self) -> Result<BridgeChipHierarchy, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetBridgeChipInfo.as_ref())?;
-
         unsafe {
             let mut info: nvmlBridgeChipHierarchy_t = mem::zeroed();
-            nvml_try(sym(self.device, &mut info))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetBridgeChipInfo(self.device, &mut info)?,
+            )?;
 
             BridgeChipHierarchy::try_from(info)
         }
@@ -480,17 +495,15 @@ impl<'nvml> Device<'nvml> {
     // Tested (except for CustomerMaxBoost)
     #[doc(alias = "nvmlDeviceGetClock")]
     pub fn clock(&self, clock_type: Clock, clock_id: ClockId) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetClock.as_ref())?;
-
         unsafe {
             let mut clock: c_uint = mem::zeroed();
 
-            nvml_try(sym(
+            nvml_try(self.nvml.backend.nvmlDeviceGetClock(
                 self.device,
                 clock_type.as_c(),
                 clock_id.as_c(),
                 &mut clock,
-            ))?;
+            )?)?;
 
             Ok(clock)
         }
@@ -517,12 +530,14 @@ impl<'nvml> Device<'nvml> {
     // Tested on machines other than my own
     #[doc(alias = "nvmlDeviceGetMaxCustomerBoostClock")]
     pub fn max_customer_boost_clock(&self, clock_type: Clock) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetMaxCustomerBoostClock.as_ref())?;
-
         unsafe {
             let mut clock: c_uint = mem::zeroed();
 
-            nvml_try(sym(self.device, clock_type.as_c(), &mut clock))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetMaxCustomerBoostClock(
+                self.device,
+                clock_type.as_c(),
+                &mut clock,
+            )?)?;
 
             Ok(clock)
         }
@@ -544,11 +559,13 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetComputeMode")]
     pub fn compute_mode(&self) -> Result<ComputeMode, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetComputeMode.as_ref())?;
-
         unsafe {
             let mut mode: nvmlComputeMode_t = mem::zeroed();
-            nvml_try(sym(self.device, &mut mode))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetComputeMode(self.device, &mut mode)?,
+            )?;
 
             ComputeMode::try_from(mode)
         }
@@ -569,13 +586,15 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceGetCudaComputeCapability")]
     pub fn cuda_compute_capability(&self) -> Result<CudaComputeCapability, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetCudaComputeCapability.as_ref())?;
-
         unsafe {
             let mut major: c_int = mem::zeroed();
             let mut minor: c_int = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut major, &mut minor))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetCudaComputeCapability(
+                self.device,
+                &mut major,
+                &mut minor,
+            )?)?;
 
             Ok(CudaComputeCapability { major, minor })
         }
@@ -600,12 +619,14 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetClockInfo")]
     pub fn clock_info(&self, clock_type: Clock) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetClockInfo.as_ref())?;
-
         unsafe {
             let mut clock: c_uint = mem::zeroed();
 
-            nvml_try(sym(self.device, clock_type.as_c(), &mut clock))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetClockInfo(
+                self.device,
+                clock_type.as_c(),
+                &mut clock,
+            )?)?;
 
             Ok(clock)
         }
@@ -628,13 +649,6 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetComputeRunningProcesses_v3")]
     pub fn running_compute_processes(&self) -> Result<Vec<ProcessInfo>, NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlDeviceGetComputeRunningProcesses_v3
-                .as_ref(),
-        )?;
-
         unsafe {
             let mut count: c_uint = match self.running_compute_processes_count()? {
                 0 => return Ok(vec![]),
@@ -646,7 +660,11 @@ impl<'nvml> Device<'nvml> {
             count += 5;
             let mut processes: Vec<nvmlProcessInfo_t> = vec![mem::zeroed(); count as usize];
 
-            nvml_try(sym(self.device, &mut count, processes.as_mut_ptr()))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetComputeRunningProcesses_v3(
+                self.device,
+                &mut count,
+                processes.as_mut_ptr(),
+            )?)?;
 
             processes.truncate(count as usize);
             Ok(processes.into_iter().map(ProcessInfo::from).collect())
@@ -654,17 +672,17 @@ impl<'nvml> Device<'nvml> {
     }
 
     fn mps_running_compute_processes_count(&self) -> Result<c_uint, NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlDeviceGetMPSComputeRunningProcesses_v3
-                .as_ref(),
-        )?;
-
         unsafe {
             let mut len: c_uint = 0;
 
-            match sym(self.device, &mut len, ptr::null_mut()) {
+            match self
+                .nvml
+                .backend
+                .nvmlDeviceGetMPSComputeRunningProcesses_v3(
+                    self.device,
+                    &mut len,
+                    ptr::null_mut(),
+                )? {
                 nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE => Ok(len),
                 another_attempt => nvml_try(another_attempt).map(|_| 0),
             }
@@ -688,13 +706,6 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceGetMPSComputeRunningProcesses_v3")]
     pub fn mps_running_compute_processes(&self) -> Result<Vec<ProcessInfo>, NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlDeviceGetMPSComputeRunningProcesses_v3
-                .as_ref(),
-        )?;
-
         unsafe {
             let mut len: c_uint = match self.mps_running_compute_processes_count()? {
                 0 => return Ok(vec![]),
@@ -703,7 +714,15 @@ impl<'nvml> Device<'nvml> {
 
             let mut processes: Vec<nvmlProcessInfo_t> = Vec::with_capacity(len as usize);
 
-            nvml_try(sym(self.device, &mut len, processes.as_mut_ptr()))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetMPSComputeRunningProcesses_v3(
+                        self.device,
+                        &mut len,
+                        processes.as_mut_ptr(),
+                    )?,
+            )?;
 
             processes.set_len(len as usize);
             Ok(processes.into_iter().map(ProcessInfo::from).collect())
@@ -727,19 +746,16 @@ impl<'nvml> Device<'nvml> {
     // Tested as part of `.running_compute_processes()`
     #[doc(alias = "nvmlDeviceGetComputeRunningProcesses_v3")]
     pub fn running_compute_processes_count(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlDeviceGetComputeRunningProcesses_v3
-                .as_ref(),
-        )?;
-
         unsafe {
             // Indicates that we want the count
             let mut count: c_uint = 0;
 
             // Passing null doesn't mean we want the count, it's just allowed
-            match sym(self.device, &mut count, ptr::null_mut()) {
+            match self.nvml.backend.nvmlDeviceGetComputeRunningProcesses_v3(
+                self.device,
+                &mut count,
+                ptr::null_mut(),
+            )? {
                 nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE => Ok(count),
                 // If success, return 0; otherwise, return error
                 other => nvml_try(other).map(|_| 0),
@@ -764,13 +780,6 @@ impl<'nvml> Device<'nvml> {
     #[doc(alias = "nvmlDeviceGetComputeRunningProcesses_v2")]
     #[cfg(feature = "legacy-functions")]
     pub fn running_compute_processes_v2(&self) -> Result<Vec<ProcessInfo>, NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlDeviceGetComputeRunningProcesses_v2
-                .as_ref(),
-        )?;
-
         unsafe {
             let mut count: c_uint = match self.running_compute_processes_count_v2()? {
                 0 => return Ok(vec![]),
@@ -782,7 +791,11 @@ impl<'nvml> Device<'nvml> {
             count += 5;
             let mut processes: Vec<nvmlProcessInfo_v2_t> = vec![mem::zeroed(); count as usize];
 
-            nvml_try(sym(self.device, &mut count, processes.as_mut_ptr()))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetComputeRunningProcesses_v2(
+                self.device,
+                &mut count,
+                processes.as_mut_ptr(),
+            )?)?;
 
             processes.truncate(count as usize);
             Ok(processes.into_iter().map(ProcessInfo::from).collect())
@@ -806,19 +819,16 @@ impl<'nvml> Device<'nvml> {
     #[doc(alias = "nvmlDeviceGetComputeRunningProcesses_v2")]
     #[cfg(feature = "legacy-functions")]
     pub fn running_compute_processes_count_v2(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlDeviceGetComputeRunningProcesses_v2
-                .as_ref(),
-        )?;
-
         unsafe {
             // Indicates that we want the count
             let mut count: c_uint = 0;
 
             // Passing null doesn't mean we want the count, it's just allowed
-            match sym(self.device, &mut count, ptr::null_mut()) {
+            match self.nvml.backend.nvmlDeviceGetComputeRunningProcesses_v2(
+                self.device,
+                &mut count,
+                ptr::null_mut(),
+            )? {
                 nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE => Ok(count),
                 // If success, return 0; otherwise, return error
                 other => nvml_try(other).map(|_| 0),
@@ -857,8 +867,6 @@ impl<'nvml> Device<'nvml> {
     #[cfg(target_os = "linux")]
     #[doc(alias = "nvmlDeviceGetCpuAffinity")]
     pub fn cpu_affinity(&self, size: usize) -> Result<Vec<c_ulong>, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetCpuAffinity.as_ref())?;
-
         unsafe {
             if size == 0 {
                 // Return an error containing the minimum size that can be passed.
@@ -867,7 +875,11 @@ impl<'nvml> Device<'nvml> {
 
             let mut affinities: Vec<c_ulong> = vec![mem::zeroed(); size];
 
-            nvml_try(sym(self.device, size as c_uint, affinities.as_mut_ptr()))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetCpuAffinity(
+                self.device,
+                size as c_uint,
+                affinities.as_mut_ptr(),
+            )?)?;
 
             Ok(affinities)
         }
@@ -882,23 +894,23 @@ impl<'nvml> Device<'nvml> {
     * `InvalidArg`, if confidential compute state is invalid
     */
     pub fn check_confidential_compute_status(&self) -> Result<bool, NvmlError> {
-        let cc_state_sym = nvml_sym(self.nvml.lib.nvmlSystemGetConfComputeState.as_ref())?;
-        let cc_gpus_ready_sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlSystemGetConfComputeGpusReadyState
-                .as_ref(),
-        )?;
-
         unsafe {
             let mut state: nvmlConfComputeSystemState_t = mem::zeroed();
-            nvml_try(cc_state_sym(&mut state))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlSystemGetConfComputeState(&mut state)?,
+            )?;
 
             let is_cc_enabled = state.ccFeature == NVML_CC_SYSTEM_FEATURE_ENABLED;
             let is_prod_environment = state.environment == NVML_CC_SYSTEM_ENVIRONMENT_PROD;
 
             let mut cc_gpus_ready: std::os::raw::c_uint = 0;
-            nvml_try(cc_gpus_ready_sym(&mut cc_gpus_ready))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlSystemGetConfComputeGpusReadyState(&mut cc_gpus_ready)?,
+            )?;
             let is_accepting_client_requests =
                 cc_gpus_ready == NVML_CC_ACCEPTING_CLIENT_REQUESTS_TRUE;
 
@@ -915,16 +927,13 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceGetConfComputeGpusReadyState")]
     pub fn get_confidential_compute_state(&self) -> Result<bool, NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlSystemGetConfComputeGpusReadyState
-                .as_ref(),
-        )?;
-
         unsafe {
             let mut is_accepting_work: u32 = 0;
-            nvml_try(sym(&mut is_accepting_work))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlSystemGetConfComputeGpusReadyState(&mut is_accepting_work)?,
+            )?;
             Ok(is_accepting_work == NVML_CC_ACCEPTING_CLIENT_REQUESTS_TRUE)
         }
     }
@@ -938,15 +947,12 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceSetConfComputeState")]
     pub fn set_confidential_compute_state(&self, is_accepting_work: bool) -> Result<(), NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlSystemSetConfComputeGpusReadyState
-                .as_ref(),
-        )?;
-
         unsafe {
-            nvml_try(sym(is_accepting_work as u32))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlSystemSetConfComputeGpusReadyState(is_accepting_work as u32)?,
+            )?;
             Ok(())
         }
     }
@@ -964,14 +970,16 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceSetConfComputeSettings")]
     pub fn is_cc_enabled(&self) -> Result<bool, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlSystemGetConfComputeSettings.as_ref())?;
-
         unsafe {
             let mut settings: nvmlSystemConfComputeSettings_t = mem::zeroed();
             // Implements NVML_STRUCT_VERSION(SystemConfComputeSettings, 1), as detailed in nvml.h
             settings.version = (std::mem::size_of::<nvmlSystemConfComputeSettings_v1_t>()
                 | (1_usize << 24_usize)) as u32;
-            nvml_try(sym(&mut settings))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlSystemGetConfComputeSettings(&mut settings)?,
+            )?;
             Ok(settings.ccFeature == NVML_CC_SYSTEM_FEATURE_ENABLED)
         }
     }
@@ -989,14 +997,16 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlSystemGetConfComputeSettings")]
     pub fn is_multi_gpu_protected_pcie_enabled(&self) -> Result<bool, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlSystemGetConfComputeSettings.as_ref())?;
-
         unsafe {
             let mut settings: nvmlSystemConfComputeSettings_t = mem::zeroed();
             // Implements NVML_STRUCT_VERSION(SystemConfComputeSettings, 1), as detailed in nvml.h
             settings.version = (std::mem::size_of::<nvmlSystemConfComputeSettings_v1_t>()
                 | (1_usize << 24_usize)) as u32;
-            nvml_try(sym(&mut settings))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlSystemGetConfComputeSettings(&mut settings)?,
+            )?;
             Ok(settings.multiGpuMode == NVML_CC_SYSTEM_MULTIGPU_PROTECTED_PCIE)
         }
     }
@@ -1014,14 +1024,16 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlSystemGetConfComputeSettings")]
     pub fn is_cc_dev_mode_enabled(&self) -> Result<bool, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlSystemGetConfComputeSettings.as_ref())?;
-
         unsafe {
             let mut settings: nvmlSystemConfComputeSettings_t = mem::zeroed();
             // Implements NVML_STRUCT_VERSION(SystemConfComputeSettings, 1), as detailed in nvml.h
             settings.version = (std::mem::size_of::<nvmlSystemConfComputeSettings_v1_t>()
                 | (1_usize << 24_usize)) as u32;
-            nvml_try(sym(&mut settings))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlSystemGetConfComputeSettings(&mut settings)?,
+            )?;
             Ok(settings.devToolsMode == NVML_CC_SYSTEM_DEVTOOLS_MODE_ON)
         }
     }
@@ -1036,11 +1048,13 @@ impl<'nvml> Device<'nvml> {
     pub fn get_confidential_compute_capabilities(
         &self,
     ) -> Result<ConfidentialComputeCapabilities, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlSystemGetConfComputeCapabilities.as_ref())?;
-
         unsafe {
             let mut capabilities: nvmlConfComputeSystemCaps_t = mem::zeroed();
-            nvml_try(sym(&mut capabilities))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlSystemGetConfComputeCapabilities(&mut capabilities)?,
+            )?;
 
             let cpu_caps = match capabilities.cpuCaps {
                 NVML_CC_SYSTEM_CPU_CAPS_NONE => ConfidentialComputeCpuCapabilities::None,
@@ -1089,18 +1103,15 @@ impl<'nvml> Device<'nvml> {
         &self,
         nonce: [u8; NVML_CC_GPU_CEC_NONCE_SIZE as usize],
     ) -> Result<ConfidentialComputeGpuAttestationReport, NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlDeviceGetConfComputeGpuAttestationReport
-                .as_ref(),
-        )?;
-
         unsafe {
             let mut report: nvmlConfComputeGpuAttestationReport_st = mem::zeroed();
             report.nonce = nonce;
 
-            nvml_try(sym(self.device, &mut report))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetConfComputeGpuAttestationReport(self.device, &mut report)?,
+            )?;
 
             let is_cec_attestation_report_present = report.isCecAttestationReportPresent == 1;
             Ok(ConfidentialComputeGpuAttestationReport {
@@ -1130,16 +1141,13 @@ impl<'nvml> Device<'nvml> {
     pub fn confidential_compute_gpu_certificate(
         &self,
     ) -> Result<ConfidentialComputeGpuCertificate, NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlDeviceGetConfComputeGpuCertificate
-                .as_ref(),
-        )?;
-
         unsafe {
             let mut certificate_chain: nvmlConfComputeGpuCertificate_t = mem::zeroed();
-            nvml_try(sym(self.device, &mut certificate_chain))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetConfComputeGpuCertificate(self.device, &mut certificate_chain)?,
+            )?;
 
             Ok(ConfidentialComputeGpuCertificate {
                 cert_chain_size: certificate_chain.certChainSize,
@@ -1172,12 +1180,14 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetCurrPcieLinkGeneration")]
     pub fn current_pcie_link_gen(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetCurrPcieLinkGeneration.as_ref())?;
-
         unsafe {
             let mut link_gen: c_uint = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut link_gen))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetCurrPcieLinkGeneration(self.device, &mut link_gen)?,
+            )?;
 
             Ok(link_gen)
         }
@@ -1202,11 +1212,13 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetCurrPcieLinkWidth")]
     pub fn current_pcie_link_width(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetCurrPcieLinkWidth.as_ref())?;
-
         unsafe {
             let mut link_width: c_uint = mem::zeroed();
-            nvml_try(sym(self.device, &mut link_width))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetCurrPcieLinkWidth(self.device, &mut link_width)?,
+            )?;
 
             Ok(link_width)
         }
@@ -1231,13 +1243,87 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetDecoderUtilization")]
     pub fn decoder_utilization(&self) -> Result<UtilizationInfo, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetDecoderUtilization.as_ref())?;
+        unsafe {
+            let mut utilization: c_uint = mem::zeroed();
+            let mut sampling_period: c_uint = mem::zeroed();
+
+            nvml_try(self.nvml.backend.nvmlDeviceGetDecoderUtilization(
+                self.device,
+                &mut utilization,
+                &mut sampling_period,
+            )?)?;
 
+            Ok(UtilizationInfo {
+                utilization,
+                sampling_period,
+            })
+        }
+    }
+
+    /**
+    Gets the current utilization and sampling size (sampling size in μs) for the JPEG
+    decoder (NVJPG).
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Turing or newer fully supported devices.
+    */
+    // Tested
+    #[doc(alias = "nvmlDeviceGetJpgUtilization")]
+    pub fn jpeg_utilization(&self) -> Result<UtilizationInfo, NvmlError> {
+        unsafe {
+            let mut utilization: c_uint = mem::zeroed();
+            let mut sampling_period: c_uint = mem::zeroed();
+
+            nvml_try(self.nvml.backend.nvmlDeviceGetJpgUtilization(
+                self.device,
+                &mut utilization,
+                &mut sampling_period,
+            )?)?;
+
+            Ok(UtilizationInfo {
+                utilization,
+                sampling_period,
+            })
+        }
+    }
+
+    /**
+    Gets the current utilization and sampling size (sampling size in μs) for the
+    optical flow accelerator (OFA).
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Turing or newer fully supported devices.
+    */
+    // Tested
+    #[doc(alias = "nvmlDeviceGetOfaUtilization")]
+    pub fn ofa_utilization(&self) -> Result<UtilizationInfo, NvmlError> {
         unsafe {
             let mut utilization: c_uint = mem::zeroed();
             let mut sampling_period: c_uint = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut utilization, &mut sampling_period))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetOfaUtilization(
+                self.device,
+                &mut utilization,
+                &mut sampling_period,
+            )?)?;
 
             Ok(UtilizationInfo {
                 utilization,
@@ -1263,11 +1349,13 @@ impl<'nvml> Device<'nvml> {
     // tested
     #[doc(alias = "nvmlDeviceGetFBCStats")]
     pub fn fbc_stats(&self) -> Result<FbcStats, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetFBCStats.as_ref())?;
-
         unsafe {
             let mut fbc_stats: nvmlFBCStats_t = mem::zeroed();
-            nvml_try(sym(self.device, &mut fbc_stats))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetFBCStats(self.device, &mut fbc_stats)?,
+            )?;
 
             Ok(fbc_stats.into())
         }
@@ -1297,8 +1385,6 @@ impl<'nvml> Device<'nvml> {
     // tested
     #[doc(alias = "nvmlDeviceGetFBCSessions")]
     pub fn fbc_sessions_info(&self) -> Result<Vec<FbcSessionInfo>, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetFBCSessions.as_ref())?;
-
         unsafe {
             let mut count: c_uint = match self.fbc_session_count()? {
                 0 => return Ok(vec![]),
@@ -1306,7 +1392,11 @@ impl<'nvml> Device<'nvml> {
             };
             let mut info: Vec<nvmlFBCSessionInfo_t> = vec![mem::zeroed(); count as usize];
 
-            nvml_try(sym(self.device, &mut count, info.as_mut_ptr()))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetFBCSessions(
+                self.device,
+                &mut count,
+                info.as_mut_ptr(),
+            )?)?;
 
             info.into_iter().map(FbcSessionInfo::try_from).collect()
         }
@@ -1325,12 +1415,14 @@ impl<'nvml> Device<'nvml> {
     // tested as part of the above
     #[doc(alias = "nvmlDeviceGetFBCSessions")]
     pub fn fbc_session_count(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetFBCSessions.as_ref())?;
-
         unsafe {
             let mut count: c_uint = 0;
 
-            nvml_try(sym(self.device, &mut count, ptr::null_mut()))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetFBCSessions(
+                self.device,
+                &mut count,
+                ptr::null_mut(),
+            )?)?;
 
             Ok(count)
         }
@@ -1351,11 +1443,13 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceGetAttributes_v2")]
     pub fn attributes(&self) -> Result<DeviceAttributes, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetAttributes_v2.as_ref())?;
-
         unsafe {
             let mut attrs: nvmlDeviceAttributes_t = mem::zeroed();
-            nvml_try(sym(self.device, &mut attrs))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetAttributes_v2(self.device, &mut attrs)?,
+            )?;
 
             Ok(attrs.into())
         }
@@ -1381,12 +1475,14 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetDefaultApplicationsClock")]
     pub fn default_applications_clock(&self, clock_type: Clock) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetDefaultApplicationsClock.as_ref())?;
-
         unsafe {
             let mut clock: c_uint = mem::zeroed();
 
-            nvml_try(sym(self.device, clock_type.as_c(), &mut clock))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetDefaultApplicationsClock(
+                self.device,
+                clock_type.as_c(),
+                &mut clock,
+            )?)?;
 
             Ok(clock)
         }
@@ -1401,17 +1497,15 @@ impl<'nvml> Device<'nvml> {
         error_type: MemoryError,
         counter_type: EccCounter,
     ) -> Result<EccErrorCounts, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetDetailedEccErrors.as_ref())?;
-
         unsafe {
             let mut counts: nvmlEccErrorCounts_t = mem::zeroed();
 
-            nvml_try(sym(
+            nvml_try(self.nvml.backend.nvmlDeviceGetDetailedEccErrors(
                 self.device,
                 error_type.as_c(),
                 counter_type.as_c(),
                 &mut counts,
-            ))?;
+            )?)?;
 
             Ok(counts.into())
         }
@@ -1439,11 +1533,13 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetDisplayActive")]
     pub fn is_display_active(&self) -> Result<bool, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetDisplayActive.as_ref())?;
-
         unsafe {
             let mut state: nvmlEnableState_t = mem::zeroed();
-            nvml_try(sym(self.device, &mut state))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetDisplayActive(self.device, &mut state)?,
+            )?;
 
             bool_from_state(state)
         }
@@ -1468,11 +1564,13 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetDisplayMode")]
     pub fn is_display_connected(&self) -> Result<bool, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetDisplayMode.as_ref())?;
-
         unsafe {
             let mut state: nvmlEnableState_t = mem::zeroed();
-            nvml_try(sym(self.device, &mut state))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetDisplayMode(self.device, &mut state)?,
+            )?;
 
             bool_from_state(state)
         }
@@ -1507,13 +1605,15 @@ impl<'nvml> Device<'nvml> {
     #[cfg(target_os = "windows")]
     #[doc(alias = "nvmlDeviceGetDriverModel")]
     pub fn driver_model(&self) -> Result<DriverModelState, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetDriverModel.as_ref())?;
-
         unsafe {
             let mut current: nvmlDriverModel_t = mem::zeroed();
             let mut pending: nvmlDriverModel_t = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut current, &mut pending))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetDriverModel(
+                self.device,
+                &mut current,
+                &mut pending,
+            )?)?;
 
             Ok(DriverModelState {
                 current: DriverModel::try_from(current)?,
@@ -1546,13 +1646,15 @@ impl<'nvml> Device<'nvml> {
     // Tested on machines other than my own
     #[doc(alias = "nvmlDeviceGetEccMode")]
     pub fn is_ecc_enabled(&self) -> Result<EccModeState, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetEccMode.as_ref())?;
-
         unsafe {
             let mut current: nvmlEnableState_t = mem::zeroed();
             let mut pending: nvmlEnableState_t = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut current, &mut pending))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetEccMode(
+                self.device,
+                &mut current,
+                &mut pending,
+            )?)?;
 
             Ok(EccModeState {
                 currently_enabled: bool_from_state(current)?,
@@ -1580,13 +1682,15 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetEncoderUtilization")]
     pub fn encoder_utilization(&self) -> Result<UtilizationInfo, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetEncoderUtilization.as_ref())?;
-
         unsafe {
             let mut utilization: c_uint = mem::zeroed();
             let mut sampling_period: c_uint = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut utilization, &mut sampling_period))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetEncoderUtilization(
+                self.device,
+                &mut utilization,
+                &mut sampling_period,
+            )?)?;
 
             Ok(UtilizationInfo {
                 utilization,
@@ -1613,12 +1717,14 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetEncoderCapacity")]
     pub fn encoder_capacity(&self, for_type: EncoderType) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetEncoderCapacity.as_ref())?;
-
         unsafe {
             let mut capacity: c_uint = mem::zeroed();
 
-            nvml_try(sym(self.device, for_type.as_c(), &mut capacity))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetEncoderCapacity(
+                self.device,
+                for_type.as_c(),
+                &mut capacity,
+            )?)?;
 
             Ok(capacity)
         }
@@ -1641,19 +1747,17 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetEncoderStats")]
     pub fn encoder_stats(&self) -> Result<EncoderStats, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetEncoderStats.as_ref())?;
-
         unsafe {
             let mut session_count: c_uint = mem::zeroed();
             let mut average_fps: c_uint = mem::zeroed();
             let mut average_latency: c_uint = mem::zeroed();
 
-            nvml_try(sym(
+            nvml_try(self.nvml.backend.nvmlDeviceGetEncoderStats(
                 self.device,
                 &mut session_count,
                 &mut average_fps,
                 &mut average_latency,
-            ))?;
+            )?)?;
 
             Ok(EncoderStats {
                 session_count,
@@ -1682,8 +1786,6 @@ impl<'nvml> Device<'nvml> {
     // TODO: Test this with an active session and make sure it works
     #[doc(alias = "nvmlDeviceGetEncoderSessions")]
     pub fn encoder_sessions(&self) -> Result<Vec<EncoderSessionInfo>, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetEncoderSessions.as_ref())?;
-
         unsafe {
             let mut count = match self.encoder_sessions_count()? {
                 0 => return Ok(vec![]),
@@ -1691,7 +1793,11 @@ impl<'nvml> Device<'nvml> {
             };
             let mut sessions: Vec<nvmlEncoderSessionInfo_t> = vec![mem::zeroed(); count as usize];
 
-            nvml_try(sym(self.device, &mut count, sessions.as_mut_ptr()))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetEncoderSessions(
+                self.device,
+                &mut count,
+                sessions.as_mut_ptr(),
+            )?)?;
 
             sessions.truncate(count as usize);
             sessions
@@ -1713,12 +1819,14 @@ impl<'nvml> Device<'nvml> {
     */
     // tested as part of the above
     fn encoder_sessions_count(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetEncoderSessions.as_ref())?;
-
         unsafe {
             let mut count: c_uint = 0;
 
-            nvml_try(sym(self.device, &mut count, ptr::null_mut()))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetEncoderSessions(
+                self.device,
+                &mut count,
+                ptr::null_mut(),
+            )?)?;
 
             Ok(count)
         }
@@ -1747,11 +1855,13 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetEnforcedPowerLimit")]
     pub fn enforced_power_limit(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetEnforcedPowerLimit.as_ref())?;
-
         unsafe {
             let mut limit: c_uint = mem::zeroed();
-            nvml_try(sym(self.device, &mut limit))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetEnforcedPowerLimit(self.device, &mut limit)?,
+            )?;
 
             Ok(limit)
         }
@@ -1777,11 +1887,13 @@ impl<'nvml> Device<'nvml> {
     // Tested (no-run)
     #[doc(alias = "nvmlDeviceGetGpcClkVfOffset")]
     pub fn gpc_clock_vf_offset(&self) -> Result<i32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetGpcClkVfOffset.as_ref())?;
-
         unsafe {
             let mut offset: c_int = mem::zeroed();
-            nvml_try(sym(self.device, &mut offset))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetGpcClkVfOffset(self.device, &mut offset)?,
+            )?;
 
             Ok(offset)
         }
@@ -1807,9 +1919,13 @@ impl<'nvml> Device<'nvml> {
     // Tested (no-run)
     #[doc(alias = "nvmlDeviceGetGpcClkVfOffset")]
     pub fn set_gpc_clock_vf_offset(&self, offset: i32) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetGpcClkVfOffset.as_ref())?;
-
-        unsafe { nvml_try(sym(self.device, offset)) }
+        unsafe {
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceSetGpcClkVfOffset(self.device, offset)?,
+            )
+        }
     }
 
     /**
@@ -1832,11 +1948,13 @@ impl<'nvml> Device<'nvml> {
     // Tested (no-run)
     #[doc(alias = "nvmlDeviceGetGpcMemClkVfOffset")]
     pub fn mem_clock_vf_offset(&self) -> Result<i32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetMemClkVfOffset.as_ref())?;
-
         unsafe {
             let mut offset: c_int = mem::zeroed();
-            nvml_try(sym(self.device, &mut offset))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetMemClkVfOffset(self.device, &mut offset)?,
+            )?;
 
             Ok(offset)
         }
@@ -1862,9 +1980,13 @@ impl<'nvml> Device<'nvml> {
     // Tested (no-run)
     #[doc(alias = "nvmlDeviceSetGpcMemClkVfOffset")]
     pub fn set_mem_clock_vf_offset(&self, offset: i32) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetMemClkVfOffset.as_ref())?;
-
-        unsafe { nvml_try(sym(self.device, offset)) }
+        unsafe {
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceSetMemClkVfOffset(self.device, offset)?,
+            )
+        }
     }
 
     /**
@@ -1892,11 +2014,13 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetFanSpeed_v2")]
     pub fn fan_speed(&self, fan_idx: u32) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetFanSpeed_v2.as_ref())?;
-
         unsafe {
             let mut speed: c_uint = mem::zeroed();
-            nvml_try(sym(self.device, fan_idx, &mut speed))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetFanSpeed_v2(
+                self.device,
+                fan_idx,
+                &mut speed,
+            )?)?;
 
             Ok(speed)
         }
@@ -1930,15 +2054,17 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetFanSpeedRPM")]
     pub fn fan_speed_rpm(&self, fan_idx: u32) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetFanSpeedRPM.as_ref())?;
-
         unsafe {
             let mut fan_speed: nvmlFanSpeedInfo_t = mem::zeroed();
             // Implements NVML_STRUCT_VERSION(FanSpeedInfo, 1), as detailed in nvml.h
             fan_speed.version =
                 (std::mem::size_of::<nvmlFanSpeedInfo_v1_t>() | (1_usize << 24_usize)) as u32;
             fan_speed.fan = fan_idx;
-            nvml_try(sym(self.device, &mut fan_speed))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetFanSpeedRPM(self.device, &mut fan_speed)?,
+            )?;
 
             Ok(fan_speed.speed)
         }
@@ -1964,12 +2090,14 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetMinMaxFanSpeed")]
     pub fn min_max_fan_speed(&self) -> Result<(u32, u32), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetMinMaxFanSpeed.as_ref())?;
-
         unsafe {
             let mut min = mem::zeroed();
             let mut max = mem::zeroed();
-            nvml_try(sym(self.device, &mut min, &mut max))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetMinMaxFanSpeed(
+                self.device,
+                &mut min,
+                &mut max,
+            )?)?;
             Ok((min, max))
         }
     }
@@ -1994,11 +2122,13 @@ impl<'nvml> Device<'nvml> {
      */
     #[doc(alias = "nvmlGetFanControlPolicy_v2")]
     pub fn fan_control_policy(&self, fan_idx: u32) -> Result<FanControlPolicy, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetFanControlPolicy_v2.as_ref())?;
-
         unsafe {
             let mut policy: nvmlFanControlPolicy_t = mem::zeroed();
-            nvml_try(sym(self.device, fan_idx, &mut policy))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetFanControlPolicy_v2(
+                self.device,
+                fan_idx,
+                &mut policy,
+            )?)?;
 
             FanControlPolicy::try_from(policy)
         }
@@ -2028,9 +2158,13 @@ impl<'nvml> Device<'nvml> {
         fan_idx: u32,
         policy: FanControlPolicy,
     ) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetFanControlPolicy.as_ref())?;
-
-        unsafe { nvml_try(sym(self.device, fan_idx, policy.as_c())) }
+        unsafe {
+            nvml_try(self.nvml.backend.nvmlDeviceSetFanControlPolicy(
+                self.device,
+                fan_idx,
+                policy.as_c(),
+            )?)
+        }
     }
 
     /**
@@ -2056,9 +2190,13 @@ impl<'nvml> Device<'nvml> {
      */
     #[doc(alias = "nvmlDeviceSetFanSpeed_v2")]
     pub fn set_fan_speed(&mut self, fan_idx: u32, speed: u32) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetFanSpeed_v2.as_ref())?;
-
-        unsafe { nvml_try(sym(self.device, fan_idx, speed)) }
+        unsafe {
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceSetFanSpeed_v2(self.device, fan_idx, speed)?,
+            )
+        }
     }
 
     /**
@@ -2081,9 +2219,13 @@ impl<'nvml> Device<'nvml> {
      */
     #[doc(alias = "nvmlDeviceSetDefaultFanSpeed_v2")]
     pub fn set_default_fan_speed(&mut self, fan_idx: u32) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetDefaultFanSpeed_v2.as_ref())?;
-
-        unsafe { nvml_try(sym(self.device, fan_idx)) }
+        unsafe {
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceSetDefaultFanSpeed_v2(self.device, fan_idx)?,
+            )
+        }
     }
 
     /**
@@ -2102,11 +2244,13 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceGetNumFans")]
     pub fn num_fans(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetNumFans.as_ref())?;
-
         unsafe {
             let mut count: c_uint = mem::zeroed();
-            nvml_try(sym(self.device, &mut count))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetNumFans(self.device, &mut count)?,
+            )?;
 
             Ok(count)
         }
@@ -2135,13 +2279,15 @@ impl<'nvml> Device<'nvml> {
     // Tested on machines other than my own
     #[doc(alias = "nvmlDeviceGetGpuOperationMode")]
     pub fn gpu_operation_mode(&self) -> Result<OperationModeState, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetGpuOperationMode.as_ref())?;
-
         unsafe {
             let mut current: nvmlGpuOperationMode_t = mem::zeroed();
             let mut pending: nvmlGpuOperationMode_t = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut current, &mut pending))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetGpuOperationMode(
+                self.device,
+                &mut current,
+                &mut pending,
+            )?)?;
 
             Ok(OperationModeState {
                 current: OperationMode::try_from(current)?,
@@ -2165,13 +2311,6 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetGraphicsRunningProcesses_v3")]
     pub fn running_graphics_processes(&self) -> Result<Vec<ProcessInfo>, NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlDeviceGetGraphicsRunningProcesses_v3
-                .as_ref(),
-        )?;
-
         unsafe {
             let mut count: c_uint = match self.running_graphics_processes_count()? {
                 0 => return Ok(vec![]),
@@ -2183,7 +2322,11 @@ impl<'nvml> Device<'nvml> {
             count += 5;
             let mut processes: Vec<nvmlProcessInfo_t> = vec![mem::zeroed(); count as usize];
 
-            nvml_try(sym(self.device, &mut count, processes.as_mut_ptr()))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetGraphicsRunningProcesses_v3(
+                self.device,
+                &mut count,
+                processes.as_mut_ptr(),
+            )?)?;
             processes.truncate(count as usize);
 
             Ok(processes.into_iter().map(ProcessInfo::from).collect())
@@ -2206,19 +2349,16 @@ impl<'nvml> Device<'nvml> {
     // Tested as part of `.running_graphics_processes()`
     #[doc(alias = "nvmlDeviceGetGraphicsRunningProcesses_v3")]
     pub fn running_graphics_processes_count(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlDeviceGetGraphicsRunningProcesses_v3
-                .as_ref(),
-        )?;
-
         unsafe {
             // Indicates that we want the count
             let mut count: c_uint = 0;
 
             // Passing null doesn't indicate that we want the count. It's just allowed.
-            nvml_try_count(sym(self.device, &mut count, ptr::null_mut()))?;
+            nvml_try_count(self.nvml.backend.nvmlDeviceGetGraphicsRunningProcesses_v3(
+                self.device,
+                &mut count,
+                ptr::null_mut(),
+            )?)?;
             Ok(count)
         }
     }
@@ -2238,13 +2378,6 @@ impl<'nvml> Device<'nvml> {
     #[doc(alias = "nvmlDeviceGetGraphicsRunningProcesses_v2")]
     #[cfg(feature = "legacy-functions")]
     pub fn running_graphics_processes_v2(&self) -> Result<Vec<ProcessInfo>, NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlDeviceGetGraphicsRunningProcesses_v2
-                .as_ref(),
-        )?;
-
         unsafe {
             let mut count: c_uint = match self.running_graphics_processes_count_v2()? {
                 0 => return Ok(vec![]),
@@ -2256,7 +2389,11 @@ impl<'nvml> Device<'nvml> {
             count += 5;
             let mut processes: Vec<nvmlProcessInfo_v2_t> = vec![mem::zeroed(); count as usize];
 
-            nvml_try(sym(self.device, &mut count, processes.as_mut_ptr()))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetGraphicsRunningProcesses_v2(
+                self.device,
+                &mut count,
+                processes.as_mut_ptr(),
+            )?)?;
             processes.truncate(count as usize);
 
             Ok(processes.into_iter().map(ProcessInfo::from).collect())
@@ -2279,19 +2416,16 @@ impl<'nvml> Device<'nvml> {
     #[doc(alias = "nvmlDeviceGetGraphicsRunningProcesses_v2")]
     #[cfg(feature = "legacy-functions")]
     pub fn running_graphics_processes_count_v2(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlDeviceGetGraphicsRunningProcesses_v2
-                .as_ref(),
-        )?;
-
         unsafe {
             // Indicates that we want the count
             let mut count: c_uint = 0;
 
             // Passing null doesn't indicate that we want the count. It's just allowed.
-            nvml_try_count(sym(self.device, &mut count, ptr::null_mut()))?;
+            nvml_try_count(self.nvml.backend.nvmlDeviceGetGraphicsRunningProcesses_v2(
+                self.device,
+                &mut count,
+                ptr::null_mut(),
+            )?)?;
             Ok(count)
         }
     }
@@ -2325,8 +2459,6 @@ impl<'nvml> Device<'nvml> {
     where
         T: Into<Option<u64>>,
     {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetProcessUtilization.as_ref())?;
-
         unsafe {
             let last_seen_timestamp = last_seen_timestamp.into().unwrap_or(0);
             let mut count = match self.process_utilization_stats_count(last_seen_timestamp)? {
@@ -2336,12 +2468,12 @@ impl<'nvml> Device<'nvml> {
             let mut utilization_samples: Vec<nvmlProcessUtilizationSample_t> =
                 vec![mem::zeroed(); count as usize];
 
-            nvml_try(sym(
+            nvml_try(self.nvml.backend.nvmlDeviceGetProcessUtilization(
                 self.device,
                 utilization_samples.as_mut_ptr(),
                 &mut count,
                 last_seen_timestamp,
-            ))?;
+            )?)?;
             utilization_samples.truncate(count as usize);
 
             Ok(utilization_samples
@@ -2355,122 +2487,309 @@ impl<'nvml> Device<'nvml> {
         &self,
         last_seen_timestamp: u64,
     ) -> Result<c_uint, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetProcessUtilization.as_ref())?;
-
         unsafe {
             let mut count: c_uint = 0;
 
-            nvml_try_count(sym(
+            nvml_try_count(self.nvml.backend.nvmlDeviceGetProcessUtilization(
                 self.device,
                 ptr::null_mut(),
                 &mut count,
                 last_seen_timestamp,
-            ))?;
+            )?)?;
             Ok(count)
         }
     }
 
     /**
-    Gets the NVML index of this `Device`.
-
-    Keep in mind that the order in which NVML enumerates devices has no guarantees of
-    consistency between reboots. Also, the NVML index may not correlate with other APIs,
-    such as the CUDA device index.
+    Gets the processes of the given kind running on this `Device`, with their
+    memory usage and MIG placement.
 
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
     * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` doesn't support this feature
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `ArgumentVersionMismatch`, if the installed driver doesn't support this call
+    * `Unknown`, on any unexpected error
     */
-    // Checked against local
-    // Tested
-    #[doc(alias = "nvmlDeviceGetIndex")]
-    pub fn index(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetIndex.as_ref())?;
-
+    #[doc(alias = "nvmlDeviceGetRunningProcessDetailList")]
+    pub fn running_process_detail_list(
+        &self,
+        mode: ProcessMode,
+    ) -> Result<Vec<ProcessDetail>, NvmlError> {
         unsafe {
-            let mut index: c_uint = mem::zeroed();
-            nvml_try(sym(self.device, &mut index))?;
+            let mut list: nvmlProcessDetailList_t = mem::zeroed();
+            list.version = process_detail_list_version();
+            list.mode = mode.as_c();
 
-            Ok(index)
+            nvml_try_count(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetRunningProcessDetailList(self.device, &mut list)?,
+            )?;
+
+            if list.numProcArrayEntries == 0 {
+                return Ok(vec![]);
+            }
+
+            // Add a bit of headroom in case more processes are launched in
+            // between the above call to get the expected count and the time we
+            // actually make the call to get data below.
+            list.numProcArrayEntries += 5;
+            let mut processes: Vec<nvmlProcessDetail_v1_t> =
+                vec![mem::zeroed(); list.numProcArrayEntries as usize];
+            list.procArray = processes.as_mut_ptr();
+
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetRunningProcessDetailList(self.device, &mut list)?,
+            )?;
+            processes.truncate(list.numProcArrayEntries as usize);
+
+            Ok(processes.into_iter().map(ProcessDetail::from).collect())
         }
     }
 
     /**
-    Gets the checksum of the config stored in this `Device`'s infoROM.
+    Gets utilization stats for relevant currently running processes, including
+    the JPEG and OFA engines.
 
-    Can be used to make sure that two GPUs have the exact same configuration.
-    The current checksum takes into account configuration stored in PWR and ECC
-    infoROM objects. The checksum can change between driver released or when the
-    user changes the configuration (e.g. disabling/enabling ECC).
+    This has the same semantics as `.process_utilization_stats()`: passing
+    `None` as the `last_seen_timestamp` will target all samples that the driver
+    has buffered; passing a timestamp retrieved from a previous query will target
+    samples taken since that timestamp.
 
     # Errors
 
-    * `CorruptedInfoROM`, if this `Device`'s checksum couldn't be retrieved due to infoROM corruption
     * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotFound`, if there are no samples
     * `NotSupported`, if this `Device` does not support this feature
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `ArgumentVersionMismatch`, if the installed driver doesn't support this call
     * `Unknown`, on any unexpected error
 
     # Device Support
 
-    Supports all devices with an infoROM.
+    Supports Turing or newer fully supported devices.
     */
-    // Checked against local
-    // Tested on machines other than my own
-    #[doc(alias = "nvmlDeviceGetInforomConfigurationChecksum")]
-    pub fn config_checksum(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlDeviceGetInforomConfigurationChecksum
-                .as_ref(),
-        )?;
-
+    #[doc(alias = "nvmlDeviceGetProcessesUtilizationInfo")]
+    pub fn processes_utilization_info<T>(
+        &self,
+        last_seen_timestamp: T,
+    ) -> Result<Vec<ProcessUtilizationInfo>, NvmlError>
+    where
+        T: Into<Option<u64>>,
+    {
         unsafe {
-            let mut checksum: c_uint = mem::zeroed();
+            let mut info: nvmlProcessesUtilizationInfo_t = mem::zeroed();
+            info.version = processes_utilization_info_version();
+            info.lastSeenTimeStamp = last_seen_timestamp.into().unwrap_or(0);
 
-            nvml_try(sym(self.device, &mut checksum))?;
+            nvml_try_count(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetProcessesUtilizationInfo(self.device, &mut info)?,
+            )?;
 
-            Ok(checksum)
+            if info.processSamplesCount == 0 {
+                return Ok(vec![]);
+            }
+
+            let mut samples: Vec<nvmlProcessUtilizationInfo_v1_t> =
+                vec![mem::zeroed(); info.processSamplesCount as usize];
+            info.procUtilArray = samples.as_mut_ptr();
+
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetProcessesUtilizationInfo(self.device, &mut info)?,
+            )?;
+            samples.truncate(info.processSamplesCount as usize);
+
+            Ok(samples
+                .into_iter()
+                .map(ProcessUtilizationInfo::from)
+                .collect())
         }
     }
 
     /**
-    Gets the global infoROM image version.
+    Gets every process running on this `Device`, whatever its kind of context,
+    with its memory usage, MIG placement and latest utilization sample.
 
-    This image version, just like the VBIOS version, uniquely describes the exact version
-    of the infoROM flashed on the board, in contrast to the infoROM object version which
-    is only an indicator of supported features.
+    This merges `.running_process_detail_list()` for each `ProcessMode` and
+    `.processes_utilization_info()` into one entry per process. Processes
+    without a utilization sample (or on devices that don't report them) have no
+    `utilization`.
 
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
     * `InvalidArg`, if this `Device` is invalid
-    * `NotSupported`, if this `Device` does not have an infoROM
+    * `NotSupported`, if this `Device` doesn't support listing processes this way
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
-    * `Utf8Error`, if the string obtained from the C function is not valid Utf8
+    * `ArgumentVersionMismatch`, if the installed driver doesn't support this call
     * `Unknown`, on any unexpected error
+    */
+    pub fn gpu_processes(&self) -> Result<Vec<GpuProcess>, NvmlError> {
+        let mut processes: Vec<GpuProcess> = Vec::new();
 
-    # Device Support
+        for mode in [
+            ProcessMode::Compute,
+            ProcessMode::Graphics,
+            ProcessMode::Mps,
+        ] {
+            let details = match self.running_process_detail_list(mode) {
+                // MPS is only supported on Volta and newer
+                Err(NvmlError::NotSupported) if mode == ProcessMode::Mps => vec![],
+                result => result?,
+            };
 
-    Supports all devices with an infoROM.
+            for detail in details {
+                match processes.iter_mut().find(|p| p.pid == detail.pid) {
+                    Some(process) => {
+                        process.types |= mode.into();
+                        process.gpu_instance_id =
+                            process.gpu_instance_id.or(detail.gpu_instance_id);
+                        process.compute_instance_id =
+                            process.compute_instance_id.or(detail.compute_instance_id);
+                    }
+                    None => processes.push(GpuProcess {
+                        pid: detail.pid,
+                        types: mode.into(),
+                        used_gpu_memory: detail.used_gpu_memory,
+                        gpu_instance_id: detail.gpu_instance_id,
+                        compute_instance_id: detail.compute_instance_id,
+                        utilization: None,
+                    }),
+                }
+            }
+        }
+
+        let samples = match self.processes_utilization_info(None) {
+            Err(NvmlError::NotFound)
+            | Err(NvmlError::NotSupported)
+            | Err(NvmlError::FailedToLoadSymbol(_)) => vec![],
+            result => result?,
+        };
+
+        for sample in samples {
+            if let Some(process) = processes.iter_mut().find(|p| p.pid == sample.pid) {
+                let is_newer = process
+                    .utilization
+                    .as_ref()
+                    .map_or(true, |latest| latest.timestamp < sample.timestamp);
+
+                if is_newer {
+                    process.utilization = Some(sample);
+                }
+            }
+        }
+
+        Ok(processes)
+    }
+
+    /**
+    Gets the NVML index of this `Device`.
+
+    Keep in mind that the order in which NVML enumerates devices has no guarantees of
+    consistency between reboots. Also, the NVML index may not correlate with other APIs,
+    such as the CUDA device index.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    */
+    // Checked against local
+    // Tested
+    #[doc(alias = "nvmlDeviceGetIndex")]
+    pub fn index(&self) -> Result<u32, NvmlError> {
+        unsafe {
+            let mut index: c_uint = mem::zeroed();
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetIndex(self.device, &mut index)?,
+            )?;
+
+            Ok(index)
+        }
+    }
+
+    /**
+    Gets the checksum of the config stored in this `Device`'s infoROM.
+
+    Can be used to make sure that two GPUs have the exact same configuration.
+    The current checksum takes into account configuration stored in PWR and ECC
+    infoROM objects. The checksum can change between driver released or when the
+    user changes the configuration (e.g. disabling/enabling ECC).
+
+    # Errors
+
+    * `CorruptedInfoROM`, if this `Device`'s checksum couldn't be retrieved due to infoROM corruption
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports all devices with an infoROM.
+    */
+    // Checked against local
+    // Tested on machines other than my own
+    #[doc(alias = "nvmlDeviceGetInforomConfigurationChecksum")]
+    pub fn config_checksum(&self) -> Result<u32, NvmlError> {
+        unsafe {
+            let mut checksum: c_uint = mem::zeroed();
+
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetInforomConfigurationChecksum(self.device, &mut checksum)?,
+            )?;
+
+            Ok(checksum)
+        }
+    }
+
+    /**
+    Gets the global infoROM image version.
+
+    This image version, just like the VBIOS version, uniquely describes the exact version
+    of the infoROM flashed on the board, in contrast to the infoROM object version which
+    is only an indicator of supported features.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not have an infoROM
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Utf8Error`, if the string obtained from the C function is not valid Utf8
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports all devices with an infoROM.
     */
     // Checked against local
     // Tested on machines other than my own
     #[doc(alias = "nvmlDeviceGetInforomImageVersion")]
     pub fn info_rom_image_version(&self) -> Result<String, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetInforomImageVersion.as_ref())?;
-
         unsafe {
             let mut version_vec = vec![0; NVML_DEVICE_INFOROM_VERSION_BUFFER_SIZE as usize];
 
-            nvml_try(sym(
+            nvml_try(self.nvml.backend.nvmlDeviceGetInforomImageVersion(
                 self.device,
                 version_vec.as_mut_ptr(),
                 NVML_DEVICE_INFOROM_VERSION_BUFFER_SIZE,
-            ))?;
+            )?)?;
 
             let version_raw = CStr::from_ptr(version_vec.as_ptr());
             Ok(version_raw.to_str()?.into())
@@ -2502,17 +2821,15 @@ impl<'nvml> Device<'nvml> {
     // Tested on machines other than my own
     #[doc(alias = "nvmlDeviceGetInforomVersion")]
     pub fn info_rom_version(&self, object: InfoRom) -> Result<String, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetInforomVersion.as_ref())?;
-
         unsafe {
             let mut version_vec = vec![0; NVML_DEVICE_INFOROM_VERSION_BUFFER_SIZE as usize];
 
-            nvml_try(sym(
+            nvml_try(self.nvml.backend.nvmlDeviceGetInforomVersion(
                 self.device,
                 object.as_c(),
                 version_vec.as_mut_ptr(),
                 NVML_DEVICE_INFOROM_VERSION_BUFFER_SIZE,
-            ))?;
+            )?)?;
 
             let version_raw = CStr::from_ptr(version_vec.as_ptr());
             Ok(version_raw.to_str()?.into())
@@ -2541,12 +2858,14 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetMaxClockInfo")]
     pub fn max_clock_info(&self, clock_type: Clock) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetMaxClockInfo.as_ref())?;
-
         unsafe {
             let mut clock: c_uint = mem::zeroed();
 
-            nvml_try(sym(self.device, clock_type.as_c(), &mut clock))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetMaxClockInfo(
+                self.device,
+                clock_type.as_c(),
+                &mut clock,
+            )?)?;
 
             Ok(clock)
         }
@@ -2574,12 +2893,14 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetMaxPcieLinkGeneration")]
     pub fn max_pcie_link_gen(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetMaxPcieLinkGeneration.as_ref())?;
-
         unsafe {
             let mut max_gen: c_uint = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut max_gen))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetMaxPcieLinkGeneration(self.device, &mut max_gen)?,
+            )?;
 
             Ok(max_gen)
         }
@@ -2607,11 +2928,13 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetMaxPcieLinkWidth")]
     pub fn max_pcie_link_width(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetMaxPcieLinkWidth.as_ref())?;
-
         unsafe {
             let mut max_width: c_uint = mem::zeroed();
-            nvml_try(sym(self.device, &mut max_width))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetMaxPcieLinkWidth(self.device, &mut max_width)?,
+            )?;
 
             Ok(max_width)
         }
@@ -2646,18 +2969,16 @@ impl<'nvml> Device<'nvml> {
         counter_type: EccCounter,
         location: MemoryLocation,
     ) -> Result<u64, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetMemoryErrorCounter.as_ref())?;
-
         unsafe {
             let mut count: c_ulonglong = mem::zeroed();
 
-            nvml_try(sym(
+            nvml_try(self.nvml.backend.nvmlDeviceGetMemoryErrorCounter(
                 self.device,
                 error_type.as_c(),
                 counter_type.as_c(),
                 location.as_c(),
                 &mut count,
-            ))?;
+            )?)?;
 
             Ok(count)
         }
@@ -2687,14 +3008,16 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetMemoryInfo")]
     pub fn memory_info(&self) -> Result<MemoryInfo, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetMemoryInfo_v2.as_ref())?;
-
         unsafe {
             let mut info: nvmlMemory_v2_t = mem::zeroed();
 
             // Implements NVML_STRUCT_VERSION(Memory, 2), as detailed in nvml.h (https://github.com/NVIDIA/nvidia-settings/issues/78)
             info.version = (std::mem::size_of::<nvmlMemory_v2_t>() | (2_usize << 24_usize)) as u32;
-            nvml_try(sym(self.device, &mut info))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetMemoryInfo_v2(self.device, &mut info)?,
+            )?;
 
             Ok(info.into())
         }
@@ -2723,11 +3046,13 @@ impl<'nvml> Device<'nvml> {
     #[cfg(target_os = "linux")]
     #[doc(alias = "nvmlDeviceGetMinorNumber")]
     pub fn minor_number(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetMinorNumber.as_ref())?;
-
         unsafe {
             let mut number: c_uint = mem::zeroed();
-            nvml_try(sym(self.device, &mut number))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetMinorNumber(self.device, &mut number)?,
+            )?;
 
             Ok(number)
         }
@@ -2752,11 +3077,13 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetMultiGpuBoard")]
     pub fn is_multi_gpu_board(&self) -> Result<bool, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetMultiGpuBoard.as_ref())?;
-
         unsafe {
             let mut int_bool: c_uint = mem::zeroed();
-            nvml_try(sym(self.device, &mut int_bool))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetMultiGpuBoard(self.device, &mut int_bool)?,
+            )?;
 
             match int_bool {
                 0 => Ok(false),
@@ -2786,16 +3113,64 @@ impl<'nvml> Device<'nvml> {
     #[cfg(target_os = "linux")]
     #[doc(alias = "nvmlDeviceGetGpuInstanceProfileInfo")]
     pub fn profile_info(&self, profile: u32) -> Result<ProfileInfo, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetGpuInstanceProfileInfo.as_ref())?;
-
         unsafe {
             let mut info: nvmlGpuInstanceProfileInfo_t = mem::zeroed();
-            nvml_try(sym(self.device, profile, &mut info))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetGpuInstanceProfileInfo(
+                self.device,
+                profile,
+                &mut info,
+            )?)?;
 
             Ok(info.into())
         }
     }
 
+    /**
+    Gets information about a GPU instance profile, including its name (e.g.
+    `MIG 3g.40gb`).
+
+    `profile` is one of the `NVML_GPU_INSTANCE_PROFILE_*` constants, as for
+    `profile_info()`. The `id` of the returned `GpuInstanceProfileInfo` is what
+    the other GPU instance methods take as `profile_id`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` or `profile` is invalid
+    * `NotSupported`, if this `Device` doesn't support MIG or the profile
+    * `NoPermission`, if the user doesn't have permission to perform this
+      operation
+    * `Utf8Error`, if the profile name isn't valid UTF-8
+    * `Unknown`, on any unexpected error
+
+    # Platform Support
+
+    Only supports Linux.
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    #[cfg(target_os = "linux")]
+    #[doc(alias = "nvmlDeviceGetGpuInstanceProfileInfoV")]
+    pub fn gpu_instance_profile_info(
+        &self,
+        profile: u32,
+    ) -> Result<GpuInstanceProfileInfo, NvmlError> {
+        unsafe {
+            let mut info: nvmlGpuInstanceProfileInfo_v2_t = mem::zeroed();
+            info.version = gpu_instance_profile_info_version();
+
+            nvml_try(self.nvml.backend.nvmlDeviceGetGpuInstanceProfileInfoV(
+                self.device,
+                profile,
+                &mut info,
+            )?)?;
+
+            GpuInstanceProfileInfo::try_from(info)
+        }
+    }
+
     /**
      Get GPU instance placements. A placement is a given location of a GPU in a device.
 
@@ -2821,25 +3196,33 @@ impl<'nvml> Device<'nvml> {
         &self,
         profile: u32,
     ) -> Result<Vec<GpuInstancePlacement>, NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlDeviceGetGpuInstancePossiblePlacements_v2
-                .as_ref(),
-        )?;
-
         unsafe {
             let mut count: c_uint = 0;
-            nvml_try(sym(self.device, profile, ptr::null_mut(), &mut count))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetGpuInstancePossiblePlacements_v2(
+                        self.device,
+                        profile,
+                        ptr::null_mut(),
+                        &mut count,
+                    )?,
+            )?;
             let mut placements: Vec<nvmlGpuInstancePlacement_t> =
-                Vec::with_capacity(count as usize);
+                vec![mem::zeroed(); count as usize];
 
-            nvml_try(sym(
-                self.device,
-                profile,
-                placements.as_mut_ptr(),
-                &mut count,
-            ))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetGpuInstancePossiblePlacements_v2(
+                        self.device,
+                        profile,
+                        placements.as_mut_ptr(),
+                        &mut count,
+                    )?,
+            )?;
+            // Never more than the buffer holds, whatever the count says
+            placements.truncate(count as usize);
 
             Ok(placements
                 .into_iter()
@@ -2848,6 +3231,225 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets how many more GPU instances of the profile with ID `profile_id` can
+    be created, given the GPU instances that already exist.
+
+    `profile_id` is the `id` of a `ProfileInfo` from `profile_info()`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` or `profile_id` is invalid
+    * `NotSupported`, if this `Device` doesn't support MIG or MIG mode is
+      disabled
+    * `NoPermission`, if the user doesn't have permission to perform this
+      operation
+    * `Unknown`, on any unexpected error
+
+    # Platform Support
+
+    Only supports Linux.
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    #[cfg(target_os = "linux")]
+    #[doc(alias = "nvmlDeviceGetGpuInstanceRemainingCapacity")]
+    pub fn gpu_instance_remaining_capacity(&self, profile_id: u32) -> Result<u32, NvmlError> {
+        unsafe {
+            let mut count: c_uint = 0;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetGpuInstanceRemainingCapacity(
+                        self.device,
+                        profile_id,
+                        &mut count,
+                    )?,
+            )?;
+
+            Ok(count)
+        }
+    }
+
+    /**
+    Creates a GPU instance of the profile with ID `profile_id`, wherever NVML
+    sees fit to place it.
+
+    `profile_id` is the `id` of a `ProfileInfo` from `profile_info()`. MIG mode
+    must be enabled. The GPU instance outlives the returned `GpuInstance`; see
+    its docs.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` or `profile_id` is invalid
+    * `NotSupported`, if this `Device` doesn't support MIG or MIG mode is
+      disabled
+    * `NoPermission`, if the user doesn't have permission to perform this
+      operation
+    * `InsufficientResources`, if there isn't room for another GPU instance of
+      this profile
+    * `Unknown`, on any unexpected error
+
+    # Platform Support
+
+    Only supports Linux.
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    #[cfg(target_os = "linux")]
+    #[doc(alias = "nvmlDeviceCreateGpuInstance")]
+    pub fn create_gpu_instance(&self, profile_id: u32) -> Result<GpuInstance<'nvml>, NvmlError> {
+        unsafe {
+            let mut instance: nvmlGpuInstance_t = mem::zeroed();
+            nvml_try(self.nvml.backend.nvmlDeviceCreateGpuInstance(
+                self.device,
+                profile_id,
+                &mut instance,
+            )?)?;
+
+            Ok(GpuInstance::new(instance, self))
+        }
+    }
+
+    /**
+    Creates a GPU instance of the profile with ID `profile_id` at `placement`,
+    which must be one of the `possible_placements()` for the profile.
+
+    See `create_gpu_instance()`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device`, `profile_id` or `placement` is invalid
+    * `NotSupported`, if this `Device` doesn't support MIG or MIG mode is
+      disabled
+    * `NoPermission`, if the user doesn't have permission to perform this
+      operation
+    * `InsufficientResources`, if `placement` overlaps an existing GPU
+      instance
+    * `Unknown`, on any unexpected error
+
+    # Platform Support
+
+    Only supports Linux.
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    #[cfg(target_os = "linux")]
+    #[doc(alias = "nvmlDeviceCreateGpuInstanceWithPlacement")]
+    pub fn create_gpu_instance_with_placement(
+        &self,
+        profile_id: u32,
+        placement: &GpuInstancePlacement,
+    ) -> Result<GpuInstance<'nvml>, NvmlError> {
+        unsafe {
+            let placement = placement.clone().into_c();
+            let mut instance: nvmlGpuInstance_t = mem::zeroed();
+            nvml_try(self.nvml.backend.nvmlDeviceCreateGpuInstanceWithPlacement(
+                self.device,
+                profile_id,
+                &placement,
+                &mut instance,
+            )?)?;
+
+            Ok(GpuInstance::new(instance, self))
+        }
+    }
+
+    /**
+    Gets the existing GPU instances of the profile with ID `profile_id`.
+
+    `profile_id` is the `id` of a `ProfileInfo` from `profile_info()`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` or `profile_id` is invalid
+    * `NotSupported`, if this `Device` doesn't support MIG or MIG mode is
+      disabled
+    * `NoPermission`, if the user doesn't have permission to perform this
+      operation
+    * `Unknown`, on any unexpected error
+
+    # Platform Support
+
+    Only supports Linux.
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    #[cfg(target_os = "linux")]
+    #[doc(alias = "nvmlDeviceGetGpuInstances")]
+    pub fn gpu_instances(&self, profile_id: u32) -> Result<Vec<GpuInstance<'nvml>>, NvmlError> {
+        // NVML has no way to ask for the count, but each instance occupies
+        // one of the possible placements
+        let capacity = self.possible_placements(profile_id)?.len();
+
+        unsafe {
+            let mut count: c_uint = capacity as c_uint;
+            let mut instances: Vec<nvmlGpuInstance_t> = vec![mem::zeroed(); capacity];
+
+            nvml_try(self.nvml.backend.nvmlDeviceGetGpuInstances(
+                self.device,
+                profile_id,
+                instances.as_mut_ptr(),
+                &mut count,
+            )?)?;
+            instances.truncate(count as usize);
+
+            Ok(instances
+                .into_iter()
+                .map(|instance| GpuInstance::new(instance, self))
+                .collect())
+        }
+    }
+
+    /**
+    Gets the existing GPU instance with the given ID.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` or `id` is invalid
+    * `NotSupported`, if this `Device` doesn't support MIG or MIG mode is
+      disabled
+    * `NoPermission`, if the user doesn't have permission to perform this
+      operation
+    * `NotFound`, if there is no GPU instance with this ID
+    * `Unknown`, on any unexpected error
+
+    # Platform Support
+
+    Only supports Linux.
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    #[cfg(target_os = "linux")]
+    #[doc(alias = "nvmlDeviceGetGpuInstanceById")]
+    pub fn gpu_instance_by_id(&self, id: u32) -> Result<GpuInstance<'nvml>, NvmlError> {
+        unsafe {
+            let mut instance: nvmlGpuInstance_t = mem::zeroed();
+            nvml_try(self.nvml.backend.nvmlDeviceGetGpuInstanceById(
+                self.device,
+                id,
+                &mut instance,
+            )?)?;
+
+            Ok(GpuInstance::new(instance, self))
+        }
+    }
+
     /**
     Checks if the `Device`supports multi partitioned GPU feature and if enabled.
     Not to confuse with `is_multi_gpu_board`, MIG is a single GPU
@@ -2864,11 +3466,13 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceGetMigMode")]
     pub fn mig_mode(&self) -> Result<MigMode, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetMigMode.as_ref())?;
-
         unsafe {
             let mut mode: MigMode = mem::zeroed();
-            nvml_try(sym(self.device, &mut mode.current, &mut mode.pending))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetMigMode(
+                self.device,
+                &mut mode.current,
+                &mut mode.pending,
+            )?)?;
 
             Ok(mode)
         }
@@ -2887,8 +3491,6 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceSetMigMode")]
     pub fn set_mig_mode(&self, m: bool) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetMigMode.as_ref())?;
-
         unsafe {
             let mode: c_uint = match m {
                 true => NVML_DEVICE_MIG_ENABLE,
@@ -2896,7 +3498,11 @@ impl<'nvml> Device<'nvml> {
             };
             let mut status: c_uint = 0;
 
-            nvml_try(sym(self.device, mode, &mut status))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceSetMigMode(self.device, mode, &mut status)?,
+            )?;
             Ok(status)
         }
     }
@@ -2913,11 +3519,13 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceGetMigDeviceHandleByIndex")]
     pub fn mig_device_by_index(&self, index: u32) -> Result<Device<'nvml>, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetMigDeviceHandleByIndex.as_ref())?;
-
         unsafe {
             let mut parent: nvmlDevice_t = mem::zeroed();
-            nvml_try(sym(self.device, index, &mut parent))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetMigDeviceHandleByIndex(
+                self.device,
+                index,
+                &mut parent,
+            )?)?;
 
             Ok(Device::new(parent, self.nvml))
         }
@@ -2935,16 +3543,13 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceGetDeviceHandleFromMigDeviceHandle")]
     pub fn mig_parent_device(&self) -> Result<Device<'nvml>, NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlDeviceGetDeviceHandleFromMigDeviceHandle
-                .as_ref(),
-        )?;
-
         unsafe {
             let mut parent: nvmlDevice_t = mem::zeroed();
-            nvml_try(sym(self.device, &mut parent))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetDeviceHandleFromMigDeviceHandle(self.device, &mut parent)?,
+            )?;
 
             Ok(Device::new(parent, self.nvml))
         }
@@ -2962,16 +3567,67 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceGetMaxMigDeviceCount")]
     pub fn mig_device_count(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetMaxMigDeviceCount.as_ref())?;
-
         unsafe {
             let mut count: c_uint = 0;
-            nvml_try(sym(self.device, &mut count))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetMaxMigDeviceCount(self.device, &mut count)?,
+            )?;
 
             Ok(count)
         }
     }
 
+    /**
+    Gets the ID of the GPU instance this MIG device belongs to.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` is not a MIG device
+    * `Unknown`, on any unexpected error
+    */
+    #[doc(alias = "nvmlDeviceGetGpuInstanceId")]
+    pub fn mig_gpu_instance_id(&self) -> Result<u32, NvmlError> {
+        unsafe {
+            let mut id: c_uint = 0;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetGpuInstanceId(self.device, &mut id)?,
+            )?;
+
+            Ok(id)
+        }
+    }
+
+    /**
+    Gets the ID of the compute instance this MIG device corresponds to,
+    within its GPU instance.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` is not a MIG device
+    * `Unknown`, on any unexpected error
+    */
+    #[doc(alias = "nvmlDeviceGetComputeInstanceId")]
+    pub fn mig_compute_instance_id(&self) -> Result<u32, NvmlError> {
+        unsafe {
+            let mut id: c_uint = 0;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetComputeInstanceId(self.device, &mut id)?,
+            )?;
+
+            Ok(id)
+        }
+    }
+
     /**
      Determines if the current device is of MIG type
      # Errors
@@ -2983,11 +3639,13 @@ impl<'nvml> Device<'nvml> {
     * `Unknown`, on any unexpected error
     */
     pub fn mig_is_mig_device_handle(&self) -> Result<bool, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceIsMigDeviceHandle.as_ref())?;
-
         unsafe {
             let mut mig_handle: c_uint = 0;
-            nvml_try(sym(self.device, &mut mig_handle))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceIsMigDeviceHandle(self.device, &mut mig_handle)?,
+            )?;
 
             Ok(mig_handle > 0)
         }
@@ -3010,16 +3668,14 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetName")]
     pub fn name(&self) -> Result<String, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetName.as_ref())?;
-
         unsafe {
             let mut name_vec = vec![0; NVML_DEVICE_NAME_V2_BUFFER_SIZE as usize];
 
-            nvml_try(sym(
+            nvml_try(self.nvml.backend.nvmlDeviceGetName(
                 self.device,
                 name_vec.as_mut_ptr(),
                 NVML_DEVICE_NAME_V2_BUFFER_SIZE,
-            ))?;
+            )?)?;
 
             let name_raw = CStr::from_ptr(name_vec.as_ptr());
             Ok(name_raw.to_str()?.into())
@@ -3043,11 +3699,13 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetPciInfo_v3")]
     pub fn pci_info(&self) -> Result<PciInfo, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetPciInfo_v3.as_ref())?;
-
         unsafe {
             let mut pci_info: nvmlPciInfo_t = mem::zeroed();
-            nvml_try(sym(self.device, &mut pci_info))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetPciInfo_v3(self.device, &mut pci_info)?,
+            )?;
 
             PciInfo::try_from(pci_info, true)
         }
@@ -3072,11 +3730,13 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetPcieReplayCounter")]
     pub fn pcie_replay_counter(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetPcieReplayCounter.as_ref())?;
-
         unsafe {
             let mut value: c_uint = mem::zeroed();
-            nvml_try(sym(self.device, &mut value))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetPcieReplayCounter(self.device, &mut value)?,
+            )?;
 
             Ok(value)
         }
@@ -3108,12 +3768,14 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetPcieThroughput")]
     pub fn pcie_throughput(&self, counter: PcieUtilCounter) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetPcieThroughput.as_ref())?;
-
         unsafe {
             let mut throughput: c_uint = mem::zeroed();
 
-            nvml_try(sym(self.device, counter.as_c(), &mut throughput))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetPcieThroughput(
+                self.device,
+                counter.as_c(),
+                &mut throughput,
+            )?)?;
 
             Ok(throughput)
         }
@@ -3139,11 +3801,13 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetPerformanceState")]
     pub fn performance_state(&self) -> Result<PerformanceState, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetPerformanceState.as_ref())?;
-
         unsafe {
             let mut state: nvmlPstates_t = mem::zeroed();
-            nvml_try(sym(self.device, &mut state))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetPerformanceState(self.device, &mut state)?,
+            )?;
 
             PerformanceState::try_from(state)
         }
@@ -3173,11 +3837,13 @@ impl<'nvml> Device<'nvml> {
     #[cfg(target_os = "linux")]
     #[doc(alias = "nvmlDeviceGetPersistenceMode")]
     pub fn is_in_persistent_mode(&self) -> Result<bool, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetPersistenceMode.as_ref())?;
-
         unsafe {
             let mut state: nvmlEnableState_t = mem::zeroed();
-            nvml_try(sym(self.device, &mut state))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetPersistenceMode(self.device, &mut state)?,
+            )?;
 
             bool_from_state(state)
         }
@@ -3204,16 +3870,13 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetPowerManagementDefaultLimit")]
     pub fn power_management_limit_default(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlDeviceGetPowerManagementDefaultLimit
-                .as_ref(),
-        )?;
-
         unsafe {
             let mut limit: c_uint = mem::zeroed();
-            nvml_try(sym(self.device, &mut limit))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetPowerManagementDefaultLimit(self.device, &mut limit)?,
+            )?;
 
             Ok(limit)
         }
@@ -3245,11 +3908,13 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetPowerManagementLimit")]
     pub fn power_management_limit(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetPowerManagementLimit.as_ref())?;
-
         unsafe {
             let mut limit: c_uint = mem::zeroed();
-            nvml_try(sym(self.device, &mut limit))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetPowerManagementLimit(self.device, &mut limit)?,
+            )?;
 
             Ok(limit)
         }
@@ -3276,18 +3941,19 @@ impl<'nvml> Device<'nvml> {
     pub fn power_management_limit_constraints(
         &self,
     ) -> Result<PowerManagementConstraints, NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlDeviceGetPowerManagementLimitConstraints
-                .as_ref(),
-        )?;
-
         unsafe {
             let mut min_limit: c_uint = mem::zeroed();
             let mut max_limit: c_uint = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut min_limit, &mut max_limit))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetPowerManagementLimitConstraints(
+                        self.device,
+                        &mut min_limit,
+                        &mut max_limit,
+                    )?,
+            )?;
 
             Ok(PowerManagementConstraints {
                 min_limit,
@@ -3316,11 +3982,13 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceGetPowerMizerMode_v1")]
     pub fn power_mizer_mode(&self) -> Result<PowerMizerModeInfo, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetPowerMizerMode_v1.as_ref())?;
-
         unsafe {
             let mut power_mizer_mode: nvmlDevicePowerMizerModes_v1_t = mem::zeroed();
-            nvml_try(sym(self.device, &mut power_mizer_mode))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetPowerMizerMode_v1(self.device, &mut power_mizer_mode)?,
+            )?;
 
             Ok(PowerMizerModeInfo {
                 current: PowerMizerMode::try_from(power_mizer_mode.currentMode)?,
@@ -3337,11 +4005,13 @@ impl<'nvml> Device<'nvml> {
     #[deprecated(note = "NVIDIA states that \"this API has been deprecated.\"")]
     #[doc(alias = "nvmlDeviceGetPowerManagementMode")]
     pub fn is_power_management_algo_active(&self) -> Result<bool, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetPowerManagementMode.as_ref())?;
-
         unsafe {
             let mut state: nvmlEnableState_t = mem::zeroed();
-            nvml_try(sym(self.device, &mut state))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetPowerManagementMode(self.device, &mut state)?,
+            )?;
 
             bool_from_state(state)
         }
@@ -3353,11 +4023,13 @@ impl<'nvml> Device<'nvml> {
     #[deprecated(note = "use `.performance_state()`.")]
     #[doc(alias = "nvmlDeviceGetPowerState")]
     pub fn power_state(&self) -> Result<PerformanceState, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetPowerState.as_ref())?;
-
         unsafe {
             let mut state: nvmlPstates_t = mem::zeroed();
-            nvml_try(sym(self.device, &mut state))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetPowerState(self.device, &mut state)?,
+            )?;
 
             PerformanceState::try_from(state)
         }
@@ -3386,11 +4058,13 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetPowerUsage")]
     pub fn power_usage(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetPowerUsage.as_ref())?;
-
         unsafe {
             let mut usage: c_uint = mem::zeroed();
-            nvml_try(sym(self.device, &mut usage))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetPowerUsage(self.device, &mut usage)?,
+            )?;
 
             Ok(usage)
         }
@@ -3414,11 +4088,13 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceGetTotalEnergyConsumption")]
     pub fn total_energy_consumption(&self) -> Result<u64, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetTotalEnergyConsumption.as_ref())?;
-
         unsafe {
             let mut total: c_ulonglong = mem::zeroed();
-            nvml_try(sym(self.device, &mut total))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetTotalEnergyConsumption(self.device, &mut total)?,
+            )?;
 
             Ok(total)
         }
@@ -3448,8 +4124,6 @@ impl<'nvml> Device<'nvml> {
     // Tested on machines other than my own
     #[doc(alias = "nvmlDeviceGetRetiredPages_v2")]
     pub fn retired_pages(&self, cause: RetirementCause) -> Result<Vec<RetiredPage>, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetRetiredPages_v2.as_ref())?;
-
         unsafe {
             let mut count = match self.retired_pages_count(&cause)? {
                 0 => return Ok(vec![]),
@@ -3458,13 +4132,13 @@ impl<'nvml> Device<'nvml> {
             let mut addresses: Vec<c_ulonglong> = vec![mem::zeroed(); count as usize];
             let mut timestamps: Vec<c_ulonglong> = vec![mem::zeroed(); count as usize];
 
-            nvml_try(sym(
+            nvml_try(self.nvml.backend.nvmlDeviceGetRetiredPages_v2(
                 self.device,
                 cause.as_c(),
                 &mut count,
                 addresses.as_mut_ptr(),
                 timestamps.as_mut_ptr(),
-            ))?;
+            )?)?;
 
             Ok(addresses
                 .into_iter()
@@ -3476,19 +4150,17 @@ impl<'nvml> Device<'nvml> {
 
     // Helper for the above function. Returns # of samples that can be queried.
     fn retired_pages_count(&self, cause: &RetirementCause) -> Result<c_uint, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetRetiredPages.as_ref())?;
-
         unsafe {
             let mut count: c_uint = 0;
 
-            nvml_try_count(sym(
+            nvml_try_count(self.nvml.backend.nvmlDeviceGetRetiredPages(
                 self.device,
                 cause.as_c(),
                 &mut count,
                 // All NVIDIA says is that this
                 // can't be null.
                 &mut mem::zeroed(),
-            ))?;
+            )?)?;
 
             Ok(count)
         }
@@ -3514,22 +4186,118 @@ impl<'nvml> Device<'nvml> {
     // Tested on machines other than my own
     #[doc(alias = "nvmlDeviceGetRetiredPagesPendingStatus")]
     pub fn are_pages_pending_retired(&self) -> Result<bool, NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlDeviceGetRetiredPagesPendingStatus
-                .as_ref(),
-        )?;
-
         unsafe {
             let mut state: nvmlEnableState_t = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut state))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetRetiredPagesPendingStatus(self.device, &mut state)?,
+            )?;
 
             bool_from_state(state)
         }
     }
 
+    /**
+    Gets the number of rows remapped due to memory errors on this `Device`, and
+    whether a remapping is pending or has ever failed.
+
+    Row remapping replaces page retirement on Ampere and newer devices. A pending
+    remapping takes effect when the GPU is reset; a failed remapping means the
+    GPU should be replaced.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceGetRemappedRows")]
+    pub fn remapped_rows(&self) -> Result<RemappedRows, NvmlError> {
+        unsafe {
+            let mut correctable: c_uint = 0;
+            let mut uncorrectable: c_uint = 0;
+            let mut is_pending: c_uint = 0;
+            let mut failure_occurred: c_uint = 0;
+
+            nvml_try(self.nvml.backend.nvmlDeviceGetRemappedRows(
+                self.device,
+                &mut correctable,
+                &mut uncorrectable,
+                &mut is_pending,
+                &mut failure_occurred,
+            )?)?;
+
+            Ok(RemappedRows {
+                correctable,
+                uncorrectable,
+                is_pending: is_pending != 0,
+                failure_occurred: failure_occurred != 0,
+            })
+        }
+    }
+
+    /**
+    Gets how many of this `Device`'s memory banks have each amount of spare rows
+    left for remapping.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceGetRowRemapperHistogram")]
+    pub fn row_remapper_histogram(&self) -> Result<RowRemapperHistogram, NvmlError> {
+        unsafe {
+            let mut histogram: nvmlRowRemapperHistogramValues_t = mem::zeroed();
+
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetRowRemapperHistogram(self.device, &mut histogram)?,
+            )?;
+
+            Ok(histogram.into())
+        }
+    }
+
+    /**
+    Assesses the health of this `Device`'s memory from its row remapping, page
+    retirement and ECC error state.
+
+    Queries this `Device` doesn't support are left out of the assessment; see
+    `high_level::memory_health` for how the verdict is reached.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    pub fn memory_health(&self) -> Result<MemoryHealth, NvmlError> {
+        memory_health::assess(self)
+    }
+
     /**
     Gets recent samples for this `Device`.
 
@@ -3596,8 +4364,6 @@ impl<'nvml> Device<'nvml> {
         T: Into<Option<u64>>,
     {
         let timestamp = last_seen_timestamp.into().unwrap_or(0);
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetSamples.as_ref())?;
-
         unsafe {
             let mut val_type: nvmlValueType_t = mem::zeroed();
             let count = match self.samples_count(&sample_type, timestamp)? {
@@ -3607,14 +4373,14 @@ impl<'nvml> Device<'nvml> {
             let mut samples: Vec<nvmlSample_t> = vec![mem::zeroed(); count as usize];
             let mut new_count = count;
 
-            nvml_try(sym(
+            nvml_try(self.nvml.backend.nvmlDeviceGetSamples(
                 self.device,
                 sample_type.as_c(),
                 timestamp,
                 &mut val_type,
                 &mut new_count,
                 samples.as_mut_ptr(),
-            ))?;
+            )?)?;
 
             let val_type_rust = SampleValueType::try_from(val_type)?;
             Ok(samples
@@ -3627,13 +4393,11 @@ impl<'nvml> Device<'nvml> {
 
     // Helper for the above function. Returns # of samples that can be queried.
     fn samples_count(&self, sample_type: &Sampling, timestamp: u64) -> Result<c_uint, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetSamples.as_ref())?;
-
         unsafe {
             let mut val_type: nvmlValueType_t = mem::zeroed();
             let mut count: c_uint = mem::zeroed();
 
-            nvml_try_count(sym(
+            nvml_try_count(self.nvml.backend.nvmlDeviceGetSamples(
                 self.device,
                 sample_type.as_c(),
                 timestamp,
@@ -3641,7 +4405,7 @@ impl<'nvml> Device<'nvml> {
                 &mut count,
                 // Indicates that we want the count
                 ptr::null_mut(),
-            ))?;
+            )?)?;
 
             Ok(count)
         }
@@ -3680,8 +4444,6 @@ impl<'nvml> Device<'nvml> {
         &self,
         id_slice: &[FieldId],
     ) -> Result<Vec<Result<FieldValueSample, NvmlError>>, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetFieldValues.as_ref())?;
-
         unsafe {
             let values_count = id_slice.len();
             let mut field_values: Vec<nvmlFieldValue_t> = Vec::with_capacity(values_count);
@@ -3693,88 +4455,237 @@ impl<'nvml> Device<'nvml> {
                 field_values.push(raw);
             }
 
-            nvml_try(sym(
+            nvml_try(self.nvml.backend.nvmlDeviceGetFieldValues(
                 self.device,
                 values_count as i32,
                 field_values.as_mut_ptr(),
-            ))?;
+            )?)?;
+
+            Ok(field_values
+                .into_iter()
+                .map(FieldValueSample::try_from)
+                .collect())
+        }
+    }
+
+    /**
+    Gets the globally unique board serial number associated with this `Device`'s board
+    as an alphanumeric string.
+
+    This serial number matches the serial number tag that is physically attached to the board.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Utf8Error`, if the string obtained from the C function is not valid Utf8
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports all products with an infoROM.
+    */
+    // Checked against local
+    // Tested on machines other than my own
+    #[doc(alias = "nvmlDeviceGetSerial")]
+    pub fn serial(&self) -> Result<String, NvmlError> {
+        unsafe {
+            let mut serial_vec = vec![0; NVML_DEVICE_SERIAL_BUFFER_SIZE as usize];
+
+            nvml_try(self.nvml.backend.nvmlDeviceGetSerial(
+                self.device,
+                serial_vec.as_mut_ptr(),
+                NVML_DEVICE_SERIAL_BUFFER_SIZE,
+            )?)?;
+
+            let serial_raw = CStr::from_ptr(serial_vec.as_ptr());
+            Ok(serial_raw.to_str()?.into())
+        }
+    }
+
+    /**
+    Gets the board part number for this `Device`.
+
+    The board part number is programmed into the board's infoROM.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `NotSupported`, if the necessary VBIOS fields have not been filled
+    * `GpuLost`, if the target GPU has fellen off the bus or is otherwise inaccessible
+    * `Utf8Error`, if the string obtained from the C function is not valid Utf8
+    * `Unknown`, on any unexpected error
+    */
+    // Checked against local
+    // Tested on machines other than my own
+    #[doc(alias = "nvmlDeviceGetBoardPartNumber")]
+    pub fn board_part_number(&self) -> Result<String, NvmlError> {
+        unsafe {
+            let mut part_num_vec = vec![0; NVML_DEVICE_PART_NUMBER_BUFFER_SIZE as usize];
+
+            nvml_try(self.nvml.backend.nvmlDeviceGetBoardPartNumber(
+                self.device,
+                part_num_vec.as_mut_ptr(),
+                NVML_DEVICE_PART_NUMBER_BUFFER_SIZE,
+            )?)?;
+
+            let part_num_raw = CStr::from_ptr(part_num_vec.as_ptr());
+            Ok(part_num_raw.to_str()?.into())
+        }
+    }
+
+    /**
+    Gets the reasons this `Device`'s clocks are currently being held below
+    their maximum.
+
+    Note that multiple reasons can be affecting clocks at once.
+
+    The returned bitmask is created via the `ClocksEventReasons::from_bits_truncate`
+    method, meaning that any bits that don't correspond to flags present in this
+    version of the wrapper will be dropped.
+
+    Drivers that predate this call are asked for their throttle reasons instead.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports all _fully supported_ devices.
+    */
+    #[doc(alias = "nvmlDeviceGetCurrentClocksEventReasons")]
+    pub fn current_clocks_event_reasons(&self) -> Result<ClocksEventReasons, NvmlError> {
+        Ok(ClocksEventReasons::from_bits_truncate(
+            self.current_clocks_event_reasons_raw()?,
+        ))
+    }
+
+    /**
+    Gets the reasons this `Device`'s clocks are currently being held below
+    their maximum, erroring if any bits correspond to non-present flags.
+
+    Note that multiple reasons can be affecting clocks at once.
+
+    Drivers that predate this call are asked for their throttle reasons instead.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `IncorrectBits`, if NVML returns any bits that do not correspond to flags in
+      `ClocksEventReasons`
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports all _fully supported_ devices.
+    */
+    pub fn current_clocks_event_reasons_strict(&self) -> Result<ClocksEventReasons, NvmlError> {
+        let reasons = self.current_clocks_event_reasons_raw()?;
+
+        ClocksEventReasons::from_bits(reasons).ok_or(NvmlError::IncorrectBits(Bits::U64(reasons)))
+    }
+
+    // Helper for the above methods.
+    fn current_clocks_event_reasons_raw(&self) -> Result<c_ulonglong, NvmlError> {
+        unsafe {
+            let mut reasons: c_ulonglong = mem::zeroed();
 
-            Ok(field_values
-                .into_iter()
-                .map(FieldValueSample::try_from)
-                .collect())
+            match self
+                .nvml
+                .backend
+                .nvmlDeviceGetCurrentClocksEventReasons(self.device, &mut reasons)
+            {
+                Err(NvmlError::FailedToLoadSymbol(_)) => self.current_throttle_reasons_raw(),
+                result => nvml_try(result?).map(|_| reasons),
+            }
         }
     }
 
     /**
-    Gets the globally unique board serial number associated with this `Device`'s board
-    as an alphanumeric string.
+    Gets a bitmask of the clocks event reasons this `Device` supports.
 
-    This serial number matches the serial number tag that is physically attached to the board.
+    These reasons can be returned by `.current_clocks_event_reasons()`.
+
+    The returned bitmask is created via the `ClocksEventReasons::from_bits_truncate`
+    method, meaning that any bits that don't correspond to flags present in this
+    version of the wrapper will be dropped.
+
+    Drivers that predate this call are asked for their supported throttle
+    reasons instead.
 
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
-    * `InvalidArg`, if this `Device` is invalid
-    * `NotSupported`, if this `Device` doesn't support this feature
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
-    * `Utf8Error`, if the string obtained from the C function is not valid Utf8
     * `Unknown`, on any unexpected error
 
     # Device Support
 
-    Supports all products with an infoROM.
-    */
-    // Checked against local
-    // Tested on machines other than my own
-    #[doc(alias = "nvmlDeviceGetSerial")]
-    pub fn serial(&self) -> Result<String, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetSerial.as_ref())?;
-
-        unsafe {
-            let mut serial_vec = vec![0; NVML_DEVICE_SERIAL_BUFFER_SIZE as usize];
+    Supports all _fully supported_ devices.
 
-            nvml_try(sym(
-                self.device,
-                serial_vec.as_mut_ptr(),
-                NVML_DEVICE_SERIAL_BUFFER_SIZE,
-            ))?;
+    # Environment Support
 
-            let serial_raw = CStr::from_ptr(serial_vec.as_ptr());
-            Ok(serial_raw.to_str()?.into())
-        }
+    This method is not supported on virtual machines running vGPUs.
+    */
+    #[doc(alias = "nvmlDeviceGetSupportedClocksEventReasons")]
+    pub fn supported_clocks_event_reasons(&self) -> Result<ClocksEventReasons, NvmlError> {
+        Ok(ClocksEventReasons::from_bits_truncate(
+            self.supported_clocks_event_reasons_raw()?,
+        ))
     }
 
     /**
-    Gets the board part number for this `Device`.
+    Gets a bitmask of the clocks event reasons this `Device` supports, erroring
+    if any bits correspond to non-present flags.
 
-    The board part number is programmed into the board's infoROM.
+    These reasons can be returned by `.current_clocks_event_reasons()`.
+
+    Drivers that predate this call are asked for their supported throttle
+    reasons instead.
 
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
-    * `NotSupported`, if the necessary VBIOS fields have not been filled
-    * `GpuLost`, if the target GPU has fellen off the bus or is otherwise inaccessible
-    * `Utf8Error`, if the string obtained from the C function is not valid Utf8
+    * `IncorrectBits`, if NVML returns any bits that do not correspond to flags in
+      `ClocksEventReasons`
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
     * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports all _fully supported_ devices.
+
+    # Environment Support
+
+    This method is not supported on virtual machines running vGPUs.
     */
-    // Checked against local
-    // Tested on machines other than my own
-    #[doc(alias = "nvmlDeviceGetBoardPartNumber")]
-    pub fn board_part_number(&self) -> Result<String, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetBoardPartNumber.as_ref())?;
+    pub fn supported_clocks_event_reasons_strict(&self) -> Result<ClocksEventReasons, NvmlError> {
+        let reasons = self.supported_clocks_event_reasons_raw()?;
 
-        unsafe {
-            let mut part_num_vec = vec![0; NVML_DEVICE_PART_NUMBER_BUFFER_SIZE as usize];
+        ClocksEventReasons::from_bits(reasons).ok_or(NvmlError::IncorrectBits(Bits::U64(reasons)))
+    }
 
-            nvml_try(sym(
-                self.device,
-                part_num_vec.as_mut_ptr(),
-                NVML_DEVICE_PART_NUMBER_BUFFER_SIZE,
-            ))?;
+    // Helper for the above methods.
+    fn supported_clocks_event_reasons_raw(&self) -> Result<c_ulonglong, NvmlError> {
+        unsafe {
+            let mut reasons: c_ulonglong = mem::zeroed();
 
-            let part_num_raw = CStr::from_ptr(part_num_vec.as_ptr());
-            Ok(part_num_raw.to_str()?.into())
+            match self
+                .nvml
+                .backend
+                .nvmlDeviceGetSupportedClocksEventReasons(self.device, &mut reasons)
+            {
+                Err(NvmlError::FailedToLoadSymbol(_)) => self.supported_throttle_reasons_raw(),
+                result => nvml_try(result?).map(|_| reasons),
+            }
         }
     }
 
@@ -3801,6 +4712,7 @@ impl<'nvml> Device<'nvml> {
     // Checked against local.
     // Tested
     #[doc(alias = "nvmlDeviceGetCurrentClocksThrottleReasons")]
+    #[deprecated(note = "use `.current_clocks_event_reasons()`.")]
     pub fn current_throttle_reasons(&self) -> Result<ThrottleReasons, NvmlError> {
         Ok(ThrottleReasons::from_bits_truncate(
             self.current_throttle_reasons_raw()?,
@@ -3828,6 +4740,7 @@ impl<'nvml> Device<'nvml> {
     */
     // Checked against local.
     // Tested
+    #[deprecated(note = "use `.current_clocks_event_reasons_strict()`.")]
     pub fn current_throttle_reasons_strict(&self) -> Result<ThrottleReasons, NvmlError> {
         let reasons = self.current_throttle_reasons_raw()?;
 
@@ -3836,17 +4749,14 @@ impl<'nvml> Device<'nvml> {
 
     // Helper for the above methods.
     fn current_throttle_reasons_raw(&self) -> Result<c_ulonglong, NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlDeviceGetCurrentClocksThrottleReasons
-                .as_ref(),
-        )?;
-
         unsafe {
             let mut reasons: c_ulonglong = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut reasons))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetCurrentClocksThrottleReasons(self.device, &mut reasons)?,
+            )?;
 
             Ok(reasons)
         }
@@ -3878,6 +4788,7 @@ impl<'nvml> Device<'nvml> {
     // Checked against local
     // Tested
     #[doc(alias = "nvmlDeviceGetSupportedClocksThrottleReasons")]
+    #[deprecated(note = "use `.supported_clocks_event_reasons()`.")]
     pub fn supported_throttle_reasons(&self) -> Result<ThrottleReasons, NvmlError> {
         Ok(ThrottleReasons::from_bits_truncate(
             self.supported_throttle_reasons_raw()?,
@@ -3908,6 +4819,7 @@ impl<'nvml> Device<'nvml> {
     */
     // Checked against local
     // Tested
+    #[deprecated(note = "use `.supported_clocks_event_reasons_strict()`.")]
     pub fn supported_throttle_reasons_strict(&self) -> Result<ThrottleReasons, NvmlError> {
         let reasons = self.supported_throttle_reasons_raw()?;
 
@@ -3916,16 +4828,14 @@ impl<'nvml> Device<'nvml> {
 
     // Helper for the above methods.
     fn supported_throttle_reasons_raw(&self) -> Result<c_ulonglong, NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlDeviceGetSupportedClocksThrottleReasons
-                .as_ref(),
-        )?;
         unsafe {
             let mut reasons: c_ulonglong = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut reasons))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetSupportedClocksThrottleReasons(self.device, &mut reasons)?,
+            )?;
 
             Ok(reasons)
         }
@@ -3971,15 +4881,13 @@ impl<'nvml> Device<'nvml> {
         let mut items: Vec<c_uint> = vec![0; size];
         let mut count = size as c_uint;
 
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetSupportedGraphicsClocks.as_ref())?;
-
         unsafe {
-            nvml_try_count(sym(
+            nvml_try_count(self.nvml.backend.nvmlDeviceGetSupportedGraphicsClocks(
                 self.device,
                 for_mem_clock,
                 &mut count,
                 items.as_mut_ptr(),
-            ))?;
+            )?)?;
         }
 
         items.truncate(count as usize);
@@ -4020,10 +4928,13 @@ impl<'nvml> Device<'nvml> {
         let mut items: Vec<c_uint> = vec![0; size];
         let mut count = size as c_uint;
 
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetSupportedMemoryClocks.as_ref())?;
         // TODO: should this fn call `sym` twice, first to populate `count` and second to fill the vec?
         unsafe {
-            match sym(self.device, &mut count, items.as_mut_ptr()) {
+            match self.nvml.backend.nvmlDeviceGetSupportedMemoryClocks(
+                self.device,
+                &mut count,
+                items.as_mut_ptr(),
+            )? {
                 // `count` is now the size that is required. Return it in the error.
                 nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE => {
                     return Err(NvmlError::InsufficientSize(Some(count as usize)))
@@ -4051,17 +4962,110 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetTemperature")]
     pub fn temperature(&self, sensor: TemperatureSensor) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetTemperature.as_ref())?;
-
         unsafe {
             let mut temp: c_uint = mem::zeroed();
 
-            nvml_try(sym(self.device, sensor.as_c(), &mut temp))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetTemperature(
+                self.device,
+                sensor.as_c(),
+                &mut temp,
+            )?)?;
 
             Ok(temp)
         }
     }
 
+    /**
+    Gets the current temperature reading for the given sensor, in °C.
+
+    Unlike `.temperature()`, the reading is signed, so temperatures below 0°C
+    are reported as such.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid or `sensor` is invalid (shouldn't occur?)
+    * `NotSupported`, if this `Device` does not have the specified sensor
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `ArgumentVersionMismatch`, if the installed driver doesn't support this call
+    * `Unknown`, on any unexpected error
+    */
+    #[doc(alias = "nvmlDeviceGetTemperatureV")]
+    pub fn temperature_v(&self, sensor: TemperatureSensor) -> Result<i32, NvmlError> {
+        unsafe {
+            let mut temperature: nvmlTemperature_t = mem::zeroed();
+            temperature.version = temperature_version();
+            temperature.sensorType = sensor.as_c();
+
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetTemperatureV(self.device, &mut temperature)?,
+            )?;
+
+            Ok(temperature.temperature)
+        }
+    }
+
+    /**
+    Gets how far this `Device`'s temperature is below the point where it starts
+    slowing down to cool off, in °C.
+
+    The margin is negative once the `Device` is past that point.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `ArgumentVersionMismatch`, if the installed driver doesn't support this call
+    * `Unknown`, on any unexpected error
+    */
+    #[doc(alias = "nvmlDeviceGetMarginTemperature")]
+    pub fn margin_temperature(&self) -> Result<i32, NvmlError> {
+        unsafe {
+            let mut margin: nvmlMarginTemperature_t = mem::zeroed();
+            margin.version = margin_temperature_version();
+
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetMarginTemperature(self.device, &mut margin)?,
+            )?;
+
+            Ok(margin.marginTemperature)
+        }
+    }
+
+    /**
+    Gets the readings and default ranges of all of this `Device`'s thermal
+    sensors, e.g. for its GPU core, memory and board.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+    */
+    #[doc(alias = "nvmlDeviceGetThermalSettings")]
+    pub fn thermal_settings(&self) -> Result<ThermalSettings, NvmlError> {
+        unsafe {
+            let mut settings: nvmlGpuThermalSettings_t = mem::zeroed();
+
+            nvml_try(self.nvml.backend.nvmlDeviceGetThermalSettings(
+                self.device,
+                ThermalTarget::All.as_c() as c_uint,
+                &mut settings,
+            )?)?;
+
+            ThermalSettings::try_from(settings)
+        }
+    }
+
     /**
     Gets the temperature threshold for this `Device` and the specified `threshold_type`, in °C.
 
@@ -4084,12 +5088,14 @@ impl<'nvml> Device<'nvml> {
         &self,
         threshold_type: TemperatureThreshold,
     ) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetTemperatureThreshold.as_ref())?;
-
         unsafe {
             let mut temp: c_uint = mem::zeroed();
 
-            nvml_try(sym(self.device, threshold_type.as_c(), &mut temp))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetTemperatureThreshold(
+                self.device,
+                threshold_type.as_c(),
+                &mut temp,
+            )?)?;
 
             Ok(temp)
         }
@@ -4119,11 +5125,13 @@ impl<'nvml> Device<'nvml> {
         threshold_type: TemperatureThreshold,
         temp: i32,
     ) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetTemperatureThreshold.as_ref())?;
-
         unsafe {
             let mut t = temp;
-            nvml_try(sym(self.device, threshold_type.as_c(), &mut t))
+            nvml_try(self.nvml.backend.nvmlDeviceSetTemperatureThreshold(
+                self.device,
+                threshold_type.as_c(),
+                &mut t,
+            )?)
         }
     }
 
@@ -4149,12 +5157,14 @@ impl<'nvml> Device<'nvml> {
         &self,
         other_device: Device,
     ) -> Result<TopologyLevel, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetTopologyCommonAncestor.as_ref())?;
-
         unsafe {
             let mut level: nvmlGpuTopologyLevel_t = mem::zeroed();
 
-            nvml_try(sym(self.device, other_device.device, &mut level))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetTopologyCommonAncestor(
+                self.device,
+                other_device.device,
+                &mut level,
+            )?)?;
 
             TopologyLevel::try_from(level)
         }
@@ -4181,8 +5191,6 @@ impl<'nvml> Device<'nvml> {
         &self,
         level: TopologyLevel,
     ) -> Result<Vec<Device<'nvml>>, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetTopologyNearestGpus.as_ref())?;
-
         unsafe {
             let mut count = match self.top_nearest_gpus_count(&level)? {
                 0 => return Ok(vec![]),
@@ -4190,12 +5198,12 @@ impl<'nvml> Device<'nvml> {
             };
             let mut gpus: Vec<nvmlDevice_t> = vec![mem::zeroed(); count as usize];
 
-            nvml_try(sym(
+            nvml_try(self.nvml.backend.nvmlDeviceGetTopologyNearestGpus(
                 self.device,
                 level.as_c(),
                 &mut count,
                 gpus.as_mut_ptr(),
-            ))?;
+            )?)?;
 
             Ok(gpus
                 .into_iter()
@@ -4207,12 +5215,10 @@ impl<'nvml> Device<'nvml> {
     // Helper for the above function. Returns # of GPUs in the set.
     #[cfg(target_os = "linux")]
     fn top_nearest_gpus_count(&self, level: &TopologyLevel) -> Result<c_uint, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetTopologyNearestGpus.as_ref())?;
-
         unsafe {
             let mut count: c_uint = 0;
 
-            nvml_try_count(sym(
+            nvml_try_count(self.nvml.backend.nvmlDeviceGetTopologyNearestGpus(
                 self.device,
                 level.as_c(),
                 &mut count,
@@ -4220,7 +5226,7 @@ impl<'nvml> Device<'nvml> {
                 // indicates that we want the
                 // GPU count
                 ptr::null_mut(),
-            ))?;
+            )?)?;
 
             Ok(count)
         }
@@ -4253,17 +5259,15 @@ impl<'nvml> Device<'nvml> {
         error_type: MemoryError,
         counter_type: EccCounter,
     ) -> Result<u64, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetTotalEccErrors.as_ref())?;
-
         unsafe {
             let mut count: c_ulonglong = mem::zeroed();
 
-            nvml_try(sym(
+            nvml_try(self.nvml.backend.nvmlDeviceGetTotalEccErrors(
                 self.device,
                 error_type.as_c(),
                 counter_type.as_c(),
                 &mut count,
-            ))?;
+            )?)?;
 
             Ok(count)
         }
@@ -4309,16 +5313,14 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetUUID")]
     pub fn uuid(&self) -> Result<String, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetUUID.as_ref())?;
-
         unsafe {
             let mut uuid_vec = vec![0; NVML_DEVICE_UUID_V2_BUFFER_SIZE as usize];
 
-            nvml_try(sym(
+            nvml_try(self.nvml.backend.nvmlDeviceGetUUID(
                 self.device,
                 uuid_vec.as_mut_ptr(),
                 NVML_DEVICE_UUID_V2_BUFFER_SIZE,
-            ))?;
+            )?)?;
 
             let uuid_raw = CStr::from_ptr(uuid_vec.as_ptr());
             Ok(uuid_raw.to_str()?.into())
@@ -4348,16 +5350,52 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetUtilizationRates")]
     pub fn utilization_rates(&self) -> Result<Utilization, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetUtilizationRates.as_ref())?;
-
         unsafe {
             let mut utilization: nvmlUtilization_t = mem::zeroed();
-            nvml_try(sym(self.device, &mut utilization))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetUtilizationRates(self.device, &mut utilization)?,
+            )?;
 
             Ok(utilization.into())
         }
     }
 
+    /**
+    Gets the utilization of the SM, memory and every media engine on this `Device`
+    in one call.
+
+    SM and memory utilization come from `.utilization_rates()`, which does not
+    report its sampling period. Each media engine reports its own; an engine
+    this `Device` lacks (or that the loaded driver cannot query) is `None`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support `.utilization_rates()`
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Fermi or newer fully supported devices.
+    */
+    // Tested
+    pub fn engine_utilization(&self) -> Result<EngineUtilization, NvmlError> {
+        let rates = self.utilization_rates()?;
+
+        Ok(EngineUtilization {
+            gpu: rates.gpu,
+            memory: rates.memory,
+            encoder: optional(self.encoder_utilization())?,
+            decoder: optional(self.decoder_utilization())?,
+            jpeg: optional(self.jpeg_utilization())?,
+            ofa: optional(self.ofa_utilization())?,
+        })
+    }
+
     /**
     Gets the VBIOS version of this `Device`.
 
@@ -4376,16 +5414,14 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetVbiosVersion")]
     pub fn vbios_version(&self) -> Result<String, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetVbiosVersion.as_ref())?;
-
         unsafe {
             let mut version_vec = vec![0; NVML_DEVICE_VBIOS_VERSION_BUFFER_SIZE as usize];
 
-            nvml_try(sym(
+            nvml_try(self.nvml.backend.nvmlDeviceGetVbiosVersion(
                 self.device,
                 version_vec.as_mut_ptr(),
                 NVML_DEVICE_VBIOS_VERSION_BUFFER_SIZE,
-            ))?;
+            )?)?;
 
             let version_raw = CStr::from_ptr(version_vec.as_ptr());
             Ok(version_raw.to_str()?.into())
@@ -4420,11 +5456,14 @@ impl<'nvml> Device<'nvml> {
         &self,
         perf_policy: PerformancePolicy,
     ) -> Result<ViolationTime, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetViolationStatus.as_ref())?;
         unsafe {
             let mut viol_time: nvmlViolationTime_t = mem::zeroed();
 
-            nvml_try(sym(self.device, perf_policy.as_c(), &mut viol_time))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetViolationStatus(
+                self.device,
+                perf_policy.as_c(),
+                &mut viol_time,
+            )?)?;
 
             Ok(viol_time.into())
         }
@@ -4441,12 +5480,14 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceGetIrqNum")]
     pub fn irq_num(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetIrqNum.as_ref())?;
-
         let irq_num = unsafe {
             let mut irq_num: c_uint = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut irq_num))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetIrqNum(self.device, &mut irq_num)?,
+            )?;
 
             irq_num
         };
@@ -4468,12 +5509,14 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceGetNumGpuCores")]
     pub fn num_cores(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetNumGpuCores.as_ref())?;
-
         unsafe {
             let mut count: c_uint = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut count))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetNumGpuCores(self.device, &mut count)?,
+            )?;
 
             Ok(count)
         }
@@ -4494,13 +5537,16 @@ impl<'nvml> Device<'nvml> {
         device2: &Device,
         p2p_index: P2pCapabilitiesIndex,
     ) -> Result<P2pStatus, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetP2PStatus.as_ref())?;
-
         let status_c = unsafe {
             let mut status: nvmlGpuP2PStatus_t = mem::zeroed();
             let device2 = device2.device;
 
-            nvml_try(sym(self.device, device2, p2p_index as u32, &mut status))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetP2PStatus(
+                self.device,
+                device2,
+                p2p_index as u32,
+                &mut status,
+            )?)?;
 
             status
         };
@@ -4519,12 +5565,14 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceGetPowerSource")]
     pub fn power_source(&self) -> Result<PowerSource, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetPowerSource.as_ref())?;
-
         let power_source_c = unsafe {
             let mut power_source: nvmlPowerSource_t = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut power_source))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetPowerSource(self.device, &mut power_source)?,
+            )?;
 
             power_source
         };
@@ -4545,12 +5593,14 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceGetMemoryBusWidth")]
     pub fn memory_bus_width(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetMemoryBusWidth.as_ref())?;
-
         let memory_bus_width = unsafe {
             let mut memory_bus_width: c_uint = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut memory_bus_width))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetMemoryBusWidth(self.device, &mut memory_bus_width)?,
+            )?;
 
             memory_bus_width
         };
@@ -4569,12 +5619,14 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceGetPcieLinkMaxSpeed")]
     pub fn max_pcie_link_speed(&self) -> Result<PcieLinkMaxSpeed, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetPcieLinkMaxSpeed.as_ref())?;
-
         let pcie_link_max_speed_c = unsafe {
             let mut pcie_link_max_speed: c_uint = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut pcie_link_max_speed))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetPcieLinkMaxSpeed(self.device, &mut pcie_link_max_speed)?,
+            )?;
 
             pcie_link_max_speed
         };
@@ -4613,12 +5665,14 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceGetPcieSpeed")]
     pub fn pcie_link_speed(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetPcieSpeed.as_ref())?;
-
         let pcie_speed_c = unsafe {
             let mut pcie_speed: c_uint = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut pcie_speed))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetPcieSpeed(self.device, &mut pcie_speed)?,
+            )?;
 
             pcie_speed
         };
@@ -4635,12 +5689,14 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceGetBusType")]
     pub fn bus_type(&self) -> Result<BusType, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetBusType.as_ref())?;
-
         let bus_type_c = unsafe {
             let mut bus_type: nvmlBusType_t = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut bus_type))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetBusType(self.device, &mut bus_type)?,
+            )?;
 
             bus_type
         };
@@ -4657,12 +5713,14 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceGetArchitecture")]
     pub fn architecture(&self) -> Result<DeviceArchitecture, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetArchitecture.as_ref())?;
-
         let architecture_c = unsafe {
             let mut architecture: nvmlDeviceArchitecture_t = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut architecture))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetArchitecture(self.device, &mut architecture)?,
+            )?;
 
             architecture
         };
@@ -4685,12 +5743,14 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceOnSameBoard")]
     pub fn is_on_same_board_as(&self, other_device: &Device) -> Result<bool, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceOnSameBoard.as_ref())?;
-
         unsafe {
             let mut bool_int: c_int = mem::zeroed();
 
-            nvml_try(sym(self.device, other_device.handle(), &mut bool_int))?;
+            nvml_try(self.nvml.backend.nvmlDeviceOnSameBoard(
+                self.device,
+                other_device.handle(),
+                &mut bool_int,
+            )?)?;
 
             #[allow(clippy::match_like_matches_macro)]
             Ok(match bool_int {
@@ -4729,9 +5789,13 @@ impl<'nvml> Device<'nvml> {
     // Tested (no-run)
     #[doc(alias = "nvmlDeviceResetApplicationsClocks")]
     pub fn reset_applications_clocks(&mut self) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceResetApplicationsClocks.as_ref())?;
-
-        unsafe { nvml_try(sym(self.device)) }
+        unsafe {
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceResetApplicationsClocks(self.device)?,
+            )
+        }
     }
 
     /**
@@ -4769,9 +5833,13 @@ impl<'nvml> Device<'nvml> {
     // Tested (no-run)
     #[doc(alias = "nvmlDeviceSetAutoBoostedClocksEnabled")]
     pub fn set_auto_boosted_clocks(&mut self, enabled: bool) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetAutoBoostedClocksEnabled.as_ref())?;
-
-        unsafe { nvml_try(sym(self.device, state_from_bool(enabled))) }
+        unsafe {
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceSetAutoBoostedClocksEnabled(self.device, state_from_bool(enabled))?,
+            )
+        }
     }
 
     /**
@@ -4801,9 +5869,7 @@ impl<'nvml> Device<'nvml> {
     #[cfg(target_os = "linux")]
     #[doc(alias = "nvmlDeviceSetCpuAffinity")]
     pub fn set_cpu_affinity(&mut self) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetCpuAffinity.as_ref())?;
-
-        unsafe { nvml_try(sym(self.device)) }
+        unsafe { nvml_try(self.nvml.backend.nvmlDeviceSetCpuAffinity(self.device)?) }
     }
 
     /**
@@ -4837,8 +5903,6 @@ impl<'nvml> Device<'nvml> {
         size: usize,
         scope: nvmlAffinityScope_t,
     ) -> Result<Vec<c_ulong>, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetCpuAffinityWithinScope.as_ref())?;
-
         unsafe {
             if size == 0 {
                 // Return an error containing the minimum size that can be passed.
@@ -4847,12 +5911,12 @@ impl<'nvml> Device<'nvml> {
 
             let mut affinities: Vec<c_ulong> = vec![mem::zeroed(); size];
 
-            nvml_try(sym(
+            nvml_try(self.nvml.backend.nvmlDeviceGetCpuAffinityWithinScope(
                 self.device,
                 size as c_uint,
                 affinities.as_mut_ptr(),
                 scope,
-            ))?;
+            )?)?;
 
             Ok(affinities)
         }
@@ -4892,16 +5956,17 @@ impl<'nvml> Device<'nvml> {
     // Tested (no-run)
     #[doc(alias = "nvmlDeviceSetDefaultAutoBoostedClocksEnabled")]
     pub fn set_auto_boosted_clocks_default(&mut self, enabled: bool) -> Result<(), NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlDeviceSetDefaultAutoBoostedClocksEnabled
-                .as_ref(),
-        )?;
-
         unsafe {
             // Passing 0 because NVIDIA says flags are not supported yet
-            nvml_try(sym(self.device, state_from_bool(enabled), 0))
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceSetDefaultAutoBoostedClocksEnabled(
+                        self.device,
+                        state_from_bool(enabled),
+                        0,
+                    )?,
+            )
         }
     }
 
@@ -4926,9 +5991,7 @@ impl<'nvml> Device<'nvml> {
     // Tested on machines other than my own
     #[doc(alias = "nvmlDeviceValidateInforom")]
     pub fn validate_info_rom(&self) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceValidateInforom.as_ref())?;
-
-        unsafe { nvml_try(sym(self.device)) }
+        unsafe { nvml_try(self.nvml.backend.nvmlDeviceValidateInforom(self.device)?) }
     }
 
     // Wrappers for things from Accounting Statistics now
@@ -4954,9 +6017,13 @@ impl<'nvml> Device<'nvml> {
     // Tested (no-run)
     #[doc(alias = "nvmlDeviceClearAccountingPids")]
     pub fn clear_accounting_pids(&mut self) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceClearAccountingPids.as_ref())?;
-
-        unsafe { nvml_try(sym(self.device)) }
+        unsafe {
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceClearAccountingPids(self.device)?,
+            )
+        }
     }
 
     /**
@@ -4983,11 +6050,13 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetAccountingBufferSize")]
     pub fn accounting_buffer_size(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetAccountingBufferSize.as_ref())?;
-
         unsafe {
             let mut count: c_uint = mem::zeroed();
-            nvml_try(sym(self.device, &mut count))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetAccountingBufferSize(self.device, &mut count)?,
+            )?;
 
             Ok(count)
         }
@@ -5012,11 +6081,13 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetAccountingMode")]
     pub fn is_accounting_enabled(&self) -> Result<bool, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetAccountingMode.as_ref())?;
-
         unsafe {
             let mut state: nvmlEnableState_t = mem::zeroed();
-            nvml_try(sym(self.device, &mut state))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetAccountingMode(self.device, &mut state)?,
+            )?;
 
             bool_from_state(state)
         }
@@ -5041,8 +6112,6 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetAccountingPids")]
     pub fn accounting_pids(&self) -> Result<Vec<u32>, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetAccountingPids.as_ref())?;
-
         unsafe {
             let mut count = match self.accounting_pids_count()? {
                 0 => return Ok(vec![]),
@@ -5050,7 +6119,11 @@ impl<'nvml> Device<'nvml> {
             };
             let mut pids: Vec<c_uint> = vec![mem::zeroed(); count as usize];
 
-            nvml_try(sym(self.device, &mut count, pids.as_mut_ptr()))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetAccountingPids(
+                self.device,
+                &mut count,
+                pids.as_mut_ptr(),
+            )?)?;
 
             Ok(pids)
         }
@@ -5058,13 +6131,15 @@ impl<'nvml> Device<'nvml> {
 
     // Helper function for the above.
     fn accounting_pids_count(&self) -> Result<c_uint, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetAccountingPids.as_ref())?;
-
         // Indicates that we want the count
         let mut count: c_uint = 0;
         unsafe {
             // Null also indicates that we want the count
-            nvml_try_count(sym(self.device, &mut count, ptr::null_mut()))?;
+            nvml_try_count(self.nvml.backend.nvmlDeviceGetAccountingPids(
+                self.device,
+                &mut count,
+                ptr::null_mut(),
+            )?)?;
         }
         Ok(count)
     }
@@ -5110,12 +6185,14 @@ impl<'nvml> Device<'nvml> {
     // Tested (for error)
     #[doc(alias = "nvmlDeviceGetAccountingStats")]
     pub fn accounting_stats_for(&self, process_id: u32) -> Result<AccountingStats, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetAccountingStats.as_ref())?;
-
         unsafe {
             let mut stats: nvmlAccountingStats_t = mem::zeroed();
 
-            nvml_try(sym(self.device, process_id, &mut stats))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetAccountingStats(
+                self.device,
+                process_id,
+                &mut stats,
+            )?)?;
 
             Ok(stats.into())
         }
@@ -5149,9 +6226,13 @@ impl<'nvml> Device<'nvml> {
     // Tested (no-run)
     #[doc(alias = "nvmlDeviceSetAccountingMode")]
     pub fn set_accounting(&mut self, enabled: bool) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetAccountingMode.as_ref())?;
-
-        unsafe { nvml_try(sym(self.device, state_from_bool(enabled))) }
+        unsafe {
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceSetAccountingMode(self.device, state_from_bool(enabled))?,
+            )
+        }
     }
 
     // Device commands starting here
@@ -5184,9 +6265,13 @@ impl<'nvml> Device<'nvml> {
     // Tested (no-run)
     #[doc(alias = "nvmlDeviceClearEccErrorCounts")]
     pub fn clear_ecc_error_counts(&mut self, counter_type: EccCounter) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceClearEccErrorCounts.as_ref())?;
-
-        unsafe { nvml_try(sym(self.device, counter_type.as_c())) }
+        unsafe {
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceClearEccErrorCounts(self.device, counter_type.as_c())?,
+            )
+        }
     }
 
     /**
@@ -5216,14 +6301,12 @@ impl<'nvml> Device<'nvml> {
     // Tested (no-run)
     #[doc(alias = "nvmlDeviceSetAPIRestriction")]
     pub fn set_api_restricted(&mut self, api_type: Api, restricted: bool) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetAPIRestriction.as_ref())?;
-
         unsafe {
-            nvml_try(sym(
+            nvml_try(self.nvml.backend.nvmlDeviceSetAPIRestriction(
                 self.device,
                 api_type.as_c(),
                 state_from_bool(restricted),
-            ))
+            )?)
         }
     }
 
@@ -5273,9 +6356,13 @@ impl<'nvml> Device<'nvml> {
         mem_clock: u32,
         graphics_clock: u32,
     ) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetApplicationsClocks.as_ref())?;
-
-        unsafe { nvml_try(sym(self.device, mem_clock, graphics_clock)) }
+        unsafe {
+            nvml_try(self.nvml.backend.nvmlDeviceSetApplicationsClocks(
+                self.device,
+                mem_clock,
+                graphics_clock,
+            )?)
+        }
     }
 
     /**
@@ -5306,9 +6393,13 @@ impl<'nvml> Device<'nvml> {
     // Tested (no-run)
     #[doc(alias = "nvmlDeviceSetComputeMode")]
     pub fn set_compute_mode(&mut self, mode: ComputeMode) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetComputeMode.as_ref())?;
-
-        unsafe { nvml_try(sym(self.device, mode.as_c())) }
+        unsafe {
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceSetComputeMode(self.device, mode.as_c())?,
+            )
+        }
     }
 
     /**
@@ -5374,9 +6465,13 @@ impl<'nvml> Device<'nvml> {
         model: DriverModel,
         flags: Behavior,
     ) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetDriverModel.as_ref())?;
-
-        unsafe { nvml_try(sym(self.device, model.as_c(), flags.bits())) }
+        unsafe {
+            nvml_try(self.nvml.backend.nvmlDeviceSetDriverModel(
+                self.device,
+                model.as_c(),
+                flags.bits(),
+            )?)
+        }
     }
 
     /**
@@ -5410,11 +6505,15 @@ impl<'nvml> Device<'nvml> {
         &mut self,
         setting: GpuLockedClocksSetting,
     ) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetGpuLockedClocks.as_ref())?;
-
         let (min_clock_mhz, max_clock_mhz) = setting.into_min_and_max_clocks();
 
-        unsafe { nvml_try(sym(self.device, min_clock_mhz, max_clock_mhz)) }
+        unsafe {
+            nvml_try(self.nvml.backend.nvmlDeviceSetGpuLockedClocks(
+                self.device,
+                min_clock_mhz,
+                max_clock_mhz,
+            )?)
+        }
     }
 
     /**
@@ -5438,9 +6537,13 @@ impl<'nvml> Device<'nvml> {
     // Tested (no-run)
     #[doc(alias = "nvmlDeviceResetGpuLockedClocks")]
     pub fn reset_gpu_locked_clocks(&mut self) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceResetGpuLockedClocks.as_ref())?;
-
-        unsafe { nvml_try(sym(self.device)) }
+        unsafe {
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceResetGpuLockedClocks(self.device)?,
+            )
+        }
     }
 
     /**
@@ -5467,9 +6570,13 @@ impl<'nvml> Device<'nvml> {
         min_clock_mhz: u32,
         max_clock_mhz: u32,
     ) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetMemoryLockedClocks.as_ref())?;
-
-        unsafe { nvml_try(sym(self.device, min_clock_mhz, max_clock_mhz)) }
+        unsafe {
+            nvml_try(self.nvml.backend.nvmlDeviceSetMemoryLockedClocks(
+                self.device,
+                min_clock_mhz,
+                max_clock_mhz,
+            )?)
+        }
     }
 
     /**
@@ -5493,9 +6600,13 @@ impl<'nvml> Device<'nvml> {
     // Tested (no-run)
     #[doc(alias = "nvmlDeviceResetMemoryLockedClocks")]
     pub fn reset_mem_locked_clocks(&mut self) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceResetMemoryLockedClocks.as_ref())?;
-
-        unsafe { nvml_try(sym(self.device)) }
+        unsafe {
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceResetMemoryLockedClocks(self.device)?,
+            )
+        }
     }
 
     /**
@@ -5523,9 +6634,13 @@ impl<'nvml> Device<'nvml> {
     // Tested (no-run)
     #[doc(alias = "nvmlDeviceSetEccMode")]
     pub fn set_ecc(&mut self, enabled: bool) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetEccMode.as_ref())?;
-
-        unsafe { nvml_try(sym(self.device, state_from_bool(enabled))) }
+        unsafe {
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceSetEccMode(self.device, state_from_bool(enabled))?,
+            )
+        }
     }
 
     /**
@@ -5557,9 +6672,13 @@ impl<'nvml> Device<'nvml> {
     // Tested (no-run)
     #[doc(alias = "nvmlDeviceSetGpuOperationMode")]
     pub fn set_gpu_op_mode(&mut self, mode: OperationMode) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetGpuOperationMode.as_ref())?;
-
-        unsafe { nvml_try(sym(self.device, mode.as_c())) }
+        unsafe {
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceSetGpuOperationMode(self.device, mode.as_c())?,
+            )
+        }
     }
 
     /**
@@ -5597,9 +6716,13 @@ impl<'nvml> Device<'nvml> {
     #[cfg(target_os = "linux")]
     #[doc(alias = "nvmlDeviceSetPersistenceMode")]
     pub fn set_persistent(&mut self, enabled: bool) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetPersistenceMode.as_ref())?;
-
-        unsafe { nvml_try(sym(self.device, state_from_bool(enabled))) }
+        unsafe {
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceSetPersistenceMode(self.device, state_from_bool(enabled))?,
+            )
+        }
     }
 
     /**
@@ -5630,9 +6753,13 @@ impl<'nvml> Device<'nvml> {
     // Tested (no-run)
     #[doc(alias = "nvmlDeviceSetPowerManagementLimit")]
     pub fn set_power_management_limit(&mut self, limit: u32) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetPowerManagementLimit.as_ref())?;
-
-        unsafe { nvml_try(sym(self.device, limit)) }
+        unsafe {
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceSetPowerManagementLimit(self.device, limit)?,
+            )
+        }
     }
 
     /**
@@ -5655,8 +6782,6 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceSetPowerMizerMode_v1")]
     pub fn set_power_mizer_mode(&mut self, mode: PowerMizerMode) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetPowerMizerMode_v1.as_ref())?;
-
         unsafe {
             let mut power_mizer_mode = nvmlDevicePowerMizerModes_v1_t {
                 currentMode: mem::zeroed(),
@@ -5664,7 +6789,11 @@ impl<'nvml> Device<'nvml> {
                 supportedPowerMizerModes: mem::zeroed(),
             };
 
-            nvml_try(sym(self.device, &mut power_mizer_mode))
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceSetPowerMizerMode_v1(self.device, &mut power_mizer_mode)?,
+            )
         }
     }
 
@@ -5690,8 +6819,6 @@ impl<'nvml> Device<'nvml> {
         clock_type: Clock,
         power_state: PerformanceState,
     ) -> Result<ClockOffset, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetClockOffsets.as_ref())?;
-
         unsafe {
             // Implements NVML_STRUCT_VERSION(ClockOffset, 1), as detailed in nvml.h
             let version =
@@ -5705,7 +6832,11 @@ impl<'nvml> Device<'nvml> {
                 minClockOffsetMHz: mem::zeroed(),
                 maxClockOffsetMHz: mem::zeroed(),
             };
-            nvml_try(sym(self.device, &mut clock_offset))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetClockOffsets(self.device, &mut clock_offset)?,
+            )?;
             ClockOffset::try_from(clock_offset)
         }
     }
@@ -5733,8 +6864,6 @@ impl<'nvml> Device<'nvml> {
         power_state: PerformanceState,
         offset: i32,
     ) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetClockOffsets.as_ref())?;
-
         unsafe {
             // Implements NVML_STRUCT_VERSION(ClockOffset, 1), as detailed in nvml.h
             let version =
@@ -5748,7 +6877,11 @@ impl<'nvml> Device<'nvml> {
                 minClockOffsetMHz: 0,
                 maxClockOffsetMHz: 0,
             };
-            nvml_try(sym(self.device, &mut clock_offset))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceSetClockOffsets(self.device, &mut clock_offset)?,
+            )?;
             Ok(())
         }
     }
@@ -5769,20 +6902,17 @@ impl<'nvml> Device<'nvml> {
     // Tested
     #[doc(alias = "nvmlDeviceGetSupportedPerformanceStates")]
     pub fn supported_performance_states(&self) -> Result<Vec<PerformanceState>, NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlDeviceGetSupportedPerformanceStates
-                .as_ref(),
-        )?;
-
         unsafe {
             let mut pstates =
                 [PerformanceState::Unknown.as_c(); NVML_MAX_GPU_PERF_PSTATES as usize];
             // The array size passed to `nvmlDeviceGetSupportedPerformanceStates` must be in bytes, not array length
             let byte_size = mem::size_of_val(&pstates);
 
-            nvml_try(sym(self.device, pstates.as_mut_ptr(), byte_size as u32))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetSupportedPerformanceStates(
+                self.device,
+                pstates.as_mut_ptr(),
+                byte_size as u32,
+            )?)?;
 
             pstates
                 .into_iter()
@@ -5811,19 +6941,17 @@ impl<'nvml> Device<'nvml> {
         clock_type: Clock,
         pstate: PerformanceState,
     ) -> Result<(u32, u32), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetMinMaxClockOfPState.as_ref())?;
-
         unsafe {
             let mut min: u32 = mem::zeroed();
             let mut max: u32 = mem::zeroed();
 
-            nvml_try(sym(
+            nvml_try(self.nvml.backend.nvmlDeviceGetMinMaxClockOfPState(
                 self.device,
                 clock_type.as_c(),
                 pstate.as_c(),
                 &mut min,
                 &mut max,
-            ))?;
+            )?)?;
 
             Ok((min, max))
         }
@@ -5907,10 +7035,12 @@ impl<'nvml> Device<'nvml> {
         events: EventTypes,
         set: EventSet<'nvml>,
     ) -> Result<EventSet<'nvml>, NvmlErrorWithSource> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceRegisterEvents.as_ref())?;
-
         unsafe {
-            match nvml_try(sym(self.device, events.bits(), set.handle())) {
+            match nvml_try(self.nvml.backend.nvmlDeviceRegisterEvents(
+                self.device,
+                events.bits(),
+                set.handle(),
+            )?) {
                 Ok(()) => Ok(set),
                 Err(NvmlError::Unknown) => {
                     // NVIDIA says that if an Unknown error is returned, `set` will
@@ -6024,11 +7154,13 @@ impl<'nvml> Device<'nvml> {
     // Helper for the above methods.
     #[cfg(target_os = "linux")]
     fn supported_event_types_raw(&self) -> Result<c_ulonglong, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetSupportedEventTypes.as_ref())?;
-
         unsafe {
             let mut ev_types: c_ulonglong = mem::zeroed();
-            nvml_try(sym(self.device, &mut ev_types))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetSupportedEventTypes(self.device, &mut ev_types)?,
+            )?;
 
             Ok(ev_types)
         }
@@ -6106,9 +7238,14 @@ impl<'nvml> Device<'nvml> {
             self.pci_info()?
         };
 
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceModifyDrainState.as_ref())?;
-
-        unsafe { nvml_try(sym(&mut pci_info.try_into()?, state_from_bool(enabled))) }
+        unsafe {
+            nvml_try(
+                self.nvml.backend.nvmlDeviceModifyDrainState(
+                    &mut pci_info.try_into()?,
+                    state_from_bool(enabled),
+                )?,
+            )
+        }
     }
 
     /**
@@ -6173,12 +7310,14 @@ impl<'nvml> Device<'nvml> {
             self.pci_info()?
         };
 
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceQueryDrainState.as_ref())?;
-
         unsafe {
             let mut state: nvmlEnableState_t = mem::zeroed();
 
-            nvml_try(sym(&mut pci_info.try_into()?, &mut state))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceQueryDrainState(&mut pci_info.try_into()?, &mut state)?,
+            )?;
 
             bool_from_state(state)
         }
@@ -6206,12 +7345,14 @@ impl<'nvml> Device<'nvml> {
     #[cfg(target_os = "linux")]
     #[doc(alias = "nvmlDeviceGetPerformanceModes")]
     pub fn performance_modes(&self) -> Result<(Vec<String>, u32), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetPerformanceModes.as_ref())?;
-
         unsafe {
             let mut pmodes: nvmlDevicePerfModes_t = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut pmodes))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetPerformanceModes(self.device, &mut pmodes)?,
+            )?;
 
             let modes_str = CStr::from_ptr(pmodes.str_.as_ptr());
             let modes = modes_str.to_str()?;
@@ -6243,19 +7384,395 @@ impl<'nvml> Device<'nvml> {
     #[cfg(target_os = "linux")]
     #[doc(alias = "nvmlDeviceGetActiveVgpus")]
     pub fn active_vgpus(&self) -> Result<Vec<nvmlVgpuInstance_t>, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetActiveVgpus.as_ref())?;
-
         unsafe {
             let mut count: u32 = 0;
 
-            nvml_try_count(sym(self.device, &mut count, std::ptr::null_mut()))?;
+            nvml_try_count(self.nvml.backend.nvmlDeviceGetActiveVgpus(
+                self.device,
+                &mut count,
+                std::ptr::null_mut(),
+            )?)?;
             let mut arr: Vec<nvmlVgpuInstance_t> = vec![0; count as usize];
-            nvml_try(sym(self.device, &mut count, arr.as_mut_ptr()))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetActiveVgpus(
+                self.device,
+                &mut count,
+                arr.as_mut_ptr(),
+            )?)?;
 
             Ok(arr)
         }
     }
 
+    /**
+    Gets the active vGPU instances for `Device`, wrapped as `VgpuInstance`s.
+
+    This is `active_vgpus()` with each handle wrapped for use with the
+    `VgpuInstance` methods.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    * `NotSupported`, if the platform does not support this feature
+
+    # Platform Support
+
+    Only supports Linux.
+    */
+    // Tested
+    #[cfg(target_os = "linux")]
+    pub fn vgpu_instances(&self) -> Result<Vec<VgpuInstance<'_>>, NvmlError> {
+        Ok(self
+            .active_vgpus()?
+            .into_iter()
+            .map(|id| VgpuInstance::new(self, id))
+            .collect())
+    }
+
+    /**
+    Gets the metadata of this `Device` as a physical GPU hosting vGPUs, used to
+    check whether a vGPU can be migrated to it with `Nvml.vgpu_compatibility()`
+    (or `high_level::vgpu_migration::check_migration()`).
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` isn't hosting vGPUs
+    * `Utf8Error`, if a string obtained from the C function is not valid Utf8
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell or newer fully supported devices.
+    */
+    // Tested
+    #[doc(alias = "nvmlDeviceGetVgpuMetadata")]
+    pub fn vgpu_metadata(&self) -> Result<VgpuPgpuMetadata, NvmlError> {
+        unsafe {
+            let mut size: c_uint = 0;
+            nvml_try_count(self.nvml.backend.nvmlDeviceGetVgpuMetadata(
+                self.device,
+                ptr::null_mut(),
+                &mut size,
+            )?)?;
+
+            let mut buffer = metadata_buffer::<nvmlVgpuPgpuMetadata_t>(size as usize);
+            let mut size = mem::size_of_val(buffer.as_slice()) as c_uint;
+            nvml_try(self.nvml.backend.nvmlDeviceGetVgpuMetadata(
+                self.device,
+                buffer.as_mut_ptr() as *mut nvmlVgpuPgpuMetadata_t,
+                &mut size,
+            )?)?;
+
+            VgpuPgpuMetadata::from_c_buffer(&buffer)
+        }
+    }
+
+    /**
+    Gets the metadata of this `Device` as a physical GPU hosting vGPUs, as a
+    string for display.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` isn't hosting vGPUs
+    * `Utf8Error`, if the string obtained from the C function is not valid Utf8
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell or newer fully supported devices.
+    */
+    // Tested
+    #[doc(alias = "nvmlDeviceGetPgpuMetadataString")]
+    pub fn pgpu_metadata_string(&self) -> Result<String, NvmlError> {
+        unsafe {
+            let mut size: c_uint = 0;
+            nvml_try_count(self.nvml.backend.nvmlDeviceGetPgpuMetadataString(
+                self.device,
+                ptr::null_mut(),
+                &mut size,
+            )?)?;
+
+            // Room for the nul terminator, in case it isn't counted
+            let mut buffer = vec![0; size as usize + 1];
+            let mut size = buffer.len() as c_uint;
+            nvml_try(self.nvml.backend.nvmlDeviceGetPgpuMetadataString(
+                self.device,
+                buffer.as_mut_ptr(),
+                &mut size,
+            )?)?;
+
+            let raw = CStr::from_ptr(buffer.as_ptr());
+            Ok(raw.to_str()?.into())
+        }
+    }
+
+    /**
+    Gets utilization stats for the vGPU instances running on this `Device`.
+
+    Stats are returned for every active vGPU instance, whether or not it had
+    non-zero utilization during the target sample period. Passing `None` as the
+    `last_seen_timestamp` will target all samples that the driver has buffered;
+    passing a timestamp retrieved from a previous query will target samples taken
+    since that timestamp.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if vGPU is not supported by this `Device`
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler or newer fully supported devices.
+    */
+    // Tested
+    #[doc(alias = "nvmlDeviceGetVgpuUtilization")]
+    pub fn vgpu_utilization_stats<T>(
+        &self,
+        last_seen_timestamp: T,
+    ) -> Result<Vec<VgpuInstanceUtilizationSample>, NvmlError>
+    where
+        T: Into<Option<u64>>,
+    {
+        unsafe {
+            let last_seen_timestamp = last_seen_timestamp.into().unwrap_or(0);
+            let mut val_type: nvmlValueType_t = mem::zeroed();
+            let mut count: c_uint = 0;
+
+            nvml_try_count(self.nvml.backend.nvmlDeviceGetVgpuUtilization(
+                self.device,
+                last_seen_timestamp,
+                &mut val_type,
+                &mut count,
+                ptr::null_mut(),
+            )?)?;
+
+            if count == 0 {
+                return Ok(vec![]);
+            }
+
+            let mut samples: Vec<nvmlVgpuInstanceUtilizationSample_t> =
+                vec![mem::zeroed(); count as usize];
+
+            nvml_try(self.nvml.backend.nvmlDeviceGetVgpuUtilization(
+                self.device,
+                last_seen_timestamp,
+                &mut val_type,
+                &mut count,
+                samples.as_mut_ptr(),
+            )?)?;
+            samples.truncate(count as usize);
+
+            let val_type = SampleValueType::try_from(val_type)?;
+            Ok(samples
+                .into_iter()
+                .map(|s| VgpuInstanceUtilizationSample::from_tag_and_struct(&val_type, s))
+                .collect())
+        }
+    }
+
+    /**
+    Gets utilization stats for the processes running inside the vGPU instances on
+    this `Device`.
+
+    Utilization stats are returned for processes that had a non-zero utilization stat
+    at some point during the target sample period. Passing `None` as the
+    `last_seen_timestamp` will target all samples that the driver has buffered; passing
+    a timestamp retrieved from a previous query will target samples taken since that
+    timestamp.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if vGPU is not supported by this `Device`
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Utf8Error`, if a process name obtained from the C function is not valid Utf8
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell or newer fully supported devices.
+    */
+    // Tested
+    #[doc(alias = "nvmlDeviceGetVgpuProcessUtilization")]
+    pub fn vgpu_process_utilization_stats<T>(
+        &self,
+        last_seen_timestamp: T,
+    ) -> Result<Vec<VgpuProcessUtilizationSample>, NvmlError>
+    where
+        T: Into<Option<u64>>,
+    {
+        unsafe {
+            let last_seen_timestamp = last_seen_timestamp.into().unwrap_or(0);
+            let mut count: c_uint = 0;
+
+            nvml_try_count(self.nvml.backend.nvmlDeviceGetVgpuProcessUtilization(
+                self.device,
+                last_seen_timestamp,
+                &mut count,
+                ptr::null_mut(),
+            )?)?;
+
+            if count == 0 {
+                return Ok(vec![]);
+            }
+
+            let mut samples: Vec<nvmlVgpuProcessUtilizationSample_t> =
+                vec![mem::zeroed(); count as usize];
+
+            nvml_try(self.nvml.backend.nvmlDeviceGetVgpuProcessUtilization(
+                self.device,
+                last_seen_timestamp,
+                &mut count,
+                samples.as_mut_ptr(),
+            )?)?;
+            samples.truncate(count as usize);
+
+            samples
+                .into_iter()
+                .map(VgpuProcessUtilizationSample::try_from)
+                .collect()
+        }
+    }
+
+    /**
+    Gets utilization stats for the vGPU instances running on this `Device`,
+    including the JPEG and OFA engines.
+
+    This has the same semantics as `.vgpu_utilization_stats()`: passing `None` as
+    the `last_seen_timestamp` will target all samples that the driver has buffered;
+    passing a timestamp retrieved from a previous query will target samples taken
+    since that timestamp.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if vGPU is not supported by this `Device`
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell or newer fully supported devices.
+    */
+    // Tested
+    #[doc(alias = "nvmlDeviceGetVgpuInstancesUtilizationInfo")]
+    pub fn vgpu_instances_utilization_info<T>(
+        &self,
+        last_seen_timestamp: T,
+    ) -> Result<Vec<VgpuInstanceUtilizationInfo>, NvmlError>
+    where
+        T: Into<Option<u64>>,
+    {
+        unsafe {
+            let mut info: nvmlVgpuInstancesUtilizationInfo_t = mem::zeroed();
+            info.version = vgpu_instances_utilization_info_version();
+            info.lastSeenTimeStamp = last_seen_timestamp.into().unwrap_or(0);
+
+            nvml_try_count(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetVgpuInstancesUtilizationInfo(self.device, &mut info)?,
+            )?;
+
+            if info.vgpuInstanceCount == 0 {
+                return Ok(vec![]);
+            }
+
+            let mut samples: Vec<nvmlVgpuInstanceUtilizationInfo_v1_t> =
+                vec![mem::zeroed(); info.vgpuInstanceCount as usize];
+            info.vgpuUtilArray = samples.as_mut_ptr();
+
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetVgpuInstancesUtilizationInfo(self.device, &mut info)?,
+            )?;
+            samples.truncate(info.vgpuInstanceCount as usize);
+
+            let val_type = SampleValueType::try_from(info.sampleValType)?;
+            Ok(samples
+                .into_iter()
+                .map(|s| VgpuInstanceUtilizationInfo::from_tag_and_struct(&val_type, s))
+                .collect())
+        }
+    }
+
+    /**
+    Gets utilization stats for the processes running inside the vGPU instances on
+    this `Device`, including the JPEG and OFA engines.
+
+    This has the same semantics as `.vgpu_process_utilization_stats()`: passing
+    `None` as the `last_seen_timestamp` will target all samples that the driver
+    has buffered; passing a timestamp retrieved from a previous query will target
+    samples taken since that timestamp.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if vGPU is not supported by this `Device`
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Utf8Error`, if a process name obtained from the C function is not valid Utf8
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell or newer fully supported devices.
+    */
+    // Tested
+    #[doc(alias = "nvmlDeviceGetVgpuProcessesUtilizationInfo")]
+    pub fn vgpu_processes_utilization_info<T>(
+        &self,
+        last_seen_timestamp: T,
+    ) -> Result<Vec<VgpuProcessUtilizationInfo>, NvmlError>
+    where
+        T: Into<Option<u64>>,
+    {
+        unsafe {
+            let mut info: nvmlVgpuProcessesUtilizationInfo_t = mem::zeroed();
+            info.version = vgpu_processes_utilization_info_version();
+            info.lastSeenTimeStamp = last_seen_timestamp.into().unwrap_or(0);
+
+            nvml_try_count(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetVgpuProcessesUtilizationInfo(self.device, &mut info)?,
+            )?;
+
+            if info.vgpuProcessCount == 0 {
+                return Ok(vec![]);
+            }
+
+            let mut samples: Vec<nvmlVgpuProcessUtilizationInfo_v1_t> =
+                vec![mem::zeroed(); info.vgpuProcessCount as usize];
+            info.vgpuProcUtilArray = samples.as_mut_ptr();
+
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetVgpuProcessesUtilizationInfo(self.device, &mut info)?,
+            )?;
+            samples.truncate(info.vgpuProcessCount as usize);
+
+            samples
+                .into_iter()
+                .map(VgpuProcessUtilizationInfo::try_from)
+                .collect()
+        }
+    }
+
     /**
     Get the list of process ids running on a given vGPU instance for stats purpose
 
@@ -6275,14 +7792,20 @@ impl<'nvml> Device<'nvml> {
         &self,
         instance: nvmlVgpuInstance_t,
     ) -> Result<Vec<u32>, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlVgpuInstanceGetAccountingPids.as_ref())?;
-
         unsafe {
             let mut count: u32 = 0;
 
-            nvml_try_count(sym(instance, &mut count, std::ptr::null_mut()))?;
+            nvml_try_count(self.nvml.backend.nvmlVgpuInstanceGetAccountingPids(
+                instance,
+                &mut count,
+                std::ptr::null_mut(),
+            )?)?;
             let mut pids: Vec<u32> = vec![0; count as usize];
-            nvml_try(sym(instance, &mut count, pids.as_mut_ptr()))?;
+            nvml_try(self.nvml.backend.nvmlVgpuInstanceGetAccountingPids(
+                instance,
+                &mut count,
+                pids.as_mut_ptr(),
+            )?)?;
 
             Ok(pids)
         }
@@ -6307,11 +7830,13 @@ impl<'nvml> Device<'nvml> {
         instance: nvmlVgpuInstance_t,
         pid: u32,
     ) -> Result<AccountingStats, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlVgpuInstanceGetAccountingStats.as_ref())?;
-
         unsafe {
             let mut stats: nvmlAccountingStats_t = mem::zeroed();
-            nvml_try(sym(instance, pid, &mut stats))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlVgpuInstanceGetAccountingStats(instance, pid, &mut stats)?,
+            )?;
 
             Ok(AccountingStats::from(stats))
         }
@@ -6339,12 +7864,14 @@ impl<'nvml> Device<'nvml> {
     #[cfg(target_os = "linux")]
     #[doc(alias = "nvmlDeviceGetVirtualizationMode")]
     pub fn virtualization_mode(&self) -> Result<GpuVirtualizationMode, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetVirtualizationMode.as_ref())?;
-
         unsafe {
             let mut mode: nvmlGpuVirtualizationMode_t = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut mode))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetVirtualizationMode(self.device, &mut mode)?,
+            )?;
 
             GpuVirtualizationMode::try_from(mode)
         }
@@ -6485,8 +8012,16 @@ impl<'nvml> Device<'nvml> {
             }
         };
 
-        let sym = match nvml_sym(self.nvml.lib.nvmlDeviceRemoveGpu_v2.as_ref()) {
-            Ok(sym) => sym,
+        let result = unsafe {
+            self.nvml.backend.nvmlDeviceRemoveGpu_v2(
+                &mut raw_pci_info,
+                gpu_state.as_c(),
+                link_state.as_c(),
+            )
+        };
+
+        let code = match result {
+            Ok(code) => code,
             Err(error) => {
                 return (
                     Err(NvmlErrorWithSource {
@@ -6498,13 +8033,11 @@ impl<'nvml> Device<'nvml> {
             }
         };
 
-        unsafe {
-            match nvml_try(sym(&mut raw_pci_info, gpu_state.as_c(), link_state.as_c())) {
-                // `Device` removed; call was successful, no `Device` to return
-                Ok(()) => (Ok(()), None),
-                // `Device` has not been removed; unsuccessful call, return `Device`
-                Err(e) => (Err(e.into()), Some(self)),
-            }
+        match nvml_try(code) {
+            // `Device` removed; call was successful, no `Device` to return
+            Ok(()) => (Ok(()), None),
+            // `Device` has not been removed; unsuccessful call, return `Device`
+            Err(e) => (Err(e.into()), Some(self)),
         }
     }
 
@@ -6520,13 +8053,15 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceGetGspFirmwareMode")]
     pub fn gsp_firmware_mode(&self) -> Result<GspFirmwareMode, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetGspFirmwareMode.as_ref())?;
-
         unsafe {
             let mut enabled: c_uint = 0;
             let mut default: c_uint = 0;
 
-            nvml_try(sym(self.device, &mut enabled, &mut default))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetGspFirmwareMode(
+                self.device,
+                &mut enabled,
+                &mut default,
+            )?)?;
 
             Ok(GspFirmwareMode {
                 enabled: enabled != 0,
@@ -6547,12 +8082,14 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlDeviceGetGspFirmwareVersion")]
     pub fn gsp_firmware_version(&self) -> Result<String, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetGspFirmwareVersion.as_ref())?;
-
         unsafe {
             let mut version = vec![0; 80];
 
-            nvml_try(sym(self.device, version.as_mut_ptr()))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetGspFirmwareVersion(self.device, version.as_mut_ptr())?,
+            )?;
             let raw = CStr::from_ptr(version.as_ptr());
 
             Ok(raw.to_str()?.into())
@@ -6575,16 +8112,23 @@ impl<'nvml> Device<'nvml> {
 
     /// Obtain a list of vGPU type (profiles) supported by the device, if any.
     pub fn vgpu_supported_types(&self) -> Result<Vec<VgpuType<'_>>, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetSupportedVgpus.as_ref())?;
         let mut ids = vec![];
 
         unsafe {
             let mut count: c_uint = 0;
 
-            nvml_try_count(sym(self.device, &mut count, ids.as_mut_ptr()))?;
+            nvml_try_count(self.nvml.backend.nvmlDeviceGetSupportedVgpus(
+                self.device,
+                &mut count,
+                ids.as_mut_ptr(),
+            )?)?;
 
             ids.resize(count as usize, 0);
-            nvml_try(sym(self.device, &mut count, ids.as_mut_ptr()))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetSupportedVgpus(
+                self.device,
+                &mut count,
+                ids.as_mut_ptr(),
+            )?)?;
         }
 
         Ok(ids.into_iter().map(|id| VgpuType::new(self, id)).collect())
@@ -6592,16 +8136,23 @@ impl<'nvml> Device<'nvml> {
 
     /// Obtain a list of vGPU type (profiles) creatable on the device, if any.
     pub fn vgpu_creatable_types(&self) -> Result<Vec<VgpuType<'_>>, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetCreatableVgpus.as_ref())?;
         let mut ids = vec![];
 
         unsafe {
             let mut count: c_uint = 0;
 
-            nvml_try_count(sym(self.device, &mut count, ids.as_mut_ptr()))?;
+            nvml_try_count(self.nvml.backend.nvmlDeviceGetCreatableVgpus(
+                self.device,
+                &mut count,
+                ids.as_mut_ptr(),
+            )?)?;
 
             ids.resize(count as usize, 0);
-            nvml_try(sym(self.device, &mut count, ids.as_mut_ptr()))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetCreatableVgpus(
+                self.device,
+                &mut count,
+                ids.as_mut_ptr(),
+            )?)?;
         }
 
         Ok(ids.into_iter().map(|id| VgpuType::new(self, id)).collect())
@@ -6609,67 +8160,95 @@ impl<'nvml> Device<'nvml> {
 
     /// Obtain a list of vGPU scheduler capabilities supported by the device, if any.
     pub fn vgpu_scheduler_capabilities(&self) -> Result<VgpuSchedulerCapabilities, NvmlError> {
-        let sym = nvml_sym(
-            self.nvml
-                .lib
-                .nvmlDeviceGetVgpuSchedulerCapabilities
-                .as_ref(),
-        )?;
-
         unsafe {
             let mut capabilities: nvmlVgpuSchedulerCapabilities_t = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut capabilities))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetVgpuSchedulerCapabilities(self.device, &mut capabilities)?,
+            )?;
 
-            Ok(VgpuSchedulerCapabilities::from(capabilities))
+            VgpuSchedulerCapabilities::try_from(capabilities)
         }
     }
 
     /// Obtain the n log entries (max 200) of the vGPU scheduler, to be called several times if need
     /// be.
     pub fn vgpu_scheduler_log(&self) -> Result<VgpuSchedulerLog, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetVgpuSchedulerLog.as_ref())?;
-
         unsafe {
             let mut schedulerlog: nvmlVgpuSchedulerLog_t = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut schedulerlog))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetVgpuSchedulerLog(self.device, &mut schedulerlog)?,
+            )?;
 
-            Ok(VgpuSchedulerLog::from(schedulerlog))
+            VgpuSchedulerLog::try_from(schedulerlog)
         }
     }
 
     /// Obtain the vGPU scheduler state of the device
     pub fn vgpu_scheduler_state(&self) -> Result<VgpuSchedulerGetState, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetVgpuSchedulerState.as_ref())?;
-
         unsafe {
             let mut scheduler_state: nvmlVgpuSchedulerGetState_t = mem::zeroed();
 
-            nvml_try(sym(self.device, &mut scheduler_state))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetVgpuSchedulerState(self.device, &mut scheduler_state)?,
+            )?;
 
-            Ok(VgpuSchedulerGetState::from(scheduler_state))
+            VgpuSchedulerGetState::try_from(scheduler_state)
         }
     }
 
-    /// Set the vGPU scheduler state of the device
+    /**
+    Set the vGPU scheduler state of the device.
+
+    The state is first checked with `VgpuSchedulerSetState.validate()` and, if
+    the device reports them, against `.vgpu_scheduler_capabilities()`, so an
+    unsupported policy or out-of-range parameter is reported without reaching
+    NVML. The state can only be set while no vGPUs are running.
+
+    # Errors
+
+    * `UnsupportedVgpuSchedulerState`, if the device doesn't support `scheduler_state`
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` doesn't support vGPU scheduling
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `Unknown`, on any unexpected error
+    */
     pub fn set_vgpu_scheduler_state(
         &self,
         scheduler_state: VgpuSchedulerSetState,
     ) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetVgpuSchedulerState.as_ref())?;
+        scheduler_state.validate()?;
+        if let Some(capabilities) = optional(self.vgpu_scheduler_capabilities())? {
+            capabilities.validate(&scheduler_state)?;
+        }
 
-        unsafe { nvml_try(sym(self.device, &mut scheduler_state.as_c())) }
+        unsafe {
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceSetVgpuSchedulerState(self.device, &mut scheduler_state.as_c())?,
+            )
+        }
     }
 
     /// Check if the GPU is on vGPU host mode
     pub fn vgpu_host_mode(&self) -> Result<HostVgpuMode, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetHostVgpuMode.as_ref())?;
-
         unsafe {
             let mut mode: nvmlHostVgpuMode_t = 0;
 
-            nvml_try(sym(self.device, &mut mode))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetHostVgpuMode(self.device, &mut mode)?,
+            )?;
 
             HostVgpuMode::try_from(mode)
         }
@@ -6677,11 +8256,13 @@ impl<'nvml> Device<'nvml> {
 
     /// Query the given vGPU capability
     pub fn vgpu_capabilities(&self, cap: VgpuCapability) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetVgpuCapabilities.as_ref())?;
-
         unsafe {
             let mut res: c_uint = 0;
-            nvml_try(sym(self.device, cap.as_c(), &mut res))?;
+            nvml_try(self.nvml.backend.nvmlDeviceGetVgpuCapabilities(
+                self.device,
+                cap.as_c(),
+                &mut res,
+            )?)?;
 
             Ok(res)
         }
@@ -6692,15 +8273,89 @@ impl<'nvml> Device<'nvml> {
         cap: VgpuCapability,
         enable: bool,
     ) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceSetVgpuCapabilities.as_ref())?;
-
         unsafe {
             let state: nvmlEnableState_t = match enable {
                 true => nvmlEnableState_enum_NVML_FEATURE_ENABLED,
                 false => nvmlEnableState_enum_NVML_FEATURE_DISABLED,
             };
 
-            nvml_try(sym(self.device, cap.as_c(), state))
+            nvml_try(self.nvml.backend.nvmlDeviceSetVgpuCapabilities(
+                self.device,
+                cap.as_c(),
+                state,
+            )?)
+        }
+    }
+
+    /**
+    Checks whether this `Device` is in vGPU heterogeneous mode, in which it can
+    concurrently host time-sliced vGPUs with differing framebuffer sizes.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    // Tested
+    #[doc(alias = "nvmlDeviceGetVgpuHeterogeneousMode")]
+    pub fn is_vgpu_heterogeneous_mode_enabled(&self) -> Result<bool, NvmlError> {
+        unsafe {
+            let mut mode: nvmlVgpuHeterogeneousMode_t = mem::zeroed();
+            mode.version = vgpu_heterogeneous_mode_version();
+
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceGetVgpuHeterogeneousMode(self.device, &mut mode)?,
+            )?;
+
+            bool_from_state(mode.mode)
+        }
+    }
+
+    /**
+    Enables or disables vGPU heterogeneous mode on this `Device`.
+
+    The mode can only be changed while no vGPUs are running on this `Device`.
+    MIG-backed vGPU hosts set it per GPU instance instead, with
+    `GpuInstance.set_vgpu_heterogeneous_mode()`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `InUse`, if vGPUs are running on this `Device`
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    // Tested (no-run)
+    #[doc(alias = "nvmlDeviceSetVgpuHeterogeneousMode")]
+    pub fn set_vgpu_heterogeneous_mode(&mut self, enabled: bool) -> Result<(), NvmlError> {
+        unsafe {
+            let mode = nvmlVgpuHeterogeneousMode_t {
+                version: vgpu_heterogeneous_mode_version(),
+                mode: state_from_bool(enabled),
+            };
+
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlDeviceSetVgpuHeterogeneousMode(self.device, &mode)?,
+            )
         }
     }
 
@@ -6721,13 +8376,15 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlGpmQueryDeviceSupport")]
     pub fn gpm_support(&self) -> Result<bool, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlGpmQueryDeviceSupport.as_ref())?;
-
         unsafe {
             let mut support: nvmlGpmSupport_t = mem::zeroed();
             support.version = NVML_GPM_SUPPORT_VERSION;
 
-            nvml_try(sym(self.device, &mut support))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlGpmQueryDeviceSupport(self.device, &mut support)?,
+            )?;
 
             Ok(support.isSupportedDevice != 0)
         }
@@ -6749,11 +8406,13 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlGpmQueryIfStreamingEnabled")]
     pub fn gpm_streaming_enabled(&self) -> Result<bool, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlGpmQueryIfStreamingEnabled.as_ref())?;
-
         unsafe {
             let mut state: c_uint = 0;
-            nvml_try(sym(self.device, &mut state))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlGpmQueryIfStreamingEnabled(self.device, &mut state)?,
+            )?;
 
             Ok(state != 0)
         }
@@ -6775,11 +8434,15 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlGpmSetStreamingEnabled")]
     pub fn set_gpm_streaming_enabled(&self, enabled: bool) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlGpmSetStreamingEnabled.as_ref())?;
-
         let state: c_uint = if enabled { 1 } else { 0 };
 
-        unsafe { nvml_try(sym(self.device, state)) }
+        unsafe {
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlGpmSetStreamingEnabled(self.device, state)?,
+            )
+        }
     }
 
     /**
@@ -6802,12 +8465,14 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlGpmSampleGet")]
     pub fn gpm_sample(&self) -> Result<GpmSample<'nvml>, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlGpmSampleGet.as_ref())?;
-
         let sample = GpmSample::alloc(self.nvml)?;
 
         unsafe {
-            nvml_try(sym(self.device, sample.handle()))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlGpmSampleGet(self.device, sample.handle())?,
+            )?;
         }
 
         Ok(sample)
@@ -6834,12 +8499,14 @@ impl<'nvml> Device<'nvml> {
     */
     #[doc(alias = "nvmlGpmMigSampleGet")]
     pub fn gpm_mig_sample(&self, gpu_instance_id: u32) -> Result<GpmSample<'nvml>, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlGpmMigSampleGet.as_ref())?;
-
         let sample = GpmSample::alloc(self.nvml)?;
 
         unsafe {
-            nvml_try(sym(self.device, gpu_instance_id, sample.handle()))?;
+            nvml_try(self.nvml.backend.nvmlGpmMigSampleGet(
+                self.device,
+                gpu_instance_id,
+                sample.handle(),
+            )?)?;
         }
 
         Ok(sample)
@@ -6854,7 +8521,7 @@ mod test {
     #[cfg(target_os = "windows")]
     use crate::bitmasks::Behavior;
     use crate::enum_wrappers::device::*;
-    use crate::enums::device::{GpuLockedClocksSetting, PowerMizerMode};
+    use crate::enums::device::{GpuLockedClocksSetting, PowerMizerMode, ProcessMode};
     use crate::error::*;
     use crate::structs::device::FieldId;
     use crate::sys_exports::field_id::*;
@@ -7040,6 +8707,18 @@ mod test {
         test_with_device(3, &nvml, |device| device.decoder_utilization())
     }
 
+    #[test]
+    fn jpeg_utilization() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.jpeg_utilization())
+    }
+
+    #[test]
+    fn ofa_utilization() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.ofa_utilization())
+    }
+
     #[test]
     #[ignore = "my machine does not support this call"]
     fn default_applications_clock() {
@@ -7171,6 +8850,26 @@ mod test {
         test_with_device(3, &nvml, |device| device.running_graphics_processes_v2())
     }
 
+    #[test]
+    fn running_process_detail_list() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.running_process_detail_list(ProcessMode::Compute)
+        })
+    }
+
+    #[test]
+    fn processes_utilization_info() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.processes_utilization_info(None))
+    }
+
+    #[test]
+    fn gpu_processes() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.gpu_processes())
+    }
+
     #[test]
     fn process_utilization_stats() {
         let nvml = nvml();
@@ -7282,6 +8981,38 @@ mod test {
         test_with_device(3, &nvml, |device| device.profile_info(0))
     }
 
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn gpu_instance_profile_info() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.gpu_instance_profile_info(0))
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn gpu_instance_remaining_capacity() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let profile = device.profile_info(0)?;
+            device.gpu_instance_remaining_capacity(profile.id)
+        })
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn gpu_instances() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let profile = device.profile_info(0)?;
+            let infos: Result<Vec<_>, _> = device
+                .gpu_instances(profile.id)?
+                .iter()
+                .map(|instance| instance.info())
+                .collect();
+            infos
+        })
+    }
+
     #[test]
     fn mig_mode() {
         let nvml = nvml();
@@ -7308,6 +9039,22 @@ mod test {
         test(3, || device.mig_device_count())
     }
 
+    #[test]
+    fn mig_gpu_instance_id() {
+        let nvml = nvml();
+        let device = device(&nvml);
+        test(3, || device.mig_device_by_index(0)?.mig_gpu_instance_id())
+    }
+
+    #[test]
+    fn mig_compute_instance_id() {
+        let nvml = nvml();
+        let device = device(&nvml);
+        test(3, || {
+            device.mig_device_by_index(0)?.mig_compute_instance_id()
+        })
+    }
+
     #[test]
     fn mig_is_mig_device_handle() {
         let nvml = nvml();
@@ -7449,6 +9196,24 @@ mod test {
         test_with_device(3, &nvml, |device| device.are_pages_pending_retired())
     }
 
+    #[test]
+    fn remapped_rows() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.remapped_rows())
+    }
+
+    #[test]
+    fn row_remapper_histogram() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.row_remapper_histogram())
+    }
+
+    #[test]
+    fn memory_health() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.memory_health())
+    }
+
     #[test]
     #[ignore = "my machine does not support this call"]
     fn samples() {
@@ -7638,10 +9403,7 @@ mod test {
             .field_values_for(&field_ids)
             .expect("field_values_for call succeeded");
 
-        println!(
-            "{:<52} {:>6} {:>10}  {}",
-            "NAME", "V12_ID", "DRIVER_ID", "RESULT"
-        );
+        println!("{:<52} {:>6} {:>10}  RESULT", "NAME", "V12_ID", "DRIVER_ID");
         println!("{}", "-".repeat(90));
 
         for ((name, v12_id), sample) in fields.iter().zip(results.iter()) {
@@ -7679,27 +9441,63 @@ mod test {
         test_with_device(3, &nvml, |device| device.board_part_number())
     }
 
+    #[test]
+    fn current_clocks_event_reasons() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.current_clocks_event_reasons())
+    }
+
+    #[test]
+    fn current_clocks_event_reasons_strict() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.current_clocks_event_reasons_strict()
+        })
+    }
+
+    #[test]
+    fn supported_clocks_event_reasons() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.supported_clocks_event_reasons())
+    }
+
+    #[test]
+    fn supported_clocks_event_reasons_strict() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.supported_clocks_event_reasons_strict()
+        })
+    }
+
     #[test]
     fn current_throttle_reasons() {
         let nvml = nvml();
+
+        #[allow(deprecated)]
         test_with_device(3, &nvml, |device| device.current_throttle_reasons())
     }
 
     #[test]
     fn current_throttle_reasons_strict() {
         let nvml = nvml();
+
+        #[allow(deprecated)]
         test_with_device(3, &nvml, |device| device.current_throttle_reasons_strict())
     }
 
     #[test]
     fn supported_throttle_reasons() {
         let nvml = nvml();
+
+        #[allow(deprecated)]
         test_with_device(3, &nvml, |device| device.supported_throttle_reasons())
     }
 
     #[test]
     fn supported_throttle_reasons_strict() {
         let nvml = nvml();
+
+        #[allow(deprecated)]
         test_with_device(3, &nvml, |device| {
             device.supported_throttle_reasons_strict()
         })
@@ -7736,6 +9534,26 @@ mod test {
         })
     }
 
+    #[test]
+    fn temperature_v() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.temperature_v(TemperatureSensor::Gpu)
+        })
+    }
+
+    #[test]
+    fn margin_temperature() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.margin_temperature())
+    }
+
+    #[test]
+    fn thermal_settings() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.thermal_settings())
+    }
+
     #[test]
     fn temperature_threshold() {
         let nvml = nvml();
@@ -7799,6 +9617,12 @@ mod test {
         test_with_device(3, &nvml, |device| device.utilization_rates())
     }
 
+    #[test]
+    fn engine_utilization() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.engine_utilization())
+    }
+
     #[test]
     fn vbios_version() {
         let nvml = nvml();
@@ -8178,6 +10002,89 @@ mod test {
         test_with_device(3, &nvml, |device| device.active_vgpus())
     }
 
+    #[test]
+    fn vgpu_type_info() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device
+                .vgpu_supported_types()?
+                .iter()
+                .map(|t| t.info())
+                .collect::<Result<Vec<_>, _>>()
+        })
+    }
+
+    #[test]
+    fn vgpu_metadata() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.vgpu_metadata())
+    }
+
+    #[test]
+    fn pgpu_metadata_string() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.pgpu_metadata_string())
+    }
+
+    #[test]
+    fn is_vgpu_heterogeneous_mode_enabled() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.is_vgpu_heterogeneous_mode_enabled()
+        })
+    }
+
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn set_vgpu_heterogeneous_mode() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        device
+            .set_vgpu_heterogeneous_mode(true)
+            .expect("set to true")
+    }
+
+    #[test]
+    fn vgpu_utilization_stats() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.vgpu_utilization_stats(None))
+    }
+
+    #[test]
+    fn vgpu_process_utilization_stats() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.vgpu_process_utilization_stats(None)
+        })
+    }
+
+    #[test]
+    fn vgpu_instances_utilization_info() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.vgpu_instances_utilization_info(None)
+        })
+    }
+
+    #[test]
+    fn vgpu_processes_utilization_info() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.vgpu_processes_utilization_info(None)
+        })
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn vgpu_instances() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let instances = device.vgpu_instances()?;
+            Ok(instances.iter().map(|i| i.id()).collect::<Vec<_>>())
+        })
+    }
+
     #[test]
     fn vgpu_accounting_pids() {
         let nvml = nvml();
@@ -677,6 +677,30 @@ pub enum HostVgpuMode {
     Sriov,
 }
 
+/// A capability of a vGPU type, queried with `VgpuType.capabilities()`.
+#[derive(EnumWrapper, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[wrap(c_enum = "nvmlVgpuCapability_enum")]
+pub enum VgpuTypeCapability {
+    /// P2P over NvLink is supported
+    #[wrap(c_variant = "NVML_VGPU_CAP_NVLINK_P2P")]
+    NvlinkP2p,
+    /// GPUDirect capability is supported
+    #[wrap(c_variant = "NVML_VGPU_CAP_GPUDIRECT")]
+    GpuDirect,
+    /// vGPUs of this type can be the only vGPUs of a VM that has several
+    #[wrap(c_variant = "NVML_VGPU_CAP_MULTI_VGPU_EXCLUSIVE")]
+    MultiVgpuExclusive,
+    /// vGPUs of this type can't share the physical GPU with vGPUs of other
+    /// types
+    #[wrap(c_variant = "NVML_VGPU_CAP_EXCLUSIVE_TYPE")]
+    ExclusiveType,
+    /// vGPUs of this type can't share the physical GPU with vGPUs of other
+    /// framebuffer sizes
+    #[wrap(c_variant = "NVML_VGPU_CAP_EXCLUSIVE_SIZE")]
+    ExclusiveSize,
+}
+
 #[derive(EnumWrapper, Debug, Clone, Copy, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[wrap(c_enum = "nvmlDeviceVgpuCapability_enum")]
@@ -697,3 +721,30 @@ pub enum VgpuCapability {
     #[wrap(c_variant = "NVML_DEVICE_VGPU_CAP_WRITE_DEVICE_BUFFER_BW")]
     WriteDeviceBufferBw,
 }
+
+/// How a vGPU instance's VM is identified by `VgpuInstance.vm_id()`.
+#[derive(EnumWrapper, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[wrap(c_enum = "nvmlVgpuVmIdType")]
+pub enum VgpuVmIdType {
+    /// The VM's domain ID
+    #[wrap(c_variant = "NVML_VGPU_VM_ID_DOMAIN_ID")]
+    DomainId,
+    /// The VM's UUID
+    #[wrap(c_variant = "NVML_VGPU_VM_ID_UUID")]
+    Uuid,
+}
+
+/// Whether the guest driver of a vGPU has reported its info to the host.
+#[derive(EnumWrapper, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[wrap(c_enum = "nvmlVgpuGuestInfoState_enum")]
+pub enum VgpuGuestInfoState {
+    /// The guest driver isn't loaded or hasn't reported yet; the guest fields
+    /// of `VgpuMetadata` are not meaningful
+    #[wrap(c_variant = "NVML_VGPU_INSTANCE_GUEST_INFO_STATE_UNINITIALIZED")]
+    Uninitialized,
+    /// The guest driver has reported its info
+    #[wrap(c_variant = "NVML_VGPU_INSTANCE_GUEST_INFO_STATE_INITIALIZED")]
+    Initialized,
+}
@@ -1,6 +1,6 @@
 use std::convert::TryFrom;
 use std::fmt::Display;
-use std::os::raw::c_uint;
+use std::os::raw::{c_int, c_uint};
 
 use crate::enum_wrappers::device::{ClockLimitId, SampleValueType};
 use crate::error::NvmlError;
@@ -17,6 +17,16 @@ pub enum FirmwareVersion {
     Version(u32),
 }
 
+impl FirmwareVersion {
+    /// Obtain the C value this firmware version corresponds to.
+    pub fn as_c(&self) -> u32 {
+        match *self {
+            FirmwareVersion::Unavailable => 0,
+            FirmwareVersion::Version(value) => value,
+        }
+    }
+}
+
 impl From<u32> for FirmwareVersion {
     fn from(value: u32) -> Self {
         match value {
@@ -38,6 +48,16 @@ pub enum UsedGpuMemory {
     Used(u64),
 }
 
+impl UsedGpuMemory {
+    /// Obtain the C value this amount of memory corresponds to.
+    pub fn as_c(&self) -> u64 {
+        match *self {
+            UsedGpuMemory::Unavailable => (NVML_VALUE_NOT_AVAILABLE) as u64,
+            UsedGpuMemory::Used(value) => value,
+        }
+    }
+}
+
 impl From<u64> for UsedGpuMemory {
     fn from(value: u64) -> Self {
         let not_available = (NVML_VALUE_NOT_AVAILABLE) as u64;
@@ -77,6 +97,27 @@ impl SampleValue {
             }
         }
     }
+
+    /// The tag that goes with this value's union representation.
+    pub fn value_type(&self) -> SampleValueType {
+        match *self {
+            SampleValue::F64(_) => SampleValueType::Double,
+            SampleValue::U32(_) => SampleValueType::UnsignedInt,
+            SampleValue::U64(_) => SampleValueType::UnsignedLongLong,
+            SampleValue::I64(_) => SampleValueType::SignedLongLong,
+        }
+    }
+
+    /// Obtain the untagged union this value corresponds to; see
+    /// [`Self::value_type()`] for its tag.
+    pub fn as_c(&self) -> nvmlValue_t {
+        match *self {
+            SampleValue::F64(v) => nvmlValue_t { dVal: v },
+            SampleValue::U32(v) => nvmlValue_t { uiVal: v },
+            SampleValue::U64(v) => nvmlValue_t { ullVal: v },
+            SampleValue::I64(v) => nvmlValue_t { sllVal: v },
+        }
+    }
 }
 
 /// Represents different types of sample values.
@@ -427,3 +468,385 @@ impl TryFrom<nvmlFanControlPolicy_t> for FanControlPolicy {
         }
     }
 }
+
+/// A MIG compute instance profile: how many of its GPU instance's slices a
+/// compute instance takes up.
+///
+/// Used with [`crate::GpuInstance::compute_instance_profile_info()`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ComputeInstanceProfile {
+    OneSlice,
+    TwoSlices,
+    ThreeSlices,
+    FourSlices,
+    SixSlices,
+    SevenSlices,
+    EightSlices,
+    /// One slice, revision 1.
+    OneSliceRev1,
+}
+
+impl ComputeInstanceProfile {
+    /// Returns the C constant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> c_uint {
+        match *self {
+            Self::OneSlice => NVML_COMPUTE_INSTANCE_PROFILE_1_SLICE,
+            Self::TwoSlices => NVML_COMPUTE_INSTANCE_PROFILE_2_SLICE,
+            Self::ThreeSlices => NVML_COMPUTE_INSTANCE_PROFILE_3_SLICE,
+            Self::FourSlices => NVML_COMPUTE_INSTANCE_PROFILE_4_SLICE,
+            Self::SixSlices => NVML_COMPUTE_INSTANCE_PROFILE_6_SLICE,
+            Self::SevenSlices => NVML_COMPUTE_INSTANCE_PROFILE_7_SLICE,
+            Self::EightSlices => NVML_COMPUTE_INSTANCE_PROFILE_8_SLICE,
+            Self::OneSliceRev1 => NVML_COMPUTE_INSTANCE_PROFILE_1_SLICE_REV1,
+        }
+    }
+}
+
+impl TryFrom<c_uint> for ComputeInstanceProfile {
+    type Error = NvmlError;
+
+    fn try_from(data: c_uint) -> Result<Self, Self::Error> {
+        match data {
+            NVML_COMPUTE_INSTANCE_PROFILE_1_SLICE => Ok(Self::OneSlice),
+            NVML_COMPUTE_INSTANCE_PROFILE_2_SLICE => Ok(Self::TwoSlices),
+            NVML_COMPUTE_INSTANCE_PROFILE_3_SLICE => Ok(Self::ThreeSlices),
+            NVML_COMPUTE_INSTANCE_PROFILE_4_SLICE => Ok(Self::FourSlices),
+            NVML_COMPUTE_INSTANCE_PROFILE_6_SLICE => Ok(Self::SixSlices),
+            NVML_COMPUTE_INSTANCE_PROFILE_7_SLICE => Ok(Self::SevenSlices),
+            NVML_COMPUTE_INSTANCE_PROFILE_8_SLICE => Ok(Self::EightSlices),
+            NVML_COMPUTE_INSTANCE_PROFILE_1_SLICE_REV1 => Ok(Self::OneSliceRev1),
+            _ => Err(NvmlError::UnexpectedVariant(data)),
+        }
+    }
+}
+
+/// How a MIG compute instance shares its GPU instance's engines (copy
+/// engines, decoders, encoders, ...) with the other compute instances.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ComputeInstanceEngineProfile {
+    /// The engines are shared between all compute instances.
+    Shared,
+}
+
+impl ComputeInstanceEngineProfile {
+    /// Returns the C constant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> c_uint {
+        match *self {
+            Self::Shared => NVML_COMPUTE_INSTANCE_ENGINE_PROFILE_SHARED,
+        }
+    }
+}
+
+impl TryFrom<c_uint> for ComputeInstanceEngineProfile {
+    type Error = NvmlError;
+
+    fn try_from(data: c_uint) -> Result<Self, Self::Error> {
+        match data {
+            NVML_COMPUTE_INSTANCE_ENGINE_PROFILE_SHARED => Ok(Self::Shared),
+            _ => Err(NvmlError::UnexpectedVariant(data)),
+        }
+    }
+}
+
+/// Which vGPU placements to list for a vGPU type: those for a physical GPU
+/// running vGPUs of different types side by side, or of a single type.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VgpuPlacementMode {
+    /// vGPUs of different types share the physical GPU.
+    Heterogeneous,
+    /// All vGPUs on the physical GPU are of the same type.
+    Homogeneous,
+}
+
+impl VgpuPlacementMode {
+    /// Returns the C constant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> c_uint {
+        match *self {
+            Self::Heterogeneous => NVML_VGPU_PGPU_HETEROGENEOUS_MODE,
+            Self::Homogeneous => NVML_VGPU_PGPU_HOMOGENEOUS_MODE,
+        }
+    }
+}
+
+impl TryFrom<c_uint> for VgpuPlacementMode {
+    type Error = NvmlError;
+
+    fn try_from(data: c_uint) -> Result<Self, Self::Error> {
+        match data {
+            NVML_VGPU_PGPU_HETEROGENEOUS_MODE => Ok(Self::Heterogeneous),
+            NVML_VGPU_PGPU_HOMOGENEOUS_MODE => Ok(Self::Homogeneous),
+            _ => Err(NvmlError::UnexpectedVariant(data)),
+        }
+    }
+}
+
+/// How the vGPU scheduler shares a physical GPU between its vGPUs.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VgpuSchedulerPolicy {
+    /// The policy couldn't be determined.
+    Unknown,
+    /// vGPUs with work to do are time-sliced round robin.
+    BestEffort,
+    /// Each running vGPU gets an equal share of time.
+    EqualShare,
+    /// Each vGPU gets a fixed share of time, set by its type.
+    FixedShare,
+}
+
+impl VgpuSchedulerPolicy {
+    /// Returns the C constant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> c_uint {
+        match *self {
+            Self::Unknown => NVML_VGPU_SCHEDULER_POLICY_UNKNOWN,
+            Self::BestEffort => NVML_VGPU_SCHEDULER_POLICY_BEST_EFFORT,
+            Self::EqualShare => NVML_VGPU_SCHEDULER_POLICY_EQUAL_SHARE,
+            Self::FixedShare => NVML_VGPU_SCHEDULER_POLICY_FIXED_SHARE,
+        }
+    }
+}
+
+impl TryFrom<c_uint> for VgpuSchedulerPolicy {
+    type Error = NvmlError;
+
+    fn try_from(data: c_uint) -> Result<Self, Self::Error> {
+        match data {
+            NVML_VGPU_SCHEDULER_POLICY_UNKNOWN => Ok(Self::Unknown),
+            NVML_VGPU_SCHEDULER_POLICY_BEST_EFFORT => Ok(Self::BestEffort),
+            NVML_VGPU_SCHEDULER_POLICY_EQUAL_SHARE => Ok(Self::EqualShare),
+            NVML_VGPU_SCHEDULER_POLICY_FIXED_SHARE => Ok(Self::FixedShare),
+            _ => Err(NvmlError::UnexpectedVariant(data)),
+        }
+    }
+}
+
+/// Whether the vGPU scheduler uses adaptive round robin (ARR), adapting
+/// timeslices to the vGPUs' recent usage.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VgpuSchedulerArrMode {
+    /// The driver's default.
+    Default,
+    Disabled,
+    Enabled,
+}
+
+impl VgpuSchedulerArrMode {
+    /// Returns the C constant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> c_uint {
+        match *self {
+            Self::Default => NVML_VGPU_SCHEDULER_ARR_DEFAULT,
+            Self::Disabled => NVML_VGPU_SCHEDULER_ARR_DISABLE,
+            Self::Enabled => NVML_VGPU_SCHEDULER_ARR_ENABLE,
+        }
+    }
+}
+
+impl TryFrom<c_uint> for VgpuSchedulerArrMode {
+    type Error = NvmlError;
+
+    fn try_from(data: c_uint) -> Result<Self, Self::Error> {
+        match data {
+            NVML_VGPU_SCHEDULER_ARR_DEFAULT => Ok(Self::Default),
+            NVML_VGPU_SCHEDULER_ARR_DISABLE => Ok(Self::Disabled),
+            NVML_VGPU_SCHEDULER_ARR_ENABLE => Ok(Self::Enabled),
+            _ => Err(NvmlError::UnexpectedVariant(data)),
+        }
+    }
+}
+
+/// What a thermal sensor measures the temperature of.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ThermalTarget {
+    /// No target.
+    None,
+    /// The GPU core.
+    Gpu,
+    /// The GPU's memory (e.g. HBM).
+    Memory,
+    /// The power supply.
+    PowerSupply,
+    /// The board.
+    Board,
+    /// The visual computing device's board.
+    VcdBoard,
+    /// The visual computing device's inlet.
+    VcdInlet,
+    /// The visual computing device's outlet.
+    VcdOutlet,
+    /// All targets.
+    All,
+    /// The target couldn't be determined.
+    Unknown,
+}
+
+impl ThermalTarget {
+    /// Returns the C constant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> c_int {
+        match *self {
+            Self::None => nvmlThermalTarget_t_NVML_THERMAL_TARGET_NONE,
+            Self::Gpu => nvmlThermalTarget_t_NVML_THERMAL_TARGET_GPU,
+            Self::Memory => nvmlThermalTarget_t_NVML_THERMAL_TARGET_MEMORY,
+            Self::PowerSupply => nvmlThermalTarget_t_NVML_THERMAL_TARGET_POWER_SUPPLY,
+            Self::Board => nvmlThermalTarget_t_NVML_THERMAL_TARGET_BOARD,
+            Self::VcdBoard => nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_BOARD,
+            Self::VcdInlet => nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_INLET,
+            Self::VcdOutlet => nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_OUTLET,
+            Self::All => nvmlThermalTarget_t_NVML_THERMAL_TARGET_ALL,
+            Self::Unknown => nvmlThermalTarget_t_NVML_THERMAL_TARGET_UNKNOWN,
+        }
+    }
+}
+
+impl TryFrom<c_int> for ThermalTarget {
+    type Error = NvmlError;
+
+    fn try_from(data: c_int) -> Result<Self, Self::Error> {
+        match data {
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_NONE => Ok(Self::None),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_GPU => Ok(Self::Gpu),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_MEMORY => Ok(Self::Memory),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_POWER_SUPPLY => Ok(Self::PowerSupply),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_BOARD => Ok(Self::Board),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_BOARD => Ok(Self::VcdBoard),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_INLET => Ok(Self::VcdInlet),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_OUTLET => Ok(Self::VcdOutlet),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_ALL => Ok(Self::All),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_UNKNOWN => Ok(Self::Unknown),
+            _ => Err(NvmlError::UnexpectedVariant(data as u32)),
+        }
+    }
+}
+
+/// The chip reading a thermal sensor.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ThermalController {
+    /// No controller.
+    None,
+    /// The GPU's internal sensor.
+    GpuInternal,
+    Adm1032,
+    Adt7461,
+    Max6649,
+    Max1617,
+    Lm99,
+    Lm89,
+    Lm64,
+    G781,
+    Adt7473,
+    SbMax6649,
+    /// Reported by the VBIOS.
+    VbiosEvt,
+    /// Reported by the OS.
+    Os,
+    NvSysConCanoas,
+    NvSysConE551,
+    Max6649R,
+    Adt7473S,
+    /// The controller couldn't be determined.
+    Unknown,
+}
+
+impl ThermalController {
+    /// Returns the C constant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> c_int {
+        match *self {
+            Self::None => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NONE,
+            Self::GpuInternal => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_GPU_INTERNAL,
+            Self::Adm1032 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADM1032,
+            Self::Adt7461 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7461,
+            Self::Max6649 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX6649,
+            Self::Max1617 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX1617,
+            Self::Lm99 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM99,
+            Self::Lm89 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM89,
+            Self::Lm64 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM64,
+            Self::G781 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_G781,
+            Self::Adt7473 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7473,
+            Self::SbMax6649 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_SBMAX6649,
+            Self::VbiosEvt => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_VBIOSEVT,
+            Self::Os => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_OS,
+            Self::NvSysConCanoas => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NVSYSCON_CANOAS,
+            Self::NvSysConE551 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NVSYSCON_E551,
+            Self::Max6649R => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX6649R,
+            Self::Adt7473S => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7473S,
+            Self::Unknown => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_UNKNOWN,
+        }
+    }
+}
+
+impl TryFrom<c_int> for ThermalController {
+    type Error = NvmlError;
+
+    fn try_from(data: c_int) -> Result<Self, Self::Error> {
+        match data {
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NONE => Ok(Self::None),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_GPU_INTERNAL => Ok(Self::GpuInternal),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADM1032 => Ok(Self::Adm1032),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7461 => Ok(Self::Adt7461),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX6649 => Ok(Self::Max6649),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX1617 => Ok(Self::Max1617),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM99 => Ok(Self::Lm99),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM89 => Ok(Self::Lm89),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM64 => Ok(Self::Lm64),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_G781 => Ok(Self::G781),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7473 => Ok(Self::Adt7473),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_SBMAX6649 => Ok(Self::SbMax6649),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_VBIOSEVT => Ok(Self::VbiosEvt),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_OS => Ok(Self::Os),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NVSYSCON_CANOAS => {
+                Ok(Self::NvSysConCanoas)
+            }
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NVSYSCON_E551 => Ok(Self::NvSysConE551),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX6649R => Ok(Self::Max6649R),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7473S => Ok(Self::Adt7473S),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_UNKNOWN => Ok(Self::Unknown),
+            _ => Err(NvmlError::UnexpectedVariant(data as u32)),
+        }
+    }
+}
+
+// `NVML_PROCESS_MODE_*`, which are missing from the bindings
+const NVML_PROCESS_MODE_COMPUTE: c_uint = 0;
+const NVML_PROCESS_MODE_GRAPHICS: c_uint = 1;
+const NVML_PROCESS_MODE_MPS: c_uint = 2;
+
+/// Which processes to list with `Device.running_process_detail_list()`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ProcessMode {
+    /// Processes with a compute context (such as a CUDA application).
+    Compute,
+    /// Processes with a graphics context (OpenGL, DirectX).
+    Graphics,
+    /// Processes running as MPS clients.
+    Mps,
+}
+
+impl ProcessMode {
+    /// Returns the C constant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> c_uint {
+        match *self {
+            Self::Compute => NVML_PROCESS_MODE_COMPUTE,
+            Self::Graphics => NVML_PROCESS_MODE_GRAPHICS,
+            Self::Mps => NVML_PROCESS_MODE_MPS,
+        }
+    }
+}
+
+impl TryFrom<c_uint> for ProcessMode {
+    type Error = NvmlError;
+
+    fn try_from(data: c_uint) -> Result<Self, Self::Error> {
+        match data {
+            NVML_PROCESS_MODE_COMPUTE => Ok(Self::Compute),
+            NVML_PROCESS_MODE_GRAPHICS => Ok(Self::Graphics),
+            NVML_PROCESS_MODE_MPS => Ok(Self::Mps),
+            _ => Err(NvmlError::UnexpectedVariant(data)),
+        }
+    }
+}
@@ -1,6 +1,14 @@
+// `std::ffi::NulError` has been reachable at that path since 1.0; clippy trips
+// over its 1.64 re-export from `alloc`
+#![allow(clippy::incompatible_msrv)]
+
+use crate::context;
 use crate::ffi::bindings::*;
+use crate::Nvml;
 #[cfg(feature = "serde")]
 use serde_derive::{Deserialize, Serialize};
+use std::fmt;
+use std::mem;
 use thiserror::Error;
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -77,6 +85,28 @@ pub enum NvmlError {
     #[error("a call to `PciInfo.try_into_c()` failed")]
     PciInfoToCFailed,
 
+    /**
+    A call recording could not be read or written.
+
+    This error is specific to this Rust wrapper. It is returned when setting up
+    recording or replay (see `NvmlBuilder::record_to()` and
+    `NvmlBuilder::replay_from()`) fails, and by `Nvml.shutdown()` if writing
+    to a recording failed at some point.
+    */
+    #[error("call recording error: {0}")]
+    Recording(String),
+
+    /**
+    A vGPU scheduler state is not supported by the device.
+
+    This error is specific to this Rust wrapper. It is returned by
+    `VgpuSchedulerCapabilities.validate()`, which the vGPU scheduler state
+    setters call before passing the state on to NVML. The field describes the
+    unsupported policy or out-of-range parameter.
+    */
+    #[error("unsupported vGPU scheduler state: {0}")]
+    UnsupportedVgpuSchedulerState(String),
+
     #[error("NVML was not first initialized with `Nvml::init()`")]
     Uninitialized,
 
@@ -161,6 +191,9 @@ pub enum NvmlError {
     )]
     VgpuEccNotSupported,
 
+    #[error("not enough resources are available to perform the operation")]
+    InsufficientResources,
+
     #[error("an internal driver error occurred")]
     Unknown,
 }
@@ -184,6 +217,16 @@ pub fn nvml_try_count(code: nvmlReturn_t) -> Result<(), NvmlError> {
     Err(code.into())
 }
 
+/// `None` for something the device or driver doesn't report, rather than an
+/// error.
+pub(crate) fn optional<T>(result: Result<T, NvmlError>) -> Result<Option<T>, NvmlError> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(NvmlError::NotSupported) | Err(NvmlError::FailedToLoadSymbol(_)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
 #[allow(deprecated)]
 impl From<nvmlReturn_t> for NvmlError {
     fn from(value: nvmlReturn_t) -> Self {
@@ -211,6 +254,7 @@ impl From<nvmlReturn_t> for NvmlError {
             nvmlReturn_enum_NVML_ERROR_MEMORY => InsufficientMemory,
             nvmlReturn_enum_NVML_ERROR_NO_DATA => NoData,
             nvmlReturn_enum_NVML_ERROR_VGPU_ECC_NOT_SUPPORTED => VgpuEccNotSupported,
+            nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_RESOURCES => InsufficientResources,
             nvmlReturn_enum_NVML_ERROR_UNKNOWN => Unknown,
             _ => UnexpectedVariant(value),
         }
@@ -245,6 +289,7 @@ impl From<NvmlError> for nvmlReturn_t {
             InsufficientMemory => nvmlReturn_enum_NVML_ERROR_MEMORY,
             NoData => nvmlReturn_enum_NVML_ERROR_NO_DATA,
             VgpuEccNotSupported => nvmlReturn_enum_NVML_ERROR_VGPU_ECC_NOT_SUPPORTED,
+            InsufficientResources => nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_RESOURCES,
             Unknown => nvmlReturn_enum_NVML_ERROR_UNKNOWN,
             UnexpectedVariant(code) => code,
             // For non-NVML errors, return UNKNOWN
@@ -256,7 +301,9 @@ impl From<NvmlError> for nvmlReturn_t {
             | IncorrectBits(_)
             | SetReleaseFailed
             | GetPciInfoFailed
-            | PciInfoToCFailed => nvmlReturn_enum_NVML_ERROR_UNKNOWN,
+            | PciInfoToCFailed
+            | Recording(_)
+            | UnsupportedVgpuSchedulerState(_) => nvmlReturn_enum_NVML_ERROR_UNKNOWN,
         }
     }
 }
@@ -265,3 +312,198 @@ impl From<NvmlError> for nvmlReturn_t {
 pub fn nvml_sym<'a, T>(sym: Result<&'a T, &libloading::Error>) -> Result<&'a T, NvmlError> {
     sym.map_err(|e| NvmlError::FailedToLoadSymbol(e.to_string()))
 }
+
+/**
+Where and how an NVML call failed.
+
+Recorded for failing calls made inside [`Nvml::with_error_context()`]. Only the
+function, its return code and the device handle it was given are recorded when
+the call fails; the description and device identity stay `None` until
+[`ErrorContext::resolve()`] queries them.
+
+[`Nvml::with_error_context()`]: crate::Nvml::with_error_context
+*/
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct ErrorContext {
+    /// The NVML function that failed, e.g. `nvmlDeviceGetTemperature`.
+    pub function: &'static str,
+    /// The raw return code.
+    pub code: nvmlReturn_t,
+    /// The driver's description of `code`, from `nvmlErrorString`.
+    pub description: Option<String>,
+    /// The device the call concerned, if any.
+    pub device: Option<DeviceIdentity>,
+    /// The raw handle of the device the call concerned, if any.
+    pub(crate) handle: Option<usize>,
+    /// Identifies the backend the call went to; see `context::backend_id()`.
+    pub(crate) backend: usize,
+}
+
+impl ErrorContext {
+    /// Whether the failing call concerned a device.
+    pub fn has_device(&self) -> bool {
+        self.handle.is_some()
+    }
+
+    /**
+    Query the description of the return code and what identifies the device
+    the call concerned.
+
+    This makes NVML calls on that device, so it's left to the caller: a device
+    that just failed may fail (or take a while) to answer them too. `nvml` must
+    be the instance the context was recorded by; for any other instance,
+    nothing is queried.
+    */
+    pub fn resolve(&mut self, nvml: &Nvml) {
+        let backend = nvml.backend();
+        if context::backend_id(backend) != self.backend {
+            return;
+        }
+
+        unsafe {
+            self.description = context::description(backend, self.code);
+            if let Some(handle) = self.handle {
+                self.device = Some(context::identify(backend, handle as nvmlDevice_t));
+            }
+        }
+    }
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` returned {}", self.function, self.code)?;
+        if let Some(ref description) = self.description {
+            write!(f, " ({:?})", description)?;
+        }
+        if let Some(ref device) = self.device {
+            write!(f, " for {}", device)?;
+        }
+
+        Ok(())
+    }
+}
+
+/**
+Identifies a device in an [`ErrorContext`].
+
+Each field is `None` if it couldn't be queried, which is likely for a device
+that fell off the bus.
+*/
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct DeviceIdentity {
+    pub index: Option<u32>,
+    pub uuid: Option<String>,
+    pub pci_bus_id: Option<String>,
+}
+
+impl fmt::Display for DeviceIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.index {
+            Some(index) => write!(f, "device {}", index)?,
+            None => f.write_str("device")?,
+        }
+
+        let ids: Vec<&str> = self
+            .uuid
+            .iter()
+            .chain(self.pci_bus_id.iter())
+            .map(String::as_str)
+            .collect();
+        if !ids.is_empty() {
+            write!(f, " ({})", ids.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+/**
+An `NvmlError` along with the context of the NVML call that caused it.
+
+Methods keep returning plain `NvmlError`s, so matching on variants works as
+before. To also get the context, make the calls inside
+[`Nvml::with_error_context()`], which returns this type:
+
+```
+# use nvml_wrapper::Nvml;
+# use nvml_wrapper::enum_wrappers::device::TemperatureSensor;
+use nvml_wrapper::error::NvmlError;
+
+# fn main() -> Result<(), NvmlError> {
+# let nvml = match Nvml::init() {
+#     Ok(nvml) => nvml,
+#     Err(_) => return Ok(()),
+# };
+let temperature = nvml.with_error_context(|nvml| {
+    nvml.device_by_index(0)?.temperature(TemperatureSensor::Gpu)
+});
+
+match temperature {
+    Ok(t) => println!("{} °C", t),
+    // e.g. "the requested operation is not available on the target device:
+    // `nvmlDeviceGetTemperature` returned 3 ("Not Supported") for device 0
+    // (GPU-..., 00000000:01:00.0)"
+    Err(e) => println!("{}", e.resolve(&nvml)),
+}
+# Ok(())
+# }
+```
+
+The context is `None` for errors that didn't come from an NVML return code,
+such as `Utf8Error`.
+
+[`Nvml::with_error_context()`]: crate::Nvml::with_error_context
+*/
+#[derive(Debug)]
+pub struct NvmlErrorWithContext {
+    pub error: NvmlError,
+    pub context: Option<Box<ErrorContext>>,
+}
+
+impl NvmlErrorWithContext {
+    /// Attach `context` to `error` if the call it describes is what caused it.
+    pub(crate) fn new(error: NvmlError, context: Option<ErrorContext>) -> Self {
+        let context = context
+            .filter(|context| {
+                mem::discriminant(&NvmlError::from(context.code)) == mem::discriminant(&error)
+            })
+            .map(Box::new);
+
+        Self { error, context }
+    }
+
+    /// Resolve the context's description and device identity; see
+    /// [`ErrorContext::resolve()`].
+    pub fn resolve(mut self, nvml: &Nvml) -> Self {
+        if let Some(ref mut context) = self.context {
+            context.resolve(nvml);
+        }
+
+        self
+    }
+}
+
+impl From<NvmlError> for NvmlErrorWithContext {
+    /// Wrap `error` without any context.
+    fn from(error: NvmlError) -> Self {
+        Self {
+            error,
+            context: None,
+        }
+    }
+}
+
+impl fmt::Display for NvmlErrorWithContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.context {
+            Some(ref context) => write!(f, "{}: {}", self.error, context),
+            None => write!(f, "{}", self.error),
+        }
+    }
+}
+
+impl std::error::Error for NvmlErrorWithContext {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.error.source()
+    }
+}
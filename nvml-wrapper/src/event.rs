@@ -1,4 +1,4 @@
-use crate::error::{nvml_sym, nvml_try, NvmlError};
+use crate::error::{nvml_try, NvmlError};
 use crate::ffi::bindings::*;
 use crate::Nvml;
 
@@ -72,10 +72,8 @@ impl<'nvml> EventSet<'nvml> {
     // Checked against local
     #[doc(alias = "nvmlEventSetFree")]
     pub fn release_events(self) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlEventSetFree.as_ref())?;
-
         unsafe {
-            nvml_try(sym(self.set))?;
+            nvml_try(self.nvml.backend.nvmlEventSetFree(self.set)?)?;
         }
 
         mem::forget(self);
@@ -112,11 +110,13 @@ impl<'nvml> EventSet<'nvml> {
     // Checked against local
     #[doc(alias = "nvmlEventSetWait_v2")]
     pub fn wait(&self, timeout_ms: u32) -> Result<EventData<'nvml>, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlEventSetWait_v2.as_ref())?;
-
         unsafe {
             let mut data: nvmlEventData_t = mem::zeroed();
-            nvml_try(sym(self.set, &mut data, timeout_ms))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlEventSetWait_v2(self.set, &mut data, timeout_ms)?,
+            )?;
 
             Ok(EventData::new(data, self.nvml))
         }
@@ -143,7 +143,7 @@ impl<'nvml> Drop for EventSet<'nvml> {
     #[doc(alias = "nvmlEventSetFree")]
     fn drop(&mut self) {
         unsafe {
-            self.nvml.lib.nvmlEventSetFree(self.set);
+            let _ = self.nvml.backend.nvmlEventSetFree(self.set);
         }
     }
 }
@@ -189,7 +189,7 @@ mod test {
             .expect("registration");
 
         let data = match set.wait(10_000) {
-            Err(NvmlError::Timeout) => return (),
+            Err(NvmlError::Timeout) => return,
             Ok(d) => d,
             _ => panic!("An error other than `Timeout` occurred"),
         };
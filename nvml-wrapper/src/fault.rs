@@ -0,0 +1,609 @@
+/*!
+Injecting failures into NVML calls, to exercise error handling that real
+hardware rarely (if ever) triggers.
+
+A [`FaultInjector`] wraps another backend and makes chosen functions return
+chosen codes instead of calling through. A [`Fault`] can fire on every call, on
+the Nth call only, with some probability, and/or only for calls concerning one
+device. The easiest way to set one up is through the builder:
+
+```no_run
+use nvml_wrapper::error::NvmlError;
+use nvml_wrapper::fault::{Fault, Faults};
+use nvml_wrapper::Nvml;
+
+# fn main() -> Result<(), NvmlError> {
+let faults = Faults::new()
+    // The second GPU falls off the bus
+    .with(Fault::error("nvmlDevice*", NvmlError::GpuLost).on_device(1))
+    // The third power reading times out
+    .with(Fault::error("nvmlDeviceGetPowerUsage", NvmlError::Timeout).on_call(3))
+    // About half of the temperature readings are refused
+    .with(Fault::error("nvmlDeviceGetTemperature", NvmlError::NoPermission).with_probability(0.5))
+    // Processes start between counting them and listing them
+    .with(Fault::grow("nvmlDeviceGetComputeRunningProcesses_v3", 8));
+
+let nvml = Nvml::builder().faults(faults).init()?;
+# Ok(())
+# }
+```
+
+Function names are NVML's (e.g. `nvmlDeviceGetPowerUsage`, not `power_usage`),
+and a name ending in `*` matches every function starting with what comes before
+it.
+
+# Growing lists
+
+Functions that fill in a caller-provided array are usually called twice: once
+to get the number of entries and once more with an array of that size. Entries
+can appear in between, in which case the second call fails with
+`NVML_ERROR_INSUFFICIENT_SIZE`. [`Fault::grow()`] simulates this: the call that
+fills in the array reports `extra` more entries than it really has, either by
+failing like NVML would if they don't fit in the array or by repeating the last
+entry if they do.
+
+# Determinism
+
+Probabilities are drawn from a pseudo-random generator seeded with
+[`Faults::seed()`] (0 by default), so a given program with a given set of
+faults makes the same calls fail every time it runs single-threaded.
+*/
+
+#![allow(non_snake_case)]
+// The safety contract is the one documented on `NvmlBackend`
+#![allow(clippy::missing_safety_doc)]
+
+use crate::args::{Kind, LenKind, RawArg, ARRAYS};
+use crate::backend::{nvml_functions, NvmlBackend};
+use crate::error::NvmlError;
+use crate::ffi::bindings::*;
+use std::collections::HashMap;
+use std::os::raw;
+use std::ptr;
+use std::sync::Mutex;
+
+/**
+A failure to inject into calls to an NVML function.
+
+Without any of the `on_*()` / `with_*()` restrictions, the fault fires on every
+call to the function.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fault {
+    function: String,
+    effect: Effect,
+    device: Option<u32>,
+    call: Option<u64>,
+    probability: Option<f64>,
+}
+
+// `probability` is checked to be in 0..=1 and so is never NaN
+impl Eq for Fault {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Effect {
+    Return(nvmlReturn_t),
+    Unavailable,
+    Grow(u32),
+}
+
+impl Fault {
+    fn new(function: &str, effect: Effect) -> Self {
+        Self {
+            function: function.into(),
+            effect,
+            device: None,
+            call: None,
+            probability: None,
+        }
+    }
+
+    /// Make calls to `function` return `code` without calling through.
+    pub fn code(function: &str, code: nvmlReturn_t) -> Self {
+        Self::new(function, Effect::Return(code))
+    }
+
+    /**
+    Make calls to `function` fail with `error` without calling through.
+
+    `NvmlError::FailedToLoadSymbol` makes the function behave as if it was
+    missing from the NVML library. Other errors that don't correspond to an NVML
+    return code (such as `NvmlError::Utf8Error`) are injected as
+    `NVML_ERROR_UNKNOWN`.
+    */
+    pub fn error(function: &str, error: NvmlError) -> Self {
+        match error {
+            NvmlError::FailedToLoadSymbol(_) => Self::new(function, Effect::Unavailable),
+            error => Self::code(function, error.into()),
+        }
+    }
+
+    /**
+    Make the array `function` fills in appear to have `extra` more entries than
+    it does.
+
+    Only applies to functions that take the array's length by pointer, like
+    `nvmlDeviceGetComputeRunningProcesses_v3`, and only to calls that pass an
+    array. See the [module-level documentation](self) for details.
+    */
+    pub fn grow(function: &str, extra: u32) -> Self {
+        Self::new(function, Effect::Grow(extra))
+    }
+
+    /**
+    Only fire for calls concerning the device at `index` (as returned by
+    `Device.index()`).
+
+    Calls that don't take a device handle, such as `nvmlDeviceGetHandleByIndex_v2`,
+    are left alone.
+    */
+    pub fn on_device(mut self, index: u32) -> Self {
+        self.device = Some(index);
+        self
+    }
+
+    /**
+    Only fire on the `n`th call (starting at 1) to a matching function.
+
+    Calls only count if they concern the device set with `on_device()`, if any.
+    */
+    pub fn on_call(mut self, n: u64) -> Self {
+        self.call = Some(n);
+        self
+    }
+
+    /**
+    Only fire with the given probability.
+
+    # Panics
+
+    Panics if `probability` is not between 0 and 1 (inclusive).
+    */
+    pub fn with_probability(mut self, probability: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&probability),
+            "probability must be between 0 and 1, got {}",
+            probability
+        );
+
+        self.probability = Some(probability);
+        self
+    }
+
+    fn matches(&self, function: &str) -> bool {
+        match self.function.strip_suffix('*') {
+            Some(prefix) => function.starts_with(prefix),
+            None => function == self.function,
+        }
+    }
+}
+
+/**
+A set of faults to inject.
+
+When several faults fire for the same call, the one added first wins.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Faults {
+    faults: Vec<Fault>,
+    seed: u64,
+}
+
+impl Faults {
+    /// An empty set of faults, with a seed of 0.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a fault.
+    pub fn with(mut self, fault: Fault) -> Self {
+        self.faults.push(fault);
+        self
+    }
+
+    /// Seed the generator that probabilities are drawn from.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+/**
+A backend that injects [`Faults`] into calls to another backend.
+
+See the [module-level documentation](self) for details.
+*/
+pub struct FaultInjector<B> {
+    inner: B,
+    faults: Vec<Fault>,
+    state: Mutex<State>,
+}
+
+struct State {
+    /// Matching calls so far, per fault.
+    calls: Vec<u64>,
+    rng: u64,
+    /// The indices of the device handles seen so far.
+    devices: HashMap<usize, Option<u32>>,
+}
+
+impl State {
+    /// A pseudo-random number in `0..1` (SplitMix64).
+    fn random(&mut self) -> f64 {
+        self.rng = self.rng.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.rng;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+impl<B: NvmlBackend> FaultInjector<B> {
+    /// Inject `faults` into calls to `inner`.
+    pub fn new(inner: B, faults: Faults) -> Self {
+        let state = State {
+            calls: vec![0; faults.faults.len()],
+            rng: faults.seed,
+            devices: HashMap::new(),
+        };
+
+        Self {
+            inner,
+            faults: faults.faults,
+            state: Mutex::new(state),
+        }
+    }
+
+    /// The backend calls are passed on to.
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    /// The index of the device `device` refers to, if the inner backend
+    /// knows.
+    unsafe fn index_of(&self, state: &mut State, device: nvmlDevice_t) -> Option<u32> {
+        *state.devices.entry(device as usize).or_insert_with(|| {
+            let mut index = 0;
+            match self.inner.nvmlDeviceGetIndex(device, &mut index) {
+                Ok(nvmlReturn_enum_NVML_SUCCESS) => Some(index),
+                _ => None,
+            }
+        })
+    }
+
+    /// The effect of the first fault that fires for this call, if any.
+    unsafe fn fire(&self, function: &str, kinds: &[Kind]) -> Option<Effect> {
+        let device = kinds.iter().find_map(|kind| match kind {
+            Kind::Handle(p) if p.is::<nvmlDevice_st>() => Some(p.addr as nvmlDevice_t),
+            _ => None,
+        });
+
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let mut effect = None;
+
+        for (i, fault) in self.faults.iter().enumerate() {
+            if !fault.matches(function) {
+                continue;
+            }
+            if let Some(index) = fault.device {
+                let on_device = match device {
+                    Some(device) => self.index_of(&mut state, device) == Some(index),
+                    None => false,
+                };
+                if !on_device {
+                    continue;
+                }
+            }
+
+            // Every matching call counts, even if an earlier fault fires
+            state.calls[i] += 1;
+            if effect.is_some() || fault.call.map_or(false, |n| state.calls[i] != n) {
+                continue;
+            }
+            if let Some(probability) = fault.probability {
+                if state.random() >= probability {
+                    continue;
+                }
+            }
+
+            effect = Some(fault.effect);
+        }
+
+        effect
+    }
+
+    unsafe fn inject<F>(
+        &self,
+        function: &str,
+        names: &[&str],
+        args: &[&dyn RawArg],
+        call: F,
+    ) -> Result<nvmlReturn_t, NvmlError>
+    where
+        F: FnOnce() -> Result<nvmlReturn_t, NvmlError>,
+    {
+        let kinds: Vec<_> = args.iter().map(|a| a.kind()).collect();
+
+        match self.fire(function, &kinds) {
+            None => call(),
+            Some(Effect::Return(code)) => Ok(code),
+            Some(Effect::Unavailable) => Err(NvmlError::FailedToLoadSymbol(format!(
+                "{} was made unavailable by fault injection",
+                function
+            ))),
+            Some(Effect::Grow(extra)) => grow(function, names, &kinds, extra, call),
+        }
+    }
+}
+
+/// Make the arrays filled in by a call appear to have `extra` more entries.
+unsafe fn grow<F>(
+    function: &str,
+    names: &[&str],
+    kinds: &[Kind],
+    extra: u32,
+    call: F,
+) -> Result<nvmlReturn_t, NvmlError>
+where
+    F: FnOnce() -> Result<nvmlReturn_t, NvmlError>,
+{
+    let arg = |name: &str| names.iter().position(|n| *n == name).map(|i| kinds[i]);
+
+    let mut count = None;
+    let mut arrays = Vec::new();
+    for (_, array, len, _) in ARRAYS
+        .iter()
+        .filter(|(f, _, _, len_kind)| *f == function && *len_kind == LenKind::Ptr)
+    {
+        match (arg(array), arg(len)) {
+            (Some(Kind::Mut(array)), Some(len)) if !array.addr.is_null() => {
+                if let (Some(capacity), Some(p)) = (len.count(), len.pointer()) {
+                    count = Some((p.addr as *mut raw::c_uint, capacity));
+                    arrays.push(array);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let (count, capacity) = match count {
+        Some(count) => count,
+        None => return call(),
+    };
+
+    let ret = call()?;
+    if ret != nvmlReturn_enum_NVML_SUCCESS {
+        return Ok(ret);
+    }
+
+    let len = *count;
+    let grown = len.saturating_add(extra);
+    if grown as usize > capacity {
+        *count = grown;
+        return Ok(nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE);
+    }
+
+    // Fill the new entries in with copies of the last one
+    for array in arrays {
+        for i in len as usize..grown as usize {
+            let entry = array.addr.add(i * array.size);
+            match (len as usize).checked_sub(1) {
+                Some(last) => {
+                    ptr::copy_nonoverlapping(array.addr.add(last * array.size), entry, array.size)
+                }
+                None => ptr::write_bytes(entry, 0, array.size),
+            }
+        }
+    }
+
+    *count = grown;
+    Ok(nvmlReturn_enum_NVML_SUCCESS)
+}
+
+macro_rules! fault_injector {
+    ($(
+        $(#[$attr:meta])*
+        fn $name:ident($($arg:ident: $ty:ty),* $(,)?);
+    )*) => {
+        impl<B: NvmlBackend> NvmlBackend for FaultInjector<B> {
+            fn lib(&self) -> Option<&NvmlLib> {
+                self.inner.lib()
+            }
+
+            fn close(self: Box<Self>) -> Result<(), NvmlError> {
+                Box::new(self.inner).close()
+            }
+
+            unsafe fn nvmlErrorString(
+                &self,
+                result: nvmlReturn_t,
+            ) -> Result<*const raw::c_char, NvmlError> {
+                self.inner.nvmlErrorString(result)
+            }
+
+            $(
+                $(#[$attr])*
+                unsafe fn $name(&self, $($arg: $ty),*) -> Result<nvmlReturn_t, NvmlError> {
+                    self.inject(
+                        stringify!($name),
+                        &[$(stringify!($arg)),*],
+                        &[$(&$arg),*],
+                        || self.inner.$name($($arg),*),
+                    )
+                }
+            )*
+        }
+    };
+}
+
+nvml_functions!(fault_injector);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::enum_wrappers::device::TemperatureSensor;
+    use crate::test_utils::mock_backend;
+    use crate::Nvml;
+
+    /// Two GPUs at 40°C and 41°C, each running one compute process.
+    struct Mock;
+
+    impl NvmlBackend for Mock {
+        mock_backend!(devices = 2);
+
+        unsafe fn nvmlDeviceGetIndex(
+            &self,
+            device: nvmlDevice_t,
+            index: *mut raw::c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *index = device as raw::c_uint - 1;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetTemperature(
+            &self,
+            device: nvmlDevice_t,
+            _sensor: nvmlTemperatureSensors_t,
+            temp: *mut raw::c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *temp = 39 + device as raw::c_uint;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetComputeRunningProcesses_v3(
+            &self,
+            _device: nvmlDevice_t,
+            count: *mut raw::c_uint,
+            infos: *mut nvmlProcessInfo_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            if *count < 1 {
+                *count = 1;
+                return Ok(nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE);
+            }
+
+            *infos = nvmlProcessInfo_t {
+                pid: 42,
+                usedGpuMemory: 1 << 20,
+                gpuInstanceId: raw::c_uint::MAX,
+                computeInstanceId: raw::c_uint::MAX,
+            };
+            *count = 1;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+    }
+
+    fn nvml(faults: Faults) -> Nvml {
+        Nvml::builder()
+            .faults(faults)
+            .init_with_backend(Mock)
+            .expect("initialized mock")
+    }
+
+    fn temperature(nvml: &Nvml, index: u32) -> Result<u32, NvmlError> {
+        nvml.device_by_index(index)?
+            .temperature(TemperatureSensor::Gpu)
+    }
+
+    #[test]
+    fn always() {
+        let nvml =
+            nvml(Faults::new().with(Fault::error("nvmlDeviceGetTemperature", NvmlError::GpuLost)));
+
+        for _ in 0..3 {
+            assert!(matches!(temperature(&nvml, 0), Err(NvmlError::GpuLost)));
+        }
+    }
+
+    #[test]
+    fn nth_call() {
+        let nvml = nvml(
+            Faults::new()
+                .with(Fault::error("nvmlDeviceGetTemperature", NvmlError::Timeout).on_call(2)),
+        );
+
+        assert_eq!(temperature(&nvml, 0).unwrap(), 40);
+        assert!(matches!(temperature(&nvml, 0), Err(NvmlError::Timeout)));
+        assert_eq!(temperature(&nvml, 0).unwrap(), 40);
+    }
+
+    #[test]
+    fn per_device_and_prefix() {
+        let nvml = nvml(
+            Faults::new().with(Fault::error("nvmlDevice*", NvmlError::ResetRequired).on_device(1)),
+        );
+
+        assert_eq!(temperature(&nvml, 0).unwrap(), 40);
+        assert!(matches!(
+            temperature(&nvml, 1),
+            Err(NvmlError::ResetRequired)
+        ));
+    }
+
+    #[test]
+    fn probability() {
+        let failures = |probability, seed| {
+            let nvml = nvml(
+                Faults::new()
+                    .with(
+                        Fault::error("nvmlDeviceGetTemperature", NvmlError::NoPermission)
+                            .with_probability(probability),
+                    )
+                    .seed(seed),
+            );
+
+            (0..1000).filter(|_| temperature(&nvml, 0).is_err()).count()
+        };
+
+        assert_eq!(failures(0.0, 0), 0);
+        assert_eq!(failures(1.0, 0), 1000);
+        assert!((400..600).contains(&failures(0.5, 0)));
+        assert_eq!(failures(0.5, 7), failures(0.5, 7));
+    }
+
+    #[test]
+    fn unavailable() {
+        let nvml = nvml(Faults::new().with(Fault::error(
+            "nvmlDeviceGetTemperature",
+            NvmlError::FailedToLoadSymbol(String::new()),
+        )));
+
+        assert!(matches!(
+            temperature(&nvml, 0),
+            Err(NvmlError::FailedToLoadSymbol(_))
+        ));
+    }
+
+    #[test]
+    fn grow_within_capacity() {
+        let nvml =
+            nvml(Faults::new().with(Fault::grow("nvmlDeviceGetComputeRunningProcesses_v3", 2)));
+        let device = nvml.device_by_index(0).unwrap();
+
+        // Counting isn't affected, only filling in
+        assert_eq!(device.running_compute_processes_count().unwrap(), 1);
+
+        let processes = device.running_compute_processes().unwrap();
+        assert_eq!(processes.len(), 3);
+        assert!(processes.iter().all(|p| p.pid == 42));
+    }
+
+    #[test]
+    fn grow_beyond_capacity() {
+        let nvml =
+            nvml(Faults::new().with(Fault::grow("nvmlDeviceGetComputeRunningProcesses_v3", 100)));
+
+        assert!(matches!(
+            nvml.device_by_index(0).unwrap().running_compute_processes(),
+            Err(NvmlError::InsufficientSize(_))
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "probability must be between 0 and 1")]
+    fn invalid_probability() {
+        Fault::code("nvmlDeviceGetTemperature", 0).with_probability(1.5);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::enums::gpm::GpmMetricId;
-use crate::error::{nvml_sym, nvml_try, NvmlError};
+use crate::error::{nvml_try, NvmlError};
 use crate::ffi::bindings::*;
 use crate::struct_wrappers::gpm::GpmMetricResult;
 use crate::Nvml;
@@ -40,11 +40,9 @@ impl<'nvml> GpmSample<'nvml> {
     /// * `Unknown`, on any unexpected error
     #[doc(alias = "nvmlGpmSampleAlloc")]
     pub(crate) fn alloc(nvml: &'nvml Nvml) -> Result<Self, NvmlError> {
-        let sym = nvml_sym(nvml.lib.nvmlGpmSampleAlloc.as_ref())?;
-
         unsafe {
             let mut sample: nvmlGpmSample_t = mem::zeroed();
-            nvml_try(sym(&mut sample))?;
+            nvml_try(nvml.backend.nvmlGpmSampleAlloc(&mut sample)?)?;
 
             Ok(Self { sample, nvml })
         }
@@ -80,10 +78,8 @@ impl<'nvml> GpmSample<'nvml> {
     */
     #[doc(alias = "nvmlGpmSampleFree")]
     pub fn free(self) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlGpmSampleFree.as_ref())?;
-
         unsafe {
-            nvml_try(sym(self.sample))?;
+            nvml_try(self.nvml.backend.nvmlGpmSampleFree(self.sample)?)?;
         }
 
         mem::forget(self);
@@ -132,7 +128,7 @@ impl<'nvml> Drop for GpmSample<'nvml> {
     #[doc(alias = "nvmlGpmSampleFree")]
     fn drop(&mut self) {
         unsafe {
-            self.nvml.lib.nvmlGpmSampleFree(self.sample);
+            let _ = self.nvml.backend.nvmlGpmSampleFree(self.sample);
         }
     }
 }
@@ -176,8 +172,6 @@ pub fn gpm_metrics_get<'nvml>(
         nvmlGpmMetricId_t_NVML_GPM_METRIC_MAX
     );
 
-    let sym = nvml_sym(nvml.lib.nvmlGpmMetricsGet.as_ref())?;
-
     unsafe {
         let mut request: nvmlGpmMetricsGet_t = mem::zeroed();
         request.version = NVML_GPM_METRICS_GET_VERSION;
@@ -189,7 +183,7 @@ pub fn gpm_metrics_get<'nvml>(
             request.metrics[i].metricId = id.as_c();
         }
 
-        nvml_try(sym(&mut request))?;
+        nvml_try(nvml.backend.nvmlGpmMetricsGet(&mut request)?)?;
 
         let results = (0..metric_ids.len())
             .map(|i| GpmMetricResult::try_from_c(&request.metrics[i]))
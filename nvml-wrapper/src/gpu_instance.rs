@@ -0,0 +1,1367 @@
+use crate::enum_wrappers::{bool_from_state, state_from_bool};
+use crate::enums::device::{ComputeInstanceEngineProfile, ComputeInstanceProfile};
+use crate::error::{nvml_try, nvml_try_count, optional, NvmlError};
+use crate::ffi::bindings::*;
+use crate::struct_wrappers::device::{
+    active_vgpu_instance_info_version, compute_instance_profile_info_version,
+    vgpu_creatable_placement_info_version, vgpu_heterogeneous_mode_version,
+    vgpu_scheduler_log_info_version, vgpu_scheduler_state_info_version,
+    vgpu_scheduler_state_version, vgpu_type_id_info_version, ComputeInstancePlacement,
+    ComputeInstanceProfileInfo, GpuInstanceInfo, VgpuSchedulerGetState, VgpuSchedulerLog,
+    VgpuSchedulerSetState,
+};
+use crate::structs::device::VgpuPlacementList;
+use crate::{ComputeInstance, Device, Nvml};
+
+use std::{convert::TryFrom, mem, os::raw::c_uint, ptr};
+
+use static_assertions::assert_impl_all;
+
+/**
+A MIG GPU instance: a partition of a `Device`'s memory slices and engines.
+
+Obtain one by creating it with `Device.create_gpu_instance()` (or
+`create_gpu_instance_with_placement()`), or look up existing ones with
+`Device.gpu_instances()` and `Device.gpu_instance_by_id()`. The device must
+have MIG mode enabled.
+
+GPU instances are part of the device's configuration, not of this process:
+dropping a `GpuInstance` leaves the instance in place, and it stays until
+[`destroy()`](Self::destroy) is called (from this or any other process) or MIG
+mode is disabled.
+
+Lifetimes are used to enforce that each `GpuInstance` instance cannot be used
+after the `Nvml` instance it was obtained from is dropped:
+
+```compile_fail
+use nvml_wrapper::Nvml;
+# use nvml_wrapper::error::*;
+
+# fn main() -> Result<(), NvmlError> {
+let nvml = Nvml::init()?;
+let instance = nvml.device_by_index(0)?.gpu_instance_by_id(1)?;
+
+drop(nvml);
+
+// This won't compile
+instance.info()?;
+# Ok(())
+# }
+```
+*/
+#[derive(Debug)]
+pub struct GpuInstance<'nvml> {
+    instance: nvmlGpuInstance_t,
+    device: nvmlDevice_t,
+    nvml: &'nvml Nvml,
+}
+
+unsafe impl Send for GpuInstance<'_> {}
+unsafe impl Sync for GpuInstance<'_> {}
+
+assert_impl_all!(GpuInstance: Send, Sync);
+
+impl<'nvml> GpuInstance<'nvml> {
+    /**
+    Create a new `GpuInstance` wrapper.
+
+    You will most likely never need to call this; see the methods available to
+    you on `Device` to get one.
+
+    # Safety
+
+    It is your responsibility to ensure that the given `nvmlGpuInstance_t`
+    pointer is valid and belongs to `device`.
+    */
+    pub unsafe fn new(instance: nvmlGpuInstance_t, device: &Device<'nvml>) -> Self {
+        Self {
+            instance,
+            device: device.handle(),
+            nvml: device.nvml(),
+        }
+    }
+
+    /// Access the `Nvml` reference this struct wraps
+    pub fn nvml(&self) -> &'nvml Nvml {
+        self.nvml
+    }
+
+    /// The `Device` this GPU instance is a partition of.
+    pub fn device(&self) -> Device<'nvml> {
+        unsafe { Device::new(self.device, self.nvml) }
+    }
+
+    /// Get the raw GPU instance handle contained in this struct
+    ///
+    /// Sometimes necessary for C interop.
+    ///
+    /// # Safety
+    ///
+    /// This is unsafe to prevent it from being used without care.
+    pub unsafe fn handle(&self) -> nvmlGpuInstance_t {
+        self.instance
+    }
+
+    /**
+    Gets the ID, profile and placement of this GPU instance.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `GpuInstance` is invalid
+    * `NotSupported`, if the device doesn't support MIG
+    * `NoPermission`, if the user doesn't have permission to perform this
+      operation
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlGpuInstanceGetInfo")]
+    pub fn info(&self) -> Result<GpuInstanceInfo, NvmlError> {
+        unsafe {
+            let mut info: nvmlGpuInstanceInfo_t = mem::zeroed();
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlGpuInstanceGetInfo(self.instance, &mut info)?,
+            )?;
+
+            Ok(info.into())
+        }
+    }
+
+    /**
+    Gets information about a compute instance profile, for compute instances
+    whose engines are shared as described by `engine_profile`.
+
+    The `id` of the returned `ComputeInstanceProfileInfo` is what the other compute instance
+    methods take as `profile_id`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `GpuInstance` or a profile is invalid
+    * `NotSupported`, if the device doesn't support MIG or this GPU instance
+      doesn't support the profile
+    * `NoPermission`, if the user doesn't have permission to perform this
+      operation
+    * `Utf8Error`, if the profile name isn't valid UTF-8
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlGpuInstanceGetComputeInstanceProfileInfoV")]
+    pub fn compute_instance_profile_info(
+        &self,
+        profile: ComputeInstanceProfile,
+        engine_profile: ComputeInstanceEngineProfile,
+    ) -> Result<ComputeInstanceProfileInfo, NvmlError> {
+        unsafe {
+            let mut info: nvmlComputeInstanceProfileInfo_v2_t = mem::zeroed();
+            info.version = compute_instance_profile_info_version();
+
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlGpuInstanceGetComputeInstanceProfileInfoV(
+                        self.instance,
+                        profile.as_c(),
+                        engine_profile.as_c(),
+                        &mut info,
+                    )?,
+            )?;
+
+            ComputeInstanceProfileInfo::try_from(info)
+        }
+    }
+
+    /**
+    Gets the slices of this GPU instance at which compute instances of the
+    profile with ID `profile_id` could be placed, were it empty.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `GpuInstance` or `profile_id` is invalid
+    * `NotSupported`, if the device doesn't support MIG
+    * `NoPermission`, if the user doesn't have permission to perform this
+      operation
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlGpuInstanceGetComputeInstancePossiblePlacements")]
+    pub fn compute_instance_possible_placements(
+        &self,
+        profile_id: u32,
+    ) -> Result<Vec<ComputeInstancePlacement>, NvmlError> {
+        unsafe {
+            let mut count: c_uint = 0;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlGpuInstanceGetComputeInstancePossiblePlacements(
+                        self.instance,
+                        profile_id,
+                        ptr::null_mut(),
+                        &mut count,
+                    )?,
+            )?;
+            let mut placements: Vec<nvmlComputeInstancePlacement_t> =
+                vec![mem::zeroed(); count as usize];
+
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlGpuInstanceGetComputeInstancePossiblePlacements(
+                        self.instance,
+                        profile_id,
+                        placements.as_mut_ptr(),
+                        &mut count,
+                    )?,
+            )?;
+            placements.truncate(count as usize);
+
+            Ok(placements
+                .into_iter()
+                .map(ComputeInstancePlacement::from)
+                .collect())
+        }
+    }
+
+    /**
+    Gets how many more compute instances of the profile with ID `profile_id`
+    can be created in this GPU instance.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `GpuInstance` or `profile_id` is invalid
+    * `NotSupported`, if the device doesn't support MIG
+    * `NoPermission`, if the user doesn't have permission to perform this
+      operation
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlGpuInstanceGetComputeInstanceRemainingCapacity")]
+    pub fn compute_instance_remaining_capacity(&self, profile_id: u32) -> Result<u32, NvmlError> {
+        unsafe {
+            let mut count: c_uint = 0;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlGpuInstanceGetComputeInstanceRemainingCapacity(
+                        self.instance,
+                        profile_id,
+                        &mut count,
+                    )?,
+            )?;
+
+            Ok(count)
+        }
+    }
+
+    /**
+    Creates a compute instance of the profile with ID `profile_id` in this GPU
+    instance, wherever NVML sees fit to place it.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `GpuInstance` or `profile_id` is invalid
+    * `NotSupported`, if the device doesn't support MIG
+    * `NoPermission`, if the user doesn't have permission to perform this
+      operation
+    * `InsufficientResources`, if there isn't room for another compute
+      instance of this profile
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlGpuInstanceCreateComputeInstance")]
+    pub fn create_compute_instance(
+        &self,
+        profile_id: u32,
+    ) -> Result<ComputeInstance<'nvml>, NvmlError> {
+        unsafe {
+            let mut instance: nvmlComputeInstance_t = mem::zeroed();
+            nvml_try(self.nvml.backend.nvmlGpuInstanceCreateComputeInstance(
+                self.instance,
+                profile_id,
+                &mut instance,
+            )?)?;
+
+            Ok(ComputeInstance::new(instance, self))
+        }
+    }
+
+    /**
+    Creates a compute instance of the profile with ID `profile_id` at
+    `placement`, which must be one of the
+    `compute_instance_possible_placements()` for the profile.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `GpuInstance`, `profile_id` or `placement` is
+      invalid
+    * `NotSupported`, if the device doesn't support MIG
+    * `NoPermission`, if the user doesn't have permission to perform this
+      operation
+    * `InsufficientResources`, if `placement` overlaps an existing compute
+      instance
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlGpuInstanceCreateComputeInstanceWithPlacement")]
+    pub fn create_compute_instance_with_placement(
+        &self,
+        profile_id: u32,
+        placement: &ComputeInstancePlacement,
+    ) -> Result<ComputeInstance<'nvml>, NvmlError> {
+        unsafe {
+            let placement = placement.clone().into_c();
+            let mut instance: nvmlComputeInstance_t = mem::zeroed();
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlGpuInstanceCreateComputeInstanceWithPlacement(
+                        self.instance,
+                        profile_id,
+                        &placement,
+                        &mut instance,
+                    )?,
+            )?;
+
+            Ok(ComputeInstance::new(instance, self))
+        }
+    }
+
+    /**
+    Gets the existing compute instances of the profile with ID `profile_id`
+    in this GPU instance.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `GpuInstance` or `profile_id` is invalid
+    * `NotSupported`, if the device doesn't support MIG
+    * `NoPermission`, if the user doesn't have permission to perform this
+      operation
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlGpuInstanceGetComputeInstances")]
+    pub fn compute_instances(
+        &self,
+        profile_id: u32,
+    ) -> Result<Vec<ComputeInstance<'nvml>>, NvmlError> {
+        // As for GPU instances, each compute instance occupies one of the
+        // possible placements
+        let capacity = self.compute_instance_possible_placements(profile_id)?.len();
+
+        unsafe {
+            let mut count: c_uint = capacity as c_uint;
+            let mut instances: Vec<nvmlComputeInstance_t> = vec![mem::zeroed(); capacity];
+
+            nvml_try(self.nvml.backend.nvmlGpuInstanceGetComputeInstances(
+                self.instance,
+                profile_id,
+                instances.as_mut_ptr(),
+                &mut count,
+            )?)?;
+            instances.truncate(count as usize);
+
+            Ok(instances
+                .into_iter()
+                .map(|instance| ComputeInstance::new(instance, self))
+                .collect())
+        }
+    }
+
+    /**
+    Gets the existing compute instance with the given ID in this GPU instance.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `GpuInstance` or `id` is invalid
+    * `NotSupported`, if the device doesn't support MIG
+    * `NoPermission`, if the user doesn't have permission to perform this
+      operation
+    * `NotFound`, if there is no compute instance with this ID
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlGpuInstanceGetComputeInstanceById")]
+    pub fn compute_instance_by_id(&self, id: u32) -> Result<ComputeInstance<'nvml>, NvmlError> {
+        unsafe {
+            let mut instance: nvmlComputeInstance_t = mem::zeroed();
+            nvml_try(self.nvml.backend.nvmlGpuInstanceGetComputeInstanceById(
+                self.instance,
+                id,
+                &mut instance,
+            )?)?;
+
+            Ok(ComputeInstance::new(instance, self))
+        }
+    }
+
+    /**
+    Gets the vGPU instances running on this GPU instance.
+
+    A list of vGPU handles is returned, to be wrapped with
+    `VgpuInstance::new()` for the device this GPU instance is a partition of.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `GpuInstance` is invalid
+    * `NotSupported`, if vGPU is not supported on this GPU instance
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Blackwell and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlGpuInstanceGetActiveVgpus")]
+    pub fn active_vgpus(&self) -> Result<Vec<nvmlVgpuInstance_t>, NvmlError> {
+        unsafe {
+            let mut info: nvmlActiveVgpuInstanceInfo_t = mem::zeroed();
+            info.version = active_vgpu_instance_info_version();
+
+            // A null list of instances asks for the count
+            nvml_try_count(
+                self.nvml
+                    .backend
+                    .nvmlGpuInstanceGetActiveVgpus(self.instance, &mut info)?,
+            )?;
+
+            let mut instances: Vec<nvmlVgpuInstance_t> = vec![0; info.vgpuCount as usize];
+            if instances.is_empty() {
+                return Ok(instances);
+            }
+            info.vgpuInstances = instances.as_mut_ptr();
+
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlGpuInstanceGetActiveVgpus(self.instance, &mut info)?,
+            )?;
+            instances.truncate(info.vgpuCount as usize);
+
+            Ok(instances)
+        }
+    }
+
+    /**
+    Gets the vGPU types that can currently be created on this GPU instance.
+
+    A list of vGPU type IDs is returned, to be wrapped with `VgpuType::new()`
+    for the device this GPU instance is a partition of.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `GpuInstance` is invalid
+    * `NotSupported`, if vGPU is not supported on this GPU instance
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Blackwell and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlGpuInstanceGetCreatableVgpus")]
+    pub fn creatable_vgpus(&self) -> Result<Vec<nvmlVgpuTypeId_t>, NvmlError> {
+        unsafe {
+            let mut info: nvmlVgpuTypeIdInfo_t = mem::zeroed();
+            info.version = vgpu_type_id_info_version();
+
+            // A null list of types asks for the count
+            nvml_try_count(
+                self.nvml
+                    .backend
+                    .nvmlGpuInstanceGetCreatableVgpus(self.instance, &mut info)?,
+            )?;
+
+            let mut ids: Vec<nvmlVgpuTypeId_t> = vec![0; info.vgpuCount as usize];
+            if ids.is_empty() {
+                return Ok(ids);
+            }
+            info.vgpuTypeIds = ids.as_mut_ptr();
+
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlGpuInstanceGetCreatableVgpus(self.instance, &mut info)?,
+            )?;
+            ids.truncate(info.vgpuCount as usize);
+
+            Ok(ids)
+        }
+    }
+
+    /**
+    Gets the placements at which a vGPU of the given type can currently be
+    created on this GPU instance.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `GpuInstance` or `type_id` is invalid
+    * `NotSupported`, if vGPU is not supported on this GPU instance
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Blackwell and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlGpuInstanceGetVgpuTypeCreatablePlacements")]
+    pub fn vgpu_type_creatable_placements(
+        &self,
+        type_id: nvmlVgpuTypeId_t,
+    ) -> Result<VgpuPlacementList, NvmlError> {
+        unsafe {
+            let mut info: nvmlVgpuCreatablePlacementInfo_t = mem::zeroed();
+            info.version = vgpu_creatable_placement_info_version();
+            info.vgpuTypeId = type_id;
+
+            // A null list of IDs asks for the count
+            nvml_try_count(
+                self.nvml
+                    .backend
+                    .nvmlGpuInstanceGetVgpuTypeCreatablePlacements(self.instance, &mut info)?,
+            )?;
+
+            let mut ids: Vec<c_uint> = vec![0; info.count as usize];
+            if !ids.is_empty() {
+                info.placementIds = ids.as_mut_ptr();
+
+                nvml_try(
+                    self.nvml
+                        .backend
+                        .nvmlGpuInstanceGetVgpuTypeCreatablePlacements(self.instance, &mut info)?,
+                )?;
+                ids.truncate(info.count as usize);
+            }
+
+            Ok(VgpuPlacementList {
+                placement_size: info.placementSize,
+                placement_ids: ids,
+            })
+        }
+    }
+
+    /**
+    Checks whether this GPU instance is in vGPU heterogeneous mode, in which it
+    can concurrently host time-sliced vGPUs with differing framebuffer sizes.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `GpuInstance` is invalid
+    * `NotSupported`, if vGPU is not supported on this GPU instance
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Blackwell and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlGpuInstanceGetVgpuHeterogeneousMode")]
+    pub fn is_vgpu_heterogeneous_mode_enabled(&self) -> Result<bool, NvmlError> {
+        unsafe {
+            let mut mode: nvmlVgpuHeterogeneousMode_t = mem::zeroed();
+            mode.version = vgpu_heterogeneous_mode_version();
+
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlGpuInstanceGetVgpuHeterogeneousMode(self.instance, &mut mode)?,
+            )?;
+
+            bool_from_state(mode.mode)
+        }
+    }
+
+    /**
+    Enables or disables vGPU heterogeneous mode on this GPU instance.
+
+    The mode can only be changed while no vGPUs are running on the GPU instance.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `GpuInstance` is invalid
+    * `NotSupported`, if vGPU is not supported on this GPU instance
+    * `NoPermission`, if the user doesn't have permission to perform this
+      operation
+    * `InUse`, if vGPUs are running on this GPU instance
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Blackwell and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlGpuInstanceSetVgpuHeterogeneousMode")]
+    pub fn set_vgpu_heterogeneous_mode(&mut self, enabled: bool) -> Result<(), NvmlError> {
+        unsafe {
+            let mode = nvmlVgpuHeterogeneousMode_t {
+                version: vgpu_heterogeneous_mode_version(),
+                mode: state_from_bool(enabled),
+            };
+
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlGpuInstanceSetVgpuHeterogeneousMode(self.instance, &mode)?,
+            )
+        }
+    }
+
+    /**
+    Gets the state of the vGPU scheduler on this GPU instance.
+
+    This is the GPU instance counterpart of `Device.vgpu_scheduler_state()`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `GpuInstance` is invalid
+    * `NotSupported`, if vGPU is not supported on this GPU instance
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Blackwell and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlGpuInstanceGetVgpuSchedulerState")]
+    pub fn vgpu_scheduler_state(&self) -> Result<VgpuSchedulerGetState, NvmlError> {
+        unsafe {
+            let mut state: nvmlVgpuSchedulerStateInfo_t = mem::zeroed();
+            state.version = vgpu_scheduler_state_info_version();
+            state.engineId = NVML_VGPU_SCHEDULER_ENGINE_TYPE_GRAPHICS;
+
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlGpuInstanceGetVgpuSchedulerState(self.instance, &mut state)?,
+            )?;
+
+            VgpuSchedulerGetState::try_from(state)
+        }
+    }
+
+    /**
+    Sets the state of the vGPU scheduler on this GPU instance.
+
+    This is the GPU instance counterpart of `Device.set_vgpu_scheduler_state()`,
+    and likewise checks the state first, against the device's
+    `.vgpu_scheduler_capabilities()` if it reports them.
+
+    # Errors
+
+    * `UnsupportedVgpuSchedulerState`, if the device doesn't support `scheduler_state`
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `GpuInstance` or `scheduler_state` is invalid
+    * `NotSupported`, if vGPU is not supported on this GPU instance
+    * `NoPermission`, if the user doesn't have permission to perform this
+      operation
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Blackwell and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlGpuInstanceSetVgpuSchedulerState")]
+    pub fn set_vgpu_scheduler_state(
+        &mut self,
+        scheduler_state: VgpuSchedulerSetState,
+    ) -> Result<(), NvmlError> {
+        scheduler_state.validate()?;
+        if let Some(capabilities) = optional(self.device().vgpu_scheduler_capabilities())? {
+            capabilities.validate(&scheduler_state)?;
+        }
+
+        let state = scheduler_state.as_c();
+        let mut state = nvmlVgpuSchedulerState_t {
+            version: vgpu_scheduler_state_version(),
+            engineId: NVML_VGPU_SCHEDULER_ENGINE_TYPE_GRAPHICS,
+            schedulerPolicy: state.schedulerPolicy,
+            enableARRMode: state.enableARRMode,
+            schedulerParams: state.schedulerParams,
+        };
+
+        unsafe {
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlGpuInstanceSetVgpuSchedulerState(self.instance, &mut state)?,
+            )
+        }
+    }
+
+    /**
+    Gets the latest log entries (max 200) of the vGPU scheduler on this GPU
+    instance.
+
+    This is the GPU instance counterpart of `Device.vgpu_scheduler_log()`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `GpuInstance` is invalid
+    * `NotSupported`, if vGPU is not supported on this GPU instance
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Blackwell and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlGpuInstanceGetVgpuSchedulerLog")]
+    pub fn vgpu_scheduler_log(&self) -> Result<VgpuSchedulerLog, NvmlError> {
+        unsafe {
+            let mut log: nvmlVgpuSchedulerLogInfo_t = mem::zeroed();
+            log.version = vgpu_scheduler_log_info_version();
+            log.engineId = NVML_VGPU_SCHEDULER_ENGINE_TYPE_GRAPHICS;
+
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlGpuInstanceGetVgpuSchedulerLog(self.instance, &mut log)?,
+            )?;
+
+            VgpuSchedulerLog::try_from(log)
+        }
+    }
+
+    /**
+    Destroys this GPU instance.
+
+    All compute instances within it must have been destroyed first.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `GpuInstance` is invalid
+    * `NotSupported`, if the device doesn't support MIG
+    * `NoPermission`, if the user doesn't have permission to perform this
+      operation
+    * `InUse`, if the GPU instance is in use (e.g. it still has compute
+      instances or processes running on it)
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlGpuInstanceDestroy")]
+    pub fn destroy(self) -> Result<(), NvmlError> {
+        unsafe { nvml_try(self.nvml.backend.nvmlGpuInstanceDestroy(self.instance)?) }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::backend::NvmlBackend;
+    use crate::enums::device::{VgpuSchedulerArrMode, VgpuSchedulerPolicy};
+    use crate::error::NvmlError;
+    use crate::ffi::bindings::*;
+    use crate::struct_wrappers::device::{
+        GpuInstanceInfo, GpuInstancePlacement, VgpuSchedulerGetState, VgpuSchedulerParams,
+        VgpuSchedulerSetParams, VgpuSchedulerSetState,
+    };
+    use crate::structs::device::VgpuPlacementList;
+    use crate::test_utils::{mock_backend, mock_device};
+    use crate::Nvml;
+    use std::os::raw::c_uint;
+    use std::sync::{Arc, Mutex};
+
+    /// One GPU with 8 memory slices and a single profile (ID 5) of 2 slices,
+    /// which can be placed at slices 0, 2, 4 and 6.
+    ///
+    /// Each GPU instance runs one vGPU (its ID * 10) and can create vGPU type
+    /// 42, at one placement in homogeneous mode and three in heterogeneous mode.
+    #[derive(Default)]
+    struct Mock {
+        /// The placements of the existing instances, by instance ID - 1.
+        instances: Mutex<Vec<Option<c_uint>>>,
+        /// The IDs of the instances in vGPU heterogeneous mode.
+        heterogeneous: Mutex<Vec<usize>>,
+        /// The vGPU scheduler policy, ARR mode, averaging factor and timeslice
+        /// set on any instance.
+        scheduler: Mutex<(c_uint, c_uint, c_uint, c_uint)>,
+        /// The `enableARRMode` the scheduler state was last set with.
+        enable_arr_mode: Arc<Mutex<Option<c_uint>>>,
+        /// Whether the device reports its vGPU scheduler capabilities.
+        no_scheduler_capabilities: bool,
+        /// Whether filling in the possible placements reports more of them than
+        /// the count asked for first.
+        overcount_placements: bool,
+    }
+
+    const PROFILE: c_uint = 5;
+    const DEVICE: nvmlDevice_t = mock_device(0);
+
+    /// The index into `Mock::instances` of the instance with the given ID or
+    /// handle, which start at 1.
+    fn instance_index(id: usize) -> Option<usize> {
+        id.checked_sub(1)
+    }
+
+    impl Mock {
+        unsafe fn create(
+            &self,
+            start: Option<c_uint>,
+            instance: *mut nvmlGpuInstance_t,
+        ) -> nvmlReturn_t {
+            let mut instances = self.instances.lock().unwrap();
+            let taken = |s| instances.contains(&Some(s));
+            let start = match start {
+                Some(s) if s % 2 == 0 && s < 8 && !taken(s) => s,
+                Some(_) => return nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_RESOURCES,
+                None => match (0..8).step_by(2).find(|&s| !taken(s)) {
+                    Some(s) => s,
+                    None => return nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_RESOURCES,
+                },
+            };
+
+            instances.push(Some(start));
+            *instance = instances.len() as nvmlGpuInstance_t;
+            nvmlReturn_enum_NVML_SUCCESS
+        }
+    }
+
+    impl NvmlBackend for Mock {
+        mock_backend!();
+
+        unsafe fn nvmlDeviceGetGpuInstancePossiblePlacements_v2(
+            &self,
+            _device: nvmlDevice_t,
+            profile_id: c_uint,
+            placements: *mut nvmlGpuInstancePlacement_t,
+            count: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            if profile_id != PROFILE {
+                return Ok(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT);
+            }
+
+            if !placements.is_null() {
+                for (i, start) in (0..8).step_by(2).enumerate() {
+                    *placements.add(i) = nvmlGpuInstancePlacement_t { start, size: 2 };
+                }
+            }
+            *count = match (placements.is_null(), self.overcount_placements) {
+                (false, true) => 64,
+                _ => 4,
+            };
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetGpuInstanceRemainingCapacity(
+            &self,
+            _device: nvmlDevice_t,
+            _profile_id: c_uint,
+            count: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let instances = self.instances.lock().unwrap();
+            *count = 4 - instances.iter().flatten().count() as c_uint;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceCreateGpuInstance(
+            &self,
+            _device: nvmlDevice_t,
+            _profile_id: c_uint,
+            instance: *mut nvmlGpuInstance_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            Ok(self.create(None, instance))
+        }
+
+        unsafe fn nvmlDeviceCreateGpuInstanceWithPlacement(
+            &self,
+            _device: nvmlDevice_t,
+            _profile_id: c_uint,
+            placement: *const nvmlGpuInstancePlacement_t,
+            instance: *mut nvmlGpuInstance_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            Ok(self.create(Some((*placement).start), instance))
+        }
+
+        unsafe fn nvmlGpuInstanceDestroy(
+            &self,
+            instance: nvmlGpuInstance_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let mut instances = self.instances.lock().unwrap();
+            match instance_index(instance as usize).and_then(|i| instances.get_mut(i)) {
+                Some(i @ Some(_)) => {
+                    *i = None;
+                    Ok(nvmlReturn_enum_NVML_SUCCESS)
+                }
+                _ => Ok(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT),
+            }
+        }
+
+        unsafe fn nvmlDeviceGetGpuInstances(
+            &self,
+            _device: nvmlDevice_t,
+            _profile_id: c_uint,
+            instances: *mut nvmlGpuInstance_t,
+            count: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let existing = self.instances.lock().unwrap();
+            let mut n = 0;
+            for (i, _) in existing.iter().enumerate().filter(|(_, s)| s.is_some()) {
+                *instances.add(n) = (i + 1) as nvmlGpuInstance_t;
+                n += 1;
+            }
+            *count = n as c_uint;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetGpuInstanceById(
+            &self,
+            _device: nvmlDevice_t,
+            id: c_uint,
+            instance: *mut nvmlGpuInstance_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let instances = self.instances.lock().unwrap();
+            match instance_index(id as usize).and_then(|i| instances.get(i)) {
+                Some(Some(_)) => {
+                    *instance = id as nvmlGpuInstance_t;
+                    Ok(nvmlReturn_enum_NVML_SUCCESS)
+                }
+                _ => Ok(nvmlReturn_enum_NVML_ERROR_NOT_FOUND),
+            }
+        }
+
+        unsafe fn nvmlGpuInstanceGetInfo(
+            &self,
+            instance: nvmlGpuInstance_t,
+            info: *mut nvmlGpuInstanceInfo_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let instances = self.instances.lock().unwrap();
+            let start = match instance_index(instance as usize).and_then(|i| instances.get(i)) {
+                Some(Some(start)) => *start,
+                _ => return Ok(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT),
+            };
+
+            *info = nvmlGpuInstanceInfo_t {
+                device: DEVICE,
+                id: instance as c_uint,
+                profileId: PROFILE,
+                placement: nvmlGpuInstancePlacement_t { start, size: 2 },
+            };
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlGpuInstanceGetActiveVgpus(
+            &self,
+            instance: nvmlGpuInstance_t,
+            info: *mut nvmlActiveVgpuInstanceInfo_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            if (*info).version >> 24 != 1 {
+                return Ok(nvmlReturn_enum_NVML_ERROR_ARGUMENT_VERSION_MISMATCH);
+            }
+
+            let available = (*info).vgpuCount;
+            (*info).vgpuCount = 1;
+            if (*info).vgpuInstances.is_null() || available < 1 {
+                return Ok(nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE);
+            }
+
+            *(*info).vgpuInstances = instance as nvmlVgpuInstance_t * 10;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlGpuInstanceGetCreatableVgpus(
+            &self,
+            _instance: nvmlGpuInstance_t,
+            info: *mut nvmlVgpuTypeIdInfo_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            if (*info).version >> 24 != 1 {
+                return Ok(nvmlReturn_enum_NVML_ERROR_ARGUMENT_VERSION_MISMATCH);
+            }
+
+            let available = (*info).vgpuCount;
+            (*info).vgpuCount = 1;
+            if (*info).vgpuTypeIds.is_null() || available < 1 {
+                return Ok(nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE);
+            }
+
+            *(*info).vgpuTypeIds = 42;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlGpuInstanceGetVgpuTypeCreatablePlacements(
+            &self,
+            instance: nvmlGpuInstance_t,
+            info: *mut nvmlVgpuCreatablePlacementInfo_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            if (*info).version >> 24 != 1 {
+                return Ok(nvmlReturn_enum_NVML_ERROR_ARGUMENT_VERSION_MISMATCH);
+            }
+            if (*info).vgpuTypeId != 42 {
+                return Ok(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT);
+            }
+
+            let heterogeneous = self
+                .heterogeneous
+                .lock()
+                .unwrap()
+                .contains(&(instance as usize));
+            let placements: &[c_uint] = if heterogeneous { &[0, 2, 4] } else { &[0] };
+            let available = (*info).count as usize;
+            (*info).placementSize = 2;
+            (*info).count = placements.len() as c_uint;
+
+            if (*info).placementIds.is_null() || available < placements.len() {
+                return Ok(nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE);
+            }
+
+            for (i, placement) in placements.iter().enumerate() {
+                *(*info).placementIds.add(i) = *placement;
+            }
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlGpuInstanceGetVgpuHeterogeneousMode(
+            &self,
+            instance: nvmlGpuInstance_t,
+            mode: *mut nvmlVgpuHeterogeneousMode_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            if (*mode).version >> 24 != 1 {
+                return Ok(nvmlReturn_enum_NVML_ERROR_ARGUMENT_VERSION_MISMATCH);
+            }
+
+            let heterogeneous = self.heterogeneous.lock().unwrap();
+            (*mode).mode = heterogeneous.contains(&(instance as usize)) as c_uint;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlGpuInstanceSetVgpuHeterogeneousMode(
+            &self,
+            instance: nvmlGpuInstance_t,
+            mode: *const nvmlVgpuHeterogeneousMode_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            if (*mode).version >> 24 != 1 {
+                return Ok(nvmlReturn_enum_NVML_ERROR_ARGUMENT_VERSION_MISMATCH);
+            }
+
+            let mut heterogeneous = self.heterogeneous.lock().unwrap();
+            heterogeneous.retain(|i| *i != instance as usize);
+            if (*mode).mode == nvmlEnableState_enum_NVML_FEATURE_ENABLED {
+                heterogeneous.push(instance as usize);
+            }
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlGpuInstanceGetVgpuSchedulerState(
+            &self,
+            _instance: nvmlGpuInstance_t,
+            state: *mut nvmlVgpuSchedulerStateInfo_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            if (*state).version >> 24 != 1 {
+                return Ok(nvmlReturn_enum_NVML_ERROR_ARGUMENT_VERSION_MISMATCH);
+            }
+
+            let (policy, arr_mode, avg_factor, timeslice) = *self.scheduler.lock().unwrap();
+            (*state).schedulerPolicy = policy;
+            (*state).arrMode = arr_mode;
+            (*state).schedulerParams.vgpuSchedDataWithARR.avgFactor = avg_factor;
+            (*state).schedulerParams.vgpuSchedDataWithARR.timeslice = timeslice;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlGpuInstanceSetVgpuSchedulerState(
+            &self,
+            _instance: nvmlGpuInstance_t,
+            state: *mut nvmlVgpuSchedulerState_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            if (*state).version >> 24 != 1
+                || (*state).engineId != NVML_VGPU_SCHEDULER_ENGINE_TYPE_GRAPHICS
+            {
+                return Ok(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT);
+            }
+
+            let params = (*state).schedulerParams;
+            let (arr_mode, avg_factor, timeslice) = match (*state).enableARRMode {
+                0 => (
+                    NVML_VGPU_SCHEDULER_ARR_DISABLE,
+                    0,
+                    params.vgpuSchedData.timeslice,
+                ),
+                1 => {
+                    let arr = params.vgpuSchedDataWithARR;
+                    (
+                        NVML_VGPU_SCHEDULER_ARR_ENABLE,
+                        arr.avgFactor,
+                        1_000_000_000 / arr.frequency,
+                    )
+                }
+                _ => return Ok(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT),
+            };
+            *self.enable_arr_mode.lock().unwrap() = Some((*state).enableARRMode);
+            *self.scheduler.lock().unwrap() =
+                ((*state).schedulerPolicy, arr_mode, avg_factor, timeslice);
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetVgpuSchedulerCapabilities(
+            &self,
+            _device: nvmlDevice_t,
+            capabilities: *mut nvmlVgpuSchedulerCapabilities_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            if self.no_scheduler_capabilities {
+                return Ok(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED);
+            }
+
+            *capabilities = nvmlVgpuSchedulerCapabilities_t {
+                supportedSchedulers: [
+                    NVML_VGPU_SCHEDULER_POLICY_BEST_EFFORT,
+                    NVML_VGPU_SCHEDULER_POLICY_EQUAL_SHARE,
+                    NVML_VGPU_SCHEDULER_POLICY_UNKNOWN,
+                ],
+                maxTimeslice: 30_000_000,
+                minTimeslice: 1_000_000,
+                isArrModeSupported: 1,
+                maxFrequencyForARR: 960,
+                minFrequencyForARR: 64,
+                maxAvgFactorForARR: 60,
+                minAvgFactorForARR: 0,
+            };
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+    }
+
+    fn nvml() -> Nvml {
+        with_mock(Mock::default())
+    }
+
+    fn with_mock(mock: Mock) -> Nvml {
+        Nvml::builder()
+            .init_with_backend(mock)
+            .expect("initialized mock")
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn create_list_and_destroy() {
+        let nvml = nvml();
+        let device = nvml.device_by_index(0).unwrap();
+
+        let first = device.create_gpu_instance(PROFILE).unwrap();
+        let placement = GpuInstancePlacement { start: 6, size: 2 };
+        let second = device
+            .create_gpu_instance_with_placement(PROFILE, &placement)
+            .unwrap();
+
+        assert_eq!(
+            second.info().unwrap(),
+            GpuInstanceInfo {
+                id: 2,
+                profile_id: PROFILE,
+                placement: placement.clone(),
+            }
+        );
+        assert_eq!(first.info().unwrap().placement.start, 0);
+        assert_eq!(device.gpu_instance_remaining_capacity(PROFILE).unwrap(), 2);
+        assert!(matches!(
+            device.create_gpu_instance_with_placement(PROFILE, &placement),
+            Err(NvmlError::InsufficientResources)
+        ));
+
+        let ids = |device: &crate::Device| -> Vec<u32> {
+            device
+                .gpu_instances(PROFILE)
+                .unwrap()
+                .iter()
+                .map(|i| i.info().unwrap().id)
+                .collect()
+        };
+        assert_eq!(ids(&device), [1, 2]);
+
+        first.destroy().unwrap();
+        assert_eq!(ids(&device), [2]);
+        assert!(matches!(
+            device.gpu_instance_by_id(1),
+            Err(NvmlError::NotFound)
+        ));
+        assert_eq!(
+            device
+                .gpu_instance_by_id(2)
+                .unwrap()
+                .info()
+                .unwrap()
+                .placement,
+            placement
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn configure_vgpu_per_instance() {
+        let nvml = nvml();
+        let device = nvml.device_by_index(0).unwrap();
+        let mut instance = device.create_gpu_instance(PROFILE).unwrap();
+
+        assert_eq!(instance.active_vgpus().unwrap(), [10]);
+        assert_eq!(instance.creatable_vgpus().unwrap(), [42]);
+
+        assert!(!instance.is_vgpu_heterogeneous_mode_enabled().unwrap());
+        assert_eq!(
+            instance.vgpu_type_creatable_placements(42).unwrap(),
+            VgpuPlacementList {
+                placement_size: 2,
+                placement_ids: vec![0],
+            }
+        );
+
+        instance.set_vgpu_heterogeneous_mode(true).unwrap();
+        assert!(instance.is_vgpu_heterogeneous_mode_enabled().unwrap());
+        assert_eq!(
+            instance
+                .vgpu_type_creatable_placements(42)
+                .unwrap()
+                .placement_ids,
+            [0, 2, 4]
+        );
+
+        // Fixed share isn't supported, so NVML isn't even asked
+        assert!(matches!(
+            instance.set_vgpu_scheduler_state(VgpuSchedulerSetState {
+                policy: VgpuSchedulerPolicy::FixedShare,
+                params: VgpuSchedulerSetParams::Timeslice(2_000_000),
+            }),
+            Err(NvmlError::UnsupportedVgpuSchedulerState(_))
+        ));
+        assert_eq!(
+            instance.vgpu_scheduler_state().unwrap().scheduler_policy,
+            VgpuSchedulerPolicy::Unknown
+        );
+
+        instance
+            .set_vgpu_scheduler_state(VgpuSchedulerSetState {
+                policy: VgpuSchedulerPolicy::BestEffort,
+                params: VgpuSchedulerSetParams::Arr {
+                    avg_factor: 33,
+                    frequency: 500,
+                },
+            })
+            .unwrap();
+        assert_eq!(
+            instance.vgpu_scheduler_state().unwrap(),
+            VgpuSchedulerGetState {
+                arr_mode: VgpuSchedulerArrMode::Enabled,
+                scheduler_policy: VgpuSchedulerPolicy::BestEffort,
+                scheduler_params: VgpuSchedulerParams {
+                    avg_factor: Some(33),
+                    timeslice: 2_000_000,
+                },
+            }
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn vgpu_scheduler_state_passes_arr_as_a_boolean() {
+        let mock = Mock::default();
+        let enable_arr_mode = mock.enable_arr_mode.clone();
+        let nvml = with_mock(mock);
+        let device = nvml.device_by_index(0).unwrap();
+        let mut instance = device.create_gpu_instance(PROFILE).unwrap();
+
+        instance
+            .set_vgpu_scheduler_state(VgpuSchedulerSetState {
+                policy: VgpuSchedulerPolicy::BestEffort,
+                params: VgpuSchedulerSetParams::Timeslice(2_000_000),
+            })
+            .unwrap();
+        assert_eq!(*enable_arr_mode.lock().unwrap(), Some(0));
+        assert_eq!(
+            instance.vgpu_scheduler_state().unwrap().arr_mode,
+            VgpuSchedulerArrMode::Disabled
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn vgpu_scheduler_state_is_set_without_capabilities() {
+        let nvml = with_mock(Mock {
+            no_scheduler_capabilities: true,
+            ..Mock::default()
+        });
+        let device = nvml.device_by_index(0).unwrap();
+        let mut instance = device.create_gpu_instance(PROFILE).unwrap();
+
+        // Only rejected by the capabilities the device doesn't report
+        instance
+            .set_vgpu_scheduler_state(VgpuSchedulerSetState {
+                policy: VgpuSchedulerPolicy::FixedShare,
+                params: VgpuSchedulerSetParams::Timeslice(2_000_000),
+            })
+            .unwrap();
+        assert_eq!(
+            instance.vgpu_scheduler_state().unwrap().scheduler_policy,
+            VgpuSchedulerPolicy::FixedShare
+        );
+
+        assert!(matches!(
+            instance.set_vgpu_scheduler_state(VgpuSchedulerSetState {
+                policy: VgpuSchedulerPolicy::EqualShare,
+                params: VgpuSchedulerSetParams::Arr {
+                    avg_factor: 33,
+                    frequency: 64,
+                },
+            }),
+            Err(NvmlError::UnsupportedVgpuSchedulerState(_))
+        ));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn placements_never_exceed_the_buffer() {
+        let nvml = with_mock(Mock {
+            overcount_placements: true,
+            ..Mock::default()
+        });
+        let device = nvml.device_by_index(0).unwrap();
+
+        assert_eq!(device.possible_placements(PROFILE).unwrap().len(), 4);
+
+        device.create_gpu_instance(PROFILE).unwrap();
+        assert_eq!(device.gpu_instances(PROFILE).unwrap().len(), 1);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn gpu_instance_id_zero_is_not_found() {
+        let nvml = nvml();
+        let device = nvml.device_by_index(0).unwrap();
+
+        assert!(matches!(
+            device.gpu_instance_by_id(0),
+            Err(NvmlError::NotFound)
+        ));
+    }
+}
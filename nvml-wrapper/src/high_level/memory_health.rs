@@ -0,0 +1,503 @@
+/*!
+Memory health assessment.
+
+NVML reports memory errors and what the GPU did about them through several
+queries: row remapping on Ampere and newer, page retirement before that, and
+ECC error counters on both. `Device.memory_health()` gathers them and reaches a
+single verdict, e.g. for deciding whether to take a node out of service:
+
+```no_run
+# extern crate nvml_wrapper as nvml;
+#
+# use nvml::Nvml;
+# use nvml::error::NvmlError;
+# use nvml::high_level::MemoryHealthVerdict;
+#
+# fn main() -> Result<(), NvmlError> {
+# let nvml = Nvml::init()?;
+for index in 0..nvml.device_count()? {
+    let health = nvml.device_by_index(index)?.memory_health()?;
+
+    match health.verdict {
+        MemoryHealthVerdict::Healthy => continue,
+        MemoryHealthVerdict::Unknown => println!("can't tell; check the device by hand"),
+        _ => {
+            for reason in &health.reasons {
+                println!("{:?}: {}", health.verdict, reason);
+            }
+        }
+    }
+}
+# Ok(())
+# }
+```
+*/
+
+use crate::enum_wrappers::device::{EccCounter, MemoryError, MemoryLocation, RetirementCause};
+use crate::error::{optional, NvmlError};
+use crate::struct_wrappers::device::RowRemapperHistogram;
+use crate::structs::device::RemappedRows;
+use crate::Device;
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+use std::fmt;
+
+/// The number of retired pages at which NVIDIA's page retirement guidance
+/// recommends replacing a GPU.
+///
+/// See the RMA policy in the [Dynamic Page Retirement] documentation.
+///
+/// [Dynamic Page Retirement]: https://docs.nvidia.com/deploy/dynamic-page-retirement/index.html
+pub const RETIRED_PAGES_RMA_THRESHOLD: usize = 60;
+
+/// What should be done about a `Device`, judging by its memory.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MemoryHealthVerdict {
+    /// Nothing needs to be done.
+    Healthy,
+    /// The GPU needs to be reset (or the machine rebooted) before it can be
+    /// trusted again.
+    ResetRequired,
+    /// The GPU should be replaced.
+    NeedsRma,
+    /// The `Device` supports none of the queries the verdict is drawn from.
+    Unknown,
+}
+
+/// Something wrong with a `Device`'s memory.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MemoryHealthReason {
+    /// A row remapping has failed.
+    RowRemappingFailed,
+    /// A row remapping takes effect at the next reset.
+    RowRemappingPending,
+    /// Memory banks have no spare rows left, so the next uncorrectable error
+    /// in one of them can't be remapped.
+    RowRemappingExhausted { banks: u32 },
+    /// Pages are waiting for a reset to be retired.
+    PageRetirementPending,
+    /// At least `RETIRED_PAGES_RMA_THRESHOLD` pages have been retired.
+    RetiredPageLimit { retired: usize },
+    /// Uncorrected ECC errors occurred since the driver was loaded.
+    UncorrectedErrors { count: u64 },
+}
+
+impl MemoryHealthReason {
+    /// The verdict this reason calls for on its own.
+    pub fn verdict(&self) -> MemoryHealthVerdict {
+        match self {
+            Self::RowRemappingFailed
+            | Self::RowRemappingExhausted { .. }
+            | Self::RetiredPageLimit { .. } => MemoryHealthVerdict::NeedsRma,
+            Self::RowRemappingPending
+            | Self::PageRetirementPending
+            | Self::UncorrectedErrors { .. } => MemoryHealthVerdict::ResetRequired,
+        }
+    }
+}
+
+impl fmt::Display for MemoryHealthReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RowRemappingFailed => write!(f, "a row remapping has failed"),
+            Self::RowRemappingPending => write!(f, "a row remapping is pending a reset"),
+            Self::RowRemappingExhausted { banks } => {
+                write!(f, "{} memory banks have no spare rows left", banks)
+            }
+            Self::PageRetirementPending => write!(f, "pages are pending retirement"),
+            Self::RetiredPageLimit { retired } => write!(
+                f,
+                "{} pages have been retired (the limit is {})",
+                retired, RETIRED_PAGES_RMA_THRESHOLD
+            ),
+            Self::UncorrectedErrors { count } => write!(
+                f,
+                "{} uncorrected ECC errors since the driver was loaded",
+                count
+            ),
+        }
+    }
+}
+
+/// Returned from `Device.memory_health()`.
+///
+/// Fields are `None` where the `Device` doesn't support the query (or, for the
+/// ECC counters, doesn't have ECC enabled).
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MemoryHealth {
+    /// The most severe verdict called for by `reasons`, `Healthy` if there are
+    /// none, or `Unknown` if neither row remapping, page retirement nor ECC
+    /// could be queried.
+    pub verdict: MemoryHealthVerdict,
+    /// What's wrong with the memory. Empty if nothing is.
+    pub reasons: Vec<MemoryHealthReason>,
+    /// From `Device.remapped_rows()`.
+    pub remapped_rows: Option<RemappedRows>,
+    /// From `Device.row_remapper_histogram()`.
+    pub row_remapper_histogram: Option<RowRemapperHistogram>,
+    /// The number of pages retired for either cause, from
+    /// `Device.retired_pages()`.
+    pub retired_pages: Option<usize>,
+    /// From `Device.are_pages_pending_retired()`.
+    pub pages_pending_retirement: Option<bool>,
+    /// Uncorrected ECC errors since the driver was loaded.
+    pub volatile_uncorrected_errors: Option<u64>,
+    /// Uncorrected ECC errors over the lifetime of the device.
+    pub aggregate_uncorrected_errors: Option<u64>,
+    /// Uncorrected ECC errors in device memory over the lifetime of the device.
+    pub aggregate_uncorrected_dram_errors: Option<u64>,
+    /// Uncorrected ECC errors in SRAM over the lifetime of the device.
+    pub aggregate_uncorrected_sram_errors: Option<u64>,
+}
+
+/**
+Assess the health of `device`'s memory.
+
+This is what `Device.memory_health()` does. Queries that are not supported are
+left out; the verdict is:
+
+* `NeedsRma` if a row remapping has failed, the row remapper histogram
+  reports banks with no spare rows left, or at least
+  `RETIRED_PAGES_RMA_THRESHOLD` pages have been retired
+* `ResetRequired` if a row remapping or page retirement is pending, or
+  uncorrected ECC errors occurred since the driver was loaded
+* `Unknown` if none of row remapping, page retirement or ECC could be queried
+* `Healthy` otherwise
+
+# Errors
+
+The same as the queries listed above, except for `NotSupported` and
+`FailedToLoadSymbol`.
+*/
+pub fn assess(device: &Device) -> Result<MemoryHealth, NvmlError> {
+    let remapped_rows = optional(device.remapped_rows())?;
+    let row_remapper_histogram = optional(device.row_remapper_histogram())?;
+
+    let retired_pages = match (
+        optional(device.retired_pages(RetirementCause::MultipleSingleBitEccErrors))?,
+        optional(device.retired_pages(RetirementCause::DoubleBitEccError))?,
+    ) {
+        (None, None) => None,
+        (sbe, dbe) => Some(sbe.map_or(0, |p| p.len()) + dbe.map_or(0, |p| p.len())),
+    };
+    let pages_pending_retirement = optional(device.are_pages_pending_retired())?;
+
+    let volatile_uncorrected_errors =
+        optional(device.total_ecc_errors(MemoryError::Uncorrected, EccCounter::Volatile))?;
+    let aggregate_uncorrected_errors =
+        optional(device.total_ecc_errors(MemoryError::Uncorrected, EccCounter::Aggregate))?;
+    let aggregate_uncorrected_dram_errors = optional(device.memory_error_counter(
+        MemoryError::Uncorrected,
+        EccCounter::Aggregate,
+        MemoryLocation::Device,
+    ))?;
+    let aggregate_uncorrected_sram_errors = optional(device.memory_error_counter(
+        MemoryError::Uncorrected,
+        EccCounter::Aggregate,
+        MemoryLocation::SRAM,
+    ))?;
+
+    let mut reasons = Vec::new();
+
+    if let Some(rows) = &remapped_rows {
+        if rows.failure_occurred {
+            reasons.push(MemoryHealthReason::RowRemappingFailed);
+        }
+        if rows.is_pending {
+            reasons.push(MemoryHealthReason::RowRemappingPending);
+        }
+    }
+
+    if let Some(histogram) = &row_remapper_histogram {
+        if histogram.none > 0 {
+            reasons.push(MemoryHealthReason::RowRemappingExhausted {
+                banks: histogram.none,
+            });
+        }
+    }
+
+    if let Some(retired) = retired_pages {
+        if retired >= RETIRED_PAGES_RMA_THRESHOLD {
+            reasons.push(MemoryHealthReason::RetiredPageLimit { retired });
+        }
+    }
+
+    if pages_pending_retirement == Some(true) {
+        reasons.push(MemoryHealthReason::PageRetirementPending);
+    }
+
+    if let Some(count) = volatile_uncorrected_errors.filter(|&count| count > 0) {
+        reasons.push(MemoryHealthReason::UncorrectedErrors { count });
+    }
+
+    let verdicts: Vec<_> = reasons.iter().map(MemoryHealthReason::verdict).collect();
+    let verdict = if verdicts.contains(&MemoryHealthVerdict::NeedsRma) {
+        MemoryHealthVerdict::NeedsRma
+    } else if verdicts.contains(&MemoryHealthVerdict::ResetRequired) {
+        MemoryHealthVerdict::ResetRequired
+    } else if remapped_rows.is_none()
+        && retired_pages.is_none()
+        && pages_pending_retirement.is_none()
+        && volatile_uncorrected_errors.is_none()
+    {
+        MemoryHealthVerdict::Unknown
+    } else {
+        MemoryHealthVerdict::Healthy
+    };
+
+    Ok(MemoryHealth {
+        verdict,
+        reasons,
+        remapped_rows,
+        row_remapper_histogram,
+        retired_pages,
+        pages_pending_retirement,
+        volatile_uncorrected_errors,
+        aggregate_uncorrected_errors,
+        aggregate_uncorrected_dram_errors,
+        aggregate_uncorrected_sram_errors,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::NvmlBackend;
+    use crate::ffi::bindings::*;
+    use crate::test_utils::mock_backend;
+    use crate::Nvml;
+    use std::os::raw::{c_uint, c_ulonglong};
+
+    /// One GPU. An Ampere GPU (`pages: None`) remaps rows; an older one
+    /// (`rows: None`) retires pages. `None` means unsupported.
+    #[derive(Default)]
+    struct Mock {
+        /// (correctable, uncorrectable, pending, failed)
+        rows: Option<(c_uint, c_uint, c_uint, c_uint)>,
+        /// (retired, pending)
+        pages: Option<(c_uint, bool)>,
+        /// Uncorrected errors since the driver was loaded.
+        volatile: Option<c_ulonglong>,
+        /// Banks with no spare rows left, if `rows` is supported.
+        exhausted_banks: c_uint,
+    }
+
+    const NOT_SUPPORTED: nvmlReturn_t = nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED;
+
+    impl NvmlBackend for Mock {
+        mock_backend!();
+
+        unsafe fn nvmlDeviceGetRemappedRows(
+            &self,
+            _device: nvmlDevice_t,
+            corr_rows: *mut c_uint,
+            unc_rows: *mut c_uint,
+            is_pending: *mut c_uint,
+            failure_occurred: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let (corr, unc, pending, failed) = match self.rows {
+                Some(rows) => rows,
+                None => return Ok(NOT_SUPPORTED),
+            };
+            *corr_rows = corr;
+            *unc_rows = unc;
+            *is_pending = pending;
+            *failure_occurred = failed;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetRowRemapperHistogram(
+            &self,
+            _device: nvmlDevice_t,
+            values: *mut nvmlRowRemapperHistogramValues_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let (corr, unc, _, _) = match self.rows {
+                Some(rows) => rows,
+                None => return Ok(NOT_SUPPORTED),
+            };
+            (*values).max = 640 - corr - unc - self.exhausted_banks;
+            (*values).high = corr + unc;
+            (*values).partial = 0;
+            (*values).low = 0;
+            (*values).none = self.exhausted_banks;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetRetiredPages(
+            &self,
+            _device: nvmlDevice_t,
+            cause: nvmlPageRetirementCause_t,
+            count: *mut c_uint,
+            _addresses: *mut c_ulonglong,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let (retired, _) = match self.pages {
+                Some(pages) => pages,
+                None => return Ok(NOT_SUPPORTED),
+            };
+            // All of them are double bit errors
+            *count = match cause {
+                nvmlPageRetirementCause_enum_NVML_PAGE_RETIREMENT_CAUSE_DOUBLE_BIT_ECC_ERROR => {
+                    retired
+                }
+                _ => 0,
+            };
+            Ok(nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE)
+        }
+
+        unsafe fn nvmlDeviceGetRetiredPages_v2(
+            &self,
+            _device: nvmlDevice_t,
+            _cause: nvmlPageRetirementCause_t,
+            count: *mut c_uint,
+            addresses: *mut c_ulonglong,
+            timestamps: *mut c_ulonglong,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            for i in 0..*count as usize {
+                *addresses.add(i) = 0x1000 * i as c_ulonglong;
+                *timestamps.add(i) = i as c_ulonglong;
+            }
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetRetiredPagesPendingStatus(
+            &self,
+            _device: nvmlDevice_t,
+            is_pending: *mut nvmlEnableState_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *is_pending = match self.pages {
+                Some((_, true)) => nvmlEnableState_enum_NVML_FEATURE_ENABLED,
+                Some((_, false)) => nvmlEnableState_enum_NVML_FEATURE_DISABLED,
+                None => return Ok(NOT_SUPPORTED),
+            };
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetTotalEccErrors(
+            &self,
+            _device: nvmlDevice_t,
+            _error_type: nvmlMemoryErrorType_t,
+            counter_type: nvmlEccCounterType_t,
+            ecc_counts: *mut c_ulonglong,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let volatile = match self.volatile {
+                Some(volatile) => volatile,
+                None => return Ok(NOT_SUPPORTED),
+            };
+            *ecc_counts = match counter_type {
+                nvmlEccCounterType_enum_NVML_VOLATILE_ECC => volatile,
+                _ => volatile + 2,
+            };
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+    }
+
+    fn assess_with(mock: Mock) -> MemoryHealth {
+        let nvml = Nvml::builder().init_with_backend(mock).unwrap();
+        let device = nvml.device_by_index(0).unwrap();
+
+        device.memory_health().unwrap()
+    }
+
+    #[test]
+    fn clean_ampere_gpu_is_healthy() {
+        let health = assess_with(Mock {
+            rows: Some((3, 0, 0, 0)),
+            volatile: Some(0),
+            ..Default::default()
+        });
+
+        assert_eq!(health.verdict, MemoryHealthVerdict::Healthy);
+        assert!(health.reasons.is_empty());
+        assert_eq!(health.row_remapper_histogram.unwrap().max, 637);
+        assert_eq!(health.retired_pages, None);
+        assert_eq!(health.aggregate_uncorrected_errors, Some(2));
+        // Not implemented by the mock
+        assert_eq!(health.aggregate_uncorrected_sram_errors, None);
+    }
+
+    #[test]
+    fn pending_remapping_requires_reset() {
+        let health = assess_with(Mock {
+            rows: Some((0, 1, 1, 0)),
+            volatile: Some(1),
+            ..Default::default()
+        });
+
+        assert_eq!(health.verdict, MemoryHealthVerdict::ResetRequired);
+        assert_eq!(
+            health.reasons,
+            vec![
+                MemoryHealthReason::RowRemappingPending,
+                MemoryHealthReason::UncorrectedErrors { count: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn failed_remapping_needs_rma() {
+        let health = assess_with(Mock {
+            rows: Some((0, 512, 1, 1)),
+            ..Default::default()
+        });
+
+        assert_eq!(health.verdict, MemoryHealthVerdict::NeedsRma);
+        assert_eq!(
+            health.reasons,
+            vec![
+                MemoryHealthReason::RowRemappingFailed,
+                MemoryHealthReason::RowRemappingPending,
+            ]
+        );
+        assert_eq!(health.volatile_uncorrected_errors, None);
+    }
+
+    #[test]
+    fn exhausted_row_remapper_needs_rma() {
+        let health = assess_with(Mock {
+            rows: Some((2, 0, 0, 0)),
+            exhausted_banks: 1,
+            ..Default::default()
+        });
+
+        assert_eq!(health.verdict, MemoryHealthVerdict::NeedsRma);
+        assert_eq!(
+            health.reasons,
+            vec![MemoryHealthReason::RowRemappingExhausted { banks: 1 }]
+        );
+        assert_eq!(health.row_remapper_histogram.unwrap().max, 637);
+    }
+
+    #[test]
+    fn retired_pages_are_counted_against_the_limit() {
+        let health = assess_with(Mock {
+            pages: Some((4, true)),
+            ..Default::default()
+        });
+        assert_eq!(health.verdict, MemoryHealthVerdict::ResetRequired);
+        assert_eq!(health.retired_pages, Some(4));
+        assert_eq!(health.remapped_rows, None);
+
+        let health = assess_with(Mock {
+            pages: Some((RETIRED_PAGES_RMA_THRESHOLD as c_uint, false)),
+            ..Default::default()
+        });
+        assert_eq!(health.verdict, MemoryHealthVerdict::NeedsRma);
+        assert_eq!(
+            health.reasons,
+            vec![MemoryHealthReason::RetiredPageLimit {
+                retired: RETIRED_PAGES_RMA_THRESHOLD
+            }]
+        );
+    }
+
+    #[test]
+    fn nothing_queryable_is_unknown() {
+        let health = assess_with(Mock::default());
+
+        assert_eq!(health.verdict, MemoryHealthVerdict::Unknown);
+        assert!(health.reasons.is_empty());
+    }
+}
@@ -0,0 +1,997 @@
+/*!
+Declarative MIG partitioning.
+
+Describe the GPU instances a device should have as a list of profile names, see
+what it would take to get there, and get there:
+
+```no_run
+# extern crate nvml_wrapper as nvml;
+#
+# #[cfg(target_os = "linux")]
+# fn main() {
+#     example::actual_main().unwrap();
+# }
+#
+# #[cfg(target_os = "windows")]
+# fn main() {}
+#
+# #[cfg(target_os = "linux")]
+# mod example {
+# use nvml::Nvml;
+# use nvml::high_level::{MigLayout, MigLayoutError};
+#
+# pub fn actual_main() -> Result<(), MigLayoutError> {
+# let nvml = Nvml::init()?;
+# let device = nvml.device_by_index(0)?;
+let layout = MigLayout::new(["3g.40gb", "2g.20gb", "1g.10gb", "1g.10gb"]);
+
+// What would change, without changing anything
+let plan = layout.plan(&device)?;
+for instance in &plan.destroy {
+    println!("would destroy {} at {:?}", instance.profile, instance.placement);
+}
+
+// Make it so; doing it again changes nothing
+layout.apply(&device)?;
+assert!(layout.plan(&device)?.is_empty());
+# Ok(())
+# }
+# }
+```
+
+GPU instances that already match the layout are kept where they are; the
+others are destroyed (along with their compute instances) and the missing ones
+are created. MIG mode must already be enabled on the device.
+
+The functionality in this module is only available on Linux platforms; NVML does
+not support MIG instance management on any other platform.
+*/
+
+use crate::enums::device::{ComputeInstanceEngineProfile, ComputeInstanceProfile};
+use crate::error::NvmlError;
+use crate::ffi::bindings::NVML_GPU_INSTANCE_PROFILE_COUNT;
+use crate::struct_wrappers::device::{
+    ComputeInstanceProfileInfo, GpuInstancePlacement, GpuInstanceProfileInfo,
+};
+use crate::{Device, GpuInstance};
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use thiserror::Error;
+
+const COMPUTE_INSTANCE_PROFILES: [ComputeInstanceProfile; 8] = [
+    ComputeInstanceProfile::OneSlice,
+    ComputeInstanceProfile::TwoSlices,
+    ComputeInstanceProfile::ThreeSlices,
+    ComputeInstanceProfile::FourSlices,
+    ComputeInstanceProfile::SixSlices,
+    ComputeInstanceProfile::SevenSlices,
+    ComputeInstanceProfile::EightSlices,
+    ComputeInstanceProfile::OneSliceRev1,
+];
+
+/// Why a `MigLayout` couldn't be planned or applied.
+#[derive(Error, Debug)]
+pub enum MigLayoutError {
+    #[error("the device has no GPU instance profile named {0:?}")]
+    UnknownProfile(String),
+    #[error(
+        "the layout has {requested} GPU instances of profile {profile:?} but the \
+         device supports at most {max}"
+    )]
+    TooManyInstances {
+        profile: String,
+        requested: u32,
+        max: u32,
+    },
+    #[error("the layout's GPU instances cannot all be placed on the device at once")]
+    DoesNotFit,
+    #[error("{0}")]
+    Nvml(#[from] NvmlError),
+}
+
+/// A GPU instance that exists or is to be created.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MigInstance {
+    /// The name of the instance's profile without the `MIG ` prefix, e.g.
+    /// `3g.40gb`.
+    pub profile: String,
+    pub profile_id: u32,
+    pub placement: GpuInstancePlacement,
+    /// The GPU instance ID, for instances that exist.
+    pub id: Option<u32>,
+}
+
+/// What it takes to get from a device's GPU instances to a `MigLayout`.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MigPlan {
+    /// Existing GPU instances that are part of the layout.
+    pub keep: Vec<MigInstance>,
+    /// Existing GPU instances that are not part of the layout.
+    pub destroy: Vec<MigInstance>,
+    /// GPU instances to create, at the placements they'll be created at.
+    pub create: Vec<MigInstance>,
+}
+
+impl MigPlan {
+    /// Whether the device already has the layout.
+    pub fn is_empty(&self) -> bool {
+        self.destroy.is_empty() && self.create.is_empty()
+    }
+}
+
+/**
+The GPU instances a device should be partitioned into, by profile name.
+
+Profile names are those NVML reports (and `nvidia-smi mig -lgip` lists), with
+or without their `MIG ` prefix: `3g.40gb` or `MIG 3g.40gb`.
+*/
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MigLayout {
+    profiles: Vec<String>,
+    compute_instances: bool,
+}
+
+impl MigLayout {
+    /// A layout with a GPU instance for each of `profiles`.
+    pub fn new<I, S>(profiles: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            profiles: profiles.into_iter().map(Into::into).collect(),
+            compute_instances: true,
+        }
+    }
+
+    /**
+    Whether `apply()` creates a compute instance spanning each GPU instance it
+    creates, as `nvidia-smi mig -cgi <profiles> -C` does. Defaults to `true`.
+
+    Without a compute instance, a GPU instance can't run anything.
+    */
+    pub fn compute_instances(mut self, create: bool) -> Self {
+        self.compute_instances = create;
+        self
+    }
+
+    /// The profile names in this layout.
+    pub fn profiles(&self) -> &[String] {
+        &self.profiles
+    }
+
+    /**
+    Work out what `apply()` would change on `device`, without changing
+    anything.
+
+    Placements are chosen so that as many existing GPU instances as possible
+    are kept.
+
+    # Errors
+
+    * `UnknownProfile`, if the device has no profile with one of the names
+    * `TooManyInstances`, if the layout has more GPU instances of a profile
+      than the device supports
+    * `DoesNotFit`, if there is no way to place all the GPU instances at once
+    * `Nvml`, if querying the device failed; `NotSupported` if MIG is not
+      supported or not enabled on it
+    */
+    pub fn plan(&self, device: &Device) -> Result<MigPlan, MigLayoutError> {
+        let profiles = profiles(device)?;
+        let existing: Vec<MigInstance> = existing(device, &profiles)?
+            .into_iter()
+            .map(|(instance, _)| instance)
+            .collect();
+
+        self.plan_for(&profiles, &existing)
+    }
+
+    /**
+    Partition `device` according to this layout, returning what was changed.
+
+    Existing GPU instances not in the plan are destroyed first, along with their
+    compute instances, then the missing ones are created. The IDs of the
+    created instances are filled in in the returned plan.
+
+    Applying a layout the device already has changes nothing. If applying
+    fails part of the way through, applying again picks up from where the
+    device was left.
+
+    # Errors
+
+    The same as `plan()`, and `Nvml` if destroying or creating an instance
+    failed, e.g. with `InUse` if processes are running on an instance being
+    destroyed.
+    */
+    pub fn apply(&self, device: &Device) -> Result<MigPlan, MigLayoutError> {
+        let profiles = profiles(device)?;
+        let existing = existing(device, &profiles)?;
+        let instances: Vec<MigInstance> = existing.iter().map(|(i, _)| i.clone()).collect();
+        let mut plan = self.plan_for(&profiles, &instances)?;
+
+        for (instance, handle) in existing {
+            if plan.destroy.contains(&instance) {
+                destroy_compute_instances(&handle)?;
+                handle.destroy()?;
+            }
+        }
+
+        for instance in &mut plan.create {
+            let handle = device
+                .create_gpu_instance_with_placement(instance.profile_id, &instance.placement)?;
+            instance.id = Some(handle.info()?.id);
+
+            if self.compute_instances {
+                create_compute_instance(&handle)?;
+            }
+        }
+
+        Ok(plan)
+    }
+
+    fn plan_for(
+        &self,
+        profiles: &[Profile],
+        existing: &[MigInstance],
+    ) -> Result<MigPlan, MigLayoutError> {
+        if profiles.is_empty() {
+            return Err(NvmlError::NotSupported.into());
+        }
+
+        let mut wanted = Vec::with_capacity(self.profiles.len());
+        for name in &self.profiles {
+            let profile = profiles
+                .iter()
+                .find(|p| p.name() == short_name(name))
+                .ok_or_else(|| MigLayoutError::UnknownProfile(name.clone()))?;
+
+            wanted.push(profile);
+        }
+
+        for profile in profiles {
+            let requested = wanted
+                .iter()
+                .filter(|p| p.info.id == profile.info.id)
+                .count() as u32;
+
+            if requested > profile.info.instance_count {
+                return Err(MigLayoutError::TooManyInstances {
+                    profile: profile.name().into(),
+                    requested,
+                    max: profile.info.instance_count,
+                });
+            }
+        }
+
+        // Placing the biggest instances first leaves the fewest holes to fill
+        wanted.sort_by_key(|p| Reverse(p.info.slice_count));
+
+        let slots = Search::new(&wanted, existing)
+            .run()
+            .ok_or(MigLayoutError::DoesNotFit)?;
+
+        let mut plan = MigPlan::default();
+        for (profile, slot) in wanted.iter().zip(slots) {
+            match slot.existing {
+                Some(i) => plan.keep.push(existing[i].clone()),
+                None => plan.create.push(MigInstance {
+                    profile: profile.name().into(),
+                    profile_id: profile.info.id,
+                    placement: slot.placement,
+                    id: None,
+                }),
+            }
+        }
+        plan.destroy = existing
+            .iter()
+            .filter(|i| !plan.keep.contains(i))
+            .cloned()
+            .collect();
+
+        Ok(plan)
+    }
+}
+
+/// A GPU instance profile the device supports.
+struct Profile {
+    info: GpuInstanceProfileInfo,
+    placements: Vec<GpuInstancePlacement>,
+}
+
+impl Profile {
+    fn name(&self) -> &str {
+        short_name(&self.info.name)
+    }
+}
+
+pub(crate) fn short_name(name: &str) -> &str {
+    name.strip_prefix("MIG ").unwrap_or(name)
+}
+
+fn profiles(device: &Device) -> Result<Vec<Profile>, NvmlError> {
+    let mut profiles = Vec::new();
+
+    for profile in 0..NVML_GPU_INSTANCE_PROFILE_COUNT {
+        let info = match device.gpu_instance_profile_info(profile) {
+            Ok(info) => info,
+            Err(NvmlError::NotSupported) | Err(NvmlError::InvalidArg) => continue,
+            Err(e) => return Err(e),
+        };
+        let placements = device.possible_placements(info.id)?;
+
+        profiles.push(Profile { info, placements });
+    }
+
+    Ok(profiles)
+}
+
+fn existing<'nvml>(
+    device: &Device<'nvml>,
+    profiles: &[Profile],
+) -> Result<Vec<(MigInstance, GpuInstance<'nvml>)>, NvmlError> {
+    let mut existing = Vec::new();
+
+    for profile in profiles {
+        for handle in device.gpu_instances(profile.info.id)? {
+            let info = handle.info()?;
+            let instance = MigInstance {
+                profile: profile.name().into(),
+                profile_id: profile.info.id,
+                placement: info.placement,
+                id: Some(info.id),
+            };
+
+            existing.push((instance, handle));
+        }
+    }
+
+    Ok(existing)
+}
+
+fn destroy_compute_instances(gpu_instance: &GpuInstance) -> Result<(), NvmlError> {
+    for profile in COMPUTE_INSTANCE_PROFILES {
+        let info = match gpu_instance
+            .compute_instance_profile_info(profile, ComputeInstanceEngineProfile::Shared)
+        {
+            Ok(info) => info,
+            Err(NvmlError::NotSupported) | Err(NvmlError::InvalidArg) => continue,
+            Err(e) => return Err(e),
+        };
+
+        for compute_instance in gpu_instance.compute_instances(info.id)? {
+            compute_instance.destroy()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Create the biggest compute instance `gpu_instance` supports.
+fn create_compute_instance(gpu_instance: &GpuInstance) -> Result<(), NvmlError> {
+    let mut biggest: Option<ComputeInstanceProfileInfo> = None;
+
+    for profile in COMPUTE_INSTANCE_PROFILES {
+        let info = match gpu_instance
+            .compute_instance_profile_info(profile, ComputeInstanceEngineProfile::Shared)
+        {
+            Ok(info) => info,
+            Err(NvmlError::NotSupported) | Err(NvmlError::InvalidArg) => continue,
+            Err(e) => return Err(e),
+        };
+
+        if biggest
+            .as_ref()
+            .map_or(true, |b| info.slice_count > b.slice_count)
+        {
+            biggest = Some(info);
+        }
+    }
+
+    let biggest = biggest.ok_or(NvmlError::NotSupported)?;
+    gpu_instance.create_compute_instance(biggest.id)?;
+
+    Ok(())
+}
+
+/// Where a wanted GPU instance goes: an existing instance's placement, or a new
+/// one.
+#[derive(Clone)]
+struct Slot {
+    placement: GpuInstancePlacement,
+    existing: Option<usize>,
+}
+
+/// A search for placements of the wanted GPU instances, keeping as many
+/// existing ones as possible.
+struct Search<'a> {
+    wanted: &'a [&'a Profile],
+    existing: &'a [MigInstance],
+    /// The most existing instances that could possibly be kept.
+    max_kept: usize,
+    chosen: Vec<Slot>,
+    best: Option<(usize, Vec<Slot>)>,
+}
+
+impl<'a> Search<'a> {
+    fn new(wanted: &'a [&'a Profile], existing: &'a [MigInstance]) -> Self {
+        let max_kept = wanted
+            .iter()
+            .enumerate()
+            .filter(|(i, p)| {
+                // Existing instances of this profile not matched to an earlier
+                // wanted one
+                let earlier = wanted[..*i]
+                    .iter()
+                    .filter(|q| q.info.id == p.info.id)
+                    .count();
+                existing
+                    .iter()
+                    .filter(|e| e.profile_id == p.info.id)
+                    .count()
+                    > earlier
+            })
+            .count();
+
+        Self {
+            wanted,
+            existing,
+            max_kept,
+            chosen: Vec::with_capacity(wanted.len()),
+            best: None,
+        }
+    }
+
+    fn run(mut self) -> Option<Vec<Slot>> {
+        self.step();
+        self.best.map(|(_, slots)| slots)
+    }
+
+    fn done(&self) -> bool {
+        matches!(self.best, Some((kept, _)) if kept == self.max_kept)
+    }
+
+    fn fits(&self, placement: &GpuInstancePlacement) -> bool {
+        self.chosen.iter().all(|slot| {
+            let other = &slot.placement;
+            placement.start + placement.size <= other.start
+                || other.start + other.size <= placement.start
+        })
+    }
+
+    fn step(&mut self) {
+        let profile = match self.wanted.get(self.chosen.len()) {
+            Some(profile) => profile,
+            None => {
+                let kept = self.chosen.iter().filter(|s| s.existing.is_some()).count();
+                if self.best.as_ref().map_or(true, |(best, _)| kept > *best) {
+                    self.best = Some((kept, self.chosen.clone()));
+                }
+                return;
+            }
+        };
+
+        // Existing instances of the profile first, so that they are kept
+        let mut candidates: Vec<Slot> = self
+            .existing
+            .iter()
+            .enumerate()
+            .filter(|(i, e)| {
+                e.profile_id == profile.info.id
+                    && !self.chosen.iter().any(|s| s.existing == Some(*i))
+            })
+            .map(|(i, e)| Slot {
+                placement: e.placement.clone(),
+                existing: Some(i),
+            })
+            .collect();
+        // Recreating an existing instance where it is can't do better than
+        // keeping it
+        candidates.extend(
+            profile
+                .placements
+                .iter()
+                .filter(|p| {
+                    !self
+                        .existing
+                        .iter()
+                        .any(|e| e.profile_id == profile.info.id && e.placement == **p)
+                })
+                .map(|p| Slot {
+                    placement: p.clone(),
+                    existing: None,
+                }),
+        );
+
+        for slot in candidates {
+            if !self.fits(&slot.placement) {
+                continue;
+            }
+
+            self.chosen.push(slot);
+            self.step();
+            self.chosen.pop();
+
+            if self.done() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::NvmlBackend;
+    use crate::ffi::bindings::*;
+    use crate::test_utils::{mock_backend, mock_device};
+    use crate::Nvml;
+    use std::os::raw::c_uint;
+    use std::sync::Mutex;
+
+    #[derive(Clone, Copy)]
+    struct MockProfile {
+        id: c_uint,
+        name: &'static str,
+        /// Compute slices
+        slices: c_uint,
+        instances: c_uint,
+        /// Memory slices
+        size: c_uint,
+        starts: &'static [c_uint],
+    }
+
+    const fn profile(
+        id: c_uint,
+        name: &'static str,
+        slices: c_uint,
+        instances: c_uint,
+        size: c_uint,
+        starts: &'static [c_uint],
+    ) -> MockProfile {
+        MockProfile {
+            id,
+            name,
+            slices,
+            instances,
+            size,
+            starts,
+        }
+    }
+
+    /// The GPU instance profiles of an A100 80GB, by `NVML_GPU_INSTANCE_PROFILE_*`.
+    const PROFILES: [MockProfile; 5] = [
+        profile(19, "MIG 1g.10gb", 1, 7, 1, &[0, 1, 2, 3, 4, 5, 6]),
+        profile(14, "MIG 2g.20gb", 2, 3, 2, &[0, 2, 4]),
+        profile(9, "MIG 3g.40gb", 3, 2, 4, &[0, 4]),
+        profile(5, "MIG 4g.40gb", 4, 1, 4, &[0]),
+        profile(0, "MIG 7g.80gb", 7, 1, 8, &[0]),
+    ];
+
+    /// Compute slices of the `NVML_COMPUTE_INSTANCE_PROFILE_*` the mock
+    /// supports, whose IDs are the constants themselves.
+    const COMPUTE_PROFILES: [(c_uint, c_uint); 5] = [
+        (NVML_COMPUTE_INSTANCE_PROFILE_1_SLICE, 1),
+        (NVML_COMPUTE_INSTANCE_PROFILE_2_SLICE, 2),
+        (NVML_COMPUTE_INSTANCE_PROFILE_3_SLICE, 3),
+        (NVML_COMPUTE_INSTANCE_PROFILE_4_SLICE, 4),
+        (NVML_COMPUTE_INSTANCE_PROFILE_7_SLICE, 7),
+    ];
+
+    /// Compute instance handles are this plus their GPU instance's handle.
+    const COMPUTE_INSTANCE: usize = 1000;
+
+    #[derive(Clone)]
+    struct Gi {
+        /// Index into `PROFILES`
+        profile: usize,
+        start: c_uint,
+        /// The compute instance's profile ID
+        compute_instance: Option<c_uint>,
+    }
+
+    /// One A100 80GB in MIG mode. GPU instance handles are their ID, which is
+    /// their index + 1 in `instances`.
+    #[derive(Default)]
+    struct Mock {
+        instances: Mutex<Vec<Option<Gi>>>,
+    }
+
+    impl Mock {
+        fn with(instances: Vec<Gi>) -> Self {
+            Self {
+                instances: Mutex::new(instances.into_iter().map(Some).collect()),
+            }
+        }
+
+        fn gi(&self, instance: nvmlGpuInstance_t) -> Option<Gi> {
+            let instances = self.instances.lock().unwrap();
+            instance_index(instance as usize)
+                .and_then(|i| instances.get(i))
+                .cloned()
+                .flatten()
+        }
+    }
+
+    /// The index into `Mock::instances` of the GPU instance with the given
+    /// handle, which start at 1.
+    fn instance_index(instance: usize) -> Option<usize> {
+        instance.checked_sub(1)
+    }
+
+    fn profile_by_id(id: c_uint) -> Option<usize> {
+        PROFILES.iter().position(|p| p.id == id)
+    }
+
+    impl NvmlBackend for Mock {
+        mock_backend!();
+
+        unsafe fn nvmlDeviceGetGpuInstanceProfileInfoV(
+            &self,
+            _device: nvmlDevice_t,
+            profile: c_uint,
+            info: *mut nvmlGpuInstanceProfileInfo_v2_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let profile = match PROFILES.get(profile as usize) {
+                Some(p) => p,
+                None => return Ok(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED),
+            };
+
+            (*info).id = profile.id;
+            (*info).sliceCount = profile.slices;
+            (*info).instanceCount = profile.instances;
+            for (i, c) in profile.name.bytes().chain(Some(0)).enumerate() {
+                (*info).name[i] = c as _;
+            }
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetGpuInstancePossiblePlacements_v2(
+            &self,
+            _device: nvmlDevice_t,
+            profile_id: c_uint,
+            placements: *mut nvmlGpuInstancePlacement_t,
+            count: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let MockProfile { size, starts, .. } = match profile_by_id(profile_id) {
+                Some(p) => PROFILES[p],
+                None => return Ok(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT),
+            };
+
+            if !placements.is_null() {
+                for (i, &start) in starts.iter().enumerate() {
+                    *placements.add(i) = nvmlGpuInstancePlacement_t { start, size };
+                }
+            }
+            *count = starts.len() as c_uint;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceCreateGpuInstanceWithPlacement(
+            &self,
+            _device: nvmlDevice_t,
+            profile_id: c_uint,
+            placement: *const nvmlGpuInstancePlacement_t,
+            instance: *mut nvmlGpuInstance_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let profile = match profile_by_id(profile_id) {
+                Some(p) => p,
+                None => return Ok(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT),
+            };
+            let (start, size) = ((*placement).start, PROFILES[profile].size);
+
+            let mut instances = self.instances.lock().unwrap();
+            let overlaps = instances.iter().flatten().any(|gi| {
+                let other_size = PROFILES[gi.profile].size;
+                start < gi.start + other_size && gi.start < start + size
+            });
+            if !PROFILES[profile].starts.contains(&start) || overlaps {
+                return Ok(nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_RESOURCES);
+            }
+
+            instances.push(Some(Gi {
+                profile,
+                start,
+                compute_instance: None,
+            }));
+            *instance = instances.len() as nvmlGpuInstance_t;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlGpuInstanceDestroy(
+            &self,
+            instance: nvmlGpuInstance_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let mut instances = self.instances.lock().unwrap();
+            match instance_index(instance as usize).and_then(|i| instances.get_mut(i)) {
+                Some(Some(gi)) if gi.compute_instance.is_some() => {
+                    Ok(nvmlReturn_enum_NVML_ERROR_IN_USE)
+                }
+                Some(gi @ Some(_)) => {
+                    *gi = None;
+                    Ok(nvmlReturn_enum_NVML_SUCCESS)
+                }
+                _ => Ok(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT),
+            }
+        }
+
+        unsafe fn nvmlDeviceGetGpuInstances(
+            &self,
+            _device: nvmlDevice_t,
+            profile_id: c_uint,
+            instances: *mut nvmlGpuInstance_t,
+            count: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let existing = self.instances.lock().unwrap();
+            let mut n = 0;
+            for (i, gi) in existing.iter().enumerate() {
+                if matches!(gi, Some(gi) if PROFILES[gi.profile].id == profile_id) {
+                    *instances.add(n) = (i + 1) as nvmlGpuInstance_t;
+                    n += 1;
+                }
+            }
+            *count = n as c_uint;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlGpuInstanceGetInfo(
+            &self,
+            instance: nvmlGpuInstance_t,
+            info: *mut nvmlGpuInstanceInfo_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let gi = match self.gi(instance) {
+                Some(gi) => gi,
+                None => return Ok(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT),
+            };
+
+            *info = nvmlGpuInstanceInfo_t {
+                device: mock_device(0),
+                id: instance as c_uint,
+                profileId: PROFILES[gi.profile].id,
+                placement: nvmlGpuInstancePlacement_t {
+                    start: gi.start,
+                    size: PROFILES[gi.profile].size,
+                },
+            };
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlGpuInstanceGetComputeInstanceProfileInfoV(
+            &self,
+            instance: nvmlGpuInstance_t,
+            profile: c_uint,
+            _engine_profile: c_uint,
+            info: *mut nvmlComputeInstanceProfileInfo_v2_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let gi = match self.gi(instance) {
+                Some(gi) => gi,
+                None => return Ok(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT),
+            };
+
+            match COMPUTE_PROFILES.iter().find(|p| p.0 == profile) {
+                Some(&(id, slices)) if slices <= PROFILES[gi.profile].slices => {
+                    (*info).id = id;
+                    (*info).sliceCount = slices;
+                    Ok(nvmlReturn_enum_NVML_SUCCESS)
+                }
+                _ => Ok(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED),
+            }
+        }
+
+        unsafe fn nvmlGpuInstanceGetComputeInstancePossiblePlacements(
+            &self,
+            _instance: nvmlGpuInstance_t,
+            _profile_id: c_uint,
+            placements: *mut nvmlComputeInstancePlacement_t,
+            count: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            if !placements.is_null() {
+                *placements = nvmlComputeInstancePlacement_t { start: 0, size: 1 };
+            }
+            *count = 1;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlGpuInstanceCreateComputeInstance(
+            &self,
+            instance: nvmlGpuInstance_t,
+            profile_id: c_uint,
+            compute_instance: *mut nvmlComputeInstance_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let mut instances = self.instances.lock().unwrap();
+            match instance_index(instance as usize).and_then(|i| instances.get_mut(i)) {
+                Some(Some(gi)) if gi.compute_instance.is_none() => {
+                    gi.compute_instance = Some(profile_id);
+                    *compute_instance = (COMPUTE_INSTANCE + instance as usize) as _;
+                    Ok(nvmlReturn_enum_NVML_SUCCESS)
+                }
+                Some(Some(_)) => Ok(nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_RESOURCES),
+                _ => Ok(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT),
+            }
+        }
+
+        unsafe fn nvmlGpuInstanceGetComputeInstances(
+            &self,
+            instance: nvmlGpuInstance_t,
+            profile_id: c_uint,
+            compute_instances: *mut nvmlComputeInstance_t,
+            count: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *count = 0;
+            if let Some(gi) = self.gi(instance) {
+                if gi.compute_instance == Some(profile_id) {
+                    *compute_instances = (COMPUTE_INSTANCE + instance as usize) as _;
+                    *count = 1;
+                }
+            }
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlComputeInstanceDestroy(
+            &self,
+            compute_instance: nvmlComputeInstance_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let mut instances = self.instances.lock().unwrap();
+            let instance = (compute_instance as usize).checked_sub(COMPUTE_INSTANCE);
+            match instance
+                .and_then(instance_index)
+                .and_then(|i| instances.get_mut(i))
+            {
+                Some(Some(gi)) if gi.compute_instance.is_some() => {
+                    gi.compute_instance = None;
+                    Ok(nvmlReturn_enum_NVML_SUCCESS)
+                }
+                _ => Ok(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT),
+            }
+        }
+    }
+
+    fn nvml(mock: Mock) -> Nvml {
+        Nvml::builder()
+            .init_with_backend(mock)
+            .expect("initialized mock")
+    }
+
+    fn instance(
+        profile: &str,
+        profile_id: u32,
+        start: u32,
+        size: u32,
+        id: Option<u32>,
+    ) -> MigInstance {
+        MigInstance {
+            profile: profile.into(),
+            profile_id,
+            placement: GpuInstancePlacement { start, size },
+            id,
+        }
+    }
+
+    #[test]
+    fn plans_and_applies_idempotently() {
+        // 3g.40gb at 4-7 and 1g.10gb at 1 and 3, each with a compute instance
+        let nvml = nvml(Mock::with(vec![
+            Gi {
+                profile: 2,
+                start: 4,
+                compute_instance: Some(NVML_COMPUTE_INSTANCE_PROFILE_3_SLICE),
+            },
+            Gi {
+                profile: 0,
+                start: 1,
+                compute_instance: Some(NVML_COMPUTE_INSTANCE_PROFILE_1_SLICE),
+            },
+            Gi {
+                profile: 0,
+                start: 3,
+                compute_instance: Some(NVML_COMPUTE_INSTANCE_PROFILE_1_SLICE),
+            },
+        ]));
+        let device = nvml.device_by_index(0).unwrap();
+        let layout = MigLayout::new(["3g.40gb", "1g.10gb", "MIG 2g.20gb", "1g.10gb"]);
+
+        // Both 1g.10gb can't stay where they are with a 2g.20gb in slices 0-3
+        let expected = MigPlan {
+            keep: vec![
+                instance("3g.40gb", 9, 4, 4, Some(1)),
+                instance("1g.10gb", 19, 3, 1, Some(3)),
+            ],
+            destroy: vec![instance("1g.10gb", 19, 1, 1, Some(2))],
+            create: vec![
+                instance("2g.20gb", 14, 0, 2, None),
+                instance("1g.10gb", 19, 2, 1, None),
+            ],
+        };
+        assert_eq!(layout.plan(&device).unwrap(), expected);
+
+        let applied = layout.apply(&device).unwrap();
+        assert_eq!(applied.keep, expected.keep);
+        assert_eq!(applied.destroy, expected.destroy);
+        assert_eq!(
+            applied.create,
+            [
+                instance("2g.20gb", 14, 0, 2, Some(4)),
+                instance("1g.10gb", 19, 2, 1, Some(5)),
+            ]
+        );
+
+        let plan = layout.plan(&device).unwrap();
+        assert!(plan.is_empty());
+        assert_eq!(plan.keep.len(), 4);
+        assert!(layout.apply(&device).unwrap().is_empty());
+
+        // Every created GPU instance got a compute instance spanning it
+        let gi = device.gpu_instances(14).unwrap().remove(0);
+        let info = gi
+            .compute_instance_profile_info(
+                ComputeInstanceProfile::TwoSlices,
+                ComputeInstanceEngineProfile::Shared,
+            )
+            .unwrap();
+        assert_eq!(gi.compute_instances(info.id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn tears_everything_down_for_an_empty_layout() {
+        let nvml = nvml(Mock::with(vec![Gi {
+            profile: 4,
+            start: 0,
+            compute_instance: Some(NVML_COMPUTE_INSTANCE_PROFILE_7_SLICE),
+        }]));
+        let device = nvml.device_by_index(0).unwrap();
+
+        let plan = MigLayout::new(Vec::<String>::new()).apply(&device).unwrap();
+        assert_eq!(plan.destroy, [instance("7g.80gb", 0, 0, 8, Some(1))]);
+        assert!(device.gpu_instances(0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn backtracks_to_find_a_fit() {
+        let nvml = nvml(Mock::default());
+        let device = nvml.device_by_index(0).unwrap();
+
+        // 3g.40gb at 0-3 leaves no 2g.20gb placement that leaves room for two
+        // 1g.10gb, since no 1g.10gb can be placed at 7
+        let plan = MigLayout::new(["1g.10gb", "1g.10gb", "2g.20gb", "3g.40gb"])
+            .compute_instances(false)
+            .plan(&device)
+            .unwrap();
+
+        assert_eq!(
+            plan.create,
+            [
+                instance("3g.40gb", 9, 4, 4, None),
+                instance("2g.20gb", 14, 0, 2, None),
+                instance("1g.10gb", 19, 2, 1, None),
+                instance("1g.10gb", 19, 3, 1, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_layouts_are_rejected() {
+        let nvml = nvml(Mock::default());
+        let device = nvml.device_by_index(0).unwrap();
+        let plan = |profiles: &[&str]| MigLayout::new(profiles.iter().copied()).plan(&device);
+
+        assert!(matches!(
+            plan(&["3g.40gb", "5g.50gb"]),
+            Err(MigLayoutError::UnknownProfile(name)) if name == "5g.50gb"
+        ));
+        assert!(matches!(
+            plan(&["3g.40gb", "3g.40gb", "3g.40gb"]),
+            Err(MigLayoutError::TooManyInstances {
+                requested: 3,
+                max: 2,
+                ..
+            })
+        ));
+        assert!(matches!(
+            plan(&["4g.40gb", "3g.40gb", "1g.10gb"]),
+            Err(MigLayoutError::DoesNotFit)
+        ));
+    }
+}
@@ -1,4 +1,12 @@
 #[cfg(target_os = "linux")]
 pub mod event_loop;
+pub mod memory_health;
+#[cfg(target_os = "linux")]
+pub mod mig;
+pub mod vgpu_migration;
 #[cfg(target_os = "linux")]
 pub use self::event_loop::{Event, EventLoop, EventLoopProvider};
+pub use self::memory_health::{MemoryHealth, MemoryHealthReason, MemoryHealthVerdict};
+#[cfg(target_os = "linux")]
+pub use self::mig::{MigInstance, MigLayout, MigLayoutError, MigPlan};
+pub use self::vgpu_migration::{MigrationCheck, MigrationReason};
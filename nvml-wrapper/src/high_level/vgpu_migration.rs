@@ -0,0 +1,389 @@
+/*!
+vGPU migration pre-checks.
+
+Before moving a VM between hosts, check that its vGPU is compatible with the
+physical GPU it would land on. The vGPU's metadata is read on the source host,
+sent to the destination host (it can be serialized with the `serde` feature
+enabled) and checked there:
+
+```no_run
+# extern crate nvml_wrapper as nvml;
+#
+# #[cfg(target_os = "linux")]
+# fn main() {
+#     example::actual_main().unwrap();
+# }
+#
+# #[cfg(target_os = "windows")]
+# fn main() {}
+#
+# #[cfg(target_os = "linux")]
+# mod example {
+# use nvml::Nvml;
+# use nvml::error::NvmlError;
+# use nvml::high_level::vgpu_migration::check_migration;
+#
+# pub fn actual_main() -> Result<(), NvmlError> {
+# let nvml = Nvml::init()?;
+# let source = nvml.device_by_index(0)?;
+# let destination = nvml.device_by_index(1)?;
+// On the source host
+let metadata = source.vgpu_instances()?[0].metadata()?;
+
+// On the destination host, with `metadata` sent over
+let check = check_migration(&nvml, &metadata, &destination.vgpu_metadata()?)?;
+if !check.can_migrate_live() {
+    for reason in &check.reasons {
+        println!("can't live migrate: {}", reason);
+    }
+}
+# Ok(())
+# }
+# }
+```
+*/
+
+use crate::bitmasks::device::{
+    VgpuCompatibilityLimit, VgpuVirtualizationCaps, VgpuVmCompatibility,
+};
+use crate::enum_wrappers::device::VgpuGuestInfoState;
+use crate::error::NvmlError;
+use crate::struct_wrappers::device::{VgpuMetadata, VgpuPgpuMetadata, VgpuVersion};
+use crate::vgpu::VgpuInstance;
+use crate::{Device, Nvml};
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+use std::fmt;
+
+/// Whether a vGPU can be migrated to a physical GPU, and why not.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MigrationCheck {
+    /// The kinds of migration the vGPU's VM is compatible with, according to
+    /// NVML.
+    pub compatibility: VgpuVmCompatibility,
+    /// What limits the compatibility, according to NVML.
+    pub limit: VgpuCompatibilityLimit,
+    /// Why the vGPU can't be migrated freely, from `limit` and from comparing
+    /// the metadata. Empty if nothing stands in the way.
+    pub reasons: Vec<MigrationReason>,
+}
+
+impl MigrationCheck {
+    /// Whether the VM can be migrated in the given way (e.g.
+    /// `VgpuVmCompatibility::COLD`).
+    pub fn allows(&self, kind: VgpuVmCompatibility) -> bool {
+        self.compatibility.contains(kind)
+    }
+
+    /// Whether the VM can be migrated while running.
+    pub fn can_migrate_live(&self) -> bool {
+        self.allows(VgpuVmCompatibility::LIVE)
+    }
+}
+
+/// Something standing in the way of migrating a vGPU to a physical GPU.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MigrationReason {
+    /// The vGPU doesn't support migration.
+    VgpuMigrationUnsupported,
+    /// The destination physical GPU doesn't support migration.
+    PgpuMigrationUnsupported,
+    /// The vGPU version of the VM's guest driver is outside the range the
+    /// destination host driver supports.
+    GuestVgpuVersion {
+        version: u32,
+        supported: VgpuVersion,
+    },
+    /// NVML reports the destination host driver as a limit.
+    HostDriver { source: String, destination: String },
+    /// NVML reports the VM's guest driver as a limit.
+    GuestDriver { version: String },
+    /// NVML reports the destination physical GPU as a limit.
+    Gpu,
+    /// NVML reports some other limit.
+    Other,
+}
+
+impl fmt::Display for MigrationReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::VgpuMigrationUnsupported => write!(f, "the vGPU does not support migration"),
+            Self::PgpuMigrationUnsupported => {
+                write!(f, "the destination GPU does not support migration")
+            }
+            Self::GuestVgpuVersion { version, supported } => write!(
+                f,
+                "guest vGPU version {:#x} is outside the destination's supported range \
+                 {:#x} to {:#x}",
+                version, supported.min, supported.max
+            ),
+            Self::HostDriver {
+                source,
+                destination,
+            } => write!(
+                f,
+                "host driver {} is not compatible with destination host driver {}",
+                source, destination
+            ),
+            Self::GuestDriver { version } => {
+                write!(f, "guest driver {} is not compatible", version)
+            }
+            Self::Gpu => write!(f, "the destination GPU is not compatible"),
+            Self::Other => write!(f, "an unspecified incompatibility"),
+        }
+    }
+}
+
+/**
+Check whether the vGPU described by `vgpu` can be migrated to the physical GPU
+described by `pgpu`.
+
+This is `Nvml.vgpu_compatibility()`, with the limits it reports (and the
+problems visible in the metadata) explained in `MigrationCheck.reasons`.
+
+# Errors
+
+The same as `Nvml.vgpu_compatibility()`.
+*/
+pub fn check_migration(
+    nvml: &Nvml,
+    vgpu: &VgpuMetadata,
+    pgpu: &VgpuPgpuMetadata,
+) -> Result<MigrationCheck, NvmlError> {
+    let compatibility = nvml.vgpu_compatibility(vgpu, pgpu)?;
+    let mut reasons = Vec::new();
+
+    if !vgpu
+        .virtualization_caps
+        .contains(VgpuVirtualizationCaps::MIGRATION)
+    {
+        reasons.push(MigrationReason::VgpuMigrationUnsupported);
+    }
+
+    if !pgpu
+        .virtualization_caps
+        .contains(VgpuVirtualizationCaps::MIGRATION)
+    {
+        reasons.push(MigrationReason::PgpuMigrationUnsupported);
+    }
+
+    let supported = &pgpu.host_supported_vgpu_range;
+    if vgpu.guest_info_state == VgpuGuestInfoState::Initialized
+        && !(supported.min..=supported.max).contains(&vgpu.guest_vgpu_version)
+    {
+        reasons.push(MigrationReason::GuestVgpuVersion {
+            version: vgpu.guest_vgpu_version,
+            supported: supported.clone(),
+        });
+    }
+
+    let limit = compatibility.limit;
+    if limit.contains(VgpuCompatibilityLimit::HOST_DRIVER) {
+        reasons.push(MigrationReason::HostDriver {
+            source: vgpu.host_driver_version.clone(),
+            destination: pgpu.host_driver_version.clone(),
+        });
+    }
+    if limit.contains(VgpuCompatibilityLimit::GUEST_DRIVER) {
+        reasons.push(MigrationReason::GuestDriver {
+            version: vgpu.guest_driver_version.clone(),
+        });
+    }
+    if limit.contains(VgpuCompatibilityLimit::GPU) {
+        reasons.push(MigrationReason::Gpu);
+    }
+    if limit.contains(VgpuCompatibilityLimit::OTHER) {
+        reasons.push(MigrationReason::Other);
+    }
+
+    Ok(MigrationCheck {
+        compatibility: compatibility.vm_compatibility,
+        limit,
+        reasons,
+    })
+}
+
+/**
+Check whether `vgpu` can be migrated to `target`, a physical GPU on the same
+host.
+
+# Errors
+
+The same as `VgpuInstance.metadata()`, `Device.vgpu_metadata()` and
+`check_migration()`.
+*/
+pub fn check_migration_to(
+    vgpu: &VgpuInstance,
+    target: &Device,
+) -> Result<MigrationCheck, NvmlError> {
+    check_migration(target.nvml(), &vgpu.metadata()?, &target.vgpu_metadata()?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::NvmlBackend;
+    use crate::ffi::bindings::*;
+    use crate::test_utils::mock_backend;
+    use std::mem;
+    use std::os::raw::c_uint;
+
+    /// One GPU running one vGPU (7). NVML finds the vGPU compatible with a
+    /// physical GPU if their opaque data and host drivers match.
+    struct Mock;
+
+    fn vgpu_metadata() -> VgpuMetadata {
+        VgpuMetadata {
+            version: 1,
+            revision: 1,
+            guest_info_state: VgpuGuestInfoState::Initialized,
+            guest_driver_version: "550.54.14".into(),
+            host_driver_version: "550.54.16".into(),
+            virtualization_caps: VgpuVirtualizationCaps::MIGRATION,
+            guest_vgpu_version: 0x120001,
+            opaque_data: b"ampere-a100".to_vec(),
+        }
+    }
+
+    fn pgpu_metadata() -> VgpuPgpuMetadata {
+        VgpuPgpuMetadata {
+            version: 1,
+            revision: 1,
+            host_driver_version: "550.54.16".into(),
+            virtualization_caps: VgpuVirtualizationCaps::MIGRATION,
+            host_supported_vgpu_range: VgpuVersion {
+                min: 0x110001,
+                max: 0x120001,
+            },
+            opaque_data: b"ampere-a100".to_vec(),
+        }
+    }
+
+    /// Copy `words` to NVML's `size`-byte `buffer`, or report the size needed.
+    unsafe fn write_buffer<T>(words: &[u32], buffer: *mut T, size: *mut c_uint) -> nvmlReturn_t {
+        let needed = mem::size_of_val(words) as c_uint;
+        if buffer.is_null() || *size < needed {
+            *size = needed;
+            return nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE;
+        }
+
+        std::ptr::copy_nonoverlapping(words.as_ptr(), buffer as *mut u32, words.len());
+        nvmlReturn_enum_NVML_SUCCESS
+    }
+
+    /// Read the metadata struct `T` and its opaque data from NVML's `buffer`.
+    unsafe fn read_buffer<T: Copy>(buffer: *const T, opaque_size: fn(&T) -> u32) -> (T, Vec<u8>) {
+        let raw = *buffer;
+        let offset = mem::size_of::<T>() - 4;
+        let opaque = std::slice::from_raw_parts(
+            (buffer as *const u8).add(offset),
+            opaque_size(&raw) as usize,
+        );
+
+        (raw, opaque.to_vec())
+    }
+
+    impl NvmlBackend for Mock {
+        mock_backend!();
+
+        unsafe fn nvmlVgpuInstanceGetMetadata(
+            &self,
+            _instance: nvmlVgpuInstance_t,
+            metadata: *mut nvmlVgpuMetadata_t,
+            size: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let words = vgpu_metadata().try_into_c_buffer()?;
+            Ok(write_buffer(&words, metadata, size))
+        }
+
+        unsafe fn nvmlDeviceGetVgpuMetadata(
+            &self,
+            _device: nvmlDevice_t,
+            metadata: *mut nvmlVgpuPgpuMetadata_t,
+            size: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let words = pgpu_metadata().try_into_c_buffer()?;
+            Ok(write_buffer(&words, metadata, size))
+        }
+
+        unsafe fn nvmlGetVgpuCompatibility(
+            &self,
+            vgpu: *mut nvmlVgpuMetadata_t,
+            pgpu: *mut nvmlVgpuPgpuMetadata_t,
+            compatibility: *mut nvmlVgpuPgpuCompatibility_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let (vgpu, vgpu_opaque) = read_buffer(vgpu, |m| m.opaqueDataSize);
+            let (pgpu, pgpu_opaque) = read_buffer(pgpu, |m| m.opaqueDataSize);
+
+            let mut limit = 0;
+            if vgpu.hostDriverVersion != pgpu.hostDriverVersion {
+                limit |= nvmlVgpuPgpuCompatibilityLimitCode_enum_NVML_VGPU_COMPATIBILITY_LIMIT_HOST_DRIVER;
+            }
+            if vgpu_opaque != pgpu_opaque {
+                limit |= nvmlVgpuPgpuCompatibilityLimitCode_enum_NVML_VGPU_COMPATIBILITY_LIMIT_GPU;
+            }
+
+            (*compatibility).compatibilityLimitCode = limit;
+            (*compatibility).vgpuVmCompatibility = match limit {
+                0 => {
+                    nvmlVgpuVmCompatibility_enum_NVML_VGPU_VM_COMPATIBILITY_LIVE
+                        | nvmlVgpuVmCompatibility_enum_NVML_VGPU_VM_COMPATIBILITY_COLD
+                }
+                _ => nvmlVgpuVmCompatibility_enum_NVML_VGPU_VM_COMPATIBILITY_NONE,
+            };
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+    }
+
+    #[test]
+    fn matching_gpu_allows_live_migration() {
+        let nvml = Nvml::builder().init_with_backend(Mock).unwrap();
+        let device = nvml.device_by_index(0).unwrap();
+        let instance = VgpuInstance::new(&device, 7);
+
+        assert_eq!(instance.metadata().unwrap(), vgpu_metadata());
+        assert_eq!(device.vgpu_metadata().unwrap(), pgpu_metadata());
+
+        let check = check_migration_to(&instance, &device).unwrap();
+        assert!(check.can_migrate_live());
+        assert!(check.allows(VgpuVmCompatibility::COLD));
+        assert!(check.limit.is_empty());
+        assert!(check.reasons.is_empty());
+    }
+
+    #[test]
+    fn mismatches_are_explained() {
+        let nvml = Nvml::builder().init_with_backend(Mock).unwrap();
+
+        let mut pgpu = pgpu_metadata();
+        pgpu.host_driver_version = "535.161.05".into();
+        pgpu.host_supported_vgpu_range.max = 0x110001;
+        pgpu.opaque_data = b"ada-l40".to_vec();
+
+        let check = check_migration(&nvml, &vgpu_metadata(), &pgpu).unwrap();
+        assert!(check.compatibility.is_empty());
+        assert_eq!(
+            check.limit,
+            VgpuCompatibilityLimit::HOST_DRIVER | VgpuCompatibilityLimit::GPU
+        );
+        assert_eq!(
+            check.reasons,
+            vec![
+                MigrationReason::GuestVgpuVersion {
+                    version: 0x120001,
+                    supported: VgpuVersion {
+                        min: 0x110001,
+                        max: 0x110001
+                    },
+                },
+                MigrationReason::HostDriver {
+                    source: "550.54.16".into(),
+                    destination: "535.161.05".into(),
+                },
+                MigrationReason::Gpu,
+            ]
+        );
+    }
+}
@@ -49,6 +49,18 @@ each time it gets called. Instead, call `Nvml::init` once and store the resultin
 `Nvml` instance somewhere to be accessed throughout the lifetime of your program
 (perhaps in a [`once_cell`][once_cell]).
 
+Libraries that can't coordinate on where that instance lives can call
+`Nvml::shared()` instead, which hands out `Arc<Nvml>` handles to one
+process-wide instance and shuts NVML down when the last handle is dropped.
+
+Errors are plain `NvmlError`s. Making calls inside `Nvml::with_error_context()`
+returns `NvmlErrorWithContext`s instead, which add which NVML function failed,
+its raw return code and which device the call concerned.
+
+`Device`s borrow the `Nvml` instance they come from. To keep devices in
+long-lived structs or move them into other threads or tasks, put the `Nvml`
+instance in an `Arc` and use `OwnedDevice` instead; see the `owned` module.
+
 ## NVML Support
 
 This wrapper is being developed against and currently supports NVML version
@@ -85,6 +97,15 @@ way to avoid bumping this.
 The `serde` feature can be toggled on in order to `#[derive(Serialize, Deserialize)]`
 for every NVML data structure.
 
+The `record-replay` feature adds `NvmlBuilder::record_to()` and
+`NvmlBuilder::replay_from()`, which log every NVML call to a file and serve
+calls from such a file later on, without a driver present. See the `record`
+module for details.
+
+Independently of features, `NvmlBuilder::faults()` makes chosen NVML calls fail
+in chosen ways, to test error handling without broken hardware. See the `fault`
+module for details.
+
 [nvml]: https://developer.nvidia.com/nvidia-management-library-nvml
 [libloading]: https://github.com/nagisa/rust_libloading
 [once_cell]: https://docs.rs/once_cell/latest/once_cell/sync/struct.Lazy.html
@@ -96,15 +117,26 @@ for every NVML data structure.
 extern crate libloading;
 extern crate nvml_wrapper_sys as ffi;
 
+mod args;
+pub mod backend;
 pub mod bitmasks;
+pub mod compute_instance;
+pub mod conformance;
+mod context;
 pub mod device;
 pub mod enum_wrappers;
 pub mod enums;
 pub mod error;
 pub mod event;
+pub mod fault;
 pub mod gpm;
+pub mod gpu_instance;
 pub mod high_level;
 pub mod nv_link;
+pub mod owned;
+#[cfg(feature = "record-replay")]
+pub mod record;
+mod shared;
 pub mod struct_wrappers;
 pub mod structs;
 #[cfg(test)]
@@ -113,10 +145,13 @@ pub mod unit;
 pub mod vgpu;
 
 // Re-exports for convenience
+pub use crate::compute_instance::ComputeInstance;
 pub use crate::device::Device;
 pub use crate::event::EventSet;
 pub use crate::gpm::GpmSample;
+pub use crate::gpu_instance::GpuInstance;
 pub use crate::nv_link::NvLink;
+pub use crate::owned::{OwnedDevice, OwnedUnit};
 pub use crate::unit::Unit;
 
 /// Re-exports from `nvml-wrapper-sys` that are necessary for use of this wrapper.
@@ -129,6 +164,8 @@ pub mod sys_exports {
 
 #[cfg(target_os = "linux")]
 use std::convert::TryInto;
+#[cfg(feature = "record-replay")]
+use std::path::Path;
 #[cfg(target_os = "linux")]
 use std::ptr;
 use std::{
@@ -143,15 +180,23 @@ use static_assertions::assert_impl_all;
 #[cfg(target_os = "linux")]
 use crate::enum_wrappers::device::TopologyLevel;
 
-use crate::error::{nvml_sym, nvml_try, NvmlError};
+use crate::backend::NvmlBackend;
+use crate::context::{ContextRecorder, LastContext};
+use crate::error::{nvml_try, NvmlError, NvmlErrorWithContext};
+use crate::fault::{FaultInjector, Faults};
 use crate::ffi::bindings::*;
+#[cfg(feature = "record-replay")]
+use crate::record::{Recorder, Replayer};
 
 use crate::struct_wrappers::ExcludedDeviceInfo;
 
 #[cfg(target_os = "linux")]
 use crate::struct_wrappers::device::PciInfo;
-use crate::struct_wrappers::device::VgpuVersion;
+use crate::struct_wrappers::device::{
+    VgpuMetadata, VgpuPgpuCompatibility, VgpuPgpuMetadata, VgpuVersion,
+};
 use crate::struct_wrappers::unit::HwbcEntry;
+use crate::structs::device::MigDeviceInfo;
 
 use crate::bitmasks::InitFlags;
 
@@ -212,7 +257,7 @@ pub enum FieldIdScheme {
 }
 
 pub struct Nvml {
-    lib: ManuallyDrop<NvmlLib>,
+    backend: ManuallyDrop<Box<dyn NvmlBackend>>,
     field_id_scheme: FieldIdScheme,
 }
 
@@ -284,24 +329,9 @@ impl Nvml {
     }
 
     fn init_internal(path: impl AsRef<std::ffi::OsStr>) -> Result<Self, NvmlError> {
-        let lib = unsafe {
-            let lib = NvmlLib::new(path)?;
-            let sym = nvml_sym(lib.nvmlInit_v2.as_ref())?;
-
-            nvml_try(sym())?;
-            ManuallyDrop::new(lib)
-        };
+        let lib = unsafe { NvmlLib::new(path)? };
 
-        let mut nvml = Self {
-            lib,
-            field_id_scheme: FieldIdScheme::V12,
-        };
-        nvml.field_id_scheme = nvml
-            .sys_driver_version()
-            .map(|v| detect_field_id_scheme(&v))
-            .unwrap_or(FieldIdScheme::V12);
-
-        Ok(nvml)
+        Self::init_with_backend_internal(Box::new(lib), None)
     }
 
     /**
@@ -340,16 +370,24 @@ impl Nvml {
         path: impl AsRef<std::ffi::OsStr>,
         flags: InitFlags,
     ) -> Result<Self, NvmlError> {
-        let lib = unsafe {
-            let lib = NvmlLib::new(path)?;
-            let sym = nvml_sym(lib.nvmlInitWithFlags.as_ref())?;
+        let lib = unsafe { NvmlLib::new(path)? };
 
-            nvml_try(sym(flags.bits()))?;
-            ManuallyDrop::new(lib)
-        };
+        Self::init_with_backend_internal(Box::new(lib), Some(flags))
+    }
+
+    fn init_with_backend_internal(
+        backend: Box<dyn NvmlBackend>,
+        flags: Option<InitFlags>,
+    ) -> Result<Self, NvmlError> {
+        unsafe {
+            match flags {
+                Some(flags) => nvml_try(backend.nvmlInitWithFlags(flags.bits())?)?,
+                None => nvml_try(backend.nvmlInit_v2()?)?,
+            }
+        }
 
         let mut nvml = Self {
-            lib,
+            backend: ManuallyDrop::new(backend),
             field_id_scheme: FieldIdScheme::V12,
         };
         nvml.field_id_scheme = nvml
@@ -365,9 +403,83 @@ impl Nvml {
         NvmlBuilder::default()
     }
 
-    /// Get the underlying `NvmlLib` instance.
+    /**
+    Get the underlying `NvmlLib` instance.
+
+    # Panics
+
+    Panics if this `Nvml` was initialized with a backend other than the NVML
+    library; use `.backend()` instead.
+    */
     pub fn lib(&self) -> &NvmlLib {
-        &self.lib
+        self.backend
+            .lib()
+            .expect("this `Nvml` instance is not backed by the NVML library")
+    }
+
+    /// Get the backend this `Nvml` instance calls into.
+    pub fn backend(&self) -> &dyn NvmlBackend {
+        &**self.backend
+    }
+
+    /**
+    Run `f` with a view of this instance that records the context of failing
+    NVML calls, and attach it to the error `f` returns.
+
+    The view is this same instance (devices and other handles obtained through
+    it are the same NVML objects) but calls made through it also record the
+    function, return code and device handle of the most recent failing call.
+    Calls made through `self` elsewhere are unaffected and record nothing.
+
+    The recorded context doesn't include the code's description or the
+    device's identity; call `.resolve()` on the error to query them.
+
+    # Examples
+
+    ```
+    # use nvml_wrapper::Nvml;
+    # use nvml_wrapper::error::NvmlError;
+    # fn main() -> Result<(), NvmlError> {
+    # let nvml = match Nvml::init() {
+    #     Ok(nvml) => nvml,
+    #     Err(_) => return Ok(()),
+    # };
+    let power = nvml.with_error_context(|nvml| nvml.device_by_index(0)?.power_usage());
+
+    if let Err(e) = power {
+        // Still matches like a plain `NvmlError`
+        if let NvmlError::GpuLost = e.error {
+            eprintln!("{}", e.resolve(&nvml));
+        }
+    }
+    # Ok(())
+    # }
+    ```
+    */
+    pub fn with_error_context<T, F>(&self, f: F) -> Result<T, NvmlErrorWithContext>
+    where
+        F: FnOnce(&Nvml) -> Result<T, NvmlError>,
+    {
+        let last = LastContext::default();
+
+        // SAFETY: the view only lives until the end of this function, and `f`
+        // can't keep anything that borrows it
+        let backend = unsafe { ContextRecorder::new(&**self.backend, last.clone()) };
+        let mut view = ManuallyDrop::new(Nvml {
+            backend: ManuallyDrop::new(Box::new(backend)),
+            field_id_scheme: self.field_id_scheme,
+        });
+
+        let result = f(&view);
+
+        // The view must not shut NVML down, so only its recorder is dropped
+        // SAFETY: `view` is not used after this
+        unsafe { ManuallyDrop::drop(&mut view.backend) };
+
+        result.map_err(|e| {
+            let context = last.lock().unwrap_or_else(|e| e.into_inner()).take();
+            NvmlErrorWithContext::new(e, context)
+        })
     }
 
     /// Returns the detected field ID numbering scheme for the loaded driver.
@@ -389,18 +501,16 @@ impl Nvml {
     // Tested
     #[doc(alias = "nvmlShutdown")]
     pub fn shutdown(mut self) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.lib.nvmlShutdown.as_ref())?;
-
         unsafe {
-            nvml_try(sym())?;
+            nvml_try(self.backend.nvmlShutdown()?)?;
         }
 
-        // SAFETY: we `mem::forget(self)` after this, so `self.lib` won't get
+        // SAFETY: we `mem::forget(self)` after this, so `self.backend` won't get
         // touched by our `Drop` impl
-        let lib = unsafe { ManuallyDrop::take(&mut self.lib) };
+        let backend = unsafe { ManuallyDrop::take(&mut self.backend) };
         mem::forget(self);
 
-        Ok(lib.__library.close()?)
+        backend.close()
     }
 
     /**
@@ -417,11 +527,9 @@ impl Nvml {
     // Tested
     #[doc(alias = "nvmlDeviceGetCount_v2")]
     pub fn device_count(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.lib.nvmlDeviceGetCount_v2.as_ref())?;
-
         unsafe {
             let mut count: c_uint = mem::zeroed();
-            nvml_try(sym(&mut count))?;
+            nvml_try(self.backend.nvmlDeviceGetCount_v2(&mut count)?)?;
 
             Ok(count)
         }
@@ -440,15 +548,13 @@ impl Nvml {
     // Tested
     #[doc(alias = "nvmlSystemGetDriverVersion")]
     pub fn sys_driver_version(&self) -> Result<String, NvmlError> {
-        let sym = nvml_sym(self.lib.nvmlSystemGetDriverVersion.as_ref())?;
-
         unsafe {
             let mut version_vec = vec![0; NVML_SYSTEM_DRIVER_VERSION_BUFFER_SIZE as usize];
 
-            nvml_try(sym(
+            nvml_try(self.backend.nvmlSystemGetDriverVersion(
                 version_vec.as_mut_ptr(),
                 NVML_SYSTEM_DRIVER_VERSION_BUFFER_SIZE,
-            ))?;
+            )?)?;
 
             let version_raw = CStr::from_ptr(version_vec.as_ptr());
             Ok(version_raw.to_str()?.into())
@@ -467,15 +573,13 @@ impl Nvml {
     // Tested
     #[doc(alias = "nvmlSystemGetNVMLVersion")]
     pub fn sys_nvml_version(&self) -> Result<String, NvmlError> {
-        let sym = nvml_sym(self.lib.nvmlSystemGetNVMLVersion.as_ref())?;
-
         unsafe {
             let mut version_vec = vec![0; NVML_SYSTEM_NVML_VERSION_BUFFER_SIZE as usize];
 
-            nvml_try(sym(
+            nvml_try(self.backend.nvmlSystemGetNVMLVersion(
                 version_vec.as_mut_ptr(),
                 NVML_SYSTEM_NVML_VERSION_BUFFER_SIZE,
-            ))?;
+            )?)?;
 
             // Thanks to `Amaranth` on IRC for help with this
             let version_raw = CStr::from_ptr(version_vec.as_ptr());
@@ -498,11 +602,12 @@ impl Nvml {
     */
     #[doc(alias = "nvmlSystemGetCudaDriverVersion_v2")]
     pub fn sys_cuda_driver_version(&self) -> Result<i32, NvmlError> {
-        let sym = nvml_sym(self.lib.nvmlSystemGetCudaDriverVersion_v2.as_ref())?;
-
         unsafe {
             let mut version: c_int = mem::zeroed();
-            nvml_try(sym(&mut version))?;
+            nvml_try(
+                self.backend
+                    .nvmlSystemGetCudaDriverVersion_v2(&mut version)?,
+            )?;
 
             Ok(version)
         }
@@ -527,12 +632,14 @@ impl Nvml {
     // Tested
     #[doc(alias = "nvmlSystemGetProcessName")]
     pub fn sys_process_name(&self, pid: u32, length: usize) -> Result<String, NvmlError> {
-        let sym = nvml_sym(self.lib.nvmlSystemGetProcessName.as_ref())?;
-
         unsafe {
             let mut name_vec = vec![0; length];
 
-            nvml_try(sym(pid, name_vec.as_mut_ptr(), length as c_uint))?;
+            nvml_try(self.backend.nvmlSystemGetProcessName(
+                pid,
+                name_vec.as_mut_ptr(),
+                length as c_uint,
+            )?)?;
 
             let name_raw = CStr::from_ptr(name_vec.as_ptr());
             Ok(name_raw.to_str()?.into())
@@ -572,11 +679,12 @@ impl Nvml {
     // Tested
     #[doc(alias = "nvmlDeviceGetHandleByIndex_v2")]
     pub fn device_by_index(&self, index: u32) -> Result<Device<'_>, NvmlError> {
-        let sym = nvml_sym(self.lib.nvmlDeviceGetHandleByIndex_v2.as_ref())?;
-
         unsafe {
             let mut device: nvmlDevice_t = mem::zeroed();
-            nvml_try(sym(index, &mut device))?;
+            nvml_try(
+                self.backend
+                    .nvmlDeviceGetHandleByIndex_v2(index, &mut device)?,
+            )?;
 
             Ok(Device::new(device, self))
         }
@@ -612,13 +720,14 @@ impl Nvml {
     where
         Vec<u8>: From<S>,
     {
-        let sym = nvml_sym(self.lib.nvmlDeviceGetHandleByPciBusId_v2.as_ref())?;
-
         unsafe {
             let c_string = CString::new(pci_bus_id)?;
             let mut device: nvmlDevice_t = mem::zeroed();
 
-            nvml_try(sym(c_string.as_ptr(), &mut device))?;
+            nvml_try(
+                self.backend
+                    .nvmlDeviceGetHandleByPciBusId_v2(c_string.as_ptr(), &mut device)?,
+            )?;
 
             Ok(Device::new(device, self))
         }
@@ -633,13 +742,14 @@ impl Nvml {
     where
         Vec<u8>: From<S>,
     {
-        let sym = nvml_sym(self.lib.nvmlDeviceGetHandleBySerial.as_ref())?;
-
         unsafe {
             let c_string = CString::new(board_serial)?;
             let mut device: nvmlDevice_t = mem::zeroed();
 
-            nvml_try(sym(c_string.as_ptr(), &mut device))?;
+            nvml_try(
+                self.backend
+                    .nvmlDeviceGetHandleBySerial(c_string.as_ptr(), &mut device)?,
+            )?;
 
             Ok(Device::new(device, self))
         }
@@ -672,13 +782,14 @@ impl Nvml {
     where
         Vec<u8>: From<S>,
     {
-        let sym = nvml_sym(self.lib.nvmlDeviceGetHandleByUUID.as_ref())?;
-
         unsafe {
             let c_string = CString::new(uuid)?;
             let mut device: nvmlDevice_t = mem::zeroed();
 
-            nvml_try(sym(c_string.as_ptr(), &mut device))?;
+            nvml_try(
+                self.backend
+                    .nvmlDeviceGetHandleByUUID(c_string.as_ptr(), &mut device)?,
+            )?;
 
             Ok(Device::new(device, self))
         }
@@ -709,12 +820,14 @@ impl Nvml {
         device1: &Device,
         device2: &Device,
     ) -> Result<TopologyLevel, NvmlError> {
-        let sym = nvml_sym(self.lib.nvmlDeviceGetTopologyCommonAncestor.as_ref())?;
-
         unsafe {
             let mut level: nvmlGpuTopologyLevel_t = mem::zeroed();
 
-            nvml_try(sym(device1.handle(), device2.handle(), &mut level))?;
+            nvml_try(self.backend.nvmlDeviceGetTopologyCommonAncestor(
+                device1.handle(),
+                device2.handle(),
+                &mut level,
+            )?)?;
 
             TopologyLevel::try_from(level)
         }
@@ -744,11 +857,12 @@ impl Nvml {
     // Tested (for an error)
     #[doc(alias = "nvmlUnitGetHandleByIndex")]
     pub fn unit_by_index(&self, index: u32) -> Result<Unit<'_>, NvmlError> {
-        let sym = nvml_sym(self.lib.nvmlUnitGetHandleByIndex.as_ref())?;
-
         unsafe {
             let mut unit: nvmlUnit_t = mem::zeroed();
-            nvml_try(sym(index as c_uint, &mut unit))?;
+            nvml_try(
+                self.backend
+                    .nvmlUnitGetHandleByIndex(index as c_uint, &mut unit)?,
+            )?;
 
             Ok(Unit::new(unit, self))
         }
@@ -775,12 +889,14 @@ impl Nvml {
         device1: &Device,
         device2: &Device,
     ) -> Result<bool, NvmlError> {
-        let sym = nvml_sym(self.lib.nvmlDeviceOnSameBoard.as_ref())?;
-
         unsafe {
             let mut bool_int: c_int = mem::zeroed();
 
-            nvml_try(sym(device1.handle(), device2.handle(), &mut bool_int))?;
+            nvml_try(self.backend.nvmlDeviceOnSameBoard(
+                device1.handle(),
+                device2.handle(),
+                &mut bool_int,
+            )?)?;
 
             match bool_int {
                 0 => Ok(false),
@@ -806,8 +922,6 @@ impl Nvml {
     #[cfg(target_os = "linux")]
     #[doc(alias = "nvmlSystemGetTopologyGpuSet")]
     pub fn topology_gpu_set(&self, cpu_number: u32) -> Result<Vec<Device<'_>>, NvmlError> {
-        let sym = nvml_sym(self.lib.nvmlSystemGetTopologyGpuSet.as_ref())?;
-
         unsafe {
             let mut count = match self.topology_gpu_set_count(cpu_number)? {
                 0 => return Ok(vec![]),
@@ -815,7 +929,11 @@ impl Nvml {
             };
             let mut devices: Vec<nvmlDevice_t> = vec![mem::zeroed(); count as usize];
 
-            nvml_try(sym(cpu_number, &mut count, devices.as_mut_ptr()))?;
+            nvml_try(self.backend.nvmlSystemGetTopologyGpuSet(
+                cpu_number,
+                &mut count,
+                devices.as_mut_ptr(),
+            )?)?;
 
             Ok(devices.into_iter().map(|d| Device::new(d, self)).collect())
         }
@@ -824,14 +942,16 @@ impl Nvml {
     // Helper function for the above.
     #[cfg(target_os = "linux")]
     fn topology_gpu_set_count(&self, cpu_number: u32) -> Result<c_uint, NvmlError> {
-        let sym = nvml_sym(self.lib.nvmlSystemGetTopologyGpuSet.as_ref())?;
-
         unsafe {
             // Indicates that we want the count
             let mut count: c_uint = 0;
 
             // Passing null doesn't indicate that we want the count, just allowed
-            nvml_try(sym(cpu_number, &mut count, ptr::null_mut()))?;
+            nvml_try(self.backend.nvmlSystemGetTopologyGpuSet(
+                cpu_number,
+                &mut count,
+                ptr::null_mut(),
+            )?)?;
 
             Ok(count)
         }
@@ -852,8 +972,6 @@ impl Nvml {
     // Tested
     #[doc(alias = "nvmlSystemGetHicVersion")]
     pub fn hic_versions(&self) -> Result<Vec<HwbcEntry>, NvmlError> {
-        let sym = nvml_sym(self.lib.nvmlSystemGetHicVersion.as_ref())?;
-
         unsafe {
             let mut count: c_uint = match self.hic_count()? {
                 0 => return Ok(vec![]),
@@ -861,7 +979,10 @@ impl Nvml {
             };
             let mut hics: Vec<nvmlHwbcEntry_t> = vec![mem::zeroed(); count as usize];
 
-            nvml_try(sym(&mut count, hics.as_mut_ptr()))?;
+            nvml_try(
+                self.backend
+                    .nvmlSystemGetHicVersion(&mut count, hics.as_mut_ptr())?,
+            )?;
 
             hics.into_iter().map(HwbcEntry::try_from).collect()
         }
@@ -881,8 +1002,6 @@ impl Nvml {
     // Tested as part of the above method
     #[doc(alias = "nvmlSystemGetHicVersion")]
     pub fn hic_count(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.lib.nvmlSystemGetHicVersion.as_ref())?;
-
         unsafe {
             /*
             NVIDIA doesn't even say that `count` will be set to the count if
@@ -900,7 +1019,10 @@ impl Nvml {
             let mut count: c_uint = 1;
             let mut hics: [nvmlHwbcEntry_t; 1] = [mem::zeroed()];
 
-            match sym(&mut count, hics.as_mut_ptr()) {
+            match self
+                .backend
+                .nvmlSystemGetHicVersion(&mut count, hics.as_mut_ptr())?
+            {
                 nvmlReturn_enum_NVML_SUCCESS | nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE => {
                     Ok(count)
                 }
@@ -926,11 +1048,9 @@ impl Nvml {
     // Tested
     #[doc(alias = "nvmlUnitGetCount")]
     pub fn unit_count(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.lib.nvmlUnitGetCount.as_ref())?;
-
         unsafe {
             let mut count: c_uint = mem::zeroed();
-            nvml_try(sym(&mut count))?;
+            nvml_try(self.backend.nvmlUnitGetCount(&mut count)?)?;
 
             Ok(count)
         }
@@ -952,11 +1072,9 @@ impl Nvml {
     // Tested
     #[doc(alias = "nvmlEventSetCreate")]
     pub fn create_event_set(&self) -> Result<EventSet<'_>, NvmlError> {
-        let sym = nvml_sym(self.lib.nvmlEventSetCreate.as_ref())?;
-
         unsafe {
             let mut set: nvmlEventSet_t = mem::zeroed();
-            nvml_try(sym(&mut set))?;
+            nvml_try(self.backend.nvmlEventSetCreate(&mut set)?)?;
 
             Ok(EventSet::new(set, self))
         }
@@ -1007,9 +1125,12 @@ impl Nvml {
     #[cfg(target_os = "linux")]
     #[doc(alias = "nvmlDeviceDiscoverGpus")]
     pub fn discover_gpus(&self, pci_info: PciInfo) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.lib.nvmlDeviceDiscoverGpus.as_ref())?;
-
-        unsafe { nvml_try(sym(&mut pci_info.try_into()?)) }
+        unsafe {
+            nvml_try(
+                self.backend
+                    .nvmlDeviceDiscoverGpus(&mut pci_info.try_into()?)?,
+            )
+        }
     }
 
     /**
@@ -1021,12 +1142,10 @@ impl Nvml {
     */
     #[doc(alias = "nvmlGetExcludedDeviceCount")]
     pub fn excluded_device_count(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.lib.nvmlGetExcludedDeviceCount.as_ref())?;
-
         unsafe {
             let mut count: c_uint = mem::zeroed();
 
-            nvml_try(sym(&mut count))?;
+            nvml_try(self.backend.nvmlGetExcludedDeviceCount(&mut count)?)?;
             Ok(count)
         }
     }
@@ -1045,16 +1164,82 @@ impl Nvml {
     */
     #[doc(alias = "nvmlGetExcludedDeviceInfoByIndex")]
     pub fn excluded_device_info(&self, index: u32) -> Result<ExcludedDeviceInfo, NvmlError> {
-        let sym = nvml_sym(self.lib.nvmlGetExcludedDeviceInfoByIndex.as_ref())?;
-
         unsafe {
             let mut info: nvmlExcludedDeviceInfo_t = mem::zeroed();
 
-            nvml_try(sym(index, &mut info))?;
+            nvml_try(
+                self.backend
+                    .nvmlGetExcludedDeviceInfoByIndex(index, &mut info)?,
+            )?;
             ExcludedDeviceInfo::try_from(info)
         }
     }
 
+    /**
+    Gets every MIG device in the system, with the device, GPU instance and
+    compute instance it belongs to.
+
+    Devices that don't support MIG or don't have it enabled are skipped.
+
+    Looking up the profile of a MIG device's GPU instance requires a privileged
+    user and is only supported on Linux; otherwise, the profile is `None`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `NoPermission`, if the user doesn't have permission to talk to a device
+    * `GpuLost`, if a device has fallen off the bus or is otherwise
+      inaccessible
+    * `Utf8Error`, if a UUID or profile name is not valid UTF-8
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    pub fn mig_devices(&self) -> Result<Vec<MigDeviceInfo>, NvmlError> {
+        let mut mig_devices = Vec::new();
+
+        for parent_index in 0..self.device_count()? {
+            let parent = self.device_by_index(parent_index)?;
+
+            match parent.mig_mode() {
+                Ok(mode) if mode.current == NVML_DEVICE_MIG_ENABLE => {}
+                Ok(_) | Err(NvmlError::NotSupported) => continue,
+                Err(e) => return Err(e),
+            }
+            let parent_uuid = parent.uuid()?;
+
+            for index in 0..parent.mig_device_count()? {
+                let mig_device = match parent.mig_device_by_index(index) {
+                    Ok(mig_device) => mig_device,
+                    // Indices without a MIG device behind them
+                    Err(NvmlError::NotFound) | Err(NvmlError::InvalidArg) => continue,
+                    Err(e) => return Err(e),
+                };
+
+                let gpu_instance_id = mig_device.mig_gpu_instance_id()?;
+                #[cfg(target_os = "linux")]
+                let profile = gpu_instance_profile_name(&parent, gpu_instance_id)?;
+                #[cfg(not(target_os = "linux"))]
+                let profile = None;
+
+                mig_devices.push(MigDeviceInfo {
+                    uuid: mig_device.uuid()?,
+                    index,
+                    parent_uuid: parent_uuid.clone(),
+                    parent_index,
+                    gpu_instance_id,
+                    compute_instance_id: mig_device.mig_compute_instance_id()?,
+                    profile,
+                    memory_size: mig_device.memory_info()?.total,
+                });
+            }
+        }
+
+        Ok(mig_devices)
+    }
+
     /**
     Gets the loaded vGPU list of capabilities
 
@@ -1072,12 +1257,13 @@ impl Nvml {
         &self,
         capability: nvmlVgpuDriverCapability_t,
     ) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.lib.nvmlGetVgpuDriverCapabilities.as_ref())?;
-
         unsafe {
             let mut mask: u32 = mem::zeroed();
 
-            nvml_try(sym(capability, &mut mask))?;
+            nvml_try(
+                self.backend
+                    .nvmlGetVgpuDriverCapabilities(capability, &mut mask)?,
+            )?;
             Ok(mask)
         }
     }
@@ -1094,25 +1280,94 @@ impl Nvml {
     */
     #[doc(alias = "nvmlGetVgpuVersion")]
     pub fn vgpu_version(&self) -> Result<(VgpuVersion, VgpuVersion), NvmlError> {
-        let sym = nvml_sym(self.lib.nvmlGetVgpuVersion.as_ref())?;
-
         unsafe {
             let mut supported: nvmlVgpuVersion_t = mem::zeroed();
             let mut current: nvmlVgpuVersion_t = mem::zeroed();
 
-            nvml_try(sym(&mut supported, &mut current))?;
+            nvml_try(
+                self.backend
+                    .nvmlGetVgpuVersion(&mut supported, &mut current)?,
+            )?;
             Ok((VgpuVersion::from(supported), VgpuVersion::from(current)))
         }
     }
 
     #[doc(alias = "nvmlSetVgpuVersion")]
     pub fn set_vgpu_version(&self, version: VgpuVersion) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.lib.nvmlSetVgpuVersion.as_ref())?;
+        unsafe { nvml_try(self.backend.nvmlSetVgpuVersion(&mut version.as_c())?) }
+    }
+
+    /**
+    Check whether the vGPU described by `vgpu` can be migrated to the physical
+    GPU described by `pgpu`, and what limits it if not.
+
+    Get `vgpu` from `VgpuInstance.metadata()` on the host the vGPU runs on and
+    `pgpu` from `Device.vgpu_metadata()` on the host it would move to. See
+    `high_level::vgpu_migration` for a check that also explains the result.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `NulError`, if a nul byte was found in a string in either metadata
+    * `StringTooLong`, if a string in either metadata is too long for NVML
+    * `InvalidArg`, if NVML rejects either metadata
+    * `IncorrectBits`, if NVML returns bits that don't match the flags in this
+      wrapper
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell or newer fully supported devices.
+    */
+    #[doc(alias = "nvmlGetVgpuCompatibility")]
+    pub fn vgpu_compatibility(
+        &self,
+        vgpu: &VgpuMetadata,
+        pgpu: &VgpuPgpuMetadata,
+    ) -> Result<VgpuPgpuCompatibility, NvmlError> {
+        let mut vgpu = vgpu.try_into_c_buffer()?;
+        let mut pgpu = pgpu.try_into_c_buffer()?;
 
-        unsafe { nvml_try(sym(&mut version.as_c())) }
+        unsafe {
+            let mut compatibility: nvmlVgpuPgpuCompatibility_t = mem::zeroed();
+            nvml_try(self.backend.nvmlGetVgpuCompatibility(
+                vgpu.as_mut_ptr() as *mut nvmlVgpuMetadata_t,
+                pgpu.as_mut_ptr() as *mut nvmlVgpuPgpuMetadata_t,
+                &mut compatibility,
+            )?)?;
+
+            VgpuPgpuCompatibility::try_from(compatibility)
+        }
     }
 }
 
+/**
+The short name (e.g. `3g.40gb`) of the profile of the GPU instance with ID `id`
+on `device`, or `None` if the user isn't allowed to look it up.
+*/
+#[cfg(target_os = "linux")]
+fn gpu_instance_profile_name(device: &Device, id: u32) -> Result<Option<String>, NvmlError> {
+    let instance = match device.gpu_instance_by_id(id) {
+        Ok(instance) => instance,
+        Err(NvmlError::NoPermission) | Err(NvmlError::NotSupported) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let profile_id = instance.info()?.profile_id;
+
+    // Profile info is looked up by the profile's index rather than its ID
+    for profile in 0..NVML_GPU_INSTANCE_PROFILE_COUNT {
+        match device.gpu_instance_profile_info(profile) {
+            Ok(info) if info.id == profile_id => {
+                return Ok(Some(high_level::mig::short_name(&info.name).into()))
+            }
+            Ok(_) | Err(NvmlError::NotSupported) | Err(NvmlError::InvalidArg) => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(None)
+}
+
 /// This `Drop` implementation ignores errors! Use the `.shutdown()` method on
 /// the `Nvml` struct
 /// if you care about handling them.
@@ -1120,10 +1375,10 @@ impl Drop for Nvml {
     #[doc(alias = "nvmlShutdown")]
     fn drop(&mut self) {
         unsafe {
-            self.lib.nvmlShutdown();
+            let _ = self.backend.nvmlShutdown();
 
-            // SAFETY: called after the last usage of `self.lib`
-            ManuallyDrop::drop(&mut self.lib);
+            // SAFETY: called after the last usage of `self.backend`
+            ManuallyDrop::drop(&mut self.backend);
         }
     }
 }
@@ -1155,6 +1410,16 @@ let init_result = Nvml::builder().lib_path(OsStr::new("/some/path/to/libnvidia-m
 pub struct NvmlBuilder<'a> {
     lib_path: Option<&'a OsStr>,
     flags: InitFlags,
+    faults: Option<Faults>,
+    #[cfg(feature = "record-replay")]
+    recording: Option<Recording<'a>>,
+}
+
+#[cfg(feature = "record-replay")]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Recording<'a> {
+    To(&'a Path),
+    From(&'a Path),
 }
 
 impl<'a> NvmlBuilder<'a> {
@@ -1177,16 +1442,113 @@ impl<'a> NvmlBuilder<'a> {
         self
     }
 
+    /**
+    Inject `faults` into the NVML calls made through the `Nvml` instance this
+    builder creates.
+
+    See the [`fault`] module for details.
+    */
+    pub fn faults(&mut self, faults: Faults) -> &mut Self {
+        self.faults = Some(faults);
+        self
+    }
+
+    /**
+    Record every NVML call made through the `Nvml` instance this builder
+    creates to the file at `path`, creating or truncating it.
+
+    The recording can be served back with `replay_from()`. See the
+    [`record`] module for details.
+    */
+    #[cfg(feature = "record-replay")]
+    pub fn record_to(&mut self, path: &'a Path) -> &mut Self {
+        self.recording = Some(Recording::To(path));
+        self
+    }
+
+    /**
+    Answer NVML calls from a recording made with `record_to()` instead of
+    loading the NVML library.
+
+    The lib path set on this builder is ignored. See the [`record`] module for
+    how calls are matched against the recording.
+    */
+    #[cfg(feature = "record-replay")]
+    pub fn replay_from(&mut self, path: &'a Path) -> &mut Self {
+        self.recording = Some(Recording::From(path));
+        self
+    }
+
     /// Perform initialization.
     pub fn init(&self) -> Result<Nvml, NvmlError> {
+        #[cfg(feature = "record-replay")]
+        if let Some(Recording::From(path)) = self.recording {
+            return self.init_with_backend(Replayer::open(path)?);
+        }
+
         let lib_path = self.lib_path.unwrap_or_else(|| LIB_PATH.as_ref());
 
+        #[cfg(feature = "record-replay")]
+        let recording = self.recording.is_some();
+        #[cfg(not(feature = "record-replay"))]
+        let recording = false;
+
+        if self.faults.is_some() || recording {
+            let lib = unsafe { NvmlLib::new(lib_path)? };
+
+            return self.init_with_backend(lib);
+        }
+
         if self.flags.is_empty() {
             Nvml::init_internal(lib_path)
         } else {
             Nvml::init_with_flags_internal(lib_path, self.flags)
         }
     }
+
+    /**
+    Perform initialization against the given backend instead of loading the
+    NVML library.
+
+    The lib path set on this builder is ignored; the flags are passed on to the
+    backend's `nvmlInitWithFlags` (or `nvmlInit_v2` if no flags were set).
+    Faults set with `faults()` are injected into calls to the backend, and
+    calls (including injected faults) are recorded if `record_to()` was used,
+    but a path set with `replay_from()` is ignored.
+
+    See the [`backend`] module for an example.
+    */
+    pub fn init_with_backend<B>(&self, backend: B) -> Result<Nvml, NvmlError>
+    where
+        B: NvmlBackend + 'static,
+    {
+        match self.faults {
+            Some(ref faults) => self.init_recorded(FaultInjector::new(backend, faults.clone())),
+            None => self.init_recorded(backend),
+        }
+    }
+
+    fn init_recorded<B>(&self, backend: B) -> Result<Nvml, NvmlError>
+    where
+        B: NvmlBackend + 'static,
+    {
+        #[cfg(feature = "record-replay")]
+        if let Some(Recording::To(path)) = self.recording {
+            let recorder = Recorder::create(backend, path)?;
+
+            return Nvml::init_with_backend_internal(Box::new(recorder), self.init_flags());
+        }
+
+        Nvml::init_with_backend_internal(Box::new(backend), self.init_flags())
+    }
+
+    fn init_flags(&self) -> Option<InitFlags> {
+        if self.flags.is_empty() {
+            None
+        } else {
+            Some(self.flags)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1198,7 +1560,7 @@ mod test {
 
     #[test]
     fn init_with_flags() {
-        Nvml::init_with_flags(InitFlags::NO_GPUS).unwrap();
+        nvml_with_flags(InitFlags::NO_GPUS).unwrap();
     }
 
     #[test]
@@ -1211,6 +1573,11 @@ mod test {
         test(3, || nvml().device_count())
     }
 
+    #[test]
+    fn mig_devices() {
+        test(3, || nvml().mig_devices())
+    }
+
     #[test]
     fn sys_driver_version() {
         test(3, || nvml().sys_driver_version())
@@ -1505,4 +1872,185 @@ mod test {
         assert_eq!(translate_field_id(FieldIdScheme::V13Update1, 250), 250);
         assert_eq!(translate_field_id(FieldIdScheme::V13Update1, 274), 274);
     }
+
+    /// Two GPUs: one without MIG support, and one in MIG mode with MIG devices
+    /// at indices 0 and 2 of 3.
+    struct MigMock;
+
+    /// MIG device handles are this plus their index.
+    const MIG_DEVICE: usize = 10;
+
+    fn write_str(s: &str, buf: *mut std::os::raw::c_char, length: c_uint) -> nvmlReturn_t {
+        if s.len() >= length as usize {
+            return nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE;
+        }
+
+        unsafe {
+            buf.copy_from(s.as_ptr() as *const _, s.len());
+            *buf.add(s.len()) = 0;
+        }
+        nvmlReturn_enum_NVML_SUCCESS
+    }
+
+    impl NvmlBackend for MigMock {
+        mock_backend!(devices = 2);
+
+        unsafe fn nvmlDeviceGetMigMode(
+            &self,
+            device: nvmlDevice_t,
+            current: *mut c_uint,
+            pending: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            if device == mock_device(0) {
+                return Ok(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED);
+            }
+
+            *current = NVML_DEVICE_MIG_ENABLE;
+            *pending = NVML_DEVICE_MIG_ENABLE;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetMaxMigDeviceCount(
+            &self,
+            _device: nvmlDevice_t,
+            count: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *count = 3;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetMigDeviceHandleByIndex(
+            &self,
+            _device: nvmlDevice_t,
+            index: c_uint,
+            mig_device: *mut nvmlDevice_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            if index == 1 {
+                return Ok(nvmlReturn_enum_NVML_ERROR_NOT_FOUND);
+            }
+
+            *mig_device = (MIG_DEVICE + index as usize) as nvmlDevice_t;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetUUID(
+            &self,
+            device: nvmlDevice_t,
+            uuid: *mut std::os::raw::c_char,
+            length: c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let s = match device as usize {
+                d if d >= MIG_DEVICE => format!("MIG-{}", d - MIG_DEVICE),
+                d => format!("GPU-{}", d - 1),
+            };
+            Ok(write_str(&s, uuid, length))
+        }
+
+        unsafe fn nvmlDeviceGetGpuInstanceById(
+            &self,
+            _device: nvmlDevice_t,
+            id: c_uint,
+            instance: *mut nvmlGpuInstance_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            // Only the first MIG device's GPU instance can be looked up
+            if id != 2 {
+                return Ok(nvmlReturn_enum_NVML_ERROR_NO_PERMISSION);
+            }
+
+            *instance = id as usize as nvmlGpuInstance_t;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlGpuInstanceGetInfo(
+            &self,
+            instance: nvmlGpuInstance_t,
+            info: *mut nvmlGpuInstanceInfo_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            (*info).id = instance as usize as c_uint;
+            (*info).profileId = 9;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetGpuInstanceProfileInfoV(
+            &self,
+            _device: nvmlDevice_t,
+            profile: c_uint,
+            info: *mut nvmlGpuInstanceProfileInfo_v2_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            // Profile IDs don't match their indices
+            let (id, name) = match profile {
+                0 => (0, "MIG 1g.10gb"),
+                2 => (9, "MIG 3g.40gb"),
+                _ => return Ok(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED),
+            };
+
+            (*info).id = id;
+            for (i, c) in name.bytes().chain(Some(0)).enumerate() {
+                (*info).name[i] = c as _;
+            }
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetGpuInstanceId(
+            &self,
+            device: nvmlDevice_t,
+            id: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *id = match (device as usize).checked_sub(MIG_DEVICE) {
+                Some(0) => 2,
+                _ => 13,
+            };
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetComputeInstanceId(
+            &self,
+            _device: nvmlDevice_t,
+            id: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *id = 0;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetMemoryInfo_v2(
+            &self,
+            device: nvmlDevice_t,
+            memory: *mut nvmlMemory_v2_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            (*memory).total = match (device as usize).checked_sub(MIG_DEVICE) {
+                Some(0) => 40 << 30,
+                _ => 10 << 30,
+            };
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn mig_devices_maps_each_to_its_parent() {
+        let nvml = Nvml::builder()
+            .init_with_backend(MigMock)
+            .expect("initialized mock");
+
+        let info = |uuid: &str, index, gpu_instance_id, profile: Option<&str>, memory_size| {
+            MigDeviceInfo {
+                uuid: uuid.into(),
+                index,
+                parent_uuid: "GPU-1".into(),
+                parent_index: 1,
+                gpu_instance_id,
+                compute_instance_id: 0,
+                profile: profile.map(String::from),
+                memory_size,
+            }
+        };
+
+        assert_eq!(
+            nvml.mig_devices().unwrap(),
+            [
+                info("MIG-0", 0, 2, Some("3g.40gb"), 40 << 30),
+                info("MIG-2", 2, 13, None, 10 << 30),
+            ]
+        );
+    }
 }
@@ -7,7 +7,7 @@ use crate::enum_wrappers::{
 };
 
 use crate::enums::nv_link::Counter;
-use crate::error::{nvml_sym, nvml_try, NvmlError};
+use crate::error::{nvml_try, NvmlError};
 use crate::ffi::bindings::*;
 use crate::struct_wrappers::{device::PciInfo, nv_link::UtilizationControl};
 use crate::structs::nv_link::UtilizationCounter;
@@ -86,12 +86,14 @@ impl<'device, 'nvml: 'device> NvLink<'device, 'nvml> {
     // Test written
     #[doc(alias = "nvmlDeviceGetNvLinkState")]
     pub fn is_active(&self) -> Result<bool, NvmlError> {
-        let sym = nvml_sym(self.device.nvml().lib.nvmlDeviceGetNvLinkState.as_ref())?;
-
         unsafe {
             let mut state: nvmlEnableState_t = mem::zeroed();
 
-            nvml_try(sym(self.device.handle(), self.link, &mut state))?;
+            nvml_try(self.device.nvml().backend.nvmlDeviceGetNvLinkState(
+                self.device.handle(),
+                self.link,
+                &mut state,
+            )?)?;
 
             bool_from_state(state)
         }
@@ -115,12 +117,14 @@ impl<'device, 'nvml: 'device> NvLink<'device, 'nvml> {
     // Test written
     #[doc(alias = "nvmlDeviceGetNvLinkVersion")]
     pub fn version(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.device.nvml().lib.nvmlDeviceGetNvLinkVersion.as_ref())?;
-
         unsafe {
             let mut version: c_uint = mem::zeroed();
 
-            nvml_try(sym(self.device.handle(), self.link, &mut version))?;
+            nvml_try(self.device.nvml().backend.nvmlDeviceGetNvLinkVersion(
+                self.device.handle(),
+                self.link,
+                &mut version,
+            )?)?;
 
             Ok(version)
         }
@@ -144,24 +148,16 @@ impl<'device, 'nvml: 'device> NvLink<'device, 'nvml> {
     // Test written
     #[doc(alias = "nvmlDeviceGetNvLinkCapability")]
     pub fn has_capability(&self, cap_type: Capability) -> Result<bool, NvmlError> {
-        let sym = nvml_sym(
-            self.device
-                .nvml()
-                .lib
-                .nvmlDeviceGetNvLinkCapability
-                .as_ref(),
-        )?;
-
         unsafe {
             // NVIDIA says that this should be interpreted as a boolean
             let mut capability: c_uint = mem::zeroed();
 
-            nvml_try(sym(
+            nvml_try(self.device.nvml().backend.nvmlDeviceGetNvLinkCapability(
                 self.device.handle(),
                 self.link,
                 cap_type.as_c(),
                 &mut capability,
-            ))?;
+            )?)?;
 
             #[allow(clippy::match_like_matches_macro)]
             Ok(match capability {
@@ -190,18 +186,19 @@ impl<'device, 'nvml: 'device> NvLink<'device, 'nvml> {
     // Test written
     #[doc(alias = "nvmlDeviceGetNvLinkRemotePciInfo_v2")]
     pub fn remote_pci_info(&self) -> Result<PciInfo, NvmlError> {
-        let sym = nvml_sym(
-            self.device
-                .nvml()
-                .lib
-                .nvmlDeviceGetNvLinkRemotePciInfo_v2
-                .as_ref(),
-        )?;
-
         unsafe {
             let mut pci_info: nvmlPciInfo_t = mem::zeroed();
 
-            nvml_try(sym(self.device.handle(), self.link, &mut pci_info))?;
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlDeviceGetNvLinkRemotePciInfo_v2(
+                        self.device.handle(),
+                        self.link,
+                        &mut pci_info,
+                    )?,
+            )?;
 
             PciInfo::try_from(pci_info, false)
         }
@@ -225,23 +222,15 @@ impl<'device, 'nvml: 'device> NvLink<'device, 'nvml> {
     // Test written
     #[doc(alias = "nvmlDeviceGetNvLinkErrorCounter")]
     pub fn error_counter(&self, counter: ErrorCounter) -> Result<u64, NvmlError> {
-        let sym = nvml_sym(
-            self.device
-                .nvml()
-                .lib
-                .nvmlDeviceGetNvLinkErrorCounter
-                .as_ref(),
-        )?;
-
         unsafe {
             let mut value: c_ulonglong = mem::zeroed();
 
-            nvml_try(sym(
+            nvml_try(self.device.nvml().backend.nvmlDeviceGetNvLinkErrorCounter(
                 self.device.handle(),
                 self.link,
                 counter.as_c(),
                 &mut value,
-            ))?;
+            )?)?;
 
             Ok(value)
         }
@@ -265,15 +254,14 @@ impl<'device, 'nvml: 'device> NvLink<'device, 'nvml> {
     // No-run test written
     #[doc(alias = "nvmlDeviceResetNvLinkErrorCounters")]
     pub fn reset_error_counters(&mut self) -> Result<(), NvmlError> {
-        let sym = nvml_sym(
-            self.device
-                .nvml()
-                .lib
-                .nvmlDeviceResetNvLinkErrorCounters
-                .as_ref(),
-        )?;
-
-        unsafe { nvml_try(sym(self.device.handle(), self.link)) }
+        unsafe {
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlDeviceResetNvLinkErrorCounters(self.device.handle(), self.link)?,
+            )
+        }
     }
 
     /**
@@ -304,22 +292,19 @@ impl<'device, 'nvml: 'device> NvLink<'device, 'nvml> {
     ) -> Result<(), NvmlError> {
         let reset: c_uint = u32::from(reset_counters);
 
-        let sym = nvml_sym(
-            self.device
-                .nvml()
-                .lib
-                .nvmlDeviceSetNvLinkUtilizationControl
-                .as_ref(),
-        )?;
-
         unsafe {
-            nvml_try(sym(
-                self.device.handle(),
-                self.link,
-                counter as c_uint,
-                &mut settings.as_c(),
-                reset,
-            ))
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlDeviceSetNvLinkUtilizationControl(
+                        self.device.handle(),
+                        self.link,
+                        counter as c_uint,
+                        &mut settings.as_c(),
+                        reset,
+                    )?,
+            )
         }
     }
 
@@ -342,23 +327,20 @@ impl<'device, 'nvml: 'device> NvLink<'device, 'nvml> {
     // Test written
     #[doc(alias = "nvmlDeviceGetNvLinkUtilizationControl")]
     pub fn utilization_control(&self, counter: Counter) -> Result<UtilizationControl, NvmlError> {
-        let sym = nvml_sym(
-            self.device
-                .nvml()
-                .lib
-                .nvmlDeviceGetNvLinkUtilizationControl
-                .as_ref(),
-        )?;
-
         unsafe {
             let mut controls: nvmlNvLinkUtilizationControl_t = mem::zeroed();
 
-            nvml_try(sym(
-                self.device.handle(),
-                self.link,
-                counter as c_uint,
-                &mut controls,
-            ))?;
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlDeviceGetNvLinkUtilizationControl(
+                        self.device.handle(),
+                        self.link,
+                        counter as c_uint,
+                        &mut controls,
+                    )?,
+            )?;
 
             UtilizationControl::try_from(controls)
         }
@@ -399,25 +381,22 @@ impl<'device, 'nvml: 'device> NvLink<'device, 'nvml> {
     // No-run test written
     #[doc(alias = "nvmlDeviceGetNvLinkUtilizationCounter")]
     pub fn utilization_counter(&self, counter: Counter) -> Result<UtilizationCounter, NvmlError> {
-        let sym = nvml_sym(
-            self.device
-                .nvml()
-                .lib
-                .nvmlDeviceGetNvLinkUtilizationCounter
-                .as_ref(),
-        )?;
-
         unsafe {
             let mut receive: c_ulonglong = mem::zeroed();
             let mut send: c_ulonglong = mem::zeroed();
 
-            nvml_try(sym(
-                self.device.handle(),
-                self.link,
-                counter as c_uint,
-                &mut receive,
-                &mut send,
-            ))?;
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlDeviceGetNvLinkUtilizationCounter(
+                        self.device.handle(),
+                        self.link,
+                        counter as c_uint,
+                        &mut receive,
+                        &mut send,
+                    )?,
+            )?;
 
             Ok(UtilizationCounter { receive, send })
         }
@@ -476,21 +455,18 @@ impl<'device, 'nvml: 'device> NvLink<'device, 'nvml> {
         counter: Counter,
         frozen: bool,
     ) -> Result<(), NvmlError> {
-        let sym = nvml_sym(
-            self.device
-                .nvml()
-                .lib
-                .nvmlDeviceFreezeNvLinkUtilizationCounter
-                .as_ref(),
-        )?;
-
         unsafe {
-            nvml_try(sym(
-                self.device.handle(),
-                self.link,
-                counter as c_uint,
-                state_from_bool(frozen),
-            ))
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlDeviceFreezeNvLinkUtilizationCounter(
+                        self.device.handle(),
+                        self.link,
+                        counter as c_uint,
+                        state_from_bool(frozen),
+                    )?,
+            )
         }
     }
 
@@ -515,15 +491,18 @@ impl<'device, 'nvml: 'device> NvLink<'device, 'nvml> {
     // No-run test written
     #[doc(alias = "nvmlDeviceResetNvLinkUtilizationCounter")]
     pub fn reset_utilization_counter(&mut self, counter: Counter) -> Result<(), NvmlError> {
-        let sym = nvml_sym(
-            self.device
-                .nvml()
-                .lib
-                .nvmlDeviceResetNvLinkUtilizationCounter
-                .as_ref(),
-        )?;
-
-        unsafe { nvml_try(sym(self.device.handle(), self.link, counter as c_uint)) }
+        unsafe {
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlDeviceResetNvLinkUtilizationCounter(
+                        self.device.handle(),
+                        self.link,
+                        counter as c_uint,
+                    )?,
+            )
+        }
     }
 
     /**
@@ -542,11 +521,14 @@ impl<'device, 'nvml: 'device> NvLink<'device, 'nvml> {
     */
     #[doc(alias = "nvmlSystemGetNvLinkBwMode")]
     pub fn bw_mode(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.device.nvml().lib.nvmlSystemGetNvlinkBwMode.as_ref())?;
-
         unsafe {
             let mut mode: c_uint = 0;
-            nvml_try(sym(&mut mode))?;
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlSystemGetNvlinkBwMode(&mut mode)?,
+            )?;
             Ok(mode)
         }
     }
@@ -567,9 +549,7 @@ impl<'device, 'nvml: 'device> NvLink<'device, 'nvml> {
     */
     #[doc(alias = "nvmlSystemSetNvLinkBwMode")]
     pub fn set_bw_mode(&self, mode: u32) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.device.nvml().lib.nvmlSystemSetNvlinkBwMode.as_ref())?;
-
-        unsafe { nvml_try(sym(mode)) }
+        unsafe { nvml_try(self.device.nvml().backend.nvmlSystemSetNvlinkBwMode(mode)?) }
     }
 
     /**
@@ -584,17 +564,18 @@ impl<'device, 'nvml: 'device> NvLink<'device, 'nvml> {
     */
     #[doc(alias = "nvmlDeviceGetNvLinkRemoteDeviceType")]
     pub fn remote_device_type(&self, link: u32) -> Result<IntDeviceType, NvmlError> {
-        let sym = nvml_sym(
-            self.device
-                .nvml()
-                .lib
-                .nvmlDeviceGetNvLinkRemoteDeviceType
-                .as_ref(),
-        )?;
-
         unsafe {
             let device_type: IntDeviceType = IntDeviceType::Unknown;
-            nvml_try(sym(self.device.handle(), link, &mut device_type.as_c()))?;
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlDeviceGetNvLinkRemoteDeviceType(
+                        self.device.handle(),
+                        link,
+                        &mut device_type.as_c(),
+                    )?,
+            )?;
             Ok(device_type)
         }
     }
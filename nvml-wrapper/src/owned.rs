@@ -0,0 +1,319 @@
+/*!
+Device and unit handles that own a reference to their `Nvml` instance.
+
+`Device` and `Unit` borrow the `Nvml` instance they were obtained from, which
+makes them awkward to store in long-lived structs, registries or spawned tasks.
+[`OwnedDevice`] and [`OwnedUnit`] hold an `Arc<Nvml>` instead, so they are
+`'static` and can be moved and cloned freely:
+
+```no_run
+use nvml_wrapper::owned::OwnedDevice;
+use nvml_wrapper::Nvml;
+use std::sync::Arc;
+use std::thread;
+# use nvml_wrapper::error::*;
+
+# fn main() -> Result<(), NvmlError> {
+let nvml = Arc::new(Nvml::init()?);
+let device = OwnedDevice::by_index(nvml, 0)?;
+
+thread::spawn(move || {
+    // Borrow a `Device` to call its methods
+    println!("{}", device.device().name()?);
+    Ok::<(), NvmlError>(())
+});
+# Ok(())
+# }
+```
+
+Converting between the two forms is cheap: [`OwnedDevice::device()`] borrows a
+`Device` (a copy of the handle and a reference), and
+[`OwnedDevice::from_device()`] goes the other way (a copy of the handle and an
+`Arc` clone).
+
+Event sets and GPM samples release their resources on drop and don't have
+owned forms; keep the `Nvml` instance they borrow from alive alongside them.
+*/
+
+use crate::device::Device;
+use crate::error::NvmlError;
+use crate::ffi::bindings::*;
+use crate::unit::Unit;
+use crate::Nvml;
+use static_assertions::assert_impl_all;
+use std::ptr;
+use std::sync::Arc;
+
+/**
+A device handle that keeps its `Nvml` instance alive.
+
+Call [`device()`](Self::device) to access the methods on `Device`.
+*/
+#[derive(Debug, Clone)]
+pub struct OwnedDevice {
+    device: nvmlDevice_t,
+    nvml: Arc<Nvml>,
+}
+
+unsafe impl Send for OwnedDevice {}
+unsafe impl Sync for OwnedDevice {}
+
+assert_impl_all!(OwnedDevice: Send, Sync);
+
+impl OwnedDevice {
+    /**
+    Obtain a device from `nvml` with `get`, e.g. `|nvml| nvml.device_by_index(0)`,
+    and keep `nvml` alive with it.
+
+    # Errors
+
+    Whatever `get` returns.
+    */
+    pub fn new<F>(nvml: Arc<Nvml>, get: F) -> Result<Self, NvmlError>
+    where
+        F: FnOnce(&Nvml) -> Result<Device<'_>, NvmlError>,
+    {
+        let device = unsafe { get(&nvml)?.handle() };
+
+        Ok(Self { device, nvml })
+    }
+
+    /**
+    Acquire the handle for a particular device based on its index.
+
+    See `Nvml.device_by_index()`.
+    */
+    #[doc(alias = "nvmlDeviceGetHandleByIndex_v2")]
+    pub fn by_index(nvml: Arc<Nvml>, index: u32) -> Result<Self, NvmlError> {
+        Self::new(nvml, |nvml| nvml.device_by_index(index))
+    }
+
+    /**
+    Acquire the handle for a particular device based on its PCI bus ID.
+
+    See `Nvml.device_by_pci_bus_id()`.
+    */
+    #[doc(alias = "nvmlDeviceGetHandleByPciBusId_v2")]
+    pub fn by_pci_bus_id<S: AsRef<str>>(nvml: Arc<Nvml>, pci_bus_id: S) -> Result<Self, NvmlError>
+    where
+        Vec<u8>: From<S>,
+    {
+        Self::new(nvml, |nvml| nvml.device_by_pci_bus_id(pci_bus_id))
+    }
+
+    /**
+    Acquire the handle for a particular device based on its UUID.
+
+    See `Nvml.device_by_uuid()`.
+    */
+    #[doc(alias = "nvmlDeviceGetHandleByUUID")]
+    pub fn by_uuid<S: AsRef<str>>(nvml: Arc<Nvml>, uuid: S) -> Result<Self, NvmlError>
+    where
+        Vec<u8>: From<S>,
+    {
+        Self::new(nvml, |nvml| nvml.device_by_uuid(uuid))
+    }
+
+    /**
+    Create an `OwnedDevice` for the same device as `device`.
+
+    # Errors
+
+    * `InvalidArg`, if `device` wasn't obtained from `nvml`
+    */
+    pub fn from_device(nvml: Arc<Nvml>, device: &Device) -> Result<Self, NvmlError> {
+        if !ptr::eq(device.nvml(), &*nvml) {
+            return Err(NvmlError::InvalidArg);
+        }
+
+        Ok(Self {
+            device: unsafe { device.handle() },
+            nvml,
+        })
+    }
+
+    /// Borrow this device as a `Device`, to call its methods.
+    pub fn device(&self) -> Device<'_> {
+        unsafe { Device::new(self.device, &self.nvml) }
+    }
+
+    /// The `Nvml` instance this device was obtained from.
+    pub fn nvml(&self) -> &Arc<Nvml> {
+        &self.nvml
+    }
+
+    /// Get the raw device handle contained in this struct
+    ///
+    /// Sometimes necessary for C interop.
+    ///
+    /// # Safety
+    ///
+    /// This is unsafe to prevent it from being used without care.
+    pub unsafe fn handle(&self) -> nvmlDevice_t {
+        self.device
+    }
+}
+
+/**
+A unit handle that keeps its `Nvml` instance alive.
+
+Call [`unit()`](Self::unit) to access the methods on `Unit`.
+*/
+#[derive(Debug, Clone)]
+pub struct OwnedUnit {
+    unit: nvmlUnit_t,
+    nvml: Arc<Nvml>,
+}
+
+unsafe impl Send for OwnedUnit {}
+unsafe impl Sync for OwnedUnit {}
+
+assert_impl_all!(OwnedUnit: Send, Sync);
+
+impl OwnedUnit {
+    /**
+    Obtain a unit from `nvml` with `get`, e.g. `|nvml| nvml.unit_by_index(0)`,
+    and keep `nvml` alive with it.
+
+    # Errors
+
+    Whatever `get` returns.
+    */
+    pub fn new<F>(nvml: Arc<Nvml>, get: F) -> Result<Self, NvmlError>
+    where
+        F: FnOnce(&Nvml) -> Result<Unit<'_>, NvmlError>,
+    {
+        let unit = unsafe { get(&nvml)?.handle() };
+
+        Ok(Self { unit, nvml })
+    }
+
+    /**
+    Acquire the handle for a particular unit based on its index.
+
+    See `Nvml.unit_by_index()`.
+    */
+    #[doc(alias = "nvmlUnitGetHandleByIndex")]
+    pub fn by_index(nvml: Arc<Nvml>, index: u32) -> Result<Self, NvmlError> {
+        Self::new(nvml, |nvml| nvml.unit_by_index(index))
+    }
+
+    /**
+    Create an `OwnedUnit` for the same unit as `unit`.
+
+    # Errors
+
+    * `InvalidArg`, if `unit` wasn't obtained from `nvml`
+    */
+    pub fn from_unit(nvml: Arc<Nvml>, unit: &Unit) -> Result<Self, NvmlError> {
+        if !ptr::eq(unit.nvml(), &*nvml) {
+            return Err(NvmlError::InvalidArg);
+        }
+
+        Ok(Self {
+            unit: unsafe { unit.handle() },
+            nvml,
+        })
+    }
+
+    /// Borrow this unit as a `Unit`, to call its methods.
+    pub fn unit(&self) -> Unit<'_> {
+        unsafe { Unit::new(self.unit, &self.nvml) }
+    }
+
+    /**
+    Gets the devices attached to this unit, as owned handles.
+
+    See `Unit.devices()`.
+    */
+    pub fn devices(&self) -> Result<Vec<OwnedDevice>, NvmlError> {
+        Ok(self
+            .unit()
+            .devices()?
+            .into_iter()
+            .map(|device| OwnedDevice {
+                device: unsafe { device.handle() },
+                nvml: self.nvml.clone(),
+            })
+            .collect())
+    }
+
+    /// The `Nvml` instance this unit was obtained from.
+    pub fn nvml(&self) -> &Arc<Nvml> {
+        &self.nvml
+    }
+
+    /// Get the raw unit handle contained in this struct
+    ///
+    /// Sometimes necessary for C interop.
+    ///
+    /// # Safety
+    ///
+    /// This is unsafe to prevent it from being used without care.
+    pub unsafe fn handle(&self) -> nvmlUnit_t {
+        self.unit
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::NvmlBackend;
+    use crate::test_utils::mock_backend;
+    use std::os::raw::c_uint;
+    use std::thread;
+
+    struct Mock;
+
+    impl NvmlBackend for Mock {
+        mock_backend!(devices = 2);
+
+        unsafe fn nvmlDeviceGetIndex(
+            &self,
+            device: nvmlDevice_t,
+            index: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *index = device as c_uint - 1;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+    }
+
+    fn nvml() -> Arc<Nvml> {
+        Arc::new(Nvml::builder().init_with_backend(Mock).unwrap())
+    }
+
+    #[test]
+    fn outlives_the_original_arc() {
+        let device = OwnedDevice::by_index(nvml(), 1).unwrap();
+
+        let index = thread::spawn(move || device.device().index())
+            .join()
+            .unwrap();
+
+        assert_eq!(index.unwrap(), 1);
+    }
+
+    #[test]
+    fn converts_to_and_from_device() {
+        let nvml = nvml();
+        let device = nvml.device_by_index(1).unwrap();
+
+        let owned = OwnedDevice::from_device(nvml.clone(), &device).unwrap();
+        assert_eq!(owned.device().index().unwrap(), 1);
+        assert!(ptr::eq(owned.device().nvml(), &*nvml));
+
+        // A different instance, even with the same backend
+        assert!(matches!(
+            OwnedDevice::from_device(self::nvml(), &device),
+            Err(NvmlError::InvalidArg)
+        ));
+    }
+
+    #[test]
+    fn errors_pass_through() {
+        assert!(matches!(
+            OwnedDevice::by_index(nvml(), 2),
+            Err(NvmlError::InvalidArg)
+        ));
+    }
+}
@@ -0,0 +1,1132 @@
+/*!
+Recording NVML calls and replaying them later without a driver.
+
+[`Recorder`] is a backend that wraps another one (usually the NVML library
+itself) and logs every call made through it: the function's name, its
+arguments, the return code and everything the function wrote through its output
+pointers. [`Replayer`] reads such a log back and serves the recorded results,
+no driver or GPU required. Use this to reproduce an issue from a machine you
+don't have access to, or to run tests against data captured on real hardware.
+
+Both are most easily set up through the builder. Requires the `record-replay`
+feature.
+
+```no_run
+use nvml_wrapper::Nvml;
+use std::path::Path;
+
+# fn main() -> Result<(), nvml_wrapper::error::NvmlError> {
+// On the machine with the GPU
+let nvml = Nvml::builder().record_to(Path::new("nvml.jsonl")).init()?;
+let name = nvml.device_by_index(0)?.name()?;
+nvml.shutdown()?;
+
+// Anywhere else
+let nvml = Nvml::builder().replay_from(Path::new("nvml.jsonl")).init()?;
+assert_eq!(nvml.device_by_index(0)?.name()?, name);
+# Ok(())
+# }
+```
+
+# Replay
+
+Each call is matched against the log by function name and by the arguments
+that are passed by value: integers, handles and strings. It is answered by the
+earliest recorded call that matches and hasn't been replayed yet, so a program
+that makes the same calls it made while recording gets the same answers, even if
+calls from different threads interleave differently this time around.
+
+A call that has no match left in the log fails with
+`NvmlError::FailedToLoadSymbol`, the same way calls to functions a backend
+doesn't provide do.
+
+Handles are replayed as the values NVML handed out while recording. They are
+opaque to the wrapper, so this works as long as they're only passed back in.
+
+# File format
+
+A recording is a [JSON Lines](https://jsonlines.org/) file. The first line is a
+header naming the format and its version:
+
+```text
+{"format":"nvml-wrapper-recording","version":1}
+```
+
+Each following line is either a call or the message `nvmlErrorString` returned
+for a given code:
+
+```text
+{"call":{"function":"nvmlDeviceGetCount_v2","args":["out"],"ret":0,"out":[{"arg":0,"bytes":"01000000"}]}}
+{"error_string":{"code":3,"message":"Not Supported"}}
+```
+
+Arguments are logged as one of:
+
+* `{"uint":n}` or `{"int":n}` for integers (including enums)
+* `{"handle":n}` for handles
+* `{"str":"..."}` for input strings
+* `{"const":"<hex>"}` for pointers to input structs (not used for matching)
+* `{"inout":"<hex>"}` for arrays that are read as well as written, such as the
+  field IDs passed to `nvmlDeviceGetFieldValues`
+* `"out"` for output pointers and `"null"` for null pointers
+
+Outputs are logged by argument index as the hex-encoded bytes the function
+wrote (`bytes`) or as a string (`string`). Structs that point to arrays, like
+`nvmlProcessDetailList_t`, have the array's contents logged as `nested`. Plain
+values are captured if the call succeeded or returned
+`NVML_ERROR_INSUFFICIENT_SIZE`; strings and arrays only if it succeeded.
+
+Since outputs are raw bytes, a recording can only be replayed on a machine with
+the same pointer width and byte order as the one it was made on.
+
+The version is bumped whenever the format changes in a way older readers would
+misinterpret, and readers reject versions newer than they understand.
+*/
+
+#![allow(non_snake_case)]
+// The safety contract is the one documented on `NvmlBackend`
+#![allow(clippy::missing_safety_doc)]
+
+use crate::args::{Kind, LenKind, RawArg, ARRAYS};
+use crate::backend::{not_provided, nvml_functions, NvmlBackend};
+use crate::error::NvmlError;
+use crate::ffi::bindings::*;
+use serde::de::{Deserializer, Error as _};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+use serde_derive::{Deserialize, Serialize};
+use std::any::TypeId;
+use std::cmp;
+use std::collections::{HashMap, HashSet};
+use std::ffi::{CStr, CString};
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, LineWriter, Write};
+use std::mem;
+use std::os::raw;
+use std::path::Path;
+use std::ptr;
+use std::slice;
+use std::sync::{Mutex, MutexGuard};
+
+/// The name in the header of every recording.
+pub const FORMAT: &str = "nvml-wrapper-recording";
+
+/// The newest version of the recording format this crate reads and the one it
+/// writes.
+pub const VERSION: u32 = 1;
+
+/// Output arrays whose contents are also read by the function, as
+/// `(function, array argument)`.
+const INOUT: &[(&str, &str)] = &[
+    ("nvmlDeviceGetFieldValues", "values"),
+    ("nvmlDeviceClearFieldValues", "values"),
+];
+
+/// How much memory an output pointer points to.
+#[derive(Debug, Clone, Copy)]
+enum Extent {
+    /// A single value.
+    One,
+    /// A nul-terminated string, with the buffer's capacity given by the
+    /// argument at this index if there is one.
+    Str(Option<usize>),
+    /// An array with its length given by the argument at this index.
+    Array(LenKind, usize),
+}
+
+/// A struct holding a pointer to an array it doesn't own.
+struct Nested {
+    /// The number of elements the struct says the array holds.
+    len: unsafe fn(*const u8) -> usize,
+    /// Where in the struct the array pointer is kept.
+    array: unsafe fn(*mut u8) -> *mut *mut u8,
+    /// The size of one element.
+    elem_size: unsafe fn(*const u8) -> usize,
+}
+
+fn pointee_size<T>(_: *const *mut T) -> usize {
+    mem::size_of::<T>()
+}
+
+macro_rules! nested_arrays {
+    ($($ty:ty => $array:ident[$len:ident],)*) => {
+        fn nested_array(ty: TypeId) -> Option<Nested> {
+            $(
+                if ty == TypeId::of::<$ty>() {
+                    return Some(Nested {
+                        len: |s| unsafe { (*(s as *const $ty)).$len as usize },
+                        array: |s| unsafe {
+                            ptr::addr_of_mut!((*(s as *mut $ty)).$array) as *mut *mut u8
+                        },
+                        elem_size: |s| unsafe {
+                            pointee_size(ptr::addr_of!((*(s as *const $ty)).$array))
+                        },
+                    });
+                }
+            )*
+
+            None
+        }
+    };
+}
+
+nested_arrays! {
+    nvmlProcessDetailList_v1_t => procArray[numProcArrayEntries],
+    nvmlProcessesUtilizationInfo_v1_t => procUtilArray[processSamplesCount],
+    nvmlEccSramUniqueUncorrectedErrorCounts_v1_t => entries[entryCount],
+    nvmlVgpuPlacementList_v1_t => placementIds[count],
+    nvmlVgpuPlacementList_v2_t => placementIds[count],
+    nvmlVgpuInstancesUtilizationInfo_v1_t => vgpuUtilArray[vgpuInstanceCount],
+    nvmlVgpuProcessesUtilizationInfo_v1_t => vgpuProcUtilArray[vgpuProcessCount],
+    nvmlVgpuTypeIdInfo_v1_t => vgpuTypeIds[vgpuCount],
+    nvmlActiveVgpuInstanceInfo_v1_t => vgpuInstances[vgpuCount],
+    nvmlVgpuCreatablePlacementInfo_v1_t => placementIds[count],
+    nvmlSystemEventSetWaitRequest_v1_t => data[numEvent],
+}
+
+/// The metric names and units NVML points to from `nvmlGpmMetricsGet_t`.
+unsafe fn gpm_strings(request: *const nvmlGpmMetricsGet_t) -> Vec<Option<String>> {
+    let request = &*request;
+    let count = cmp::min(request.numMetrics as usize, request.metrics.len());
+
+    request.metrics[..count]
+        .iter()
+        .flat_map(|m| {
+            [
+                m.metricInfo.shortName,
+                m.metricInfo.longName,
+                m.metricInfo.unit,
+            ]
+        })
+        .map(|s| (!s.is_null()).then(|| CStr::from_ptr(s).to_string_lossy().into_owned()))
+        .collect()
+}
+
+unsafe fn restore_gpm_strings(
+    request: *mut nvmlGpmMetricsGet_t,
+    strings: &[Option<String>],
+    interned: &mut HashMap<String, CString>,
+) {
+    let mut strings = strings.iter();
+    let mut next = || match strings.next() {
+        Some(Some(s)) => intern(interned, s),
+        _ => ptr::null_mut(),
+    };
+
+    for metric in (*request).metrics.iter_mut() {
+        metric.metricInfo.shortName = next();
+        metric.metricInfo.longName = next();
+        metric.metricInfo.unit = next();
+    }
+}
+
+/// A nul-terminated copy of `s` that lives as long as `interned`.
+fn intern(interned: &mut HashMap<String, CString>, s: &str) -> *mut raw::c_char {
+    interned
+        .entry(s.into())
+        .or_insert_with(|| CString::new(s.replace('\0', "")).expect("nul bytes removed"))
+        .as_ptr() as *mut raw::c_char
+}
+
+/// Bytes, serialized as a hex string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Bytes(Vec<u8>);
+
+impl Serialize for Bytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut hex = String::with_capacity(self.0.len() * 2);
+        for byte in &self.0 {
+            let _ = write!(hex, "{:02x}", byte);
+        }
+
+        serializer.serialize_str(&hex)
+    }
+}
+
+impl<'de> Deserialize<'de> for Bytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        // Slicing below is by byte
+        if !hex.is_ascii() {
+            return Err(D::Error::custom("non-ASCII hex digits"));
+        }
+        if hex.len() % 2 != 0 {
+            return Err(D::Error::custom("odd number of hex digits"));
+        }
+
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+            .collect::<Result<_, _>>()
+            .map(Bytes)
+            .map_err(D::Error::custom)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Header {
+    format: String,
+    version: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Line {
+    Call(Call),
+    ErrorString { code: nvmlReturn_t, message: String },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Call {
+    function: String,
+    args: Vec<Input>,
+    ret: nvmlReturn_t,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    out: Vec<Output>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Input {
+    Int(i64),
+    Uint(u64),
+    Handle(u64),
+    Str(String),
+    Const(Bytes),
+    Inout(Bytes),
+    Out,
+    Null,
+}
+
+impl Input {
+    fn matches(&self, other: &Input) -> bool {
+        match (self, other) {
+            // Padding makes the bytes of input structs unreliable to compare
+            (Input::Const(_), Input::Const(_)) => true,
+            (a, b) => a == b,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Output {
+    arg: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bytes: Option<Bytes>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    string: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    nested: Option<Bytes>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    strings: Vec<Option<String>>,
+}
+
+/// The arguments to one call, along with how much memory each output pointer
+/// can take as of before the call.
+struct Args<'a> {
+    function: &'a str,
+    names: &'a [&'a str],
+    kinds: Vec<Kind>,
+    extents: Vec<Extent>,
+    /// Bytes each output can take, `None` if unknown (strings only).
+    caps: Vec<Option<usize>>,
+    /// Bytes the array pointed to by an output struct can take, and where
+    /// the struct points.
+    nested_caps: Vec<Option<(usize, *mut u8)>>,
+}
+
+impl<'a> Args<'a> {
+    unsafe fn new(function: &'a str, names: &'a [&'a str], args: &[&dyn RawArg]) -> Self {
+        let kinds: Vec<_> = args.iter().map(|a| a.kind()).collect();
+        let extents: Vec<_> = (0..kinds.len())
+            .map(|i| extent(function, names, &kinds, i))
+            .collect();
+
+        let mut args = Self {
+            function,
+            names,
+            kinds,
+            extents,
+            caps: Vec::new(),
+            nested_caps: Vec::new(),
+        };
+        args.caps = (0..args.kinds.len()).map(|i| args.bytes(i)).collect();
+        args.nested_caps = (0..args.kinds.len()).map(|i| args.nested(i)).collect();
+
+        args
+    }
+
+    /// Bytes the output at `i` currently holds (or can hold).
+    unsafe fn bytes(&self, i: usize) -> Option<usize> {
+        let p = self.kinds[i].pointer()?;
+
+        match self.extents[i] {
+            Extent::One => Some(p.size),
+            Extent::Str(len) => len.and_then(|j| self.kinds[j].count()),
+            Extent::Array(LenKind::BytesPtr, j) => self.kinds[j].count(),
+            Extent::Array(_, j) => self.kinds[j].count().map(|n| n * p.size),
+        }
+    }
+
+    /// Bytes the array the struct at `i` points to currently holds (or can
+    /// hold), and the array.
+    unsafe fn nested(&self, i: usize) -> Option<(usize, *mut u8)> {
+        let p = self.kinds[i].pointer()?;
+        let nested = nested_array(p.ty)?;
+
+        let array = *(nested.array)(p.addr);
+        if array.is_null() {
+            return None;
+        }
+
+        Some(((nested.len)(p.addr) * (nested.elem_size)(p.addr), array))
+    }
+
+    unsafe fn inputs(&self) -> Vec<Input> {
+        (0..self.kinds.len()).map(|i| self.input(i)).collect()
+    }
+
+    unsafe fn input(&self, i: usize) -> Input {
+        match self.kinds[i] {
+            Kind::Int(value) => Input::Int(value),
+            Kind::Uint(value) => Input::Uint(value),
+            Kind::Handle(p) => Input::Handle(p.addr as u64),
+            Kind::Const(p) | Kind::Mut(p) if p.addr.is_null() => Input::Null,
+            Kind::Const(p) if p.is::<raw::c_char>() => Input::Str(
+                CStr::from_ptr(p.addr as *const raw::c_char)
+                    .to_string_lossy()
+                    .into_owned(),
+            ),
+            Kind::Const(p) => Input::Const(Bytes(read(p.addr, p.size))),
+            Kind::Mut(p) if INOUT.contains(&(self.function, self.names[i])) => {
+                Input::Inout(Bytes(read(p.addr, self.caps[i].unwrap_or(0))))
+            }
+            Kind::Mut(_) => Input::Out,
+        }
+    }
+
+    /// Capture what the call wrote through its output pointers.
+    unsafe fn outputs(&self, ret: nvmlReturn_t) -> Vec<Output> {
+        let succeeded = ret == nvmlReturn_enum_NVML_SUCCESS;
+        let insufficient = ret == nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE;
+
+        let mut outputs = Vec::new();
+        for (i, kind) in self.kinds.iter().enumerate() {
+            let p = match kind {
+                Kind::Mut(p) if !p.addr.is_null() => p,
+                _ => continue,
+            };
+
+            let mut output = Output {
+                arg: i,
+                ..Default::default()
+            };
+
+            match self.extents[i] {
+                Extent::One if succeeded || insufficient => {
+                    output.bytes = Some(Bytes(read(p.addr, p.size)));
+
+                    if let (true, Some((cap, array))) = (succeeded, self.nested_caps[i]) {
+                        let len = self.nested(i).map_or(0, |(len, _)| len);
+                        output.nested = Some(Bytes(read(array, cmp::min(cap, len))));
+                    }
+                    if succeeded && p.is::<nvmlGpmMetricsGet_t>() {
+                        output.strings = gpm_strings(p.addr as *const _);
+                    }
+                }
+                Extent::Str(_) if succeeded => {
+                    output.string = Some(read_str(p.addr as *const _, self.caps[i]));
+                }
+                Extent::Array(..) if succeeded => {
+                    let len = match (self.caps[i], self.bytes(i)) {
+                        (Some(cap), Some(len)) => cmp::min(cap, len),
+                        _ => 0,
+                    };
+                    output.bytes = Some(Bytes(read(p.addr, len)));
+                }
+                _ => continue,
+            }
+
+            outputs.push(output);
+        }
+
+        outputs
+    }
+
+    /// Write a recorded call's outputs through the output pointers.
+    unsafe fn restore(&self, outputs: &[Output], interned: &mut HashMap<String, CString>) {
+        for output in outputs {
+            let p = match self.kinds.get(output.arg) {
+                Some(Kind::Mut(p)) if !p.addr.is_null() => *p,
+                _ => continue,
+            };
+            let cap = self.caps[output.arg];
+
+            if let Some(Bytes(bytes)) = &output.bytes {
+                let len = cmp::min(bytes.len(), cap.unwrap_or(0));
+                ptr::copy_nonoverlapping(bytes.as_ptr(), p.addr, len);
+            }
+
+            if let Some(s) = &output.string {
+                write_str(p.addr as *mut _, s, cap);
+            }
+
+            if let Some(nested) = nested_array(p.ty) {
+                // The recorded pointer is meaningless here; point back at the
+                // caller's array and fill that in instead
+                let (cap, array) = self.nested_caps[output.arg].unwrap_or((0, ptr::null_mut()));
+                *(nested.array)(p.addr) = array;
+
+                if let Some(Bytes(bytes)) = &output.nested {
+                    ptr::copy_nonoverlapping(bytes.as_ptr(), array, cmp::min(bytes.len(), cap));
+                }
+            }
+
+            if p.is::<nvmlGpmMetricsGet_t>() {
+                restore_gpm_strings(p.addr as *mut _, &output.strings, interned);
+            }
+        }
+    }
+}
+
+fn extent(function: &str, names: &[&str], kinds: &[Kind], i: usize) -> Extent {
+    let array = ARRAYS
+        .iter()
+        .find(|(f, array, ..)| *f == function && *array == names[i]);
+
+    if let Some((_, _, len, len_kind)) = array {
+        if let Some(j) = names.iter().position(|name| name == len) {
+            return Extent::Array(*len_kind, j);
+        }
+    }
+
+    match kinds[i] {
+        Kind::Mut(p) if p.is::<raw::c_char>() => {
+            // String buffers are followed by their size, if it's passed at all
+            let len = match kinds.get(i + 1) {
+                Some(Kind::Uint(_)) => Some(i + 1),
+                Some(Kind::Mut(p)) if p.is::<raw::c_uint>() => Some(i + 1),
+                _ => None,
+            };
+
+            Extent::Str(len)
+        }
+        _ => Extent::One,
+    }
+}
+
+unsafe fn read(addr: *const u8, len: usize) -> Vec<u8> {
+    if len == 0 {
+        return Vec::new();
+    }
+
+    slice::from_raw_parts(addr, len).to_vec()
+}
+
+/// Read the nul-terminated string at `addr`, looking at no more than `cap`
+/// bytes.
+unsafe fn read_str(addr: *const raw::c_char, cap: Option<usize>) -> String {
+    let bytes = match cap {
+        Some(cap) => {
+            let buf = slice::from_raw_parts(addr as *const u8, cap);
+            &buf[..buf.iter().position(|&b| b == 0).unwrap_or(cap)]
+        }
+        None => CStr::from_ptr(addr).to_bytes(),
+    };
+
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+/// Write `s` nul-terminated to `addr`, truncating it to fit `cap` bytes.
+unsafe fn write_str(addr: *mut raw::c_char, s: &str, cap: Option<usize>) {
+    let len = match cap {
+        Some(0) => return,
+        Some(cap) => cmp::min(s.len(), cap - 1),
+        None => s.len(),
+    };
+
+    ptr::copy_nonoverlapping(s.as_ptr() as *const raw::c_char, addr, len);
+    *addr.add(len) = 0;
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn recording_error(e: impl std::fmt::Display) -> NvmlError {
+    NvmlError::Recording(e.to_string())
+}
+
+/**
+A backend that logs every call made through it before passing on the result.
+
+See the [module-level documentation](self) for the file format.
+
+Writing is best-effort: if writing to the log fails, calls keep going through to
+the inner backend and the error is reported when the backend is closed (i.e.
+from `Nvml.shutdown()`).
+*/
+pub struct Recorder<B> {
+    inner: B,
+    log: Mutex<Log>,
+}
+
+struct Log {
+    writer: Box<dyn Write + Send>,
+    error: Option<io::Error>,
+    error_strings: HashSet<nvmlReturn_t>,
+}
+
+impl<B: NvmlBackend> Recorder<B> {
+    /// Record calls to `inner` to the file at `path`, creating or truncating it.
+    pub fn create<P: AsRef<Path>>(inner: B, path: P) -> Result<Self, NvmlError> {
+        let file = File::create(path).map_err(recording_error)?;
+
+        Self::new(inner, LineWriter::new(file))
+    }
+
+    /// Record calls to `inner` to `writer`.
+    pub fn new<W: Write + Send + 'static>(inner: B, writer: W) -> Result<Self, NvmlError> {
+        let mut writer: Box<dyn Write + Send> = Box::new(writer);
+
+        let header = Header {
+            format: FORMAT.into(),
+            version: VERSION,
+        };
+        serde_json::to_writer(&mut writer, &header).map_err(recording_error)?;
+        writer.write_all(b"\n").map_err(recording_error)?;
+
+        Ok(Self {
+            inner,
+            log: Mutex::new(Log {
+                writer,
+                error: None,
+                error_strings: HashSet::new(),
+            }),
+        })
+    }
+
+    /// The backend calls are passed on to.
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    fn write(&self, line: &Line) {
+        let mut guard = lock(&self.log);
+        let log = &mut *guard;
+        if log.error.is_some() {
+            return;
+        }
+
+        let result = serde_json::to_writer(&mut log.writer, line)
+            .map_err(io::Error::from)
+            .and_then(|_| log.writer.write_all(b"\n"));
+
+        if let Err(e) = result {
+            log.error = Some(e);
+        }
+    }
+
+    unsafe fn record<F>(
+        &self,
+        function: &str,
+        names: &[&str],
+        args: &[&dyn RawArg],
+        call: F,
+    ) -> Result<nvmlReturn_t, NvmlError>
+    where
+        F: FnOnce() -> Result<nvmlReturn_t, NvmlError>,
+    {
+        let args = Args::new(function, names, args);
+        let inputs = args.inputs();
+
+        let ret = call()?;
+
+        self.write(&Line::Call(Call {
+            function: function.into(),
+            args: inputs,
+            ret,
+            out: args.outputs(ret),
+        }));
+
+        Ok(ret)
+    }
+}
+
+/**
+A backend that answers calls from a log written by a [`Recorder`].
+
+See the [module-level documentation](self) for how calls are matched.
+*/
+pub struct Replayer {
+    state: Mutex<Replay>,
+}
+
+struct Replay {
+    calls: Vec<Option<Call>>,
+    /// The index of the first call that hasn't been replayed yet.
+    next: usize,
+    error_strings: HashMap<nvmlReturn_t, CString>,
+    interned: HashMap<String, CString>,
+}
+
+impl Replayer {
+    /// Replay the recording in the file at `path`.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, NvmlError> {
+        let file = File::open(path).map_err(recording_error)?;
+
+        Self::from_reader(BufReader::new(file))
+    }
+
+    /// Replay the recording read from `reader`.
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, NvmlError> {
+        let mut lines = reader
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !matches!(line, Ok(l) if l.trim().is_empty()));
+
+        let header: Header = match lines.next() {
+            Some((_, line)) => {
+                serde_json::from_str(&line.map_err(recording_error)?).map_err(recording_error)?
+            }
+            None => return Err(NvmlError::Recording("the recording is empty".into())),
+        };
+        if header.format != FORMAT {
+            return Err(NvmlError::Recording(format!(
+                "not a recording (format is {:?})",
+                header.format
+            )));
+        }
+        if header.version > VERSION {
+            return Err(NvmlError::Recording(format!(
+                "unsupported recording version {} (newest supported is {})",
+                header.version, VERSION
+            )));
+        }
+
+        let mut replay = Replay {
+            calls: Vec::new(),
+            next: 0,
+            error_strings: HashMap::new(),
+            interned: HashMap::new(),
+        };
+
+        for (index, line) in lines {
+            let line = line.map_err(recording_error)?;
+            let line = serde_json::from_str(&line)
+                .map_err(|e| NvmlError::Recording(format!("line {}: {}", index + 1, e)))?;
+
+            match line {
+                Line::Call(call) => replay.calls.push(Some(call)),
+                Line::ErrorString { code, message } => {
+                    let message =
+                        CString::new(message.replace('\0', "")).expect("nul bytes removed");
+                    replay.error_strings.insert(code, message);
+                }
+            }
+        }
+
+        Ok(Self {
+            state: Mutex::new(replay),
+        })
+    }
+
+    /// The number of recorded calls that haven't been replayed yet.
+    pub fn remaining(&self) -> usize {
+        let state = lock(&self.state);
+
+        state.calls[state.next..]
+            .iter()
+            .filter(|c| c.is_some())
+            .count()
+    }
+
+    unsafe fn replay(
+        &self,
+        function: &str,
+        names: &[&str],
+        args: &[&dyn RawArg],
+    ) -> Result<nvmlReturn_t, NvmlError> {
+        let args = Args::new(function, names, args);
+        let inputs = args.inputs();
+
+        let mut guard = lock(&self.state);
+        let state = &mut *guard;
+
+        let call = state
+            .take(function, &inputs)
+            .ok_or_else(|| not_recorded(function))?;
+        args.restore(&call.out, &mut state.interned);
+
+        Ok(call.ret)
+    }
+}
+
+impl Replay {
+    /// Take the earliest call to `function` with matching arguments that
+    /// hasn't been replayed yet.
+    fn take(&mut self, function: &str, args: &[Input]) -> Option<Call> {
+        let index = self.calls[self.next..].iter().position(|call| {
+            call.as_ref().map_or(false, |call| {
+                call.function == function
+                    && call.args.len() == args.len()
+                    && call.args.iter().zip(args).all(|(a, b)| a.matches(b))
+            })
+        })? + self.next;
+
+        let call = self.calls[index].take();
+        while self.calls.get(self.next).map_or(false, Option::is_none) {
+            self.next += 1;
+        }
+
+        call
+    }
+}
+
+fn not_recorded(function: &str) -> NvmlError {
+    NvmlError::FailedToLoadSymbol(format!(
+        "{} has no matching call left in the recording",
+        function
+    ))
+}
+
+macro_rules! recording_backends {
+    ($(
+        $(#[$attr:meta])*
+        fn $name:ident($($arg:ident: $ty:ty),* $(,)?);
+    )*) => {
+        impl<B: NvmlBackend> NvmlBackend for Recorder<B> {
+            fn lib(&self) -> Option<&NvmlLib> {
+                self.inner.lib()
+            }
+
+            fn close(self: Box<Self>) -> Result<(), NvmlError> {
+                let Self { inner, log } = *self;
+                let mut log = log.into_inner().unwrap_or_else(|e| e.into_inner());
+
+                let flushed = match log.error.take() {
+                    Some(e) => Err(e),
+                    None => log.writer.flush(),
+                };
+                Box::new(inner).close()?;
+
+                flushed.map_err(recording_error)
+            }
+
+            unsafe fn nvmlErrorString(
+                &self,
+                result: nvmlReturn_t,
+            ) -> Result<*const raw::c_char, NvmlError> {
+                let message = self.inner.nvmlErrorString(result)?;
+
+                let first = lock(&self.log).error_strings.insert(result);
+                if first && !message.is_null() {
+                    self.write(&Line::ErrorString {
+                        code: result,
+                        message: CStr::from_ptr(message).to_string_lossy().into_owned(),
+                    });
+                }
+
+                Ok(message)
+            }
+
+            $(
+                $(#[$attr])*
+                unsafe fn $name(&self, $($arg: $ty),*) -> Result<nvmlReturn_t, NvmlError> {
+                    self.record(
+                        stringify!($name),
+                        &[$(stringify!($arg)),*],
+                        &[$(&$arg),*],
+                        || self.inner.$name($($arg),*),
+                    )
+                }
+            )*
+        }
+
+        impl NvmlBackend for Replayer {
+            unsafe fn nvmlErrorString(
+                &self,
+                result: nvmlReturn_t,
+            ) -> Result<*const raw::c_char, NvmlError> {
+                lock(&self.state)
+                    .error_strings
+                    .get(&result)
+                    .map(|message| message.as_ptr())
+                    .ok_or_else(|| not_provided("nvmlErrorString"))
+            }
+
+            $(
+                $(#[$attr])*
+                unsafe fn $name(&self, $($arg: $ty),*) -> Result<nvmlReturn_t, NvmlError> {
+                    self.replay(stringify!($name), &[$(stringify!($arg)),*], &[$(&$arg),*])
+                }
+            )*
+        }
+
+        /// The argument names of every function.
+        #[cfg(test)]
+        #[allow(clippy::vec_init_then_push)]
+        fn signatures() -> Vec<(&'static str, Vec<&'static str>)> {
+            let mut signatures = Vec::new();
+            $(
+                $(#[$attr])*
+                signatures.push((stringify!($name), vec![$(stringify!($arg)),*]));
+            )*
+
+            signatures
+        }
+    };
+}
+
+nvml_functions!(recording_backends);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::enums::device::UsedGpuMemory;
+    use crate::test_utils::mock_backend;
+    use crate::Nvml;
+    use std::sync::Arc;
+
+    /// A GPU with a name and two compute processes, one of which is hidden
+    /// once the first call to list them has been made.
+    struct Mock;
+
+    impl NvmlBackend for Mock {
+        mock_backend!();
+
+        unsafe fn nvmlDeviceGetName(
+            &self,
+            _device: nvmlDevice_t,
+            name: *mut raw::c_char,
+            length: raw::c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            write_str(name, "Mock GPU", Some(length as usize));
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetComputeRunningProcesses_v3(
+            &self,
+            _device: nvmlDevice_t,
+            count: *mut raw::c_uint,
+            infos: *mut nvmlProcessInfo_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            if *count < 2 {
+                *count = 2;
+                return Ok(nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE);
+            }
+
+            for (i, pid) in [10, 20].iter().enumerate() {
+                *infos.add(i) = nvmlProcessInfo_t {
+                    pid: *pid,
+                    usedGpuMemory: 1 << 20,
+                    gpuInstanceId: raw::c_uint::MAX,
+                    computeInstanceId: raw::c_uint::MAX,
+                };
+            }
+
+            *count = 2;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetProcessesUtilizationInfo(
+            &self,
+            _device: nvmlDevice_t,
+            info: *mut nvmlProcessesUtilizationInfo_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let info = &mut *info;
+            if info.processSamplesCount < 1 || info.procUtilArray.is_null() {
+                info.processSamplesCount = 1;
+                return Ok(nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE);
+            }
+
+            (*info.procUtilArray).pid = 10;
+            (*info.procUtilArray).smUtil = 42;
+            info.processSamplesCount = 1;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlErrorString(
+            &self,
+            _result: nvmlReturn_t,
+        ) -> Result<*const raw::c_char, NvmlError> {
+            Ok(b"Mock error\0".as_ptr() as *const _)
+        }
+    }
+
+    /// A writer whose output can be read back after it's been handed off.
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            lock(&self.0).extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Run `f` against the mock while recording, then again against the
+    /// recording, checking that it produces the same result both times.
+    fn round_trip<T, F>(f: F) -> (String, Replayer)
+    where
+        T: PartialEq + std::fmt::Debug,
+        F: Fn(&Nvml) -> Result<T, NvmlError>,
+    {
+        let log = Shared::default();
+        let nvml = Nvml::builder()
+            .init_with_backend(Recorder::new(Mock, log.clone()).unwrap())
+            .unwrap();
+        let recorded = f(&nvml).unwrap();
+        nvml.shutdown().unwrap();
+
+        let log = String::from_utf8(lock(&log.0).clone()).unwrap();
+        let replayer = Replayer::from_reader(log.as_bytes()).unwrap();
+        let nvml = Nvml::builder()
+            .init_with_backend(Replayer::from_reader(log.as_bytes()).unwrap())
+            .unwrap();
+        assert_eq!(f(&nvml).unwrap(), recorded);
+        nvml.shutdown().unwrap();
+
+        (log, replayer)
+    }
+
+    #[test]
+    fn strings_and_handles() {
+        let (log, _) = round_trip(|nvml| {
+            let device = nvml.device_by_index(0)?;
+            Ok((nvml.device_count()?, device.name()?))
+        });
+
+        let mut lines = log.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            r#"{"format":"nvml-wrapper-recording","version":1}"#
+        );
+        assert!(log.contains(r#""string":"Mock GPU""#));
+        assert!(log.contains(r#"{"handle":1}"#));
+    }
+
+    #[test]
+    fn arrays() {
+        round_trip(|nvml| {
+            let processes = nvml.device_by_index(0)?.running_compute_processes()?;
+            assert_eq!(processes.len(), 2);
+            assert_eq!(processes[1].used_gpu_memory, UsedGpuMemory::Used(1 << 20));
+
+            Ok(processes)
+        });
+    }
+
+    #[test]
+    fn nested_arrays_point_at_the_callers_buffer() {
+        let log = Shared::default();
+        let recorder = Recorder::new(Mock, log.clone()).unwrap();
+
+        let query = |backend: &dyn NvmlBackend| unsafe {
+            let mut samples = [mem::zeroed::<nvmlProcessUtilizationInfo_v1_t>(); 4];
+            let mut info: nvmlProcessesUtilizationInfo_t = mem::zeroed();
+            info.processSamplesCount = samples.len() as raw::c_uint;
+            info.procUtilArray = samples.as_mut_ptr();
+
+            let ret = backend
+                .nvmlDeviceGetProcessesUtilizationInfo(ptr::null_mut(), &mut info)
+                .unwrap();
+            assert_eq!(ret, nvmlReturn_enum_NVML_SUCCESS);
+            assert_eq!(info.procUtilArray, samples.as_mut_ptr());
+
+            (info.processSamplesCount, samples[0].pid, samples[0].smUtil)
+        };
+
+        let recorded = query(&recorder);
+        let log = lock(&log.0).clone();
+        let replayed = query(&Replayer::from_reader(&log[..]).unwrap());
+
+        assert_eq!(recorded, (1, 10, 42));
+        assert_eq!(replayed, recorded);
+    }
+
+    #[test]
+    fn unmatched_calls_fail() {
+        let (_, replayer) = round_trip(|nvml| nvml.device_count());
+        let nvml = Nvml::builder().init_with_backend(replayer).unwrap();
+
+        // The recording's only `nvmlDeviceGetCount_v2` call has been used up
+        assert_eq!(nvml.device_count().unwrap(), 1);
+        assert!(matches!(
+            nvml.device_count(),
+            Err(NvmlError::FailedToLoadSymbol(_))
+        ));
+        assert!(matches!(
+            nvml.device_by_index(0),
+            Err(NvmlError::FailedToLoadSymbol(_))
+        ));
+    }
+
+    #[test]
+    fn error_strings() {
+        let log = Shared::default();
+        let recorder = Recorder::new(Mock, log.clone()).unwrap();
+        unsafe {
+            recorder.nvmlErrorString(3).unwrap();
+            recorder.nvmlErrorString(3).unwrap();
+        }
+
+        let log = lock(&log.0).clone();
+        assert_eq!(
+            log.split(|&b| b == b'\n').filter(|l| !l.is_empty()).count(),
+            2
+        );
+
+        let replayer = Replayer::from_reader(&log[..]).unwrap();
+        unsafe {
+            let message = CStr::from_ptr(replayer.nvmlErrorString(3).unwrap());
+            assert_eq!(message.to_str().unwrap(), "Mock error");
+            assert!(replayer.nvmlErrorString(4).is_err());
+        }
+    }
+
+    #[test]
+    fn rejects_other_formats_and_newer_versions() {
+        let newer = format!(r#"{{"format":"{}","version":{}}}"#, FORMAT, VERSION + 1);
+        let other = r#"{"format":"something-else","version":1}"#;
+
+        for log in [newer.as_str(), other, ""] {
+            assert!(matches!(
+                Replayer::from_reader(log.as_bytes()),
+                Err(NvmlError::Recording(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_hex_bytes() {
+        for hex in [r#""abc""#, r#""zz""#, r#""aéa""#] {
+            assert!(serde_json::from_str::<Bytes>(hex).is_err(), "{}", hex);
+        }
+        assert_eq!(
+            serde_json::from_str::<Bytes>(r#""00ff""#).unwrap(),
+            Bytes(vec![0, 255])
+        );
+    }
+
+    #[test]
+    fn array_table_matches_signatures() {
+        let signatures: HashMap<_, _> = signatures().into_iter().collect();
+
+        for (function, array, len, _) in ARRAYS {
+            // Legacy functions are only present with the `legacy-functions` feature
+            if let Some(args) = signatures.get(function) {
+                assert!(args.contains(array), "{}({})", function, array);
+                assert!(args.contains(len), "{}({})", function, len);
+            }
+        }
+        for (function, array) in INOUT {
+            assert!(signatures[function].contains(array));
+        }
+    }
+}
@@ -0,0 +1,285 @@
+/*!
+The process-wide `Nvml` instance behind `Nvml::shared()`.
+
+The instance is held weakly: callers get `Arc<Nvml>`s, and NVML is shut down
+when the last of them is dropped. The next call initializes it again.
+
+A failed initialization is remembered for the configuration (lib path and
+flags) it was attempted with, so hosts without NVML pay for the failed `dlopen`
+once rather than on every call.
+*/
+
+use crate::bitmasks::InitFlags;
+use crate::error::NvmlError;
+use crate::{Nvml, NvmlBuilder};
+use once_cell::sync::Lazy;
+use std::ffi::{OsStr, OsString};
+use std::sync::{Arc, Mutex, Weak};
+
+/// The configuration an instance was (or failed to be) initialized with.
+type Key = (Option<OsString>, InitFlags);
+
+#[derive(Default)]
+pub(crate) struct Shared {
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    instance: Weak<Nvml>,
+    failure: Option<(Key, NvmlError)>,
+}
+
+static SHARED: Lazy<Shared> = Lazy::new(Shared::default);
+
+impl Shared {
+    /**
+    Get the live instance, or create one with `init` unless `init` already
+    failed for `key`.
+
+    A live instance is returned whatever `key` it was created with.
+    */
+    pub(crate) fn get<F>(&self, key: Key, init: F) -> Result<Arc<Nvml>, NvmlError>
+    where
+        F: FnOnce() -> Result<Nvml, NvmlError>,
+    {
+        // `init` runs under the lock so that racing callers don't each load
+        // the library; a panic in it leaves nothing half-updated
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some(nvml) = state.instance.upgrade() {
+            return Ok(nvml);
+        }
+
+        if let Some((ref failed, ref error)) = state.failure {
+            if *failed == key {
+                return Err(duplicate(error));
+            }
+        }
+
+        match init() {
+            Ok(nvml) => {
+                let nvml = Arc::new(nvml);
+                state.instance = Arc::downgrade(&nvml);
+                state.failure = None;
+
+                Ok(nvml)
+            }
+            Err(error) => {
+                state.failure = Some((key, duplicate(&error)));
+
+                Err(error)
+            }
+        }
+    }
+}
+
+impl Nvml {
+    /**
+    Get a handle to the process-wide `Nvml` instance, initializing it with
+    `Nvml::init()` if there isn't one alive.
+
+    Every caller in the process, including other libraries, gets the same
+    instance, so the NVML library is loaded and its symbols are resolved once.
+    NVML is shut down when the last handle is dropped; the next call initializes
+    it again. Pair this with `OwnedDevice` to keep devices alongside the handle.
+
+    Use `NvmlBuilder::shared()` to choose the lib path and flags.
+
+    # Errors
+
+    The same as `Nvml::init()`. If initialization fails, the error is
+    remembered and returned by later calls without retrying, except that a
+    library loading error is returned as `LibraryNotFound`. Calls with a
+    different lib path or flags (through `NvmlBuilder::shared()`) try again.
+
+    # Examples
+
+    ```
+    use nvml_wrapper::Nvml;
+
+    match Nvml::shared() {
+        Ok(nvml) => println!("{} GPUs", nvml.device_count().unwrap_or(0)),
+        Err(e) => println!("no NVML: {}", e),
+    }
+    ```
+    */
+    pub fn shared() -> Result<Arc<Nvml>, NvmlError> {
+        Nvml::builder().shared()
+    }
+}
+
+impl<'a> NvmlBuilder<'a> {
+    /**
+    Get a handle to the process-wide `Nvml` instance, initializing it with
+    this builder if there isn't one alive.
+
+    If an instance is alive, it's returned as is: the lib path, flags and
+    other settings on this builder only apply when this call initializes NVML.
+    See `Nvml::shared()` for details.
+
+    # Errors
+
+    The same as `init()`. A failed initialization is remembered for this
+    builder's lib path and flags, and returned again (a library loading error
+    as `LibraryNotFound`) by later calls with the same lib path and flags.
+    */
+    pub fn shared(&self) -> Result<Arc<Nvml>, NvmlError> {
+        let key = (self.lib_path.map(OsStr::to_os_string), self.flags);
+
+        SHARED.get(key, || self.init())
+    }
+}
+
+/// Copy `error`, which isn't `Clone` because `libloading::Error` isn't.
+#[allow(deprecated)]
+fn duplicate(error: &NvmlError) -> NvmlError {
+    use NvmlError::*;
+
+    match error {
+        Utf8Error(e) => Utf8Error(*e),
+        NulError(e) => NulError(e.clone()),
+        LibloadingError(_) => LibraryNotFound,
+        FailedToLoadSymbol(s) => FailedToLoadSymbol(s.clone()),
+        StringTooLong {
+            max_len,
+            actual_len,
+        } => StringTooLong {
+            max_len: *max_len,
+            actual_len: *actual_len,
+        },
+        IncorrectBits(bits) => IncorrectBits(bits.clone()),
+        UnexpectedVariant(v) => UnexpectedVariant(*v),
+        SetReleaseFailed => SetReleaseFailed,
+        GetPciInfoFailed => GetPciInfoFailed,
+        PciInfoToCFailed => PciInfoToCFailed,
+        Recording(s) => Recording(s.clone()),
+        UnsupportedVgpuSchedulerState(s) => UnsupportedVgpuSchedulerState(s.clone()),
+        Uninitialized => Uninitialized,
+        InvalidArg => InvalidArg,
+        NotSupported => NotSupported,
+        NoPermission => NoPermission,
+        AlreadyInitialized => AlreadyInitialized,
+        NotFound => NotFound,
+        InsufficientSize(size) => InsufficientSize(*size),
+        InsufficientPower => InsufficientPower,
+        DriverNotLoaded => DriverNotLoaded,
+        Timeout => Timeout,
+        IrqIssue => IrqIssue,
+        LibraryNotFound => LibraryNotFound,
+        FunctionNotFound => FunctionNotFound,
+        CorruptedInfoROM => CorruptedInfoROM,
+        GpuLost => GpuLost,
+        ResetRequired => ResetRequired,
+        OperatingSystem => OperatingSystem,
+        LibRmVersionMismatch => LibRmVersionMismatch,
+        InUse => InUse,
+        InsufficientMemory => InsufficientMemory,
+        NoData => NoData,
+        VgpuEccNotSupported => VgpuEccNotSupported,
+        InsufficientResources => InsufficientResources,
+        Unknown => Unknown,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::NvmlBackend;
+    use crate::ffi::bindings::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// How many times a `Mock` was initialized and shut down.
+    #[derive(Default)]
+    struct Counts {
+        inits: AtomicUsize,
+        shutdowns: AtomicUsize,
+    }
+
+    /// Fails to initialize with the given code, if any.
+    struct Mock(Arc<Counts>, Option<nvmlReturn_t>);
+
+    impl NvmlBackend for Mock {
+        unsafe fn nvmlInit_v2(&self) -> Result<nvmlReturn_t, NvmlError> {
+            self.0.inits.fetch_add(1, Ordering::SeqCst);
+            Ok(self.1.unwrap_or(nvmlReturn_enum_NVML_SUCCESS))
+        }
+
+        unsafe fn nvmlShutdown(&self) -> Result<nvmlReturn_t, NvmlError> {
+            self.0.shutdowns.fetch_add(1, Ordering::SeqCst);
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+    }
+
+    fn init(counts: &Arc<Counts>, fail: Option<nvmlReturn_t>) -> Result<Nvml, NvmlError> {
+        Nvml::builder().init_with_backend(Mock(counts.clone(), fail))
+    }
+
+    fn key(path: Option<&str>) -> Key {
+        (path.map(OsString::from), InitFlags::empty())
+    }
+
+    #[test]
+    fn handles_share_one_instance_until_the_last_drops() {
+        let shared = Shared::default();
+        let counts = Arc::new(Counts::default());
+
+        let a = shared.get(key(None), || init(&counts, None)).unwrap();
+        // A live instance is handed out whatever the configuration
+        let b = shared
+            .get(key(Some("other")), || init(&counts, None))
+            .unwrap();
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(counts.inits.load(Ordering::SeqCst), 1);
+
+        drop(a);
+        assert_eq!(counts.shutdowns.load(Ordering::SeqCst), 0);
+        drop(b);
+        assert_eq!(counts.shutdowns.load(Ordering::SeqCst), 1);
+
+        let _c = shared.get(key(None), || init(&counts, None)).unwrap();
+        assert_eq!(counts.inits.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn failures_are_cached_per_configuration() {
+        let shared = Shared::default();
+        let counts = Arc::new(Counts::default());
+        let not_loaded = Some(nvmlReturn_enum_NVML_ERROR_DRIVER_NOT_LOADED);
+
+        for _ in 0..3 {
+            assert!(matches!(
+                shared.get(key(None), || init(&counts, not_loaded)),
+                Err(NvmlError::DriverNotLoaded)
+            ));
+        }
+        assert_eq!(counts.inits.load(Ordering::SeqCst), 1);
+
+        // Another configuration is worth trying, and clears the failure
+        shared
+            .get(key(Some("other")), || init(&counts, None))
+            .unwrap();
+        assert_eq!(counts.inits.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn library_loading_errors_are_cached() {
+        let shared = Shared::default();
+        let mut attempts = 0;
+
+        for _ in 0..2 {
+            let result = shared.get(key(Some("/nonexistent/libnvidia-ml.so")), || {
+                attempts += 1;
+                Nvml::builder()
+                    .lib_path("/nonexistent/libnvidia-ml.so".as_ref())
+                    .init()
+            });
+
+            assert!(matches!(
+                result,
+                Err(NvmlError::LibloadingError(_)) | Err(NvmlError::LibraryNotFound)
+            ));
+        }
+        assert_eq!(attempts, 1);
+    }
+}
@@ -1,21 +1,24 @@
-use crate::bitmasks::device::FbcFlags;
+use crate::bitmasks::device::{
+    FbcFlags, VgpuCompatibilityLimit, VgpuVirtualizationCaps, VgpuVmCompatibility,
+};
 use crate::enum_wrappers::device::{
     BridgeChip, Clock, EncoderType, FbcSessionType, PerformanceState, SampleValueType,
+    VgpuGuestInfoState,
+};
+use crate::enums::device::{
+    FirmwareVersion, SampleValue, ThermalController, ThermalTarget, UsedGpuMemory,
+    VgpuSchedulerArrMode, VgpuSchedulerPolicy,
 };
-use crate::enums::device::{FirmwareVersion, SampleValue, UsedGpuMemory};
 use crate::error::{nvml_try, Bits, NvmlError};
 use crate::ffi::bindings::*;
+use crate::struct_wrappers::{c_array, c_string};
 use crate::structs::device::FieldId;
 #[cfg(feature = "serde")]
 use serde_derive::{Deserialize, Serialize};
-use std::{
-    cmp::Ordering,
-    ffi::{CStr, CString},
-};
-use std::{
-    convert::{TryFrom, TryInto},
-    os::raw::c_char,
-};
+use std::convert::{TryFrom, TryInto};
+use std::ffi::CStr;
+use std::time::Duration;
+use std::{mem, ptr, slice};
 
 /// PCI information about a GPU device.
 // Checked against local
@@ -74,10 +77,6 @@ impl PciInfo {
             })
         }
     }
-}
-
-impl TryInto<nvmlPciInfo_t> for PciInfo {
-    type Error = NvmlError;
 
     /**
     Convert this `PciInfo` back into its C equivalent.
@@ -90,35 +89,7 @@ impl TryInto<nvmlPciInfo_t> for PciInfo {
       occur if the user modifies `bus_id` in some fashion. We return an error
       rather than panicking.
     */
-    fn try_into(self) -> Result<nvmlPciInfo_t, Self::Error> {
-        // This is more readable than spraying `buf_size as usize` everywhere
-        const fn buf_size() -> usize {
-            NVML_DEVICE_PCI_BUS_ID_BUFFER_SIZE as usize
-        }
-
-        let mut bus_id_c: [c_char; buf_size()] = [0; buf_size()];
-        let mut bus_id = CString::new(self.bus_id)?.into_bytes_with_nul();
-
-        // Make the string the same length as the array we need to clone it to
-        match bus_id.len().cmp(&buf_size()) {
-            Ordering::Less => {
-                while bus_id.len() != buf_size() {
-                    bus_id.push(0);
-                }
-            }
-            Ordering::Equal => {
-                // No need to do anything; the buffers are already the same length
-            }
-            Ordering::Greater => {
-                return Err(NvmlError::StringTooLong {
-                    max_len: buf_size(),
-                    actual_len: bus_id.len(),
-                })
-            }
-        }
-
-        bus_id_c.clone_from_slice(&bus_id.into_iter().map(|b| b as c_char).collect::<Vec<_>>());
-
+    pub fn try_into_c(self) -> Result<nvmlPciInfo_t, NvmlError> {
         Ok(nvmlPciInfo_t {
             busIdLegacy: [0; NVML_DEVICE_PCI_BUS_ID_BUFFER_V2_SIZE as usize],
             domain: self.domain,
@@ -128,11 +99,24 @@ impl TryInto<nvmlPciInfo_t> for PciInfo {
             // This seems the most correct thing to do? Since this should only
             // be none if obtained from `NvLink.remote_pci_info()`.
             pciSubSystemId: self.pci_sub_system_id.unwrap_or(0),
-            busId: bus_id_c,
+            busId: c_string(self.bus_id)?,
         })
     }
 }
 
+impl TryInto<nvmlPciInfo_t> for PciInfo {
+    type Error = NvmlError;
+
+    /**
+    Convert this `PciInfo` back into its C equivalent.
+
+    See [`PciInfo::try_into_c()`].
+    */
+    fn try_into(self) -> Result<nvmlPciInfo_t, Self::Error> {
+        self.try_into_c()
+    }
+}
+
 /// BAR1 memory allocation information for a device (in bytes)
 // Checked against local
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -156,6 +140,17 @@ impl From<nvmlBAR1Memory_t> for BAR1MemoryInfo {
     }
 }
 
+impl BAR1MemoryInfo {
+    /// Convert this `BAR1MemoryInfo` back into its C counterpart.
+    pub fn into_c(self) -> nvmlBAR1Memory_t {
+        nvmlBAR1Memory_t {
+            bar1Free: self.free,
+            bar1Total: self.total,
+            bar1Used: self.used,
+        }
+    }
+}
+
 /// Information about a bridge chip.
 // Checked against local
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -186,6 +181,16 @@ impl TryFrom<nvmlBridgeChipInfo_t> for BridgeChipInfo {
     }
 }
 
+impl BridgeChipInfo {
+    /// Convert this `BridgeChipInfo` back into its C counterpart.
+    pub fn into_c(self) -> nvmlBridgeChipInfo_t {
+        nvmlBridgeChipInfo_t {
+            type_: self.chip_type.as_c(),
+            fwVersion: self.fw_version.as_c(),
+        }
+    }
+}
+
 /**
 This struct stores the complete hierarchy of the bridge chip within the board.
 
@@ -226,6 +231,25 @@ impl TryFrom<nvmlBridgeChipHierarchy_t> for BridgeChipHierarchy {
     }
 }
 
+impl BridgeChipHierarchy {
+    /**
+    Convert this `BridgeChipHierarchy` back into its C counterpart.
+
+    Entries of the C array past the end of `chips_hierarchy` are zeroed.
+
+    # Errors
+
+    * `InvalidArg`, if `chips_hierarchy` has more than `NVML_MAX_PHYSICAL_BRIDGE`
+      entries
+    */
+    pub fn try_into_c(self) -> Result<nvmlBridgeChipHierarchy_t, NvmlError> {
+        Ok(nvmlBridgeChipHierarchy_t {
+            bridgeCount: self.chip_count,
+            bridgeChipInfo: c_array(self.chips_hierarchy.into_iter().map(|c| c.into_c()))?,
+        })
+    }
+}
+
 /// Information about compute processes running on the GPU.
 // Checked against local
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -263,6 +287,20 @@ impl From<nvmlProcessInfo_t> for ProcessInfo {
     }
 }
 
+impl ProcessInfo {
+    /// Convert this `ProcessInfo` back into its C counterpart.
+    pub fn into_c(self) -> nvmlProcessInfo_t {
+        const NO_VALUE: u32 = 0xFFFFFFFF;
+
+        nvmlProcessInfo_t {
+            pid: self.pid,
+            usedGpuMemory: self.used_gpu_memory.as_c(),
+            gpuInstanceId: self.gpu_instance_id.unwrap_or(NO_VALUE),
+            computeInstanceId: self.compute_instance_id.unwrap_or(NO_VALUE),
+        }
+    }
+}
+
 /// Detailed ECC error counts for a device.
 // Checked against local
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -285,6 +323,62 @@ impl From<nvmlEccErrorCounts_t> for EccErrorCounts {
     }
 }
 
+impl EccErrorCounts {
+    /// Convert this `EccErrorCounts` back into its C counterpart.
+    pub fn into_c(self) -> nvmlEccErrorCounts_t {
+        nvmlEccErrorCounts_t {
+            deviceMemory: self.device_memory,
+            l1Cache: self.l1_cache,
+            l2Cache: self.l2_cache,
+            registerFile: self.register_file,
+        }
+    }
+}
+
+/// How many memory banks have each amount of spare rows left for remapping.
+///
+/// Each field counts the banks with that availability; a bank in `none` can't
+/// remap any more rows.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RowRemapperHistogram {
+    /// Banks with all of their spare rows available.
+    pub max: u32,
+    /// Banks with most of their spare rows available.
+    pub high: u32,
+    /// Banks with some of their spare rows available.
+    pub partial: u32,
+    /// Banks with few of their spare rows available.
+    pub low: u32,
+    /// Banks with no spare rows available.
+    pub none: u32,
+}
+
+impl From<nvmlRowRemapperHistogramValues_t> for RowRemapperHistogram {
+    fn from(struct_: nvmlRowRemapperHistogramValues_t) -> Self {
+        Self {
+            max: struct_.max,
+            high: struct_.high,
+            partial: struct_.partial,
+            low: struct_.low,
+            none: struct_.none,
+        }
+    }
+}
+
+impl RowRemapperHistogram {
+    /// Convert this `RowRemapperHistogram` back into its C counterpart.
+    pub fn into_c(self) -> nvmlRowRemapperHistogramValues_t {
+        nvmlRowRemapperHistogramValues_t {
+            max: self.max,
+            high: self.high,
+            partial: self.partial,
+            low: self.low,
+            none: self.none,
+        }
+    }
+}
+
 /// Memory allocation information for a device (in bytes).
 // Checked against local
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -320,6 +414,19 @@ impl From<nvmlMemory_v2_t> for MemoryInfo {
     }
 }
 
+impl MemoryInfo {
+    /// Convert this `MemoryInfo` back into its C counterpart.
+    pub fn into_c(self) -> nvmlMemory_v2_t {
+        nvmlMemory_v2_t {
+            version: self.version,
+            total: self.total,
+            reserved: self.reserved,
+            free: self.free,
+            used: self.used,
+        }
+    }
+}
+
 /// Utilization information for a device. Each sample period may be between 1
 /// second and 1/6 second, depending on the product being queried.
 // Checked against local
@@ -343,6 +450,16 @@ impl From<nvmlUtilization_t> for Utilization {
     }
 }
 
+impl Utilization {
+    /// Convert this `Utilization` back into its C counterpart.
+    pub fn into_c(self) -> nvmlUtilization_t {
+        nvmlUtilization_t {
+            gpu: self.gpu,
+            memory: self.memory,
+        }
+    }
+}
+
 /// Performance policy violation status data.
 // Checked against local
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -363,6 +480,16 @@ impl From<nvmlViolationTime_t> for ViolationTime {
     }
 }
 
+impl ViolationTime {
+    /// Convert this `ViolationTime` back into its C counterpart.
+    pub fn into_c(self) -> nvmlViolationTime_t {
+        nvmlViolationTime_t {
+            referenceTime: self.reference_time,
+            violationTime: self.violation_time,
+        }
+    }
+}
+
 /**
 Accounting statistics for a process.
 
@@ -434,6 +561,24 @@ impl From<nvmlAccountingStats_t> for AccountingStats {
     }
 }
 
+impl AccountingStats {
+    /// Convert this `AccountingStats` back into its C counterpart.
+    pub fn into_c(self) -> nvmlAccountingStats_t {
+        let not_avail_u64 = (NVML_VALUE_NOT_AVAILABLE) as u64;
+        let not_avail_u32 = (NVML_VALUE_NOT_AVAILABLE) as u32;
+
+        nvmlAccountingStats_t {
+            gpuUtilization: self.gpu_utilization.unwrap_or(not_avail_u32),
+            memoryUtilization: self.memory_utilization.unwrap_or(not_avail_u32),
+            maxMemoryUsage: self.max_memory_usage.unwrap_or(not_avail_u64),
+            time: self.time,
+            startTime: self.start_time,
+            isRunning: self.is_running as u32,
+            reserved: [0; 5],
+        }
+    }
+}
+
 /// Holds encoder session information.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -483,6 +628,22 @@ impl TryFrom<nvmlEncoderSessionInfo_t> for EncoderSessionInfo {
     }
 }
 
+impl EncoderSessionInfo {
+    /// Convert this `EncoderSessionInfo` back into its C counterpart.
+    pub fn into_c(self) -> nvmlEncoderSessionInfo_t {
+        nvmlEncoderSessionInfo_t {
+            sessionId: self.session_id,
+            pid: self.pid,
+            vgpuInstance: self.vgpu_instance.unwrap_or(0),
+            codecType: self.codec_type.as_c(),
+            hResolution: self.hres,
+            vResolution: self.vres,
+            averageFps: self.average_fps,
+            averageLatency: self.average_latency,
+        }
+    }
+}
+
 /// Sample info.
 // Checked against local
 #[derive(Debug, Clone, PartialEq)]
@@ -502,6 +663,15 @@ impl Sample {
             value: SampleValue::from_tag_and_union(tag, struct_.sampleValue),
         }
     }
+
+    /// Convert this `Sample` back into its C counterpart. The union's tag is
+    /// `self.value.value_type()`.
+    pub fn into_c(self) -> nvmlSample_t {
+        nvmlSample_t {
+            timeStamp: self.timestamp,
+            sampleValue: self.value.as_c(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -533,6 +703,137 @@ impl From<nvmlProcessUtilizationSample_t> for ProcessUtilizationSample {
     }
 }
 
+impl ProcessUtilizationSample {
+    /// Convert this `ProcessUtilizationSample` back into its C counterpart.
+    pub fn into_c(self) -> nvmlProcessUtilizationSample_t {
+        nvmlProcessUtilizationSample_t {
+            pid: self.pid,
+            timeStamp: self.timestamp,
+            smUtil: self.sm_util,
+            memUtil: self.mem_util,
+            encUtil: self.enc_util,
+            decUtil: self.dec_util,
+        }
+    }
+}
+
+/// A process running on a device, from `Device.running_process_detail_list()`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessDetail {
+    /// Process ID.
+    pub pid: u32,
+    /// Amount of used GPU memory in bytes.
+    pub used_gpu_memory: UsedGpuMemory,
+    /// The ID of the GPU instance this process is running on, if applicable.
+    ///
+    /// MIG (Multi-Instance GPU) must be enabled on the device for this field
+    /// to be set.
+    pub gpu_instance_id: Option<u32>,
+    /// The ID of the compute instance this process is running on, if applicable.
+    ///
+    /// MIG (Multi-Instance GPU) must be enabled on the device for this field
+    /// to be set.
+    pub compute_instance_id: Option<u32>,
+    /// Amount of used confidential compute protected memory in bytes.
+    pub used_gpu_cc_protected_memory: UsedGpuMemory,
+}
+
+impl From<nvmlProcessDetail_v1_t> for ProcessDetail {
+    fn from(struct_: nvmlProcessDetail_v1_t) -> Self {
+        const NO_VALUE: u32 = 0xFFFFFFFF;
+
+        let gpu_instance_id = Some(struct_.gpuInstanceId).filter(|id| *id != NO_VALUE);
+        let compute_instance_id = Some(struct_.computeInstanceId).filter(|id| *id != NO_VALUE);
+
+        Self {
+            pid: struct_.pid,
+            used_gpu_memory: UsedGpuMemory::from(struct_.usedGpuMemory),
+            gpu_instance_id,
+            compute_instance_id,
+            used_gpu_cc_protected_memory: UsedGpuMemory::from(struct_.usedGpuCcProtectedMemory),
+        }
+    }
+}
+
+impl ProcessDetail {
+    /// Convert this `ProcessDetail` back into its C counterpart.
+    pub fn into_c(self) -> nvmlProcessDetail_v1_t {
+        const NO_VALUE: u32 = 0xFFFFFFFF;
+
+        nvmlProcessDetail_v1_t {
+            pid: self.pid,
+            usedGpuMemory: self.used_gpu_memory.as_c(),
+            gpuInstanceId: self.gpu_instance_id.unwrap_or(NO_VALUE),
+            computeInstanceId: self.compute_instance_id.unwrap_or(NO_VALUE),
+            usedGpuCcProtectedMemory: self.used_gpu_cc_protected_memory.as_c(),
+        }
+    }
+}
+
+/// Implements NVML_STRUCT_VERSION(ProcessDetailList, 1), as detailed in nvml.h
+pub(crate) fn process_detail_list_version() -> u32 {
+    (mem::size_of::<nvmlProcessDetailList_v1_t>() | (1_usize << 24_usize)) as u32
+}
+
+/// Utilization sample for a process, including the JPEG and OFA engines.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessUtilizationInfo {
+    /// CPU timestamp in μs
+    pub timestamp: u64,
+    pub pid: u32,
+    /// SM (3D / compute) utilization
+    pub sm_util: u32,
+    /// Frame buffer memory utilization
+    pub mem_util: u32,
+    /// Encoder utilization
+    pub enc_util: u32,
+    /// Decoder utilization
+    pub dec_util: u32,
+    /// JPEG decoder utilization
+    pub jpg_util: u32,
+    /// Optical flow accelerator utilization
+    pub ofa_util: u32,
+}
+
+impl From<nvmlProcessUtilizationInfo_v1_t> for ProcessUtilizationInfo {
+    fn from(struct_: nvmlProcessUtilizationInfo_v1_t) -> Self {
+        Self {
+            timestamp: struct_.timeStamp,
+            pid: struct_.pid,
+            sm_util: struct_.smUtil,
+            mem_util: struct_.memUtil,
+            enc_util: struct_.encUtil,
+            dec_util: struct_.decUtil,
+            jpg_util: struct_.jpgUtil,
+            ofa_util: struct_.ofaUtil,
+        }
+    }
+}
+
+impl ProcessUtilizationInfo {
+    /// Convert this `ProcessUtilizationInfo` back into its C counterpart.
+    pub fn into_c(self) -> nvmlProcessUtilizationInfo_v1_t {
+        nvmlProcessUtilizationInfo_v1_t {
+            timeStamp: self.timestamp,
+            pid: self.pid,
+            smUtil: self.sm_util,
+            memUtil: self.mem_util,
+            encUtil: self.enc_util,
+            decUtil: self.dec_util,
+            jpgUtil: self.jpg_util,
+            ofaUtil: self.ofa_util,
+        }
+    }
+}
+
+/// Implements NVML_STRUCT_VERSION(ProcessesUtilizationInfo, 1), as detailed in
+/// nvml.h
+pub(crate) fn processes_utilization_info_version() -> u32 {
+    (mem::size_of::<nvmlProcessesUtilizationInfo_v1_t>() | (1_usize << 24_usize)) as u32
+}
+
 /// Struct that stores information returned from `Device.field_values_for()`.
 // TODO: Missing a lot of derives because of the `Result`
 #[derive(Debug)]
@@ -580,6 +881,35 @@ impl TryFrom<nvmlFieldValue_t> for FieldValueSample {
     }
 }
 
+impl FieldValueSample {
+    /**
+    Convert this `FieldValueSample` back into its C counterpart.
+
+    An error `value` becomes the struct's return code (see
+    `impl From<NvmlError> for nvmlReturn_t`), with a zeroed value.
+    */
+    pub fn into_c(self) -> nvmlFieldValue_t {
+        let (value_type, value, code) = match self.value {
+            Ok(value) => (
+                value.value_type().as_c(),
+                value.as_c(),
+                nvmlReturn_enum_NVML_SUCCESS,
+            ),
+            Err(e) => (0, nvmlValue_t { ullVal: 0 }, e.into()),
+        };
+
+        nvmlFieldValue_t {
+            fieldId: self.field.0,
+            scopeId: 0,
+            timestamp: self.timestamp,
+            latencyUsec: self.latency,
+            valueType: value_type,
+            nvmlReturn: code,
+            value,
+        }
+    }
+}
+
 /// Holds global frame buffer capture session statistics.
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -602,6 +932,17 @@ impl From<nvmlFBCStats_t> for FbcStats {
     }
 }
 
+impl FbcStats {
+    /// Convert this `FbcStats` back into its C counterpart.
+    pub fn into_c(self) -> nvmlFBCStats_t {
+        nvmlFBCStats_t {
+            sessionsCount: self.sessions_count,
+            averageFPS: self.average_fps,
+            averageLatency: self.average_latency,
+        }
+    }
+}
+
 /// Information about a frame buffer capture session.
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -667,6 +1008,26 @@ impl TryFrom<nvmlFBCSessionInfo_t> for FbcSessionInfo {
     }
 }
 
+impl FbcSessionInfo {
+    /// Convert this `FbcSessionInfo` back into its C counterpart.
+    pub fn into_c(self) -> nvmlFBCSessionInfo_t {
+        nvmlFBCSessionInfo_t {
+            sessionId: self.session_id,
+            pid: self.pid,
+            vgpuInstance: self.vgpu_instance.unwrap_or(0),
+            displayOrdinal: self.display_ordinal,
+            sessionType: self.session_type.as_c(),
+            sessionFlags: self.session_flags.bits(),
+            hMaxResolution: self.hres_max,
+            vMaxResolution: self.vres_max,
+            hResolution: self.hres,
+            vResolution: self.vres,
+            averageFPS: self.average_fps,
+            averageLatency: self.average_latency,
+        }
+    }
+}
+
 /// Hardware level attributes from a GPU device
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -707,6 +1068,23 @@ impl From<nvmlDeviceAttributes_t> for DeviceAttributes {
     }
 }
 
+impl DeviceAttributes {
+    /// Convert this `DeviceAttributes` back into its C counterpart.
+    pub fn into_c(self) -> nvmlDeviceAttributes_t {
+        nvmlDeviceAttributes_t {
+            multiprocessorCount: self.multiprocessor_count,
+            sharedCopyEngineCount: self.shared_copy_engine_count,
+            sharedDecoderCount: self.shared_decoder_count,
+            sharedEncoderCount: self.shared_encoder_count,
+            sharedJpegCount: self.shared_jpeg_count,
+            sharedOfaCount: self.shared_ofa_count,
+            gpuInstanceSliceCount: self.gpu_instance_slice_count,
+            computeInstanceSliceCount: self.compute_instance_slice_count,
+            memorySizeMB: self.memory_size_mb,
+        }
+    }
+}
+
 /// Fan speed info
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -729,6 +1107,17 @@ impl From<nvmlFanSpeedInfo_t> for FanSpeedInfo {
     }
 }
 
+impl FanSpeedInfo {
+    /// Convert this `FanSpeedInfo` back into its C counterpart.
+    pub fn into_c(self) -> nvmlFanSpeedInfo_t {
+        nvmlFanSpeedInfo_t {
+            version: self.version,
+            fan: self.fan,
+            speed: self.speed,
+        }
+    }
+}
+
 /// Clock offset info.
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -757,6 +1146,20 @@ impl TryFrom<nvmlClockOffset_v1_t> for ClockOffset {
     }
 }
 
+impl ClockOffset {
+    /// Convert this `ClockOffset` back into its C counterpart.
+    pub fn into_c(self) -> nvmlClockOffset_v1_t {
+        nvmlClockOffset_v1_t {
+            version: self.version,
+            type_: self.clock_type.as_c(),
+            pstate: self.state.as_c(),
+            clockOffsetMHz: self.clock_offset_mhz,
+            minClockOffsetMHz: self.min_clock_offset_mhz,
+            maxClockOffsetMHz: self.max_clock_offset_mhz,
+        }
+    }
+}
+
 /// Profile info.
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -792,6 +1195,111 @@ impl From<nvmlGpuInstanceProfileInfo_t> for ProfileInfo {
     }
 }
 
+impl ProfileInfo {
+    /// Convert this `ProfileInfo` back into its C counterpart.
+    pub fn into_c(self) -> nvmlGpuInstanceProfileInfo_t {
+        nvmlGpuInstanceProfileInfo_t {
+            id: self.id,
+            isP2pSupported: self.is_p2p_supported as u32,
+            sliceCount: self.slice_count,
+            instanceCount: self.instance_count,
+            multiprocessorCount: self.multiprocessor_count,
+            copyEngineCount: self.copy_engine_count,
+            decoderCount: self.decoder_count,
+            encoderCount: self.encoder_count,
+            jpegCount: self.jpeg_count,
+            ofaCount: self.ofa_count,
+            memorySizeMB: self.memory_size_mb,
+        }
+    }
+}
+
+/// MIG GPU instance profile info, including the profile's name.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GpuInstanceProfileInfo {
+    /// The profile ID, to create GPU instances with.
+    pub id: u32,
+    /// The profile's name, e.g. `MIG 3g.40gb`.
+    pub name: String,
+    pub is_p2p_supported: bool,
+    /// Compute slices taken up by each GPU instance.
+    pub slice_count: u32,
+    /// The maximum number of GPU instances of this profile.
+    pub instance_count: u32,
+    pub multiprocessor_count: u32,
+    pub copy_engine_count: u32,
+    pub decoder_count: u32,
+    pub encoder_count: u32,
+    pub jpeg_count: u32,
+    pub ofa_count: u32,
+    pub memory_size_mb: u64,
+}
+
+impl TryFrom<nvmlGpuInstanceProfileInfo_v2_t> for GpuInstanceProfileInfo {
+    type Error = NvmlError;
+
+    /**
+    Construct `GpuInstanceProfileInfo` from the corresponding C struct.
+
+    # Errors
+
+    * `Utf8Error`, if the name isn't valid UTF-8
+    */
+    fn try_from(value: nvmlGpuInstanceProfileInfo_v2_t) -> Result<Self, Self::Error> {
+        let name = unsafe { CStr::from_ptr(value.name.as_ptr()) };
+
+        Ok(Self {
+            id: value.id,
+            name: name.to_str()?.into(),
+            is_p2p_supported: value.isP2pSupported > 0,
+            slice_count: value.sliceCount,
+            instance_count: value.instanceCount,
+            multiprocessor_count: value.multiprocessorCount,
+            copy_engine_count: value.copyEngineCount,
+            decoder_count: value.decoderCount,
+            encoder_count: value.encoderCount,
+            jpeg_count: value.jpegCount,
+            ofa_count: value.ofaCount,
+            memory_size_mb: value.memorySizeMB,
+        })
+    }
+}
+
+impl GpuInstanceProfileInfo {
+    /**
+    Convert this `GpuInstanceProfileInfo` back into its C counterpart.
+
+    # Errors
+
+    * `NulError`, if a nul byte was found in the name
+    * `StringTooLong`, if the name is too long for its C buffer
+    */
+    pub fn try_into_c(self) -> Result<nvmlGpuInstanceProfileInfo_v2_t, NvmlError> {
+        Ok(nvmlGpuInstanceProfileInfo_v2_t {
+            version: gpu_instance_profile_info_version(),
+            id: self.id,
+            isP2pSupported: self.is_p2p_supported as u32,
+            sliceCount: self.slice_count,
+            instanceCount: self.instance_count,
+            multiprocessorCount: self.multiprocessor_count,
+            copyEngineCount: self.copy_engine_count,
+            decoderCount: self.decoder_count,
+            encoderCount: self.encoder_count,
+            jpegCount: self.jpeg_count,
+            ofaCount: self.ofa_count,
+            memorySizeMB: self.memory_size_mb,
+            name: c_string(self.name)?,
+        })
+    }
+}
+
+/// Implements NVML_STRUCT_VERSION(GpuInstanceProfileInfo, 2), as detailed in
+/// nvml.h
+pub(crate) fn gpu_instance_profile_info_version() -> u32 {
+    (mem::size_of::<nvmlGpuInstanceProfileInfo_v2_t>() | (2_usize << 24_usize)) as u32
+}
+
 /// MIG profile placements
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -811,15 +1319,178 @@ impl From<nvmlGpuInstancePlacement_t> for GpuInstancePlacement {
     }
 }
 
-// Vgpu
-/// Vgpu scheduler capabilities
+impl GpuInstancePlacement {
+    /// Convert this `GpuInstancePlacement` back into its C counterpart.
+    pub fn into_c(self) -> nvmlGpuInstancePlacement_t {
+        nvmlGpuInstancePlacement_t {
+            start: self.start,
+            size: self.size,
+        }
+    }
+}
+
+/// Information about a MIG GPU instance.
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct VgpuSchedulerCapabilities {
-    // Adaptative Round Robin mode on/off
-    pub is_arr_mode_supported: bool,
-    // Maximum averaging factor for Adaptative Round Robin mode
-    pub max_avg_factor_for_arr: u32,
+pub struct GpuInstanceInfo {
+    /// The GPU instance's ID, unique within its device.
+    pub id: u32,
+    /// The ID of the profile the GPU instance was created with.
+    pub profile_id: u32,
+    /// The memory slices the GPU instance occupies.
+    pub placement: GpuInstancePlacement,
+}
+
+impl From<nvmlGpuInstanceInfo_t> for GpuInstanceInfo {
+    fn from(value: nvmlGpuInstanceInfo_t) -> Self {
+        Self {
+            id: value.id,
+            profile_id: value.profileId,
+            placement: value.placement.into(),
+        }
+    }
+}
+
+/// MIG compute instance profile info.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ComputeInstanceProfileInfo {
+    /// The profile ID, to create compute instances with.
+    pub id: u32,
+    /// The profile's name, e.g. `1c.3g.40gb`.
+    pub name: String,
+    /// GPU instance slices taken up by each compute instance.
+    pub slice_count: u32,
+    /// The maximum number of compute instances of this profile.
+    pub instance_count: u32,
+    pub multiprocessor_count: u32,
+    pub shared_copy_engine_count: u32,
+    pub shared_decoder_count: u32,
+    pub shared_encoder_count: u32,
+    pub shared_jpeg_count: u32,
+    pub shared_ofa_count: u32,
+}
+
+impl TryFrom<nvmlComputeInstanceProfileInfo_v2_t> for ComputeInstanceProfileInfo {
+    type Error = NvmlError;
+
+    /**
+    Construct `ComputeInstanceProfileInfo` from the corresponding C struct.
+
+    # Errors
+
+    * `Utf8Error`, if the name isn't valid UTF-8
+    */
+    fn try_from(value: nvmlComputeInstanceProfileInfo_v2_t) -> Result<Self, Self::Error> {
+        let name = unsafe { CStr::from_ptr(value.name.as_ptr()) };
+
+        Ok(Self {
+            id: value.id,
+            name: name.to_str()?.into(),
+            slice_count: value.sliceCount,
+            instance_count: value.instanceCount,
+            multiprocessor_count: value.multiprocessorCount,
+            shared_copy_engine_count: value.sharedCopyEngineCount,
+            shared_decoder_count: value.sharedDecoderCount,
+            shared_encoder_count: value.sharedEncoderCount,
+            shared_jpeg_count: value.sharedJpegCount,
+            shared_ofa_count: value.sharedOfaCount,
+        })
+    }
+}
+
+impl ComputeInstanceProfileInfo {
+    /**
+    Convert this `ComputeInstanceProfileInfo` back into its C counterpart.
+
+    # Errors
+
+    * `NulError`, if a nul byte was found in the name
+    * `StringTooLong`, if the name is too long for its C buffer
+    */
+    pub fn try_into_c(self) -> Result<nvmlComputeInstanceProfileInfo_v2_t, NvmlError> {
+        Ok(nvmlComputeInstanceProfileInfo_v2_t {
+            version: compute_instance_profile_info_version(),
+            id: self.id,
+            sliceCount: self.slice_count,
+            instanceCount: self.instance_count,
+            multiprocessorCount: self.multiprocessor_count,
+            sharedCopyEngineCount: self.shared_copy_engine_count,
+            sharedDecoderCount: self.shared_decoder_count,
+            sharedEncoderCount: self.shared_encoder_count,
+            sharedJpegCount: self.shared_jpeg_count,
+            sharedOfaCount: self.shared_ofa_count,
+            name: c_string(self.name)?,
+        })
+    }
+}
+
+/// Implements NVML_STRUCT_VERSION(ComputeInstanceProfileInfo, 2), as detailed
+/// in nvml.h
+pub(crate) fn compute_instance_profile_info_version() -> u32 {
+    (mem::size_of::<nvmlComputeInstanceProfileInfo_v2_t>() | (2_usize << 24_usize)) as u32
+}
+
+/// MIG compute instance placements, in slices of the GPU instance.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ComputeInstancePlacement {
+    /// Slices occupied
+    pub size: u32,
+    /// Index of first occupied slice (inclusive)
+    pub start: u32,
+}
+
+impl From<nvmlComputeInstancePlacement_t> for ComputeInstancePlacement {
+    fn from(value: nvmlComputeInstancePlacement_t) -> Self {
+        Self {
+            size: value.size,
+            start: value.start,
+        }
+    }
+}
+
+impl ComputeInstancePlacement {
+    /// Convert this `ComputeInstancePlacement` back into its C counterpart.
+    pub fn into_c(self) -> nvmlComputeInstancePlacement_t {
+        nvmlComputeInstancePlacement_t {
+            start: self.start,
+            size: self.size,
+        }
+    }
+}
+
+/// Information about a MIG compute instance.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ComputeInstanceInfo {
+    /// The compute instance's ID, unique within its GPU instance.
+    pub id: u32,
+    /// The ID of the profile the compute instance was created with.
+    pub profile_id: u32,
+    /// The GPU instance slices the compute instance occupies.
+    pub placement: ComputeInstancePlacement,
+}
+
+impl From<nvmlComputeInstanceInfo_t> for ComputeInstanceInfo {
+    fn from(value: nvmlComputeInstanceInfo_t) -> Self {
+        Self {
+            id: value.id,
+            profile_id: value.profileId,
+            placement: value.placement.into(),
+        }
+    }
+}
+
+// Vgpu
+/// Vgpu scheduler capabilities
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuSchedulerCapabilities {
+    // Adaptative Round Robin mode on/off
+    pub is_arr_mode_supported: bool,
+    // Maximum averaging factor for Adaptative Round Robin mode
+    pub max_avg_factor_for_arr: u32,
     // Maximum frequency for Adaptative Round Robin mode
     pub max_freq_for_arr: u32,
     // Maximum timeslice value in ns
@@ -830,14 +1501,31 @@ pub struct VgpuSchedulerCapabilities {
     pub min_freq_for_arr: u32,
     // Minimum timeslice value in ns
     pub min_time_slice: u32,
-    // List of supported scheduler
-    pub supported_schedulers: Vec<u32>,
+    // List of supported scheduler policies
+    pub supported_schedulers: Vec<VgpuSchedulerPolicy>,
 }
 
-impl From<nvmlVgpuSchedulerCapabilities_t> for VgpuSchedulerCapabilities {
-    fn from(value: nvmlVgpuSchedulerCapabilities_t) -> Self {
-        let supported_schedulers = value.supportedSchedulers.to_vec();
-        Self {
+impl TryFrom<nvmlVgpuSchedulerCapabilities_t> for VgpuSchedulerCapabilities {
+    type Error = NvmlError;
+
+    /**
+    Construct `VgpuSchedulerCapabilities` from the corresponding C struct.
+
+    Unused entries of the C array of supported policies are skipped.
+
+    # Errors
+
+    * `UnexpectedVariant`, for which you can read the docs for
+    */
+    fn try_from(value: nvmlVgpuSchedulerCapabilities_t) -> Result<Self, Self::Error> {
+        let supported_schedulers = value
+            .supportedSchedulers
+            .iter()
+            .filter(|p| **p != NVML_VGPU_SCHEDULER_POLICY_UNKNOWN)
+            .map(|p| VgpuSchedulerPolicy::try_from(*p))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
             is_arr_mode_supported: value.isArrModeSupported > 0,
             max_avg_factor_for_arr: value.maxAvgFactorForARR,
             max_freq_for_arr: value.maxFrequencyForARR,
@@ -846,10 +1534,174 @@ impl From<nvmlVgpuSchedulerCapabilities_t> for VgpuSchedulerCapabilities {
             min_freq_for_arr: value.minFrequencyForARR,
             min_time_slice: value.minTimeslice,
             supported_schedulers,
+        })
+    }
+}
+
+impl VgpuSchedulerCapabilities {
+    /**
+    Convert this `VgpuSchedulerCapabilities` back into its C counterpart.
+
+    # Errors
+
+    * `InvalidArg`, if `supported_schedulers` has more than
+      `NVML_SUPPORTED_VGPU_SCHEDULER_POLICY_COUNT` entries
+    */
+    pub fn try_into_c(self) -> Result<nvmlVgpuSchedulerCapabilities_t, NvmlError> {
+        Ok(nvmlVgpuSchedulerCapabilities_t {
+            supportedSchedulers: c_array(self.supported_schedulers.iter().map(|p| p.as_c()))?,
+            maxTimeslice: self.max_time_slice,
+            minTimeslice: self.min_time_slice,
+            isArrModeSupported: self.is_arr_mode_supported as u32,
+            maxFrequencyForARR: self.max_freq_for_arr,
+            minFrequencyForARR: self.min_freq_for_arr,
+            maxAvgFactorForARR: self.max_avg_factor_for_arr,
+            minAvgFactorForARR: self.min_avg_factor_for_arr,
+        })
+    }
+
+    /**
+    Checks that `state` is supported: that it passes
+    `VgpuSchedulerSetState.validate()`, that its policy is one of
+    `supported_schedulers`, and that its parameters are within the limits for
+    its ARR mode.
+
+    # Errors
+
+    * `UnsupportedVgpuSchedulerState`, describing the first unsupported setting
+    */
+    pub fn validate(&self, state: &VgpuSchedulerSetState) -> Result<(), NvmlError> {
+        fn check(name: &str, value: u32, min: u32, max: u32) -> Result<(), NvmlError> {
+            if (min..=max).contains(&value) {
+                Ok(())
+            } else {
+                Err(NvmlError::UnsupportedVgpuSchedulerState(format!(
+                    "{} {} is outside of {}..={}",
+                    name, value, min, max
+                )))
+            }
+        }
+
+        state.validate()?;
+
+        if !self.supported_schedulers.contains(&state.policy) {
+            return Err(NvmlError::UnsupportedVgpuSchedulerState(format!(
+                "policy {:?} is not one of {:?}",
+                state.policy, self.supported_schedulers
+            )));
+        }
+
+        match state.params {
+            VgpuSchedulerSetParams::Timeslice(timeslice) => check(
+                "timeslice (ns)",
+                timeslice,
+                self.min_time_slice,
+                self.max_time_slice,
+            ),
+            VgpuSchedulerSetParams::Arr { .. } if !self.is_arr_mode_supported => Err(
+                NvmlError::UnsupportedVgpuSchedulerState("ARR mode is not supported".into()),
+            ),
+            VgpuSchedulerSetParams::Arr {
+                avg_factor,
+                frequency,
+            } => {
+                check(
+                    "ARR averaging factor",
+                    avg_factor,
+                    self.min_avg_factor_for_arr,
+                    self.max_avg_factor_for_arr,
+                )?;
+                check(
+                    "ARR frequency",
+                    frequency,
+                    self.min_freq_for_arr,
+                    self.max_freq_for_arr,
+                )
+            }
+        }
+    }
+}
+
+/// When a vGPU instance's license expires.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuLicenseExpiry {
+    pub year: u32,
+    pub month: u16,
+    pub day: u16,
+    pub hour: u16,
+    pub min: u16,
+    pub sec: u16,
+    /// One of the `NVML_GRID_LICENSE_EXPIRY_*` constants; the date is only
+    /// meaningful if this is `NVML_GRID_LICENSE_EXPIRY_VALID`.
+    pub status: u8,
+}
+
+impl From<nvmlVgpuLicenseExpiry_t> for VgpuLicenseExpiry {
+    fn from(value: nvmlVgpuLicenseExpiry_t) -> Self {
+        Self {
+            year: value.year,
+            month: value.month,
+            day: value.day,
+            hour: value.hour,
+            min: value.min,
+            sec: value.sec,
+            status: value.status,
+        }
+    }
+}
+
+impl VgpuLicenseExpiry {
+    /// Convert this `VgpuLicenseExpiry` back into its C counterpart.
+    pub fn into_c(self) -> nvmlVgpuLicenseExpiry_t {
+        nvmlVgpuLicenseExpiry_t {
+            year: self.year,
+            month: self.month,
+            day: self.day,
+            hour: self.hour,
+            min: self.min,
+            sec: self.sec,
+            status: self.status,
+        }
+    }
+}
+
+/// Returned from `VgpuInstance.license_info()`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuLicenseInfo {
+    pub is_licensed: bool,
+    pub expiry: VgpuLicenseExpiry,
+    /// One of the `NVML_GRID_LICENSE_STATE_*` constants.
+    pub current_state: u32,
+}
+
+impl From<nvmlVgpuLicenseInfo_t> for VgpuLicenseInfo {
+    fn from(value: nvmlVgpuLicenseInfo_t) -> Self {
+        Self {
+            is_licensed: value.isLicensed > 0,
+            expiry: value.licenseExpiry.into(),
+            current_state: value.currentState,
+        }
+    }
+}
+
+impl VgpuLicenseInfo {
+    /// Convert this `VgpuLicenseInfo` back into its C counterpart.
+    pub fn into_c(self) -> nvmlVgpuLicenseInfo_t {
+        nvmlVgpuLicenseInfo_t {
+            isLicensed: self.is_licensed as u8,
+            licenseExpiry: self.expiry.into_c(),
+            currentState: self.current_state,
         }
     }
 }
 
+/// Implements NVML_STRUCT_VERSION(VgpuPlacementId, 1), as detailed in nvml.h
+pub(crate) fn vgpu_placement_id_version() -> u32 {
+    (mem::size_of::<nvmlVgpuPlacementId_t>() | (1_usize << 24_usize)) as u32
+}
+
 /// Vgpu versions range
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -878,170 +1730,2024 @@ impl VgpuVersion {
     }
 }
 
-/// Vgpu scheduler Params
-#[derive(Debug, Clone, Eq, PartialEq)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct VgpuSchedulerParams {
-    pub avg_factor: Option<u32>,
-    pub timeslice: u32,
+/**
+Allocate a zeroed buffer for one of NVML's variable-length metadata structs
+(`T`), big enough for `size` bytes and at least for `T` itself.
+
+These structs end in `opaqueDataSize` bytes of opaque data, of which the
+declared 4-byte `opaqueData` array is only the start. They only hold `u32`s
+and `c_char`s, so a `u32` buffer is aligned for them.
+*/
+pub(crate) fn metadata_buffer<T>(size: usize) -> Vec<u32> {
+    let size = size.max(mem::size_of::<T>());
+    vec![0; (size + 3) / 4]
 }
 
-/// Vgpu scheduler Log entry
-#[derive(Debug, Clone, Eq, PartialEq)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct VgpuSchedulerLogEntry {
-    /// Timesteamp when the software runlist was preemopted (in ns)
-    pub timestamp: u64,
-    /// Total time this runlist has run (in ns)
-    pub time_run_total: u64,
-    /// Time this runlist ran before preemption (in ns)
-    pub time_run: u64,
-    /// Runlist Id
-    pub sw_runlist_id: u32,
-    /// timeslice after deduction
-    pub target_time_slice: u64,
-    /// Preemption time for this runlist (in ns)
-    pub cumulative_preemption_time: u64,
+/// The offset of the opaque data in the metadata struct `T`: its last field,
+/// the 4-byte `opaqueData` array.
+fn opaque_data_offset<T>() -> usize {
+    mem::size_of::<T>() - 4
 }
 
-impl From<nvmlVgpuSchedulerLogEntry_t> for VgpuSchedulerLogEntry {
-    fn from(value: nvmlVgpuSchedulerLogEntry_t) -> Self {
-        Self {
-            timestamp: value.timestamp,
-            time_run_total: value.timeRunTotal,
-            time_run: value.timeRun,
-            sw_runlist_id: value.swRunlistId,
-            target_time_slice: value.targetTimeSlice,
-            cumulative_preemption_time: value.cumulativePreemptionTime,
-        }
+/**
+Read a metadata struct `T` from `buffer`, along with the `opaque_size` bytes
+of opaque data following its header (or as many as the buffer holds).
+
+# Errors
+
+* `InvalidArg`, if `buffer` is too short to hold a `T`
+*/
+fn read_metadata<T: Copy>(
+    buffer: &[u32],
+    opaque_size: impl Fn(&T) -> u32,
+) -> Result<(T, Vec<u8>), NvmlError> {
+    let bytes = mem::size_of_val(buffer);
+    if bytes < mem::size_of::<T>() {
+        return Err(NvmlError::InvalidArg);
     }
-}
 
-/// Vgpu scheduler Log
-#[derive(Debug, Clone, Eq, PartialEq)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct VgpuSchedulerLog {
-    /// Engine id whose software runlist are fetched
-    pub engine_id: u32,
-    /// Scheduler policy
-    pub scheduler_policy: u32,
-    /// Scheduler Round Robin Mode
-    pub arr_mode: u32,
-    pub scheduler_params: VgpuSchedulerParams,
-    /// Number of log entries fetched during the call
-    pub entries_count: u32,
-    /// Log entries
-    pub entries: Vec<VgpuSchedulerLogEntry>,
+    // SAFETY: the buffer is long enough and aligned for `T`, a C struct that's
+    // valid for any bit pattern
+    unsafe {
+        let raw = ptr::read(buffer.as_ptr() as *const T);
+        let bytes = slice::from_raw_parts(buffer.as_ptr() as *const u8, bytes);
+        let offset = opaque_data_offset::<T>();
+        let len = (opaque_size(&raw) as usize).min(bytes.len() - offset);
+
+        Ok((raw, bytes[offset..offset + len].to_vec()))
+    }
 }
 
-impl From<nvmlVgpuSchedulerLog_t> for VgpuSchedulerLog {
-    fn from(value: nvmlVgpuSchedulerLog_t) -> Self {
-        let entries = value
-            .logEntries
-            .iter()
-            .map(|e| VgpuSchedulerLogEntry::from(*e))
-            .collect::<Vec<_>>();
-        let params = match value.arrMode {
-            2 => {
-                let data = unsafe { value.schedulerParams.vgpuSchedDataWithARR };
-                VgpuSchedulerParams {
-                    avg_factor: Some(data.avgFactor),
-                    timeslice: data.timeslice,
-                }
-            }
-            _ => {
-                let data = unsafe { value.schedulerParams.vgpuSchedData };
-                VgpuSchedulerParams {
-                    avg_factor: None,
-                    timeslice: data.timeslice,
-                }
-            }
-        };
+/// Write the metadata struct `raw` to a new buffer, followed by `opaque_data`.
+fn write_metadata<T>(raw: T, opaque_data: &[u8]) -> Vec<u32> {
+    let offset = opaque_data_offset::<T>();
+    let mut buffer = metadata_buffer::<T>(offset + opaque_data.len());
 
-        Self {
-            engine_id: value.engineId,
-            scheduler_policy: value.schedulerPolicy,
-            arr_mode: value.arrMode,
-            scheduler_params: params,
-            entries_count: entries.len() as u32,
-            entries,
-        }
+    // SAFETY: the buffer is long enough and aligned for `T` and the data
+    unsafe {
+        ptr::write(buffer.as_mut_ptr() as *mut T, raw);
+
+        let len = mem::size_of_val(buffer.as_slice());
+        let bytes = slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, len);
+        bytes[offset..offset + opaque_data.len()].copy_from_slice(opaque_data);
     }
+
+    buffer
 }
 
-/// Vgpu scheduler state
-#[derive(Debug, Clone, Eq, PartialEq)]
+/**
+Metadata about a vGPU instance, used to check whether it can be migrated to a
+physical GPU (see `Nvml.vgpu_compatibility()`).
+
+Returned from `VgpuInstance.metadata()`. It's meant to be sent to the host a
+VM is being migrated to and checked there; with the `serde` feature enabled it
+can be (de)serialized for that.
+*/
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct VgpuSchedulerGetState {
-    /// Adaptative Round Robin scheduler mode
-    pub arr_mode: u32,
-    /// Scheduler policy
-    pub scheduler_policy: u32,
+pub struct VgpuMetadata {
+    /// The version of the metadata format.
+    pub version: u32,
+    /// The revision of the metadata format.
+    pub revision: u32,
+    /// Whether the guest fields below have been reported.
+    pub guest_info_state: VgpuGuestInfoState,
+    /// The version of the driver in the VM.
+    pub guest_driver_version: String,
+    /// The version of the driver on the host.
+    pub host_driver_version: String,
+    pub virtualization_caps: VgpuVirtualizationCaps,
+    /// The vGPU version of the VM.
+    pub guest_vgpu_version: u32,
+    /// Data only NVML knows how to interpret.
+    pub opaque_data: Vec<u8>,
 }
 
-impl From<nvmlVgpuSchedulerGetState_t> for VgpuSchedulerGetState {
-    fn from(value: nvmlVgpuSchedulerGetState_t) -> Self {
-        Self {
-            arr_mode: value.arrMode,
-            scheduler_policy: value.schedulerPolicy,
+impl VgpuMetadata {
+    /**
+    Read the metadata NVML wrote to `buffer`.
+
+    # Errors
+
+    * `InvalidArg`, if `buffer` is too short to hold the metadata
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Utf8Error`, if a string obtained from the C function is not valid Utf8
+    */
+    pub(crate) fn from_c_buffer(buffer: &[u32]) -> Result<Self, NvmlError> {
+        let (raw, opaque_data) =
+            read_metadata(buffer, |raw: &nvmlVgpuMetadata_t| raw.opaqueDataSize)?;
+
+        unsafe {
+            Ok(Self {
+                version: raw.version,
+                revision: raw.revision,
+                guest_info_state: VgpuGuestInfoState::try_from(raw.guestInfoState)?,
+                guest_driver_version: CStr::from_ptr(raw.guestDriverVersion.as_ptr())
+                    .to_str()?
+                    .into(),
+                host_driver_version: CStr::from_ptr(raw.hostDriverVersion.as_ptr())
+                    .to_str()?
+                    .into(),
+                // Kept as is, so the metadata reaches NVML unchanged
+                virtualization_caps: VgpuVirtualizationCaps::from_bits_retain(
+                    raw.vgpuVirtualizationCaps,
+                ),
+                guest_vgpu_version: raw.guestVgpuVersion,
+                opaque_data,
+            })
         }
     }
-}
 
-#[derive(Debug, Clone, Eq, PartialEq)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct VgpuSchedulerSetParams {
-    /// Average factor in compensating the timeslice for Adaptive Round Robin mode
-    pub avg_factor: Option<u32>,
-    /// Frequency for Adaptative Mode (when avg_factor is set)/timeslice in ns for each software run list as configured, or the default value otherwise
-    pub frequency_or_timeslice: u32,
-}
+    /**
+    Convert this `VgpuMetadata` back into its C counterpart, followed by its
+    opaque data, in a buffer NVML can read it from.
 
-impl VgpuSchedulerSetParams {
-    pub fn as_c(&self) -> nvmlVgpuSchedulerSetParams_t {
-        match self.avg_factor {
-            Some(a) => nvmlVgpuSchedulerSetParams_t {
-                vgpuSchedDataWithARR: nvmlVgpuSchedulerSetParams_t__bindgen_ty_1 {
-                    avgFactor: a,
-                    frequency: self.frequency_or_timeslice,
-                },
-            },
-            _ => nvmlVgpuSchedulerSetParams_t {
-                vgpuSchedData: nvmlVgpuSchedulerSetParams_t__bindgen_ty_2 {
-                    timeslice: self.frequency_or_timeslice,
-                },
-            },
-        }
+    # Errors
+
+    * `NulError`, if a nul byte was found in a string
+    * `StringTooLong`, if a string is too long for its C buffer
+    * `InvalidArg`, if there's more opaque data than NVML can describe
+    */
+    pub(crate) fn try_into_c_buffer(&self) -> Result<Vec<u32>, NvmlError> {
+        let raw = nvmlVgpuMetadata_t {
+            version: self.version,
+            revision: self.revision,
+            guestInfoState: self.guest_info_state.as_c(),
+            guestDriverVersion: c_string(self.guest_driver_version.clone())?,
+            hostDriverVersion: c_string(self.host_driver_version.clone())?,
+            reserved: [0; 6],
+            vgpuVirtualizationCaps: self.virtualization_caps.bits(),
+            guestVgpuVersion: self.guest_vgpu_version,
+            opaqueDataSize: self
+                .opaque_data
+                .len()
+                .try_into()
+                .map_err(|_| NvmlError::InvalidArg)?,
+            opaqueData: [0; 4],
+        };
+
+        Ok(write_metadata(raw, &self.opaque_data))
     }
 }
 
+/**
+Metadata about a physical GPU, used to check whether a vGPU can be migrated to
+it (see `Nvml.vgpu_compatibility()`).
+
+Returned from `Device.vgpu_metadata()`. With the `serde` feature enabled it
+can be (de)serialized.
+*/
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct VgpuSchedulerSetState {
-    pub scheduler_policy: u32,
-    pub enable_arr_mode: u32,
-    pub scheduler_params: VgpuSchedulerSetParams,
+pub struct VgpuPgpuMetadata {
+    /// The version of the metadata format.
+    pub version: u32,
+    /// The revision of the metadata format.
+    pub revision: u32,
+    /// The version of the driver on the host.
+    pub host_driver_version: String,
+    pub virtualization_caps: VgpuVirtualizationCaps,
+    /// The range of guest vGPU versions the host driver supports.
+    pub host_supported_vgpu_range: VgpuVersion,
+    /// Data only NVML knows how to interpret.
+    pub opaque_data: Vec<u8>,
 }
 
-impl VgpuSchedulerSetState {
-    pub fn as_c(&self) -> nvmlVgpuSchedulerSetState_t {
+impl VgpuPgpuMetadata {
+    /**
+    Read the metadata NVML wrote to `buffer`.
+
+    # Errors
+
+    * `InvalidArg`, if `buffer` is too short to hold the metadata
+    * `Utf8Error`, if a string obtained from the C function is not valid Utf8
+    */
+    pub(crate) fn from_c_buffer(buffer: &[u32]) -> Result<Self, NvmlError> {
+        let (raw, opaque_data) =
+            read_metadata(buffer, |raw: &nvmlVgpuPgpuMetadata_t| raw.opaqueDataSize)?;
+
+        unsafe {
+            Ok(Self {
+                version: raw.version,
+                revision: raw.revision,
+                host_driver_version: CStr::from_ptr(raw.hostDriverVersion.as_ptr())
+                    .to_str()?
+                    .into(),
+                // Kept as is, so the metadata reaches NVML unchanged
+                virtualization_caps: VgpuVirtualizationCaps::from_bits_retain(
+                    raw.pgpuVirtualizationCaps,
+                ),
+                host_supported_vgpu_range: raw.hostSupportedVgpuRange.into(),
+                opaque_data,
+            })
+        }
+    }
+
+    /**
+    Convert this `VgpuPgpuMetadata` back into its C counterpart, followed by
+    its opaque data, in a buffer NVML can read it from.
+
+    # Errors
+
+    * `NulError`, if a nul byte was found in a string
+    * `StringTooLong`, if a string is too long for its C buffer
+    * `InvalidArg`, if there's more opaque data than NVML can describe
+    */
+    pub(crate) fn try_into_c_buffer(&self) -> Result<Vec<u32>, NvmlError> {
+        let raw = nvmlVgpuPgpuMetadata_t {
+            version: self.version,
+            revision: self.revision,
+            hostDriverVersion: c_string(self.host_driver_version.clone())?,
+            pgpuVirtualizationCaps: self.virtualization_caps.bits(),
+            reserved: [0; 5],
+            hostSupportedVgpuRange: self.host_supported_vgpu_range.as_c(),
+            opaqueDataSize: self
+                .opaque_data
+                .len()
+                .try_into()
+                .map_err(|_| NvmlError::InvalidArg)?,
+            opaqueData: [0; 4],
+        };
+
+        Ok(write_metadata(raw, &self.opaque_data))
+    }
+}
+
+/// Returned from `Nvml.vgpu_compatibility()`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuPgpuCompatibility {
+    /// The kinds of migration the vGPU's VM is compatible with.
+    pub vm_compatibility: VgpuVmCompatibility,
+    /// What limits the compatibility, if anything.
+    pub limit: VgpuCompatibilityLimit,
+}
+
+impl TryFrom<nvmlVgpuPgpuCompatibility_t> for VgpuPgpuCompatibility {
+    type Error = NvmlError;
+
+    /**
+    Construct `VgpuPgpuCompatibility` from the corresponding C struct.
+
+    # Errors
+
+    * `IncorrectBits`, if bits are found in either field that don't match the
+      flags in this wrapper
+    */
+    fn try_from(value: nvmlVgpuPgpuCompatibility_t) -> Result<Self, Self::Error> {
+        Ok(Self {
+            vm_compatibility: VgpuVmCompatibility::from_bits(value.vgpuVmCompatibility).ok_or(
+                NvmlError::IncorrectBits(Bits::U32(value.vgpuVmCompatibility)),
+            )?,
+            limit: VgpuCompatibilityLimit::from_bits(value.compatibilityLimitCode).ok_or(
+                NvmlError::IncorrectBits(Bits::U32(value.compatibilityLimitCode)),
+            )?,
+        })
+    }
+}
+
+impl VgpuPgpuCompatibility {
+    /// Convert this `VgpuPgpuCompatibility` back into its C counterpart.
+    pub fn into_c(self) -> nvmlVgpuPgpuCompatibility_t {
+        nvmlVgpuPgpuCompatibility_t {
+            vgpuVmCompatibility: self.vm_compatibility.bits(),
+            compatibilityLimitCode: self.limit.bits(),
+        }
+    }
+}
+
+/// Implements NVML_STRUCT_VERSION(VgpuTypeBar1Info, 1), as detailed in nvml.h
+pub(crate) fn vgpu_type_bar1_info_version() -> u32 {
+    (mem::size_of::<nvmlVgpuTypeBar1Info_t>() | (1_usize << 24_usize)) as u32
+}
+
+/// Implements NVML_STRUCT_VERSION(VgpuTypeMaxInstance, 1), as detailed in nvml.h
+pub(crate) fn vgpu_type_max_instance_version() -> u32 {
+    (mem::size_of::<nvmlVgpuTypeMaxInstance_t>() | (1_usize << 24_usize)) as u32
+}
+
+/// Implements NVML_STRUCT_VERSION(VgpuPlacementList, 2), as detailed in nvml.h
+pub(crate) fn vgpu_placement_list_version() -> u32 {
+    (mem::size_of::<nvmlVgpuPlacementList_t>() | (2_usize << 24_usize)) as u32
+}
+
+/// Implements NVML_STRUCT_VERSION(VgpuRuntimeState, 1), as detailed in nvml.h
+pub(crate) fn vgpu_runtime_state_version() -> u32 {
+    (mem::size_of::<nvmlVgpuRuntimeState_t>() | (1_usize << 24_usize)) as u32
+}
+
+/// Implements NVML_STRUCT_VERSION(VgpuHeterogeneousMode, 1), as detailed in
+/// nvml.h
+pub(crate) fn vgpu_heterogeneous_mode_version() -> u32 {
+    (mem::size_of::<nvmlVgpuHeterogeneousMode_t>() | (1_usize << 24_usize)) as u32
+}
+
+/// Implements NVML_STRUCT_VERSION(ActiveVgpuInstanceInfo, 1), as detailed in
+/// nvml.h
+pub(crate) fn active_vgpu_instance_info_version() -> u32 {
+    (mem::size_of::<nvmlActiveVgpuInstanceInfo_t>() | (1_usize << 24_usize)) as u32
+}
+
+/// Implements NVML_STRUCT_VERSION(VgpuTypeIdInfo, 1), as detailed in nvml.h
+pub(crate) fn vgpu_type_id_info_version() -> u32 {
+    (mem::size_of::<nvmlVgpuTypeIdInfo_t>() | (1_usize << 24_usize)) as u32
+}
+
+/// Implements NVML_STRUCT_VERSION(VgpuCreatablePlacementInfo, 1), as detailed
+/// in nvml.h
+pub(crate) fn vgpu_creatable_placement_info_version() -> u32 {
+    (mem::size_of::<nvmlVgpuCreatablePlacementInfo_t>() | (1_usize << 24_usize)) as u32
+}
+
+/// Implements NVML_STRUCT_VERSION(VgpuSchedulerState, 1), as detailed in
+/// nvml.h
+pub(crate) fn vgpu_scheduler_state_version() -> u32 {
+    (mem::size_of::<nvmlVgpuSchedulerState_t>() | (1_usize << 24_usize)) as u32
+}
+
+/// Implements NVML_STRUCT_VERSION(VgpuSchedulerStateInfo, 1), as detailed in
+/// nvml.h
+pub(crate) fn vgpu_scheduler_state_info_version() -> u32 {
+    (mem::size_of::<nvmlVgpuSchedulerStateInfo_t>() | (1_usize << 24_usize)) as u32
+}
+
+/// Implements NVML_STRUCT_VERSION(VgpuSchedulerLogInfo, 1), as detailed in
+/// nvml.h
+pub(crate) fn vgpu_scheduler_log_info_version() -> u32 {
+    (mem::size_of::<nvmlVgpuSchedulerLogInfo_t>() | (1_usize << 24_usize)) as u32
+}
+
+/// Utilization sample for a single vGPU instance.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuInstanceUtilizationSample {
+    /// The vGPU instance this sample is for
+    pub vgpu_instance: u32,
+    /// CPU timestamp in μs
+    pub timestamp: u64,
+    /// SM (3D / compute) utilization
+    pub sm_util: SampleValue,
+    /// Frame buffer memory utilization
+    pub mem_util: SampleValue,
+    /// Encoder utilization
+    pub enc_util: SampleValue,
+    /// Decoder utilization
+    pub dec_util: SampleValue,
+}
+
+impl VgpuInstanceUtilizationSample {
+    /// Given a tag and a C struct with untagged unions, returns a Rust struct
+    /// with the correct union variants.
+    pub fn from_tag_and_struct(
+        tag: &SampleValueType,
+        struct_: nvmlVgpuInstanceUtilizationSample_t,
+    ) -> Self {
+        Self {
+            vgpu_instance: struct_.vgpuInstance,
+            timestamp: struct_.timeStamp,
+            sm_util: SampleValue::from_tag_and_union(tag, struct_.smUtil),
+            mem_util: SampleValue::from_tag_and_union(tag, struct_.memUtil),
+            enc_util: SampleValue::from_tag_and_union(tag, struct_.encUtil),
+            dec_util: SampleValue::from_tag_and_union(tag, struct_.decUtil),
+        }
+    }
+
+    /// Convert this `VgpuInstanceUtilizationSample` back into its C
+    /// counterpart. The unions' tag is the `value_type()` of each value.
+    pub fn into_c(self) -> nvmlVgpuInstanceUtilizationSample_t {
+        nvmlVgpuInstanceUtilizationSample_t {
+            vgpuInstance: self.vgpu_instance,
+            timeStamp: self.timestamp,
+            smUtil: self.sm_util.as_c(),
+            memUtil: self.mem_util.as_c(),
+            encUtil: self.enc_util.as_c(),
+            decUtil: self.dec_util.as_c(),
+        }
+    }
+}
+
+/// Utilization sample for a single vGPU instance, including the JPEG and OFA
+/// engines.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuInstanceUtilizationInfo {
+    /// The vGPU instance this sample is for
+    pub vgpu_instance: u32,
+    /// CPU timestamp in μs
+    pub timestamp: u64,
+    /// SM (3D / compute) utilization
+    pub sm_util: SampleValue,
+    /// Frame buffer memory utilization
+    pub mem_util: SampleValue,
+    /// Encoder utilization
+    pub enc_util: SampleValue,
+    /// Decoder utilization
+    pub dec_util: SampleValue,
+    /// JPEG utilization
+    pub jpg_util: SampleValue,
+    /// Optical flow accelerator utilization
+    pub ofa_util: SampleValue,
+}
+
+impl VgpuInstanceUtilizationInfo {
+    /// Given a tag and a C struct with untagged unions, returns a Rust struct
+    /// with the correct union variants.
+    pub fn from_tag_and_struct(
+        tag: &SampleValueType,
+        struct_: nvmlVgpuInstanceUtilizationInfo_v1_t,
+    ) -> Self {
+        Self {
+            vgpu_instance: struct_.vgpuInstance,
+            timestamp: struct_.timeStamp,
+            sm_util: SampleValue::from_tag_and_union(tag, struct_.smUtil),
+            mem_util: SampleValue::from_tag_and_union(tag, struct_.memUtil),
+            enc_util: SampleValue::from_tag_and_union(tag, struct_.encUtil),
+            dec_util: SampleValue::from_tag_and_union(tag, struct_.decUtil),
+            jpg_util: SampleValue::from_tag_and_union(tag, struct_.jpgUtil),
+            ofa_util: SampleValue::from_tag_and_union(tag, struct_.ofaUtil),
+        }
+    }
+
+    /// Convert this `VgpuInstanceUtilizationInfo` back into its C
+    /// counterpart. The unions' tag is the `value_type()` of each value.
+    pub fn into_c(self) -> nvmlVgpuInstanceUtilizationInfo_v1_t {
+        nvmlVgpuInstanceUtilizationInfo_v1_t {
+            timeStamp: self.timestamp,
+            vgpuInstance: self.vgpu_instance,
+            smUtil: self.sm_util.as_c(),
+            memUtil: self.mem_util.as_c(),
+            encUtil: self.enc_util.as_c(),
+            decUtil: self.dec_util.as_c(),
+            jpgUtil: self.jpg_util.as_c(),
+            ofaUtil: self.ofa_util.as_c(),
+        }
+    }
+}
+
+/// Implements NVML_STRUCT_VERSION(VgpuInstancesUtilizationInfo, 1), as
+/// detailed in nvml.h
+pub(crate) fn vgpu_instances_utilization_info_version() -> u32 {
+    (mem::size_of::<nvmlVgpuInstancesUtilizationInfo_v1_t>() | (1_usize << 24_usize)) as u32
+}
+
+/// Utilization sample for a single process running inside a vGPU instance.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuProcessUtilizationSample {
+    /// The vGPU instance the process is running in
+    pub vgpu_instance: u32,
+    /// PID of the process within the guest
+    pub pid: u32,
+    /// Name of the process within the guest
+    pub process_name: String,
+    /// CPU timestamp in μs
+    pub timestamp: u64,
+    /// SM (3D / compute) utilization
+    pub sm_util: u32,
+    /// Frame buffer memory utilization
+    pub mem_util: u32,
+    /// Encoder utilization
+    pub enc_util: u32,
+    /// Decoder utilization
+    pub dec_util: u32,
+}
+
+impl TryFrom<nvmlVgpuProcessUtilizationSample_t> for VgpuProcessUtilizationSample {
+    type Error = NvmlError;
+
+    /**
+    Construct `VgpuProcessUtilizationSample` from the corresponding C struct.
+
+    # Errors
+
+    * `Utf8Error`, if the process name is not valid UTF-8
+    */
+    fn try_from(value: nvmlVgpuProcessUtilizationSample_t) -> Result<Self, Self::Error> {
+        let process_name = unsafe { CStr::from_ptr(value.processName.as_ptr()) };
+
+        Ok(Self {
+            vgpu_instance: value.vgpuInstance,
+            pid: value.pid,
+            process_name: process_name.to_str()?.into(),
+            timestamp: value.timeStamp,
+            sm_util: value.smUtil,
+            mem_util: value.memUtil,
+            enc_util: value.encUtil,
+            dec_util: value.decUtil,
+        })
+    }
+}
+
+impl VgpuProcessUtilizationSample {
+    /**
+    Convert this `VgpuProcessUtilizationSample` back into its C counterpart.
+
+    # Errors
+
+    * `NulError`, if a nul byte was found in the process name
+    * `StringTooLong`, if the process name is too long for its C buffer
+    */
+    pub fn try_into_c(self) -> Result<nvmlVgpuProcessUtilizationSample_t, NvmlError> {
+        Ok(nvmlVgpuProcessUtilizationSample_t {
+            vgpuInstance: self.vgpu_instance,
+            pid: self.pid,
+            processName: c_string(self.process_name)?,
+            timeStamp: self.timestamp,
+            smUtil: self.sm_util,
+            memUtil: self.mem_util,
+            encUtil: self.enc_util,
+            decUtil: self.dec_util,
+        })
+    }
+}
+
+/// Utilization sample for a single process running inside a vGPU instance,
+/// including the JPEG and OFA engines.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuProcessUtilizationInfo {
+    /// The vGPU instance the process is running in
+    pub vgpu_instance: u32,
+    /// PID of the process within the guest
+    pub pid: u32,
+    /// Name of the process within the guest
+    pub process_name: String,
+    /// CPU timestamp in μs
+    pub timestamp: u64,
+    /// SM (3D / compute) utilization
+    pub sm_util: u32,
+    /// Frame buffer memory utilization
+    pub mem_util: u32,
+    /// Encoder utilization
+    pub enc_util: u32,
+    /// Decoder utilization
+    pub dec_util: u32,
+    /// JPEG utilization
+    pub jpg_util: u32,
+    /// Optical flow accelerator utilization
+    pub ofa_util: u32,
+}
+
+impl TryFrom<nvmlVgpuProcessUtilizationInfo_v1_t> for VgpuProcessUtilizationInfo {
+    type Error = NvmlError;
+
+    /**
+    Construct `VgpuProcessUtilizationInfo` from the corresponding C struct.
+
+    # Errors
+
+    * `Utf8Error`, if the process name is not valid UTF-8
+    */
+    fn try_from(value: nvmlVgpuProcessUtilizationInfo_v1_t) -> Result<Self, Self::Error> {
+        let process_name = unsafe { CStr::from_ptr(value.processName.as_ptr()) };
+
+        Ok(Self {
+            vgpu_instance: value.vgpuInstance,
+            pid: value.pid,
+            process_name: process_name.to_str()?.into(),
+            timestamp: value.timeStamp,
+            sm_util: value.smUtil,
+            mem_util: value.memUtil,
+            enc_util: value.encUtil,
+            dec_util: value.decUtil,
+            jpg_util: value.jpgUtil,
+            ofa_util: value.ofaUtil,
+        })
+    }
+}
+
+impl VgpuProcessUtilizationInfo {
+    /**
+    Convert this `VgpuProcessUtilizationInfo` back into its C counterpart.
+
+    # Errors
+
+    * `NulError`, if a nul byte was found in the process name
+    * `StringTooLong`, if the process name is too long for its C buffer
+    */
+    pub fn try_into_c(self) -> Result<nvmlVgpuProcessUtilizationInfo_v1_t, NvmlError> {
+        Ok(nvmlVgpuProcessUtilizationInfo_v1_t {
+            processName: c_string(self.process_name)?,
+            timeStamp: self.timestamp,
+            vgpuInstance: self.vgpu_instance,
+            pid: self.pid,
+            smUtil: self.sm_util,
+            memUtil: self.mem_util,
+            encUtil: self.enc_util,
+            decUtil: self.dec_util,
+            jpgUtil: self.jpg_util,
+            ofaUtil: self.ofa_util,
+        })
+    }
+}
+
+/// Implements NVML_STRUCT_VERSION(VgpuProcessesUtilizationInfo, 1), as
+/// detailed in nvml.h
+pub(crate) fn vgpu_processes_utilization_info_version() -> u32 {
+    (mem::size_of::<nvmlVgpuProcessesUtilizationInfo_v1_t>() | (1_usize << 24_usize)) as u32
+}
+
+/// Vgpu scheduler Params
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuSchedulerParams {
+    /// Average factor in compensating the timeslice, in ARR mode
+    pub avg_factor: Option<u32>,
+    /// Timeslice in ns
+    pub timeslice: u32,
+}
+
+impl VgpuSchedulerParams {
+    /// Given the ARR mode the union is tagged with and the untagged union,
+    /// returns the parameters for that mode.
+    pub fn from_mode_and_union(
+        arr_mode: VgpuSchedulerArrMode,
+        union_: nvmlVgpuSchedulerParams_t,
+    ) -> Self {
+        unsafe {
+            match arr_mode {
+                VgpuSchedulerArrMode::Enabled => {
+                    let data = union_.vgpuSchedDataWithARR;
+                    Self {
+                        avg_factor: Some(data.avgFactor),
+                        timeslice: data.timeslice,
+                    }
+                }
+                _ => Self {
+                    avg_factor: None,
+                    timeslice: union_.vgpuSchedData.timeslice,
+                },
+            }
+        }
+    }
+
+    /// Obtain this struct's C counterpart.
+    pub fn as_c(&self) -> nvmlVgpuSchedulerParams_t {
+        match self.avg_factor {
+            Some(a) => nvmlVgpuSchedulerParams_t {
+                vgpuSchedDataWithARR: nvmlVgpuSchedulerParams_t__bindgen_ty_1 {
+                    avgFactor: a,
+                    timeslice: self.timeslice,
+                },
+            },
+            None => nvmlVgpuSchedulerParams_t {
+                vgpuSchedData: nvmlVgpuSchedulerParams_t__bindgen_ty_2 {
+                    timeslice: self.timeslice,
+                },
+            },
+        }
+    }
+}
+
+/// Vgpu scheduler Log entry
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuSchedulerLogEntry {
+    /// Timestamp when the software runlist was preempted (in ns)
+    pub timestamp: u64,
+    /// Total time this runlist has run
+    pub time_run_total: Duration,
+    /// Time this runlist ran before preemption
+    pub time_run: Duration,
+    /// Runlist Id
+    pub sw_runlist_id: u32,
+    /// Timeslice after deduction
+    pub target_time_slice: Duration,
+    /// Preemption time for this runlist
+    pub cumulative_preemption_time: Duration,
+}
+
+impl From<nvmlVgpuSchedulerLogEntry_t> for VgpuSchedulerLogEntry {
+    fn from(value: nvmlVgpuSchedulerLogEntry_t) -> Self {
+        Self {
+            timestamp: value.timestamp,
+            time_run_total: Duration::from_nanos(value.timeRunTotal),
+            time_run: Duration::from_nanos(value.timeRun),
+            sw_runlist_id: value.swRunlistId,
+            target_time_slice: Duration::from_nanos(value.targetTimeSlice),
+            cumulative_preemption_time: Duration::from_nanos(value.cumulativePreemptionTime),
+        }
+    }
+}
+
+impl VgpuSchedulerLogEntry {
+    /// Convert this `VgpuSchedulerLogEntry` back into its C counterpart.
+    ///
+    /// Durations too long for a `u64` of ns are truncated.
+    pub fn into_c(self) -> nvmlVgpuSchedulerLogEntry_t {
+        nvmlVgpuSchedulerLogEntry_t {
+            timestamp: self.timestamp,
+            timeRunTotal: self.time_run_total.as_nanos() as u64,
+            timeRun: self.time_run.as_nanos() as u64,
+            swRunlistId: self.sw_runlist_id,
+            targetTimeSlice: self.target_time_slice.as_nanos() as u64,
+            cumulativePreemptionTime: self.cumulative_preemption_time.as_nanos() as u64,
+        }
+    }
+}
+
+/// Vgpu scheduler Log
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuSchedulerLog {
+    /// Engine id whose software runlist are fetched
+    pub engine_id: u32,
+    /// Scheduler policy
+    pub scheduler_policy: VgpuSchedulerPolicy,
+    /// Scheduler Round Robin Mode
+    pub arr_mode: VgpuSchedulerArrMode,
+    pub scheduler_params: VgpuSchedulerParams,
+    /// Number of log entries fetched during the call
+    pub entries_count: u32,
+    /// Log entries
+    pub entries: Vec<VgpuSchedulerLogEntry>,
+}
+
+impl TryFrom<nvmlVgpuSchedulerLog_t> for VgpuSchedulerLog {
+    type Error = NvmlError;
+
+    /**
+    Construct `VgpuSchedulerLog` from the corresponding C struct.
+
+    Only the first `entriesCount` entries of the C array are kept.
+
+    # Errors
+
+    * `UnexpectedVariant`, for which you can read the docs for
+    */
+    fn try_from(value: nvmlVgpuSchedulerLog_t) -> Result<Self, Self::Error> {
+        let arr_mode = VgpuSchedulerArrMode::try_from(value.arrMode)?;
+        let entries = value
+            .logEntries
+            .iter()
+            .take(value.entriesCount as usize)
+            .map(|e| VgpuSchedulerLogEntry::from(*e))
+            .collect::<Vec<_>>();
+
+        Ok(Self {
+            engine_id: value.engineId,
+            scheduler_policy: VgpuSchedulerPolicy::try_from(value.schedulerPolicy)?,
+            arr_mode,
+            scheduler_params: VgpuSchedulerParams::from_mode_and_union(
+                arr_mode,
+                value.schedulerParams,
+            ),
+            entries_count: entries.len() as u32,
+            entries,
+        })
+    }
+}
+
+impl TryFrom<nvmlVgpuSchedulerLogInfo_t> for VgpuSchedulerLog {
+    type Error = NvmlError;
+
+    /**
+    Construct `VgpuSchedulerLog` from the GPU instance variant of the C
+    struct, which adds a version to the device one.
+
+    # Errors
+
+    * `UnexpectedVariant`, for which you can read the docs for
+    */
+    fn try_from(value: nvmlVgpuSchedulerLogInfo_t) -> Result<Self, Self::Error> {
+        Self::try_from(nvmlVgpuSchedulerLog_t {
+            engineId: value.engineId,
+            schedulerPolicy: value.schedulerPolicy,
+            arrMode: value.arrMode,
+            schedulerParams: value.schedulerParams,
+            entriesCount: value.entriesCount,
+            logEntries: value.logEntries,
+        })
+    }
+}
+
+impl VgpuSchedulerLog {
+    /**
+    Convert this `VgpuSchedulerLog` back into its C counterpart.
+
+    Entries of the C array past the end of `entries` are zeroed.
+
+    # Errors
+
+    * `InvalidArg`, if `entries` has more than
+      `NVML_SCHEDULER_SW_MAX_LOG_ENTRIES` entries
+    */
+    pub fn try_into_c(self) -> Result<nvmlVgpuSchedulerLog_t, NvmlError> {
+        Ok(nvmlVgpuSchedulerLog_t {
+            engineId: self.engine_id,
+            schedulerPolicy: self.scheduler_policy.as_c(),
+            arrMode: self.arr_mode.as_c(),
+            schedulerParams: self.scheduler_params.as_c(),
+            entriesCount: self.entries_count,
+            logEntries: c_array(self.entries.into_iter().map(|e| e.into_c()))?,
+        })
+    }
+}
+
+/// Vgpu scheduler state
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuSchedulerGetState {
+    /// Adaptative Round Robin scheduler mode
+    pub arr_mode: VgpuSchedulerArrMode,
+    /// Scheduler policy
+    pub scheduler_policy: VgpuSchedulerPolicy,
+    pub scheduler_params: VgpuSchedulerParams,
+}
+
+impl TryFrom<nvmlVgpuSchedulerGetState_t> for VgpuSchedulerGetState {
+    type Error = NvmlError;
+
+    /**
+    Construct `VgpuSchedulerGetState` from the corresponding C struct.
+
+    # Errors
+
+    * `UnexpectedVariant`, for which you can read the docs for
+    */
+    fn try_from(value: nvmlVgpuSchedulerGetState_t) -> Result<Self, Self::Error> {
+        let arr_mode = VgpuSchedulerArrMode::try_from(value.arrMode)?;
+
+        Ok(Self {
+            arr_mode,
+            scheduler_policy: VgpuSchedulerPolicy::try_from(value.schedulerPolicy)?,
+            scheduler_params: VgpuSchedulerParams::from_mode_and_union(
+                arr_mode,
+                value.schedulerParams,
+            ),
+        })
+    }
+}
+
+impl TryFrom<nvmlVgpuSchedulerStateInfo_t> for VgpuSchedulerGetState {
+    type Error = NvmlError;
+
+    /**
+    Construct `VgpuSchedulerGetState` from the GPU instance variant of the C
+    struct.
+
+    # Errors
+
+    * `UnexpectedVariant`, for which you can read the docs for
+    */
+    fn try_from(value: nvmlVgpuSchedulerStateInfo_t) -> Result<Self, Self::Error> {
+        Self::try_from(nvmlVgpuSchedulerGetState_t {
+            schedulerPolicy: value.schedulerPolicy,
+            arrMode: value.arrMode,
+            schedulerParams: value.schedulerParams,
+        })
+    }
+}
+
+impl VgpuSchedulerGetState {
+    /// Convert this `VgpuSchedulerGetState` back into its C counterpart.
+    pub fn into_c(self) -> nvmlVgpuSchedulerGetState_t {
+        nvmlVgpuSchedulerGetState_t {
+            schedulerPolicy: self.scheduler_policy.as_c(),
+            arrMode: self.arr_mode.as_c(),
+            schedulerParams: self.scheduler_params.as_c(),
+        }
+    }
+}
+
+/// How the vGPU scheduler should time-slice the vGPUs.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VgpuSchedulerSetParams {
+    /// Fixed timeslice in ns for each software run list, with ARR disabled
+    Timeslice(u32),
+    /// Adaptive Round Robin, adapting the timeslice to the vGPUs' usage
+    Arr {
+        /// Average factor in compensating the timeslice
+        avg_factor: u32,
+        /// Scheduling frequency
+        frequency: u32,
+    },
+}
+
+impl VgpuSchedulerSetParams {
+    /// The ARR mode these parameters are for.
+    pub fn arr_mode(&self) -> VgpuSchedulerArrMode {
+        match self {
+            Self::Timeslice(_) => VgpuSchedulerArrMode::Disabled,
+            Self::Arr { .. } => VgpuSchedulerArrMode::Enabled,
+        }
+    }
+
+    /// Obtain this enum's C counterpart.
+    pub fn as_c(&self) -> nvmlVgpuSchedulerSetParams_t {
+        match *self {
+            Self::Timeslice(timeslice) => nvmlVgpuSchedulerSetParams_t {
+                vgpuSchedData: nvmlVgpuSchedulerSetParams_t__bindgen_ty_2 { timeslice },
+            },
+            Self::Arr {
+                avg_factor,
+                frequency,
+            } => nvmlVgpuSchedulerSetParams_t {
+                vgpuSchedDataWithARR: nvmlVgpuSchedulerSetParams_t__bindgen_ty_1 {
+                    avgFactor: avg_factor,
+                    frequency,
+                },
+            },
+        }
+    }
+}
+
+/// vGPU scheduler policy and parameters to set, checked against the device's
+/// `VgpuSchedulerCapabilities` before being set.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuSchedulerSetState {
+    pub policy: VgpuSchedulerPolicy,
+    pub params: VgpuSchedulerSetParams,
+}
+
+impl VgpuSchedulerSetState {
+    /**
+    Checks the combination of policy and parameters, regardless of what the
+    device supports: ARR only applies to the best effort policy.
+
+    # Errors
+
+    * `UnsupportedVgpuSchedulerState`, if ARR is requested with another policy
+    */
+    pub fn validate(&self) -> Result<(), NvmlError> {
+        match self.params {
+            VgpuSchedulerSetParams::Arr { .. }
+                if self.policy != VgpuSchedulerPolicy::BestEffort =>
+            {
+                Err(NvmlError::UnsupportedVgpuSchedulerState(format!(
+                    "ARR mode requires the {:?} policy, not {:?}",
+                    VgpuSchedulerPolicy::BestEffort,
+                    self.policy
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Obtain this struct's C counterpart.
+    pub fn as_c(&self) -> nvmlVgpuSchedulerSetState_t {
         nvmlVgpuSchedulerSetState_t {
-            enableARRMode: self.enable_arr_mode,
-            schedulerPolicy: self.scheduler_policy,
-            schedulerParams: self.scheduler_params.as_c(),
+            schedulerPolicy: self.policy.as_c(),
+            // Unlike `arrMode` when getting the state, this is a boolean rather
+            // than one of the `NVML_VGPU_SCHEDULER_ARR_*` constants
+            enableARRMode: (self.params.arr_mode() == VgpuSchedulerArrMode::Enabled) as u32,
+            schedulerParams: self.params.as_c(),
+        }
+    }
+}
+
+/// A thermal sensor's reading and its default range, in °C.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ThermalSensor {
+    /// The chip reading the sensor.
+    pub controller: ThermalController,
+    pub default_min_temp: i32,
+    pub default_max_temp: i32,
+    pub current_temp: i32,
+    /// What the sensor measures the temperature of.
+    pub target: ThermalTarget,
+}
+
+impl TryFrom<nvmlGpuThermalSettings_t__bindgen_ty_1> for ThermalSensor {
+    type Error = NvmlError;
+
+    /**
+    Construct `ThermalSensor` from the corresponding C struct.
+
+    # Errors
+
+    * `UnexpectedVariant`, for which you can read the docs for
+    */
+    fn try_from(value: nvmlGpuThermalSettings_t__bindgen_ty_1) -> Result<Self, Self::Error> {
+        Ok(Self {
+            controller: ThermalController::try_from(value.controller)?,
+            default_min_temp: value.defaultMinTemp,
+            default_max_temp: value.defaultMaxTemp,
+            current_temp: value.currentTemp,
+            target: ThermalTarget::try_from(value.target)?,
+        })
+    }
+}
+
+impl ThermalSensor {
+    /// Convert this `ThermalSensor` back into its C counterpart.
+    pub fn into_c(self) -> nvmlGpuThermalSettings_t__bindgen_ty_1 {
+        nvmlGpuThermalSettings_t__bindgen_ty_1 {
+            controller: self.controller.as_c(),
+            defaultMinTemp: self.default_min_temp,
+            defaultMaxTemp: self.default_max_temp,
+            currentTemp: self.current_temp,
+            target: self.target.as_c(),
         }
     }
 }
 
+/// Returned from `Device.thermal_settings()`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ThermalSettings {
+    /// The device's thermal sensors, at most `NVML_MAX_THERMAL_SENSORS_PER_GPU`.
+    pub sensors: Vec<ThermalSensor>,
+}
+
+impl TryFrom<nvmlGpuThermalSettings_t> for ThermalSettings {
+    type Error = NvmlError;
+
+    /**
+    Construct `ThermalSettings` from the corresponding C struct.
+
+    # Errors
+
+    * `UnexpectedVariant`, for which you can read the docs for
+    */
+    fn try_from(value: nvmlGpuThermalSettings_t) -> Result<Self, Self::Error> {
+        let sensors = value
+            .sensor
+            .iter()
+            .take(value.count as usize)
+            .map(|sensor| ThermalSensor::try_from(*sensor))
+            .collect::<Result<_, NvmlError>>()?;
+
+        Ok(Self { sensors })
+    }
+}
+
+impl ThermalSettings {
+    /// The first sensor measuring `target`, if there is one.
+    pub fn sensor(&self, target: ThermalTarget) -> Option<&ThermalSensor> {
+        self.sensors.iter().find(|sensor| sensor.target == target)
+    }
+
+    /**
+    Convert this `ThermalSettings` back into its C counterpart.
+
+    Entries of the C array past the end of `sensors` are zeroed.
+
+    # Errors
+
+    * `InvalidArg`, if `sensors` has more than `NVML_MAX_THERMAL_SENSORS_PER_GPU`
+      entries
+    */
+    pub fn try_into_c(self) -> Result<nvmlGpuThermalSettings_t, NvmlError> {
+        Ok(nvmlGpuThermalSettings_t {
+            count: self.sensors.len() as u32,
+            sensor: c_array(self.sensors.into_iter().map(|s| s.into_c()))?,
+        })
+    }
+}
+
+/// Implements NVML_STRUCT_VERSION(Temperature, 1), as detailed in nvml.h
+pub(crate) fn temperature_version() -> u32 {
+    (mem::size_of::<nvmlTemperature_v1_t>() | (1_usize << 24_usize)) as u32
+}
+
+/// Implements NVML_STRUCT_VERSION(MarginTemperature, 1), as detailed in nvml.h
+pub(crate) fn margin_temperature_version() -> u32 {
+    (mem::size_of::<nvmlMarginTemperature_v1_t>() | (1_usize << 24_usize)) as u32
+}
+
 #[cfg(test)]
 #[allow(unused_variables, unused_imports)]
 mod tests {
+    use super::*;
     use crate::error::*;
     use crate::ffi::bindings::*;
+    use crate::struct_wrappers::test::pci_info;
     use crate::test_utils::*;
+    use proptest::prelude::*;
     use std::convert::TryInto;
     use std::mem;
 
+    const NOT_AVAILABLE_U32: u32 = NVML_VALUE_NOT_AVAILABLE as u32;
+    const NOT_AVAILABLE_U64: u64 = NVML_VALUE_NOT_AVAILABLE as u64;
+
+    fn sample_value() -> impl Strategy<Value = SampleValue> {
+        prop_oneof![
+            (-1e12..1e12f64).prop_map(SampleValue::F64),
+            any::<u32>().prop_map(SampleValue::U32),
+            any::<u64>().prop_map(SampleValue::U64),
+            any::<i64>().prop_map(SampleValue::I64),
+        ]
+    }
+
+    /// `n` sample values sharing a single tag, as NVML returns them
+    fn sample_values(n: usize) -> impl Strategy<Value = Vec<SampleValue>> {
+        prop_oneof![
+            prop::collection::vec((-1e12..1e12f64).prop_map(SampleValue::F64), n),
+            prop::collection::vec(any::<u32>().prop_map(SampleValue::U32), n),
+            prop::collection::vec(any::<u64>().prop_map(SampleValue::U64), n),
+            prop::collection::vec(any::<i64>().prop_map(SampleValue::I64), n),
+        ]
+    }
+
+    fn bridge_chip_info() -> impl Strategy<Value = BridgeChipInfo> {
+        (
+            any::<u32>(),
+            prop::sample::select(vec![BridgeChip::PLX, BridgeChip::BRO4]),
+        )
+            .prop_map(|(fw_version, chip_type)| BridgeChipInfo {
+                fw_version: FirmwareVersion::from(fw_version),
+                chip_type,
+            })
+    }
+
+    fn thermal_sensor() -> impl Strategy<Value = ThermalSensor> {
+        (
+            prop::sample::select(vec![
+                ThermalController::GpuInternal,
+                ThermalController::Max6649,
+                ThermalController::Unknown,
+            ]),
+            any::<(i32, i32, i32)>(),
+            prop::sample::select(vec![
+                ThermalTarget::Gpu,
+                ThermalTarget::Memory,
+                ThermalTarget::Board,
+            ]),
+        )
+            .prop_map(|(controller, temps, target)| ThermalSensor {
+                controller,
+                default_min_temp: temps.0,
+                default_max_temp: temps.1,
+                current_temp: temps.2,
+                target,
+            })
+    }
+
+    fn vgpu_scheduler_log_entry() -> impl Strategy<Value = VgpuSchedulerLogEntry> {
+        any::<(u64, u64, u64, u32, u64, u64)>().prop_map(
+            |(
+                timestamp,
+                time_run_total,
+                time_run,
+                sw_runlist_id,
+                target_time_slice,
+                cumulative_preemption_time,
+            )| VgpuSchedulerLogEntry {
+                timestamp,
+                time_run_total: Duration::from_nanos(time_run_total),
+                time_run: Duration::from_nanos(time_run),
+                sw_runlist_id,
+                target_time_slice: Duration::from_nanos(target_time_slice),
+                cumulative_preemption_time: Duration::from_nanos(cumulative_preemption_time),
+            },
+        )
+    }
+
+    fn vgpu_scheduler_policy() -> impl Strategy<Value = VgpuSchedulerPolicy> {
+        prop::sample::select(vec![
+            VgpuSchedulerPolicy::Unknown,
+            VgpuSchedulerPolicy::BestEffort,
+            VgpuSchedulerPolicy::EqualShare,
+            VgpuSchedulerPolicy::FixedShare,
+        ])
+    }
+
+    fn vgpu_scheduler_arr_mode() -> impl Strategy<Value = VgpuSchedulerArrMode> {
+        prop::sample::select(vec![
+            VgpuSchedulerArrMode::Default,
+            VgpuSchedulerArrMode::Disabled,
+            VgpuSchedulerArrMode::Enabled,
+        ])
+    }
+
+    fn scheduler_capabilities() -> VgpuSchedulerCapabilities {
+        VgpuSchedulerCapabilities {
+            is_arr_mode_supported: true,
+            max_avg_factor_for_arr: 60,
+            max_freq_for_arr: 960,
+            max_time_slice: 30_000_000,
+            min_avg_factor_for_arr: 0,
+            min_freq_for_arr: 64,
+            min_time_slice: 1_000_000,
+            supported_schedulers: vec![
+                VgpuSchedulerPolicy::BestEffort,
+                VgpuSchedulerPolicy::EqualShare,
+            ],
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn pci_info_round_trip(info in pci_info()) {
+            let c = info.clone().try_into_c().unwrap();
+            let present = info.pci_sub_system_id.is_some();
+
+            prop_assert_eq!(PciInfo::try_from(c, present).unwrap(), info);
+        }
+
+        #[test]
+        fn bar1_memory_info_round_trip(free: u64, total: u64, used: u64) {
+            let info = BAR1MemoryInfo { free, total, used };
+
+            prop_assert_eq!(BAR1MemoryInfo::from(info.clone().into_c()), info);
+        }
+
+        #[test]
+        fn bridge_chip_hierarchy_round_trip(
+            chips_hierarchy in prop::collection::vec(
+                bridge_chip_info(),
+                NVML_MAX_PHYSICAL_BRIDGE as usize,
+            ),
+            chip_count: u8,
+        ) {
+            let hierarchy = BridgeChipHierarchy { chips_hierarchy, chip_count };
+            let c = hierarchy.clone().try_into_c().unwrap();
+
+            prop_assert_eq!(BridgeChipHierarchy::try_from(c).unwrap(), hierarchy);
+        }
+
+        #[test]
+        fn process_info_round_trip(
+            pid: u32,
+            used_gpu_memory in prop_oneof![
+                Just(UsedGpuMemory::Unavailable),
+                (0..NOT_AVAILABLE_U64).prop_map(UsedGpuMemory::Used),
+            ],
+            gpu_instance_id in prop::option::of(0..u32::MAX),
+            compute_instance_id in prop::option::of(0..u32::MAX),
+        ) {
+            let info = ProcessInfo { pid, used_gpu_memory, gpu_instance_id, compute_instance_id };
+
+            prop_assert_eq!(ProcessInfo::from(info.clone().into_c()), info);
+        }
+
+        #[test]
+        fn process_detail_round_trip(
+            pid: u32,
+            used in prop::collection::vec(
+                prop_oneof![
+                    Just(UsedGpuMemory::Unavailable),
+                    (0..NOT_AVAILABLE_U64).prop_map(UsedGpuMemory::Used),
+                ],
+                2,
+            ),
+            gpu_instance_id in prop::option::of(0..u32::MAX),
+            compute_instance_id in prop::option::of(0..u32::MAX),
+        ) {
+            let detail = ProcessDetail {
+                pid,
+                used_gpu_memory: used[0].clone(),
+                gpu_instance_id,
+                compute_instance_id,
+                used_gpu_cc_protected_memory: used[1].clone(),
+            };
+
+            prop_assert_eq!(ProcessDetail::from(detail.clone().into_c()), detail);
+        }
+
+        #[test]
+        fn ecc_error_counts_round_trip(counts: (u64, u64, u64, u64)) {
+            let counts = EccErrorCounts {
+                device_memory: counts.0,
+                l1_cache: counts.1,
+                l2_cache: counts.2,
+                register_file: counts.3,
+            };
+
+            prop_assert_eq!(EccErrorCounts::from(counts.clone().into_c()), counts);
+        }
+
+        #[test]
+        fn row_remapper_histogram_round_trip(buckets: (u32, u32, u32, u32, u32)) {
+            let histogram = RowRemapperHistogram {
+                max: buckets.0,
+                high: buckets.1,
+                partial: buckets.2,
+                low: buckets.3,
+                none: buckets.4,
+            };
+
+            prop_assert_eq!(RowRemapperHistogram::from(histogram.clone().into_c()), histogram);
+        }
+
+        #[test]
+        fn thermal_settings_round_trip(
+            sensors in prop::collection::vec(
+                thermal_sensor(),
+                0..=NVML_MAX_THERMAL_SENSORS_PER_GPU as usize,
+            ),
+        ) {
+            let settings = ThermalSettings { sensors };
+            let c = settings.clone().try_into_c().unwrap();
+
+            prop_assert_eq!(ThermalSettings::try_from(c).unwrap(), settings);
+        }
+
+        #[test]
+        fn memory_info_round_trip(free: u64, reserved: u64, total: u64, used: u64, version: u32) {
+            let info = MemoryInfo { free, reserved, total, used, version };
+
+            prop_assert_eq!(MemoryInfo::from(info.clone().into_c()), info);
+        }
+
+        #[test]
+        fn utilization_round_trip(gpu: u32, memory: u32) {
+            let utilization = Utilization { gpu, memory };
+
+            prop_assert_eq!(Utilization::from(utilization.clone().into_c()), utilization);
+        }
+
+        #[test]
+        fn violation_time_round_trip(reference_time: u64, violation_time: u64) {
+            let time = ViolationTime { reference_time, violation_time };
+
+            prop_assert_eq!(ViolationTime::from(time.clone().into_c()), time);
+        }
+
+        #[test]
+        fn accounting_stats_round_trip(
+            gpu_utilization in prop::option::of(0..NOT_AVAILABLE_U32),
+            is_running: bool,
+            max_memory_usage in prop::option::of(0..NOT_AVAILABLE_U64),
+            memory_utilization in prop::option::of(0..NOT_AVAILABLE_U32),
+            start_time: u64,
+            time: u64,
+        ) {
+            let stats = AccountingStats {
+                gpu_utilization,
+                is_running,
+                max_memory_usage,
+                memory_utilization,
+                start_time,
+                time,
+            };
+
+            prop_assert_eq!(AccountingStats::from(stats.clone().into_c()), stats);
+        }
+
+        #[test]
+        fn encoder_session_info_round_trip(
+            ids: (u32, u32),
+            vgpu_instance in prop::option::of(1..=u32::MAX),
+            codec_type in prop::sample::select(vec![EncoderType::H264, EncoderType::HEVC]),
+            stats: (u32, u32, u32, u32),
+        ) {
+            let info = EncoderSessionInfo {
+                session_id: ids.0,
+                pid: ids.1,
+                vgpu_instance,
+                codec_type,
+                hres: stats.0,
+                vres: stats.1,
+                average_fps: stats.2,
+                average_latency: stats.3,
+            };
+
+            prop_assert_eq!(EncoderSessionInfo::try_from(info.clone().into_c()).unwrap(), info);
+        }
+
+        #[test]
+        fn sample_round_trip(timestamp: u64, value in sample_value()) {
+            let sample = Sample { timestamp, value };
+            let tag = sample.value.value_type();
+
+            prop_assert_eq!(Sample::from_tag_and_struct(&tag, sample.clone().into_c()), sample);
+        }
+
+        #[test]
+        fn process_utilization_sample_round_trip(
+            pid: u32,
+            timestamp: u64,
+            utils: (u32, u32, u32, u32),
+        ) {
+            let sample = ProcessUtilizationSample {
+                pid,
+                timestamp,
+                sm_util: utils.0,
+                mem_util: utils.1,
+                enc_util: utils.2,
+                dec_util: utils.3,
+            };
+
+            prop_assert_eq!(ProcessUtilizationSample::from(sample.clone().into_c()), sample);
+        }
+
+        #[test]
+        fn process_utilization_info_round_trip(
+            pid: u32,
+            timestamp: u64,
+            utils: (u32, u32, u32, u32, u32, u32),
+        ) {
+            let info = ProcessUtilizationInfo {
+                timestamp,
+                pid,
+                sm_util: utils.0,
+                mem_util: utils.1,
+                enc_util: utils.2,
+                dec_util: utils.3,
+                jpg_util: utils.4,
+                ofa_util: utils.5,
+            };
+
+            prop_assert_eq!(ProcessUtilizationInfo::from(info.clone().into_c()), info);
+        }
+
+        #[test]
+        fn field_value_sample_round_trip(
+            field: u32,
+            timestamp: i64,
+            latency: i64,
+            value in prop_oneof![
+                sample_value().prop_map(Ok),
+                prop::sample::select(vec![
+                    nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED,
+                    nvmlReturn_enum_NVML_ERROR_NO_PERMISSION,
+                    nvmlReturn_enum_NVML_ERROR_GPU_IS_LOST,
+                    nvmlReturn_enum_NVML_ERROR_UNKNOWN,
+                ])
+                .prop_map(Err),
+            ],
+        ) {
+            let sample = FieldValueSample {
+                field: FieldId(field),
+                timestamp,
+                latency,
+                value: value.clone().map_err(NvmlError::from),
+            };
+            let back = FieldValueSample::try_from(sample.into_c()).unwrap();
+
+            prop_assert_eq!(back.field, FieldId(field));
+            prop_assert_eq!(back.timestamp, timestamp);
+            prop_assert_eq!(back.latency, latency);
+            match (back.value, value) {
+                (Ok(back), Ok(value)) => prop_assert_eq!(back, value),
+                (Err(back), Err(code)) => prop_assert_eq!(nvmlReturn_t::from(back), code),
+                (back, value) => prop_assert!(false, "{:?} became {:?}", value, back),
+            }
+        }
+
+        #[test]
+        fn fbc_stats_round_trip(sessions_count: u32, average_fps: u32, average_latency: u32) {
+            let stats = FbcStats { sessions_count, average_fps, average_latency };
+
+            prop_assert_eq!(FbcStats::from(stats.clone().into_c()), stats);
+        }
+
+        #[test]
+        fn fbc_session_info_round_trip(
+            ids: (u32, u32, u32),
+            vgpu_instance in prop::option::of(1..=u32::MAX),
+            session_type in prop::sample::select(vec![
+                FbcSessionType::Unknown,
+                FbcSessionType::ToSys,
+                FbcSessionType::Cuda,
+                FbcSessionType::Vid,
+                FbcSessionType::HwEnc,
+            ]),
+            session_flags: u32,
+            resolutions: (u32, u32, u32, u32),
+            stats: (u32, u32),
+        ) {
+            let info = FbcSessionInfo {
+                session_id: ids.0,
+                pid: ids.1,
+                vgpu_instance,
+                display_ordinal: ids.2,
+                session_type,
+                session_flags: FbcFlags::from_bits_truncate(session_flags),
+                hres_max: resolutions.0,
+                vres_max: resolutions.1,
+                hres: resolutions.2,
+                vres: resolutions.3,
+                average_fps: stats.0,
+                average_latency: stats.1,
+            };
+
+            prop_assert_eq!(FbcSessionInfo::try_from(info.clone().into_c()).unwrap(), info);
+        }
+
+        #[test]
+        fn device_attributes_round_trip(
+            counts: (u32, u32, u32, u32, u32, u32, u32, u32),
+            memory_size_mb: u64,
+        ) {
+            let attributes = DeviceAttributes {
+                multiprocessor_count: counts.0,
+                shared_copy_engine_count: counts.1,
+                shared_decoder_count: counts.2,
+                shared_encoder_count: counts.3,
+                shared_jpeg_count: counts.4,
+                shared_ofa_count: counts.5,
+                gpu_instance_slice_count: counts.6,
+                compute_instance_slice_count: counts.7,
+                memory_size_mb,
+            };
+
+            prop_assert_eq!(DeviceAttributes::from(attributes.clone().into_c()), attributes);
+        }
+
+        #[test]
+        fn fan_speed_info_round_trip(version: u32, fan: u32, speed: u32) {
+            let info = FanSpeedInfo { version, fan, speed };
+
+            prop_assert_eq!(FanSpeedInfo::from(info.clone().into_c()), info);
+        }
+
+        #[test]
+        fn clock_offset_round_trip(
+            version: u32,
+            clock_type in prop::sample::select(vec![
+                Clock::Graphics,
+                Clock::SM,
+                Clock::Memory,
+                Clock::Video,
+            ]),
+            state in (0..16u32).prop_map(|p| PerformanceState::try_from(p).unwrap()),
+            offsets: (i32, i32, i32),
+        ) {
+            let offset = ClockOffset {
+                version,
+                clock_type,
+                state,
+                clock_offset_mhz: offsets.0,
+                min_clock_offset_mhz: offsets.1,
+                max_clock_offset_mhz: offsets.2,
+            };
+
+            prop_assert_eq!(ClockOffset::try_from(offset.clone().into_c()).unwrap(), offset);
+        }
+
+        #[test]
+        fn profile_info_round_trip(
+            counts: (u32, u32, u32, u32, u32),
+            is_p2p_supported: bool,
+            more_counts: (u32, u32, u32, u32),
+            memory_size_mb: u64,
+        ) {
+            let info = ProfileInfo {
+                copy_engine_count: counts.0,
+                decoder_count: counts.1,
+                encoder_count: counts.2,
+                id: counts.3,
+                instance_count: counts.4,
+                is_p2p_supported,
+                jpeg_count: more_counts.0,
+                memory_size_mb,
+                multiprocessor_count: more_counts.1,
+                ofa_count: more_counts.2,
+                slice_count: more_counts.3,
+            };
+
+            prop_assert_eq!(ProfileInfo::from(info.clone().into_c()), info);
+        }
+
+        #[test]
+        fn gpu_instance_profile_info_round_trip(
+            counts in prop::array::uniform9(any::<u32>()),
+            is_p2p_supported: bool,
+            memory_size_mb: u64,
+            name in "[0-9a-zA-Z. ]{0,95}",
+        ) {
+            let info = GpuInstanceProfileInfo {
+                id: counts[0],
+                name,
+                is_p2p_supported,
+                slice_count: counts[1],
+                instance_count: counts[2],
+                multiprocessor_count: counts[3],
+                copy_engine_count: counts[4],
+                decoder_count: counts[5],
+                encoder_count: counts[6],
+                jpeg_count: counts[7],
+                ofa_count: counts[8],
+                memory_size_mb,
+            };
+            let c = info.clone().try_into_c().unwrap();
+
+            prop_assert_eq!(GpuInstanceProfileInfo::try_from(c).unwrap(), info);
+        }
+
+        #[test]
+        fn gpu_instance_placement_round_trip(size: u32, start: u32) {
+            let placement = GpuInstancePlacement { size, start };
+
+            prop_assert_eq!(GpuInstancePlacement::from(placement.clone().into_c()), placement);
+        }
+
+        #[test]
+        fn compute_instance_profile_info_round_trip(
+            counts in prop::array::uniform9(any::<u32>()),
+            name in "[0-9a-z.]{0,95}",
+        ) {
+            let info = ComputeInstanceProfileInfo {
+                id: counts[0],
+                name,
+                slice_count: counts[1],
+                instance_count: counts[2],
+                multiprocessor_count: counts[3],
+                shared_copy_engine_count: counts[4],
+                shared_decoder_count: counts[5],
+                shared_encoder_count: counts[6],
+                shared_jpeg_count: counts[7],
+                shared_ofa_count: counts[8],
+            };
+            let c = info.clone().try_into_c().unwrap();
+
+            prop_assert_eq!(ComputeInstanceProfileInfo::try_from(c).unwrap(), info);
+        }
+
+        #[test]
+        fn compute_instance_placement_round_trip(size: u32, start: u32) {
+            let placement = ComputeInstancePlacement { size, start };
+
+            prop_assert_eq!(ComputeInstancePlacement::from(placement.clone().into_c()), placement);
+        }
+
+        #[test]
+        fn vgpu_metadata_round_trip(
+            version: u32,
+            revision: u32,
+            initialized: bool,
+            guest_driver_version in "[0-9.]{0,20}",
+            host_driver_version in "[0-9.]{0,20}",
+            caps: u32,
+            guest_vgpu_version: u32,
+            opaque_data in prop::collection::vec(any::<u8>(), 0..64),
+        ) {
+            let metadata = VgpuMetadata {
+                version,
+                revision,
+                guest_info_state: if initialized {
+                    VgpuGuestInfoState::Initialized
+                } else {
+                    VgpuGuestInfoState::Uninitialized
+                },
+                guest_driver_version,
+                host_driver_version,
+                virtualization_caps: VgpuVirtualizationCaps::from_bits_retain(caps),
+                guest_vgpu_version,
+                opaque_data,
+            };
+
+            let buffer = metadata.try_into_c_buffer().unwrap();
+            prop_assert_eq!(VgpuMetadata::from_c_buffer(&buffer).unwrap(), metadata);
+        }
+
+        #[test]
+        fn vgpu_pgpu_metadata_round_trip(
+            version: u32,
+            revision: u32,
+            host_driver_version in "[0-9.]{0,20}",
+            caps: u32,
+            min: u32,
+            max: u32,
+            opaque_data in prop::collection::vec(any::<u8>(), 0..64),
+        ) {
+            let metadata = VgpuPgpuMetadata {
+                version,
+                revision,
+                host_driver_version,
+                virtualization_caps: VgpuVirtualizationCaps::from_bits_retain(caps),
+                host_supported_vgpu_range: VgpuVersion { min, max },
+                opaque_data,
+            };
+
+            let buffer = metadata.try_into_c_buffer().unwrap();
+            prop_assert_eq!(VgpuPgpuMetadata::from_c_buffer(&buffer).unwrap(), metadata);
+        }
+
+        #[test]
+        fn vgpu_pgpu_compatibility_round_trip(vm: u8, limit: u8) {
+            let compatibility = VgpuPgpuCompatibility {
+                vm_compatibility: VgpuVmCompatibility::from_bits_truncate(vm as u32),
+                limit: VgpuCompatibilityLimit::from_bits_truncate(limit as u32),
+            };
+
+            prop_assert_eq!(
+                VgpuPgpuCompatibility::try_from(compatibility.clone().into_c()).unwrap(),
+                compatibility
+            );
+        }
+
+        #[test]
+        fn vgpu_instance_utilization_sample_round_trip(
+            vgpu_instance: u32,
+            timestamp: u64,
+            utils in sample_values(4),
+        ) {
+            let sample = VgpuInstanceUtilizationSample {
+                vgpu_instance,
+                timestamp,
+                sm_util: utils[0].clone(),
+                mem_util: utils[1].clone(),
+                enc_util: utils[2].clone(),
+                dec_util: utils[3].clone(),
+            };
+            let tag = sample.sm_util.value_type();
+
+            prop_assert_eq!(
+                VgpuInstanceUtilizationSample::from_tag_and_struct(&tag, sample.clone().into_c()),
+                sample
+            );
+        }
+
+        #[test]
+        fn vgpu_instance_utilization_info_round_trip(
+            vgpu_instance: u32,
+            timestamp: u64,
+            utils in sample_values(6),
+        ) {
+            let info = VgpuInstanceUtilizationInfo {
+                vgpu_instance,
+                timestamp,
+                sm_util: utils[0].clone(),
+                mem_util: utils[1].clone(),
+                enc_util: utils[2].clone(),
+                dec_util: utils[3].clone(),
+                jpg_util: utils[4].clone(),
+                ofa_util: utils[5].clone(),
+            };
+            let tag = info.sm_util.value_type();
+
+            prop_assert_eq!(
+                VgpuInstanceUtilizationInfo::from_tag_and_struct(&tag, info.clone().into_c()),
+                info
+            );
+        }
+
+        #[test]
+        fn vgpu_process_utilization_sample_round_trip(
+            vgpu_instance: u32,
+            pid: u32,
+            process_name in "[0-9a-zA-Z._/-]{0,63}",
+            timestamp: u64,
+            utils: (u32, u32, u32, u32),
+        ) {
+            let sample = VgpuProcessUtilizationSample {
+                vgpu_instance,
+                pid,
+                process_name,
+                timestamp,
+                sm_util: utils.0,
+                mem_util: utils.1,
+                enc_util: utils.2,
+                dec_util: utils.3,
+            };
+
+            prop_assert_eq!(
+                VgpuProcessUtilizationSample::try_from(sample.clone().try_into_c().unwrap())
+                    .unwrap(),
+                sample
+            );
+        }
+
+        #[test]
+        fn vgpu_process_utilization_info_round_trip(
+            vgpu_instance: u32,
+            pid: u32,
+            process_name in "[0-9a-zA-Z._/-]{0,63}",
+            timestamp: u64,
+            utils: (u32, u32, u32, u32, u32, u32),
+        ) {
+            let info = VgpuProcessUtilizationInfo {
+                vgpu_instance,
+                pid,
+                process_name,
+                timestamp,
+                sm_util: utils.0,
+                mem_util: utils.1,
+                enc_util: utils.2,
+                dec_util: utils.3,
+                jpg_util: utils.4,
+                ofa_util: utils.5,
+            };
+
+            prop_assert_eq!(
+                VgpuProcessUtilizationInfo::try_from(info.clone().try_into_c().unwrap()).unwrap(),
+                info
+            );
+        }
+
+        #[test]
+        fn vgpu_license_info_round_trip(
+            is_licensed: bool,
+            year: u32,
+            date in prop::array::uniform5(any::<u16>()),
+            status: u8,
+            current_state: u32,
+        ) {
+            let info = VgpuLicenseInfo {
+                is_licensed,
+                expiry: VgpuLicenseExpiry {
+                    year,
+                    month: date[0],
+                    day: date[1],
+                    hour: date[2],
+                    min: date[3],
+                    sec: date[4],
+                    status,
+                },
+                current_state,
+            };
+
+            prop_assert_eq!(VgpuLicenseInfo::from(info.clone().into_c()), info);
+        }
+
+        #[test]
+        fn vgpu_scheduler_capabilities_round_trip(
+            is_arr_mode_supported: bool,
+            limits: (u32, u32, u32, u32, u32, u32),
+            supported_schedulers in prop::collection::vec(
+                vgpu_scheduler_policy().prop_filter("padding", |p| {
+                    *p != VgpuSchedulerPolicy::Unknown
+                }),
+                0..=NVML_SUPPORTED_VGPU_SCHEDULER_POLICY_COUNT as usize,
+            ),
+        ) {
+            let capabilities = VgpuSchedulerCapabilities {
+                is_arr_mode_supported,
+                max_avg_factor_for_arr: limits.0,
+                max_freq_for_arr: limits.1,
+                max_time_slice: limits.2,
+                min_avg_factor_for_arr: limits.3,
+                min_freq_for_arr: limits.4,
+                min_time_slice: limits.5,
+                supported_schedulers,
+            };
+            let c = capabilities.clone().try_into_c().unwrap();
+
+            prop_assert_eq!(VgpuSchedulerCapabilities::try_from(c).unwrap(), capabilities);
+        }
+
+        #[test]
+        fn vgpu_scheduler_log_entry_round_trip(entry in vgpu_scheduler_log_entry()) {
+            prop_assert_eq!(VgpuSchedulerLogEntry::from(entry.clone().into_c()), entry);
+        }
+
+        #[test]
+        fn vgpu_scheduler_log_round_trip(
+            engine_id: u32,
+            scheduler_policy in vgpu_scheduler_policy(),
+            arr_mode in vgpu_scheduler_arr_mode(),
+            avg_factor: u32,
+            timeslice: u32,
+            entries in prop::collection::vec(
+                vgpu_scheduler_log_entry(),
+                0..=NVML_SCHEDULER_SW_MAX_LOG_ENTRIES as usize,
+            ),
+        ) {
+            let log = VgpuSchedulerLog {
+                engine_id,
+                scheduler_policy,
+                arr_mode,
+                // The averaging factor is only there in ARR mode
+                scheduler_params: VgpuSchedulerParams {
+                    avg_factor: Some(avg_factor)
+                        .filter(|_| arr_mode == VgpuSchedulerArrMode::Enabled),
+                    timeslice,
+                },
+                entries_count: entries.len() as u32,
+                entries,
+            };
+            let c = log.clone().try_into_c().unwrap();
+
+            prop_assert_eq!(VgpuSchedulerLog::try_from(c).unwrap(), log);
+        }
+
+        #[test]
+        fn vgpu_scheduler_get_state_round_trip(
+            arr_mode in vgpu_scheduler_arr_mode(),
+            scheduler_policy in vgpu_scheduler_policy(),
+            avg_factor: u32,
+            timeslice: u32,
+        ) {
+            let state = VgpuSchedulerGetState {
+                arr_mode,
+                scheduler_policy,
+                scheduler_params: VgpuSchedulerParams {
+                    avg_factor: Some(avg_factor)
+                        .filter(|_| arr_mode == VgpuSchedulerArrMode::Enabled),
+                    timeslice,
+                },
+            };
+
+            prop_assert_eq!(VgpuSchedulerGetState::try_from(state.clone().into_c()).unwrap(), state);
+        }
+    }
+
+    #[test]
+    fn vgpu_scheduler_state_is_validated() {
+        let capabilities = scheduler_capabilities();
+        let state = |policy, params| VgpuSchedulerSetState { policy, params };
+
+        capabilities
+            .validate(&state(
+                VgpuSchedulerPolicy::EqualShare,
+                VgpuSchedulerSetParams::Timeslice(2_000_000),
+            ))
+            .unwrap();
+        capabilities
+            .validate(&state(
+                VgpuSchedulerPolicy::BestEffort,
+                VgpuSchedulerSetParams::Arr {
+                    avg_factor: 33,
+                    frequency: 64,
+                },
+            ))
+            .unwrap();
+
+        for rejected in [
+            state(
+                VgpuSchedulerPolicy::FixedShare,
+                VgpuSchedulerSetParams::Timeslice(2_000_000),
+            ),
+            state(
+                VgpuSchedulerPolicy::EqualShare,
+                VgpuSchedulerSetParams::Timeslice(500),
+            ),
+            state(
+                VgpuSchedulerPolicy::BestEffort,
+                VgpuSchedulerSetParams::Arr {
+                    avg_factor: 33,
+                    frequency: 1000,
+                },
+            ),
+            // ARR only applies to best effort
+            state(
+                VgpuSchedulerPolicy::EqualShare,
+                VgpuSchedulerSetParams::Arr {
+                    avg_factor: 33,
+                    frequency: 64,
+                },
+            ),
+        ] {
+            assert!(matches!(
+                capabilities.validate(&rejected),
+                Err(NvmlError::UnsupportedVgpuSchedulerState(_))
+            ));
+        }
+
+        let no_arr = VgpuSchedulerCapabilities {
+            is_arr_mode_supported: false,
+            ..capabilities
+        };
+        assert!(matches!(
+            no_arr.validate(&state(
+                VgpuSchedulerPolicy::BestEffort,
+                VgpuSchedulerSetParams::Arr {
+                    avg_factor: 33,
+                    frequency: 64,
+                },
+            )),
+            Err(NvmlError::UnsupportedVgpuSchedulerState(_))
+        ));
+    }
+
+    #[test]
+    fn vgpu_scheduler_set_state_enables_arr_as_a_boolean() {
+        let state = |params| {
+            VgpuSchedulerSetState {
+                policy: VgpuSchedulerPolicy::BestEffort,
+                params,
+            }
+            .as_c()
+            .enableARRMode
+        };
+
+        assert_eq!(state(VgpuSchedulerSetParams::Timeslice(2_000_000)), 0);
+        assert_eq!(
+            state(VgpuSchedulerSetParams::Arr {
+                avg_factor: 33,
+                frequency: 64,
+            }),
+            1
+        );
+    }
+
+    #[test]
+    fn vgpu_scheduler_log_keeps_only_fetched_entries() {
+        let mut c: nvmlVgpuSchedulerLog_t = unsafe { mem::zeroed() };
+        c.schedulerPolicy = NVML_VGPU_SCHEDULER_POLICY_BEST_EFFORT;
+        c.arrMode = NVML_VGPU_SCHEDULER_ARR_DISABLE;
+        c.entriesCount = 2;
+        c.logEntries[1].timeRun = 1_500_000;
+
+        let log = VgpuSchedulerLog::try_from(c).unwrap();
+        assert_eq!(log.scheduler_policy, VgpuSchedulerPolicy::BestEffort);
+        assert_eq!(log.entries.len(), 2);
+        assert_eq!(log.entries[1].time_run, Duration::from_micros(1500));
+    }
+
+    #[test]
+    fn arrays_that_do_not_fit_are_rejected() {
+        let hierarchy = BridgeChipHierarchy {
+            chips_hierarchy: vec![
+                BridgeChipInfo {
+                    fw_version: FirmwareVersion::Unavailable,
+                    chip_type: BridgeChip::PLX,
+                };
+                NVML_MAX_PHYSICAL_BRIDGE as usize + 1
+            ],
+            chip_count: 0,
+        };
+
+        assert!(matches!(hierarchy.try_into_c(), Err(NvmlError::InvalidArg)));
+    }
+
     #[test]
     fn pci_info_from_to_c() {
         let nvml = nvml();
@@ -1052,11 +3758,13 @@ mod tests {
                 .try_into()
                 .expect("converted c pci info");
 
-            let sym = nvml_sym(nvml.lib.nvmlDeviceGetPciInfo_v3.as_ref())?;
-
             let raw = unsafe {
                 let mut pci_info: nvmlPciInfo_t = mem::zeroed();
-                nvml_try(sym(device.handle(), &mut pci_info)).expect("raw pci info");
+                nvml_try(
+                    nvml.backend
+                        .nvmlDeviceGetPciInfo_v3(device.handle(), &mut pci_info)?,
+                )
+                .expect("raw pci info");
                 pci_info
             };
 
@@ -58,4 +58,28 @@ impl<'nvml> EventData<'nvml> {
             },
         }
     }
+
+    /**
+    Convert this `EventData` back into its C counterpart.
+
+    The GPU and compute instance IDs, which this wrapper doesn't expose, are
+    set to `0xFFFFFFFF` (not applicable).
+
+    # Safety
+
+    The result contains the raw device handle; see `Device.handle()`.
+    */
+    pub unsafe fn into_c(self) -> nvmlEventData_t {
+        nvmlEventData_t {
+            device: self.device.handle(),
+            eventType: self.event_type.bits(),
+            eventData: match self.event_data {
+                Some(XidError::Value(v)) => v,
+                Some(XidError::Unknown) => 999,
+                None => 0,
+            },
+            gpuInstanceId: 0xFFFFFFFF,
+            computeInstanceId: 0xFFFFFFFF,
+        }
+    }
 }
@@ -9,7 +9,12 @@ use crate::error::NvmlError;
 use crate::ffi::bindings::*;
 #[cfg(feature = "serde")]
 use serde_derive::{Deserialize, Serialize};
-use std::{convert::TryFrom, ffi::CStr};
+use std::{
+    convert::TryFrom,
+    ffi::{CStr, CString},
+    mem,
+    os::raw::c_char,
+};
 
 /// Information about an excluded device.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -19,6 +24,23 @@ pub struct ExcludedDeviceInfo {
     uuid: String,
 }
 
+impl ExcludedDeviceInfo {
+    /**
+    Convert this `ExcludedDeviceInfo` back into its C equivalent.
+
+    # Errors
+
+    * `NulError`, if a nul byte was found in a string
+    * `StringTooLong`, if a string is too long for its C buffer
+    */
+    pub fn try_into_c(self) -> Result<nvmlExcludedDeviceInfo_t, NvmlError> {
+        Ok(nvmlExcludedDeviceInfo_t {
+            pciInfo: self.pci_info.try_into_c()?,
+            uuid: c_string(self.uuid)?,
+        })
+    }
+}
+
 impl TryFrom<nvmlExcludedDeviceInfo_t> for ExcludedDeviceInfo {
     type Error = NvmlError;
 
@@ -40,3 +62,128 @@ impl TryFrom<nvmlExcludedDeviceInfo_t> for ExcludedDeviceInfo {
         }
     }
 }
+
+/**
+Copy `string` into a nul-terminated C string buffer of `N` bytes.
+
+# Errors
+
+* `NulError`, if a nul byte was found in `string`
+* `StringTooLong`, if `string` and its nul terminator don't fit in `N` bytes
+*/
+pub(crate) fn c_string<const N: usize>(string: String) -> Result<[c_char; N], NvmlError> {
+    let bytes = CString::new(string)?.into_bytes_with_nul();
+
+    if bytes.len() > N {
+        return Err(NvmlError::StringTooLong {
+            max_len: N,
+            actual_len: bytes.len(),
+        });
+    }
+
+    let mut buffer = [0; N];
+    for (c, b) in buffer.iter_mut().zip(bytes) {
+        *c = b as c_char;
+    }
+
+    Ok(buffer)
+}
+
+/**
+Copy `items` into a C array of `N` elements, zeroing the elements past the end
+of `items`.
+
+`T` must be a C type that is valid when zeroed.
+
+# Errors
+
+* `InvalidArg`, if there are more than `N` items
+*/
+pub(crate) fn c_array<T: Copy, const N: usize>(
+    items: impl IntoIterator<Item = T>,
+) -> Result<[T; N], NvmlError> {
+    // SAFETY: only used with C types, which are valid when zeroed
+    let mut array: [T; N] = unsafe { mem::zeroed() };
+    let mut items = items.into_iter();
+
+    for (slot, item) in array.iter_mut().zip(&mut items) {
+        *slot = item;
+    }
+
+    match items.next() {
+        Some(_) => Err(NvmlError::InvalidArg),
+        None => Ok(array),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// `PciInfo`s as NVML returns them.
+    pub(crate) fn pci_info() -> impl Strategy<Value = PciInfo> {
+        (
+            0..=0xffu32,
+            0..32u32,
+            0..=0xffffu32,
+            any::<u32>(),
+            prop::option::of(any::<u32>()),
+        )
+            .prop_map(
+                |(bus, device, domain, pci_device_id, pci_sub_system_id)| PciInfo {
+                    bus,
+                    bus_id: format!("{:08X}:{:02X}:{:02X}.0", domain, bus, device),
+                    device,
+                    domain,
+                    pci_device_id,
+                    pci_sub_system_id,
+                },
+            )
+    }
+
+    #[test]
+    fn c_string_fits_exactly() {
+        let buffer: [c_char; 4] = c_string("abc".into()).unwrap();
+        assert_eq!(buffer, [b'a' as c_char, b'b' as c_char, b'c' as c_char, 0]);
+
+        assert!(matches!(
+            c_string::<3>("abc".into()),
+            Err(NvmlError::StringTooLong {
+                max_len: 3,
+                actual_len: 4
+            })
+        ));
+        assert!(matches!(
+            c_string::<8>("a\0c".into()),
+            Err(NvmlError::NulError(_))
+        ));
+    }
+
+    #[test]
+    fn c_array_rejects_overflow() {
+        assert_eq!(c_array::<u32, 3>([1, 2]).unwrap(), [1, 2, 0]);
+        assert!(matches!(
+            c_array::<u32, 2>([1, 2, 3]),
+            Err(NvmlError::InvalidArg)
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn excluded_device_info_round_trip(
+            pci_info in pci_info(),
+            uuid in "GPU-[0-9a-f-]{0,70}",
+        ) {
+            // Always present for excluded devices
+            let pci_info = PciInfo {
+                pci_sub_system_id: pci_info.pci_sub_system_id.or(Some(0)),
+                ..pci_info
+            };
+            let info = ExcludedDeviceInfo { pci_info, uuid };
+            let c = info.clone().try_into_c().unwrap();
+
+            prop_assert_eq!(ExcludedDeviceInfo::try_from(c).unwrap(), info);
+        }
+    }
+}
@@ -7,7 +7,6 @@ use serde_derive::{Deserialize, Serialize};
 use std::convert::TryFrom;
 
 /// Defines NvLink counter controls.
-// TODO: Write a test going to / from C repr
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct UtilizationControl {
@@ -48,3 +47,28 @@ impl TryFrom<nvmlNvLinkUtilizationControl_t> for UtilizationControl {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn utilization_control_round_trip(
+            units in prop::sample::select(vec![
+                UtilizationCountUnit::Cycles,
+                UtilizationCountUnit::Packets,
+                UtilizationCountUnit::Bytes,
+            ]),
+            packet_filter: u32,
+        ) {
+            let control = UtilizationControl {
+                units,
+                packet_filter: PacketTypes::from_bits_truncate(packet_filter),
+            };
+
+            prop_assert_eq!(UtilizationControl::try_from(control.as_c()).unwrap(), control);
+        }
+    }
+}
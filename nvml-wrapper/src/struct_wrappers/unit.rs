@@ -1,6 +1,7 @@
 use crate::enum_wrappers::unit::FanState;
 use crate::error::NvmlError;
 use crate::ffi::bindings::*;
+use crate::struct_wrappers::{c_array, c_string};
 #[cfg(feature = "serde")]
 use serde_derive::{Deserialize, Serialize};
 use std::{convert::TryFrom, ffi::CStr};
@@ -40,6 +41,24 @@ impl TryFrom<nvmlUnitFanSpeeds_t> for FansInfo {
     }
 }
 
+impl FansInfo {
+    /**
+    Convert this `FansInfo` back into its C counterpart.
+
+    Entries of the C array past the end of `fans` are zeroed.
+
+    # Errors
+
+    * `InvalidArg`, if `fans` has more entries than the C array can hold
+    */
+    pub fn try_into_c(self) -> Result<nvmlUnitFanSpeeds_t, NvmlError> {
+        Ok(nvmlUnitFanSpeeds_t {
+            fans: c_array(self.fans.into_iter().map(|f| f.into_c()))?,
+            count: self.count,
+        })
+    }
+}
+
 /// Fan info reading for a single fan in an S-class unit.
 // Checked against local
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -69,6 +88,16 @@ impl TryFrom<nvmlUnitFanInfo_t> for FanInfo {
     }
 }
 
+impl FanInfo {
+    /// Convert this `FanInfo` back into its C counterpart.
+    pub fn into_c(self) -> nvmlUnitFanInfo_t {
+        nvmlUnitFanInfo_t {
+            speed: self.speed,
+            state: self.state.as_c(),
+        }
+    }
+}
+
 /**
 Power usage information for an S-class unit.
 
@@ -123,6 +152,25 @@ impl TryFrom<nvmlPSUInfo_t> for PsuInfo {
     }
 }
 
+impl PsuInfo {
+    /**
+    Convert this `PsuInfo` back into its C counterpart.
+
+    # Errors
+
+    * `NulError`, if a nul byte was found in a string
+    * `StringTooLong`, if a string is too long for its C buffer
+    */
+    pub fn try_into_c(self) -> Result<nvmlPSUInfo_t, NvmlError> {
+        Ok(nvmlPSUInfo_t {
+            state: c_string(self.state)?,
+            current: self.current,
+            voltage: self.voltage,
+            power: self.power_draw,
+        })
+    }
+}
+
 /// Static S-class unit info.
 // Checked against local
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -163,6 +211,25 @@ impl TryFrom<nvmlUnitInfo_t> for UnitInfo {
     }
 }
 
+impl UnitInfo {
+    /**
+    Convert this `UnitInfo` back into its C counterpart.
+
+    # Errors
+
+    * `NulError`, if a nul byte was found in a string
+    * `StringTooLong`, if a string is too long for its C buffer
+    */
+    pub fn try_into_c(self) -> Result<nvmlUnitInfo_t, NvmlError> {
+        Ok(nvmlUnitInfo_t {
+            name: c_string(self.name)?,
+            id: c_string(self.id)?,
+            serial: c_string(self.serial)?,
+            firmwareVersion: c_string(self.firmware_version)?,
+        })
+    }
+}
+
 /// Description of an HWBC entry.
 // Checked against local
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -192,3 +259,97 @@ impl TryFrom<nvmlHwbcEntry_t> for HwbcEntry {
         }
     }
 }
+
+impl HwbcEntry {
+    /**
+    Convert this `HwbcEntry` back into its C counterpart.
+
+    # Errors
+
+    * `NulError`, if a nul byte was found in a string
+    * `StringTooLong`, if a string is too long for its C buffer
+    */
+    pub fn try_into_c(self) -> Result<nvmlHwbcEntry_t, NvmlError> {
+        Ok(nvmlHwbcEntry_t {
+            hwbcId: self.id,
+            firmwareVersion: c_string(self.firmware_version)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn fan_info() -> impl Strategy<Value = FanInfo> {
+        (
+            any::<u32>(),
+            prop::sample::select(vec![FanState::Normal, FanState::Failed]),
+        )
+            .prop_map(|(speed, state)| FanInfo { speed, state })
+    }
+
+    proptest! {
+        #[test]
+        fn fans_info_round_trip(
+            count: u32,
+            fans in prop::collection::vec(fan_info(), 24),
+        ) {
+            let info = FansInfo { count, fans };
+            let c = info.clone().try_into_c().unwrap();
+
+            prop_assert_eq!(FansInfo::try_from(c).unwrap(), info);
+        }
+
+        #[test]
+        fn psu_info_round_trip(
+            current: u32,
+            power_draw: u32,
+            state in "[ -~]{0,255}",
+            voltage: u32,
+        ) {
+            let info = PsuInfo { current, power_draw, state, voltage };
+            let c = info.clone().try_into_c().unwrap();
+
+            prop_assert_eq!(PsuInfo::try_from(c).unwrap(), info);
+        }
+
+        #[test]
+        fn unit_info_round_trip(
+            firmware_version in "[ -~]{0,95}",
+            id in "[ -~]{0,95}",
+            name in "[ -~]{0,95}",
+            serial in "[ -~]{0,95}",
+        ) {
+            let info = UnitInfo { firmware_version, id, name, serial };
+            let c = info.clone().try_into_c().unwrap();
+
+            prop_assert_eq!(UnitInfo::try_from(c).unwrap(), info);
+        }
+
+        #[test]
+        fn hwbc_entry_round_trip(id: u32, firmware_version in "[ -~]{0,31}") {
+            let entry = HwbcEntry { id, firmware_version };
+            let c = entry.clone().try_into_c().unwrap();
+
+            prop_assert_eq!(HwbcEntry::try_from(c).unwrap(), entry);
+        }
+    }
+
+    #[test]
+    fn strings_that_do_not_fit_are_rejected() {
+        let entry = HwbcEntry {
+            id: 0,
+            firmware_version: "x".repeat(32),
+        };
+
+        assert!(matches!(
+            entry.try_into_c(),
+            Err(NvmlError::StringTooLong {
+                max_len: 32,
+                actual_len: 33
+            })
+        ));
+    }
+}
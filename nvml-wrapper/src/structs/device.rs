@@ -1,8 +1,9 @@
-use crate::bitmasks::device::PowerMizerModes;
+use crate::bitmasks::device::{PowerMizerModes, ProcessTypes};
 #[cfg(target_os = "windows")]
 use crate::enum_wrappers::device::DriverModel;
-use crate::enum_wrappers::device::OperationMode;
-use crate::enums::device::PowerMizerMode;
+use crate::enum_wrappers::device::{OperationMode, VgpuTypeCapability};
+use crate::enums::device::{PowerMizerMode, UsedGpuMemory};
+use crate::struct_wrappers::device::ProcessUtilizationInfo;
 #[cfg(feature = "serde")]
 use serde_derive::{Deserialize, Serialize};
 
@@ -85,8 +86,8 @@ pub struct AutoBoostClocksEnabledInfo {
     pub is_enabled_default: bool,
 }
 
-/// Returned from `Device.decoder_utilization()` and
-/// `Device.encoder_utilization()`.
+/// Returned from `Device.decoder_utilization()`, `Device.encoder_utilization()`,
+/// `Device.jpeg_utilization()` and `Device.ofa_utilization()`.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct UtilizationInfo {
@@ -95,6 +96,28 @@ pub struct UtilizationInfo {
     pub sampling_period: u32,
 }
 
+/// Returned from `Device.engine_utilization()`.
+///
+/// Engines the device does not have are `None`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EngineUtilization {
+    /// Percent of time over the past sample period during which one or more
+    /// kernels was executing on the SMs.
+    pub gpu: u32,
+    /// Percent of time over the past sample period during which global (device)
+    /// memory was being read or written to.
+    pub memory: u32,
+    /// Video encoder (NVENC).
+    pub encoder: Option<UtilizationInfo>,
+    /// Video decoder (NVDEC).
+    pub decoder: Option<UtilizationInfo>,
+    /// JPEG decoder (NVJPG).
+    pub jpeg: Option<UtilizationInfo>,
+    /// Optical flow accelerator (OFA).
+    pub ofa: Option<UtilizationInfo>,
+}
+
 /// Returned from `Device.driver_model()`
 #[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -171,6 +194,40 @@ pub struct RetiredPage {
     pub timestamp: u64,
 }
 
+/// Returned from `Device.remapped_rows()`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RemappedRows {
+    /// Rows remapped due to correctable errors.
+    pub correctable: u32,
+    /// Rows remapped due to uncorrectable errors.
+    pub uncorrectable: u32,
+    /// Whether a remapping is pending; it takes effect when the GPU is reset.
+    pub is_pending: bool,
+    /// Whether a remapping has failed in the past.
+    pub failure_occurred: bool,
+}
+
+/// Returned from `Device.gpu_processes()`: everything known about a process
+/// running on a `Device`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GpuProcess {
+    /// Process ID.
+    pub pid: u32,
+    /// The kinds of context the process has on the `Device`.
+    pub types: ProcessTypes,
+    /// Amount of used GPU memory in bytes.
+    pub used_gpu_memory: UsedGpuMemory,
+    /// The ID of the GPU instance the process is running on, if MIG is enabled.
+    pub gpu_instance_id: Option<u32>,
+    /// The ID of the compute instance the process is running on, if MIG is
+    /// enabled.
+    pub compute_instance_id: Option<u32>,
+    /// The process's latest utilization sample, if it has one.
+    pub utilization: Option<ProcessUtilizationInfo>,
+}
+
 /// Populate this newtype with the constants `nvml_wrapper::sys_exports::field_id::*`.
 ///
 /// Used in `FieldValue` and `Device.field_values_for()`.
@@ -188,6 +245,72 @@ pub struct MigMode {
     pub pending: u32,
 }
 
+/// A MIG device and what it is a partition of, returned from
+/// `Nvml.mig_devices()`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MigDeviceInfo {
+    /// The MIG device's UUID, e.g. `MIG-41ac4ec6-5c4f-5b40-8fd6-8c8b7a3e1f5a`.
+    pub uuid: String,
+    /// Its index on the parent device, for `Device.mig_device_by_index()`.
+    pub index: u32,
+    pub parent_uuid: String,
+    /// The parent device's index, for `Nvml.device_by_index()`.
+    pub parent_index: u32,
+    pub gpu_instance_id: u32,
+    pub compute_instance_id: u32,
+    /// The name of its GPU instance's profile, e.g. `1g.10gb`, or `None` if
+    /// it couldn't be looked up; see `Nvml.mig_devices()`.
+    pub profile: Option<String>,
+    /// Total memory, in bytes.
+    pub memory_size: u64,
+}
+
+/// Returned from `VgpuType.supported_placements()` and
+/// `VgpuType.creatable_placements()`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuPlacementList {
+    /// How many placement slots a vGPU of the type takes up.
+    pub placement_size: u32,
+    /// The placement IDs (first slots) a vGPU of the type can be placed at.
+    pub placement_ids: Vec<u32>,
+}
+
+/// A summary of a vGPU type's properties, returned from `VgpuType.info()`.
+///
+/// The properties that older drivers or non-MIG types don't report are `None`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuTypeInfo {
+    pub id: u32,
+    /// The name, e.g. `GRID A100-4C`.
+    pub name: String,
+    pub class_name: String,
+    pub license: String,
+    /// The framebuffer size, in bytes.
+    pub framebuffer_size: u64,
+    /// The framebuffer reserved on the physical GPU per vGPU, in bytes.
+    pub fb_reservation: Option<u64>,
+    /// The GSP heap size per vGPU, in bytes.
+    pub gsp_heap_size: Option<u64>,
+    /// The BAR1 size, in bytes.
+    pub bar1_size: Option<u64>,
+    /// How many vGPUs of this type the device can host.
+    pub max_instances: u32,
+    pub max_instances_per_vm: u32,
+    /// How many vGPUs of this type a GPU instance can host, for MIG-backed
+    /// types.
+    pub max_instances_per_gpu_instance: Option<u32>,
+    /// The GPU instance profile ID, for MIG-backed types.
+    pub gpu_instance_profile_id: Option<u32>,
+    pub num_display_heads: u32,
+    /// The frame rate limit, if the frame rate limiter is on.
+    pub frame_rate_limit: Option<u32>,
+    /// The capabilities the type has.
+    pub capabilities: Vec<VgpuTypeCapability>,
+}
+
 /// Returned from `Device.gsp_firmware_mode()`
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -4,7 +4,7 @@ use crate::NvLink;
 use crate::Nvml;
 use crate::Unit;
 
-use crate::bitmasks::{device::*, event::*};
+use crate::bitmasks::{device::*, event::*, InitFlags};
 
 use crate::enum_wrappers::device::*;
 use crate::enums::device::BusType;
@@ -15,7 +15,10 @@ use crate::enums::device::PowerSource;
 use crate::enums::unit::*;
 use crate::error::NvmlError;
 use crate::event::EventSet;
+use crate::ffi::bindings::nvmlDevice_t;
+use crate::high_level::MemoryHealth;
 use crate::struct_wrappers::gpm::GpmMetricResult;
+use std::env;
 use std::fmt::Debug;
 
 use crate::struct_wrappers::nv_link::*;
@@ -92,6 +95,7 @@ impl ShouldPrint for BAR1MemoryInfo {}
 impl ShouldPrint for BridgeChipHierarchy {}
 impl ShouldPrint for ComputeMode {}
 impl ShouldPrint for UtilizationInfo {}
+impl ShouldPrint for EngineUtilization {}
 impl ShouldPrint for EccModeState {}
 impl ShouldPrint for OperationModeState {}
 impl ShouldPrint for InfoRom {}
@@ -99,12 +103,20 @@ impl ShouldPrint for PowerMizerMode {}
 impl ShouldPrint for PowerMizerModeInfo {}
 impl ShouldPrint for PowerMizerModes {}
 impl ShouldPrint for Vec<RetiredPage> {}
+impl ShouldPrint for RemappedRows {}
+impl ShouldPrint for RowRemapperHistogram {}
+impl ShouldPrint for MemoryHealth {}
+impl ShouldPrint for ThermalSettings {}
 impl ShouldPrint for ExcludedDeviceInfo {}
 impl ShouldPrint for MemoryInfo {}
 impl ShouldPrint for PciInfo {}
 impl ShouldPrint for PerformanceState {}
 impl ShouldPrint for PowerManagementConstraints {}
 impl ShouldPrint for ThrottleReasons {}
+impl ShouldPrint for ClocksEventReasons {}
+impl ShouldPrint for Vec<ProcessDetail> {}
+impl ShouldPrint for Vec<ProcessUtilizationInfo> {}
+impl ShouldPrint for Vec<GpuProcess> {}
 impl ShouldPrint for ViolationTime {}
 impl ShouldPrint for AccountingStats {}
 impl ShouldPrint for EventTypes {}
@@ -124,16 +136,40 @@ impl ShouldPrint for (Vec<String>, u32) {}
 impl ShouldPrint for GpuVirtualizationMode {}
 impl ShouldPrint for ClockOffset {}
 impl ShouldPrint for MigMode {}
+impl ShouldPrint for Vec<MigDeviceInfo> {}
 impl ShouldPrint for Vec<GpuInstancePlacement> {}
+impl ShouldPrint for Vec<GpuInstanceInfo> {}
 impl ShouldPrint for (VgpuVersion, VgpuVersion) {}
 impl ShouldPrint for ProfileInfo {}
+impl ShouldPrint for Vec<VgpuTypeInfo> {}
+impl ShouldPrint for VgpuPgpuMetadata {}
+impl ShouldPrint for Vec<VgpuInstanceUtilizationSample> {}
+impl ShouldPrint for Vec<VgpuProcessUtilizationSample> {}
+impl ShouldPrint for Vec<VgpuInstanceUtilizationInfo> {}
+impl ShouldPrint for Vec<VgpuProcessUtilizationInfo> {}
+impl ShouldPrint for GpuInstanceProfileInfo {}
 impl ShouldPrint for GspFirmwareMode {}
 
 #[cfg(target_os = "windows")]
 impl ShouldPrint for DriverModelState {}
 
+/// Environment variable pointing the tests at an NVML library other than the
+/// system one, such as the fake built by `nvml-wrapper-fake`.
+pub const LIB_PATH_ENV_VAR: &str = "NVML_WRAPPER_TEST_LIB_PATH";
+
+pub fn nvml_with_flags(flags: InitFlags) -> Result<Nvml, NvmlError> {
+    let lib_path = env::var_os(LIB_PATH_ENV_VAR);
+    let mut builder = Nvml::builder();
+
+    if let Some(path) = &lib_path {
+        builder.lib_path(path);
+    }
+
+    builder.flags(flags).init()
+}
+
 pub fn nvml() -> Nvml {
-    Nvml::init().expect("initialized library")
+    nvml_with_flags(InitFlags::empty()).expect("initialized library")
 }
 
 pub fn device(nvml: &Nvml) -> Device<'_> {
@@ -216,3 +252,73 @@ where
         test().unwrap_or_else(|_| panic!("successful multi call #{}", i));
     }
 }
+
+/**
+The `NvmlBackend` methods every mock backend needs, for use inside its `impl`
+block so that it only has to implement the calls its tests are about.
+
+`nvmlInit_v2` and `nvmlShutdown` succeed, `nvmlDeviceGetCount_v2` reports
+`devices` GPUs (one if left out) and `nvmlDeviceGetHandleByIndex_v2` hands out
+`mock_device(index)` for each of them:
+
+```ignore
+impl NvmlBackend for Mock {
+    mock_backend!(devices = 2);
+
+    unsafe fn nvmlDeviceGetIndex(
+        &self,
+        device: nvmlDevice_t,
+        index: *mut c_uint,
+    ) -> Result<nvmlReturn_t, NvmlError> {
+        *index = device as c_uint - 1;
+        Ok(nvmlReturn_enum_NVML_SUCCESS)
+    }
+}
+```
+*/
+macro_rules! mock_backend {
+    () => {
+        $crate::test_utils::mock_backend!(devices = 1);
+    };
+    (devices = $devices:expr) => {
+        unsafe fn nvmlInit_v2(
+            &self,
+        ) -> Result<$crate::ffi::bindings::nvmlReturn_t, $crate::error::NvmlError> {
+            Ok($crate::ffi::bindings::nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlShutdown(
+            &self,
+        ) -> Result<$crate::ffi::bindings::nvmlReturn_t, $crate::error::NvmlError> {
+            Ok($crate::ffi::bindings::nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetCount_v2(
+            &self,
+            count: *mut std::os::raw::c_uint,
+        ) -> Result<$crate::ffi::bindings::nvmlReturn_t, $crate::error::NvmlError> {
+            *count = $devices;
+            Ok($crate::ffi::bindings::nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetHandleByIndex_v2(
+            &self,
+            index: std::os::raw::c_uint,
+            device: *mut $crate::ffi::bindings::nvmlDevice_t,
+        ) -> Result<$crate::ffi::bindings::nvmlReturn_t, $crate::error::NvmlError> {
+            if index >= $devices {
+                return Ok($crate::ffi::bindings::nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT);
+            }
+
+            *device = $crate::test_utils::mock_device(index);
+            Ok($crate::ffi::bindings::nvmlReturn_enum_NVML_SUCCESS)
+        }
+    };
+}
+
+pub(crate) use mock_backend;
+
+/// The handle `mock_backend!` hands out for the GPU at `index`.
+pub const fn mock_device(index: u32) -> nvmlDevice_t {
+    (index as usize + 1) as nvmlDevice_t
+}
@@ -1,7 +1,7 @@
 use crate::device::Device;
 use crate::enum_wrappers::unit::LedColor;
 use crate::enums::unit::{LedState, TemperatureReading};
-use crate::error::{nvml_sym, nvml_try, nvml_try_count, NvmlError};
+use crate::error::{nvml_try, nvml_try_count, NvmlError};
 use crate::ffi::bindings::*;
 use crate::struct_wrappers::unit::{FansInfo, PsuInfo, UnitInfo};
 use crate::Nvml;
@@ -104,8 +104,6 @@ impl<'nvml> Unit<'nvml> {
     // Tested
     #[doc(alias = "nvmlUnitGetDevices")]
     pub fn devices(&self) -> Result<Vec<Device<'_>>, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlUnitGetDevices.as_ref())?;
-
         unsafe {
             let mut count: c_uint = match self.device_count()? {
                 0 => return Ok(vec![]),
@@ -113,7 +111,11 @@ impl<'nvml> Unit<'nvml> {
             };
             let mut devices: Vec<nvmlDevice_t> = vec![mem::zeroed(); count as usize];
 
-            nvml_try(sym(self.unit, &mut count, devices.as_mut_ptr()))?;
+            nvml_try(self.nvml.backend.nvmlUnitGetDevices(
+                self.unit,
+                &mut count,
+                devices.as_mut_ptr(),
+            )?)?;
 
             Ok(devices
                 .into_iter()
@@ -143,8 +145,6 @@ impl<'nvml> Unit<'nvml> {
     // Tested as part of the above
     #[doc(alias = "nvmlUnitGetDevices")]
     pub fn device_count(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlUnitGetDevices.as_ref())?;
-
         /*
         From the docs:
         deviceCount
@@ -153,7 +153,11 @@ impl<'nvml> Unit<'nvml> {
         */
         let mut count: c_uint = 0;
         unsafe {
-            nvml_try_count(sym(self.unit, &mut count, std::ptr::null_mut()))?;
+            nvml_try_count(self.nvml.backend.nvmlUnitGetDevices(
+                self.unit,
+                &mut count,
+                std::ptr::null_mut(),
+            )?)?;
         }
         Ok(count)
     }
@@ -177,11 +181,13 @@ impl<'nvml> Unit<'nvml> {
     // Tested
     #[doc(alias = "nvmlUnitGetFanSpeedInfo")]
     pub fn fan_info(&self) -> Result<FansInfo, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlUnitGetFanSpeedInfo.as_ref())?;
-
         unsafe {
             let mut fans_info: nvmlUnitFanSpeeds_t = mem::zeroed();
-            nvml_try(sym(self.unit, &mut fans_info))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlUnitGetFanSpeedInfo(self.unit, &mut fans_info)?,
+            )?;
 
             FansInfo::try_from(fans_info)
         }
@@ -206,11 +212,13 @@ impl<'nvml> Unit<'nvml> {
     // Tested
     #[doc(alias = "nvmlUnitGetLedState")]
     pub fn led_state(&self) -> Result<LedState, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlUnitGetLedState.as_ref())?;
-
         unsafe {
             let mut state: nvmlLedState_t = mem::zeroed();
-            nvml_try(sym(self.unit, &mut state))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlUnitGetLedState(self.unit, &mut state)?,
+            )?;
 
             LedState::try_from(state)
         }
@@ -235,10 +243,9 @@ impl<'nvml> Unit<'nvml> {
     // Tested
     #[doc(alias = "nvmlUnitGetPsuInfo")]
     pub fn psu_info(&self) -> Result<PsuInfo, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlUnitGetPsuInfo.as_ref())?;
         unsafe {
             let mut info: nvmlPSUInfo_t = mem::zeroed();
-            nvml_try(sym(self.unit, &mut info))?;
+            nvml_try(self.nvml.backend.nvmlUnitGetPsuInfo(self.unit, &mut info)?)?;
 
             PsuInfo::try_from(info)
         }
@@ -264,12 +271,14 @@ impl<'nvml> Unit<'nvml> {
     // Tested
     #[doc(alias = "nvmlUnitGetTemperature")]
     pub fn temperature(&self, reading_type: TemperatureReading) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlUnitGetTemperature.as_ref())?;
-
         unsafe {
             let mut temp: c_uint = mem::zeroed();
 
-            nvml_try(sym(self.unit, reading_type as c_uint, &mut temp))?;
+            nvml_try(self.nvml.backend.nvmlUnitGetTemperature(
+                self.unit,
+                reading_type as c_uint,
+                &mut temp,
+            )?)?;
 
             Ok(temp)
         }
@@ -292,11 +301,13 @@ impl<'nvml> Unit<'nvml> {
     // Tested
     #[doc(alias = "nvmlUnitGetUnitInfo")]
     pub fn info(&self) -> Result<UnitInfo, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlUnitGetUnitInfo.as_ref())?;
-
         unsafe {
             let mut info: nvmlUnitInfo_t = mem::zeroed();
-            nvml_try(sym(self.unit, &mut info))?;
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlUnitGetUnitInfo(self.unit, &mut info)?,
+            )?;
 
             UnitInfo::try_from(info)
         }
@@ -329,9 +340,13 @@ impl<'nvml> Unit<'nvml> {
     // Tested (no-run)
     #[doc(alias = "nvmlUnitSetLedState")]
     pub fn set_led_color(&mut self, color: LedColor) -> Result<(), NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlUnitSetLedState.as_ref())?;
-
-        unsafe { nvml_try(sym(self.unit, color.as_c())) }
+        unsafe {
+            nvml_try(
+                self.nvml
+                    .backend
+                    .nvmlUnitSetLedState(self.unit, color.as_c())?,
+            )
+        }
     }
 }
 
@@ -1,13 +1,30 @@
-use std::{ffi::CStr, os::raw::c_uint};
+use std::{convert::TryFrom, ffi::CStr, mem, os::raw::c_uint, ptr};
 
 use ffi::bindings::{
-    nvmlVgpuCapability_t, nvmlVgpuTypeId_t, NVML_DEVICE_NAME_BUFFER_SIZE,
-    NVML_GRID_LICENSE_BUFFER_SIZE,
+    nvmlAccountingStats_t, nvmlEnableState_t, nvmlEncoderSessionInfo_t, nvmlFBCSessionInfo_t,
+    nvmlFBCStats_t, nvmlReturn_t, nvmlVgpuInstance_t, nvmlVgpuLicenseInfo_t, nvmlVgpuMetadata_t,
+    nvmlVgpuPlacementId_t, nvmlVgpuPlacementList_t, nvmlVgpuRuntimeState_t, nvmlVgpuTypeBar1Info_t,
+    nvmlVgpuTypeId_t, nvmlVgpuTypeMaxInstance_t, nvmlVgpuVmIdType_t,
+    INVALID_GPU_INSTANCE_PROFILE_ID, NVML_DEVICE_NAME_BUFFER_SIZE,
+    NVML_DEVICE_PCI_BUS_ID_BUFFER_SIZE, NVML_DEVICE_UUID_BUFFER_SIZE,
+    NVML_GRID_LICENSE_BUFFER_SIZE, NVML_SYSTEM_DRIVER_VERSION_BUFFER_SIZE,
 };
 use static_assertions::assert_impl_all;
 
 use crate::{
-    error::{nvml_sym, nvml_try, NvmlError},
+    enum_wrappers::{
+        bool_from_state,
+        device::{VgpuTypeCapability, VgpuVmIdType},
+    },
+    enums::device::VgpuPlacementMode,
+    error::{nvml_try, nvml_try_count, optional, NvmlError},
+    struct_wrappers::device::{
+        metadata_buffer, vgpu_placement_id_version, vgpu_placement_list_version,
+        vgpu_runtime_state_version, vgpu_type_bar1_info_version, vgpu_type_max_instance_version,
+        AccountingStats, EncoderSessionInfo, FbcSessionInfo, FbcStats, VgpuLicenseInfo,
+        VgpuMetadata,
+    },
+    structs::device::{EncoderStats, VgpuPlacementList, VgpuTypeInfo},
     Device,
 };
 
@@ -51,13 +68,15 @@ impl<'dev> VgpuType<'dev> {
     /// Kepler or newer fully supported devices.
     #[doc(alias = "nvmlVgpuTypeGetClass")]
     pub fn class_name(&self) -> Result<String, NvmlError> {
-        let sym = nvml_sym(self.device.nvml().lib.nvmlVgpuTypeGetClass.as_ref())?;
-
         unsafe {
             let mut size = NVML_DEVICE_NAME_BUFFER_SIZE;
             let mut buffer = vec![0; size as usize];
 
-            nvml_try(sym(self.id, buffer.as_mut_ptr(), &mut size))?;
+            nvml_try(self.device.nvml().backend.nvmlVgpuTypeGetClass(
+                self.id,
+                buffer.as_mut_ptr(),
+                &mut size,
+            )?)?;
 
             let version_raw = CStr::from_ptr(buffer.as_ptr());
             Ok(version_raw.to_str()?.into())
@@ -84,12 +103,14 @@ impl<'dev> VgpuType<'dev> {
     /// Kepler or newer fully supported devices.
     #[doc(alias = "nvmlVgpuTypeGetLicense")]
     pub fn license(&self) -> Result<String, NvmlError> {
-        let sym = nvml_sym(self.device.nvml().lib.nvmlVgpuTypeGetLicense.as_ref())?;
-
         unsafe {
             let mut buffer = vec![0; NVML_GRID_LICENSE_BUFFER_SIZE as usize];
 
-            nvml_try(sym(self.id, buffer.as_mut_ptr(), buffer.len() as u32))?;
+            nvml_try(self.device.nvml().backend.nvmlVgpuTypeGetLicense(
+                self.id,
+                buffer.as_mut_ptr(),
+                buffer.len() as u32,
+            )?)?;
 
             let version_raw = CStr::from_ptr(buffer.as_ptr());
             Ok(version_raw.to_str()?.into())
@@ -111,22 +132,22 @@ impl<'dev> VgpuType<'dev> {
     /// Kepler or newer fully supported devices.
     #[doc(alias = "nvmlVgpuTypeGetName")]
     pub fn name(&self) -> Result<String, NvmlError> {
-        let sym = nvml_sym(self.device.nvml().lib.nvmlVgpuTypeGetName.as_ref())?;
-
         unsafe {
             let mut size = NVML_DEVICE_NAME_BUFFER_SIZE;
             let mut buffer = vec![0; size as usize];
 
-            nvml_try(sym(self.id, buffer.as_mut_ptr(), &mut size))?;
+            nvml_try(self.device.nvml().backend.nvmlVgpuTypeGetName(
+                self.id,
+                buffer.as_mut_ptr(),
+                &mut size,
+            )?)?;
 
             let version_raw = CStr::from_ptr(buffer.as_ptr());
             Ok(version_raw.to_str()?.into())
         }
     }
 
-    /// Retrieve the requested capability for a given vGPU type. Refer to the
-    /// `nvmlVgpuCapability_t` structure for the specific capabilities that can be
-    /// queried.
+    /// Check whether the vGPU type has the given capability.
     ///
     /// # Errors
     ///
@@ -138,12 +159,14 @@ impl<'dev> VgpuType<'dev> {
     ///
     /// Maxwell or newer fully supported devices.
     #[doc(alias = "nvmlVgpuTypeGetCapabilities")]
-    pub fn capabilities(&self, capability: nvmlVgpuCapability_t) -> Result<bool, NvmlError> {
-        let sym = nvml_sym(self.device.nvml().lib.nvmlVgpuTypeGetCapabilities.as_ref())?;
-
+    pub fn capabilities(&self, capability: VgpuTypeCapability) -> Result<bool, NvmlError> {
         let mut result: c_uint = 0;
         unsafe {
-            nvml_try(sym(self.id, capability, &mut result))?;
+            nvml_try(self.device.nvml().backend.nvmlVgpuTypeGetCapabilities(
+                self.id,
+                capability.as_c(),
+                &mut result,
+            )?)?;
         }
         Ok(result != 0)
     }
@@ -161,11 +184,13 @@ impl<'dev> VgpuType<'dev> {
     /// Kepler or newer fully supported devices.
     #[doc(alias = "nvmlVgpuTypeGetDeviceID")]
     pub fn device_id(&self) -> Result<(u64, u64), NvmlError> {
-        let sym = nvml_sym(self.device.nvml().lib.nvmlVgpuTypeGetDeviceID.as_ref())?;
-
         let (mut device_id, mut subsystem_id) = (0, 0);
         unsafe {
-            nvml_try(sym(self.id, &mut device_id, &mut subsystem_id))?;
+            nvml_try(self.device.nvml().backend.nvmlVgpuTypeGetDeviceID(
+                self.id,
+                &mut device_id,
+                &mut subsystem_id,
+            )?)?;
         }
         Ok((device_id, subsystem_id))
     }
@@ -184,17 +209,14 @@ impl<'dev> VgpuType<'dev> {
     /// Kepler or newer fully supported devices.
     #[doc(alias = "nvmlVgpuTypeGetFrameRateLimit")]
     pub fn frame_rate_limit(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(
-            self.device
-                .nvml()
-                .lib
-                .nvmlVgpuTypeGetFrameRateLimit
-                .as_ref(),
-        )?;
-
         let mut limit = 0;
         unsafe {
-            nvml_try(sym(self.id, &mut limit))?;
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuTypeGetFrameRateLimit(self.id, &mut limit)?,
+            )?;
         }
         Ok(limit)
     }
@@ -212,17 +234,14 @@ impl<'dev> VgpuType<'dev> {
     /// Kepler or newer fully supported devices.
     #[doc(alias = "nvmlVgpuTypeGetFramebufferSize")]
     pub fn framebuffer_size(&self) -> Result<u64, NvmlError> {
-        let sym = nvml_sym(
-            self.device
-                .nvml()
-                .lib
-                .nvmlVgpuTypeGetFramebufferSize
-                .as_ref(),
-        )?;
-
         let mut size = 0;
         unsafe {
-            nvml_try(sym(self.id, &mut size))?;
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuTypeGetFramebufferSize(self.id, &mut size)?,
+            )?;
         }
         Ok(size)
     }
@@ -242,17 +261,14 @@ impl<'dev> VgpuType<'dev> {
     /// Kepler or newer fully supported devices.
     #[doc(alias = "nvmlVgpuTypeGetGpuInstanceProfileId")]
     pub fn instance_profile_id(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(
-            self.device
-                .nvml()
-                .lib
-                .nvmlVgpuTypeGetGpuInstanceProfileId
-                .as_ref(),
-        )?;
-
         let mut profile_id = 0;
         unsafe {
-            nvml_try(sym(self.id, &mut profile_id))?;
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuTypeGetGpuInstanceProfileId(self.id, &mut profile_id)?,
+            )?;
         }
         Ok(profile_id)
     }
@@ -270,11 +286,13 @@ impl<'dev> VgpuType<'dev> {
     /// Kepler or newer fully supported devices.
     #[doc(alias = "nvmlVgpuTypeGetMaxInstances")]
     pub fn max_instances(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(self.device.nvml().lib.nvmlVgpuTypeGetMaxInstances.as_ref())?;
-
         let mut max = 0;
         unsafe {
-            nvml_try(sym(self.device.handle(), self.id, &mut max))?;
+            nvml_try(self.device.nvml().backend.nvmlVgpuTypeGetMaxInstances(
+                self.device.handle(),
+                self.id,
+                &mut max,
+            )?)?;
         }
         Ok(max)
     }
@@ -292,17 +310,14 @@ impl<'dev> VgpuType<'dev> {
     /// Kepler or newer fully supported devices.
     #[doc(alias = "nvmlVgpuTypeGetMaxInstancesPerVm")]
     pub fn max_instances_per_vm(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(
-            self.device
-                .nvml()
-                .lib
-                .nvmlVgpuTypeGetMaxInstancesPerVm
-                .as_ref(),
-        )?;
-
         let mut max = 0;
         unsafe {
-            nvml_try(sym(self.id, &mut max))?;
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuTypeGetMaxInstancesPerVm(self.id, &mut max)?,
+            )?;
         }
         Ok(max)
     }
@@ -320,17 +335,14 @@ impl<'dev> VgpuType<'dev> {
     /// Kepler or newer fully supported devices.
     #[doc(alias = "nvmlVgpuTypeGetNumDisplayHeads")]
     pub fn num_display_heads(&self) -> Result<u32, NvmlError> {
-        let sym = nvml_sym(
-            self.device
-                .nvml()
-                .lib
-                .nvmlVgpuTypeGetNumDisplayHeads
-                .as_ref(),
-        )?;
-
         let mut heads = 0;
         unsafe {
-            nvml_try(sym(self.id, &mut heads))?;
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuTypeGetNumDisplayHeads(self.id, &mut heads)?,
+            )?;
         }
         Ok(heads)
     }
@@ -351,12 +363,1434 @@ impl<'dev> VgpuType<'dev> {
     /// Kepler or newer fully supported devices.
     #[doc(alias = "nvmlVgpuTypeGetResolution")]
     pub fn resolution(&self, display_head: u32) -> Result<(u32, u32), NvmlError> {
-        let sym = nvml_sym(self.device.nvml().lib.nvmlVgpuTypeGetResolution.as_ref())?;
-
         let (mut x, mut y) = (0, 0);
         unsafe {
-            nvml_try(sym(self.id, display_head, &mut x, &mut y))?;
+            nvml_try(self.device.nvml().backend.nvmlVgpuTypeGetResolution(
+                self.id,
+                display_head,
+                &mut x,
+                &mut y,
+            )?)?;
         }
         Ok((x, y))
     }
+
+    /// Retrieve the BAR1 size of the vGPU type, in bytes.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU type is invalid
+    /// * `NotSupported`, if the driver doesn't report it
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuTypeGetBAR1Info")]
+    pub fn bar1_size(&self) -> Result<u64, NvmlError> {
+        unsafe {
+            let mut info: nvmlVgpuTypeBar1Info_t = mem::zeroed();
+            info.version = vgpu_type_bar1_info_version();
+
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuTypeGetBAR1Info(self.id, &mut info)?,
+            )?;
+
+            Ok(info.bar1Size)
+        }
+    }
+
+    /// Retrieve the framebuffer the physical GPU reserves for each vGPU of the
+    /// type, in bytes, on top of its framebuffer size.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU type is invalid
+    /// * `NotSupported`, if the driver doesn't report it
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuTypeGetFbReservation")]
+    pub fn fb_reservation(&self) -> Result<u64, NvmlError> {
+        let mut size = 0;
+        unsafe {
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuTypeGetFbReservation(self.id, &mut size)?,
+            )?;
+        }
+        Ok(size)
+    }
+
+    /// Retrieve the GSP heap size of each vGPU of the type, in bytes.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU type is invalid
+    /// * `NotSupported`, if the driver doesn't report it
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuTypeGetGspHeapSize")]
+    pub fn gsp_heap_size(&self) -> Result<u64, NvmlError> {
+        let mut size = 0;
+        unsafe {
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuTypeGetGspHeapSize(self.id, &mut size)?,
+            )?;
+        }
+        Ok(size)
+    }
+
+    /// Retrieve the maximum number of vGPU instances of the type a GPU instance
+    /// can host, for MIG-backed vGPU types.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU type is invalid
+    /// * `NotSupported`, if this vGPU type isn't MIG-backed
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Ampere or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuTypeGetMaxInstancesPerGpuInstance")]
+    pub fn max_instances_per_gpu_instance(&self) -> Result<u32, NvmlError> {
+        unsafe {
+            let mut max: nvmlVgpuTypeMaxInstance_t = mem::zeroed();
+            max.version = vgpu_type_max_instance_version();
+            max.vgpuTypeId = self.id;
+
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuTypeGetMaxInstancesPerGpuInstance(&mut max)?,
+            )?;
+
+            Ok(max.maxInstancePerGI)
+        }
+    }
+
+    /// Retrieve the placements vGPUs of the type can have on the device,
+    /// whether or not they're free.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU type or the `Device` is invalid
+    /// * `NotSupported`, if the device doesn't support vGPU placements
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Ampere or newer fully supported devices.
+    #[doc(alias = "nvmlDeviceGetVgpuTypeSupportedPlacements")]
+    pub fn supported_placements(
+        &self,
+        mode: VgpuPlacementMode,
+    ) -> Result<VgpuPlacementList, NvmlError> {
+        self.placements(mode, |list| unsafe {
+            self.device
+                .nvml()
+                .backend
+                .nvmlDeviceGetVgpuTypeSupportedPlacements(self.device.handle(), self.id, list)
+        })
+    }
+
+    /// Retrieve the placements a vGPU of the type could be created at on the
+    /// device right now.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU type or the `Device` is invalid
+    /// * `NotSupported`, if the device doesn't support vGPU placements
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Ampere or newer fully supported devices.
+    #[doc(alias = "nvmlDeviceGetVgpuTypeCreatablePlacements")]
+    pub fn creatable_placements(
+        &self,
+        mode: VgpuPlacementMode,
+    ) -> Result<VgpuPlacementList, NvmlError> {
+        self.placements(mode, |list| unsafe {
+            self.device
+                .nvml()
+                .backend
+                .nvmlDeviceGetVgpuTypeCreatablePlacements(self.device.handle(), self.id, list)
+        })
+    }
+
+    // Helper for the above; `get` calls the placements function.
+    fn placements<F>(&self, mode: VgpuPlacementMode, get: F) -> Result<VgpuPlacementList, NvmlError>
+    where
+        F: Fn(&mut nvmlVgpuPlacementList_t) -> Result<nvmlReturn_t, NvmlError>,
+    {
+        let mut list: nvmlVgpuPlacementList_t = unsafe { mem::zeroed() };
+        list.version = vgpu_placement_list_version();
+        list.mode = mode.as_c();
+
+        // A null list of IDs asks for the count
+        nvml_try_count(get(&mut list)?)?;
+
+        let mut ids: Vec<c_uint> = vec![0; list.count as usize];
+        if !ids.is_empty() {
+            list.placementIds = ids.as_mut_ptr();
+            nvml_try(get(&mut list)?)?;
+            ids.truncate(list.count as usize);
+        }
+
+        Ok(VgpuPlacementList {
+            placement_size: list.placementSize,
+            placement_ids: ids,
+        })
+    }
+
+    /// Retrieve a summary of the vGPU type's properties.
+    ///
+    /// Properties that are `NotSupported` (or whose functions are missing from
+    /// the NVML library) are `None` in the summary, and capabilities that are
+    /// are left out.
+    ///
+    /// # Errors
+    ///
+    /// The same as the methods for each property, except as above.
+    pub fn info(&self) -> Result<VgpuTypeInfo, NvmlError> {
+        let mut capabilities = Vec::new();
+        for capability in [
+            VgpuTypeCapability::NvlinkP2p,
+            VgpuTypeCapability::GpuDirect,
+            VgpuTypeCapability::MultiVgpuExclusive,
+            VgpuTypeCapability::ExclusiveType,
+            VgpuTypeCapability::ExclusiveSize,
+        ] {
+            if optional(self.capabilities(capability))?.unwrap_or(false) {
+                capabilities.push(capability);
+            }
+        }
+
+        Ok(VgpuTypeInfo {
+            id: self.id,
+            name: self.name()?,
+            class_name: self.class_name()?,
+            license: self.license()?,
+            framebuffer_size: self.framebuffer_size()?,
+            fb_reservation: optional(self.fb_reservation())?,
+            gsp_heap_size: optional(self.gsp_heap_size())?,
+            bar1_size: optional(self.bar1_size())?,
+            max_instances: self.max_instances()?,
+            max_instances_per_vm: self.max_instances_per_vm()?,
+            max_instances_per_gpu_instance: optional(self.max_instances_per_gpu_instance())?,
+            gpu_instance_profile_id: optional(self.instance_profile_id())?
+                .filter(|&id| id != INVALID_GPU_INSTANCE_PROFILE_ID),
+            num_display_heads: self.num_display_heads()?,
+            frame_rate_limit: optional(self.frame_rate_limit())?,
+            capabilities,
+        })
+    }
+}
+
+/// A running vGPU instance on a `Device`.
+///
+/// Lifetimes are used to enforce that each `VgpuInstance` cannot be used after
+/// the `Device` it was obtained from is dropped.
+pub struct VgpuInstance<'dev> {
+    id: nvmlVgpuInstance_t,
+    device: &'dev Device<'dev>,
+}
+
+assert_impl_all!(VgpuInstance: Send, Sync);
+
+impl<'dev> VgpuInstance<'dev> {
+    /// Create a new vGPU instance wrapper.
+    ///
+    /// You probably don't need to use this yourself, but rather through
+    /// [`Device::vgpu_instances`].
+    pub fn new(device: &'dev Device, id: nvmlVgpuInstance_t) -> Self {
+        Self { id, device }
+    }
+
+    /// Access the `Device` this struct belongs to.
+    pub fn device(&self) -> &'dev Device<'_> {
+        self.device
+    }
+
+    /// Get the underlying vGPU instance id.
+    pub fn id(&self) -> nvmlVgpuInstance_t {
+        self.id
+    }
+
+    /// Retrieve the UUID of this vGPU instance.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance is not active
+    /// * `Utf8Error`, if the string obtained from the C function is not valid Utf8
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Kepler or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetUUID")]
+    pub fn uuid(&self) -> Result<String, NvmlError> {
+        unsafe {
+            let mut buffer = vec![0; NVML_DEVICE_UUID_BUFFER_SIZE as usize];
+
+            nvml_try(self.device.nvml().backend.nvmlVgpuInstanceGetUUID(
+                self.id,
+                buffer.as_mut_ptr(),
+                buffer.len() as u32,
+            )?)?;
+
+            let raw = CStr::from_ptr(buffer.as_ptr());
+            Ok(raw.to_str()?.into())
+        }
+    }
+
+    /// Retrieve the ID of the VM this vGPU instance is attached to, along with
+    /// how that ID identifies the VM (a domain ID or a UUID).
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance is not active
+    /// * `UnexpectedVariant`, for which you can read the docs for
+    /// * `Utf8Error`, if the string obtained from the C function is not valid Utf8
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Kepler or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetVmID")]
+    pub fn vm_id(&self) -> Result<(String, VgpuVmIdType), NvmlError> {
+        unsafe {
+            let mut buffer = vec![0; NVML_DEVICE_UUID_BUFFER_SIZE as usize];
+            let mut id_type: nvmlVgpuVmIdType_t = mem::zeroed();
+
+            nvml_try(self.device.nvml().backend.nvmlVgpuInstanceGetVmID(
+                self.id,
+                buffer.as_mut_ptr(),
+                buffer.len() as u32,
+                &mut id_type,
+            )?)?;
+
+            let raw = CStr::from_ptr(buffer.as_ptr());
+            Ok((raw.to_str()?.into(), VgpuVmIdType::try_from(id_type)?))
+        }
+    }
+
+    /// Retrieve the version of the NVIDIA driver running in the VM this vGPU
+    /// instance is attached to.
+    ///
+    /// The version is returned as "Not Available" if no driver is loaded in
+    /// the VM.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance is not active
+    /// * `Utf8Error`, if the string obtained from the C function is not valid Utf8
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Kepler or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetVmDriverVersion")]
+    pub fn vm_driver_version(&self) -> Result<String, NvmlError> {
+        unsafe {
+            let mut buffer = vec![0; NVML_SYSTEM_DRIVER_VERSION_BUFFER_SIZE as usize];
+
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuInstanceGetVmDriverVersion(
+                        self.id,
+                        buffer.as_mut_ptr(),
+                        buffer.len() as u32,
+                    )?,
+            )?;
+
+            let raw = CStr::from_ptr(buffer.as_ptr());
+            Ok(raw.to_str()?.into())
+        }
+    }
+
+    /// Retrieve the mediated device (mdev) UUID of this vGPU instance.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance is not active
+    /// * `NotSupported`, on hypervisors without mediated devices
+    /// * `Utf8Error`, if the string obtained from the C function is not valid Utf8
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetMdevUUID")]
+    pub fn mdev_uuid(&self) -> Result<String, NvmlError> {
+        unsafe {
+            let mut buffer = vec![0; NVML_DEVICE_UUID_BUFFER_SIZE as usize];
+
+            nvml_try(self.device.nvml().backend.nvmlVgpuInstanceGetMdevUUID(
+                self.id,
+                buffer.as_mut_ptr(),
+                buffer.len() as u32,
+            )?)?;
+
+            let raw = CStr::from_ptr(buffer.as_ptr());
+            Ok(raw.to_str()?.into())
+        }
+    }
+
+    /// Retrieve the PCI ID of this vGPU instance as seen by the VM, in the
+    /// form "domain:bus:device.function".
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance is not active
+    /// * `DriverNotLoaded`, if no driver is loaded in the VM
+    /// * `NotSupported`, if the guest driver doesn't report its PCI ID
+    /// * `Utf8Error`, if the string obtained from the C function is not valid Utf8
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetGpuPciId")]
+    pub fn gpu_pci_id(&self) -> Result<String, NvmlError> {
+        unsafe {
+            let mut length = NVML_DEVICE_PCI_BUS_ID_BUFFER_SIZE;
+            let mut buffer = vec![0; length as usize];
+
+            nvml_try(self.device.nvml().backend.nvmlVgpuInstanceGetGpuPciId(
+                self.id,
+                buffer.as_mut_ptr(),
+                &mut length,
+            )?)?;
+
+            let raw = CStr::from_ptr(buffer.as_ptr());
+            Ok(raw.to_str()?.into())
+        }
+    }
+
+    /// Retrieve the vGPU type of this vGPU instance.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance is not active
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Kepler or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetType")]
+    pub fn vgpu_type(&self) -> Result<VgpuType<'dev>, NvmlError> {
+        let mut type_id: nvmlVgpuTypeId_t = 0;
+        unsafe {
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuInstanceGetType(self.id, &mut type_id)?,
+            )?;
+        }
+        Ok(VgpuType::new(self.device, type_id))
+    }
+
+    /// Retrieve the framebuffer usage of this vGPU instance, in bytes.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance is not active
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Kepler or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetFbUsage")]
+    pub fn fb_usage(&self) -> Result<u64, NvmlError> {
+        let mut usage = 0;
+        unsafe {
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuInstanceGetFbUsage(self.id, &mut usage)?,
+            )?;
+        }
+        Ok(usage)
+    }
+
+    /// Retrieve the frame rate limit set for this vGPU instance.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance is not active
+    /// * `NotSupported`, if the frame rate limiter is turned off for this vGPU
+    ///   instance
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Kepler or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetFrameRateLimit")]
+    pub fn frame_rate_limit(&self) -> Result<u32, NvmlError> {
+        let mut limit = 0;
+        unsafe {
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuInstanceGetFrameRateLimit(self.id, &mut limit)?,
+            )?;
+        }
+        Ok(limit)
+    }
+
+    /// Check whether ECC is enabled for this vGPU instance.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance is not active
+    /// * `NotSupported`, if this vGPU instance doesn't support ECC
+    /// * `UnexpectedVariant`, for which you can read the docs for
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetEccMode")]
+    pub fn is_ecc_enabled(&self) -> Result<bool, NvmlError> {
+        unsafe {
+            let mut state: nvmlEnableState_t = mem::zeroed();
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuInstanceGetEccMode(self.id, &mut state)?,
+            )?;
+
+            bool_from_state(state)
+        }
+    }
+
+    /// Retrieve the ID of the GPU instance this vGPU instance was created on.
+    ///
+    /// This is only meaningful for MIG-backed vGPUs.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance is not active
+    /// * `NotSupported`, if this vGPU instance isn't MIG-backed
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Ampere or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetGpuInstanceId")]
+    pub fn gpu_instance_id(&self) -> Result<u32, NvmlError> {
+        let mut id = 0;
+        unsafe {
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuInstanceGetGpuInstanceId(self.id, &mut id)?,
+            )?;
+        }
+        Ok(id)
+    }
+
+    /// Retrieve the placement ID of this vGPU instance.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance is not active
+    /// * `NotSupported`, if the driver doesn't support vGPU placements
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Ampere or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetPlacementId")]
+    pub fn placement_id(&self) -> Result<u32, NvmlError> {
+        unsafe {
+            let mut placement: nvmlVgpuPlacementId_t = mem::zeroed();
+            placement.version = vgpu_placement_id_version();
+
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuInstanceGetPlacementId(self.id, &mut placement)?,
+            )?;
+
+            Ok(placement.placementId)
+        }
+    }
+
+    /// Retrieve the metadata of this vGPU instance, used to check whether it
+    /// can be migrated to a physical GPU with [`crate::Nvml::vgpu_compatibility`]
+    /// (or [`crate::high_level::vgpu_migration::check_migration`]).
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance is not active
+    /// * `UnexpectedVariant`, for which you can read the docs for
+    /// * `Utf8Error`, if a string obtained from the C function is not valid Utf8
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetMetadata")]
+    pub fn metadata(&self) -> Result<VgpuMetadata, NvmlError> {
+        unsafe {
+            let mut size: c_uint = 0;
+            nvml_try_count(self.device.nvml().backend.nvmlVgpuInstanceGetMetadata(
+                self.id,
+                ptr::null_mut(),
+                &mut size,
+            )?)?;
+
+            let mut buffer = metadata_buffer::<nvmlVgpuMetadata_t>(size as usize);
+            let mut size = mem::size_of_val(buffer.as_slice()) as c_uint;
+            nvml_try(self.device.nvml().backend.nvmlVgpuInstanceGetMetadata(
+                self.id,
+                buffer.as_mut_ptr() as *mut nvmlVgpuMetadata_t,
+                &mut size,
+            )?)?;
+
+            VgpuMetadata::from_c_buffer(&buffer)
+        }
+    }
+
+    /// Retrieve the size of the state of this vGPU instance that has to be
+    /// copied when migrating it, in bytes.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance is not active
+    /// * `NotSupported`, if the driver doesn't report the state size
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetRuntimeStateSize")]
+    pub fn runtime_state_size(&self) -> Result<u64, NvmlError> {
+        unsafe {
+            let mut state: nvmlVgpuRuntimeState_t = mem::zeroed();
+            state.version = vgpu_runtime_state_version();
+
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuInstanceGetRuntimeStateSize(self.id, &mut state)?,
+            )?;
+
+            Ok(state.size)
+        }
+    }
+
+    /// Retrieve the licensing state of this vGPU instance, including when its
+    /// license expires.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance is not active
+    /// * `DriverNotLoaded`, if no driver is loaded in the VM
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetLicenseInfo_v2")]
+    pub fn license_info(&self) -> Result<VgpuLicenseInfo, NvmlError> {
+        unsafe {
+            let mut info: nvmlVgpuLicenseInfo_t = mem::zeroed();
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuInstanceGetLicenseInfo_v2(self.id, &mut info)?,
+            )?;
+
+            Ok(info.into())
+        }
+    }
+
+    /// Retrieve the encoder capacity of this vGPU instance, as a percentage of
+    /// the maximum encoder capacity (0 to 100).
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance is not active
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetEncoderCapacity")]
+    pub fn encoder_capacity(&self) -> Result<u32, NvmlError> {
+        let mut capacity = 0;
+        unsafe {
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuInstanceGetEncoderCapacity(self.id, &mut capacity)?,
+            )?;
+        }
+        Ok(capacity)
+    }
+
+    /// Set the encoder capacity of this vGPU instance, as a percentage of the
+    /// maximum encoder capacity (0 to 100).
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid or `capacity` is
+    ///   over 100
+    /// * `NotFound`, if this vGPU instance is not active
+    /// * `NoPermission`, if the user doesn't have permission to perform this
+    ///   operation
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceSetEncoderCapacity")]
+    pub fn set_encoder_capacity(&mut self, capacity: u32) -> Result<(), NvmlError> {
+        unsafe {
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuInstanceSetEncoderCapacity(self.id, capacity)?,
+            )
+        }
+    }
+
+    /// Retrieve the current encoder stats of this vGPU instance.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance is not active
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetEncoderStats")]
+    pub fn encoder_stats(&self) -> Result<EncoderStats, NvmlError> {
+        let (mut session_count, mut average_fps, mut average_latency) = (0, 0, 0);
+        unsafe {
+            nvml_try(self.device.nvml().backend.nvmlVgpuInstanceGetEncoderStats(
+                self.id,
+                &mut session_count,
+                &mut average_fps,
+                &mut average_latency,
+            )?)?;
+        }
+        Ok(EncoderStats {
+            session_count,
+            average_fps,
+            average_latency,
+        })
+    }
+
+    /// Retrieve information about the active encoder sessions of this vGPU
+    /// instance.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance is not active
+    /// * `UnexpectedVariant`, if an enum variant not defined in this wrapper gets
+    ///   returned in a field of an `EncoderSessionInfo` struct
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetEncoderSessions")]
+    pub fn encoder_sessions(&self) -> Result<Vec<EncoderSessionInfo>, NvmlError> {
+        unsafe {
+            let mut count: c_uint = 0;
+            nvml_try_count(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuInstanceGetEncoderSessions(self.id, &mut count, ptr::null_mut())?,
+            )?;
+
+            if count == 0 {
+                return Ok(vec![]);
+            }
+
+            let mut sessions: Vec<nvmlEncoderSessionInfo_t> = vec![mem::zeroed(); count as usize];
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuInstanceGetEncoderSessions(
+                        self.id,
+                        &mut count,
+                        sessions.as_mut_ptr(),
+                    )?,
+            )?;
+
+            sessions.truncate(count as usize);
+            sessions
+                .into_iter()
+                .map(EncoderSessionInfo::try_from)
+                .collect()
+        }
+    }
+
+    /// Retrieve the frame buffer capture stats of this vGPU instance.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance is not active
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetFBCStats")]
+    pub fn fbc_stats(&self) -> Result<FbcStats, NvmlError> {
+        unsafe {
+            let mut stats: nvmlFBCStats_t = mem::zeroed();
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuInstanceGetFBCStats(self.id, &mut stats)?,
+            )?;
+
+            Ok(stats.into())
+        }
+    }
+
+    /// Retrieve information about the active frame buffer capture sessions of
+    /// this vGPU instance.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance is not active
+    /// * `UnexpectedVariant`, for which you can read the docs for
+    /// * `IncorrectBits`, if bits are found in a session's info flags that don't
+    ///   match the flags in this wrapper
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetFBCSessions")]
+    pub fn fbc_sessions(&self) -> Result<Vec<FbcSessionInfo>, NvmlError> {
+        unsafe {
+            let mut count: c_uint = 0;
+            nvml_try_count(self.device.nvml().backend.nvmlVgpuInstanceGetFBCSessions(
+                self.id,
+                &mut count,
+                ptr::null_mut(),
+            )?)?;
+
+            if count == 0 {
+                return Ok(vec![]);
+            }
+
+            let mut sessions: Vec<nvmlFBCSessionInfo_t> = vec![mem::zeroed(); count as usize];
+            nvml_try(self.device.nvml().backend.nvmlVgpuInstanceGetFBCSessions(
+                self.id,
+                &mut count,
+                sessions.as_mut_ptr(),
+            )?)?;
+
+            sessions.truncate(count as usize);
+            sessions.into_iter().map(FbcSessionInfo::try_from).collect()
+        }
+    }
+
+    /// Check whether per-process accounting is enabled for this vGPU instance.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance is not active
+    /// * `DriverNotLoaded`, if no driver is loaded in the VM
+    /// * `UnexpectedVariant`, for which you can read the docs for
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetAccountingMode")]
+    pub fn is_accounting_enabled(&self) -> Result<bool, NvmlError> {
+        unsafe {
+            let mut state: nvmlEnableState_t = mem::zeroed();
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuInstanceGetAccountingMode(self.id, &mut state)?,
+            )?;
+
+            bool_from_state(state)
+        }
+    }
+
+    /// Retrieve the processes in the VM that can be queried for accounting
+    /// stats with [`VgpuInstance::accounting_stats_for`].
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance is not active
+    /// * `NotSupported`, if accounting mode is disabled
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetAccountingPids")]
+    pub fn accounting_pids(&self) -> Result<Vec<u32>, NvmlError> {
+        self.device.vgpu_accounting_pids(self.id)
+    }
+
+    /// Retrieve the accounting stats of a process in the VM.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if the process stats were not found
+    /// * `NotSupported`, if accounting mode is disabled
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceGetAccountingStats")]
+    pub fn accounting_stats_for(&self, process_id: u32) -> Result<AccountingStats, NvmlError> {
+        unsafe {
+            let mut stats: nvmlAccountingStats_t = mem::zeroed();
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuInstanceGetAccountingStats(self.id, process_id, &mut stats)?,
+            )?;
+
+            Ok(stats.into())
+        }
+    }
+
+    /// Clear the accounting information of this vGPU instance.
+    ///
+    /// Requires root/admin permissions.
+    ///
+    /// # Errors
+    ///
+    /// * `Uninitialized`, if the library has not been successfully initialized
+    /// * `InvalidArg`, if this vGPU instance is invalid
+    /// * `NotFound`, if this vGPU instance is not active
+    /// * `NoPermission`, if the user doesn't have permission to perform this
+    ///   operation
+    /// * `NotSupported`, if accounting mode is disabled
+    /// * `Unknown`, on any unexpected error
+    ///
+    /// # Device Support
+    ///
+    /// Maxwell or newer fully supported devices.
+    #[doc(alias = "nvmlVgpuInstanceClearAccountingPids")]
+    pub fn clear_accounting_pids(&mut self) -> Result<(), NvmlError> {
+        unsafe {
+            nvml_try(
+                self.device
+                    .nvml()
+                    .backend
+                    .nvmlVgpuInstanceClearAccountingPids(self.id)?,
+            )
+        }
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod test {
+    use crate::backend::NvmlBackend;
+    use crate::enum_wrappers::device::{VgpuTypeCapability, VgpuVmIdType};
+    use crate::enums::device::{SampleValue, VgpuPlacementMode};
+    use crate::error::NvmlError;
+    use crate::ffi::bindings::*;
+    use crate::test_utils::mock_backend;
+    use crate::Nvml;
+    use std::os::raw::{c_char, c_uint, c_ulonglong};
+
+    /// One GPU running two vGPUs (7 and 8) of type 42, each in its own VM.
+    /// Type 42 takes 2 placement slots, and has 3 free placements for
+    /// heterogeneous use. Each vGPU runs a single guest process, and is
+    /// sampled at `instance * 100` μs.
+    struct Mock;
+
+    const INSTANCES: [nvmlVgpuInstance_t; 2] = [7, 8];
+
+    /// Write `value` and a nul terminator into the `size`-long `buffer`.
+    unsafe fn write_str(value: &str, buffer: *mut c_char, size: c_uint) -> nvmlReturn_t {
+        if value.len() >= size as usize {
+            return nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE;
+        }
+
+        for (i, byte) in value.bytes().enumerate() {
+            *buffer.add(i) = byte as c_char;
+        }
+        *buffer.add(value.len()) = 0;
+
+        nvmlReturn_enum_NVML_SUCCESS
+    }
+
+    impl NvmlBackend for Mock {
+        mock_backend!();
+
+        unsafe fn nvmlDeviceGetActiveVgpus(
+            &self,
+            _device: nvmlDevice_t,
+            count: *mut c_uint,
+            instances: *mut nvmlVgpuInstance_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let available = *count as usize;
+            *count = INSTANCES.len() as c_uint;
+
+            if instances.is_null() || available < INSTANCES.len() {
+                return Ok(nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE);
+            }
+
+            for (i, instance) in INSTANCES.iter().enumerate() {
+                *instances.add(i) = *instance;
+            }
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlVgpuInstanceGetUUID(
+            &self,
+            instance: nvmlVgpuInstance_t,
+            uuid: *mut c_char,
+            size: c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            let value = format!("GPU-0000000{}-0000-0000-0000-000000000000", instance);
+            Ok(write_str(&value, uuid, size))
+        }
+
+        unsafe fn nvmlVgpuInstanceGetVmID(
+            &self,
+            instance: nvmlVgpuInstance_t,
+            vm_id: *mut c_char,
+            size: c_uint,
+            id_type: *mut nvmlVgpuVmIdType_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *id_type = nvmlVgpuVmIdType_NVML_VGPU_VM_ID_DOMAIN_ID;
+            Ok(write_str(&format!("{}", instance * 10), vm_id, size))
+        }
+
+        unsafe fn nvmlVgpuInstanceGetType(
+            &self,
+            _instance: nvmlVgpuInstance_t,
+            type_id: *mut nvmlVgpuTypeId_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *type_id = 42;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlVgpuInstanceGetPlacementId(
+            &self,
+            instance: nvmlVgpuInstance_t,
+            placement: *mut nvmlVgpuPlacementId_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            if (*placement).version >> 24 != 1 {
+                return Ok(nvmlReturn_enum_NVML_ERROR_ARGUMENT_VERSION_MISMATCH);
+            }
+
+            (*placement).placementId = instance * 2;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlVgpuInstanceGetLicenseInfo_v2(
+            &self,
+            _instance: nvmlVgpuInstance_t,
+            info: *mut nvmlVgpuLicenseInfo_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            (*info).isLicensed = 1;
+            (*info).licenseExpiry.year = 2030;
+            (*info).licenseExpiry.status = NVML_GRID_LICENSE_EXPIRY_VALID as u8;
+            (*info).currentState = NVML_GRID_LICENSE_STATE_LICENSED;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlVgpuTypeGetName(
+            &self,
+            _type_id: nvmlVgpuTypeId_t,
+            name: *mut c_char,
+            size: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            Ok(write_str("GRID A100-4C", name, *size))
+        }
+
+        unsafe fn nvmlVgpuTypeGetClass(
+            &self,
+            _type_id: nvmlVgpuTypeId_t,
+            class: *mut c_char,
+            size: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            Ok(write_str("Compute", class, *size))
+        }
+
+        unsafe fn nvmlVgpuTypeGetLicense(
+            &self,
+            _type_id: nvmlVgpuTypeId_t,
+            license: *mut c_char,
+            size: c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            Ok(write_str(
+                "NVIDIA-Virtual-Compute-Server,9.0",
+                license,
+                size,
+            ))
+        }
+
+        unsafe fn nvmlVgpuTypeGetFramebufferSize(
+            &self,
+            _type_id: nvmlVgpuTypeId_t,
+            size: *mut c_ulonglong,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *size = 4 << 30;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlVgpuTypeGetFbReservation(
+            &self,
+            _type_id: nvmlVgpuTypeId_t,
+            size: *mut c_ulonglong,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *size = 64 << 20;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlVgpuTypeGetMaxInstances(
+            &self,
+            _device: nvmlDevice_t,
+            _type_id: nvmlVgpuTypeId_t,
+            count: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *count = 10;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlVgpuTypeGetMaxInstancesPerVm(
+            &self,
+            _type_id: nvmlVgpuTypeId_t,
+            count: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *count = 1;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlVgpuTypeGetMaxInstancesPerGpuInstance(
+            &self,
+            _max: *mut nvmlVgpuTypeMaxInstance_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            Ok(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED)
+        }
+
+        unsafe fn nvmlVgpuTypeGetGpuInstanceProfileId(
+            &self,
+            _type_id: nvmlVgpuTypeId_t,
+            profile_id: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *profile_id = INVALID_GPU_INSTANCE_PROFILE_ID;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlVgpuTypeGetNumDisplayHeads(
+            &self,
+            _type_id: nvmlVgpuTypeId_t,
+            heads: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *heads = 1;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlVgpuTypeGetFrameRateLimit(
+            &self,
+            _type_id: nvmlVgpuTypeId_t,
+            _limit: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            Ok(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED)
+        }
+
+        unsafe fn nvmlVgpuTypeGetCapabilities(
+            &self,
+            _type_id: nvmlVgpuTypeId_t,
+            capability: nvmlVgpuCapability_t,
+            result: *mut c_uint,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *result =
+                (capability == nvmlVgpuCapability_enum_NVML_VGPU_CAP_EXCLUSIVE_SIZE) as c_uint;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetVgpuTypeCreatablePlacements(
+            &self,
+            _device: nvmlDevice_t,
+            _type_id: nvmlVgpuTypeId_t,
+            list: *mut nvmlVgpuPlacementList_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            if (*list).version >> 24 != 2 || (*list).mode != NVML_VGPU_PGPU_HETEROGENEOUS_MODE {
+                return Ok(nvmlReturn_enum_NVML_ERROR_INVALID_ARGUMENT);
+            }
+
+            let placements = [0, 4, 6];
+            let available = (*list).count as usize;
+            (*list).placementSize = 2;
+            (*list).count = placements.len() as c_uint;
+
+            if (*list).placementIds.is_null() || available < placements.len() {
+                return Ok(nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE);
+            }
+
+            for (i, placement) in placements.iter().enumerate() {
+                *(*list).placementIds.add(i) = *placement;
+            }
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlVgpuInstanceGetEncoderSessions(
+            &self,
+            _instance: nvmlVgpuInstance_t,
+            count: *mut c_uint,
+            _info: *mut nvmlEncoderSessionInfo_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            *count = 0;
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetVgpuInstancesUtilizationInfo(
+            &self,
+            _device: nvmlDevice_t,
+            info: *mut nvmlVgpuInstancesUtilizationInfo_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            if (*info).version >> 24 != 1 {
+                return Ok(nvmlReturn_enum_NVML_ERROR_ARGUMENT_VERSION_MISMATCH);
+            }
+
+            let last_seen = (*info).lastSeenTimeStamp;
+            let sampled: Vec<_> = INSTANCES
+                .iter()
+                .filter(|i| **i as c_ulonglong * 100 > last_seen)
+                .collect();
+            let available = (*info).vgpuInstanceCount as usize;
+            (*info).sampleValType = nvmlValueType_enum_NVML_VALUE_TYPE_UNSIGNED_INT;
+            (*info).vgpuInstanceCount = sampled.len() as c_uint;
+
+            if (*info).vgpuUtilArray.is_null() || available < sampled.len() {
+                return Ok(nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE);
+            }
+
+            for (i, instance) in sampled.into_iter().enumerate() {
+                let sample = &mut *(*info).vgpuUtilArray.add(i);
+                sample.vgpuInstance = *instance;
+                sample.timeStamp = *instance as c_ulonglong * 100;
+                sample.smUtil.uiVal = *instance * 10;
+                sample.memUtil.uiVal = *instance;
+                sample.jpgUtil.uiVal = 0;
+                sample.ofaUtil.uiVal = 0;
+            }
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+
+        unsafe fn nvmlDeviceGetVgpuProcessesUtilizationInfo(
+            &self,
+            _device: nvmlDevice_t,
+            info: *mut nvmlVgpuProcessesUtilizationInfo_t,
+        ) -> Result<nvmlReturn_t, NvmlError> {
+            if (*info).version >> 24 != 1 {
+                return Ok(nvmlReturn_enum_NVML_ERROR_ARGUMENT_VERSION_MISMATCH);
+            }
+
+            let last_seen = (*info).lastSeenTimeStamp;
+            let sampled: Vec<_> = INSTANCES
+                .iter()
+                .filter(|i| **i as c_ulonglong * 100 > last_seen)
+                .collect();
+            let available = (*info).vgpuProcessCount as usize;
+            (*info).vgpuProcessCount = sampled.len() as c_uint;
+
+            if (*info).vgpuProcUtilArray.is_null() || available < sampled.len() {
+                return Ok(nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE);
+            }
+
+            for (i, instance) in sampled.into_iter().enumerate() {
+                let sample = &mut *(*info).vgpuProcUtilArray.add(i);
+                write_str("render", sample.processName.as_mut_ptr(), 64);
+                sample.vgpuInstance = *instance;
+                sample.pid = 1000 + *instance;
+                sample.timeStamp = *instance as c_ulonglong * 100;
+                sample.smUtil = *instance * 10;
+                sample.encUtil = 5;
+            }
+            Ok(nvmlReturn_enum_NVML_SUCCESS)
+        }
+    }
+
+    #[test]
+    fn inventory_instances_by_vm() {
+        let nvml = Nvml::builder().init_with_backend(Mock).unwrap();
+        let device = nvml.device_by_index(0).unwrap();
+
+        let instances = device.vgpu_instances().unwrap();
+        assert_eq!(
+            instances.iter().map(|i| i.id()).collect::<Vec<_>>(),
+            INSTANCES
+        );
+
+        let instance = &instances[1];
+        assert_eq!(
+            instance.uuid().unwrap(),
+            "GPU-00000008-0000-0000-0000-000000000000"
+        );
+        assert_eq!(
+            instance.vm_id().unwrap(),
+            ("80".to_string(), VgpuVmIdType::DomainId)
+        );
+        assert_eq!(instance.vgpu_type().unwrap().id(), 42);
+        assert_eq!(instance.placement_id().unwrap(), 16);
+        assert!(instance.encoder_sessions().unwrap().is_empty());
+
+        let license = instance.license_info().unwrap();
+        assert!(license.is_licensed);
+        assert_eq!(license.expiry.year, 2030);
+        assert_eq!(license.current_state, NVML_GRID_LICENSE_STATE_LICENSED);
+    }
+
+    #[test]
+    fn type_summary_and_placements() {
+        let nvml = Nvml::builder().init_with_backend(Mock).unwrap();
+        let device = nvml.device_by_index(0).unwrap();
+        let vgpu_type = device.vgpu_instances().unwrap()[0].vgpu_type().unwrap();
+
+        let info = vgpu_type.info().unwrap();
+        assert_eq!(info.name, "GRID A100-4C");
+        assert_eq!(info.framebuffer_size, 4 << 30);
+        assert_eq!(info.fb_reservation, Some(64 << 20));
+        // Missing from the backend and not supported respectively
+        assert_eq!(info.gsp_heap_size, None);
+        assert_eq!(info.max_instances_per_gpu_instance, None);
+        assert_eq!(info.gpu_instance_profile_id, None);
+        assert_eq!(info.frame_rate_limit, None);
+        assert_eq!(info.max_instances, 10);
+        assert_eq!(info.capabilities, vec![VgpuTypeCapability::ExclusiveSize]);
+
+        let placements = vgpu_type
+            .creatable_placements(VgpuPlacementMode::Heterogeneous)
+            .unwrap();
+        assert_eq!(placements.placement_size, 2);
+        assert_eq!(placements.placement_ids, vec![0, 4, 6]);
+    }
+
+    #[test]
+    fn attribute_utilization_since_last_seen() {
+        let nvml = Nvml::builder().init_with_backend(Mock).unwrap();
+        let device = nvml.device_by_index(0).unwrap();
+
+        let instances = device.vgpu_instances_utilization_info(None).unwrap();
+        assert_eq!(
+            instances
+                .iter()
+                .map(|i| i.vgpu_instance)
+                .collect::<Vec<_>>(),
+            INSTANCES
+        );
+        assert_eq!(instances[1].timestamp, 800);
+        assert_eq!(instances[1].sm_util, SampleValue::U32(80));
+        assert_eq!(instances[1].mem_util, SampleValue::U32(8));
+        assert_eq!(instances[1].ofa_util, SampleValue::U32(0));
+
+        let processes = device.vgpu_processes_utilization_info(None).unwrap();
+        assert_eq!(processes.len(), 2);
+        assert_eq!(processes[0].vgpu_instance, 7);
+        assert_eq!(processes[0].pid, 1007);
+        assert_eq!(processes[0].process_name, "render");
+        assert_eq!(processes[0].sm_util, 70);
+        assert_eq!(processes[0].enc_util, 5);
+
+        // Only samples taken after the last one seen are returned
+        let last_seen = instances[0].timestamp;
+        let instances = device.vgpu_instances_utilization_info(last_seen).unwrap();
+        assert_eq!(instances.len(), 1);
+        assert_eq!(instances[0].vgpu_instance, 8);
+
+        let processes = device.vgpu_processes_utilization_info(800).unwrap();
+        assert!(processes.is_empty());
+    }
 }